@@ -15,6 +15,7 @@ pub use crate::deserialize::value::{
     UdtDeserializationErrorKind, UdtIterator, UdtTypeCheckErrorKind as DeserUdtTypeCheckErrorKind,
     deser_error_replace_rust_name as value_deser_error_replace_rust_name,
     mk_deser_err as mk_value_deser_err, mk_typck_err as mk_value_typck_err,
+    typck_error_replace_rust_name as value_typck_error_replace_rust_name,
 };
 pub use crate::deserialize::{DeserializationError, FrameSlice, TypeCheckError};
 pub use crate::serialize::row::{
@@ -33,6 +34,7 @@ pub use crate::serialize::value::{
 };
 pub use crate::serialize::writers::WrittenCellProof;
 pub use crate::serialize::{CellValueBuilder, CellWriter, RowWriter, SerializationError};
+pub use crate::value::MaybeUnset;
 
 /// Represents a set of values that can be sent along a CQL statement when serializing by name
 ///
@@ -309,4 +311,91 @@ pub mod ser {
             }
         }
     }
+
+    pub mod value {
+        use crate::serialize::SerializationError;
+
+        /// Replaces the Rust type name in a builtin serialization error
+        /// with the name of `T`.
+        ///
+        /// Used by types that delegate their serialization to another type
+        /// (e.g. `transparent` newtypes), so that errors report the outer type.
+        #[inline]
+        pub fn ser_error_replace_rust_name<T>(err: SerializationError) -> SerializationError {
+            crate::serialize::value::fix_rust_name_in_err::<T>(err)
+        }
+    }
+}
+
+pub mod deser {
+    pub mod value {
+        use crate::deserialize::value::{
+            DeserializeValue, UdtDeserializationErrorKind, UdtIterator, UdtTypeCheckErrorKind,
+            deser_error_replace_rust_name, mk_deser_err, mk_typck_err,
+        };
+        use crate::deserialize::{DeserializationError, FrameSlice, TypeCheckError};
+        use crate::frame::response::result::ColumnType;
+
+        /// Checks that `typ` is a UDT with a textual field named `tag_field`.
+        ///
+        /// Implementation detail of `#[derive(DeserializeValue)]` for enums with the `tag` attribute.
+        pub fn udt_tag_type_check<T>(
+            typ: &ColumnType,
+            tag_field: &'static str,
+        ) -> Result<(), TypeCheckError> {
+            let ColumnType::UserDefinedType { definition, .. } = typ else {
+                return Err(mk_typck_err::<T>(typ, UdtTypeCheckErrorKind::NotUdt));
+            };
+            let Some((_, tag_typ)) = definition
+                .field_types
+                .iter()
+                .find(|(name, _)| name == tag_field)
+            else {
+                return Err(mk_typck_err::<T>(
+                    typ,
+                    UdtTypeCheckErrorKind::ValuesMissingForUdtFields {
+                        field_names: vec![tag_field],
+                    },
+                ));
+            };
+            <&str as DeserializeValue>::type_check(tag_typ).map_err(|err| {
+                mk_typck_err::<T>(
+                    typ,
+                    UdtTypeCheckErrorKind::FieldTypeCheckFailed {
+                        field_name: tag_field.to_owned(),
+                        err,
+                    },
+                )
+            })
+        }
+
+        /// Reads the value of the `tag_field` field of a serialized UDT.
+        ///
+        /// Implementation detail of `#[derive(DeserializeValue)]` for enums with the `tag` attribute.
+        pub fn deserialize_udt_tag<'frame, 'metadata, T>(
+            typ: &'metadata ColumnType<'metadata>,
+            v: Option<FrameSlice<'frame>>,
+            tag_field: &'static str,
+        ) -> Result<&'frame str, DeserializationError> {
+            let udt =
+                UdtIterator::deserialize(typ, v).map_err(deser_error_replace_rust_name::<T>)?;
+            for ((name, field_typ), value) in udt {
+                if name != tag_field {
+                    continue;
+                }
+                let value = value.map_err(deser_error_replace_rust_name::<T>)?;
+                return <&str as DeserializeValue>::deserialize(field_typ, value.flatten())
+                    .map_err(|err| {
+                        mk_deser_err::<T>(
+                            typ,
+                            UdtDeserializationErrorKind::FieldDeserializationFailed {
+                                field_name: tag_field.to_owned(),
+                                err,
+                            },
+                        )
+                    });
+            }
+            unreachable!("Typecheck should have prevented this scenario!")
+        }
+    }
 }
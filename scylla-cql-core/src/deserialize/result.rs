@@ -14,6 +14,7 @@ pub struct RawRowIterator<'frame, 'metadata> {
     specs: &'metadata [ColumnSpec<'metadata>],
     remaining: usize,
     slice: FrameSlice<'frame>,
+    max_row_size: Option<usize>,
 }
 
 impl<'frame, 'metadata> RawRowIterator<'frame, 'metadata> {
@@ -32,9 +33,21 @@ impl<'frame, 'metadata> RawRowIterator<'frame, 'metadata> {
             specs,
             remaining,
             slice,
+            max_row_size: None,
         }
     }
 
+    /// Limits the size of a single serialized row, in bytes.
+    ///
+    /// A row larger than the limit is returned as a
+    /// [`RowTooLarge`](BuiltinDeserializationErrorKind::RowTooLarge) error.
+    /// Subsequent rows are still returned.
+    #[inline]
+    pub fn with_max_row_size(mut self, max_row_size: usize) -> Self {
+        self.max_row_size = Some(max_row_size);
+        self
+    }
+
     /// Returns information about the columns of rows that are iterated over.
     #[inline]
     pub fn specs(&self) -> &'metadata [ColumnSpec<'metadata>] {
@@ -57,6 +70,7 @@ impl<'frame, 'metadata> Iterator for RawRowIterator<'frame, 'metadata> {
         self.remaining = self.remaining.checked_sub(1)?;
 
         let iter = ColumnIterator::new(self.specs, self.slice);
+        let len_before_row = self.slice.as_slice().len();
 
         // Skip the row here, manually
         for (column_index, spec) in self.specs.iter().enumerate() {
@@ -71,6 +85,15 @@ impl<'frame, 'metadata> Iterator for RawRowIterator<'frame, 'metadata> {
             }
         }
 
+        if let Some(max_size) = self.max_row_size {
+            let size = len_before_row - self.slice.as_slice().len();
+            if size > max_size {
+                return Some(Err(mk_deser_err::<Self>(
+                    BuiltinDeserializationErrorKind::RowTooLarge { size, max_size },
+                )));
+            }
+        }
+
         Some(Ok(iter))
     }
 
@@ -110,6 +133,17 @@ where
         })
     }
 
+    /// Limits the size of a single serialized row, in bytes.
+    ///
+    /// See [RawRowIterator::with_max_row_size].
+    #[inline]
+    pub fn with_max_row_size(self, max_row_size: usize) -> Self {
+        Self {
+            inner: self.inner.with_max_row_size(max_row_size),
+            _phantom: PhantomData,
+        }
+    }
+
     /// Returns information about the columns of rows that are iterated over.
     #[inline]
     pub fn specs(&self) -> &'metadata [ColumnSpec<'metadata>] {
@@ -152,12 +186,14 @@ impl<'frame, 'metadata, R> ExactSizeIterator for TypedRowIterator<'frame, 'metad
 
 #[cfg(test)]
 mod tests {
+    use assert_matches::assert_matches;
     use bytes::Bytes;
 
     use crate::frame::response::result::{ColumnType, NativeType};
 
+    use super::super::row::BuiltinDeserializationError;
     use super::super::tests::{CELL1, CELL2, serialize_cells, spec};
-    use super::{FrameSlice, RawRowIterator, TypedRowIterator};
+    use super::{BuiltinDeserializationErrorKind, FrameSlice, RawRowIterator, TypedRowIterator};
 
     #[test]
     fn test_raw_row_iterator_basic_parse() {
@@ -229,4 +265,32 @@ mod tests {
         let iter = RawRowIterator::new(0, &specs, FrameSlice::new(&raw_data));
         assert!(TypedRowIterator::<'_, '_, (i32, i64)>::new(iter).is_err());
     }
+
+    #[test]
+    fn test_typed_row_iterator_max_row_size() {
+        let long_cell = [0xab; 100];
+        let raw_data = serialize_cells([Some(CELL1), Some(&long_cell[..]), Some(CELL2)]);
+        let specs = [spec("b", ColumnType::Native(NativeType::Blob))];
+        let iter = RawRowIterator::new(3, &specs, FrameSlice::new(&raw_data));
+        // Each cell is prefixed with its 4-byte length.
+        let mut iter = TypedRowIterator::<'_, '_, (&[u8],)>::new(iter)
+            .unwrap()
+            .with_max_row_size(8);
+
+        assert_eq!(iter.next().unwrap().unwrap(), (CELL1,));
+
+        let err = iter.next().unwrap().unwrap_err();
+        let err = err.downcast_ref::<BuiltinDeserializationError>().unwrap();
+        assert_matches!(
+            err.kind,
+            BuiltinDeserializationErrorKind::RowTooLarge {
+                size: 104,
+                max_size: 8,
+            }
+        );
+
+        // Rows following the oversized one are still returned.
+        assert_eq!(iter.next().unwrap().unwrap(), (CELL2,));
+        assert!(iter.next().is_none());
+    }
 }
@@ -5,6 +5,7 @@
 
 use std::fmt::Display;
 
+use bytes::Bytes;
 use thiserror::Error;
 
 use super::value::DeserializeValue;
@@ -55,6 +56,22 @@ impl<'frame, 'metadata> ColumnIterator<'frame, 'metadata> {
         self.specs.len()
     }
 
+    /// Returns the serialized bytes of the remaining columns, exactly as received
+    /// from the database. The iterator itself is not advanced.
+    ///
+    /// The returned [Bytes] point into the frame, so no data is copied.
+    /// Fails if some of the remaining columns are malformed.
+    pub fn raw_row(&self) -> Result<Bytes, DeserializationError> {
+        let mut rest = self.clone();
+        rest.try_for_each(|column| column.map(|_| ()))?;
+
+        let remaining = self.slice.as_slice();
+        let row_len = remaining.len() - rest.slice.as_slice().len();
+        let mut row = self.slice;
+        *row.as_slice_mut() = &remaining[..row_len];
+        Ok(row.to_bytes())
+    }
+
     /// Performs a type check (see [DeserializeRow::type_check]) on remaining columns.
     #[inline]
     pub fn type_check<RowT: DeserializeRow<'frame, 'metadata>>(
@@ -302,6 +319,69 @@ impl_tuple_multiple!(
     t0, t1, t2, t3, t4, t5, t6, t7, t8, t9, t10, t11, t12, t13, t14, t15
 );
 
+// arrays
+//
+/// A row with a known, fixed number of columns of the same type can be deserialized
+/// into an array. The number of columns must be equal to the length of the array.
+impl<'frame, 'metadata, T, const N: usize> DeserializeRow<'frame, 'metadata> for [T; N]
+where
+    T: DeserializeValue<'frame, 'metadata>,
+{
+    fn type_check(specs: &[ColumnSpec]) -> Result<(), TypeCheckError> {
+        let column_types_iter = || specs.iter().map(|spec| spec.typ().clone().into_owned());
+        if specs.len() != N {
+            return Err(mk_typck_err::<Self>(
+                column_types_iter(),
+                BuiltinTypeCheckErrorKind::WrongColumnCount {
+                    rust_cols: N,
+                    cql_cols: specs.len(),
+                },
+            ));
+        }
+        for (column_index, spec) in specs.iter().enumerate() {
+            <T as DeserializeValue<'frame, 'metadata>>::type_check(spec.typ()).map_err(|err| {
+                mk_typck_err::<Self>(
+                    column_types_iter(),
+                    BuiltinTypeCheckErrorKind::ColumnTypeCheckFailed {
+                        column_index,
+                        column_name: spec.name().to_owned(),
+                        err,
+                    },
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(row: ColumnIterator<'frame, 'metadata>) -> Result<Self, DeserializationError> {
+        let mut elements = Vec::with_capacity(N);
+        for column in row {
+            let column = column.map_err(deser_error_replace_rust_name::<Self>)?;
+            let element = <T as DeserializeValue<'frame, 'metadata>>::deserialize(
+                column.spec.typ(),
+                column.slice,
+            )
+            .map_err(|err| {
+                mk_deser_err::<Self>(
+                    BuiltinDeserializationErrorKind::ColumnDeserializationFailed {
+                        column_index: column.index,
+                        column_name: column.spec.name().to_owned(),
+                        err,
+                    },
+                )
+            })?;
+            elements.push(element);
+        }
+        let elements_len = elements.len();
+        Ok(elements.try_into().unwrap_or_else(|_| {
+            unreachable!(
+                "Typecheck should have prevented this scenario! Column count mismatch: rust type {}, cql row {}",
+                N, elements_len
+            )
+        }))
+    }
+}
+
 // Error facilities
 
 /// Failed to type check incoming result column types again given Rust type,
@@ -518,6 +598,15 @@ pub enum BuiltinDeserializationErrorKind {
         /// The error that caused the raw column deserialization to fail.
         err: DeserializationError,
     },
+
+    /// The serialized row is larger than the configured maximum row size.
+    RowTooLarge {
+        /// Size of the serialized row, in bytes.
+        size: usize,
+
+        /// The configured maximum row size, in bytes.
+        max_size: usize,
+    },
 }
 
 impl Display for BuiltinDeserializationErrorKind {
@@ -543,6 +632,12 @@ impl Display for BuiltinDeserializationErrorKind {
                     "failed to deserialize raw column {column_name} at index {column_index} (most probably due to invalid column structure inside a row): {err}"
                 )
             }
+            BuiltinDeserializationErrorKind::RowTooLarge { size, max_size } => {
+                write!(
+                    f,
+                    "the row is {size} bytes long, which exceeds the maximum row size of {max_size} bytes"
+                )
+            }
         }
     }
 }
@@ -53,6 +53,90 @@ fn test_tuple_deserialization() {
     assert_eq!(s, "abc");
 }
 
+#[test]
+fn test_array_deserialization() {
+    // Empty array
+    let [] = deserialize::<[i32; 0]>(&[], &Bytes::new()).unwrap();
+
+    // Three-column int row
+    let specs = [
+        spec("i1", ColumnType::Native(NativeType::Int)),
+        spec("i2", ColumnType::Native(NativeType::Int)),
+        spec("i3", ColumnType::Native(NativeType::Int)),
+    ];
+    let byts = serialize_cells([val_int(123), val_int(456), val_int(789)]);
+    let arr = deserialize::<[i32; 3]>(&specs, &byts).unwrap();
+    assert_eq!(arr, [123, 456, 789]);
+
+    // Borrowing types work as well
+    let specs = [
+        spec("s1", ColumnType::Native(NativeType::Text)),
+        spec("s2", ColumnType::Native(NativeType::Text)),
+    ];
+    let byts = serialize_cells([val_str("abc"), val_str("def")]);
+    let arr = deserialize::<[&str; 2]>(&specs, &byts).unwrap();
+    assert_eq!(arr, ["abc", "def"]);
+}
+
+#[test]
+fn test_array_errors() {
+    let specs = [
+        spec("i1", ColumnType::Native(NativeType::Int)),
+        spec("i2", ColumnType::Native(NativeType::Int)),
+        spec("i3", ColumnType::Native(NativeType::Int)),
+    ];
+    let byts = serialize_cells([val_int(123), val_int(456), val_int(789)]);
+
+    // Wrong column count
+    {
+        let err = deserialize::<[i32; 2]>(&specs, &byts).unwrap_err();
+        let err = get_typck_err(&err);
+        assert_eq!(err.rust_name, std::any::type_name::<[i32; 2]>());
+        assert_matches!(
+            err.kind,
+            BuiltinTypeCheckErrorKind::WrongColumnCount {
+                rust_cols: 2,
+                cql_cols: 3
+            }
+        );
+    }
+
+    // Column type check failure
+    {
+        let err = deserialize::<[i64; 3]>(&specs, &byts).unwrap_err();
+        let err = get_typck_err(&err);
+        assert_eq!(err.rust_name, std::any::type_name::<[i64; 3]>());
+        let BuiltinTypeCheckErrorKind::ColumnTypeCheckFailed {
+            column_index,
+            ref column_name,
+            ..
+        } = err.kind
+        else {
+            panic!("unexpected error kind: {}", err.kind)
+        };
+        assert_eq!(column_index, 0);
+        assert_eq!(column_name, "i1");
+    }
+
+    // Column deserialization failure
+    {
+        let byts = serialize_cells([val_int(123), Some(vec![0x01]), val_int(789)]);
+        let err = deserialize::<[i32; 3]>(&specs, &byts).unwrap_err();
+        let err = get_deser_err(&err);
+        assert_eq!(err.rust_name, std::any::type_name::<[i32; 3]>());
+        let BuiltinDeserializationErrorKind::ColumnDeserializationFailed {
+            column_index,
+            ref column_name,
+            ..
+        } = err.kind
+        else {
+            panic!("unexpected error kind: {}", err.kind)
+        };
+        assert_eq!(column_index, 1);
+        assert_eq!(column_name, "i2");
+    }
+}
+
 #[test]
 fn test_deserialization_as_column_iterator() {
     let col_specs = [
@@ -81,6 +165,96 @@ fn test_deserialization_as_column_iterator() {
     assert!(iter.next().is_none());
 }
 
+#[test]
+fn test_deserialization_via_custom_row_trait() {
+    use crate::deserialize::value::DeserializeValue;
+
+    // A trait of a user's framework, bridged to `DeserializeRow` with a generic adapter.
+    trait FromRow: Sized {
+        fn from_row(row: ColumnIterator<'_, '_>) -> Result<Self, DeserializationError>;
+    }
+
+    #[derive(Debug)]
+    struct Adapter<T>(T);
+
+    impl<'frame, 'metadata, T: FromRow> DeserializeRow<'frame, 'metadata> for Adapter<T> {
+        fn type_check(_specs: &[ColumnSpec]) -> Result<(), TypeCheckError> {
+            Ok(())
+        }
+
+        fn deserialize(
+            row: ColumnIterator<'frame, 'metadata>,
+        ) -> Result<Self, DeserializationError> {
+            T::from_row(row).map(Adapter)
+        }
+    }
+
+    // Collects columns by name, ignoring their order.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Person {
+        name: String,
+        age: Option<i32>,
+    }
+
+    impl FromRow for Person {
+        fn from_row(row: ColumnIterator<'_, '_>) -> Result<Self, DeserializationError> {
+            fn value<'frame, 'metadata, T: DeserializeValue<'frame, 'metadata>>(
+                column: super::RawColumn<'frame, 'metadata>,
+            ) -> Result<T, DeserializationError> {
+                T::type_check(column.spec.typ()).map_err(DeserializationError::new)?;
+                T::deserialize(column.spec.typ(), column.slice)
+            }
+
+            let mut name = None;
+            let mut age = None;
+            for column in row {
+                let column = column?;
+                match column.spec.name() {
+                    "name" => name = Some(value::<String>(column)?),
+                    "age" => age = value::<Option<i32>>(column)?,
+                    _ => {}
+                }
+            }
+            Ok(Person {
+                name: name.unwrap_or_default(),
+                age,
+            })
+        }
+    }
+
+    let specs = [
+        spec("age", ColumnType::Native(NativeType::Int)),
+        spec("name", ColumnType::Native(NativeType::Text)),
+    ];
+    let byts = serialize_cells([val_int(42), val_str("Alice")]);
+    let Adapter(person) = deserialize::<Adapter<Person>>(&specs, &byts).unwrap();
+    assert_eq!(
+        person,
+        Person {
+            name: "Alice".to_owned(),
+            age: Some(42),
+        }
+    );
+
+    let byts = serialize_cells([None, val_str("Bob")]);
+    let Adapter(person) = deserialize::<Adapter<Person>>(&specs, &byts).unwrap();
+    assert_eq!(
+        person,
+        Person {
+            name: "Bob".to_owned(),
+            age: None,
+        }
+    );
+
+    // Type mismatches are reported by the custom implementation.
+    let specs = [spec("age", ColumnType::Native(NativeType::Text))];
+    let byts = serialize_cells([val_str("Alice")]);
+    assert_matches!(
+        deserialize::<Adapter<Person>>(&specs, &byts),
+        Err(TestDeserializeError::Deserialization(_))
+    );
+}
+
 fn val_int(i: i32) -> Option<Vec<u8>> {
     Some(i.to_be_bytes().to_vec())
 }
@@ -22,9 +22,11 @@ use crate::frame::response::result::UserDefinedType;
 use crate::frame::response::result::{ColumnType, NativeType};
 use crate::frame::types;
 use crate::value::CqlVarintBorrowed;
+#[cfg(feature = "serde-json-1")]
+use crate::value::Json;
 use crate::value::{
-    Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
-    CqlTimeuuid, CqlValue, CqlVarint,
+    Cidr, Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
+    CqlTimeuuid, CqlValue, CqlVarint, InvalidCidr, Maybe, WriteTime,
 };
 
 // Re-export for backwards compatibility. These types were moved to crate::value module.
@@ -293,6 +295,25 @@ where
     }
 }
 
+// Maybe represents nullability of CQL values, just like Option.
+impl<'frame, 'metadata, T> DeserializeValue<'frame, 'metadata> for Maybe<T>
+where
+    T: DeserializeValue<'frame, 'metadata>,
+{
+    fn type_check(typ: &ColumnType) -> Result<(), TypeCheckError> {
+        T::type_check(typ).map_err(typck_error_replace_rust_name::<Self>)
+    }
+
+    fn deserialize(
+        typ: &'metadata ColumnType<'metadata>,
+        v: Option<FrameSlice<'frame>>,
+    ) -> Result<Self, DeserializationError> {
+        <Option<T>>::deserialize(typ, v)
+            .map(Maybe::from)
+            .map_err(deser_error_replace_rust_name::<Self>)
+    }
+}
+
 macro_rules! impl_strict_type {
     ($t:ty, [$($cql:ident)|+], $conv:expr $(, $l:lifetime)?) => {
         impl<$($l,)? 'frame, 'metadata> DeserializeValue<'frame, 'metadata> for $t
@@ -482,6 +503,103 @@ impl_strict_type!(
     }
 );
 
+/// A blob of exactly `N` bytes. Deserialization fails if the length of the blob differs.
+impl<'frame, 'metadata, const N: usize> DeserializeValue<'frame, 'metadata> for [u8; N] {
+    fn type_check(typ: &ColumnType) -> Result<(), TypeCheckError> {
+        exact_type_check!(typ, Blob);
+        Ok(())
+    }
+
+    fn deserialize(
+        typ: &'metadata ColumnType<'metadata>,
+        v: Option<FrameSlice<'frame>>,
+    ) -> Result<Self, DeserializationError> {
+        let val = ensure_not_null_slice::<Self>(typ, v)?;
+        let arr = ensure_exact_length::<Self, N>(typ, val)?;
+        Ok(*arr)
+    }
+}
+
+/// A blob borrowed from the frame, read in chunks of bytes.
+///
+/// Deserializing a huge blob into a `Vec<u8>` requires a single allocation of its size.
+/// `BlobChunks` does not allocate, but yields subslices of the frame buffer of at most
+/// [chunk_size](Self::chunk_size) bytes each, so that the blob can be processed piece by piece.
+/// It can be consumed either as an [Iterator] of chunks or as a [std::io::Read].
+#[derive(Debug, Clone)]
+pub struct BlobChunks<'frame> {
+    remaining: &'frame [u8],
+    chunk_size: usize,
+}
+
+impl<'frame> BlobChunks<'frame> {
+    /// The size of chunks, unless set with [with_chunk_size](Self::with_chunk_size).
+    pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+    /// Sets the maximum size of the yielded chunks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn with_chunk_size(self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be positive");
+        Self { chunk_size, ..self }
+    }
+
+    /// Returns the maximum size of the yielded chunks.
+    #[inline]
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Returns the number of bytes of the blob which have not been read yet.
+    #[inline]
+    pub fn remaining_len(&self) -> usize {
+        self.remaining.len()
+    }
+}
+
+impl_strict_type!(
+    BlobChunks<'a>,
+    Blob,
+    |typ: &'metadata ColumnType<'metadata>, v: Option<FrameSlice<'frame>>| {
+        let val = ensure_not_null_slice::<Self>(typ, v)?;
+        Ok(BlobChunks {
+            remaining: val,
+            chunk_size: BlobChunks::DEFAULT_CHUNK_SIZE,
+        })
+    },
+    'a
+);
+
+impl<'frame> Iterator for BlobChunks<'frame> {
+    type Item = &'frame [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let (chunk, rest) = self
+            .remaining
+            .split_at(self.chunk_size.min(self.remaining.len()));
+        self.remaining = rest;
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let chunks = self.remaining.len().div_ceil(self.chunk_size);
+        (chunks, Some(chunks))
+    }
+}
+
+impl ExactSizeIterator for BlobChunks<'_> {}
+
+impl std::io::Read for BlobChunks<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        std::io::Read::read(&mut self.remaining, buf)
+    }
+}
+
 // string
 
 macro_rules! impl_string_type {
@@ -531,6 +649,44 @@ impl_string_type!(
 
 // TODO: Consider support for deserialization of string::String<Bytes>
 
+impl_string_type!(
+    Cidr,
+    |typ: &'metadata ColumnType<'metadata>, v: Option<FrameSlice<'frame>>| {
+        let val = ensure_not_null_slice::<Self>(typ, v)?;
+        let s = std::str::from_utf8(val).map_err(|err| {
+            mk_deser_err::<Self>(typ, BuiltinDeserializationErrorKind::InvalidUtf8(err))
+        })?;
+        s.parse().map_err(|err| {
+            mk_deser_err::<Self>(typ, BuiltinDeserializationErrorKind::InvalidCidr(err))
+        })
+    }
+);
+
+#[cfg(feature = "serde-json-1")]
+impl<'frame, 'metadata, T> DeserializeValue<'frame, 'metadata> for Json<T>
+where
+    T: serde::Deserialize<'frame>,
+{
+    fn type_check(typ: &ColumnType) -> Result<(), TypeCheckError> {
+        exact_type_check!(typ, Ascii, Text);
+        Ok(())
+    }
+
+    fn deserialize(
+        typ: &'metadata ColumnType<'metadata>,
+        v: Option<FrameSlice<'frame>>,
+    ) -> Result<Self, DeserializationError> {
+        let val = ensure_not_null_slice::<Self>(typ, v)?;
+        check_ascii::<Self>(typ, val)?;
+        serde_json_1::from_slice(val).map(Json).map_err(|err| {
+            mk_deser_err::<Self>(
+                typ,
+                BuiltinDeserializationErrorKind::InvalidJson(Arc::new(err)),
+            )
+        })
+    }
+}
+
 // counter
 
 impl_strict_type!(
@@ -544,6 +700,18 @@ impl_strict_type!(
     }
 );
 
+// writetime
+
+impl_strict_type!(
+    WriteTime,
+    BigInt,
+    |typ: &'metadata ColumnType<'metadata>, v: Option<FrameSlice<'frame>>| {
+        let val = ensure_not_null_slice::<Self>(typ, v)?;
+        let arr = ensure_exact_length::<Self, 8>(typ, val)?;
+        Ok(WriteTime(i64::from_be_bytes(*arr)))
+    }
+);
+
 // date and time types
 
 // duration
@@ -592,6 +760,18 @@ impl_strict_type!(
     }
 );
 
+#[cfg(feature = "chrono-04")]
+impl_strict_type!(
+    chrono_04::Duration,
+    Duration,
+    |typ: &'metadata ColumnType<'metadata>, v: Option<FrameSlice<'frame>>| {
+        let cql_duration = CqlDuration::deserialize(typ, v)?;
+        cql_duration
+            .try_into()
+            .map_err(|_| mk_deser_err::<Self>(typ, BuiltinDeserializationErrorKind::ValueOverflow))
+    }
+);
+
 impl_strict_type!(
     CqlDate,
     Date,
@@ -895,7 +1075,7 @@ make_error_replace_rust_name!(
     /// - It should be called **before** the error is cloned, as it attempts to mutably access
     ///   the inner error stored in `Arc`; if the `Arc` has already been cloned,
     ///   a new [`BuiltinTypeCheckError`] will be allocated with the updated name instead.
-    pub(crate),
+    pub,
     typck_error_replace_rust_name,
     TypeCheckError,
     BuiltinTypeCheckError
@@ -2476,6 +2656,9 @@ pub enum BuiltinDeserializationErrorKind {
     /// The length of read value in bytes is not suitable for IP address.
     BadInetLength { got: usize },
 
+    /// The read string is not a valid IP network in CIDR notation.
+    InvalidCidr(InvalidCidr),
+
     /// A deserialization failure specific to a CQL set or list.
     SetOrListError(SetOrListDeserializationErrorKind),
 
@@ -2493,6 +2676,10 @@ pub enum BuiltinDeserializationErrorKind {
 
     /// Deserialization of this CQL type is not supported by the driver.
     Unsupported,
+
+    /// The read string is not a valid JSON representation of the Rust type.
+    #[cfg(feature = "serde-json-1")]
+    InvalidJson(Arc<serde_json_1::Error>),
 }
 
 impl Display for BuiltinDeserializationErrorKind {
@@ -2527,6 +2714,7 @@ impl Display for BuiltinDeserializationErrorKind {
                 f,
                 "the length of read value in bytes ({got}) is not suitable for IP address; expected 4 or 16"
             ),
+            BuiltinDeserializationErrorKind::InvalidCidr(err) => err.fmt(f),
             BuiltinDeserializationErrorKind::SetOrListError(err) => err.fmt(f),
             BuiltinDeserializationErrorKind::VectorError(err) => err.fmt(f),
             BuiltinDeserializationErrorKind::MapError(err) => err.fmt(f),
@@ -2535,6 +2723,13 @@ impl Display for BuiltinDeserializationErrorKind {
             BuiltinDeserializationErrorKind::Unsupported => {
                 f.write_str("deserialization of this CQL type is not supported by the driver")
             }
+            #[cfg(feature = "serde-json-1")]
+            BuiltinDeserializationErrorKind::InvalidJson(err) => {
+                write!(
+                    f,
+                    "the read string is not valid JSON of the Rust type: {err}"
+                )
+            }
         }
     }
 }
@@ -2670,6 +2865,16 @@ pub enum UdtDeserializationErrorKind {
         /// The error that caused the UDT field deserialization to fail.
         err: DeserializationError,
     },
+
+    /// The tag field of a UDT has a value which does not correspond
+    /// to any variant of the Rust enum.
+    UnknownTag {
+        /// Name of the tag field.
+        tag_field: String,
+
+        /// The value of the tag field.
+        tag_value: String,
+    },
 }
 
 impl Display for UdtDeserializationErrorKind {
@@ -2678,6 +2883,15 @@ impl Display for UdtDeserializationErrorKind {
             UdtDeserializationErrorKind::FieldDeserializationFailed { field_name, err } => {
                 write!(f, "field {field_name} failed to deserialize: {err}")
             }
+            UdtDeserializationErrorKind::UnknownTag {
+                tag_field,
+                tag_value,
+            } => {
+                write!(
+                    f,
+                    "the value \"{tag_value}\" of the tag field {tag_field} does not match any variant"
+                )
+            }
         }
     }
 }
@@ -16,16 +16,16 @@ use crate::frame::response::result::{CollectionType, ColumnType, NativeType, Use
 use crate::serialize::CellWriter;
 use crate::serialize::value::SerializeValue;
 use crate::value::{
-    Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
-    CqlTimeuuid, CqlValue, CqlVarint, CqlVarintBorrowed,
+    Cidr, Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
+    CqlTimeuuid, CqlValue, CqlVarint, CqlVarintBorrowed, InvalidCidr, Maybe, WriteTime,
 };
 
 #[allow(deprecated)]
 use super::{
-    BuiltinDeserializationError, BuiltinDeserializationErrorKind, BuiltinTypeCheckError,
-    BuiltinTypeCheckErrorKind, DeserializeValue, ListlikeIterator, MapDeserializationErrorKind,
-    MapIterator, MapTypeCheckErrorKind, MaybeEmpty, SetOrListDeserializationErrorKind,
-    SetOrListTypeCheckErrorKind, VectorIterator, mk_deser_err,
+    BlobChunks, BuiltinDeserializationError, BuiltinDeserializationErrorKind,
+    BuiltinTypeCheckError, BuiltinTypeCheckErrorKind, DeserializeValue, ListlikeIterator,
+    MapDeserializationErrorKind, MapIterator, MapTypeCheckErrorKind, MaybeEmpty,
+    SetOrListDeserializationErrorKind, SetOrListTypeCheckErrorKind, VectorIterator, mk_deser_err,
 };
 
 #[test]
@@ -62,6 +62,70 @@ fn test_deserialize_bytes() {
     );
 }
 
+#[test]
+fn test_deserialize_fixed_size_blob() {
+    const ORIGINAL_BYTES: [u8; 16] = [
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee,
+        0xff,
+    ];
+
+    let bytes = make_bytes(&ORIGINAL_BYTES);
+
+    let decoded_array =
+        deserialize::<[u8; 16]>(&ColumnType::Native(NativeType::Blob), &bytes).unwrap();
+    assert_eq!(decoded_array, ORIGINAL_BYTES);
+
+    // ser/de identity
+    assert_ser_de_identity(
+        &ColumnType::Native(NativeType::Blob),
+        &ORIGINAL_BYTES,
+        &mut Bytes::new(),
+    );
+}
+
+#[test]
+fn test_deserialize_blob_in_chunks() {
+    use std::io::Read;
+
+    // A multi-megabyte blob, whose size is not a multiple of the chunk size.
+    let original: Vec<u8> = (0..5 * 1024 * 1024 + 123)
+        .map(|i: usize| (i % 251) as u8)
+        .collect();
+    let bytes = make_bytes(&original);
+    let typ = ColumnType::Native(NativeType::Blob);
+
+    let chunks = deserialize::<BlobChunks>(&typ, &bytes).unwrap();
+    assert_eq!(chunks.chunk_size(), BlobChunks::DEFAULT_CHUNK_SIZE);
+    assert_eq!(chunks.remaining_len(), original.len());
+
+    // Chunks are subslices of the frame, so nothing is copied.
+    let chunks = chunks.with_chunk_size(1024 * 1024);
+    assert_eq!(chunks.len(), 6);
+    let mut read = Vec::new();
+    for chunk in chunks {
+        assert!(chunk.len() <= 1024 * 1024);
+        assert!(bytes.as_ptr_range().contains(&chunk.as_ptr()));
+        read.extend_from_slice(chunk);
+    }
+    assert_eq!(read, original);
+
+    // The blob can also be consumed as a reader.
+    let mut reader = deserialize::<BlobChunks>(&typ, &bytes).unwrap();
+    let mut buf = [0u8; 4096];
+    let n = reader.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], &original[..n]);
+    assert_eq!(reader.remaining_len(), original.len() - n);
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, original[n..]);
+
+    // An empty blob has no chunks, and null is not a blob.
+    let empty = make_bytes(&[]);
+    assert_eq!(deserialize::<BlobChunks>(&typ, &empty).unwrap().count(), 0);
+    deserialize::<BlobChunks>(&typ, &make_null()).unwrap_err();
+    deserialize::<BlobChunks>(&ColumnType::Native(NativeType::Text), &empty).unwrap_err();
+}
+
 #[test]
 fn test_deserialize_vector() {
     // ser/de identity
@@ -788,6 +852,21 @@ fn test_inet() {
     );
 }
 
+#[test]
+fn test_cidr() {
+    assert_ser_de_identity(
+        &ColumnType::Native(NativeType::Text),
+        &Cidr::from_str("192.168.0.0/16").unwrap(),
+        &mut Bytes::new(),
+    );
+
+    assert_ser_de_identity(
+        &ColumnType::Native(NativeType::Ascii),
+        &Cidr::from_str("fe80::/10").unwrap(),
+        &mut Bytes::new(),
+    );
+}
+
 #[test]
 fn test_uuid() {
     assert_ser_de_identity(
@@ -870,6 +949,29 @@ fn test_maybe_empty() {
     assert_eq!(decoded_non_empty, MaybeEmpty::Value(0x01));
 }
 
+#[test]
+fn test_maybe() {
+    let null = make_null();
+    let decoded_null =
+        deserialize::<Maybe<i32>>(&ColumnType::Native(NativeType::Int), &null).unwrap();
+    assert_eq!(decoded_null, Maybe::Null);
+    assert!(decoded_null.is_null());
+    assert_eq!(decoded_null.into_option(), None);
+
+    let non_null = make_bytes(&[0, 0, 0, 42]);
+    let decoded_non_null =
+        deserialize::<Maybe<i32>>(&ColumnType::Native(NativeType::Int), &non_null).unwrap();
+    assert_eq!(decoded_non_null, Maybe::Value(42));
+    assert!(!decoded_non_null.is_null());
+    assert_eq!(decoded_non_null.into_option(), Some(42));
+
+    // Type check errors are reported just like for `Option`.
+    let err =
+        deserialize::<Maybe<i32>>(&ColumnType::Native(NativeType::Text), &non_null).unwrap_err();
+    let err = get_typeck_err(&err);
+    assert_eq!(err.rust_name, std::any::type_name::<Maybe<i32>>());
+}
+
 #[test]
 fn test_cql_value() {
     assert_ser_de_identity(
@@ -1154,6 +1256,68 @@ fn test_tuples() {
     );
 }
 
+#[test]
+fn test_tuples_in_collections() {
+    let tuple_typ = ColumnType::Tuple(vec![
+        ColumnType::Native(NativeType::Int),
+        ColumnType::Native(NativeType::Text),
+    ]);
+    let list_typ = ColumnType::Collection {
+        frozen: false,
+        typ: CollectionType::List(Box::new(tuple_typ.clone())),
+    };
+
+    // list<tuple<int, text>>
+    let list = vec![
+        (1, "one".to_string()),
+        (2, String::new()),
+        (-3, "three".to_string()),
+    ];
+    assert_ser_de_identity(&list_typ, &list, &mut Bytes::new());
+
+    // Elements borrowed from the frame.
+    let serialized = serialize(&list_typ, &list);
+    let borrowed = deserialize::<Vec<(i32, &str)>>(&list_typ, &serialized).unwrap();
+    assert_eq!(borrowed, vec![(1, "one"), (2, ""), (-3, "three")]);
+
+    // Null tuple fields.
+    let with_nulls = vec![(Some(1), None), (None, Some("two".to_string()))];
+    assert_ser_de_identity::<Vec<(Option<i32>, Option<String>)>>(
+        &list_typ,
+        &with_nulls,
+        &mut Bytes::new(),
+    );
+
+    // set<tuple<int, text>> and map<int, tuple<int, text>>
+    let set_typ = ColumnType::Collection {
+        frozen: false,
+        typ: CollectionType::Set(Box::new(tuple_typ.clone())),
+    };
+    let set: BTreeSet<(i32, String)> = list.iter().cloned().collect();
+    assert_ser_de_identity(&set_typ, &set, &mut Bytes::new());
+
+    let map_typ = ColumnType::Collection {
+        frozen: false,
+        typ: CollectionType::Map(
+            Box::new(ColumnType::Native(NativeType::Int)),
+            Box::new(tuple_typ.clone()),
+        ),
+    };
+    let map: BTreeMap<i32, (i32, String)> = list.iter().cloned().map(|t| (t.0, t)).collect();
+    assert_ser_de_identity(&map_typ, &map, &mut Bytes::new());
+
+    // list<frozen<list<tuple<int, text>>>>
+    let nested_typ = ColumnType::Collection {
+        frozen: false,
+        typ: CollectionType::List(Box::new(ColumnType::Collection {
+            frozen: true,
+            typ: CollectionType::List(Box::new(tuple_typ)),
+        })),
+    };
+    let nested = vec![list.clone(), Vec::new(), list];
+    assert_ser_de_identity(&nested_typ, &nested, &mut Bytes::new());
+}
+
 #[test]
 fn test_box() {
     {
@@ -1474,6 +1638,134 @@ fn test_native_errors() {
     }
 }
 
+#[test]
+fn test_fixed_size_blob_errors() {
+    let bytes = make_bytes(&[1, 2, 3, 4]);
+
+    assert_type_check_error!(
+        &bytes,
+        [u8; 16],
+        ColumnType::Native(NativeType::Text),
+        super::BuiltinTypeCheckErrorKind::MismatchedType {
+            expected: &[ColumnType::Native(NativeType::Blob)],
+        }
+    );
+
+    // The blob is shorter than the array.
+    assert_deser_error!(
+        &bytes,
+        [u8; 16],
+        ColumnType::Native(NativeType::Blob),
+        BuiltinDeserializationErrorKind::ByteLengthMismatch {
+            expected: 16,
+            got: 4,
+        }
+    );
+
+    // The blob is longer than the array.
+    assert_deser_error!(
+        &bytes,
+        [u8; 2],
+        ColumnType::Native(NativeType::Blob),
+        BuiltinDeserializationErrorKind::ByteLengthMismatch {
+            expected: 2,
+            got: 4,
+        }
+    );
+}
+
+#[test]
+fn test_deserialize_writetime() {
+    let bytes = make_bytes(&1_700_000_000_123_456_i64.to_be_bytes());
+
+    let decoded = deserialize::<WriteTime>(&ColumnType::Native(BigInt), &bytes).unwrap();
+    assert_eq!(decoded, WriteTime(1_700_000_000_123_456));
+
+    // ser/de identity
+    assert_ser_de_identity(
+        &ColumnType::Native(BigInt),
+        &WriteTime(-1),
+        &mut Bytes::new(),
+    );
+
+    // A writetime is always a bigint.
+    assert_type_check_error!(
+        &bytes,
+        WriteTime,
+        ColumnType::Native(Timestamp),
+        super::BuiltinTypeCheckErrorKind::MismatchedType {
+            expected: &[ColumnType::Native(NativeType::BigInt)],
+        }
+    );
+}
+
+#[test]
+fn test_cidr_errors() {
+    // Text which is not a valid network is rejected.
+    for (text, expected) in [
+        ("192.168.0.0", InvalidCidr::MissingPrefixLength),
+        (
+            "192.168.0.0/40",
+            InvalidCidr::PrefixLengthTooLong {
+                prefix_len: 40,
+                address_len: 32,
+            },
+        ),
+        ("192.168.0.1/16", InvalidCidr::HostBitsSet),
+    ] {
+        let bytes = serialize(&ColumnType::Native(NativeType::Text), &text);
+        let err = deserialize::<Cidr>(&ColumnType::Native(NativeType::Text), &bytes).unwrap_err();
+        let err = get_deser_err(&err);
+        assert_matches!(
+            err.kind,
+            BuiltinDeserializationErrorKind::InvalidCidr(cidr_err) if cidr_err == expected
+        );
+    }
+}
+
+#[cfg(feature = "serde-json-1")]
+#[test]
+fn test_json() {
+    use crate::value::Json;
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Document {
+        name: String,
+        tags: Vec<String>,
+        score: Option<f64>,
+    }
+
+    let document = Json(Document {
+        name: "zażółć".to_owned(),
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        score: Some(2.5),
+    });
+    assert_ser_de_identity(
+        &ColumnType::Native(NativeType::Text),
+        &document,
+        &mut Bytes::new(),
+    );
+
+    // The value is stored as plain JSON text.
+    let bytes = serialize(&ColumnType::Native(NativeType::Text), &document);
+    let text = deserialize::<&str>(&ColumnType::Native(NativeType::Text), &bytes).unwrap();
+    assert_eq!(text, r#"{"name":"zażółć","tags":["a","b"],"score":2.5}"#);
+
+    // Borrowed strings can be deserialized straight from the frame.
+    let bytes = serialize(&ColumnType::Native(NativeType::Text), &r#"{"key":"value"}"#);
+    let decoded =
+        deserialize::<Json<HashMap<&str, &str>>>(&ColumnType::Native(NativeType::Text), &bytes)
+            .unwrap();
+    assert_eq!(decoded.0, HashMap::from([("key", "value")]));
+
+    // Text which is not a valid JSON of the type is rejected.
+    let bytes = serialize(&ColumnType::Native(NativeType::Text), &r#"{"name":"x"}"#);
+    let err =
+        deserialize::<Json<Document>>(&ColumnType::Native(NativeType::Text), &bytes).unwrap_err();
+    let err = get_deser_err(&err);
+    assert_matches!(err.kind, BuiltinDeserializationErrorKind::InvalidJson(_));
+}
+
 #[test]
 fn test_option_errors() {
     // Type check correctly renames Rust type
@@ -2423,6 +2715,26 @@ fn test_bigint03() {
         let value = deserialize::<CqlValue>(&ColumnType::Native(Varint), &bytes).unwrap();
         assert_eq!(CqlValue::Varint(t.value.to_bigint().unwrap().into()), value);
     }
+
+    // Values just outside of the range of i64 need 9 bytes.
+    let typ = ColumnType::Native(Varint);
+    for (value, encoding) in [
+        (
+            num_bigint_03::BigInt::from(i64::MAX) + 1,
+            [0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ),
+        (
+            num_bigint_03::BigInt::from(i64::MIN) - 1,
+            [0xFF, 0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+        ),
+    ] {
+        let bytes = make_bytes(&encoding);
+        assert_eq!(serialize(&typ, &value), bytes);
+        assert_eq!(
+            deserialize::<num_bigint_03::BigInt>(&typ, &bytes).unwrap(),
+            value
+        );
+    }
 }
 
 #[cfg(feature = "num-bigint-04")]
@@ -2437,6 +2749,26 @@ fn test_bigint04() {
         let value = deserialize::<CqlValue>(&ColumnType::Native(Varint), &bytes).unwrap();
         assert_eq!(CqlValue::Varint(t.value.to_bigint().unwrap().into()), value);
     }
+
+    // Values just outside of the range of i64 need 9 bytes.
+    let typ = ColumnType::Native(Varint);
+    for (value, encoding) in [
+        (
+            num_bigint_04::BigInt::from(i64::MAX) + 1,
+            [0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ),
+        (
+            num_bigint_04::BigInt::from(i64::MIN) - 1,
+            [0xFF, 0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+        ),
+    ] {
+        let bytes = make_bytes(&encoding);
+        assert_eq!(serialize(&typ, &value), bytes);
+        assert_eq!(
+            deserialize::<num_bigint_04::BigInt>(&typ, &bytes).unwrap(),
+            value
+        );
+    }
 }
 
 #[cfg(feature = "bigdecimal-04")]
@@ -2477,6 +2809,50 @@ fn test_decimal() {
     }
 }
 
+#[cfg(feature = "bigdecimal-04")]
+#[test]
+fn test_decimal_scale_round_trip() {
+    use bigdecimal_04::BigDecimal;
+
+    // `BigDecimal` equality ignores scale (1.50 == 1.5), so the unscaled value
+    // and the scale are compared explicitly.
+    let values = [
+        // Trailing zeros are a part of the scale and must not be normalized away.
+        BigDecimal::from_str("1.500000000000000000000000000000000000000000000000").unwrap(),
+        BigDecimal::from_str("-0.000000000000000000000000000000000000000000000000000000000001")
+            .unwrap(),
+        BigDecimal::from_str("3.14159265358979323846264338327950288419716939937510582097494")
+            .unwrap(),
+        // Negative scale.
+        BigDecimal::new(12345.into(), -20),
+        BigDecimal::new(1.into(), i32::MAX as i64),
+    ];
+
+    let typ = ColumnType::Native(Decimal);
+    for value in values {
+        let (unscaled, scale) = value.as_bigint_and_exponent();
+        let bytes = serialize(&typ, &value);
+
+        let expected_bytes = make_bytes(
+            &(scale as i32)
+                .to_be_bytes()
+                .into_iter()
+                .chain(unscaled.to_signed_bytes_be())
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(bytes, expected_bytes);
+
+        let cql_decimal = deserialize::<CqlDecimal>(&typ, &bytes).unwrap();
+        assert_eq!(
+            cql_decimal.as_signed_be_bytes_slice_and_exponent(),
+            (unscaled.to_signed_bytes_be().as_slice(), scale as i32)
+        );
+
+        let deserialized = deserialize::<BigDecimal>(&typ, &bytes).unwrap();
+        assert_eq!(deserialized.as_bigint_and_exponent(), (unscaled, scale));
+    }
+}
+
 #[test]
 fn test_deserialize_counter() {
     let counter_bytes = make_bytes(&[0, 0, 0, 0, 0, 0, 1, 0]);
@@ -3018,6 +3394,35 @@ fn test_duration_deserialize() {
     );
 }
 
+#[cfg(feature = "chrono-04")]
+#[test]
+fn test_duration_04() {
+    use chrono_04::Duration as ChronoDuration;
+
+    let col_type = ColumnType::Native(Duration);
+    for duration in [
+        ChronoDuration::zero(),
+        ChronoDuration::nanoseconds(-1),
+        ChronoDuration::try_days(3).unwrap() + ChronoDuration::nanoseconds(42),
+        -(ChronoDuration::try_days(2).unwrap() + ChronoDuration::try_hours(5).unwrap()),
+    ] {
+        assert_ser_de_identity(&col_type, &duration, &mut Bytes::new());
+    }
+
+    // Months have no fixed length, so they cannot be converted
+    let bytes = serialize(
+        &col_type,
+        &CqlDuration {
+            months: 1,
+            days: 0,
+            nanoseconds: 0,
+        },
+    );
+    let err = deserialize::<ChronoDuration>(&col_type, &bytes).unwrap_err();
+    let err = get_deser_err(&err);
+    assert_matches!(err.kind, BuiltinDeserializationErrorKind::ValueOverflow);
+}
+
 #[test]
 fn test_deserialize_empty_payload() {
     for (test_type, res_cql) in [
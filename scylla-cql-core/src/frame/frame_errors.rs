@@ -292,6 +292,10 @@ pub enum CqlRequestSerializationError {
     /// Request body compression failed.
     #[error("Snap compression error: {0}")]
     SnapCompressError(Arc<dyn Error + Sync + Send>),
+
+    /// The custom payload has too many entries, or one of them is too large.
+    #[error("Failed to serialize the custom payload: {0}")]
+    CustomPayloadSerialization(std::num::TryFromIntError),
 }
 
 /// An error type returned when deserialization of CQL
@@ -509,6 +513,20 @@ pub enum PreparedParseError {
     NonZeroPagingState(Arc<[u8]>),
 }
 
+/// An error type returned when serialization of a prepared statement
+/// in the format of `RESULT::Prepared` response fails.
+#[non_exhaustive]
+#[derive(Debug, Error, Clone)]
+pub enum PreparedSerializationError {
+    /// A length does not fit in its serialized representation.
+    #[error("Length out of range: {0}")]
+    LengthOutOfRange(#[from] TryFromIntError),
+
+    /// A column type is not supported by this version of the driver.
+    #[error("Unsupported column type")]
+    UnsupportedColumnType,
+}
+
 /// An error that occurred during initial deserialization of
 /// `RESULT:Rows` response. Since the deserialization of rows is lazy,
 /// we initially only need to deserialize:
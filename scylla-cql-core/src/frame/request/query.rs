@@ -1,7 +1,10 @@
 //! Paging-related types for CQL queries.
 
+use std::fmt::Write as _;
 use std::{ops::ControlFlow, sync::Arc};
 
+use crate::frame::types;
+
 /// A response containing the paging state of a paged query,
 /// i.e. whether there are more pages to fetch or not, and if so,
 /// what is the state to use for resuming the query from the next page.
@@ -85,6 +88,75 @@ impl PagingState {
     pub fn new_from_raw_bytes(raw_bytes: impl Into<Arc<[u8]>>) -> Self {
         Self(Some(raw_bytes.into()))
     }
+
+    /// Describes the paging state in a human-readable form, for diagnostic logging.
+    ///
+    /// The paging state is opaque to the driver and its format depends on the server.
+    /// If it follows the layout used by Cassandra (partition key, row mark and
+    /// counters of remaining rows), its fields are decoded. Otherwise, the raw
+    /// bytes are printed. The output format is unspecified and may change,
+    /// so it must not be parsed.
+    pub fn debug_describe(&self) -> String {
+        let Some(bytes) = self.0.as_deref() else {
+            return "PagingState(start)".to_owned();
+        };
+
+        match CassandraPagingState::decode(bytes) {
+            Some(state) => format!(
+                "PagingState {{ partition_key: {}, row_mark: {}, remaining: {}, remaining_in_partition: {} }}",
+                hex(state.partition_key),
+                hex(state.row_mark),
+                state.remaining,
+                state.remaining_in_partition,
+            ),
+            None => format!(
+                "PagingState {{ opaque ({} bytes): {} }}",
+                bytes.len(),
+                hex(bytes)
+            ),
+        }
+    }
+}
+
+/// Paging state in the layout used by Cassandra (protocol v4 and later).
+struct CassandraPagingState<'a> {
+    partition_key: &'a [u8],
+    row_mark: &'a [u8],
+    remaining: u64,
+    remaining_in_partition: u64,
+}
+
+impl<'a> CassandraPagingState<'a> {
+    /// Returns `None` unless the whole buffer is consumed by the expected fields.
+    fn decode(mut buf: &'a [u8]) -> Option<Self> {
+        fn read_vint_bytes<'a>(buf: &mut &'a [u8]) -> Option<&'a [u8]> {
+            let len = usize::try_from(types::unsigned_vint_decode(buf).ok()?).ok()?;
+            let (bytes, rest) = buf.split_at_checked(len)?;
+            *buf = rest;
+            Some(bytes)
+        }
+
+        let partition_key = read_vint_bytes(&mut buf)?;
+        let row_mark = read_vint_bytes(&mut buf)?;
+        let remaining = types::unsigned_vint_decode(&mut buf).ok()?;
+        let remaining_in_partition = types::unsigned_vint_decode(&mut buf).ok()?;
+
+        buf.is_empty().then_some(Self {
+            partition_key,
+            row_mark,
+            remaining,
+            remaining_in_partition,
+        })
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(2 + 2 * bytes.len());
+    out.push_str("0x");
+    for byte in bytes {
+        let _ = write!(out, "{byte:02x}");
+    }
+    out
 }
 
 impl Default for PagingState {
@@ -92,3 +164,48 @@ impl Default for PagingState {
         Self::start()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PagingState;
+    use crate::frame::types;
+
+    #[test]
+    fn debug_describe_decodes_cassandra_layout() {
+        assert_eq!(PagingState::start().debug_describe(), "PagingState(start)");
+
+        let mut raw = Vec::new();
+        types::unsigned_vint_encode(2, &mut raw);
+        raw.extend_from_slice(&[0xab, 0xcd]);
+        types::unsigned_vint_encode(1, &mut raw);
+        raw.push(0x01);
+        types::unsigned_vint_encode(300, &mut raw);
+        types::unsigned_vint_encode(7, &mut raw);
+        assert_eq!(
+            PagingState::new_from_raw_bytes(raw).debug_describe(),
+            "PagingState { partition_key: 0xabcd, row_mark: 0x01, remaining: 300, remaining_in_partition: 7 }"
+        );
+    }
+
+    #[test]
+    fn debug_describe_falls_back_to_raw_bytes() {
+        // Length of the partition key exceeds the buffer.
+        let state = PagingState::new_from_raw_bytes(vec![0x7f, 0x00]);
+        assert_eq!(
+            state.debug_describe(),
+            "PagingState { opaque (2 bytes): 0x7f00 }"
+        );
+
+        // Trailing bytes after the expected fields.
+        let state = PagingState::new_from_raw_bytes(vec![0x00, 0x00, 0x00, 0x00, 0xff]);
+        assert_eq!(
+            state.debug_describe(),
+            "PagingState { opaque (5 bytes): 0x00000000ff }"
+        );
+
+        assert_eq!(
+            PagingState::new_from_raw_bytes(Vec::new()).debug_describe(),
+            "PagingState { opaque (0 bytes): 0x }"
+        );
+    }
+}
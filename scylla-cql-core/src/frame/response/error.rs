@@ -311,6 +311,12 @@ pub enum DbError {
     IsBootstrapping,
 
     /// Error during truncate operation
+    ///
+    /// The coordinator failed to truncate the table, e.g. because some replicas did not
+    /// respond in time. TRUNCATE is idempotent, so the error is transient and can be retried,
+    /// also on another node. The built-in retry policies of the `scylla` crate retry it on
+    /// another node if the statement is idempotent. This can be overridden per execution
+    /// profile with its `RetryErrorKind::TruncateError` retry decision.
     #[error("Error during truncate operation")]
     TruncateError,
 
@@ -393,7 +399,9 @@ pub enum DbError {
         statement_id: Bytes,
     },
 
-    /// Internal server error. This indicates a server-side bug
+    /// Internal server error. This indicates a server-side bug.
+    /// The exception which caused it can be parsed from the reason
+    /// with [`ServerErrorDetails::parse`].
     #[error("Internal server error. This indicates a server-side bug")]
     ServerError,
 
@@ -470,7 +478,7 @@ impl DbError {
 
             // Not sure when these can appear - don't ignore them
             // TODO: Investigate these errors
-            DbError::ConfigError | DbError::TruncateError => false,
+            DbError::ConfigError => false,
 
             // Errors that we can ignore and perform a retry on some other node
             DbError::Unavailable { .. }
@@ -483,14 +491,70 @@ impl DbError {
             // Preparation may succeed on some other node.
             | DbError::Unprepared { .. }
             | DbError::ServerError
+            // Truncation may succeed on retry, once the replicas respond.
+            | DbError::TruncateError
             | DbError::RateLimitReached { .. } => true,
         }
     }
 }
 
+/// Details of an internal server error, parsed from the reason of a [`DbError::ServerError`].
+///
+/// The protocol carries no structured data for server errors, only a message.
+/// However, servers put the exception that caused the error into that message in a known format:
+/// - ScyllaDB: `<exception type> (<message>)`, e.g. `std::runtime_error (something failed)`,
+/// - Cassandra: `<exception class>: <message>`, e.g. `java.lang.RuntimeException: something failed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ServerErrorDetails<'a> {
+    /// Type of the exception which caused the error on the server.
+    pub exception_type: &'a str,
+
+    /// Message of the exception.
+    pub message: &'a str,
+}
+
+impl<'a> ServerErrorDetails<'a> {
+    /// Parses the reason of a [`DbError::ServerError`].
+    /// Returns `None` if the reason is not in any of the known formats.
+    pub fn parse(reason: &'a str) -> Option<Self> {
+        let reason = reason.trim();
+        let is_exception_type = |name: &str| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '_' | ':' | '.' | '$' | '<' | '>'))
+        };
+
+        // ScyllaDB: `<exception type> (<message>)`
+        if let Some((exception_type, rest)) = reason.split_once(" (")
+            && let Some(message) = rest.strip_suffix(')')
+            && is_exception_type(exception_type)
+        {
+            return Some(Self {
+                exception_type,
+                message,
+            });
+        }
+
+        // Cassandra: `<exception class>: <message>`
+        if let Some((exception_type, message)) = reason.split_once(": ")
+            && exception_type.contains('.')
+            && is_exception_type(exception_type)
+        {
+            return Some(Self {
+                exception_type,
+                message,
+            });
+        }
+
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{DbError, Error, OperationType, WriteType};
+    use super::{DbError, Error, OperationType, ServerErrorDetails, WriteType};
     use crate::frame::protocol_features::ProtocolFeatures;
     use crate::frame::types::Consistency;
     use bytes::Bytes;
@@ -536,6 +600,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn deserialize_truncate_error() {
+        let features = ProtocolFeatures::default();
+        let bytes = make_error_request_bytes(0x1003, "Error during truncate: timed out");
+        let error: Error = Error::deserialize(&features, &mut bytes.as_slice()).unwrap();
+
+        assert_eq!(error.error, DbError::TruncateError);
+        assert_eq!(error.reason, "Error during truncate: timed out");
+        assert_eq!(error.error.code(&features), 0x1003);
+        assert!(error.error.can_speculative_retry());
+    }
+
+    #[test]
+    fn parse_server_error_details() {
+        let features = ProtocolFeatures::default();
+
+        let bytes = make_error_request_bytes(
+            0x0000,
+            "std::runtime_error (Failed to apply mutation: (system.local) no such column)",
+        );
+        let error: Error = Error::deserialize(&features, &mut bytes.as_slice()).unwrap();
+        assert_eq!(error.error, DbError::ServerError);
+        assert_eq!(
+            ServerErrorDetails::parse(&error.reason),
+            Some(ServerErrorDetails {
+                exception_type: "std::runtime_error",
+                message: "Failed to apply mutation: (system.local) no such column",
+            })
+        );
+
+        assert_eq!(
+            ServerErrorDetails::parse("java.lang.AssertionError: Unknown keyspace ks"),
+            Some(ServerErrorDetails {
+                exception_type: "java.lang.AssertionError",
+                message: "Unknown keyspace ks",
+            })
+        );
+
+        assert_eq!(ServerErrorDetails::parse("Something went wrong"), None);
+        assert_eq!(
+            ServerErrorDetails::parse("Failed: something went wrong"),
+            None
+        );
+    }
+
     #[test]
     fn deserialize_unavailable() {
         let features = ProtocolFeatures::default();
@@ -92,6 +92,67 @@ mod row {
     /// }
     /// ```
     fn _test_struct_deserialization_rename_collision_with_another_rename() {}
+
+    /// ```compile_fail
+    ///
+    /// #[derive(scylla_macros::DeserializeRow)]
+    /// #[scylla(crate = scylla_cql_core, flavor = "enforce_order")]
+    /// struct TestRow {
+    ///     a: i32,
+    ///     #[scylla(default)]
+    ///     b: String,
+    /// }
+    /// ```
+    fn _test_struct_deserialization_default_requires_match_by_name() {}
+
+    /// ```compile_fail
+    ///
+    /// #[derive(scylla_macros::DeserializeRow)]
+    /// #[scylla(crate = scylla_cql_core, case_insensitive)]
+    /// struct TestRow {
+    ///     #[scylla(rename = "A")]
+    ///     a: i32,
+    ///     #[scylla(rename = "a")]
+    ///     b: String,
+    /// }
+    /// ```
+    fn _test_struct_deserialization_case_insensitive_rename_collision() {}
+
+    /// ```compile_fail
+    ///
+    /// #[derive(scylla_macros::DeserializeRow)]
+    /// #[scylla(crate = scylla_cql_core, flavor = "enforce_order", skip_name_checks, case_insensitive)]
+    /// struct TestRow {
+    ///     a: i32,
+    /// }
+    /// ```
+    fn _test_struct_deserialization_case_insensitive_conflicts_with_skip_name_checks() {}
+
+    /// ```compile_fail
+    ///
+    /// #[derive(scylla_macros::DeserializeRow)]
+    /// #[scylla(crate = scylla_cql_core)]
+    /// struct TestRow {
+    ///     a: i32,
+    ///     #[scylla(raw_row, rename = "b")]
+    ///     raw: bytes::Bytes,
+    /// }
+    /// ```
+    fn _test_struct_deserialization_raw_row_conflicts_with_column_attrs() {}
+
+    /// ```compile_fail
+    ///
+    /// #[derive(scylla_macros::DeserializeRow)]
+    /// #[scylla(crate = scylla_cql_core)]
+    /// struct TestRow {
+    ///     a: i32,
+    ///     #[scylla(raw_row)]
+    ///     raw: bytes::Bytes,
+    ///     #[scylla(raw_row)]
+    ///     raw_again: bytes::Bytes,
+    /// }
+    /// ```
+    fn _test_struct_deserialization_multiple_raw_row_fields() {}
 }
 
 mod value {
@@ -263,4 +324,66 @@ mod value {
     /// }
     /// ```
     fn _test_deserialization_udt_unordered_flavour_no_limitations_on_allow_missing() {}
+
+    /// ```compile_fail
+    ///
+    /// #[derive(scylla_macros::SerializeValue)]
+    /// #[scylla(crate = scylla_cql_core, transparent)]
+    /// struct TestNewtype(Vec<u8>, i32);
+    /// ```
+    fn _test_serialization_transparent_requires_single_field() {}
+
+    /// ```compile_fail
+    ///
+    /// #[derive(scylla_macros::DeserializeValue)]
+    /// #[scylla(crate = scylla_cql_core, transparent)]
+    /// struct TestNewtype {
+    ///     a: Vec<u8>,
+    ///     b: i32,
+    /// }
+    /// ```
+    fn _test_deserialization_transparent_requires_single_field() {}
+
+    /// ```compile_fail
+    ///
+    /// #[derive(scylla_macros::SerializeValue)]
+    /// #[scylla(crate = scylla_cql_core, transparent, flavor = "enforce_order")]
+    /// struct TestNewtype(Vec<u8>);
+    /// ```
+    fn _test_serialization_transparent_rejects_flavor() {}
+
+    /// ```compile_fail
+    ///
+    /// #[derive(scylla_macros::DeserializeValue)]
+    /// #[scylla(crate = scylla_cql_core, transparent, flavor = "enforce_order")]
+    /// struct TestNewtype(Vec<u8>);
+    /// ```
+    fn _test_deserialization_transparent_rejects_flavor() {}
+
+    /// ```
+    /// #[derive(scylla_macros::SerializeValue, scylla_macros::DeserializeValue)]
+    /// #[scylla(crate = scylla_cql_core, transparent)]
+    /// struct TestNewtype<'a>(&'a [u8]);
+    /// ```
+    fn _test_transparent_newtype_with_lifetime_is_accepted() {}
+
+    /// ```compile_fail
+    ///
+    /// #[derive(scylla_macros::DeserializeValue)]
+    /// #[scylla(crate = scylla_cql_core)]
+    /// enum TestEnum {
+    ///     A(i32),
+    /// }
+    /// ```
+    fn _test_deserialization_enum_requires_tag() {}
+
+    /// ```compile_fail
+    ///
+    /// #[derive(scylla_macros::DeserializeValue)]
+    /// #[scylla(crate = scylla_cql_core, tag = "kind")]
+    /// enum TestEnum {
+    ///     A { a: i32 },
+    /// }
+    /// ```
+    fn _test_deserialization_tagged_enum_requires_newtype_variants() {}
 }
@@ -255,6 +255,52 @@ mod derive_macros_integration {
             assert_eq!(deserialized_udt, expected_deserialized_udt);
         }
 
+        #[test]
+        fn derive_serialize_and_deserialize_value_transparent_blob_newtype() {
+            #[derive(
+                scylla_macros::DeserializeValue, scylla_macros::SerializeValue, PartialEq, Eq, Debug,
+            )]
+            #[scylla(crate = "crate", transparent)]
+            struct Sig(Vec<u8>);
+
+            #[derive(scylla_macros::DeserializeValue, PartialEq, Eq, Debug)]
+            #[scylla(crate = "crate", transparent)]
+            struct BorrowedSig<'a> {
+                bytes: &'a [u8],
+            }
+
+            let typ = ColumnType::Native(NativeType::Blob);
+            let sig = Sig(vec![0xde, 0xad, 0xbe, 0xef]);
+
+            // Serialized exactly like the inner `Vec<u8>`.
+            let serialized = Bytes::from(do_serialize(&sig, &typ));
+            assert_eq!(serialized, Bytes::from(do_serialize(&sig.0, &typ)));
+
+            let deserialized = deserialize::<Sig>(&typ, &serialized).unwrap();
+            assert_eq!(deserialized, sig);
+
+            let borrowed = deserialize::<BorrowedSig<'_>>(&typ, &serialized).unwrap();
+            assert_eq!(borrowed.bytes, sig.0.as_slice());
+
+            // Type checks are delegated to the inner type, but report the newtype's name.
+            let err =
+                do_serialize_result(Sig(sig.0.clone()), &ColumnType::Native(NativeType::Text))
+                    .unwrap_err();
+            let err = err
+                .downcast_ref::<crate::serialize::value::BuiltinTypeCheckError>()
+                .unwrap();
+            assert_eq!(err.rust_name, std::any::type_name::<Sig>());
+
+            let err = <Sig as DeserializeValue<'_, '_>>::type_check(&ColumnType::Native(
+                NativeType::Text,
+            ))
+            .unwrap_err();
+            let err = err
+                .downcast_ref::<crate::deserialize::value::BuiltinTypeCheckError>()
+                .unwrap();
+            assert_eq!(err.rust_name, std::any::type_name::<Sig>());
+        }
+
         mod serialize {
             use std::sync::Arc;
 
@@ -1690,6 +1736,43 @@ mod derive_macros_integration {
                 }
             }
 
+            #[test]
+            fn test_udt_borrowed_fields_point_into_frame() {
+                #[derive(scylla_macros::DeserializeValue, PartialEq, Eq, Debug)]
+                #[scylla(crate = "crate")]
+                struct Udt<'a> {
+                    a: &'a str,
+                    b: Option<&'a [u8]>,
+                    c: i32,
+                }
+
+                let udt_bytes = UdtSerializer::new()
+                    .field("The quick brown fox".as_bytes())
+                    .field(&[1, 2, 3])
+                    .field(&42_i32.to_be_bytes())
+                    .finalize();
+                let typ = udt_def_with_fields([
+                    ("a", ColumnType::Native(NativeType::Text)),
+                    ("b", ColumnType::Native(NativeType::Blob)),
+                    ("c", ColumnType::Native(NativeType::Int)),
+                ]);
+
+                let udt = deserialize::<Udt<'_>>(&typ, &udt_bytes).unwrap();
+                assert_eq!(
+                    udt,
+                    Udt {
+                        a: "The quick brown fox",
+                        b: Some(&[1, 2, 3]),
+                        c: 42,
+                    }
+                );
+
+                // Borrowed fields are not copied, they point into the frame.
+                let frame = udt_bytes.as_ptr_range();
+                assert!(frame.contains(&udt.a.as_ptr()));
+                assert!(frame.contains(&udt.b.unwrap().as_ptr()));
+            }
+
             #[test]
             fn test_udt_cross_rename_fields() {
                 #[derive(scylla_macros::DeserializeValue, PartialEq, Eq, Debug)]
@@ -2147,6 +2230,141 @@ mod derive_macros_integration {
                 }
             }
 
+            #[test]
+            fn test_udt_tagged_enum() {
+                #[derive(scylla_macros::DeserializeValue, PartialEq, Debug)]
+                #[scylla(crate = crate)]
+                struct Circle {
+                    radius: f64,
+                }
+
+                #[derive(scylla_macros::DeserializeValue, PartialEq, Debug)]
+                #[scylla(crate = crate)]
+                struct Square<'a> {
+                    side: f64,
+                    label: &'a str,
+                }
+
+                #[derive(scylla_macros::DeserializeValue, PartialEq, Debug)]
+                #[scylla(crate = crate, tag = "kind")]
+                enum Shape<'a> {
+                    #[scylla(rename = "circle")]
+                    Circle(Circle),
+                    Square(Square<'a>),
+                }
+
+                let typ = udt_def_with_fields([
+                    ("kind", ColumnType::Native(NativeType::Text)),
+                    ("radius", ColumnType::Native(NativeType::Double)),
+                    ("side", ColumnType::Native(NativeType::Double)),
+                    ("label", ColumnType::Native(NativeType::Text)),
+                ]);
+
+                // Variant renamed with <rename>
+                {
+                    let udt = UdtSerializer::new()
+                        .field(b"circle")
+                        .field(&1.5_f64.to_be_bytes())
+                        .null_field()
+                        .null_field()
+                        .finalize();
+                    let shape = deserialize::<Shape>(&typ, &udt).unwrap();
+                    assert_eq!(shape, Shape::Circle(Circle { radius: 1.5 }));
+                }
+
+                // Variant matched by its name, borrowing from the frame
+                {
+                    let udt = UdtSerializer::new()
+                        .field(b"Square")
+                        .null_field()
+                        .field(&2.0_f64.to_be_bytes())
+                        .field(b"big")
+                        .finalize();
+                    let shape = deserialize::<Shape>(&typ, &udt).unwrap();
+                    assert_eq!(
+                        shape,
+                        Shape::Square(Square {
+                            side: 2.0,
+                            label: "big"
+                        })
+                    );
+                }
+
+                // Unknown tag value
+                {
+                    let udt = UdtSerializer::new()
+                        .field(b"triangle")
+                        .null_field()
+                        .null_field()
+                        .null_field()
+                        .finalize();
+                    let err = deserialize::<Shape>(&typ, &udt).unwrap_err();
+                    let err = get_deser_err(&err);
+                    assert_eq!(err.rust_name, std::any::type_name::<Shape>());
+                    let BuiltinDeserializationErrorKind::UdtError(
+                        UdtDeserializationErrorKind::UnknownTag {
+                            tag_field,
+                            tag_value,
+                        },
+                    ) = &err.kind
+                    else {
+                        panic!("unexpected error kind: {:?}", err.kind)
+                    };
+                    assert_eq!(tag_field, "kind");
+                    assert_eq!(tag_value, "triangle");
+                }
+
+                // Null tag
+                {
+                    let udt = UdtSerializer::new()
+                        .null_field()
+                        .field(&1.5_f64.to_be_bytes())
+                        .null_field()
+                        .null_field()
+                        .finalize();
+                    let err = deserialize::<Shape>(&typ, &udt).unwrap_err();
+                    let err = get_deser_err(&err);
+                    let BuiltinDeserializationErrorKind::UdtError(
+                        UdtDeserializationErrorKind::FieldDeserializationFailed {
+                            field_name, ..
+                        },
+                    ) = &err.kind
+                    else {
+                        panic!("unexpected error kind: {:?}", err.kind)
+                    };
+                    assert_eq!(field_name, "kind");
+                }
+
+                // Missing tag field
+                {
+                    let typ = udt_def_with_fields([
+                        ("radius", ColumnType::Native(NativeType::Double)),
+                        ("side", ColumnType::Native(NativeType::Double)),
+                        ("label", ColumnType::Native(NativeType::Text)),
+                    ]);
+                    let err = <Shape as DeserializeValue>::type_check(&typ).unwrap_err();
+                    let err = get_typeck_err_inner(&err);
+                    assert_eq!(err.rust_name, std::any::type_name::<Shape>());
+                    assert_matches!(
+                        &err.kind,
+                        BuiltinTypeCheckErrorKind::UdtError(
+                            UdtTypeCheckErrorKind::ValuesMissingForUdtFields { field_names }
+                        ) if field_names == &["kind"]
+                    );
+                }
+
+                // A variant does not fit the UDT
+                {
+                    let typ = udt_def_with_fields([
+                        ("kind", ColumnType::Native(NativeType::Text)),
+                        ("radius", ColumnType::Native(NativeType::Double)),
+                    ]);
+                    let err = <Shape as DeserializeValue>::type_check(&typ).unwrap_err();
+                    let err = get_typeck_err_inner(&err);
+                    assert_eq!(err.rust_name, std::any::type_name::<Shape>());
+                }
+            }
+
             #[test]
             fn metadata_does_not_bound_deserialized_values() {
                 /* This test covers the UDT part of the lifetime check.
@@ -2338,6 +2556,7 @@ mod derive_macros_integration {
             };
             use crate::serialize::value::SerializeValue;
             use crate::serialize::writers::RowWriter;
+            use crate::value::MaybeUnset;
 
             use super::do_serialize;
 
@@ -2617,6 +2836,71 @@ mod derive_macros_integration {
                 );
             }
 
+            #[derive(SerializeRow, Debug)]
+            #[scylla(crate = crate)]
+            struct TestRowWithBorrowedOptionals<'a> {
+                a: Option<&'a str>,
+                b: Option<&'a str>,
+                #[scylla(unset_when_none)]
+                c: Option<&'a str>,
+                #[scylla(unset_when_none)]
+                d: Option<&'a str>,
+            }
+
+            #[derive(SerializeRow, Debug)]
+            #[scylla(crate = crate, flavor = "enforce_order")]
+            struct TestRowWithBorrowedOptionalsAndEnforceOrder<'a> {
+                a: Option<&'a str>,
+                b: Option<&'a str>,
+                #[scylla(unset_when_none)]
+                c: Option<&'a str>,
+                #[scylla(unset_when_none)]
+                d: Option<&'a str>,
+            }
+
+            #[test]
+            fn test_row_serialization_with_borrowed_optionals() {
+                let spec = [
+                    spec("a", ColumnType::Native(NativeType::Text)),
+                    spec("b", ColumnType::Native(NativeType::Text)),
+                    spec("c", ColumnType::Native(NativeType::Text)),
+                    spec("d", ColumnType::Native(NativeType::Text)),
+                ];
+                let text = String::from("Ala ma kota");
+
+                // `None` is NULL, unless the field is marked as unset when none.
+                let reference = do_serialize(
+                    (
+                        Some(text.as_str()),
+                        None::<&str>,
+                        Some(text.as_str()),
+                        MaybeUnset::<&str>::Unset,
+                    ),
+                    &spec,
+                );
+                let row = do_serialize(
+                    TestRowWithBorrowedOptionals {
+                        a: Some(&text),
+                        b: None,
+                        c: Some(&text),
+                        d: None,
+                    },
+                    &spec,
+                );
+                assert_eq!(reference, row);
+
+                let row = do_serialize(
+                    TestRowWithBorrowedOptionalsAndEnforceOrder {
+                        a: Some(&text),
+                        b: None,
+                        c: Some(&text),
+                        d: None,
+                    },
+                    &spec,
+                );
+                assert_eq!(reference, row);
+            }
+
             #[derive(SerializeRow, Debug)]
             #[scylla(crate = crate)]
             struct TestRowWithColumnRename {
@@ -3123,6 +3407,126 @@ mod derive_macros_integration {
                 MyRow::type_check(specs).unwrap_err();
             }
 
+            #[test]
+            fn test_struct_deserialization_raw_row() {
+                #[derive(DeserializeRow, PartialEq, Eq, Debug)]
+                #[scylla(crate = "crate")]
+                struct MyRow {
+                    a: String,
+                    #[scylla(raw_row)]
+                    raw: Bytes,
+                    b: i32,
+                }
+
+                #[derive(DeserializeRow, PartialEq, Eq, Debug)]
+                #[scylla(crate = "crate", flavor = "enforce_order")]
+                struct MyOrderedRow {
+                    #[scylla(raw_row)]
+                    raw: Bytes,
+                    a: String,
+                    b: i32,
+                }
+
+                let specs = &[
+                    spec("a", ColumnType::Native(NativeType::Text)),
+                    spec("b", ColumnType::Native(NativeType::Int)),
+                ];
+                let row_bytes = serialize_cells([val_str("abc"), val_int(123)]);
+                // The frame continues after the row, e.g. with the next row.
+                let frame_bytes = [
+                    row_bytes.as_ref(),
+                    &serialize_cells([val_str("def"), val_int(456)]),
+                ]
+                .concat()
+                .into();
+
+                let row = deserialize::<MyRow>(specs, &frame_bytes).unwrap();
+                assert_eq!(
+                    row,
+                    MyRow {
+                        a: "abc".to_owned(),
+                        raw: row_bytes.clone(),
+                        b: 123,
+                    }
+                );
+
+                // The raw bytes round-trip: deserializing them again yields the same row.
+                let row_again = deserialize::<MyRow>(specs, &row.raw).unwrap();
+                assert_eq!(row_again, row);
+
+                let ordered_row = deserialize::<MyOrderedRow>(specs, &frame_bytes).unwrap();
+                assert_eq!(
+                    ordered_row,
+                    MyOrderedRow {
+                        raw: row_bytes,
+                        a: "abc".to_owned(),
+                        b: 123,
+                    }
+                );
+
+                // The raw row field does not correspond to a column.
+                let specs = &[
+                    spec("a", ColumnType::Native(NativeType::Text)),
+                    spec("raw", ColumnType::Native(NativeType::Blob)),
+                    spec("b", ColumnType::Native(NativeType::Int)),
+                ];
+                MyRow::type_check(specs).unwrap_err();
+            }
+
+            #[test]
+            fn test_struct_deserialization_default_when_missing() {
+                #[derive(DeserializeRow, PartialEq, Eq, Debug)]
+                #[scylla(crate = "crate")]
+                struct MyRow<'a> {
+                    a: &'a str,
+                    #[scylla(default)]
+                    b: Option<i32>,
+                    #[scylla(default)]
+                    c: String,
+                }
+
+                // All columns present
+                let specs = &[
+                    spec("a", ColumnType::Native(NativeType::Text)),
+                    spec("b", ColumnType::Native(NativeType::Int)),
+                    spec("c", ColumnType::Native(NativeType::Text)),
+                ];
+                let byts = serialize_cells([val_str("abc"), val_int(123), val_str("def")]);
+                let row = deserialize::<MyRow<'_>>(specs, &byts).unwrap();
+                assert_eq!(
+                    row,
+                    MyRow {
+                        a: "abc",
+                        b: Some(123),
+                        c: "def".to_owned(),
+                    }
+                );
+
+                // Columns with `default` missing, e.g. a result from an older schema version
+                let specs = &[spec("a", ColumnType::Native(NativeType::Text))];
+                let byts = serialize_cells([val_str("abc")]);
+                let row = deserialize::<MyRow<'_>>(specs, &byts).unwrap();
+                assert_eq!(
+                    row,
+                    MyRow {
+                        a: "abc",
+                        b: None,
+                        c: String::new(),
+                    }
+                );
+
+                // Missing column without `default`
+                let specs = &[spec("b", ColumnType::Native(NativeType::Int))];
+                MyRow::type_check(specs).unwrap_err();
+
+                // Column with `default` present, but of a wrong type
+                let specs = &[
+                    spec("a", ColumnType::Native(NativeType::Text)),
+                    spec("c", ColumnType::Native(NativeType::Int)),
+                ];
+                MyRow::type_check(specs).unwrap_err();
+            }
+
             #[test]
             fn test_struct_deserialization_strict_ordering() {
                 #[derive(DeserializeRow, PartialEq, Eq, Debug)]
@@ -3233,6 +3637,77 @@ mod derive_macros_integration {
                 );
             }
 
+            #[test]
+            fn test_struct_deserialization_case_insensitive() {
+                #[derive(DeserializeRow, PartialEq, Eq, Debug)]
+                #[scylla(crate = "crate", case_insensitive)]
+                struct MyRow<'a> {
+                    user_id: i32,
+                    #[scylla(rename = "Name")]
+                    name: &'a str,
+                }
+
+                #[derive(DeserializeRow, PartialEq, Eq, Debug)]
+                #[scylla(crate = "crate", flavor = "enforce_order", case_insensitive)]
+                struct MyOrderedRow<'a> {
+                    user_id: i32,
+                    name: &'a str,
+                }
+
+                // Uppercase columns, in any order
+                let specs = &[
+                    spec("NAME", ColumnType::Native(NativeType::Text)),
+                    spec("USER_ID", ColumnType::Native(NativeType::Int)),
+                ];
+                let byts = serialize_cells([val_str("abc"), val_int(123)]);
+                let row = deserialize::<MyRow<'_>>(specs, &byts).unwrap();
+                assert_eq!(
+                    row,
+                    MyRow {
+                        user_id: 123,
+                        name: "abc",
+                    }
+                );
+
+                let specs = &[
+                    spec("User_Id", ColumnType::Native(NativeType::Int)),
+                    spec("NAME", ColumnType::Native(NativeType::Text)),
+                ];
+                let byts = serialize_cells([val_int(123), val_str("abc")]);
+                let row = deserialize::<MyOrderedRow<'_>>(specs, &byts).unwrap();
+                assert_eq!(
+                    row,
+                    MyOrderedRow {
+                        user_id: 123,
+                        name: "abc",
+                    }
+                );
+
+                // Names must still match, apart from the case
+                let specs = &[
+                    spec("USER", ColumnType::Native(NativeType::Int)),
+                    spec("NAME", ColumnType::Native(NativeType::Text)),
+                ];
+                MyRow::type_check(specs).unwrap_err();
+                MyOrderedRow::type_check(specs).unwrap_err();
+
+                // Columns differing only in case are duplicates
+                let specs = &[
+                    spec("user_id", ColumnType::Native(NativeType::Int)),
+                    spec("USER_ID", ColumnType::Native(NativeType::Int)),
+                    spec("name", ColumnType::Native(NativeType::Text)),
+                ];
+                let err = MyRow::type_check(specs).unwrap_err();
+                let err = get_typeck_err_inner(&err);
+                assert_matches!(
+                    err.kind,
+                    BuiltinTypeCheckErrorKind::DuplicatedColumn {
+                        column_index: 1,
+                        column_name: "user_id",
+                    }
+                );
+            }
+
             #[test]
             fn test_struct_deserialization_cross_rename_fields() {
                 #[derive(scylla_macros::DeserializeRow, PartialEq, Eq, Debug)]
@@ -90,12 +90,7 @@ macro_rules! impl_serialize_row_for_unit {
             _writer: &mut RowWriter,
         ) -> Result<(), SerializationError> {
             if !ctx.columns().is_empty() {
-                return Err(mk_typck_err::<Self>(
-                    BuiltinTypeCheckErrorKind::WrongColumnCount {
-                        rust_cols: 0,
-                        cql_cols: ctx.columns().len(),
-                    },
-                ));
+                return Err(mk_typck_err::<Self>(wrong_column_count(0, ctx.columns())));
             }
             // Row is empty - do nothing
             Ok(())
@@ -116,6 +111,21 @@ impl SerializeRow for [u8; 0] {
     impl_serialize_row_for_unit!();
 }
 
+/// Creates a [`BuiltinTypeCheckErrorKind::ColumnCountMismatch`] for a Rust type
+/// which provides `rust_cols` values positionally for the given columns.
+fn wrong_column_count(rust_cols: usize, columns: &[ColumnSpec]) -> BuiltinTypeCheckErrorKind {
+    BuiltinTypeCheckErrorKind::ColumnCountMismatch {
+        rust_cols,
+        cql_cols: columns.len(),
+        missing_columns: columns
+            .iter()
+            .skip(rust_cols)
+            .map(|col| col.name().to_owned())
+            .collect(),
+        surplus_values: columns.len().min(rust_cols)..rust_cols,
+    }
+}
+
 /// Serializes a single value coming from type T into the writer
 ///
 /// `T` is not used for any sanity nor logical checks; it is only used when creating an
@@ -143,12 +153,10 @@ macro_rules! impl_serialize_row_for_slice {
             writer: &mut RowWriter,
         ) -> Result<(), SerializationError> {
             if ctx.columns().len() != self.len() {
-                return Err(mk_typck_err::<Self>(
-                    BuiltinTypeCheckErrorKind::WrongColumnCount {
-                        rust_cols: self.len(),
-                        cql_cols: ctx.columns().len(),
-                    },
-                ));
+                return Err(mk_typck_err::<Self>(wrong_column_count(
+                    self.len(),
+                    ctx.columns(),
+                )));
             }
             for (col, val) in ctx.columns().iter().zip(self.iter()) {
                 serialize_column::<Self>(val, col, writer)?;
@@ -282,10 +290,7 @@ macro_rules! impl_tuple {
                 let ($($tidents,)*) = match ctx.columns() {
                     [$($tidents),*] => ($($tidents,)*),
                     _ => return Err(mk_typck_err::<Self>(
-                        BuiltinTypeCheckErrorKind::WrongColumnCount {
-                            rust_cols: $length,
-                            cql_cols: ctx.columns().len(),
-                        },
+                        wrong_column_count($length, ctx.columns()),
                     )),
                 };
                 let ($($fidents,)*) = self;
@@ -392,6 +397,13 @@ fn mk_ser_err_named(
 #[non_exhaustive]
 pub enum BuiltinTypeCheckErrorKind {
     /// The Rust type provides `rust_cols` columns, but the statement operates on `cql_cols`.
+    ///
+    /// The built-in implementations report [`BuiltinTypeCheckErrorKind::ColumnCountMismatch`]
+    /// instead, which also names the values that don't match any column.
+    #[deprecated(
+        since = "1.8.0",
+        note = "No longer reported by the built-in implementations. Use `ColumnCountMismatch` instead."
+    )]
     WrongColumnCount {
         /// The number of values that the Rust type provides.
         rust_cols: usize,
@@ -400,6 +412,24 @@ pub enum BuiltinTypeCheckErrorKind {
         cql_cols: usize,
     },
 
+    /// The Rust type provides `rust_cols` values positionally, but the statement operates
+    /// on `cql_cols` columns.
+    ColumnCountMismatch {
+        /// The number of values that the Rust type provides.
+        rust_cols: usize,
+
+        /// The number of columns that the statement operates on.
+        cql_cols: usize,
+
+        /// Names of the columns for which the Rust type doesn't provide a value,
+        /// in the order of bind markers. Empty if the Rust type provides too many values.
+        missing_columns: Vec<String>,
+
+        /// Positions of the values provided by the Rust type which don't correspond
+        /// to any column. Empty if the Rust type provides too few values.
+        surplus_values: std::ops::Range<usize>,
+    },
+
     /// The Rust type provides a value for some column, but that column is not
     /// present in the statement.
     NoColumnWithName {
@@ -427,6 +457,7 @@ pub enum BuiltinTypeCheckErrorKind {
 impl Display for BuiltinTypeCheckErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            #[expect(deprecated)]
             BuiltinTypeCheckErrorKind::WrongColumnCount {
                 rust_cols,
                 cql_cols,
@@ -436,6 +467,32 @@ impl Display for BuiltinTypeCheckErrorKind {
                     "wrong column count: the statement operates on {cql_cols} columns, but the given rust type provides {rust_cols}"
                 )
             }
+            BuiltinTypeCheckErrorKind::ColumnCountMismatch {
+                rust_cols,
+                cql_cols,
+                missing_columns,
+                surplus_values,
+            } => {
+                write!(
+                    f,
+                    "wrong column count: the statement operates on {cql_cols} columns, but the given rust type provides {rust_cols}"
+                )?;
+                if !missing_columns.is_empty() {
+                    write!(
+                        f,
+                        "; missing values for columns: {}",
+                        missing_columns.join(", ")
+                    )?;
+                }
+                if !surplus_values.is_empty() {
+                    write!(
+                        f,
+                        "; no columns for values at positions {}..{}",
+                        surplus_values.start, surplus_values.end
+                    )?;
+                }
+                Ok(())
+            }
             BuiltinTypeCheckErrorKind::NoColumnWithName { name } => {
                 write!(
                     f,
@@ -585,6 +642,29 @@ impl SerializedValues {
         buf.put(self.serialized_values.as_slice())
     }
 
+    /// Returns the values exactly as they are sent in a request, for debugging purposes.
+    ///
+    /// The dump contains the bytes written by [SerializedValues::write_to_request]
+    /// and the length of each value.
+    pub fn debug_dump(&self) -> SerializedValuesDump {
+        let mut wire_bytes = Vec::with_capacity(2 + self.buffer_size());
+        self.write_to_request(&mut wire_bytes);
+        let value_lengths = self
+            .iter()
+            .map(|value| match value {
+                RawValue::Null => -1,
+                RawValue::Unset => -2,
+                // A value longer than i32::MAX can't be serialized.
+                RawValue::Value(bytes) => bytes.len() as i32,
+            })
+            .collect();
+
+        SerializedValuesDump {
+            wire_bytes,
+            value_lengths,
+        }
+    }
+
     // Gets the serialized values as raw bytes, without the preceding u16 length.
     pub(crate) fn get_contents(&self) -> &[u8] {
         &self.serialized_values
@@ -643,6 +723,45 @@ impl Default for SerializedValues {
     }
 }
 
+/// The serialized values of a request, dumped with [SerializedValues::debug_dump].
+///
+/// Its [Display] implementation prints the length and the bytes of each value in hex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SerializedValuesDump {
+    /// The bytes sent in a request, including the preceding `[short]` element count.
+    pub wire_bytes: Vec<u8>,
+
+    /// The length of each value, as written before its contents:
+    /// -1 for a null value and -2 for an unset one.
+    pub value_lengths: Vec<i32>,
+}
+
+impl Display for SerializedValuesDump {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} values", self.value_lengths.len())?;
+        // Skip the element count.
+        let mut contents = self.wire_bytes.get(2..).unwrap_or_default();
+        for (i, &len) in self.value_lengths.iter().enumerate() {
+            let value_len = usize::try_from(len).unwrap_or(0);
+            let (value, rest) = contents
+                .get(4..)
+                .map(|value| value.split_at(value_len.min(value.len())))
+                .unwrap_or_default();
+            contents = rest;
+            write!(f, "; #{i}: len {len}")?;
+            if len >= 0 {
+                write!(f, " [")?;
+                for byte in value {
+                    write!(f, "{byte:02x}")?;
+                }
+                write!(f, "]")?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// An iterator over raw values in some [`SerializedValues`].
 #[derive(Clone, Copy)]
 pub struct SerializedValuesIterator<'a> {
@@ -91,11 +91,13 @@ fn test_tuple_errors() {
     let err = get_typeck_err(&err);
     assert_eq!(err.rust_name, std::any::type_name::<()>());
     assert_matches!(
-        err.kind,
-        BuiltinTypeCheckErrorKind::WrongColumnCount {
+        &err.kind,
+        BuiltinTypeCheckErrorKind::ColumnCountMismatch {
             rust_cols: 0,
             cql_cols: 1,
-        }
+            missing_columns,
+            surplus_values,
+        } if missing_columns == &["a"] && surplus_values.is_empty()
     );
 
     // Non-unit tuple
@@ -109,11 +111,13 @@ fn test_tuple_errors() {
     let err = get_typeck_err(&err);
     assert_eq!(err.rust_name, std::any::type_name::<(&str,)>());
     assert_matches!(
-        err.kind,
-        BuiltinTypeCheckErrorKind::WrongColumnCount {
+        &err.kind,
+        BuiltinTypeCheckErrorKind::ColumnCountMismatch {
             rust_cols: 1,
             cql_cols: 2,
-        }
+            missing_columns,
+            surplus_values,
+        } if missing_columns == &["b"] && surplus_values.is_empty()
     );
 
     // Serialization of one of the element fails
@@ -145,11 +149,13 @@ fn test_slice_errors() {
     let err = get_typeck_err(&err);
     assert_eq!(err.rust_name, std::any::type_name::<Vec<&str>>());
     assert_matches!(
-        err.kind,
-        BuiltinTypeCheckErrorKind::WrongColumnCount {
+        &err.kind,
+        BuiltinTypeCheckErrorKind::ColumnCountMismatch {
             rust_cols: 1,
             cql_cols: 2,
-        }
+            missing_columns,
+            surplus_values,
+        } if missing_columns == &["b"] && surplus_values.is_empty()
     );
 
     // Serialization of one of the element fails
@@ -168,6 +174,41 @@ fn test_slice_errors() {
     assert_eq!(name, "b");
 }
 
+#[test]
+fn test_wrong_column_count_message() {
+    // Too few values - the columns without values are named.
+    let v = (1_i32,);
+    let spec = [
+        col("a", ColumnType::Native(NativeType::Int)),
+        col("b", ColumnType::Native(NativeType::Text)),
+        col("c", ColumnType::Native(NativeType::Text)),
+    ];
+    let err = do_serialize_err(v, &spec);
+    assert_eq!(
+        get_typeck_err(&err).kind.to_string(),
+        "wrong column count: the statement operates on 3 columns, but the given rust type provides 1; missing values for columns: b, c"
+    );
+
+    // Too many values - the positions of the values without columns are named.
+    let v = vec![1_i32, 2_i32, 3_i32];
+    let spec = [col("a", ColumnType::Native(NativeType::Int))];
+    let err = do_serialize_err(v, &spec);
+    let err = get_typeck_err(&err);
+    assert_matches!(
+        &err.kind,
+        BuiltinTypeCheckErrorKind::ColumnCountMismatch {
+            rust_cols: 3,
+            cql_cols: 1,
+            missing_columns,
+            surplus_values,
+        } if missing_columns.is_empty() && *surplus_values == (1..3)
+    );
+    assert_eq!(
+        err.kind.to_string(),
+        "wrong column count: the statement operates on 1 columns, but the given rust type provides 3; no columns for values at positions 1..3"
+    );
+}
+
 #[test]
 fn test_map_errors() {
     // Missing value for a bind marker
@@ -262,6 +303,50 @@ fn test_serialized_values_iter() {
     assert_eq!(iter.next(), None);
 }
 
+#[test]
+fn test_serialized_values_debug_dump() {
+    let mut values = SerializedValues::new();
+    values
+        .add_value(&1234i32, &ColumnType::Native(NativeType::Int))
+        .unwrap();
+    values
+        .add_value(&None::<i32>, &ColumnType::Native(NativeType::Int))
+        .unwrap();
+    values
+        .add_value(
+            &MaybeUnset::<i32>::Unset,
+            &ColumnType::Native(NativeType::Int),
+        )
+        .unwrap();
+    values
+        .add_value(&"ab", &ColumnType::Native(NativeType::Text))
+        .unwrap();
+
+    let dump = values.debug_dump();
+    assert_eq!(
+        dump.wire_bytes,
+        [
+            0, 4, // element count
+            0, 0, 0, 4, // size of int
+            0, 0, 4, 210, // content of int (1234)
+            255, 255, 255, 255, // null
+            255, 255, 255, 254, // unset
+            0, 0, 0, 2, // size of string
+            97, 98, // content of string ('ab')
+        ]
+    );
+    assert_eq!(dump.value_lengths, [4, -1, -2, 2]);
+
+    let mut wire_bytes = Vec::new();
+    values.write_to_request(&mut wire_bytes);
+    assert_eq!(dump.wire_bytes, wire_bytes);
+
+    assert_eq!(
+        dump.to_string(),
+        "4 values; #0: len 4 [000004d2]; #1: len -1; #2: len -2; #3: len 2 [6162]"
+    );
+}
+
 #[test]
 fn test_serialized_values_max_capacity() {
     let mut values = SerializedValues::new();
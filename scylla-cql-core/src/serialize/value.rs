@@ -18,12 +18,15 @@ use uuid::Uuid;
 use crate::frame::response::result::{CollectionType, ColumnType, NativeType};
 use crate::frame::types::{unsigned_vint_encode, vint_encode};
 use crate::value::{
-    Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
-    CqlTimeuuid, CqlValue, CqlVarint, CqlVarintBorrowed, Emptiable, MaybeEmpty, MaybeUnset, Unset,
+    Cidr, Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
+    CqlTimeuuid, CqlValue, CqlVarint, CqlVarintBorrowed, Emptiable, ListIter, MaybeEmpty,
+    MaybeUnset, Unset, WriteTime,
 };
 
+#[cfg(feature = "serde-json-1")]
+use crate::value::Json;
 #[cfg(feature = "chrono-04")]
-use crate::value::ValueOverflow;
+use crate::value::{ChronoTimestamp, ValueOverflow};
 
 use super::writers::WrittenCellProof;
 use super::{CellValueBuilder, CellWriter, SerializationError};
@@ -187,6 +190,16 @@ impl SerializeValue for chrono_04::DateTime<chrono_04::Utc> {
     });
 }
 #[cfg(feature = "chrono-04")]
+impl SerializeValue for ChronoTimestamp {
+    impl_serialize_via_writer!(|me, typ, writer| {
+        exact_type_check!(typ, Timestamp);
+        let cql_timestamp = me
+            .to_cql_timestamp()
+            .ok_or_else(|| mk_ser_err::<Self>(typ, BuiltinSerializationErrorKind::PrecisionLoss))?;
+        <CqlTimestamp as SerializeValue>::serialize(&cql_timestamp, typ, writer)?
+    });
+}
+#[cfg(feature = "chrono-04")]
 impl SerializeValue for chrono_04::NaiveTime {
     impl_serialize_via_writer!(|me, typ, writer| {
         exact_type_check!(typ, Time);
@@ -196,6 +209,16 @@ impl SerializeValue for chrono_04::NaiveTime {
         <CqlTime as SerializeValue>::serialize(&cql_time, typ, writer)?
     });
 }
+#[cfg(feature = "chrono-04")]
+impl SerializeValue for chrono_04::Duration {
+    impl_serialize_via_writer!(|me, typ, writer| {
+        exact_type_check!(typ, Duration);
+        let cql_duration = CqlDuration::try_from(*me).map_err(|_: ValueOverflow| {
+            mk_ser_err::<Self>(typ, BuiltinSerializationErrorKind::ValueOverflow)
+        })?;
+        <CqlDuration as SerializeValue>::serialize(&cql_duration, typ, writer)?
+    });
+}
 #[cfg(feature = "time-03")]
 impl SerializeValue for time_03::Date {
     impl_serialize_via_writer!(|me, typ, writer| {
@@ -358,6 +381,13 @@ impl SerializeValue for IpAddr {
         }
     });
 }
+impl SerializeValue for Cidr {
+    impl_serialize_via_writer!(|me, typ, writer| {
+        exact_type_check!(typ, Ascii, Text);
+        // The CIDR notation is at most a few dozen bytes long.
+        writer.set_value(me.to_string().as_bytes()).unwrap()
+    });
+}
 impl SerializeValue for String {
     impl_serialize_via_writer!(|me, typ, writer| {
         exact_type_check!(typ, Ascii, Text);
@@ -366,6 +396,21 @@ impl SerializeValue for String {
             .map_err(|_| mk_ser_err::<Self>(typ, BuiltinSerializationErrorKind::SizeOverflow))?
     });
 }
+#[cfg(feature = "serde-json-1")]
+impl<T: serde::Serialize> SerializeValue for Json<T> {
+    impl_serialize_via_writer!(|me, typ, writer| {
+        exact_type_check!(typ, Ascii, Text);
+        let json = serde_json_1::to_vec(&me.0).map_err(|err| {
+            mk_ser_err::<Self>(
+                typ,
+                BuiltinSerializationErrorKind::InvalidJson(Arc::new(err)),
+            )
+        })?;
+        writer
+            .set_value(&json)
+            .map_err(|_| mk_ser_err::<Self>(typ, BuiltinSerializationErrorKind::SizeOverflow))?
+    });
+}
 impl<T: SerializeValue> SerializeValue for Option<T> {
     fn serialize<'b>(
         &self,
@@ -389,6 +434,12 @@ impl SerializeValue for Counter {
         writer.set_value(me.0.to_be_bytes().as_slice()).unwrap()
     });
 }
+impl SerializeValue for WriteTime {
+    impl_serialize_via_writer!(|me, typ, writer| {
+        exact_type_check!(typ, BigInt);
+        writer.set_value(me.0.to_be_bytes().as_slice()).unwrap()
+    });
+}
 impl SerializeValue for CqlDuration {
     impl_serialize_via_writer!(|me, typ, writer| {
         exact_type_check!(typ, Duration);
@@ -610,6 +661,22 @@ impl<T: SerializeValue> SerializeValue for [T] {
         }
     }
 }
+impl<I> SerializeValue for ListIter<I>
+where
+    I: IntoIterator + Clone,
+    I::IntoIter: ExactSizeIterator,
+    I::Item: SerializeValue,
+{
+    fn serialize<'b>(
+        &self,
+        typ: &ColumnType,
+        writer: CellWriter<'b>,
+    ) -> Result<WrittenCellProof<'b>, SerializationError> {
+        let iter = self.0.clone().into_iter();
+        serialize_sequence(std::any::type_name::<Self>(), iter.len(), iter, typ, writer)
+    }
+}
+
 impl SerializeValue for CqlValue {
     fn serialize<'b>(
         &self,
@@ -705,7 +772,7 @@ fn serialize_cql_value<'b>(
     }
 }
 
-fn fix_rust_name_in_err<RustT>(mut err: SerializationError) -> SerializationError {
+pub(crate) fn fix_rust_name_in_err<RustT>(mut err: SerializationError) -> SerializationError {
     // The purpose of this function is to change the `rust_name` field
     // in the error to the given one. Most of the time, the `err` given to the
     // function here will be the sole owner of the data, so theoretically
@@ -929,10 +996,10 @@ impl_tuples!(
     16
 );
 
-fn serialize_sequence<'t, 'b, T: SerializeValue + 't>(
+fn serialize_sequence<'b, T: SerializeValue>(
     rust_name: &'static str,
     len: usize,
-    iter: impl Iterator<Item = &'t T>,
+    iter: impl Iterator<Item = T>,
     typ: &ColumnType,
     writer: CellWriter<'b>,
 ) -> Result<WrittenCellProof<'b>, SerializationError> {
@@ -965,14 +1032,28 @@ fn serialize_sequence<'t, 'b, T: SerializeValue + 't>(
     })?;
     builder.append_bytes(&element_count.to_be_bytes());
 
+    let mut actual = 0;
     for el in iter {
-        T::serialize(el, elt, builder.make_sub_writer()).map_err(|err| {
+        T::serialize(&el, elt, builder.make_sub_writer()).map_err(|err| {
             mk_ser_err_named(
                 rust_name,
                 typ,
                 SetOrListSerializationErrorKind::ElementSerializationFailed(err),
             )
         })?;
+        actual += 1;
+    }
+    // The count was written up front, so it must not be contradicted
+    // by an iterator reporting a wrong length.
+    if actual != len {
+        return Err(mk_ser_err_named(
+            rust_name,
+            typ,
+            SetOrListSerializationErrorKind::ElementCountMismatch {
+                expected: len,
+                actual,
+            },
+        ));
     }
 
     builder
@@ -1271,6 +1352,9 @@ pub enum BuiltinSerializationErrorKind {
     /// The Rust value is out of range supported by the CQL type.
     ValueOverflow,
 
+    /// The Rust value is more precise than the CQL type, and truncating it was not allowed.
+    PrecisionLoss,
+
     /// A serialization failure specific to a CQL set or list.
     SetOrListError(SetOrListSerializationErrorKind),
 
@@ -1285,6 +1369,10 @@ pub enum BuiltinSerializationErrorKind {
 
     /// A serialization failure specific to a CQL UDT.
     UdtError(UdtSerializationErrorKind),
+
+    /// The Rust value could not be represented as JSON.
+    #[cfg(feature = "serde-json-1")]
+    InvalidJson(Arc<serde_json_1::Error>),
 }
 
 impl From<SetOrListSerializationErrorKind> for BuiltinSerializationErrorKind {
@@ -1326,11 +1414,18 @@ impl Display for BuiltinSerializationErrorKind {
             BuiltinSerializationErrorKind::ValueOverflow => {
                 f.write_str("the Rust value is out of range supported by the CQL type")
             }
+            BuiltinSerializationErrorKind::PrecisionLoss => f.write_str(
+                "the Rust value is more precise than the CQL type, and truncating it is not allowed",
+            ),
             BuiltinSerializationErrorKind::SetOrListError(err) => err.fmt(f),
             BuiltinSerializationErrorKind::VectorError(err) => err.fmt(f),
             BuiltinSerializationErrorKind::MapError(err) => err.fmt(f),
             BuiltinSerializationErrorKind::TupleError(err) => err.fmt(f),
             BuiltinSerializationErrorKind::UdtError(err) => err.fmt(f),
+            #[cfg(feature = "serde-json-1")]
+            BuiltinSerializationErrorKind::InvalidJson(err) => {
+                write!(f, "the Rust value could not be serialized as JSON: {err}")
+            }
         }
     }
 }
@@ -1410,6 +1505,14 @@ pub enum SetOrListSerializationErrorKind {
 
     /// One of the elements of the set/list failed to serialize.
     ElementSerializationFailed(SerializationError),
+
+    /// The iterator yielded a different number of elements than its reported length.
+    ElementCountMismatch {
+        /// The reported length.
+        expected: usize,
+        /// The number of elements actually yielded.
+        actual: usize,
+    },
 }
 
 impl Display for SetOrListSerializationErrorKind {
@@ -1421,6 +1524,12 @@ impl Display for SetOrListSerializationErrorKind {
             SetOrListSerializationErrorKind::ElementSerializationFailed(err) => {
                 write!(f, "failed to serialize one of the elements: {err}")
             }
+            SetOrListSerializationErrorKind::ElementCountMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "the iterator yielded {actual} elements, but reported {expected}"
+                )
+            }
         }
     }
 }
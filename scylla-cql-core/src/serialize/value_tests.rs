@@ -8,8 +8,8 @@ use crate::serialize::value::{
 use crate::serialize::writers::WrittenCellProof;
 use crate::serialize::{CellWriter, SerializationError};
 use crate::value::{
-    Counter, CqlDate, CqlDuration, CqlTime, CqlTimestamp, CqlTimeuuid, CqlValue, CqlVarint,
-    Emptiable, MaybeEmpty, MaybeUnset, Unset,
+    Cidr, Counter, CqlDate, CqlDuration, CqlTime, CqlTimestamp, CqlTimeuuid, CqlValue, CqlVarint,
+    Emptiable, ListIter, MaybeEmpty, MaybeUnset, Unset,
 };
 
 use std::borrow::Cow;
@@ -318,6 +318,83 @@ fn test_set_or_list_errors() {
         .expect("CustomSerializationError");
 }
 
+#[test]
+fn test_list_iter_serialization() {
+    const LEN: usize = 100_000;
+    let typ = ColumnType::Collection {
+        frozen: false,
+        typ: CollectionType::List(Box::new(ColumnType::Native(NativeType::Int))),
+    };
+    let iter = ListIter((0..LEN as i32).map(|i| i * 2));
+
+    // The serialized list: its length, element count and the elements with their lengths.
+    let serialized_len = 4 + 4 + LEN * (4 + 4);
+    let mut data = Vec::with_capacity(serialized_len);
+    iter.serialize(&typ, CellWriter::new(&mut data)).unwrap();
+
+    // The elements are written directly to the buffer, which never needs to grow.
+    assert_eq!(data.len(), serialized_len);
+    assert_eq!(data.capacity(), serialized_len);
+    let expected = do_serialize((0..LEN as i32).map(|i| i * 2).collect::<Vec<_>>(), &typ);
+    assert_eq!(data, expected);
+
+    // Sets are supported as well.
+    let typ = ColumnType::Collection {
+        frozen: true,
+        typ: CollectionType::Set(Box::new(ColumnType::Native(NativeType::Text))),
+    };
+    assert_eq!(
+        do_serialize(ListIter(["a", "b"].iter()), &typ),
+        do_serialize(vec!["a", "b"], &typ),
+    );
+}
+
+#[test]
+fn test_list_iter_serialization_errors() {
+    // An iterator which yields fewer elements than it reports.
+    #[derive(Clone)]
+    struct Lying(std::ops::Range<i32>);
+
+    impl Iterator for Lying {
+        type Item = i32;
+
+        fn next(&mut self) -> Option<i32> {
+            self.0.next()
+        }
+    }
+
+    impl ExactSizeIterator for Lying {
+        fn len(&self) -> usize {
+            self.0.len() + 1
+        }
+    }
+
+    let typ = ColumnType::Collection {
+        frozen: false,
+        typ: CollectionType::List(Box::new(ColumnType::Native(NativeType::Int))),
+    };
+    let err = do_serialize_err(ListIter(Lying(0..3)), &typ);
+    let ser_err = get_ser_err(&err);
+    assert_eq!(ser_err.rust_name, std::any::type_name::<ListIter<Lying>>());
+    assert_matches!(
+        ser_err.kind,
+        BuiltinSerializationErrorKind::SetOrListError(
+            SetOrListSerializationErrorKind::ElementCountMismatch {
+                expected: 4,
+                actual: 3,
+            }
+        )
+    );
+
+    // Type check is performed as for other lists.
+    let err = do_serialize_err(ListIter(0..3), &ColumnType::Native(NativeType::Int));
+    let typeck_err = get_typeck_err(&err);
+    assert_matches!(
+        typeck_err.kind,
+        BuiltinTypeCheckErrorKind::SetOrListError(SetOrListTypeCheckErrorKind::NotSetOrList)
+    );
+}
+
 #[test]
 fn test_map_errors() {
     // Not a map
@@ -785,6 +862,25 @@ fn counter_serialization() {
     );
 }
 
+#[test]
+fn tuple_with_unset_element_serialization() {
+    let typ = ColumnType::Tuple(vec![
+        ColumnType::Native(NativeType::Int),
+        ColumnType::Native(NativeType::Int),
+        ColumnType::Native(NativeType::Int),
+    ]);
+    let v: (i32, MaybeUnset<i32>, Option<i32>) = (1, MaybeUnset::Unset, None);
+    assert_eq!(
+        do_serialize(v, &typ),
+        vec![
+            0, 0, 0, 16, // Length of the tuple
+            0, 0, 0, 4, 0, 0, 0, 1, // First element: 1
+            255, 255, 255, 254, // Second element: unset (encoded as -2)
+            255, 255, 255, 255, // Third element: null (encoded as -1)
+        ]
+    );
+}
+
 fn cql_varint_normalization_test_cases() -> [(Vec<u8>, Vec<u8>); 11] {
     [
         (vec![], vec![0x00]),                 // 0
@@ -962,6 +1058,23 @@ fn text_serialization() {
     );
 }
 
+#[test]
+fn cidr_serialization() {
+    let cidr = Cidr::from_str("10.0.0.0/8").unwrap();
+    let mut expected = vec![0, 0, 0, 10];
+    expected.extend_from_slice(b"10.0.0.0/8");
+    assert_eq!(
+        do_serialize(cidr, &ColumnType::Native(NativeType::Text)),
+        expected
+    );
+
+    let err = do_serialize_err(cidr, &ColumnType::Native(NativeType::Inet));
+    assert_matches!(
+        get_typeck_err(&err).kind,
+        BuiltinTypeCheckErrorKind::MismatchedType { .. }
+    );
+}
+
 #[test]
 fn u8_array_serialization() {
     let val = [1u8; 4];
@@ -1171,6 +1284,54 @@ fn naive_time_04_serialization() {
     )
 }
 
+#[cfg(feature = "chrono-04")]
+#[test]
+fn duration_04_serialization() {
+    use chrono_04::Duration;
+
+    let typ = ColumnType::Native(NativeType::Duration);
+    let test_cases = [
+        (
+            Duration::zero(),
+            CqlDuration {
+                months: 0,
+                days: 0,
+                nanoseconds: 0,
+            },
+        ),
+        (
+            Duration::try_days(3).unwrap() + Duration::nanoseconds(42),
+            CqlDuration {
+                months: 0,
+                days: 3,
+                nanoseconds: 42,
+            },
+        ),
+        (
+            -(Duration::try_days(2).unwrap() + Duration::try_hours(5).unwrap()),
+            CqlDuration {
+                months: 0,
+                days: -2,
+                nanoseconds: -5 * 3600 * 1_000_000_000,
+            },
+        ),
+    ];
+    for (duration, cql_duration) in test_cases {
+        assert_eq!(
+            do_serialize(duration, &typ),
+            do_serialize(cql_duration, &typ)
+        );
+    }
+
+    // The number of days must fit in an i32
+    let too_long = Duration::try_days(i32::MAX as i64 + 1).unwrap();
+    let err = do_serialize_err(too_long, &typ);
+    assert_matches!(
+        get_ser_err(&err).kind,
+        BuiltinSerializationErrorKind::ValueOverflow
+    );
+}
+
 #[cfg(feature = "time-03")]
 #[test]
 fn time_03_serialization() {
@@ -1268,6 +1429,41 @@ fn date_time_04_serialization() {
     }
 }
 
+#[cfg(feature = "chrono-04")]
+#[test]
+fn chrono_timestamp_sub_millisecond_precision() {
+    use crate::value::ChronoTimestamp;
+    use chrono_04::DateTime;
+
+    let typ = ColumnType::Native(NativeType::Timestamp);
+    let whole_millis = DateTime::from_timestamp(1, 2_000_000).unwrap();
+    let sub_millis = DateTime::from_timestamp(1, 2_000_001).unwrap();
+    let before_epoch = DateTime::from_timestamp(-1, 999_999_000).unwrap();
+
+    // Truncating mode behaves like the bare `DateTime<Utc>`.
+    for datetime in [whole_millis, sub_millis, before_epoch] {
+        assert_eq!(
+            do_serialize(ChronoTimestamp::truncating(datetime), &typ),
+            do_serialize(datetime, &typ)
+        );
+    }
+
+    // Strict mode accepts timestamps with millisecond precision...
+    assert_eq!(
+        do_serialize(ChronoTimestamp::strict(whole_millis), &typ),
+        do_serialize(whole_millis, &typ)
+    );
+
+    // ...and rejects more precise ones.
+    for datetime in [sub_millis, before_epoch] {
+        let err = do_serialize_err(ChronoTimestamp::strict(datetime), &typ);
+        let err = get_ser_err(&err);
+        assert_eq!(err.rust_name, std::any::type_name::<ChronoTimestamp>());
+        assert_eq!(err.got, typ);
+        assert_matches!(err.kind, BuiltinSerializationErrorKind::PrecisionLoss);
+    }
+}
+
 #[cfg(feature = "time-03")]
 #[test]
 fn offset_date_time_03_serialization() {
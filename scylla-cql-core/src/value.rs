@@ -119,6 +119,97 @@ pub enum MaybeEmpty<T: Emptiable> {
     Value(T),
 }
 
+/// Binds the items of an iterator as a CQL `list` or `set`, without collecting them first.
+///
+/// The number of items has to be known up front, because it precedes the items
+/// in the serialized form - hence the [`ExactSizeIterator`] requirement.
+/// The wrapped value is cloned to be iterated over each time it's serialized,
+/// so it should be cheap to clone, e.g. a range or an adapter over borrowed data.
+///
+/// ```
+/// # use scylla_cql_core::value::ListIter;
+/// let ids = ListIter((0..100_000).map(|i| i * 2));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ListIter<I>(pub I);
+
+/// A value that may be null or not.
+///
+/// This is equivalent to deserializing into `Option<T>`, but gives the null case
+/// a name of its own, which reads better in generic code handling many columns.
+/// It can be converted to and from `Option<T>` at no cost.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Maybe<T> {
+    /// Represents a null value.
+    Null,
+    /// Represents a non-null value.
+    Value(T),
+}
+
+impl<T> Maybe<T> {
+    /// Returns `true` if the value is null.
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Returns `true` if the value is not null.
+    #[inline]
+    pub fn is_value(&self) -> bool {
+        matches!(self, Self::Value(_))
+    }
+
+    /// Converts the value into an `Option<T>`, mapping null to `None`.
+    #[inline]
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Self::Null => None,
+            Self::Value(v) => Some(v),
+        }
+    }
+
+    /// Converts from `&Maybe<T>` to `Maybe<&T>`.
+    #[inline]
+    pub fn as_ref(&self) -> Maybe<&T> {
+        match self {
+            Self::Null => Maybe::Null,
+            Self::Value(v) => Maybe::Value(v),
+        }
+    }
+
+    /// Maps a non-null value with the given function, leaving null untouched.
+    #[inline]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Maybe<U> {
+        match self {
+            Self::Null => Maybe::Null,
+            Self::Value(v) => Maybe::Value(f(v)),
+        }
+    }
+
+    /// Returns the non-null value, or the given default if the value is null.
+    #[inline]
+    pub fn unwrap_or(self, default: T) -> T {
+        self.into_option().unwrap_or(default)
+    }
+}
+
+impl<T> From<Option<T>> for Maybe<T> {
+    #[inline]
+    fn from(opt: Option<T>) -> Self {
+        match opt {
+            Some(v) => Self::Value(v),
+            None => Self::Null,
+        }
+    }
+}
+
+impl<T> From<Maybe<T>> for Option<T> {
+    #[inline]
+    fn from(maybe: Maybe<T>) -> Self {
+        maybe.into_option()
+    }
+}
+
 /// Represents timeuuid (uuid V1) value
 ///
 /// This type has custom comparison logic which follows ScyllaDB/Cassandra semantics.
@@ -709,6 +800,15 @@ pub struct CqlTimestamp(pub i64);
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct CqlTime(pub i64);
 
+/// Write time of a column, as returned by `WRITETIME(column)`.
+///
+/// Represented as signed microseconds since unix epoch. Although it is sent
+/// as a CQL `bigint`, it is a distinct type so that it is not confused with
+/// plain integers or with millisecond-precision [`CqlTimestamp`]s.
+/// It can also be bound in `USING TIMESTAMP ?`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct WriteTime(pub i64);
+
 impl CqlDate {
     fn try_to_chrono_04_naive_date(&self) -> Result<chrono_04::NaiveDate, ValueOverflow> {
         let days_since_unix_epoch = self.0 as i64 - (1 << 31);
@@ -775,6 +875,85 @@ impl TryInto<chrono_04::DateTime<chrono_04::Utc>> for CqlTimestamp {
     }
 }
 
+#[cfg(feature = "chrono-04")]
+impl From<chrono_04::DateTime<chrono_04::Utc>> for WriteTime {
+    fn from(value: chrono_04::DateTime<chrono_04::Utc>) -> Self {
+        Self(value.timestamp_micros())
+    }
+}
+
+#[cfg(feature = "chrono-04")]
+impl TryInto<chrono_04::DateTime<chrono_04::Utc>> for WriteTime {
+    type Error = ValueOverflow;
+
+    fn try_into(self) -> Result<chrono_04::DateTime<chrono_04::Utc>, Self::Error> {
+        use chrono_04::TimeZone;
+        match chrono_04::Utc.timestamp_micros(self.0) {
+            chrono_04::LocalResult::Single(datetime) => Ok(datetime),
+            _ => Err(ValueOverflow),
+        }
+    }
+}
+
+/// Controls what happens to the sub-millisecond part of a timestamp
+/// serialized to a CQL `timestamp`, which has millisecond precision.
+#[cfg(feature = "chrono-04")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SubMillisecondPrecision {
+    /// The sub-millisecond part is silently dropped.
+    #[default]
+    Truncate,
+    /// Serialization fails if the timestamp has a non-zero sub-millisecond part.
+    Reject,
+}
+
+/// A `chrono::DateTime<Utc>` with explicit handling of sub-millisecond precision
+/// when serialized to a CQL `timestamp`.
+///
+/// A bare `DateTime<Utc>` is always serialized with [`SubMillisecondPrecision::Truncate`].
+/// Wrap it in `ChronoTimestamp` with [`SubMillisecondPrecision::Reject`] to be notified
+/// instead of losing precision.
+#[cfg(feature = "chrono-04")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ChronoTimestamp {
+    /// The timestamp to serialize.
+    pub datetime: chrono_04::DateTime<chrono_04::Utc>,
+    /// What to do with the sub-millisecond part of the timestamp.
+    pub sub_millisecond: SubMillisecondPrecision,
+}
+
+#[cfg(feature = "chrono-04")]
+impl ChronoTimestamp {
+    /// Wraps a timestamp whose sub-millisecond part is dropped during serialization.
+    pub fn truncating(datetime: chrono_04::DateTime<chrono_04::Utc>) -> Self {
+        Self {
+            datetime,
+            sub_millisecond: SubMillisecondPrecision::Truncate,
+        }
+    }
+
+    /// Wraps a timestamp whose serialization fails if it has a sub-millisecond part.
+    pub fn strict(datetime: chrono_04::DateTime<chrono_04::Utc>) -> Self {
+        Self {
+            datetime,
+            sub_millisecond: SubMillisecondPrecision::Reject,
+        }
+    }
+
+    /// Converts the timestamp to [`CqlTimestamp`], or returns `None` if it has
+    /// a sub-millisecond part which may not be truncated.
+    pub fn to_cql_timestamp(self) -> Option<CqlTimestamp> {
+        let has_sub_millisecond_part = !self
+            .datetime
+            .timestamp_subsec_nanos()
+            .is_multiple_of(1_000_000);
+        match self.sub_millisecond {
+            SubMillisecondPrecision::Reject if has_sub_millisecond_part => None,
+            _ => Some(self.datetime.into()),
+        }
+    }
+}
+
 #[cfg(feature = "chrono-04")]
 impl TryFrom<chrono_04::NaiveTime> for CqlTime {
     type Error = ValueOverflow;
@@ -924,6 +1103,149 @@ pub struct CqlDuration {
     pub nanoseconds: i64,
 }
 
+/// Splits the duration into whole days and the remaining nanoseconds, with zero months.
+/// Fails if the number of days doesn't fit in an [i32].
+#[cfg(feature = "chrono-04")]
+impl TryFrom<chrono_04::Duration> for CqlDuration {
+    type Error = ValueOverflow;
+
+    fn try_from(value: chrono_04::Duration) -> Result<Self, Self::Error> {
+        let days = value.num_days();
+        // The remainder is shorter than a day, so it fits in nanoseconds.
+        let nanoseconds = (value - chrono_04::Duration::try_days(days).ok_or(ValueOverflow)?)
+            .num_nanoseconds()
+            .ok_or(ValueOverflow)?;
+
+        Ok(Self {
+            months: 0,
+            days: days.try_into().map_err(|_| ValueOverflow)?,
+            nanoseconds,
+        })
+    }
+}
+
+/// Fails if the duration has a non-zero number of months, which have no fixed length,
+/// or if it's out of the range of [chrono_04::Duration].
+#[cfg(feature = "chrono-04")]
+impl TryInto<chrono_04::Duration> for CqlDuration {
+    type Error = ValueOverflow;
+
+    fn try_into(self) -> Result<chrono_04::Duration, Self::Error> {
+        if self.months != 0 {
+            return Err(ValueOverflow);
+        }
+        chrono_04::Duration::try_days(self.days.into())
+            .and_then(|days| days.checked_add(&chrono_04::Duration::nanoseconds(self.nanoseconds)))
+            .ok_or(ValueOverflow)
+    }
+}
+
+/// Represents an IP network in CIDR notation, such as `10.0.0.0/8` or `2001:db8::/32`.
+///
+/// CQL has no native type for IP networks, so `Cidr` is stored in `text` and `ascii`
+/// columns as its CIDR notation. It only holds valid networks: the prefix length
+/// does not exceed the length of the address, and the bits of the address beyond
+/// the prefix are zero. Deserializing a string which is not a valid network fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cidr {
+    address: IpAddr,
+    prefix_len: u8,
+}
+
+impl Cidr {
+    /// Creates the network of the given address and prefix length.
+    pub fn new(address: IpAddr, prefix_len: u8) -> Result<Self, InvalidCidr> {
+        let (bits, address_len) = match address {
+            IpAddr::V4(ip) => (u32::from(ip) as u128, 32),
+            IpAddr::V6(ip) => (u128::from(ip), 128),
+        };
+        if prefix_len > address_len {
+            return Err(InvalidCidr::PrefixLengthTooLong {
+                prefix_len,
+                address_len,
+            });
+        }
+        let host_bits = address_len - prefix_len;
+        if host_bits > 0 && bits & (u128::MAX >> (128 - host_bits)) != 0 {
+            return Err(InvalidCidr::HostBitsSet);
+        }
+        Ok(Self {
+            address,
+            prefix_len,
+        })
+    }
+
+    /// The first address of the network.
+    pub fn address(&self) -> IpAddr {
+        self.address
+    }
+
+    /// The number of leading bits of the address which identify the network.
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+}
+
+impl std::str::FromStr for Cidr {
+    type Err = InvalidCidr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, prefix_len) = s.split_once('/').ok_or(InvalidCidr::MissingPrefixLength)?;
+        let address = address.parse().map_err(|_| InvalidCidr::BadAddress)?;
+        let prefix_len = prefix_len
+            .parse()
+            .map_err(|_| InvalidCidr::BadPrefixLength)?;
+        Self::new(address, prefix_len)
+    }
+}
+
+impl std::fmt::Display for Cidr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix_len)
+    }
+}
+
+/// An error returned when a [`Cidr`] is not a valid IP network.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InvalidCidr {
+    /// The string has no `/` separating the address from the prefix length.
+    #[error("Invalid CIDR: missing prefix length")]
+    MissingPrefixLength,
+
+    /// The address is not a valid IPv4 or IPv6 address.
+    #[error("Invalid CIDR: malformed IP address")]
+    BadAddress,
+
+    /// The prefix length is not a valid number.
+    #[error("Invalid CIDR: malformed prefix length")]
+    BadPrefixLength,
+
+    /// The prefix length exceeds the length of the address.
+    #[error(
+        "Invalid CIDR: prefix length {prefix_len} exceeds the address length of {address_len} bits"
+    )]
+    PrefixLengthTooLong {
+        /// The prefix length.
+        prefix_len: u8,
+        /// The length of the address in bits, 32 for IPv4 and 128 for IPv6.
+        address_len: u8,
+    },
+
+    /// The address has bits set beyond the prefix, so it does not denote the network.
+    #[error("Invalid CIDR: the address has bits set beyond the prefix length")]
+    HostBitsSet,
+}
+
+/// Wraps a value which is stored in `text` and `ascii` columns as JSON.
+///
+/// Useful for schemaless data: the wrapped value is serialized with `serde_json` when bound,
+/// and parsed from the JSON text when deserialized. Deserializing text which is not a valid
+/// JSON representation of `T` fails.
+#[cfg(feature = "serde-json-1")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Json<T>(pub T);
+
 /// Represents all possible CQL values that can be returned by the database.
 ///
 /// This type can represent a CQL value of any type. Therefore, it should be used in places
@@ -1384,6 +1706,43 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn cidr_parsing() {
+        let v4 = Cidr::from_str("10.0.0.0/8").unwrap();
+        assert_eq!(v4.address(), IpAddr::from([10, 0, 0, 0]));
+        assert_eq!(v4.prefix_len(), 8);
+        assert_eq!(v4.to_string(), "10.0.0.0/8");
+
+        let v6 = Cidr::from_str("2001:db8::/32").unwrap();
+        assert_eq!(v6.prefix_len(), 32);
+        assert_eq!(v6.to_string(), "2001:db8::/32");
+
+        assert!(Cidr::from_str("0.0.0.0/0").is_ok());
+        assert!(Cidr::from_str("192.168.1.1/32").is_ok());
+
+        assert_eq!(
+            Cidr::from_str("10.0.0.0"),
+            Err(InvalidCidr::MissingPrefixLength)
+        );
+        assert_eq!(Cidr::from_str("10.0.0/8"), Err(InvalidCidr::BadAddress));
+        assert_eq!(
+            Cidr::from_str("10.0.0.0/x"),
+            Err(InvalidCidr::BadPrefixLength)
+        );
+        assert_eq!(
+            Cidr::from_str("10.0.0.0/33"),
+            Err(InvalidCidr::PrefixLengthTooLong {
+                prefix_len: 33,
+                address_len: 32
+            })
+        );
+        assert_eq!(Cidr::from_str("10.0.0.1/8"), Err(InvalidCidr::HostBitsSet));
+        assert_eq!(
+            Cidr::from_str("2001:db8::1/32"),
+            Err(InvalidCidr::HostBitsSet)
+        );
+    }
+
     #[test]
     fn timeuuid_msb_byte_order() {
         let uuid = CqlTimeuuid::from_str("00010203-0405-0607-0809-0a0b0c0d0e0f").unwrap();
@@ -71,21 +71,74 @@ impl SerializedRequest {
         req: &R,
         compression: Option<Compression>,
         tracing: bool,
+    ) -> Result<SerializedRequest, CqlRequestSerializationError> {
+        Self::make_in(req, compression, tracing, Vec::new())
+    }
+
+    /// Creates a new serialized request frame, like [SerializedRequest::make],
+    /// but writes it into the provided buffer, reusing its allocation.
+    ///
+    /// The previous contents of the buffer are discarded. The buffer can be
+    /// recovered after the frame is sent with [SerializedRequest::into_buffer].
+    pub fn make_in<R: SerializableRequest>(
+        req: &R,
+        compression: Option<Compression>,
+        tracing: bool,
+        data: Vec<u8>,
+    ) -> Result<SerializedRequest, CqlRequestSerializationError> {
+        Self::make_in_with_custom_payload(req, compression, 0, tracing, None, data, &mut Vec::new())
+    }
+
+    /// Creates a new serialized request frame, like [SerializedRequest::make_in],
+    /// which carries the given custom payload in its body, if any.
+    ///
+    /// The body is compressed only if it's at least `compression_threshold` bytes long,
+    /// as compressing tiny bodies costs more than it saves.
+    ///
+    /// If compression is enabled, the uncompressed body is first serialized into
+    /// `compression_buffer`, whose allocation is reused. Its contents are discarded.
+    pub fn make_in_with_custom_payload<R: SerializableRequest>(
+        req: &R,
+        compression: Option<Compression>,
+        compression_threshold: usize,
+        tracing: bool,
+        custom_payload: Option<&HashMap<String, Bytes>>,
+        mut data: Vec<u8>,
+        compression_buffer: &mut Vec<u8>,
     ) -> Result<SerializedRequest, CqlRequestSerializationError> {
         let mut flags = 0;
-        let mut data = vec![0; HEADER_SIZE];
+        data.clear();
 
         if let Some(compression) = compression {
-            flags |= flag::COMPRESSION;
-            let body = req.to_bytes()?;
-            compress_append(&body, compression, &mut data)?;
+            data.resize(HEADER_SIZE, 0);
+            let body = compression_buffer;
+            body.clear();
+            if let Some(custom_payload) = custom_payload {
+                types::write_bytes_map(custom_payload, body)
+                    .map_err(CqlRequestSerializationError::CustomPayloadSerialization)?;
+            }
+            req.serialize(body)?;
+            if body.len() >= compression_threshold {
+                flags |= flag::COMPRESSION;
+                compress_append(body, compression, &mut data)?;
+            } else {
+                data.extend_from_slice(body);
+            }
         } else {
+            data.resize(HEADER_SIZE, 0);
+            if let Some(custom_payload) = custom_payload {
+                types::write_bytes_map(custom_payload, &mut data)
+                    .map_err(CqlRequestSerializationError::CustomPayloadSerialization)?;
+            }
             req.serialize(&mut data)?;
         }
 
         if tracing {
             flags |= flag::TRACING;
         }
+        if custom_payload.is_some() {
+            flags |= flag::CUSTOM_PAYLOAD;
+        }
 
         data[0] = 4; // We only support version 4 for now
         data[1] = flags;
@@ -109,6 +162,12 @@ impl SerializedRequest {
     pub fn get_data(&self) -> &[u8] {
         &self.data[..]
     }
+
+    /// Consumes the request, returning the buffer holding the frame,
+    /// so that it can be reused for another request.
+    pub fn into_buffer(self) -> Vec<u8> {
+        self.data
+    }
 }
 
 /// Parts of the frame header which are not determined by the request/response type.
@@ -141,6 +200,16 @@ impl Default for FrameParams {
 /// Then parses and validates the frame header and extracts the body.
 pub async fn read_response_frame(
     reader: &mut (impl AsyncRead + Unpin),
+) -> Result<(FrameParams, ResponseOpcode, Bytes), FrameHeaderParseError> {
+    read_response_frame_in(reader, |_| Vec::new()).await
+}
+
+/// Reads a response frame, like [read_response_frame], but reads its body
+/// into the buffer returned by `body_buffer` for the parsed frame header,
+/// reusing its allocation. The previous contents of the buffer are discarded.
+pub async fn read_response_frame_in(
+    reader: &mut (impl AsyncRead + Unpin),
+    body_buffer: impl FnOnce(&FrameParams) -> Vec<u8>,
 ) -> Result<(FrameParams, ResponseOpcode, Bytes), FrameHeaderParseError> {
     let mut raw_header = [0u8; HEADER_SIZE];
     reader
@@ -173,7 +242,10 @@ pub async fn read_response_frame(
     // TODO: Guard from frames that are too large
     let length = buf.get_u32() as usize;
 
-    let mut raw_body = Vec::with_capacity(length).limit(length);
+    let mut raw_body = body_buffer(&frame_params);
+    raw_body.clear();
+    raw_body.reserve_exact(length);
+    let mut raw_body = raw_body.limit(length);
     while raw_body.has_remaining_mut() {
         let n = reader.read_buf(&mut raw_body).await.map_err(|err| {
             FrameHeaderParseError::BodyChunkIoError(raw_body.remaining_mut(), err)
@@ -362,4 +434,105 @@ mod test {
             ));
         }
     }
+
+    #[test]
+    fn test_serialized_request_reuses_provided_buffer() {
+        use crate::frame::request::query::{Query, QueryParameters};
+        use std::borrow::Cow;
+
+        let query = Query {
+            contents: Cow::Borrowed("SELECT a FROM ks.t"),
+            parameters: QueryParameters::default(),
+        };
+        let expected = SerializedRequest::make(&query, None, false).unwrap();
+
+        let mut buffer = Vec::with_capacity(4096);
+        buffer.extend_from_slice(b"leftovers of a previous frame");
+        let buffer_ptr = buffer.as_ptr();
+
+        let serialized = SerializedRequest::make_in(&query, None, false, buffer).unwrap();
+        assert_eq!(serialized.get_data(), expected.get_data());
+
+        let buffer = serialized.into_buffer();
+        assert_eq!(buffer.as_ptr(), buffer_ptr);
+        assert_eq!(buffer.capacity(), 4096);
+    }
+
+    #[test]
+    fn test_serialized_request_with_custom_payload() {
+        use crate::frame::request::query::{Query, QueryParameters};
+        use std::borrow::Cow;
+
+        let query = Query {
+            contents: Cow::Borrowed("SELECT a FROM ks.t"),
+            parameters: QueryParameters::default(),
+        };
+        let plain = SerializedRequest::make(&query, None, false).unwrap();
+        let custom_payload = HashMap::from([("key".to_owned(), Bytes::from_static(b"value"))]);
+
+        for compression in [None, Some(Compression::Lz4), Some(Compression::Snappy)] {
+            let serialized = SerializedRequest::make_in_with_custom_payload(
+                &query,
+                compression,
+                0,
+                false,
+                Some(&custom_payload),
+                Vec::new(),
+                &mut Vec::new(),
+            )
+            .unwrap();
+            let data = serialized.get_data();
+            assert_ne!(data[1] & flag::CUSTOM_PAYLOAD, 0);
+
+            // The payload precedes the body of the request.
+            let body = parse_response_body_extensions(
+                data[1],
+                compression,
+                Bytes::copy_from_slice(&data[HEADER_SIZE..]),
+            )
+            .unwrap();
+            assert_eq!(body.custom_payload, Some(custom_payload.clone()));
+            assert_eq!(body.body, plain.get_data()[HEADER_SIZE..]);
+        }
+    }
+
+    #[test]
+    fn test_serialized_request_below_compression_threshold_is_not_compressed() {
+        use crate::frame::request::query::{Query, QueryParameters};
+        use std::borrow::Cow;
+
+        let query = Query {
+            contents: Cow::Borrowed("SELECT a FROM ks.t"),
+            parameters: QueryParameters::default(),
+        };
+        let plain = SerializedRequest::make(&query, None, false).unwrap();
+        let body_len = plain.get_data().len() - HEADER_SIZE;
+
+        for compression in [Compression::Lz4, Compression::Snappy] {
+            let small = SerializedRequest::make_in_with_custom_payload(
+                &query,
+                Some(compression),
+                body_len + 1,
+                false,
+                None,
+                Vec::new(),
+                &mut Vec::new(),
+            )
+            .unwrap();
+            assert_eq!(small.get_data()[1] & flag::COMPRESSION, 0);
+            assert_eq!(small.get_data(), plain.get_data());
+
+            let large = SerializedRequest::make_in_with_custom_payload(
+                &query,
+                Some(compression),
+                body_len,
+                false,
+                None,
+                Vec::new(),
+                &mut Vec::new(),
+            )
+            .unwrap();
+            assert_ne!(large.get_data()[1] & flag::COMPRESSION, 0);
+        }
+    }
 }
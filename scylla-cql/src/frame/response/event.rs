@@ -61,7 +61,7 @@ pub enum StatusChangeEvent {
 }
 
 /// Event that notifies about changes in the cluster topology.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 // Check triggers because all variants end with "Change".
 // TODO(2.0): Remove the "Change" postfix from variants.
 #[expect(clippy::enum_variant_names)]
@@ -116,7 +116,7 @@ pub enum SchemaChangeEvent {
 }
 
 /// Type of change that was made to the schema.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SchemaChangeType {
     /// The affected schema item was created.
     Created,
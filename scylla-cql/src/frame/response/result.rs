@@ -7,15 +7,15 @@ use crate::frame::frame_errors::CustomTypeParseError;
 use crate::frame::frame_errors::{
     ColumnSpecParseError, ColumnSpecParseErrorKind, CqlResultParseError, CqlTypeParseError,
     LowLevelDeserializationError, PreparedMetadataParseError, PreparedParseError,
-    RawRowsAndPagingStateResponseParseError, ResultMetadataAndRowsCountParseError,
-    ResultMetadataParseError, SchemaChangeEventParseError, SetKeyspaceParseError,
-    TableSpecParseError,
+    PreparedSerializationError, RawRowsAndPagingStateResponseParseError,
+    ResultMetadataAndRowsCountParseError, ResultMetadataParseError, SchemaChangeEventParseError,
+    SetKeyspaceParseError, TableSpecParseError,
 };
 use crate::frame::protocol_features::ProtocolFeatures;
 use crate::frame::request::query::PagingStateResponse;
 use crate::frame::response::event::SchemaChangeEvent;
 use crate::frame::types;
-use bytes::Bytes;
+use bytes::{BufMut, Bytes};
 use std::borrow::Cow;
 use std::sync::Arc;
 use std::{result::Result as StdResult, str};
@@ -1062,6 +1062,202 @@ pub fn deserialize(
     deserialize_with_features(buf_bytes, cached_metadata, &ProtocolFeatures::default())
 }
 
+impl Prepared {
+    /// Serializes the prepared statement in the format of the body of a `RESULT::Prepared`
+    /// response (without the result kind), as the server would send it if the given
+    /// protocol features were negotiated.
+    ///
+    /// Column types are always written as custom types, i.e. by their class names,
+    /// because only those can represent every type (including vectors).
+    /// The output can be read back with [Prepared::deserialize].
+    pub fn serialize(
+        &self,
+        buf: &mut impl BufMut,
+        features: &ProtocolFeatures,
+    ) -> StdResult<(), PreparedSerializationError> {
+        types::write_short_bytes(&self.id, buf)?;
+        if features.scylla_metadata_id_supported {
+            types::write_short_bytes(self.result_metadata.id().unwrap_or_default(), buf)?;
+        }
+
+        // Prepared metadata. Table specs are written per column, so the global flag is cleared.
+        let prepared_metadata = &self.prepared_metadata;
+        types::write_int(prepared_metadata.flags & !0x0001, buf);
+        types::write_int_length(prepared_metadata.col_count, buf)?;
+        types::write_int_length(prepared_metadata.pk_indexes.len(), buf)?;
+        let mut pk_indexes = prepared_metadata.pk_indexes.clone();
+        pk_indexes.sort_unstable_by_key(|pki| pki.sequence);
+        for pki in pk_indexes {
+            types::write_short(pki.index, buf);
+        }
+        for col_spec in &prepared_metadata.col_specs {
+            ser_col_spec(col_spec, buf)?;
+        }
+
+        // Result metadata, which the server may have omitted.
+        let result_metadata = &self.result_metadata;
+        let no_metadata = result_metadata.col_specs.len() < result_metadata.col_count;
+        types::write_int(if no_metadata { 0x0004 } else { 0 }, buf);
+        types::write_int_length(result_metadata.col_count, buf)?;
+        if !no_metadata {
+            for col_spec in &result_metadata.col_specs {
+                ser_col_spec(col_spec, buf)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Deserializes the prepared statement from the body of a `RESULT::Prepared` response
+    /// (without the result kind), e.g. one produced by [Prepared::serialize].
+    pub fn deserialize(
+        buf: &mut &[u8],
+        features: &ProtocolFeatures,
+    ) -> StdResult<Self, PreparedParseError> {
+        deser_prepared(buf, features)
+    }
+}
+
+fn ser_col_spec(
+    col_spec: &ColumnSpec<'_>,
+    buf: &mut impl BufMut,
+) -> StdResult<(), PreparedSerializationError> {
+    types::write_string(col_spec.table_spec().ks_name(), buf)?;
+    types::write_string(col_spec.table_spec().table_name(), buf)?;
+    types::write_string(col_spec.name(), buf)?;
+
+    let mut class_name = String::new();
+    write_type_class_name(col_spec.typ(), &mut class_name)?;
+    types::write_short(0x0000, buf);
+    types::write_string(&class_name, buf)?;
+
+    Ok(())
+}
+
+/// Writes the class name of the type, as understood by [CustomTypeParser].
+fn write_type_class_name(
+    typ: &ColumnType<'_>,
+    out: &mut String,
+) -> StdResult<(), PreparedSerializationError> {
+    use NativeType::*;
+    use std::fmt::Write;
+
+    const PREFIX: &str = "org.apache.cassandra.db.marshal.";
+
+    fn write_hex(s: &str, out: &mut String) {
+        for byte in s.bytes() {
+            // Writing to a String cannot fail.
+            write!(out, "{byte:02x}").unwrap();
+        }
+    }
+
+    let frozen = match typ {
+        ColumnType::Collection { frozen, .. } | ColumnType::UserDefinedType { frozen, .. } => {
+            *frozen
+        }
+        _ => false,
+    };
+    if frozen {
+        out.push_str(PREFIX);
+        out.push_str("FrozenType(");
+    }
+    out.push_str(PREFIX);
+
+    #[deny(clippy::wildcard_enum_match_arm)]
+    match typ {
+        ColumnType::Native(native) => out.push_str(match native {
+            Ascii => "AsciiType",
+            BigInt => "LongType",
+            Blob => "BytesType",
+            Boolean => "BooleanType",
+            Counter => "CounterColumnType",
+            Date => "SimpleDateType",
+            Decimal => "DecimalType",
+            Double => "DoubleType",
+            Duration => "DurationType",
+            Float => "FloatType",
+            Int => "Int32Type",
+            Timestamp => "TimestampType",
+            Inet => "InetAddressType",
+            SmallInt => "ShortType",
+            Text => "UTF8Type",
+            Time => "TimeType",
+            Timeuuid => "TimeUUIDType",
+            TinyInt => "ByteType",
+            Uuid => "UUIDType",
+            Varint => "IntegerType",
+            // Catch future variants from #[non_exhaustive] enums.
+            _ => return Err(PreparedSerializationError::UnsupportedColumnType),
+        }),
+        ColumnType::Collection {
+            typ: CollectionType::List(elem_type),
+            ..
+        } => {
+            out.push_str("ListType(");
+            write_type_class_name(elem_type, out)?;
+            out.push(')');
+        }
+        ColumnType::Collection {
+            typ: CollectionType::Set(elem_type),
+            ..
+        } => {
+            out.push_str("SetType(");
+            write_type_class_name(elem_type, out)?;
+            out.push(')');
+        }
+        ColumnType::Collection {
+            typ: CollectionType::Map(key_type, value_type),
+            ..
+        } => {
+            out.push_str("MapType(");
+            write_type_class_name(key_type, out)?;
+            out.push(',');
+            write_type_class_name(value_type, out)?;
+            out.push(')');
+        }
+        ColumnType::Vector { typ, dimensions } => {
+            out.push_str("VectorType(");
+            write_type_class_name(typ, out)?;
+            // Writing to a String cannot fail.
+            write!(out, ",{dimensions})").unwrap();
+        }
+        ColumnType::UserDefinedType {
+            definition: udt, ..
+        } => {
+            out.push_str("UserType(");
+            out.push_str(&udt.keyspace);
+            out.push(',');
+            write_hex(&udt.name, out);
+            for (field_name, field_type) in &udt.field_types {
+                out.push(',');
+                write_hex(field_name, out);
+                out.push(':');
+                write_type_class_name(field_type, out)?;
+            }
+            out.push(')');
+        }
+        ColumnType::Tuple(types) => {
+            out.push_str("TupleType(");
+            for (i, typ) in types.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_type_class_name(typ, out)?;
+            }
+            out.push(')');
+        }
+        // Catch future variants from #[non_exhaustive] enums.
+        ColumnType::Collection { .. } | _ => {
+            return Err(PreparedSerializationError::UnsupportedColumnType);
+        }
+    }
+
+    if frozen {
+        out.push(')');
+    }
+    Ok(())
+}
+
 // This is not #[cfg(test)], because it is used by scylla crate.
 // Unfortunately, this attribute does not apply recursively to
 // children item. Therefore, every `pub` item here must use have
@@ -1294,3 +1490,128 @@ mod test_utils {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+
+    use super::{
+        CollectionType, ColumnSpec, ColumnType, NativeType, PartitionKeyIndex, Prepared,
+        PreparedMetadata, ResultMetadata, TableSpec, UserDefinedType, cow_bytes::CowBytes,
+    };
+    use crate::frame::protocol_features::ProtocolFeatures;
+
+    fn col_spec(name: &str, typ: ColumnType<'static>) -> ColumnSpec<'static> {
+        ColumnSpec::owned(
+            name.to_owned(),
+            typ,
+            TableSpec::owned("ks".to_owned(), "t".to_owned()),
+        )
+    }
+
+    #[test]
+    fn prepared_serialization_roundtrip() {
+        let udt = ColumnType::UserDefinedType {
+            frozen: true,
+            definition: Arc::new(UserDefinedType {
+                name: "address".into(),
+                keyspace: "ks".into(),
+                field_types: vec![
+                    (
+                        Cow::Borrowed("street"),
+                        ColumnType::Native(NativeType::Text),
+                    ),
+                    (Cow::Borrowed("number"), ColumnType::Native(NativeType::Int)),
+                ],
+            }),
+        };
+        let bind_markers = vec![
+            col_spec("a", ColumnType::Native(NativeType::BigInt)),
+            col_spec("b", ColumnType::Native(NativeType::Text)),
+            col_spec(
+                "c",
+                ColumnType::Collection {
+                    frozen: false,
+                    typ: CollectionType::Map(
+                        Box::new(ColumnType::Native(NativeType::Uuid)),
+                        Box::new(ColumnType::Tuple(vec![
+                            ColumnType::Native(NativeType::Date),
+                            udt,
+                        ])),
+                    ),
+                },
+            ),
+            col_spec(
+                "d",
+                ColumnType::Vector {
+                    typ: Box::new(ColumnType::Native(NativeType::Float)),
+                    dimensions: 3,
+                },
+            ),
+        ];
+        let prepared = Prepared {
+            id: Bytes::from_static(&[1, 2, 3, 4]),
+            prepared_metadata: PreparedMetadata {
+                flags: 0x0001,
+                col_count: bind_markers.len(),
+                // The partition key is (b, a).
+                pk_indexes: vec![
+                    PartitionKeyIndex {
+                        index: 0,
+                        sequence: 1,
+                    },
+                    PartitionKeyIndex {
+                        index: 1,
+                        sequence: 0,
+                    },
+                ],
+                col_specs: bind_markers,
+            },
+            result_metadata: ResultMetadata {
+                id: Some(CowBytes::Owned(Bytes::from_static(&[5, 6]))),
+                col_count: 1,
+                col_specs: vec![col_spec(
+                    "e",
+                    ColumnType::Collection {
+                        frozen: true,
+                        typ: CollectionType::List(Box::new(ColumnType::Native(
+                            NativeType::Duration,
+                        ))),
+                    },
+                )],
+            },
+        };
+
+        for scylla_metadata_id_supported in [false, true] {
+            let mut features = ProtocolFeatures::default();
+            features.scylla_metadata_id_supported = scylla_metadata_id_supported;
+            let mut buf = Vec::new();
+            prepared.serialize(&mut buf, &features).unwrap();
+            let deserialized = Prepared::deserialize(&mut &buf[..], &features).unwrap();
+
+            assert_eq!(deserialized.id, prepared.id);
+            let (expected, actual) = (&prepared.prepared_metadata, &deserialized.prepared_metadata);
+            assert_eq!(actual.col_count, expected.col_count);
+            assert_eq!(actual.col_specs, expected.col_specs);
+            assert_eq!(
+                actual
+                    .pk_indexes
+                    .iter()
+                    .map(|pki| (pki.index, pki.sequence))
+                    .collect::<Vec<_>>(),
+                [(0, 1), (1, 0)]
+            );
+            assert_eq!(
+                deserialized.result_metadata.col_specs(),
+                prepared.result_metadata.col_specs()
+            );
+            assert_eq!(
+                deserialized.result_metadata.id(),
+                scylla_metadata_id_supported.then_some(&[5, 6][..])
+            );
+        }
+    }
+}
@@ -7,9 +7,9 @@ use crate::frame::response::result::ColumnType;
 
 // Re-export all public types from scylla-cql-core for backward compatibility.
 pub use scylla_cql_core::value::{
-    Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
-    CqlTimeuuid, CqlValue, CqlVarint, CqlVarintBorrowed, Emptiable, MaybeEmpty, MaybeUnset, Row,
-    Unset, ValueOverflow,
+    Cidr, Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
+    CqlTimeuuid, CqlValue, CqlVarint, CqlVarintBorrowed, Emptiable, InvalidCidr, MaybeEmpty,
+    MaybeUnset, Row, Unset, ValueOverflow,
 };
 
 /// Deserializes any CQL value from a byte slice according to the provided CQL type.
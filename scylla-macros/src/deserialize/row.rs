@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use darling::{FromAttributes, FromField};
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream};
 use syn::ext::IdentExt;
 use syn::parse_quote;
 
@@ -24,6 +24,11 @@ struct StructAttrs {
     // This annotation only works if `enforce_order` is specified.
     #[darling(default)]
     skip_name_checks: bool,
+
+    // If true, then column names are matched to struct fields ignoring
+    // the ASCII case, e.g. the `NAME` column is deserialized into the `name` field.
+    #[darling(default)]
+    case_insensitive: bool,
 }
 
 impl DeserializeCommonStructAttrs for StructAttrs {
@@ -46,18 +51,34 @@ struct Field {
     #[darling(default)]
     rename: Option<String>,
 
+    // If true, then - if this column is missing from the row metadata
+    // - it will be initialized to Default::default().
+    #[darling(default)]
+    #[darling(rename = "default")]
+    default_when_missing: bool,
+
     // If true, then - if this column is present but set to null - it will be
     // initialized to Default::default().
     #[darling(default)]
     default_when_null: bool,
 
+    // If true, then the field does not correspond to any column, but it is
+    // initialized with the serialized bytes of the whole row instead.
+    #[darling(default)]
+    raw_row: bool,
+
+    // Used for serialization only. Ignored in deserialization.
+    #[darling(default)]
+    #[darling(rename = "unset_when_none")]
+    _unset_when_none: bool,
+
     ident: Option<syn::Ident>,
     ty: syn::Type,
 }
 
 impl DeserializeCommonFieldAttrs for Field {
     fn needs_default(&self) -> bool {
-        self.skip || self.default_when_null
+        self.skip || self.default_when_missing || self.default_when_null
     }
 
     fn deserialize_target(&self) -> &syn::Type {
@@ -95,6 +116,40 @@ pub(crate) fn deserialize_row_derive(
 fn validate_attrs(attrs: &StructAttrs, fields: &[Field]) -> Result<(), darling::Error> {
     let mut errors = darling::Error::accumulator();
 
+    // A <raw_row> field does not correspond to any column, so column-related
+    // annotations don't make sense on it.
+    for field in fields.iter().filter(|f| f.raw_row) {
+        if field.skip
+            || field.rename.is_some()
+            || field.default_when_missing
+            || field.default_when_null
+        {
+            let err = darling::Error::custom(
+                "<raw_row> annotation can't be combined with <skip>, <rename>, <default> or <default_when_null>",
+            )
+            .with_span(&field.ident);
+            errors.push(err);
+        }
+    }
+    if let Some(second_raw_row_field) = fields.iter().filter(|f| f.raw_row).nth(1) {
+        let err = darling::Error::custom("only one field can have the <raw_row> annotation")
+            .with_span(&second_raw_row_field.ident);
+        errors.push(err);
+    }
+
+    if attrs.flavor == Flavor::EnforceOrder {
+        // Columns are matched to fields by position, so a column can't be missing.
+        for field in fields {
+            if field.default_when_missing {
+                let err = darling::Error::custom(
+                    "<default> annotations require the <match_by_name> flavor",
+                )
+                .with_span(&field.ident);
+                errors.push(err);
+            }
+        }
+    }
+
     if attrs.skip_name_checks {
         // Skipping name checks is only available in enforce_order mode
         if attrs.flavor != Flavor::EnforceOrder {
@@ -103,6 +158,14 @@ fn validate_attrs(attrs: &StructAttrs, fields: &[Field]) -> Result<(), darling::
             errors.push(error);
         }
 
+        // Names are not compared at all, so their case doesn't matter
+        if attrs.case_insensitive {
+            let error = darling::Error::custom(
+                "attribute <case_insensitive> doesn't make sense with <skip_name_checks> attribute",
+            );
+            errors.push(error);
+        }
+
         // <rename> annotations don't make sense with skipped name checks
         for field in fields {
             if field.rename.is_some() {
@@ -114,11 +177,16 @@ fn validate_attrs(attrs: &StructAttrs, fields: &[Field]) -> Result<(), darling::
             }
         }
     } else {
-        // Detect name collisions caused by `rename`.
+        // Detect name collisions caused by `rename` (or by ignoring the case).
         let mut used_names = HashMap::<String, &Field>::new();
-        for field in fields {
+        for field in fields.iter().filter(|f| !f.raw_row) {
             let column_name = field.column_name();
-            if let Some(other_field) = used_names.get(&column_name) {
+            let used_name = if attrs.case_insensitive {
+                column_name.to_ascii_lowercase()
+            } else {
+                column_name.clone()
+            };
+            if let Some(other_field) = used_names.get(&used_name) {
                 let other_field_ident = other_field.ident.as_ref().unwrap();
                 let msg = format!(
                     "the column name `{column_name}` used by this struct field is already used by field `{other_field_ident}`"
@@ -126,7 +194,7 @@ fn validate_attrs(attrs: &StructAttrs, fields: &[Field]) -> Result<(), darling::
                 let err = darling::Error::custom(msg).with_span(&field.ident);
                 errors.push(err);
             } else {
-                used_names.insert(column_name, field);
+                used_names.insert(used_name, field);
             }
         }
     }
@@ -135,9 +203,14 @@ fn validate_attrs(attrs: &StructAttrs, fields: &[Field]) -> Result<(), darling::
 }
 
 impl Field {
+    // Returns whether this field is deserialized from a column.
+    fn is_column(&self) -> bool {
+        !self.skip && !self.raw_row
+    }
+
     // Returns whether this field is mandatory for deserialization.
     fn is_required(&self) -> bool {
-        !self.skip
+        self.is_column() && !self.default_when_missing
     }
 
     // The name of the column corresponding to this Rust struct field
@@ -157,6 +230,46 @@ impl Field {
 type StructDesc = super::StructDescForDeserialize<StructAttrs, Field>;
 
 impl StructDesc {
+    // An expression checking whether `column_name` (a `&str`) is the name
+    // of the column corresponding to the field
+    fn column_name_matches(&self, column_name: &syn::Expr, field: &Field) -> syn::Expr {
+        let cql_name_literal = field.cql_name_literal();
+        if self.attrs.case_insensitive {
+            parse_quote! {
+                ::std::primitive::str::eq_ignore_ascii_case(#column_name, #cql_name_literal)
+            }
+        } else {
+            parse_quote! {
+                #column_name == #cql_name_literal
+            }
+        }
+    }
+
+    // A pattern (with a guard, if needed) of a `match` arm on a column name,
+    // which matches the column corresponding to the field
+    fn column_name_pattern(&self, field: &Field) -> TokenStream {
+        let cql_name_literal = field.cql_name_literal();
+        if self.attrs.case_insensitive {
+            let column_name: syn::Expr = parse_quote!(column_name);
+            let matches = self.column_name_matches(&column_name, field);
+            quote::quote!(column_name if #matches)
+        } else {
+            quote::quote!(#cql_name_literal)
+        }
+    }
+
+    // A statement capturing the serialized bytes of the whole row into the `raw_row` variable,
+    // if some field needs them. It must be executed before any column is consumed.
+    fn generate_raw_row_capture(&self) -> Option<syn::Stmt> {
+        self.fields().iter().any(|f| f.raw_row).then(|| {
+            let macro_internal = self.struct_attrs().macro_internal_path();
+            parse_quote! {
+                let raw_row = #macro_internal::ColumnIterator::raw_row(&row)
+                    .map_err(#macro_internal::row_deser_error_replace_rust_name::<Self>)?;
+            }
+        })
+    }
+
     fn generate_type_check_method(&self) -> syn::ImplItemFn {
         match self.attrs.flavor {
             Flavor::MatchByName => TypeCheckUnorderedGenerator(self).generate(),
@@ -185,9 +298,12 @@ impl TypeCheckAssumeOrderGenerator<'_> {
         (!self.0.attrs.skip_name_checks).then(|| {
             let macro_internal = self.0.struct_attrs().macro_internal_path();
             let rust_field_name = field.cql_name_literal();
+            let name_matches = self
+                .0
+                .column_name_matches(&parse_quote!(#column_spec.name()), field);
 
             parse_quote! {
-                if #column_spec.name() != #rust_field_name {
+                if !(#name_matches) {
                     return ::std::result::Result::Err(
                         #macro_internal::mk_row_typck_err::<Self>(
                             column_types_iter(),
@@ -290,14 +406,20 @@ impl DeserializeAssumeOrderGenerator<'_> {
             // Skipped fields are initialized with Default::default()
             return parse_quote!(::std::default::Default::default());
         }
+        if field.raw_row {
+            return parse_quote!(::std::clone::Clone::clone(&raw_row));
+        }
 
         let macro_internal = self.0.struct_attrs().macro_internal_path();
         let cql_name_literal = field.cql_name_literal();
         let deserializer = field.deserialize_target();
         let (frame_lifetime, metadata_lifetime) = self.0.constraint_lifetimes();
 
+        let name_matches = self
+            .0
+            .column_name_matches(&parse_quote!(col.spec.name()), field);
         let name_check: Option<syn::Stmt> = (!self.0.struct_attrs().skip_name_checks).then(|| parse_quote! {
-            if col.spec.name() != #cql_name_literal {
+            if !(#name_matches) {
                 ::std::panic!(
                     "Typecheck should have prevented this scenario - field-column name mismatch! Rust field name {}, CQL column name {}",
                     #cql_name_literal,
@@ -354,11 +476,14 @@ impl DeserializeAssumeOrderGenerator<'_> {
             .iter()
             .enumerate()
             .map(|(field_idx, f)| self.generate_finalize_field(field_idx, f));
+        let raw_row_capture = self.0.generate_raw_row_capture();
 
         parse_quote! {
             fn deserialize(
                 mut row: #macro_internal::ColumnIterator<#frame_lifetime, #metadata_lifetime>,
             ) -> ::std::result::Result<Self, #macro_internal::DeserializationError> {
+                #raw_row_capture
+
                 ::std::result::Result::Ok(Self {
                     #(#field_idents: #field_finalizers,)*
                 })
@@ -380,7 +505,7 @@ impl TypeCheckUnorderedGenerator<'_> {
     // We generate it even if the flag is not required in order to protect
     // from fields appearing more than once
     fn generate_visited_flag_decl(field: &Field) -> Option<syn::Stmt> {
-        (!field.skip).then(|| {
+        field.is_column().then(|| {
             let visited_flag = Self::visited_flag_variable(field);
             parse_quote! {
                 let mut #visited_flag = false;
@@ -390,7 +515,7 @@ impl TypeCheckUnorderedGenerator<'_> {
 
     // Generates code that, given variable `typ`, type-checks given field
     fn generate_type_check(&self, field: &Field) -> Option<syn::Block> {
-        (!field.skip).then(|| {
+        field.is_column().then(|| {
             let macro_internal = self.0.struct_attrs().macro_internal_path();
             let (frame_lifetime, metadata_lifetime) = self.0.constraint_lifetimes();
             let visited_flag = Self::visited_flag_variable(field);
@@ -467,8 +592,8 @@ impl TypeCheckUnorderedGenerator<'_> {
         let append_name_blocks = fields.iter().flat_map(Self::generate_append_name);
         let nonskipped_field_names = fields
             .iter()
-            .filter(|f| !f.skip)
-            .map(|f| f.cql_name_literal());
+            .filter(|f| f.is_column())
+            .map(|f| self.0.column_name_pattern(f));
         let field_count_lit = fields.iter().filter(|f| f.is_required()).count();
 
         parse_quote! {
@@ -541,7 +666,20 @@ impl DeserializeUnorderedGenerator<'_> {
             };
         }
 
+        if field.raw_row {
+            return parse_quote! {
+                ::std::clone::Clone::clone(&raw_row)
+            };
+        }
+
         let deserialize_field = Self::deserialize_field_variable(field);
+        if field.default_when_missing {
+            // Fields allowed to be missing are initialized with Default::default()
+            return parse_quote! {
+                #deserialize_field.unwrap_or_default()
+            };
+        }
+
         let cql_name_literal = field.cql_name_literal();
         parse_quote! {
             #deserialize_field.unwrap_or_else(|| ::std::panic!(
@@ -554,7 +692,7 @@ impl DeserializeUnorderedGenerator<'_> {
     // Generated code that performs deserialization when the raw field
     // is being processed
     fn generate_deserialization(&self, column_index: usize, field: &Field) -> syn::Expr {
-        assert!(!field.skip);
+        assert!(field.is_column());
         let macro_internal = self.0.struct_attrs().macro_internal_path();
         let (frame_lifetime, metadata_lifetime) = self.0.constraint_lifetimes();
         let deserialize_field = Self::deserialize_field_variable(field);
@@ -607,7 +745,7 @@ impl DeserializeUnorderedGenerator<'_> {
     // Generate a declaration of a variable that temporarily keeps
     // the deserialized value
     fn generate_deserialize_field_decl(field: &Field) -> Option<syn::Stmt> {
-        (!field.skip).then(|| {
+        field.is_column().then(|| {
             let deserialize_field = Self::deserialize_field_variable(field);
             parse_quote! {
                 let mut #deserialize_field = ::std::option::Option::None;
@@ -625,16 +763,17 @@ impl DeserializeUnorderedGenerator<'_> {
             .flat_map(Self::generate_deserialize_field_decl);
         let deserialize_blocks = fields
             .iter()
-            .filter(|f| !f.skip)
+            .filter(|f| f.is_column())
             .enumerate()
             .map(|(col_idx, f)| self.generate_deserialization(col_idx, f));
         let field_idents = fields.iter().map(|f| f.ident.as_ref().unwrap());
         let nonskipped_field_names = fields
             .iter()
-            .filter(|&f| !f.skip)
-            .map(|f| f.cql_name_literal());
+            .filter(|&f| f.is_column())
+            .map(|f| self.0.column_name_pattern(f));
 
         let field_finalizers = fields.iter().map(|f| self.generate_finalize_field(f));
+        let raw_row_capture = self.0.generate_raw_row_capture();
 
         // TODO: Allow collecting unrecognized fields into some special field
 
@@ -642,6 +781,7 @@ impl DeserializeUnorderedGenerator<'_> {
             fn deserialize(
                 row: #macro_internal::ColumnIterator<#frame_lifetime, #metadata_lifetime>,
             ) -> ::std::result::Result<Self, #macro_internal::DeserializationError> {
+                #raw_row_capture
 
                 // Generate fields that will serve as temporary storage
                 // for the fields' values. Those are of type Option<FieldType>.
@@ -659,7 +799,7 @@ impl DeserializeUnorderedGenerator<'_> {
                 // Create the final struct. The finalizer expressions convert
                 // the temporary storage fields to the final field values.
                 // For example, if a field is missing but marked as
-                // `default_when_null` it will create a default value, otherwise
+                // `default` it will create a default value, otherwise
                 // it will report an error.
                 ::std::result::Result::Ok(Self {
                     #(#field_idents: #field_finalizers,)*
@@ -31,6 +31,18 @@ struct StructAttrs {
     // they will be ignored. With true, an error will be raised.
     #[darling(default)]
     forbid_excess_udt_fields: bool,
+
+    // If true, then the struct must be a newtype (have exactly one field),
+    // and it will be deserialized in the same way as its only field, instead
+    // of being deserialized from a UDT.
+    #[darling(default)]
+    transparent: bool,
+
+    // If set, then the type must be an enum whose variants are newtypes.
+    // It is deserialized from a UDT into the variant whose name (or <rename>)
+    // matches the value of the UDT field with this name.
+    #[darling(default)]
+    tag: Option<String>,
 }
 
 impl DeserializeCommonStructAttrs for StructAttrs {
@@ -68,6 +80,15 @@ struct Field {
     ty: syn::Type,
 }
 
+#[derive(FromAttributes)]
+#[darling(attributes(scylla))]
+struct VariantAttrs {
+    // If set, then the variant is chosen for this value of the tag field
+    // instead of the Rust variant name.
+    #[darling(default)]
+    rename: Option<String>,
+}
+
 impl DeserializeCommonFieldAttrs for Field {
     fn needs_default(&self) -> bool {
         self.skip || self.default_when_missing
@@ -82,7 +103,7 @@ impl DeserializeCommonFieldAttrs for Field {
 pub(crate) fn deserialize_value_derive(
     tokens_input: TokenStream,
 ) -> Result<syn::ItemImpl, syn::Error> {
-    let input = syn::parse(tokens_input)?;
+    let input: syn::DeriveInput = syn::parse(tokens_input)?;
 
     let implemented_trait: syn::Path = parse_quote!(DeserializeValue);
     let implemented_trait_name = implemented_trait
@@ -93,6 +114,15 @@ pub(crate) fn deserialize_value_derive(
         .unraw()
         .to_string();
     let constraining_trait = implemented_trait.clone();
+
+    let attrs = StructAttrs::from_attributes(&input.attrs)?;
+    if let syn::Data::Enum(data) = &input.data {
+        return deserialize_value_derive_tagged_enum(&input, data, &attrs, &implemented_trait_name);
+    }
+    if attrs.transparent {
+        return deserialize_value_derive_transparent(&input, &attrs, &implemented_trait_name);
+    }
+
     let s = StructDesc::new(&input, &implemented_trait_name, constraining_trait)?;
 
     validate_attrs(&s.attrs, s.fields())?;
@@ -105,9 +135,242 @@ pub(crate) fn deserialize_value_derive(
     Ok(s.generate_impl(implemented_trait, items))
 }
 
+// Generates an implementation which delegates type checking and deserialization
+// to the only field of a newtype struct.
+fn deserialize_value_derive_transparent(
+    input: &syn::DeriveInput,
+    attrs: &StructAttrs,
+    trait_name: &str,
+) -> Result<syn::ItemImpl, syn::Error> {
+    let (member, field) = crate::parser::parse_newtype_field(input, trait_name, "<transparent>")?;
+    let field_type = &field.ty;
+
+    let mut errors = darling::Error::accumulator();
+    if attrs.skip_name_checks
+        || attrs.forbid_excess_udt_fields
+        || attrs.flavor != Flavor::MatchByName
+        || attrs.tag.is_some()
+    {
+        errors.push(
+            darling::Error::custom(
+                "attributes <skip_name_checks>, <forbid_excess_udt_fields>, <flavor> and <tag> are not allowed with <transparent>.",
+            )
+            .with_span(&input.ident),
+        );
+    }
+    errors.finish()?;
+
+    let macro_internal = attrs.macro_internal_path();
+    let struct_name = &input.ident;
+    let (frame_lifetime, metadata_lifetime) =
+        super::generate_pair_of_unique_lifetimes_for_impl(&input.generics);
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let impl_generics = &input.generics.params;
+
+    // Constrain the frame lifetime with the existing lifetime parameters,
+    // so that borrowed inner types (e.g. `&'a [u8]`) are supported.
+    let lifetimes = input
+        .generics
+        .lifetimes()
+        .map(|l| &l.lifetime)
+        .collect::<Vec<_>>();
+    let lifetime_constraints = (!lifetimes.is_empty())
+        .then(|| -> syn::WherePredicate { parse_quote!(#frame_lifetime: #(#lifetimes)+*) });
+    let existing_predicates = input
+        .generics
+        .where_clause
+        .iter()
+        .flat_map(|w| w.predicates.iter().cloned());
+    let predicates = lifetime_constraints
+        .into_iter()
+        .chain(existing_predicates)
+        .chain(std::iter::once(parse_quote!(
+            #field_type: #macro_internal::DeserializeValue<#frame_lifetime, #metadata_lifetime>
+        )));
+
+    Ok(parse_quote! {
+        #[automatically_derived]
+        impl<#frame_lifetime, #metadata_lifetime, #impl_generics>
+            #macro_internal::DeserializeValue<#frame_lifetime, #metadata_lifetime> for #struct_name #ty_generics
+        where #(#predicates),*
+        {
+            fn type_check(
+                typ: &#macro_internal::ColumnType,
+            ) -> ::std::result::Result<(), #macro_internal::TypeCheckError> {
+                <#field_type as #macro_internal::DeserializeValue<#frame_lifetime, #metadata_lifetime>>::type_check(typ)
+                    .map_err(#macro_internal::value_typck_error_replace_rust_name::<Self>)
+            }
+
+            fn deserialize(
+                typ: &#metadata_lifetime #macro_internal::ColumnType<#metadata_lifetime>,
+                v: ::std::option::Option<#macro_internal::FrameSlice<#frame_lifetime>>,
+            ) -> ::std::result::Result<Self, #macro_internal::DeserializationError> {
+                <#field_type as #macro_internal::DeserializeValue<#frame_lifetime, #metadata_lifetime>>::deserialize(typ, v)
+                    .map(|inner| Self { #member: inner })
+                    .map_err(#macro_internal::value_deser_error_replace_rust_name::<Self>)
+            }
+        }
+    })
+}
+
+// Generates an implementation for an enum, which deserializes a UDT into the variant
+// chosen by the value of the UDT's tag field. Each variant must be a newtype, and its
+// only field is deserialized from the whole UDT.
+fn deserialize_value_derive_tagged_enum(
+    input: &syn::DeriveInput,
+    data: &syn::DataEnum,
+    attrs: &StructAttrs,
+    trait_name: &str,
+) -> Result<syn::ItemImpl, syn::Error> {
+    let mut errors = darling::Error::accumulator();
+    if attrs.transparent
+        || attrs.skip_name_checks
+        || attrs.forbid_excess_udt_fields
+        || attrs.flavor != Flavor::MatchByName
+    {
+        errors.push(
+            darling::Error::custom(
+                "attributes <transparent>, <skip_name_checks>, <forbid_excess_udt_fields> and <flavor> are not allowed on enums.",
+            )
+            .with_span(&input.ident),
+        );
+    }
+    let tag_field = match &attrs.tag {
+        Some(tag_field) => syn::LitStr::new(tag_field, Span::call_site()),
+        None => {
+            errors.push(
+                darling::Error::custom(format!(
+                    "derive({trait_name}) works for enums only with the <tag> attribute"
+                ))
+                .with_span(&data.enum_token),
+            );
+            syn::LitStr::new("", Span::call_site())
+        }
+    };
+
+    let mut variants = Vec::with_capacity(data.variants.len());
+    let mut used_tag_values = HashMap::<String, &syn::Ident>::new();
+    for variant in &data.variants {
+        let Some(variant_attrs) = errors.handle(VariantAttrs::from_attributes(&variant.attrs))
+        else {
+            continue;
+        };
+        let ty = match &variant.fields {
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => {
+                errors.push(
+                    darling::Error::custom(format!(
+                        "derive({trait_name}) with <tag> attribute works only for enums whose variants have exactly one unnamed field"
+                    ))
+                    .with_span(&variant.ident),
+                );
+                continue;
+            }
+        };
+        let tag_value = variant_attrs
+            .rename
+            .unwrap_or_else(|| variant.ident.unraw().to_string());
+        if let Some(other_variant) = used_tag_values.get(&tag_value) {
+            let msg = format!(
+                "the tag value `{tag_value}` used by this variant is already used by variant `{other_variant}`"
+            );
+            errors.push(darling::Error::custom(msg).with_span(&variant.ident));
+            continue;
+        }
+        used_tag_values.insert(tag_value.clone(), &variant.ident);
+        variants.push((
+            &variant.ident,
+            ty,
+            syn::LitStr::new(&tag_value, Span::call_site()),
+        ));
+    }
+    errors.finish()?;
+
+    let macro_internal = attrs.macro_internal_path();
+    let enum_name = &input.ident;
+    let (frame_lifetime, metadata_lifetime) =
+        super::generate_pair_of_unique_lifetimes_for_impl(&input.generics);
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let impl_generics = &input.generics.params;
+
+    // Constrain the frame lifetime with the existing lifetime parameters,
+    // so that variants borrowing from the frame are supported.
+    let lifetimes = input
+        .generics
+        .lifetimes()
+        .map(|l| &l.lifetime)
+        .collect::<Vec<_>>();
+    let lifetime_constraints = (!lifetimes.is_empty())
+        .then(|| -> syn::WherePredicate { parse_quote!(#frame_lifetime: #(#lifetimes)+*) });
+    let existing_predicates = input
+        .generics
+        .where_clause
+        .iter()
+        .flat_map(|w| w.predicates.iter().cloned());
+    let variant_predicates = variants.iter().map(|(_, ty, _)| -> syn::WherePredicate {
+        parse_quote!(#ty: #macro_internal::DeserializeValue<#frame_lifetime, #metadata_lifetime>)
+    });
+    let predicates = lifetime_constraints
+        .into_iter()
+        .chain(existing_predicates)
+        .chain(variant_predicates);
+
+    let variant_type_checks = variants.iter().map(|(_, ty, _)| -> syn::Stmt {
+        parse_quote! {
+            <#ty as #macro_internal::DeserializeValue<#frame_lifetime, #metadata_lifetime>>::type_check(typ)
+                .map_err(#macro_internal::value_typck_error_replace_rust_name::<Self>)?;
+        }
+    });
+    let variant_arms = variants.iter().map(|(ident, ty, tag_value)| -> syn::Arm {
+        parse_quote! {
+            #tag_value => <#ty as #macro_internal::DeserializeValue<#frame_lifetime, #metadata_lifetime>>::deserialize(typ, v)
+                .map(Self::#ident)
+                .map_err(#macro_internal::value_deser_error_replace_rust_name::<Self>),
+        }
+    });
+
+    Ok(parse_quote! {
+        #[automatically_derived]
+        impl<#frame_lifetime, #metadata_lifetime, #impl_generics>
+            #macro_internal::DeserializeValue<#frame_lifetime, #metadata_lifetime> for #enum_name #ty_generics
+        where #(#predicates),*
+        {
+            fn type_check(
+                typ: &#macro_internal::ColumnType,
+            ) -> ::std::result::Result<(), #macro_internal::TypeCheckError> {
+                #macro_internal::deser::value::udt_tag_type_check::<Self>(typ, #tag_field)?;
+                #(#variant_type_checks)*
+                ::std::result::Result::Ok(())
+            }
+
+            fn deserialize(
+                typ: &#metadata_lifetime #macro_internal::ColumnType<#metadata_lifetime>,
+                v: ::std::option::Option<#macro_internal::FrameSlice<#frame_lifetime>>,
+            ) -> ::std::result::Result<Self, #macro_internal::DeserializationError> {
+                match #macro_internal::deser::value::deserialize_udt_tag::<Self>(typ, v, #tag_field)? {
+                    #(#variant_arms)*
+                    tag_value => ::std::result::Result::Err(#macro_internal::mk_value_deser_err::<Self>(
+                        typ,
+                        #macro_internal::UdtDeserializationErrorKind::UnknownTag {
+                            tag_field: ::std::borrow::ToOwned::to_owned(#tag_field),
+                            tag_value: ::std::borrow::ToOwned::to_owned(tag_value),
+                        },
+                    )),
+                }
+            }
+        }
+    })
+}
+
 fn validate_attrs(attrs: &StructAttrs, fields: &[Field]) -> Result<(), darling::Error> {
     let mut errors = darling::Error::accumulator();
 
+    if attrs.tag.is_some() {
+        errors.push(darling::Error::custom(
+            "attribute <tag> is only allowed on enums.",
+        ));
+    }
+
     if attrs.skip_name_checks {
         // Skipping name checks is only available in enforce_order mode
         if attrs.flavor != Flavor::EnforceOrder {
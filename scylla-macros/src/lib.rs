@@ -143,6 +143,19 @@ mod serialize;
 /// Forces Rust struct to have all the fields present in UDT, otherwise
 /// serialization fails.
 ///
+/// `#[scylla(transparent)]`
+///
+/// Serializes a newtype struct (a struct with exactly one field) in the same
+/// way as its only field, instead of serializing it as a UDT. This is useful
+/// for domain types wrapping a byte container, which should be sent as a CQL `blob`:
+///
+/// ```rust
+/// # use scylla::SerializeValue;
+/// #[derive(SerializeValue)]
+/// #[scylla(transparent)]
+/// struct Signature(Vec<u8>);
+/// ```
+///
 /// # Field attributes
 ///
 /// `#[scylla(rename = "name_in_the_udt")]`
@@ -196,6 +209,18 @@ pub fn serialize_value_derive(tokens_input: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// Fields may also borrow their values, so the following struct works as well:
+///
+/// ```rust
+/// # use scylla::SerializeRow;
+/// #[derive(SerializeRow)]
+/// struct MyBorrowedValues<'a> {
+///     a: i32,
+///     b: Option<&'a str>,
+///     c: &'a [u8],
+/// }
+/// ```
+///
 /// # Struct attributes
 ///
 /// `#[scylla(flavor = "flavor_name")]`
@@ -266,6 +291,12 @@ pub fn serialize_value_derive(tokens_input: TokenStream) -> TokenStream {
 ///
 /// Note that the name of this field is ignored and hence the `rename` attribute does not make sense
 /// here and will cause a compilation error.
+///
+/// `#[scylla(unset_when_none)]`
+///
+/// Only allowed on fields of type `Option<T>`. Serializes `None` as an unset value
+/// instead of NULL, leaving the column unchanged when writing.
+/// `Some(value)` is serialized as `value`.
 #[proc_macro_derive(SerializeRow, attributes(scylla))]
 pub fn serialize_row_derive(tokens_input: TokenStream) -> TokenStream {
     match serialize::row::derive_serialize_row(tokens_input) {
@@ -378,6 +409,14 @@ mod deserialize;
 /// column into the first field, second column into the second field and so on.
 /// It will still still verify that the column types and field types match.
 ///
+/// `#[scylla(case_insensitive)]`
+///
+/// If set, the column names are matched to the field names (or the names
+/// given with `rename`) ignoring the ASCII case, so that e.g. the `USER_ID`
+/// column is deserialized into the `user_id` field. Columns whose names differ
+/// only in case are considered duplicates. This attribute can't be used
+/// together with `skip_name_checks`.
+///
 /// ## Field attributes
 ///
 /// `#[scylla(skip)]`
@@ -385,6 +424,14 @@ mod deserialize;
 /// The field will be completely ignored during deserialization and will
 /// be initialized with `Default::default()`.
 ///
+/// `#[scylla(default)]`
+///
+/// This attribute only works with the default `flavor = "match_by_name"`.
+///
+/// If the row does not contain a column for this field, the field will be
+/// initialized with `Default::default()`. This is useful when querying tables
+/// whose schema has changed, e.g. when a column was added.
+///
 /// `#[scylla(default_when_null)]`
 ///
 /// If the value of the field received from DB is null, the field will be
@@ -395,6 +442,14 @@ mod deserialize;
 /// By default, the generated implementation will try to match the Rust field
 /// to a column with the same name. This attribute allows to match to a column
 /// with provided name.
+///
+/// `#[scylla(raw_row)]`
+///
+/// The field does not correspond to any column. Instead, it is initialized
+/// with the serialized bytes of the whole row, exactly as received from the database,
+/// e.g. for auditing purposes. The field must be of type `bytes::Bytes`, which
+/// points into the response frame, so no data is copied. At most one field can have
+/// this attribute, and it can't be combined with other field attributes.
 #[proc_macro_derive(DeserializeRow, attributes(scylla))]
 pub fn deserialize_row_derive(tokens_input: TokenStream) -> TokenStream {
     match deserialize::row::deserialize_row_derive(tokens_input) {
@@ -407,7 +462,8 @@ pub fn deserialize_row_derive(tokens_input: TokenStream) -> TokenStream {
 /// trait that generates an implementation which deserializes a User Defined Type
 /// with the same layout as the Rust struct.
 ///
-/// At the moment, only structs with named fields are supported.
+/// At the moment, only structs with named fields are supported, as well as
+/// enums discriminated by a tag field of the UDT (see `#[scylla(tag = "...")]`).
 ///
 /// This macro properly supports structs with lifetimes, meaning that you can
 /// deserialize UDTs with fields that borrow memory from the serialized response.
@@ -501,6 +557,20 @@ pub fn deserialize_row_derive(tokens_input: TokenStream) -> TokenStream {
 /// If more strictness is desired, this flag makes sure that no excess fields
 /// are present and forces error in case there are some.
 ///
+/// `#[scylla(transparent)]`
+///
+/// Deserializes a newtype struct (a struct with exactly one field) in the same
+/// way as its only field, instead of deserializing it from a UDT. This is useful
+/// for domain types wrapping a byte container, which are stored as a CQL `blob`:
+///
+/// ```rust
+/// # use scylla::DeserializeValue;
+/// #[derive(DeserializeValue)]
+/// # #[scylla(crate = "scylla_cql")]
+/// #[scylla(transparent)]
+/// struct Signature(Vec<u8>);
+/// ```
+///
 /// ## Field attributes
 ///
 /// `#[scylla(skip)]`
@@ -523,6 +593,51 @@ pub fn deserialize_row_derive(tokens_input: TokenStream) -> TokenStream {
 /// By default, the generated implementation will try to match the Rust field
 /// to a UDT field with the same name. This attribute instead allows to match
 /// to a UDT field with provided name.
+///
+/// ## Enum attributes
+///
+/// `#[scylla(tag = "field_name")]`
+///
+/// Deserializes a polymorphic UDT into an enum, whose variant is chosen by the
+/// value of the UDT's `text` (or `ascii`) field with the given name. Each variant
+/// must have exactly one unnamed field, which is deserialized from the whole UDT,
+/// e.g. using a struct which derives `DeserializeValue` itself. The type check
+/// requires the UDT to have the tag field and to fit the types of all variants.
+/// A tag value which does not match any variant fails the deserialization.
+///
+/// ```rust
+/// # use scylla::DeserializeValue;
+/// #[derive(DeserializeValue)]
+/// # #[scylla(crate = "scylla_cql")]
+/// struct Circle {
+///     radius: Option<f64>,
+/// }
+///
+/// #[derive(DeserializeValue)]
+/// # #[scylla(crate = "scylla_cql")]
+/// struct Square {
+///     side: Option<f64>,
+/// }
+///
+/// // Deserializes from `CREATE TYPE ks.shape (kind text, radius double, side double)`.
+/// #[derive(DeserializeValue)]
+/// # #[scylla(crate = "scylla_cql")]
+/// #[scylla(tag = "kind")]
+/// enum Shape {
+///     #[scylla(rename = "circle")]
+///     Circle(Circle),
+///     #[scylla(rename = "square")]
+///     Square(Square),
+/// }
+/// ```
+///
+/// ## Variant attributes
+///
+/// `#[scylla(rename = "tag_value")]`
+///
+/// By default, a variant is chosen when the value of the tag field is equal
+/// to the name of the variant. This attribute instead allows to choose it
+/// for the provided value.
 #[proc_macro_derive(DeserializeValue, attributes(scylla))]
 pub fn deserialize_value_derive(tokens_input: TokenStream) -> TokenStream {
     match deserialize::value::deserialize_value_derive(tokens_input) {
@@ -17,3 +17,38 @@ pub(crate) fn parse_named_fields<'a>(
         Data::Union(u) => Err(syn::Error::new_spanned(u.union_token, create_err_msg())),
     }
 }
+
+/// Parses a newtype struct DeriveInput and returns its only field,
+/// together with a member expression that can be used to access it.
+///
+/// `transparent_attr` is the name of the attribute which requires the struct
+/// to be a newtype, quoted in the style of the current derive's error messages.
+pub(crate) fn parse_newtype_field<'a>(
+    input: &'a DeriveInput,
+    current_derive: &str,
+    transparent_attr: &str,
+) -> Result<(syn::Member, &'a syn::Field), syn::Error> {
+    let create_err_msg = || {
+        format!(
+            "derive({current_derive}) with {transparent_attr} attribute works only for structs with exactly one field"
+        )
+    };
+
+    match &input.data {
+        Data::Struct(data) => {
+            let mut fields = data.fields.iter();
+            match (fields.next(), fields.next()) {
+                (Some(field), None) => {
+                    let member = match &field.ident {
+                        Some(ident) => syn::Member::Named(ident.clone()),
+                        None => syn::Member::Unnamed(0.into()),
+                    };
+                    Ok((member, field))
+                }
+                _ => Err(syn::Error::new_spanned(data.struct_token, create_err_msg())),
+            }
+        }
+        Data::Enum(e) => Err(syn::Error::new_spanned(e.enum_token, create_err_msg())),
+        Data::Union(u) => Err(syn::Error::new_spanned(u.union_token, create_err_msg())),
+    }
+}
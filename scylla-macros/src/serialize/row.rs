@@ -66,6 +66,11 @@ struct FieldAttributes {
     #[darling(default)]
     skip: bool,
 
+    // If true, then `None` is serialized as an unset value instead of NULL.
+    // Only allowed on fields of type `Option<T>`.
+    #[darling(default)]
+    unset_when_none: bool,
+
     // Used for deserialization only. Ignored in serialization.
     #[darling(default)]
     #[darling(rename = "default_when_null")]
@@ -168,6 +173,19 @@ impl Context {
             });
         errors.extend(rename_flatten_errors);
 
+        // Check that flattened fields are not marked as unset when none
+        let unset_flatten_errors = self
+            .fields
+            .iter()
+            .filter(|f| f.attrs.flatten && f.attrs.unset_when_none)
+            .map(|f| {
+                darling::Error::custom(
+                    "`unset_when_none` and `flatten` annotations do not make sense together",
+                )
+                .with_span(&f.ident)
+            });
+        errors.extend(unset_flatten_errors);
+
         // Check for name collisions
         let mut used_names = HashMap::<String, &Field>::new();
         for field in self.fields.iter() {
@@ -266,6 +284,19 @@ impl Generator for ColumnSortingGenerator<'_> {
             .filter(|f| !f.attrs.flatten)
             .collect();
         let nonflattened_fields: Vec<_> = nonflattened.iter().map(|f| &f.ident).collect();
+        let nonflattened_values: Vec<syn::Expr> = nonflattened
+            .iter()
+            .map(|f| {
+                let field = &f.ident;
+                if f.attrs.unset_when_none {
+                    parse_quote! {
+                        &#crate_path::MaybeUnset::from_option(::std::option::Option::as_ref(self.#field))
+                    }
+                } else {
+                    parse_quote!(&self.#field)
+                }
+            })
+            .collect();
         let nonflattened_visited_flag_names: Vec<_> = nonflattened_fields
             .iter()
             .map(|ident| format_ident!("__visited_flag_{}", ident))
@@ -295,7 +326,7 @@ impl Generator for ColumnSortingGenerator<'_> {
                     // first check if the spec name matches a non-flattened column
                     #(#nonflattened_columns => {
                         #crate_path::ser::row::serialize_column::<#struct_name #ty_generics>(
-                            &self.#nonflattened_fields, spec, writer,
+                            #nonflattened_values, spec, writer,
                         )?;
                         if !self.#nonflattened_visited_flag_names {
                             self.#nonflattened_visited_flag_names = true;
@@ -442,8 +473,15 @@ impl Generator for ColumnOrderedGenerator<'_> {
             } else {
                 let column = f.column_name();
                 let enforce_name = !self.ctx.attributes.skip_name_checks;
+                let value: syn::Expr = if f.attrs.unset_when_none {
+                    syn::parse_quote! {
+                        &#crate_path::MaybeUnset::from_option(::std::option::Option::as_ref(&self.#field))
+                    }
+                } else {
+                    syn::parse_quote!(&self.#field)
+                };
                 syn::parse_quote! {
-                    #crate_path::ser::row::NextColumnSerializer::serialize::<Self, #enforce_name>(columns, #column, &self.#field, writer)?;
+                    #crate_path::ser::row::NextColumnSerializer::serialize::<Self, #enforce_name>(columns, #column, #value, writer)?;
                 }
             }
         });
@@ -30,6 +30,12 @@ struct Attributes {
     // the DB will interpret them as NULLs anyway.
     #[darling(default)]
     forbid_excess_udt_fields: bool,
+
+    // If true, then the struct must be a newtype (have exactly one field),
+    // and it will be serialized in the same way as its only field, instead
+    // of being serialized as a UDT.
+    #[darling(default)]
+    transparent: bool,
 }
 
 impl Attributes {
@@ -96,13 +102,18 @@ pub(crate) fn derive_serialize_value(
 ) -> Result<syn::ItemImpl, syn::Error> {
     let input: syn::DeriveInput = syn::parse(tokens_input)?;
     let struct_name = input.ident.clone();
-    let named_fields = crate::parser::parse_named_fields(&input, "SerializeValue")?;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     let attributes = Attributes::from_attributes(&input.attrs)?;
 
     let crate_path = attributes.crate_path();
     let implemented_trait: syn::Path = parse_quote!(#crate_path::SerializeValue);
 
+    if attributes.transparent {
+        return derive_serialize_value_transparent(&input, &attributes);
+    }
+
+    let named_fields = crate::parser::parse_named_fields(&input, "SerializeValue")?;
+
     let fields = named_fields
         .named
         .iter()
@@ -136,6 +147,50 @@ pub(crate) fn derive_serialize_value(
     Ok(res)
 }
 
+// Generates an implementation which delegates serialization to the only field
+// of a newtype struct.
+fn derive_serialize_value_transparent(
+    input: &syn::DeriveInput,
+    attributes: &Attributes,
+) -> Result<syn::ItemImpl, syn::Error> {
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (member, field) =
+        crate::parser::parse_newtype_field(input, "SerializeValue", "the `transparent`")?;
+    let field_type = &field.ty;
+
+    let mut errors = darling::Error::accumulator();
+    if attributes.skip_name_checks
+        || attributes.forbid_excess_udt_fields
+        || attributes.flavor != Flavor::MatchByName
+    {
+        errors.push(
+            darling::Error::custom(
+                "the `skip_name_checks`, `forbid_excess_udt_fields` and `flavor` attributes are not allowed with the `transparent` attribute",
+            )
+            .with_span(struct_name),
+        );
+    }
+    errors.finish()?;
+
+    let crate_path = attributes.crate_path();
+
+    let res = parse_quote! {
+        #[automatically_derived]
+        impl #impl_generics #crate_path::SerializeValue for #struct_name #ty_generics #where_clause {
+            fn serialize<'b>(
+                &self,
+                typ: &#crate_path::ColumnType,
+                writer: #crate_path::CellWriter<'b>,
+            ) -> ::std::result::Result<#crate_path::WrittenCellProof<'b>, #crate_path::SerializationError> {
+                <#field_type as #crate_path::SerializeValue>::serialize(&self.#member, typ, writer)
+                    .map_err(#crate_path::ser::value::ser_error_replace_rust_name::<Self>)
+            }
+        }
+    };
+    Ok(res)
+}
+
 impl Context {
     fn validate(&self, struct_ident: &syn::Ident) -> Result<(), syn::Error> {
         let mut errors = darling::Error::accumulator();
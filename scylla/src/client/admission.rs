@@ -0,0 +1,530 @@
+//! Bounded admission of requests, ordered by their priority.
+//!
+//! Enabled with [`SessionBuilder::max_concurrent_requests`](crate::client::session_builder::SessionBuilder::max_concurrent_requests),
+//! or with [`SessionBuilder::adaptive_concurrency`](crate::client::session_builder::SessionBuilder::adaptive_concurrency)
+//! for a limit which adapts to the load of the cluster.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::num::NonZeroUsize;
+#[cfg(feature = "metrics")]
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+
+use thiserror::Error;
+use tokio::sync::oneshot;
+
+use crate::errors::{DbError, RequestAttemptError};
+#[cfg(feature = "metrics")]
+use crate::observability::metrics::Metrics;
+
+/// Configuration of the adaptive limit of requests executed concurrently by a session.
+///
+/// The limit is adjusted with AIMD (additive increase, multiplicative decrease).
+/// Whenever an attempt fails with an [`Overloaded`](crate::errors::DbError::Overloaded) error,
+/// the limit is multiplied by the decrease factor. Attempts which were already executing
+/// when the limit was lowered are likely to be rejected too, so after a decrease, further
+/// overloads are ignored until as many attempts as the limit before the decrease complete.
+/// After as many successful attempts as the current limit, the limit is increased by one.
+/// The limit starts at the maximum and never leaves the `[min_limit, max_limit]` range.
+///
+/// See [`SessionBuilder::adaptive_concurrency`](crate::client::session_builder::SessionBuilder::adaptive_concurrency).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveConcurrency {
+    min_limit: NonZeroUsize,
+    max_limit: NonZeroUsize,
+    decrease_factor: f64,
+}
+
+impl AdaptiveConcurrency {
+    /// Creates a configuration of a limit which varies between `min_limit` and `max_limit`
+    /// and is multiplied by `decrease_factor` on each overload.
+    ///
+    /// The error is returned if `min_limit` is greater than `max_limit`,
+    /// or if `decrease_factor` is not within the `(0, 1)` range.
+    pub fn new(
+        min_limit: NonZeroUsize,
+        max_limit: NonZeroUsize,
+        decrease_factor: f64,
+    ) -> Result<Self, InvalidAdaptiveConcurrency> {
+        if min_limit > max_limit || !(decrease_factor > 0.0 && decrease_factor < 1.0) {
+            return Err(InvalidAdaptiveConcurrency);
+        }
+        Ok(Self {
+            min_limit,
+            max_limit,
+            decrease_factor,
+        })
+    }
+
+    /// The lowest value of the limit.
+    pub fn min_limit(&self) -> NonZeroUsize {
+        self.min_limit
+    }
+
+    /// The highest value of the limit, which is also its initial value.
+    pub fn max_limit(&self) -> NonZeroUsize {
+        self.max_limit
+    }
+
+    /// The factor by which the limit is multiplied on overload.
+    pub fn decrease_factor(&self) -> f64 {
+        self.decrease_factor
+    }
+}
+
+/// An error returned by [`AdaptiveConcurrency::new()`].
+#[derive(Debug, Error)]
+#[error(
+    "Invalid adaptive concurrency: the minimum limit must not exceed the maximum limit, \
+    and the decrease factor must be within (0, 1)"
+)]
+pub struct InvalidAdaptiveConcurrency;
+
+/// Limits the number of requests executed concurrently by a session.
+///
+/// Requests which can't be admitted immediately wait in a queue. When a request
+/// finishes, the waiting request with the highest priority is admitted;
+/// requests of equal priority are admitted in the order they arrived.
+///
+/// If the queue is adaptive, its limit is adjusted according to the outcomes
+/// of request attempts, including page fetches of pagers, reported with
+/// [`record_attempt`](Self::record_attempt).
+#[derive(Debug)]
+pub(crate) struct AdmissionQueue {
+    state: StdMutex<AdmissionState>,
+    adaptive: Option<AdaptiveConcurrency>,
+    /// Reports the number of waiting requests.
+    #[cfg(feature = "metrics")]
+    metrics: Arc<Metrics>,
+}
+
+#[derive(Debug)]
+struct AdmissionState {
+    limit: usize,
+    executing: usize,
+    /// Successful attempts counted towards the next increase of an adaptive limit.
+    successes: usize,
+    /// Attempts which have to complete before an overload may lower an adaptive limit again.
+    decrease_cooldown: usize,
+    waiters: BinaryHeap<Waiter>,
+    next_seq: u64,
+}
+
+#[derive(Debug)]
+struct Waiter {
+    priority: u8,
+    seq: u64,
+    admit: oneshot::Sender<()>,
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiter {
+    // BinaryHeap is a max-heap: higher priority first, then earlier arrival.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl AdmissionQueue {
+    pub(crate) fn new(
+        max_concurrent_requests: NonZeroUsize,
+        #[cfg(feature = "metrics")] metrics: Arc<Metrics>,
+    ) -> Self {
+        Self::with_limit(
+            max_concurrent_requests,
+            None,
+            #[cfg(feature = "metrics")]
+            metrics,
+        )
+    }
+
+    pub(crate) fn new_adaptive(
+        adaptive: AdaptiveConcurrency,
+        #[cfg(feature = "metrics")] metrics: Arc<Metrics>,
+    ) -> Self {
+        Self::with_limit(
+            adaptive.max_limit,
+            Some(adaptive),
+            #[cfg(feature = "metrics")]
+            metrics,
+        )
+    }
+
+    fn with_limit(
+        limit: NonZeroUsize,
+        adaptive: Option<AdaptiveConcurrency>,
+        #[cfg(feature = "metrics")] metrics: Arc<Metrics>,
+    ) -> Self {
+        Self {
+            state: StdMutex::new(AdmissionState {
+                limit: limit.get(),
+                executing: 0,
+                successes: 0,
+                decrease_cooldown: 0,
+                waiters: BinaryHeap::new(),
+                next_seq: 0,
+            }),
+            adaptive,
+            #[cfg(feature = "metrics")]
+            metrics,
+        }
+    }
+
+    /// The current limit of requests executed concurrently.
+    pub(crate) fn limit(&self) -> usize {
+        self.state.lock().unwrap().limit
+    }
+
+    /// Waits until a request of the given priority is admitted.
+    /// The request counts as executing until the returned permit is dropped.
+    pub(crate) async fn acquire(&self, priority: u8) -> AdmissionPermit<'_> {
+        let receiver = {
+            let mut state = self.state.lock().unwrap();
+            if state.executing < state.limit && state.waiters.is_empty() {
+                state.executing += 1;
+                return AdmissionPermit { queue: self };
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            let seq = state.next_seq;
+            state.next_seq += 1;
+            state.waiters.push(Waiter {
+                priority,
+                seq,
+                admit: sender,
+            });
+            #[cfg(feature = "metrics")]
+            self.metrics.inc_admission_queue_depth();
+            receiver
+        };
+
+        let mut pending = PendingAdmission {
+            queue: self,
+            receiver: Some(receiver),
+        };
+        // The sender is only dropped after sending, so the receiver can't fail.
+        let _ = pending.receiver.as_mut().unwrap().await;
+        pending.receiver = None;
+
+        AdmissionPermit { queue: self }
+    }
+
+    /// Passes a released slot to the first waiting request, or makes it available.
+    fn release(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.executing -= 1;
+        self.admit_waiters(&mut state);
+    }
+
+    /// Admits waiting requests as long as the limit allows.
+    fn admit_waiters(&self, state: &mut AdmissionState) {
+        while state.executing < state.limit {
+            let Some(waiter) = state.waiters.pop() else {
+                return;
+            };
+            // Requests that stopped waiting have dropped their receivers.
+            if waiter.admit.send(()).is_ok() {
+                state.executing += 1;
+                #[cfg(feature = "metrics")]
+                self.metrics.dec_admission_queue_depth();
+            }
+        }
+    }
+
+    /// Reports the outcome of an attempt, if it affects an adaptive limit.
+    pub(crate) fn record_attempt<T>(&self, result: &Result<T, RequestAttemptError>) {
+        match result {
+            Ok(_) => self.record_success(),
+            Err(RequestAttemptError::DbError(DbError::Overloaded, _)) => self.record_overload(),
+            Err(_) => (),
+        }
+    }
+
+    /// Reports a successful attempt. Raises an adaptive limit by one after
+    /// as many successes as the current limit.
+    pub(crate) fn record_success(&self) {
+        let Some(adaptive) = &self.adaptive else {
+            return;
+        };
+        let mut state = self.state.lock().unwrap();
+        state.decrease_cooldown = state.decrease_cooldown.saturating_sub(1);
+        state.successes += 1;
+        if state.successes >= state.limit && state.limit < adaptive.max_limit.get() {
+            state.limit += 1;
+            state.successes = 0;
+            self.admit_waiters(&mut state);
+        }
+    }
+
+    /// Reports an attempt which failed because the coordinator was overloaded.
+    /// Lowers an adaptive limit by the decrease factor, unless it was already
+    /// lowered less than the previous limit's worth of attempts ago.
+    ///
+    /// Requests executing above the lowered limit are not interrupted,
+    /// but no new request is admitted until they finish.
+    pub(crate) fn record_overload(&self) {
+        let Some(adaptive) = &self.adaptive else {
+            return;
+        };
+        let mut state = self.state.lock().unwrap();
+        if state.decrease_cooldown > 0 {
+            state.decrease_cooldown -= 1;
+            return;
+        }
+        // The overloads of attempts sent before the decrease don't lower the limit again.
+        state.decrease_cooldown = state.limit;
+        let decreased = (state.limit as f64 * adaptive.decrease_factor) as usize;
+        state.limit = decreased.max(adaptive.min_limit.get());
+        state.successes = 0;
+    }
+
+    #[cfg(test)]
+    fn waiting(&self) -> usize {
+        self.state.lock().unwrap().waiters.len()
+    }
+}
+
+/// Represents an admitted request. Releases its slot when dropped.
+#[derive(Debug)]
+pub(crate) struct AdmissionPermit<'a> {
+    queue: &'a AdmissionQueue,
+}
+
+impl Drop for AdmissionPermit<'_> {
+    fn drop(&mut self) {
+        self.queue.release();
+    }
+}
+
+/// Makes sure that a slot passed to a request which stopped waiting is not lost.
+struct PendingAdmission<'a> {
+    queue: &'a AdmissionQueue,
+    receiver: Option<oneshot::Receiver<()>>,
+}
+
+impl Drop for PendingAdmission<'_> {
+    fn drop(&mut self) {
+        if let Some(mut receiver) = self.receiver.take() {
+            receiver.close();
+            if receiver.try_recv().is_ok() {
+                self.queue.release();
+            } else {
+                // Not admitted, so the request stops waiting only now.
+                #[cfg(feature = "metrics")]
+                self.queue.metrics.dec_admission_queue_depth();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::sync::Arc;
+
+    use tokio::sync::mpsc;
+
+    use super::{AdaptiveConcurrency, AdmissionQueue};
+    #[cfg(feature = "metrics")]
+    use crate::observability::metrics::Metrics;
+
+    fn new_queue(max_concurrent_requests: usize) -> AdmissionQueue {
+        AdmissionQueue::new(
+            NonZeroUsize::new(max_concurrent_requests).unwrap(),
+            #[cfg(feature = "metrics")]
+            Arc::new(Metrics::new()),
+        )
+    }
+
+    #[tokio::test]
+    async fn high_priority_requests_are_admitted_first() {
+        let queue = Arc::new(new_queue(1));
+        let permit = queue.acquire(0).await;
+
+        let (admitted_tx, mut admitted_rx) = mpsc::unbounded_channel();
+        for (waiting, priority) in [0, 10, 5, 10].into_iter().enumerate() {
+            let task_queue = Arc::clone(&queue);
+            let admitted_tx = admitted_tx.clone();
+            tokio::spawn(async move {
+                let _permit = task_queue.acquire(priority).await;
+                admitted_tx.send(priority).unwrap();
+            });
+            // Let the request start waiting, so that the arrival order is deterministic.
+            while queue.waiting() <= waiting {
+                tokio::task::yield_now().await;
+            }
+        }
+        drop(admitted_tx);
+
+        drop(permit);
+        let mut admitted = Vec::new();
+        while let Some(priority) = admitted_rx.recv().await {
+            admitted.push(priority);
+        }
+        assert_eq!(admitted, vec![10, 10, 5, 0]);
+    }
+
+    fn new_adaptive_queue(min_limit: usize, max_limit: usize) -> AdmissionQueue {
+        AdmissionQueue::new_adaptive(
+            AdaptiveConcurrency::new(
+                NonZeroUsize::new(min_limit).unwrap(),
+                NonZeroUsize::new(max_limit).unwrap(),
+                0.5,
+            )
+            .unwrap(),
+            #[cfg(feature = "metrics")]
+            Arc::new(Metrics::new()),
+        )
+    }
+
+    #[test]
+    fn adaptive_limit_decreases_on_overload() {
+        let queue = new_adaptive_queue(3, 32);
+        assert_eq!(queue.limit(), 32);
+
+        // The first overload lowers the limit, but the overloads of the 32 attempts
+        // which could have been executing at the time don't.
+        for _ in 0..=32 {
+            queue.record_overload();
+        }
+        assert_eq!(queue.limit(), 16);
+
+        // Once as many attempts as the previous limit have completed,
+        // the next overload lowers the limit again.
+        let mut limits = Vec::new();
+        for _ in 0..4 {
+            let window = queue.limit();
+            queue.record_overload();
+            limits.push(queue.limit());
+            for _ in 0..window {
+                queue.record_overload();
+            }
+        }
+        assert_eq!(limits, vec![8, 4, 3, 3]);
+    }
+
+    #[test]
+    fn adaptive_limit_increases_slowly_on_success() {
+        let queue = new_adaptive_queue(1, 6);
+        queue.record_overload();
+        assert_eq!(queue.limit(), 3);
+
+        // The limit is raised by one after as many successes as the limit.
+        for _ in 0..2 {
+            queue.record_success();
+        }
+        assert_eq!(queue.limit(), 3);
+        queue.record_success();
+        assert_eq!(queue.limit(), 4);
+
+        for _ in 0..100 {
+            queue.record_success();
+        }
+        assert_eq!(queue.limit(), 6);
+    }
+
+    #[tokio::test]
+    async fn lowered_limit_applies_to_new_requests() {
+        let queue = new_adaptive_queue(1, 2);
+        let first = queue.acquire(0).await;
+        let second = queue.acquire(0).await;
+        queue.record_overload();
+
+        // Both requests keep executing, but the next one must wait for both of them.
+        let mut waiting = Box::pin(queue.acquire(0));
+        assert!(futures::poll!(waiting.as_mut()).is_pending());
+        drop(first);
+        assert!(futures::poll!(waiting.as_mut()).is_pending());
+        drop(second);
+        let _permit = waiting.await;
+
+        // Once raised, the limit lets another request in.
+        queue.record_success();
+        let _another = queue.acquire(0).await;
+    }
+
+    #[test]
+    fn invalid_adaptive_concurrency_is_rejected() {
+        let limit = |limit| NonZeroUsize::new(limit).unwrap();
+        assert!(AdaptiveConcurrency::new(limit(10), limit(5), 0.5).is_err());
+        for decrease_factor in [0.0, 1.0, -0.5, f64::NAN] {
+            assert!(AdaptiveConcurrency::new(limit(1), limit(5), decrease_factor).is_err());
+        }
+        assert!(AdaptiveConcurrency::new(limit(5), limit(5), 0.9).is_ok());
+    }
+
+    #[tokio::test]
+    async fn slot_is_not_lost_when_waiting_request_is_cancelled() {
+        let queue = new_queue(1);
+        let permit = queue.acquire(0).await;
+
+        let mut cancelled = Box::pin(queue.acquire(5));
+        assert!(futures::poll!(cancelled.as_mut()).is_pending());
+        assert_eq!(queue.waiting(), 1);
+
+        // The slot is passed to the waiting request, which is dropped before noticing it.
+        drop(permit);
+        drop(cancelled);
+
+        let _permit = queue.acquire(0).await;
+        assert_eq!(queue.waiting(), 0);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[tokio::test]
+    async fn queue_depth_and_high_water_mark_are_reported() {
+        let metrics = Arc::new(Metrics::new());
+        let queue = Arc::new(AdmissionQueue::new(
+            NonZeroUsize::new(2).unwrap(),
+            Arc::clone(&metrics),
+        ));
+        let permits = [queue.acquire(0).await, queue.acquire(0).await];
+        assert_eq!(metrics.admission_queue_depth(), 0);
+        assert_eq!(metrics.admission_queue_high_water(), 0);
+
+        // Saturate the queue with requests which can't be admitted.
+        let waiting_requests = (0..5)
+            .map(|_| {
+                let task_queue = Arc::clone(&queue);
+                tokio::spawn(async move {
+                    let _permit = task_queue.acquire(0).await;
+                })
+            })
+            .collect::<Vec<_>>();
+        while queue.waiting() < 5 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(metrics.admission_queue_depth(), 5);
+        assert_eq!(metrics.admission_queue_high_water(), 5);
+
+        // A cancelled request stops waiting.
+        let mut cancelled = Box::pin(queue.acquire(0));
+        assert!(futures::poll!(cancelled.as_mut()).is_pending());
+        assert_eq!(metrics.admission_queue_depth(), 6);
+        drop(cancelled);
+        assert_eq!(metrics.admission_queue_depth(), 5);
+
+        drop(permits);
+        for request in waiting_requests {
+            request.await.unwrap();
+        }
+        assert_eq!(metrics.admission_queue_depth(), 0);
+        assert_eq!(metrics.admission_queue_high_water(), 6);
+    }
+}
@@ -2,6 +2,8 @@
 //! prepared statements automatically and reuses them when possible.
 
 use crate::errors::{ExecutionError, PagerExecutionError, PrepareError};
+#[cfg(feature = "metrics")]
+use crate::observability::metrics::Metrics;
 use crate::response::query_result::QueryResult;
 use crate::response::{PagingState, PagingStateResponse};
 use crate::serialize::batch::BatchValues;
@@ -11,10 +13,12 @@ use crate::statement::prepared::{PreparedStatement, UnconfiguredPreparedStatemen
 use crate::statement::unprepared::Statement;
 use dashmap::DashMap;
 use futures::future::try_join_all;
+use std::collections::BTreeMap;
 use std::collections::hash_map::RandomState;
 use std::fmt;
 use std::hash::BuildHasher;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 use crate::client::pager::QueryPager;
 use crate::client::session::Session;
@@ -26,11 +30,25 @@ where
 {
     session: Arc<Session>,
     /// The prepared statement cache size
-    /// If a prepared statement is added while the limit is reached, the oldest prepared statement
-    /// is removed from the cache
+    /// If a prepared statement is added while the limit is reached, the least recently used
+    /// prepared statement is removed from the cache
     max_capacity: usize,
-    cache: DashMap<String, UnconfiguredPreparedStatement, S>,
+    cache: DashMap<String, CacheEntry, S>,
+    /// Incremented on every use of a cached statement.
+    clock: AtomicU64,
+    /// Cached statements, keyed by the value of the clock at their last use, as known
+    /// when they were ordered. See [`CachingSession::evict_least_recently_used`].
+    eviction_order: Mutex<BTreeMap<u64, String>>,
     use_cached_metadata: bool,
+    #[cfg(feature = "metrics")]
+    metrics: Arc<Metrics>,
+}
+
+/// A cached statement, along with the value of the clock at its last use.
+#[derive(Debug)]
+struct CacheEntry {
+    statement: UnconfiguredPreparedStatement,
+    last_use: AtomicU64,
 }
 
 impl<S> fmt::Debug for CachingSession<S>
@@ -60,12 +78,10 @@ where
             cache_size > 0,
             "prepared statement cache capacity must be greater than 0"
         );
-        Self {
-            session: Arc::new(session),
-            max_capacity: cache_size,
-            cache: Default::default(),
-            use_cached_metadata: false,
-        }
+        CachingSessionBuilder::new(session)
+            .max_capacity(cache_size)
+            .hasher(S::default())
+            .build()
     }
 }
 
@@ -84,12 +100,10 @@ where
             cache_size > 0,
             "prepared statement cache capacity must be greater than 0"
         );
-        Self {
-            session: Arc::new(session),
-            max_capacity: cache_size,
-            cache: DashMap::with_hasher(hasher),
-            use_cached_metadata: false,
-        }
+        CachingSessionBuilder::new(session)
+            .max_capacity(cache_size)
+            .hasher(hasher)
+            .build()
     }
 }
 
@@ -202,9 +216,15 @@ where
     ) -> Result<PreparedStatement, PrepareError> {
         let query = query.into();
 
-        if let Some(raw) = self.cache.get(&query.contents) {
+        if let Some(entry) = self.cache.get(&query.contents) {
+            entry.last_use.fetch_max(
+                self.clock.fetch_add(1, Ordering::Relaxed),
+                Ordering::Relaxed,
+            );
             let page_size = query.get_validated_page_size();
-            let mut stmt = raw.make_configured_handle(query.config, page_size);
+            let mut stmt = entry
+                .statement
+                .make_configured_handle(query.config, page_size);
             stmt.set_use_cached_result_metadata(self.use_cached_metadata);
             Ok(stmt)
         } else {
@@ -224,36 +244,109 @@ where
             // some thread for a bit. If this becomes a problem then maybe we should research how
             // some more robust caching crates are implemented?
             while self.max_capacity <= self.cache.len() {
-                // Cache is full, remove the first entry
-                // Don't hold a reference into the map (that's why the to_string() is called)
-                // This is because the documentation of the remove fn tells us that it may deadlock
-                // when holding some sort of reference into the map
-                let query = self.cache.iter().next().map(|c| c.key().to_string());
-
-                // Don't inline this: https://stackoverflow.com/questions/69873846/an-owned-value-is-still-references-somehow
-                if let Some(q) = query {
-                    self.cache.remove(&q);
+                // Cache is full, remove the least recently used entry
+                if !self.evict_least_recently_used() {
+                    break;
                 }
             }
 
-            let raw = prepared.make_unconfigured_handle();
-            self.cache.insert(query_contents, raw);
+            if let Some(repreparation_set) = self.session.repreparation_set() {
+                repreparation_set.register(&query_contents);
+            }
+            let now = self.clock.fetch_add(1, Ordering::Relaxed);
+            let entry = CacheEntry {
+                statement: prepared.make_unconfigured_handle(),
+                last_use: AtomicU64::new(now),
+            };
+            // The statement is ordered only once it is cached, so that a concurrent eviction
+            // doesn't drop it from the order.
+            let replaced = self.cache.insert(query_contents.clone(), entry).is_some();
+            self.eviction_order
+                .lock()
+                .unwrap()
+                .insert(now, query_contents);
+            if replaced {
+                // The statement was cached concurrently, and is already registered.
+                if let Some(repreparation_set) = self.session.repreparation_set() {
+                    repreparation_set.deregister(prepared.get_statement());
+                }
+            } else {
+                #[cfg(feature = "metrics")]
+                self.metrics.inc_cached_prepared_statements();
+            }
 
             Ok(prepared)
         }
     }
 
+    /// Evicts the least recently used statement from the cache.
+    /// Returns false if there is no statement to evict.
+    ///
+    /// Cache hits only update the clock value of the used statement, so that they don't
+    /// contend on a lock. The eviction order is therefore brought up to date lazily:
+    /// a statement used since it was ordered is ordered again by its last use.
+    fn evict_least_recently_used(&self) -> bool {
+        let mut eviction_order = self.eviction_order.lock().unwrap();
+        while let Some((ordered_use, contents)) = eviction_order.pop_first() {
+            if self
+                .cache
+                .remove_if(&contents, |_, entry| {
+                    entry.last_use.load(Ordering::Relaxed) <= ordered_use
+                })
+                .is_some()
+            {
+                #[cfg(feature = "metrics")]
+                self.metrics.sub_cached_prepared_statements(1);
+                if let Some(repreparation_set) = self.session.repreparation_set() {
+                    repreparation_set.deregister(&contents);
+                }
+                return true;
+            }
+
+            // The statement was used since it was ordered, or it is no longer cached.
+            let last_use = self
+                .cache
+                .get(&contents)
+                .map(|entry| entry.last_use.load(Ordering::Relaxed));
+            if let Some(last_use) = last_use {
+                eviction_order.insert(last_use, contents);
+            }
+        }
+        false
+    }
+
     /// Retrieves the maximum capacity of the prepared statements cache.
     pub fn get_max_capacity(&self) -> usize {
         self.max_capacity
     }
 
+    /// Retrieves the number of prepared statements currently in the cache.
+    pub fn get_cache_size(&self) -> usize {
+        self.cache.len()
+    }
+
     /// Retrieves the underlying [Session] instance.
     pub fn get_session(&self) -> &Session {
         &self.session
     }
 }
 
+impl<S> Drop for CachingSession<S>
+where
+    S: Clone + BuildHasher,
+{
+    fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        self.metrics
+            .sub_cached_prepared_statements(self.cache.len() as u64);
+        if let Some(repreparation_set) = self.session.repreparation_set() {
+            for entry in self.cache.iter() {
+                repreparation_set.deregister(entry.key());
+            }
+        }
+    }
+}
+
 /// The default cache capacity set on the [CachingSessionBuilder].
 /// Can be changed using [CachingSessionBuilder::max_capacity].
 pub const DEFAULT_MAX_CAPACITY: usize = 128;
@@ -352,9 +445,13 @@ where
     /// Finishes configuration of [CachingSession].
     pub fn build(self) -> CachingSession<S> {
         CachingSession {
+            #[cfg(feature = "metrics")]
+            metrics: self.session.get_metrics(),
             session: self.session,
             max_capacity: self.max_capacity,
             cache: DashMap::with_hasher(self.hasher),
+            clock: AtomicU64::new(0),
+            eviction_order: Default::default(),
             use_cached_metadata: self.use_cached_metadata,
         }
     }
@@ -501,6 +598,7 @@ mod tests {
         assert_eq!(session.cache.len(), 1);
 
         session.cache.clear();
+        session.eviction_order.lock().unwrap().clear();
 
         session
     }
@@ -543,6 +641,38 @@ mod tests {
         teardown_keyspace(session.get_session()).await;
     }
 
+    /// Test that when the cache is full, the least recently used query is evicted
+    #[tokio::test]
+    async fn test_full_evicts_least_recently_used() {
+        setup_tracing();
+        let session: CachingSession = CachingSession::from(new_for_test(true).await, 2);
+
+        let first_query = "SELECT * FROM test_table";
+        let middle_query = "INSERT INTO test_table(a, b) VALUES (?, ?)";
+        let last_query = "UPDATE test_table SET b = ? WHERE a = 1";
+
+        for query in [first_query, middle_query, first_query, last_query] {
+            session.add_prepared_statement(&query.into()).await.unwrap();
+        }
+
+        // The first query was used more recently than the middle one
+        assert_eq!(2, session.get_cache_size());
+        assert!(session.cache.get(first_query).is_some());
+        assert!(session.cache.get(middle_query).is_none());
+        assert!(session.cache.get(last_query).is_some());
+
+        #[cfg(feature = "metrics")]
+        assert_eq!(
+            2,
+            session
+                .get_session()
+                .get_metrics()
+                .get_cached_prepared_statements()
+        );
+
+        teardown_keyspace(session.get_session()).await;
+    }
+
     /// Checks that the same prepared statement is reused when executing the same query twice
     #[tokio::test]
     async fn test_execute_unpaged_cached() {
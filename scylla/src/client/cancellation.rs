@@ -0,0 +1,71 @@
+//! Cancelling requests from outside of the task executing them.
+
+use std::future::Future;
+
+use futures::future::{AbortHandle, Abortable, Aborted};
+
+use crate::errors::ExecutionError;
+
+/// A handle allowing to cancel an in-flight request.
+///
+/// Cancelling a request makes its execution complete with [`ExecutionError::Cancelled`].
+/// Attempts which were already sent are abandoned: their stream ids are marked as orphaned
+/// and are reclaimed once the responses arrive, just like when a request future is dropped.
+#[derive(Debug, Clone)]
+pub struct CancelHandle(AbortHandle);
+
+impl CancelHandle {
+    /// Cancels the request. Has no effect if the request has already completed.
+    pub fn cancel(&self) {
+        self.0.abort();
+    }
+
+    /// Returns whether [`CancelHandle::cancel`] was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.is_aborted()
+    }
+}
+
+/// Makes a request execution cancellable with the returned handle.
+pub(crate) fn cancellable<T>(
+    execution: impl Future<Output = Result<T, ExecutionError>>,
+) -> (
+    CancelHandle,
+    impl Future<Output = Result<T, ExecutionError>>,
+) {
+    let (handle, registration) = AbortHandle::new_pair();
+    let execution = Abortable::new(execution, registration);
+    let execution = async move {
+        match execution.await {
+            Ok(result) => result,
+            Err(Aborted) => Err(ExecutionError::Cancelled),
+        }
+    };
+    (CancelHandle(handle), execution)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::cancellable;
+    use crate::errors::ExecutionError;
+
+    #[tokio::test]
+    async fn cancelled_execution_returns_cancelled_error() {
+        let (handle, execution) =
+            cancellable(futures::future::pending::<Result<(), ExecutionError>>());
+        let execution = tokio::spawn(execution);
+
+        handle.cancel();
+        assert!(handle.is_cancelled());
+        assert_matches!(execution.await.unwrap(), Err(ExecutionError::Cancelled));
+    }
+
+    #[tokio::test]
+    async fn cancelling_completed_execution_has_no_effect() {
+        let (handle, execution) = cancellable(async { Ok::<_, ExecutionError>(42) });
+        assert_matches!(execution.await, Ok(42));
+        handle.cancel();
+    }
+}
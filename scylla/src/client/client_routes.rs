@@ -127,6 +127,7 @@ pub(crate) struct ClientRoutesAddressTranslator {
     hostname_overrides: HashMap<String, String>,
     client_routes: RwLock<HashMap<Uuid, KnownHostRoutes>>,
     hostname_resolution_timeout: Option<Duration>,
+    prefer_ipv6: bool,
     use_tls: bool,
 }
 
@@ -195,9 +196,15 @@ impl ClientRoutesAddressTranslator {
             hostname_overrides,
             client_routes: RwLock::new(HashMap::new()),
             hostname_resolution_timeout,
+            prefer_ipv6: false,
             use_tls,
         }
     }
+
+    pub(crate) fn with_prefer_ipv6(mut self, prefer_ipv6: bool) -> Self {
+        self.prefer_ipv6 = prefer_ipv6;
+        self
+    }
 }
 
 // We want connection ids to be "sticky": once a connection is successfully established to a node
@@ -434,9 +441,13 @@ impl AddressTranslator for ClientRoutesAddressTranslator {
             format!("{}:{}", hostname, port)
         };
 
-        let addr = resolve_hostname(&hostport, self.hostname_resolution_timeout)
-            .await
-            .map_err(TranslationError::DnsLookupFailed)?;
+        let addr = resolve_hostname(
+            &hostport,
+            self.hostname_resolution_timeout,
+            self.prefer_ipv6,
+        )
+        .await
+        .map_err(TranslationError::DnsLookupFailed)?;
 
         debug!(
             "ClientRoutesAddressTranslator: translated host_id {} to address {}",
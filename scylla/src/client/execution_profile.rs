@@ -162,13 +162,15 @@
 //! ```
 //!
 
-use std::{fmt::Debug, sync::Arc, time::Duration};
+use std::{collections::HashMap, fmt::Debug, sync::Arc, time::Duration};
 
 use crate::frame::types::{Consistency, SerialConsistency};
 use arc_swap::ArcSwap;
 
 use crate::policies::load_balancing::LoadBalancingPolicy;
-use crate::policies::retry::RetryPolicy;
+use crate::policies::retry::{
+    MappedRetrySession, RetryDecision, RetryErrorKind, RetryPolicy, RetrySession,
+};
 use crate::policies::speculative_execution::SpeculativeExecutionPolicy;
 
 pub(crate) mod defaults {
@@ -206,6 +208,7 @@ pub(crate) mod defaults {
                 serial_consistency: serial_consistency(),
                 load_balancing_policy: load_balancing_policy(),
                 retry_policy: retry_policy(),
+                retry_decisions: Default::default(),
                 speculative_execution_policy: speculative_execution_policy(),
             }
         }
@@ -235,6 +238,7 @@ pub struct ExecutionProfileBuilder {
     serial_consistency: Option<Option<SerialConsistency>>,
     load_balancing_policy: Option<Arc<dyn LoadBalancingPolicy>>,
     retry_policy: Option<Arc<dyn RetryPolicy>>,
+    retry_decisions: Option<Arc<HashMap<RetryErrorKind, RetryDecision>>>,
     speculative_execution_policy: Option<Option<Arc<dyn SpeculativeExecutionPolicy>>>,
 }
 
@@ -318,6 +322,41 @@ impl ExecutionProfileBuilder {
         self
     }
 
+    /// Sets fixed retry decisions for given kinds of errors.
+    ///
+    /// When an attempt of an idempotent request fails with an error of a kind
+    /// present in the map, the corresponding decision is applied and the retry
+    /// policy is not consulted. Errors of other kinds, as well as all errors of
+    /// non-idempotent requests, are handled by the retry policy as usual.
+    /// At most 3 retries per request are performed on behalf of the map;
+    /// once they are used up, mapped retry decisions become [`RetryDecision::DontRetry`].
+    ///
+    /// The decisions are not applied to statements which have their own retry policy
+    /// set with `set_retry_policy`.
+    /// This allows adjusting retry behaviour declaratively, without implementing
+    /// a custom [`RetryPolicy`].
+    /// The default is an empty map.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::execution_profile::ExecutionProfile;
+    /// # use scylla::policies::retry::{RetryDecision, RetryErrorKind};
+    /// # use std::collections::HashMap;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let profile: ExecutionProfile = ExecutionProfile::builder()
+    ///     .retry_decisions(HashMap::from([
+    ///         (RetryErrorKind::Overloaded, RetryDecision::RetryNextTarget(None)),
+    ///         (RetryErrorKind::WriteTimeout, RetryDecision::DontRetry),
+    ///     ]))
+    ///     .build();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn retry_decisions(mut self, decisions: HashMap<RetryErrorKind, RetryDecision>) -> Self {
+        self.retry_decisions = Some(Arc::new(decisions));
+        self
+    }
+
     /// Sets the speculative execution policy.
     /// The default is None.
     /// # Example
@@ -377,6 +416,7 @@ impl ExecutionProfileBuilder {
                 .load_balancing_policy
                 .unwrap_or_else(defaults::load_balancing_policy),
             retry_policy: self.retry_policy.unwrap_or_else(defaults::retry_policy),
+            retry_decisions: self.retry_decisions.unwrap_or_default(),
             speculative_execution_policy: self
                 .speculative_execution_policy
                 .unwrap_or_else(defaults::speculative_execution_policy),
@@ -408,6 +448,7 @@ pub(crate) struct ExecutionProfileInner {
 
     pub(crate) load_balancing_policy: Arc<dyn LoadBalancingPolicy>,
     pub(crate) retry_policy: Arc<dyn RetryPolicy>,
+    pub(crate) retry_decisions: Arc<HashMap<RetryErrorKind, RetryDecision>>,
     pub(crate) speculative_execution_policy: Option<Arc<dyn SpeculativeExecutionPolicy>>,
 }
 
@@ -420,9 +461,31 @@ impl ExecutionProfileInner {
             serial_consistency: Some(self.serial_consistency),
             load_balancing_policy: Some(self.load_balancing_policy.clone()),
             retry_policy: Some(self.retry_policy.clone()),
+            retry_decisions: Some(self.retry_decisions.clone()),
             speculative_execution_policy: Some(self.speculative_execution_policy.clone()),
         }
     }
+
+    /// Starts a new retry session for a request, using the retry policy set on the statement
+    /// if any. Otherwise, the retry policy from this profile is used, with retry decisions
+    /// configured on this profile taking precedence over it.
+    pub(crate) fn new_retry_session(
+        &self,
+        statement_retry_policy: Option<&Arc<dyn RetryPolicy>>,
+    ) -> Box<dyn RetrySession> {
+        if let Some(statement_retry_policy) = statement_retry_policy {
+            return statement_retry_policy.new_session();
+        }
+        let retry_session = self.retry_policy.new_session();
+        if self.retry_decisions.is_empty() {
+            retry_session
+        } else {
+            Box::new(MappedRetrySession::new(
+                Arc::clone(&self.retry_decisions),
+                retry_session,
+            ))
+        }
+    }
 }
 
 impl ExecutionProfile {
@@ -438,6 +501,7 @@ impl ExecutionProfile {
             serial_consistency: None,
             load_balancing_policy: None,
             retry_policy: None,
+            retry_decisions: None,
             speculative_execution_policy: None,
         }
     }
@@ -485,6 +549,11 @@ impl ExecutionProfile {
         &self.0.retry_policy
     }
 
+    /// Gets retry decisions for given kinds of errors associated with this profile.
+    pub fn get_retry_decisions(&self) -> &HashMap<RetryErrorKind, RetryDecision> {
+        &self.0.retry_decisions
+    }
+
     /// Gets speculative execution policy associated with this profile.
     pub fn get_speculative_execution_policy(&self) -> Option<&Arc<dyn SpeculativeExecutionPolicy>> {
         self.0.speculative_execution_policy.as_ref()
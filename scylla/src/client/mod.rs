@@ -15,10 +15,21 @@
 //! - [QueryPager](pager::QueryPager) and [TypedRowStream](pager::TypedRowStream) - entities that provide
 //!   automated transparent paging of a query.
 
+mod admission;
+pub use admission::{AdaptiveConcurrency, InvalidAdaptiveConcurrency};
+
+mod cancellation;
+pub use cancellation::CancelHandle;
+
 pub mod execution_profile;
 
 pub mod pager;
 
+mod pipeline;
+pub use pipeline::OrderedPipeline;
+
+pub mod read_your_writes;
+
 pub mod client_routes;
 
 pub mod caching_session;
@@ -32,4 +43,7 @@ pub mod session_builder;
 
 pub use crate::frame::Compression;
 
-pub use crate::network::{PoolSize, WriteCoalescingDelay};
+pub use crate::network::{
+    ConnectionSelection, ErrorRateCircuitBreaker, InvalidErrorRateCircuitBreaker,
+    InvalidPoolAutoscaling, PoolAutoscaling, PoolSize, WriteCoalescingDelay,
+};
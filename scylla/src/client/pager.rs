@@ -4,6 +4,7 @@
 
 use std::future::Future;
 use std::net::SocketAddr;
+use std::num::NonZeroU32;
 use std::ops::ControlFlow;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -12,25 +13,29 @@ use std::time::Duration;
 
 use crate::deserialize::result::RawRowLendingIterator;
 use crate::deserialize::row::{ColumnIterator, DeserializeRow};
-use crate::deserialize::{DeserializationError, TypeCheckError};
+use crate::deserialize::{DeserializationError, FrameSlice, TypeCheckError};
 use crate::frame::frame_errors::ResultMetadataAndRowsCountParseError;
 use crate::frame::request::query::PagingState;
 use crate::frame::response::NonErrorResponseWithDeserializedMetadataV2 as NonErrorResponseWithDeserializedMetadata;
-use crate::frame::response::result::{DeserializedMetadataAndRawRows, SchemaChange, SetKeyspace};
+use crate::frame::response::result::{
+    ColumnSpec, DeserializedMetadataAndRawRows, SchemaChange, SetKeyspace,
+};
 use crate::frame::types::{Consistency, SerialConsistency};
 use crate::serialize::row::SerializedValues;
+use bytes::Bytes;
 use futures::Stream;
 use std::result::Result;
 use thiserror::Error;
 use tokio::sync::{mpsc, oneshot};
 
+use crate::client::admission::AdmissionQueue;
 use crate::client::execution_profile::ExecutionProfileInner;
 use crate::client::session::{AutoSchemaAwaitingError, Session};
-use crate::cluster::{ClusterState, NodeRef};
+use crate::cluster::{ClusterState, NodeAddr, NodeRef};
 use crate::deserialize::DeserializeOwnedRow;
 use crate::errors::{
-    MetadataError, PagerExecutionError, RequestAttemptError, RequestError, SchemaAgreementError,
-    UseKeyspaceError,
+    ConnectionPoolError, MetadataError, PagerExecutionError, RequestAttemptError, RequestError,
+    SchemaAgreementError, UseKeyspaceError,
 };
 use crate::frame::response::result;
 use crate::network::Connection;
@@ -38,13 +43,16 @@ use crate::observability::driver_tracing::RequestSpan;
 use crate::observability::history::{self, HistoryListener};
 #[cfg(feature = "metrics")]
 use crate::observability::metrics::Metrics;
+use crate::observability::metrics_sink::{MetricsSink, RequestCompleteEvent, RetryEvent};
 use crate::policies::load_balancing::{self, LoadBalancingPolicy, RoutingInfo};
 use crate::policies::retry::{RequestInfo, RetryDecision, RetrySession};
-use crate::response::query_result::ColumnSpecs;
+use crate::response::query_result::{ColumnSpecs, QueryResult};
 use crate::response::{Coordinator, NonErrorQueryResponse, QueryResponse};
-use crate::routing::NodeLocationPreference;
+use crate::routing::partitioner::{PartitionerName, calculate_token_for_raw_partition_key};
+use crate::routing::{NodeLocationPreference, Token};
 use crate::statement::prepared::{PartitionKeyError, PreparedStatement};
 use crate::statement::unprepared::Statement;
+use crate::statement::{AttemptCallback, PageSize, PageSizer};
 use tracing::{Instrument, error, trace, trace_span, warn};
 use uuid::Uuid;
 
@@ -185,39 +193,70 @@ mod timeouter {
     use tokio::time::Instant;
 
     /// Encapsulation of a timeout for paging queries.
+    ///
+    /// Each page fetch is bounded by the per-page timeout, if set, and by the
+    /// total deadline of the whole paged execution, if set. Whichever elapses
+    /// first fails the fetch.
     pub(super) struct PageQueryTimeouter {
-        timeout: Duration,
-        timeout_instant: Instant,
+        page_timeout: Option<(Duration, Instant)>,
+        paging_deadline: Option<(Duration, Instant)>,
     }
 
     impl PageQueryTimeouter {
-        /// Creates a new PageQueryTimeouter with the given timeout duration,
-        /// starting from now.
-        pub(super) fn new(timeout: Duration) -> Self {
-            Self {
-                timeout,
-                timeout_instant: Instant::now() + timeout,
+        /// Creates a new PageQueryTimeouter with the given per-page timeout
+        /// and total paging deadline, both starting from now.
+        ///
+        /// Returns None if neither is set.
+        pub(super) fn new(
+            page_timeout: Option<Duration>,
+            paging_deadline: Option<Duration>,
+        ) -> Option<Self> {
+            if page_timeout.is_none() && paging_deadline.is_none() {
+                return None;
+            }
+            let now = Instant::now();
+            Some(Self {
+                page_timeout: page_timeout.map(|timeout| (timeout, now + timeout)),
+                paging_deadline: paging_deadline.map(|deadline| (deadline, now + deadline)),
+            })
+        }
+
+        // The timeout which elapses first, with the instant at which it elapses.
+        fn earliest(&self) -> (Duration, Instant) {
+            match (self.page_timeout, self.paging_deadline) {
+                (Some(page), Some(paging)) => {
+                    if paging.1 < page.1 {
+                        paging
+                    } else {
+                        page
+                    }
+                }
+                (Some(timeout), None) | (None, Some(timeout)) => timeout,
+                // The constructor ensures that at least one is set.
+                (None, None) => unreachable!(),
             }
         }
 
-        /// Returns the timeout duration.
+        /// Returns the duration of the timeout which elapses first.
         pub(super) fn timeout_duration(&self) -> Duration {
-            self.timeout
+            self.earliest().0
         }
 
         /// Returns the instant at which the timeout will elapse.
         ///
         /// This can be used with `tokio::time::timeout_at`.
         pub(super) fn deadline(&self) -> Instant {
-            self.timeout_instant
+            self.earliest().1
         }
 
-        /// Resets the timeout countdown.
+        /// Resets the per-page timeout countdown. The total paging deadline is not affected.
         ///
         /// This should be called right before beginning first page fetch
         /// and after each successful page fetch.
         pub(super) fn reset(&mut self) {
-            self.timeout_instant = Instant::now() + self.timeout;
+            if let Some((timeout, timeout_instant)) = &mut self.page_timeout {
+                *timeout_instant = Instant::now() + *timeout;
+            }
         }
     }
 }
@@ -296,7 +335,7 @@ impl PageSender {
 // QueryPager receives them through a channel
 struct PagerWorker<'a, QueryFunc, SpanCreatorFunc> {
     // Closure used to perform a single page query
-    // AsyncFn(Arc<Connection>, Option<Arc<[u8]>>) -> Result<QueryResponse, RequestAttemptError>
+    // AsyncFn(Arc<Connection>, Consistency, PagingState, PageSize) -> Result<QueryResponse, RequestAttemptError>
     page_query: QueryFunc,
 
     load_balancing_policy: Arc<dyn LoadBalancingPolicy>,
@@ -307,20 +346,38 @@ struct PagerWorker<'a, QueryFunc, SpanCreatorFunc> {
     timeouter: Option<PageQueryTimeouter>,
     #[cfg(feature = "metrics")]
     metrics: Arc<Metrics>,
+    metrics_sink: Option<Arc<dyn MetricsSink>>,
 
     paging_state: PagingState,
+    page_sizer: PageSizer,
+    // A page fetched before the worker was started, sent before the pages fetched by the worker.
+    prefetched_page: Option<NextReceivedPage>,
 
     history_listener: Option<Arc<dyn HistoryListener>>,
     current_request_id: Option<history::RequestId>,
     current_attempt_id: Option<history::AttemptId>,
 
+    on_attempt: Option<AttemptCallback>,
+    // Nodes excluded from the plan by the statement.
+    excluded_nodes: Vec<Uuid>,
+    require_replica_coordinator: bool,
+    preferred_replica_index: Option<usize>,
+    // Number of attempts to fetch the current page.
+    attempts_started: u32,
+    // Maximum number of attempts to fetch a single page, if limited by the session.
+    max_attempts: Option<NonZeroU32>,
+    // Admission queue of the session, if it limits concurrent requests.
+    // Each page fetch is admitted separately, with the statement's priority.
+    admission_queue: Option<Arc<AdmissionQueue>>,
+    priority: u8,
+
     parent_span: tracing::Span,
     span_creator: SpanCreatorFunc,
 }
 
 impl<QueryFunc, QueryFut, SpanCreator> PagerWorker<'_, QueryFunc, SpanCreator>
 where
-    QueryFunc: Fn(Arc<Connection>, Consistency, PagingState) -> QueryFut,
+    QueryFunc: Fn(Arc<Connection>, Consistency, PagingState, PageSize) -> QueryFut,
     QueryFut: Future<Output = Result<QueryResponse, RequestAttemptError>>,
     SpanCreator: Fn() -> RequestSpan,
 {
@@ -332,19 +389,47 @@ where
     ) -> FirstPageSendAttemptedProof {
         let load_balancer = Arc::clone(&self.load_balancing_policy);
         let statement_info = self.routing_info.clone();
-        let query_plan =
-            load_balancing::Plan::new(load_balancer.as_ref(), &statement_info, &cluster_state);
+        let excluded_nodes = self.excluded_nodes.clone();
+        let preferred_replica = self
+            .preferred_replica_index
+            .and_then(|index| cluster_state.replica_at(&statement_info, index));
+        let query_plan = load_balancing::with_preferred_first(
+            load_balancing::Plan::new(load_balancer.as_ref(), &statement_info, &cluster_state),
+            preferred_replica,
+        )
+        .filter(|(node, _)| !excluded_nodes.contains(&node.host_id));
+        let required_replicas = self
+            .require_replica_coordinator
+            .then(|| cluster_state.replica_host_ids(&statement_info));
 
         let mut last_error: RequestError = RequestError::EmptyPlan;
         let mut current_consistency: Consistency = self.query_consistency;
 
         let mut sender = PageSender::FirstPage(first_page_sender);
+        if let Some(page) = self.prefetched_page.take() {
+            let (proof, new_sender, res) = sender.send(page).await;
+            if res.is_err() {
+                // The pager was dropped, so there is no need to fetch more pages.
+                return proof;
+            }
+            sender = new_sender;
+        }
 
         self.log_request_start();
         self.timeouter.as_mut().map(PageQueryTimeouter::reset);
 
         'nodes_in_plan: for (node, shard) in query_plan {
             let span = trace_span!(parent: &self.parent_span, "Executing query", node = %node.address, shard = %shard);
+            if let Some(replicas) = &required_replicas
+                && !replicas.contains(&node.host_id)
+            {
+                trace!(parent: &span, "Coordinator is not a replica, skipping it");
+                // Reported only if no replica in the plan was tried.
+                if matches!(last_error, RequestError::EmptyPlan) {
+                    last_error = RequestError::CoordinatorNotReplica(node.address);
+                }
+                continue 'nodes_in_plan;
+            }
             // For each node in the plan choose a connection to use
             // This connection will be reused for same node retries to preserve paging cache on the shard
             let connection: Arc<Connection> = match node
@@ -359,6 +444,7 @@ where
                         error = %e,
                         "Choosing connection failed"
                     );
+                    self.log_connection_pool_error(node.address.into_inner(), &e);
                     last_error = e.into();
                     // Broken connection doesn't count as a failed query, don't log in metrics
                     continue 'nodes_in_plan;
@@ -439,15 +525,28 @@ where
                 last_error = request_error.into();
 
                 match retry_decision {
+                    RetryDecision::RetrySameTarget(_) | RetryDecision::RetryNextTarget(_)
+                        if self
+                            .max_attempts
+                            .is_some_and(|max| self.attempts_started >= max.get()) =>
+                    {
+                        trace!(
+                            parent: &span,
+                            "Maximum number of attempts per request reached"
+                        );
+                        break 'nodes_in_plan;
+                    }
                     RetryDecision::RetrySameTarget(cl) => {
                         #[cfg(feature = "metrics")]
                         self.metrics.inc_retries_num();
+                        self.report_retry(node.address, &retry_decision);
                         current_consistency = cl.unwrap_or(current_consistency);
                         continue 'same_node_retries;
                     }
                     RetryDecision::RetryNextTarget(cl) => {
                         #[cfg(feature = "metrics")]
                         self.metrics.inc_retries_num();
+                        self.report_retry(node.address, &retry_decision);
                         current_consistency = cl.unwrap_or(current_consistency);
                         continue 'nodes_in_plan;
                     }
@@ -541,6 +640,11 @@ where
         >,
         PageSender,
     ) {
+        self.attempts_started += 1;
+        if let Some(on_attempt) = &self.on_attempt {
+            on_attempt.call(self.attempts_started, node);
+        }
+
         let (elapsed, page_result) = match self
             .fetch_one_page(connection, consistency, request_span)
             .await
@@ -548,6 +652,13 @@ where
             Err(timeout_err) => return (Err(timeout_err), sender),
             Ok((elapsed, resp)) => (elapsed, resp),
         };
+        if let Some(metrics_sink) = &self.metrics_sink {
+            metrics_sink.on_request_complete(&RequestCompleteEvent {
+                node_address: node.address,
+                latency: elapsed,
+                succeeded: page_result.is_ok(),
+            });
+        }
 
         let res = match sender {
             PageSender::FirstPage(first_page_sender) => {
@@ -609,9 +720,22 @@ where
         self.log_attempt_start(connect_address);
 
         let runner = async {
-            (self.page_query)(connection.clone(), consistency, self.paging_state.clone())
-                .await
-                .and_then(QueryResponse::into_non_error_query_response)
+            let _admission_permit = match &self.admission_queue {
+                Some(admission_queue) => Some(admission_queue.acquire(self.priority).await),
+                None => None,
+            };
+            let response = (self.page_query)(
+                connection.clone(),
+                consistency,
+                self.paging_state.clone(),
+                self.page_sizer.page_size(),
+            )
+            .await
+            .and_then(QueryResponse::into_non_error_query_response);
+            if let Some(admission_queue) = &self.admission_queue {
+                admission_queue.record_attempt(&response);
+            }
+            response
         };
         let query_response = match self.timeouter {
             Some(ref timeouter) => {
@@ -670,6 +794,8 @@ where
             }) => {
                 log_success();
                 request_span.record_raw_rows_fields(&rows);
+                self.page_sizer
+                    .observe_page(rows.rows_count(), rows.rows_bytes_size());
 
                 let received_page = FirstReceivedPage {
                     content: FirstPageContent::Rows { rows },
@@ -822,6 +948,8 @@ where
                     .on_request_success(&self.routing_info, elapsed, node);
 
                 request_span.record_raw_rows_fields(&rows);
+                self.page_sizer
+                    .observe_page(rows.rows_count(), rows.rows_bytes_size());
 
                 let received_page = NextReceivedPage {
                     rows,
@@ -881,7 +1009,19 @@ where
         }
     }
 
+    fn report_retry(&self, node_address: NodeAddr, decision: &RetryDecision) {
+        if let Some(metrics_sink) = &self.metrics_sink {
+            metrics_sink.on_retry(&RetryEvent {
+                node_address,
+                decision: decision.clone(),
+            });
+        }
+    }
+
     fn log_request_start(&mut self) {
+        // Each page is a separate request, with its own attempts.
+        self.attempts_started = 0;
+
         let history_listener: &dyn HistoryListener = match &self.history_listener {
             Some(hl) => &**hl,
             None => return,
@@ -960,6 +1100,20 @@ where
 
         history_listener.log_attempt_error(attempt_id, error, retry_decision);
     }
+
+    fn log_connection_pool_error(&self, node_addr: SocketAddr, error: &ConnectionPoolError) {
+        let history_listener: &dyn HistoryListener = match &self.history_listener {
+            Some(hl) => &**hl,
+            None => return,
+        };
+
+        let request_id: history::RequestId = match &self.current_request_id {
+            Some(id) => *id,
+            None => return,
+        };
+
+        history_listener.log_connection_pool_error(request_id, None, node_addr, error);
+    }
 }
 
 /// A massively simplified version of the PagerWorker. It does not have
@@ -1088,6 +1242,12 @@ pub(crate) struct PreparedPagerConfig {
     #[cfg(feature = "metrics")]
     pub(crate) metrics: Arc<Metrics>,
     pub(crate) location_preference: Arc<NodeLocationPreference>,
+    pub(crate) max_attempts: Option<NonZeroU32>,
+    pub(crate) metrics_sink: Option<Arc<dyn MetricsSink>>,
+    pub(crate) admission_queue: Option<Arc<AdmissionQueue>>,
+    /// The first page, if it was already fetched, with the paging state to continue from.
+    /// The pager yields it before the pages it fetches itself.
+    pub(crate) fetched_first_page: Option<(QueryResult, PagingState)>,
 }
 
 /// An intermediate object that allows to construct a stream over a query
@@ -1097,12 +1257,107 @@ pub(crate) struct PreparedPagerConfig {
 /// needs to be cast into a typed stream. This is done by use of `rows_stream()` method.
 /// As the method is generic over the target type, the turbofish syntax
 /// can come in handy there, e.g. `query_pager.rows_stream::<(i32, String, Uuid)>()`.
-#[derive(Debug)]
 pub struct QueryPager {
     current_page: RawRowLendingIterator,
     page_receiver: mpsc::Receiver<Result<NextReceivedPage, NextPageError>>,
     tracing_ids: Vec<Uuid>,
     request_coordinators: Vec<Coordinator>,
+    // Used to find the partition key columns of the pages. None if not paging with a Session.
+    cluster_state: Option<Arc<ClusterState>>,
+    row_token_context: Option<Arc<RowTokenContext>>,
+    // The last read row, kept to compute its token on demand, along with the context of its page.
+    last_row: Option<(Bytes, Arc<RowTokenContext>)>,
+    scan_summary: ScanSummary,
+}
+
+// Manual implementation, because ClusterState doesn't implement Debug.
+impl std::fmt::Debug for QueryPager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryPager")
+            .field("current_page", &self.current_page)
+            .field("page_receiver", &self.page_receiver)
+            .field("tracing_ids", &self.tracing_ids)
+            .field("request_coordinators", &self.request_coordinators)
+            .field("row_token_context", &self.row_token_context)
+            .field("last_row", &self.last_row)
+            .field("scan_summary", &self.scan_summary)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Totals of the pages received by a [QueryPager], returned by [QueryPager::scan_summary].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ScanSummary {
+    /// Number of rows in all received pages.
+    pub rows: usize,
+    /// Number of received pages of rows, including empty ones.
+    pub pages: usize,
+    /// Serialized size of the rows in all received pages, in bytes.
+    /// Result metadata is not included.
+    pub bytes: usize,
+}
+
+impl ScanSummary {
+    fn record_page(&mut self, rows: &DeserializedMetadataAndRawRows) {
+        self.rows += rows.rows_count();
+        self.pages += 1;
+        self.bytes += rows.rows_bytes_size();
+    }
+}
+
+/// Positions of the partition key columns among the columns of a page,
+/// along with the partitioner of their table. Allows computing tokens of the rows.
+#[derive(Debug)]
+struct RowTokenContext {
+    pk_indexes: Vec<usize>,
+    partitioner: PartitionerName,
+}
+
+impl RowTokenContext {
+    /// Returns None if the table is unknown or not all partition key columns are selected.
+    fn for_page(cluster_state: &ClusterState, col_specs: &[ColumnSpec]) -> Option<Self> {
+        let table_spec = col_specs.first()?.table_spec();
+        let keyspace = cluster_state.get_keyspace(table_spec.ks_name())?;
+        let table = keyspace.tables.get(table_spec.table_name())?;
+        let pk_indexes = table
+            .partition_key
+            .iter()
+            .map(|pk_column| {
+                col_specs
+                    .iter()
+                    .position(|spec| spec.name() == pk_column && spec.table_spec() == table_spec)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let partitioner = keyspace
+            .table_partitioner(table_spec.table_name())
+            .and_then(PartitionerName::from_str)
+            .unwrap_or_default();
+
+        Some(Self {
+            pk_indexes,
+            partitioner,
+        })
+    }
+
+    /// Computes the token of a row, given its serialized columns.
+    fn row_token(&self, row: &Bytes) -> Option<Token> {
+        let columns_to_read = self.pk_indexes.iter().max()? + 1;
+        let mut pk_values = vec![None; self.pk_indexes.len()];
+        let mut row = FrameSlice::new(row);
+        for index in 0..columns_to_read {
+            let value = row.read_cql_bytes().ok()?;
+            if let Some(pk_position) = self.pk_indexes.iter().position(|&i| i == index) {
+                pk_values[pk_position] = value.map(|value| value.as_slice());
+            }
+        }
+        calculate_token_for_raw_partition_key(
+            pk_values.len(),
+            pk_values.into_iter(),
+            &self.partitioner,
+        )
+        .ok()
+    }
 }
 
 // QueryPager is not an iterator or a stream! However, it implements
@@ -1129,13 +1384,20 @@ impl QueryPager {
             None => return None,
         };
 
-        Some(
-            self.current_page
-                .next()
-                .unwrap()
-                .map_err(NextRowError::RowDeserializationError)
-                .map(|x| (x, fresh_page)),
-        )
+        let row = match self.current_page.next().unwrap() {
+            Ok(row) => row,
+            Err(err) => return Some(Err(NextRowError::RowDeserializationError(err))),
+        };
+        // The token is computed lazily, because most users don't need it.
+        self.last_row = match &self.row_token_context {
+            Some(context) => row
+                .raw_row()
+                .ok()
+                .map(|raw_row| (raw_row, Arc::clone(context))),
+            None => None,
+        };
+
+        Some(Ok((row, fresh_page)))
     }
 
     /// This is essentially the same as `next()`, but it is *public* and only available
@@ -1184,7 +1446,12 @@ impl QueryPager {
 
         let received_page = ready_some_ok!(Pin::new(&mut s.page_receiver).poll_recv(cx));
 
+        s.scan_summary.record_page(&received_page.rows);
         s.current_page = RawRowLendingIterator::new(received_page.rows);
+        s.row_token_context = s.cluster_state.as_deref().and_then(|cluster_state| {
+            RowTokenContext::for_page(cluster_state, s.current_page.metadata().col_specs())
+                .map(Arc::new)
+        });
 
         if let Some(tracing_id) = received_page.tracing_id {
             s.tracing_ids.push(tracing_id);
@@ -1224,6 +1491,7 @@ If you are using this API, you are probably doing something wrong."
         TypedRowStream::<RowT>::new(self)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn new_for_query(
         session: &Session,
         statement: Statement,
@@ -1231,6 +1499,9 @@ If you are using this API, you are probably doing something wrong."
         cluster_state: Arc<ClusterState>,
         #[cfg(feature = "metrics")] metrics: Arc<Metrics>,
         node_location_preference: Arc<NodeLocationPreference>,
+        max_attempts: Option<NonZeroU32>,
+        metrics_sink: Option<Arc<dyn MetricsSink>>,
+        admission_queue: Option<Arc<AdmissionQueue>>,
     ) -> Result<Self, PagerExecutionError> {
         let (sender, receiver) = oneshot::channel::<ResultFirstPage>();
 
@@ -1243,12 +1514,17 @@ If you are using this API, you are probably doing something wrong."
             .serial_consistency
             .unwrap_or(execution_profile.serial_consistency);
 
-        let timeouter = statement
-            .get_request_timeout()
-            .or(execution_profile.request_timeout)
-            .map(PageQueryTimeouter::new);
+        let timeouter = PageQueryTimeouter::new(
+            statement
+                .get_request_timeout()
+                .or(execution_profile.request_timeout),
+            statement.config.paging_deadline,
+        );
 
-        let page_size = statement.get_validated_page_size();
+        let page_sizer = PageSizer::new(
+            statement.get_validated_page_size(),
+            statement.config.adaptive_page_size,
+        );
 
         let load_balancing_policy = Arc::clone(
             statement
@@ -1256,11 +1532,7 @@ If you are using this API, you are probably doing something wrong."
                 .unwrap_or(&execution_profile.load_balancing_policy),
         );
 
-        let retry_session = statement
-            .get_retry_policy()
-            .map(|rp| &**rp)
-            .unwrap_or(&*execution_profile.retry_policy)
-            .new_session();
+        let retry_session = execution_profile.new_retry_session(statement.get_retry_policy());
 
         let parent_span = tracing::Span::current();
         let worker_task = async move {
@@ -1268,14 +1540,15 @@ If you are using this API, you are probably doing something wrong."
 
             let page_query = |connection: Arc<Connection>,
                               consistency: Consistency,
-                              paging_state: PagingState| {
+                              paging_state: PagingState,
+                              page_size: PageSize| {
                 async move {
                     connection
                         .query_raw_with_consistency(
                             statement_ref,
                             consistency,
                             serial_consistency,
-                            Some(page_size),
+                            statement_ref.config.requested_page_size(page_size),
                             paging_state,
                         )
                         .await
@@ -1309,10 +1582,21 @@ If you are using this API, you are probably doing something wrong."
                 timeouter,
                 #[cfg(feature = "metrics")]
                 metrics,
+                metrics_sink,
                 paging_state: PagingState::start(),
+                page_sizer,
+                prefetched_page: None,
                 history_listener: statement.config.history_listener.clone(),
                 current_request_id: None,
                 current_attempt_id: None,
+                on_attempt: statement.config.on_attempt.clone(),
+                excluded_nodes: statement.config.excluded_nodes.clone(),
+                require_replica_coordinator: statement.config.require_replica_coordinator,
+                preferred_replica_index: statement.config.preferred_replica_index,
+                attempts_started: 0,
+                max_attempts,
+                admission_queue,
+                priority: statement.config.priority,
                 parent_span,
                 span_creator,
             };
@@ -1327,7 +1611,7 @@ If you are using this API, you are probably doing something wrong."
 
     pub(crate) async fn new_for_prepared_statement(
         session: &Session,
-        config: PreparedPagerConfig,
+        mut config: PreparedPagerConfig,
     ) -> Result<Self, PagerExecutionError> {
         let (sender, receiver) = oneshot::channel::<ResultFirstPage>();
 
@@ -1342,13 +1626,18 @@ If you are using this API, you are probably doing something wrong."
             .serial_consistency
             .unwrap_or(config.execution_profile.serial_consistency);
 
-        let timeouter = config
-            .prepared
-            .get_request_timeout()
-            .or(config.execution_profile.request_timeout)
-            .map(PageQueryTimeouter::new);
+        let timeouter = PageQueryTimeouter::new(
+            config
+                .prepared
+                .get_request_timeout()
+                .or(config.execution_profile.request_timeout),
+            config.prepared.config.paging_deadline,
+        );
 
-        let page_size = config.prepared.get_validated_page_size();
+        let page_sizer = PageSizer::new(
+            config.prepared.get_validated_page_size(),
+            config.prepared.config.adaptive_page_size,
+        );
 
         let load_balancing_policy = Arc::clone(
             config
@@ -1358,11 +1647,21 @@ If you are using this API, you are probably doing something wrong."
         );
 
         let retry_session = config
-            .prepared
-            .get_retry_policy()
-            .map(|rp| &**rp)
-            .unwrap_or(&*config.execution_profile.retry_policy)
-            .new_session();
+            .execution_profile
+            .new_retry_session(config.prepared.get_retry_policy());
+
+        let (prefetched_page, paging_state) = match config.fetched_first_page.take() {
+            Some((first_page, paging_state)) => {
+                let (rows, tracing_id, request_coordinator) = first_page.into_page_parts();
+                let page = NextReceivedPage {
+                    rows: rows.unwrap_or_else(DeserializedMetadataAndRawRows::mock_empty),
+                    tracing_id,
+                    request_coordinator,
+                };
+                (Some(page), paging_state)
+            }
+            None => (None, PagingState::start()),
+        };
 
         let parent_span = tracing::Span::current();
         let worker_task = async move {
@@ -1382,11 +1681,12 @@ If you are using this API, you are probably doing something wrong."
                 }
             };
 
-            let table_spec = config.prepared.get_table_spec();
+            let table_spec = config.prepared.get_routing_table_spec();
             let statement_info = RoutingInfo {
                 consistency,
                 serial_consistency,
-                token,
+                // Without a token, the load balancing policy can't route to the replicas.
+                token: token.filter(|_| !config.prepared.config.disable_token_aware),
                 table: table_spec,
                 is_confirmed_lwt: config.prepared.is_confirmed_lwt(),
                 node_location_preference: &config.location_preference,
@@ -1394,14 +1694,15 @@ If you are using this API, you are probably doing something wrong."
 
             let page_query = |connection: Arc<Connection>,
                               consistency: Consistency,
-                              paging_state: PagingState| async move {
+                              paging_state: PagingState,
+                              page_size: PageSize| async move {
                 connection
                     .execute_raw_with_consistency(
                         prepared_ref,
                         values_ref,
                         consistency,
                         serial_consistency,
-                        Some(page_size),
+                        prepared_ref.config.requested_page_size(page_size),
                         paging_state,
                     )
                     .await
@@ -1446,10 +1747,21 @@ If you are using this API, you are probably doing something wrong."
                 timeouter,
                 #[cfg(feature = "metrics")]
                 metrics: config.metrics,
-                paging_state: PagingState::start(),
+                metrics_sink: config.metrics_sink,
+                paging_state,
+                page_sizer,
+                prefetched_page,
                 history_listener: config.prepared.config.history_listener.clone(),
                 current_request_id: None,
                 current_attempt_id: None,
+                on_attempt: config.prepared.config.on_attempt.clone(),
+                excluded_nodes: config.prepared.config.excluded_nodes.clone(),
+                require_replica_coordinator: config.prepared.config.require_replica_coordinator,
+                preferred_replica_index: config.prepared.config.preferred_replica_index,
+                attempts_started: 0,
+                max_attempts: config.max_attempts,
+                admission_queue: config.admission_queue,
+                priority: config.prepared.config.priority,
                 parent_span,
                 span_creator,
             };
@@ -1487,7 +1799,7 @@ If you are using this API, you are probably doing something wrong."
                         &values,
                         consistency,
                         serial_consistency,
-                        Some(page_size),
+                        prepared.config.requested_page_size(page_size),
                         paging_state,
                     )
                 },
@@ -1574,8 +1886,12 @@ If you are using this API, you are probably doing something wrong."
             .map(|coordinator| coordinator.node().host_id);
         let request_coordinators = Vec::from_iter(first_page.request_coordinator);
 
+        let mut scan_summary = ScanSummary::default();
         let current_page = match first_page.content {
-            FirstPageContent::Rows { rows } => RawRowLendingIterator::new(rows),
+            FirstPageContent::Rows { rows } => {
+                scan_summary.record_page(&rows);
+                RawRowLendingIterator::new(rows)
+            }
             FirstPageContent::SetKeyspace { set_keyspace } => {
                 if let Some(session) = session {
                     // If we are here, this means that we received a SET_KEYSPACE response as a first page.
@@ -1591,6 +1907,10 @@ If you are using this API, you are probably doing something wrong."
                         ),
                         tracing_id: None,
                         warnings: Vec::new(),
+                        unknown_result: None,
+                        bytes_sent: 0,
+                        bytes_received: 0,
+                        response_flags: 0,
                     };
                     session.handle_set_keyspace_response(&response).await?;
                 } else {
@@ -1620,6 +1940,10 @@ If you are using this API, you are probably doing something wrong."
                         ),
                         tracing_id: None,
                         warnings: Vec::new(),
+                        unknown_result: None,
+                        bytes_sent: 0,
+                        bytes_received: 0,
+                        response_flags: 0,
                     };
                     session
                         .handle_auto_await_schema_agreement(
@@ -1651,11 +1975,21 @@ If you are using this API, you are probably doing something wrong."
             }
         };
 
+        let cluster_state = session.map(Session::get_cluster_state);
+        let row_token_context = cluster_state.as_deref().and_then(|cluster_state| {
+            RowTokenContext::for_page(cluster_state, current_page.metadata().col_specs())
+                .map(Arc::new)
+        });
+
         Ok(Self {
             current_page,
             page_receiver: remaining_pages_receiver,
             tracing_ids,
             request_coordinators,
+            cluster_state,
+            row_token_context,
+            last_row: None,
+            scan_summary,
         })
     }
 
@@ -1677,6 +2011,31 @@ If you are using this API, you are probably doing something wrong."
         ColumnSpecs::new(self.current_page.metadata().col_specs())
     }
 
+    /// Returns the token of the partition of the last read row.
+    ///
+    /// It can be used to resume a scan of a table from that row,
+    /// e.g. with `WHERE token(pk) >= ?`.
+    /// The token is computed from the values of the partition key columns of the row,
+    /// so it's `None` if not all of them are selected, if the schema of the table
+    /// is not known to the driver, or if no row has been read yet.
+    #[inline]
+    pub fn last_row_token(&self) -> Option<Token> {
+        let (row, context) = self.last_row.as_ref()?;
+        context.row_token(row)
+    }
+
+    /// Fetches all remaining pages without deserializing their rows,
+    /// and returns the totals of all pages received by the pager,
+    /// including the ones whose rows were already read.
+    pub async fn scan_summary(mut self) -> Result<ScanSummary, NextRowError> {
+        while let Some(res) =
+            std::future::poll_fn(|cx| Pin::new(&mut self).poll_next_page(cx)).await
+        {
+            res?;
+        }
+        Ok(self.scan_summary)
+    }
+
     fn is_current_page_exhausted(&self) -> bool {
         self.current_page.rows_remaining() == 0
     }
@@ -1739,6 +2098,20 @@ impl<RowT> TypedRowStream<RowT> {
     pub fn column_specs(&self) -> ColumnSpecs<'_, '_> {
         self.raw_row_lending_stream.column_specs()
     }
+
+    /// Returns the token of the partition of the last read row.
+    /// See [`QueryPager::last_row_token`].
+    #[inline]
+    pub fn last_row_token(&self) -> Option<Token> {
+        self.raw_row_lending_stream.last_row_token()
+    }
+
+    /// Fetches all remaining pages and returns the totals of all received pages.
+    /// See [`QueryPager::scan_summary`].
+    #[inline]
+    pub async fn scan_summary(self) -> Result<ScanSummary, NextRowError> {
+        self.raw_row_lending_stream.scan_summary().await
+    }
 }
 
 /// Stream implementation for TypedRowStream.
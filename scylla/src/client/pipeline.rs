@@ -0,0 +1,126 @@
+//! Sending requests in submission order on a single connection.
+
+use std::sync::Arc;
+
+use crate::client::execution_profile::ExecutionProfileHandle;
+use crate::cluster::Node;
+use crate::errors::ExecutionError;
+use crate::network::{Connection, StreamIdSequence};
+use crate::response::Coordinator;
+use crate::response::query_result::QueryResult;
+use crate::routing::Shard;
+use crate::serialize::row::SerializeRow;
+use crate::statement::prepared::PreparedStatement;
+
+/// A handle for sending statements in submission order on a single, pinned connection.
+///
+/// The requests are written to the connection in the order in which they are submitted,
+/// i.e. in which the futures returned by [`OrderedPipeline::execute_unpaged`] are first polled.
+/// Only the send order is guaranteed: the server may execute concurrent requests, and respond
+/// to them, in any order. To have a request executed after another one, await the first one
+/// before submitting the second.
+///
+/// A request which has to be reprepared is sent again after the repreparation, i.e. after
+/// requests submitted later. Stream ids grow with the submission order until they run out,
+/// and then start over from the lowest free one, so they don't reflect the order either.
+///
+/// The requests are neither retried nor routed by the load balancing policy, as that would
+/// break the send order. If the pinned connection breaks, all further requests fail, and a new
+/// pipeline has to be acquired with [`Session::ordered_pipeline`](crate::client::session::Session::ordered_pipeline).
+///
+/// # Example
+/// ```rust
+/// # use scylla::client::session::Session;
+/// # async fn example(session: &Session) -> Result<(), Box<dyn std::error::Error>> {
+/// let cluster_state = session.get_cluster_state();
+/// let node = &cluster_state.get_nodes_info()[0];
+/// let pipeline = session.ordered_pipeline(node)?;
+///
+/// let insert = session.prepare("INSERT INTO ks.log (id, seq) VALUES (1, ?)").await?;
+/// // The inserts are sent in order, but they may be executed in any order.
+/// let (first, second) = futures::join!(
+///     pipeline.execute_unpaged(&insert, (1,)),
+///     pipeline.execute_unpaged(&insert, (2,)),
+/// );
+/// first?;
+/// second?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct OrderedPipeline {
+    connection: Arc<Connection>,
+    node: Arc<Node>,
+    stream_id_sequence: Arc<StreamIdSequence>,
+    default_execution_profile_handle: ExecutionProfileHandle,
+}
+
+impl std::fmt::Debug for OrderedPipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OrderedPipeline")
+            .field("connection_address", &self.connection.get_connect_address())
+            .field("node", &self.node.host_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OrderedPipeline {
+    pub(crate) fn new(
+        connection: Arc<Connection>,
+        node: Arc<Node>,
+        default_execution_profile_handle: ExecutionProfileHandle,
+    ) -> Self {
+        Self {
+            connection,
+            node,
+            stream_id_sequence: Arc::new(StreamIdSequence::new()),
+            default_execution_profile_handle,
+        }
+    }
+
+    /// Executes a prepared statement without paging, as the next request of the pipeline.
+    ///
+    /// The consistencies are taken from the statement or its execution profile,
+    /// falling back to the session's default execution profile.
+    pub async fn execute_unpaged(
+        &self,
+        prepared: &PreparedStatement,
+        values: impl SerializeRow,
+    ) -> Result<QueryResult, ExecutionError> {
+        let execution_profile = prepared
+            .get_execution_profile_handle()
+            .unwrap_or(&self.default_execution_profile_handle)
+            .access();
+        let consistency = prepared
+            .config
+            .determine_consistency(execution_profile.consistency);
+        let serial_consistency = prepared
+            .config
+            .serial_consistency
+            .unwrap_or(execution_profile.serial_consistency);
+
+        let serialized_values = prepared.serialize_values(&values)?;
+        let response = self
+            .connection
+            .execute_raw_in_sequence(
+                prepared,
+                &serialized_values,
+                consistency,
+                serial_consistency,
+                &self.stream_id_sequence,
+            )
+            .await?;
+
+        let coordinator = Coordinator::new(
+            &self.node,
+            self.connection
+                .get_shard_info()
+                .as_ref()
+                .map(|shard_info| shard_info.shard as Shard),
+            &self.connection,
+        );
+        response
+            .into_non_error_query_response()
+            .and_then(|response| response.into_query_result(coordinator))
+            .map_err(ExecutionError::LastAttemptError)
+    }
+}
@@ -0,0 +1,185 @@
+//! Helpers for reading back one's own writes.
+//!
+//! A write executed with [`Session::execute_write_tracked`](crate::client::session::Session::execute_write_tracked)
+//! yields a [`WriteToken`], which describes how the write was performed. Passing the token to
+//! [`Session::execute_read_your_writes`](crate::client::session::Session::execute_read_your_writes)
+//! executes a read at a consistency level which guarantees that the write is visible:
+//! - for regular writes, the read and write consistency levels are chosen so that the sets of replicas
+//!   which acknowledged the write and which respond to the read overlap,
+//! - for lightweight transactions, the read is performed at the serial consistency of the write,
+//!   which makes it observe all committed transactions.
+
+use crate::cluster::metadata::Strategy;
+use crate::statement::{Consistency, SerialConsistency};
+
+/// Describes a write executed by [`Session::execute_write_tracked`](crate::client::session::Session::execute_write_tracked),
+/// allowing to read it back with [`Session::execute_read_your_writes`](crate::client::session::Session::execute_read_your_writes).
+#[derive(Debug, Clone)]
+pub struct WriteToken {
+    pub(crate) timestamp: Option<i64>,
+    pub(crate) consistency: Consistency,
+    pub(crate) serial_consistency: Option<SerialConsistency>,
+    pub(crate) keyspace: Option<String>,
+}
+
+impl WriteToken {
+    /// The timestamp the write was performed with, in microseconds since the unix epoch.
+    ///
+    /// `None` for lightweight transactions, whose timestamps are assigned by the database.
+    pub fn timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+
+    /// The consistency level the write was performed at.
+    ///
+    /// If the retry policy downgraded the consistency of the write,
+    /// this is the consistency of the attempt which succeeded.
+    pub fn consistency(&self) -> Consistency {
+        self.consistency
+    }
+
+    /// The serial consistency level of the write, if it was a lightweight transaction.
+    pub fn serial_consistency(&self) -> Option<SerialConsistency> {
+        self.serial_consistency
+    }
+
+    /// Returns the weakest consistency level at which a read is guaranteed to observe the write,
+    /// or `None` if there is no such level.
+    ///
+    /// `strategy` is the replication strategy of the written keyspace, if known.
+    pub(crate) fn read_consistency(&self, strategy: Option<&Strategy>) -> Option<Consistency> {
+        match self.serial_consistency {
+            Some(SerialConsistency::Serial) => Some(Consistency::Serial),
+            Some(SerialConsistency::LocalSerial) => Some(Consistency::LocalSerial),
+            None => read_consistency_after_write(self.consistency, strategy),
+        }
+    }
+}
+
+fn read_consistency_after_write(
+    write_consistency: Consistency,
+    strategy: Option<&Strategy>,
+) -> Option<Consistency> {
+    let acked = match write_consistency {
+        // A write at ANY may be stored only as a hint, which reads don't observe.
+        Consistency::Any | Consistency::Serial | Consistency::LocalSerial => return None,
+        Consistency::All => return Some(Consistency::One),
+        Consistency::Quorum => return Some(Consistency::Quorum),
+        // A quorum of the local DC overlaps with the quorum of each DC,
+        // and with the local quorum if the read is served by the same DC.
+        Consistency::LocalQuorum | Consistency::EachQuorum => {
+            return Some(Consistency::LocalQuorum);
+        }
+        // The write may have been acknowledged by any single replica in the local DC.
+        Consistency::LocalOne => return Some(Consistency::All),
+        Consistency::One => 1,
+        Consistency::Two => 2,
+        Consistency::Three => 3,
+    };
+
+    let Some(replication_factor) = strategy.and_then(replication_factor) else {
+        return Some(Consistency::All);
+    };
+    // A read overlaps with the write if it contacts more replicas
+    // than there are replicas which didn't acknowledge the write.
+    Some(match (replication_factor + 1).saturating_sub(acked) {
+        0 | 1 => Consistency::One,
+        2 => Consistency::Two,
+        3 => Consistency::Three,
+        _ => Consistency::All,
+    })
+}
+
+/// Total number of replicas of each partition, if known.
+fn replication_factor(strategy: &Strategy) -> Option<usize> {
+    match strategy {
+        Strategy::SimpleStrategy { replication_factor } => Some(*replication_factor),
+        Strategy::NetworkTopologyStrategy {
+            datacenter_repfactors,
+        } => Some(datacenter_repfactors.values().sum()),
+        Strategy::LocalStrategy => Some(1),
+        Strategy::Other { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{WriteToken, read_consistency_after_write};
+    use crate::cluster::metadata::Strategy;
+    use crate::statement::{Consistency, SerialConsistency};
+
+    fn network_topology(repfactors: &[(&str, usize)]) -> Strategy {
+        Strategy::NetworkTopologyStrategy {
+            datacenter_repfactors: repfactors
+                .iter()
+                .map(|(dc, rf)| (dc.to_string(), *rf))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn read_overlaps_with_write_of_given_replica_count() {
+        let rf3 = Strategy::SimpleStrategy {
+            replication_factor: 3,
+        };
+        let cases = [
+            (Consistency::One, Consistency::Three),
+            (Consistency::Two, Consistency::Two),
+            (Consistency::Three, Consistency::One),
+            (Consistency::Quorum, Consistency::Quorum),
+            (Consistency::All, Consistency::One),
+            (Consistency::LocalQuorum, Consistency::LocalQuorum),
+            (Consistency::EachQuorum, Consistency::LocalQuorum),
+            (Consistency::LocalOne, Consistency::All),
+        ];
+        for (write, read) in cases {
+            assert_eq!(
+                read_consistency_after_write(write, Some(&rf3)),
+                Some(read),
+                "write at {write}"
+            );
+        }
+
+        let rf5 = network_topology(&[("dc1", 3), ("dc2", 2)]);
+        assert_eq!(
+            read_consistency_after_write(Consistency::One, Some(&rf5)),
+            Some(Consistency::All)
+        );
+        assert_eq!(
+            read_consistency_after_write(Consistency::Three, Some(&rf5)),
+            Some(Consistency::Three)
+        );
+    }
+
+    #[test]
+    fn unknown_replication_requires_reading_all_replicas() {
+        assert_eq!(
+            read_consistency_after_write(Consistency::Two, None),
+            Some(Consistency::All)
+        );
+    }
+
+    #[test]
+    fn visibility_of_writes_at_any_is_not_guaranteed() {
+        let rf1 = Strategy::SimpleStrategy {
+            replication_factor: 1,
+        };
+        assert_eq!(
+            read_consistency_after_write(Consistency::Any, Some(&rf1)),
+            None
+        );
+    }
+
+    #[test]
+    fn lightweight_transactions_are_read_at_serial_consistency() {
+        let token = WriteToken {
+            timestamp: None,
+            consistency: Consistency::One,
+            serial_consistency: Some(SerialConsistency::LocalSerial),
+            keyspace: None,
+        };
+        assert_eq!(token.read_consistency(None), Some(Consistency::LocalSerial));
+    }
+}
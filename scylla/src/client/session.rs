@@ -1,71 +1,110 @@
 //! `Session` is the main object used in the driver.\
 //! It manages all connections to the cluster and allows to execute CQL requests.
 
+use super::admission::AdmissionQueue;
+use super::cancellation::{CancelHandle, cancellable};
 use super::execution_profile::{ExecutionProfile, ExecutionProfileHandle, ExecutionProfileInner};
 use super::pager::{PreparedPagerConfig, QueryPager};
-use super::{Compression, PoolSize, SelfIdentity, WriteCoalescingDelay};
+use super::read_your_writes::WriteToken;
+use super::{
+    AdaptiveConcurrency, Compression, ConnectionSelection, ErrorRateCircuitBreaker,
+    OrderedPipeline, PoolAutoscaling, PoolSize, SelfIdentity, WriteCoalescingDelay,
+};
 use crate::authentication::AuthenticatorProvider;
 use crate::client::client_routes::ClientRoutesConfig;
-use crate::cluster::node::{KnownNode, NodeRef};
-use crate::cluster::{Cluster, ClusterNeatDebug, ClusterState};
+use crate::cluster::node::{KnownNode, Node, NodeAddr, NodeRef};
+use crate::cluster::{
+    Cluster, ClusterNeatDebug, ClusterState, SCHEMA_CHANGE_STREAM_CAPACITY, SchemaChangeStream,
+};
+use crate::deserialize::row::DeserializeRow;
 use crate::errors::DbError;
 use crate::errors::{
-    BadQuery, BrokenConnectionError, ExecutionError, MetadataError, NewSessionError,
-    PagerExecutionError, PrepareError, RequestAttemptError, RequestError, SchemaAgreementError,
-    TracingError, UseKeyspaceError,
+    AutoExecutionError, BadQuery, BrokenConnectionError, ClockSkewError, ConnectionPoolError,
+    ExecutionError, MetadataError, MultigetError, NewSessionError, PagerExecutionError,
+    PrepareError, RequestAttemptError, RequestError, SchemaAgreementError, SerializationError,
+    ServerClientsError, SinglePageRowsError, TracingError, UseKeyspaceError,
 };
 use crate::frame::response::NonErrorResponseWithDeserializedMetadataV2 as NonErrorResponseWithDeserializedMetadata;
 use crate::frame::response::result;
 use crate::network::tls::TlsProvider;
 use crate::network::{
-    Connection, ConnectionConfig, PoolConfig, TcpSocketOptions, VerifiedKeyspaceName,
+    Connection, ConnectionBudget, ConnectionConfig, FrameBufferPool, PoolConfig, RepreparationSet,
+    TcpSocketOptions, VerifiedKeyspaceName,
 };
+use crate::observability::clients::ServerClientInfo;
+use crate::observability::clock_skew::{COORDINATOR_TIME_QUERY_STR, ClockSkew};
+use crate::observability::connection_stats::ConnectionStats;
 use crate::observability::driver_tracing::RequestSpan;
 use crate::observability::history::{self, HistoryListener};
 #[cfg(feature = "metrics")]
 use crate::observability::metrics::Metrics;
+use crate::observability::metrics_sink::{MetricsSink, RequestCompleteEvent, RetryEvent};
+use crate::observability::statement_history::StatementExecution;
 use crate::observability::tracing::TracingInfo;
+use crate::observability::warnings::{ServerWarning, WarningsLog};
 use crate::policies::address_translator::AddressTranslator;
 use crate::policies::host_filter::HostFilter;
-use crate::policies::load_balancing::{self, RoutingInfo};
+use crate::policies::load_balancing::{
+    self, NodeIdentifier, RoutingInfo, SingleTargetLoadBalancingPolicy,
+};
 use crate::policies::reconnect::ExponentialReconnectPolicy;
 #[cfg(all(scylla_unstable, feature = "unstable-reconnect-policy"))]
 use crate::policies::reconnect::ReconnectPolicy;
 use crate::policies::retry::{RequestInfo, RetryDecision, RetrySession};
 use crate::policies::speculative_execution;
-use crate::policies::timestamp_generator::TimestampGenerator;
+use crate::policies::timestamp_generator::{SimpleTimestampGenerator, TimestampGenerator};
+#[cfg(all(scylla_unstable, feature = "unstable-csharp-rs"))]
+use crate::response::RawResponse;
 use crate::response::query_result::{MaybeFirstRowError, QueryResult, RowsError};
 use crate::response::{
-    Coordinator, NonErrorQueryResponse, PagingState, PagingStateResponse, QueryResponse,
+    AttemptInfo, Coordinator, NonErrorQueryResponse, PagingState, PagingStateResponse,
+    QueryResponse, is_local_datacenter,
 };
 use crate::routing::NodeLocationPreference;
 use crate::routing::partitioner::PartitionerName;
-use crate::routing::{Shard, ShardAwarePortRange};
+use crate::routing::{Shard, ShardAwarePortRange, Token};
 use crate::serialize::batch::BatchValues;
 use crate::serialize::row::{SerializeRow, SerializedValues};
 use crate::statement::batch::batch_values;
 use crate::statement::batch::{Batch, BatchStatement};
-use crate::statement::prepared::{PartitionKeyError, PreparedStatement};
+use crate::statement::prepared::{PartitionKeyError, PreparedStatement, RawPreparedStatement};
+use crate::statement::prepared_cache::PreloadedPreparedStatements;
 use crate::statement::unprepared::Statement;
-use crate::statement::{Consistency, PageSize, StatementConfig};
+use crate::statement::{
+    AttemptCallback, Consistency, PageSize, SerialConsistency, StatementConfig,
+};
+use crate::value::Row;
 use arc_swap::ArcSwapOption;
 use futures::future::join_all;
 use futures::future::try_join_all;
+use futures::{Stream, StreamExt, TryStreamExt};
 use itertools::Itertools;
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::net::{IpAddr, SocketAddr};
-use std::num::NonZeroU32;
+use std::num::{NonZeroU32, NonZeroUsize};
 use std::ops::ControlFlow;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, OnceLock};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
 use tokio::time::timeout;
-use tracing::{Instrument, debug, error, trace, trace_span};
+use tracing::{Instrument, debug, error, trace, trace_span, warn};
 use uuid::Uuid;
 
 pub(crate) const TABLET_CHANNEL_SIZE: usize = 8192;
 
+/// Default value of [`SessionConfig::frame_buffer_pool_size`].
+pub const DEFAULT_FRAME_BUFFER_POOL_SIZE: usize = 0;
+
+/// Default value of [`SessionConfig::frame_buffer_max_retained_size`].
+pub const DEFAULT_FRAME_BUFFER_MAX_RETAINED_SIZE: usize = 64 * 1024;
+
+/// Default value of [`SessionConfig::read_buffer_size`].
+pub const DEFAULT_READ_BUFFER_SIZE: usize = 8192;
+
 // Query used for schema agreement checks
 const SCHEMA_VERSION_QUERY_STR: &str = "SELECT schema_version FROM system.local WHERE key='local'";
 
@@ -98,7 +137,14 @@ pub struct Session {
     tracing_info_fetch_interval: Duration,
     tracing_info_fetch_consistency: Consistency,
     node_location_preference: Arc<NodeLocationPreference>,
+    max_attempts_per_request: Option<NonZeroU32>,
+    admission_queue: Option<Arc<AdmissionQueue>>,
+    timestamp_generator: Option<Arc<dyn TimestampGenerator>>,
     internal_statements: InternalStatements,
+    repreparation_set: Option<Arc<RepreparationSet>>,
+    preloaded_prepared: PreloadedPreparedStatements,
+    warnings_log: Option<Arc<WarningsLog>>,
+    metrics_sink: Option<Arc<dyn MetricsSink>>,
 }
 
 /// This implementation deliberately omits some details from Cluster in order
@@ -142,6 +188,8 @@ impl std::fmt::Debug for Session {
             &self.tracing_info_fetch_consistency,
         )
         .field("node_location_preference", &self.node_location_preference)
+        .field("max_attempts_per_request", &self.max_attempts_per_request)
+        .field("admission_queue", &self.admission_queue)
         .finish()
     }
 }
@@ -173,6 +221,38 @@ impl From<Arc<rustls::ClientConfig>> for TlsContext {
     }
 }
 
+/// Outcome of [`Session::dry_run`]: what executing a prepared statement
+/// with given values would look like, computed without contacting the cluster.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct DryRunInfo {
+    /// Size in bytes of the serialized bound values.
+    pub serialized_values_size: usize,
+
+    /// Token of the partition targeted by the statement,
+    /// or `None` if the statement is not token-aware.
+    pub token: Option<Token>,
+
+    /// Node which would be contacted first, according to the load balancing policy
+    /// and the statement's preferred replica, or `None` if the load balancing plan is empty.
+    pub coordinator: Option<Arc<Node>>,
+
+    /// Shard of the coordinator which would be contacted first,
+    /// or `None` if the coordinator is not sharded or there is no coordinator.
+    pub shard: Option<Shard>,
+}
+
+/// Outcome of [`Session::execute_auto`]: the whole result if it fit in a single page,
+/// or a pager over it otherwise.
+#[derive(Debug)]
+pub enum AutoExecutionResult {
+    /// The result fit in a single page, so it is returned whole.
+    Unpaged(QueryResult),
+
+    /// The result spans many pages, which are fetched by the pager.
+    Paged(QueryPager),
+}
+
 /// Configuration options for [`Session`].
 /// Can be created manually, but usually it's easier to use
 /// [SessionBuilder](super::session_builder::SessionBuilder)
@@ -209,6 +289,13 @@ pub struct SessionConfig {
     /// If it's not supported by database server Session will fall back to no compression.
     pub compression: Option<Compression>,
 
+    /// Request frames whose body is shorter than this number of bytes are sent uncompressed,
+    /// even if compression is enabled, because compressing them wastes CPU.
+    /// Whether response frames are compressed is decided by the server.
+    ///
+    /// The default is 0, which means that all request frames are compressed.
+    pub compression_threshold: usize,
+
     /// Whether to set the nodelay TCP flag.
     pub tcp_nodelay: bool,
 
@@ -266,6 +353,41 @@ pub struct SessionConfig {
     /// The default is `PerShard(1)`, which is the recommended setting for ScyllaDB clusters.
     pub connection_pool_size: PoolSize,
 
+    /// If set, the number of connections in each per-node pool scales with load,
+    /// between the bounds set in [`PoolAutoscaling`]. The number of connections
+    /// set in [`connection_pool_size`](Self::connection_pool_size) is then ignored,
+    /// and only its kind (per host or per shard) is used.
+    ///
+    /// The default is `None`, which keeps pool sizes fixed.
+    pub connection_pool_autoscaling: Option<PoolAutoscaling>,
+
+    /// How a request picks one of the pooled connections to its target node (or shard),
+    /// when there is more than one. The default is [`ConnectionSelection::Random`].
+    pub connection_selection: ConnectionSelection,
+
+    /// Maximum number of pooled connections the session may keep open to all nodes in total.
+    /// The limit is split evenly among the nodes, so that connections are distributed across them.
+    /// The default is `None`, i.e. no limit.
+    pub max_total_connections: Option<NonZeroUsize>,
+
+    /// Maximum number of connections which may be opened to a single node at the same time,
+    /// e.g. while its pool is being filled, so that opening many connections does not
+    /// overwhelm the node. The default is `None`, i.e. no limit.
+    pub max_concurrent_connection_opens: Option<NonZeroUsize>,
+
+    /// If true, statements cached by [`CachingSession`](crate::client::caching_session::CachingSession)s
+    /// wrapping the session are prepared in the background on each newly opened connection,
+    /// so that their first execution on it does not fail with UNPREPARED, e.g. after a node restart.
+    /// The default is false.
+    pub reprepare_on_new_connections: bool,
+
+    /// Path to a file with prepared statements saved with
+    /// [`save_prepared_cache`](crate::statement::prepared_cache::save_prepared_cache).
+    /// Preparing any of these statements with the session for the first time does not send
+    /// a PREPARE request, but uses the saved preparation result instead.
+    /// The default is `None`.
+    pub preload_prepared_from: Option<PathBuf>,
+
     /// If true, prevents the driver from connecting to the shard-aware port, even if the node supports it.
     /// Generally, this options is best left as default (false).
     pub disallow_shard_aware_port: bool,
@@ -288,6 +410,11 @@ pub struct SessionConfig {
     /// Custom timeout for requests that query metadata.
     pub metadata_request_serverside_timeout: Option<Duration>,
 
+    /// If true, a warm standby control connection is kept open to a second node.
+    /// When the control connection breaks, the standby is promoted in its place,
+    /// so that metadata refresh does not have to wait for a new connection.
+    pub standby_control_connection: bool,
+
     /// Interval of sending keepalive requests.
     /// If `None`, keepalives are never sent, so `Self::keepalive_timeout` has no effect.
     pub keepalive_interval: Option<Duration>,
@@ -296,6 +423,28 @@ pub struct SessionConfig {
     /// If `None`, connections are never closed due to lack of response to a keepalive message.
     pub keepalive_timeout: Option<Duration>,
 
+    /// Interval of sending RTT probes on each connection.
+    /// If `None`, RTT is not measured and [`ConnectionStats::rtt`] is always `None`.
+    pub rtt_probe_interval: Option<Duration>,
+
+    /// Maximum number of attempts made to execute a single request, counting retries
+    /// and attempts started by speculative execution. For paged requests,
+    /// it applies to fetching each page separately.
+    /// If `None`, the number of attempts is limited only by the retry policy
+    /// and the speculative execution policy.
+    pub max_attempts_per_request: Option<NonZeroU32>,
+
+    /// Maximum number of requests executed concurrently by the session.
+    /// Requests above the limit wait in a queue, ordered by their priority
+    /// (see [`Statement::set_priority`]).
+    /// If `None`, the number of concurrent requests is not limited.
+    pub max_concurrent_requests: Option<NonZeroUsize>,
+
+    /// Makes the limit of requests executed concurrently by the session adapt
+    /// to overload errors, see [`AdaptiveConcurrency`].
+    /// If set, `max_concurrent_requests` is ignored.
+    pub adaptive_concurrency: Option<AdaptiveConcurrency>,
+
     /// How often the driver should ask if schema is in agreement.
     pub schema_agreement_interval: Duration,
 
@@ -316,6 +465,11 @@ pub struct SessionConfig {
     /// If `None`, the driver will wait for hostname resolution indefinitely.
     pub hostname_resolution_timeout: Option<Duration>,
 
+    /// If true, IPv6 addresses are preferred over IPv4 ones when a hostname
+    /// resolves to both, e.g. on IPv6-only networks.
+    /// The default is false, which prefers IPv4 addresses.
+    pub prefer_ipv6: bool,
+
     /// The address translator is used to translate addresses received from ScyllaDB nodes
     /// (either with cluster metadata or with an event) to addresses that can be used to
     /// actually connect to those nodes. This may be needed e.g. when there is NAT
@@ -355,6 +509,56 @@ pub struct SessionConfig {
     /// This option is [`WriteCoalescingDelay::SmallNondeterministic`] by default.
     pub write_coalescing_delay: WriteCoalescingDelay,
 
+    /// Maximum number of idle frame buffers that the session keeps for reuse,
+    /// shared by all connections.
+    ///
+    /// Reusing buffers avoids allocating a new one for every request frame,
+    /// and for every compressed response frame.
+    /// Setting this to zero disables pooling. The default is [`DEFAULT_FRAME_BUFFER_POOL_SIZE`],
+    /// i.e. pooling is disabled unless requested.
+    pub frame_buffer_pool_size: usize,
+
+    /// The maximum capacity, in bytes, of a frame buffer kept for reuse.
+    ///
+    /// A buffer which grew larger, e.g. because a huge request was serialized into it,
+    /// is freed instead of being given back to the pool, so that its memory is not
+    /// retained for the lifetime of the session.
+    /// The default is [`DEFAULT_FRAME_BUFFER_MAX_RETAINED_SIZE`].
+    pub frame_buffer_max_retained_size: usize,
+
+    /// Size of the buffer, per connection, into which responses are read from the socket.
+    ///
+    /// Responses which are already available on the socket are read with a single syscall
+    /// as long as they fit in the buffer. Reads are never delayed to wait for more responses.
+    /// A larger buffer uses more memory on every connection. Setting this to zero disables
+    /// buffering, so the header and the body of each frame are read separately.
+    /// The default is [`DEFAULT_READ_BUFFER_SIZE`].
+    pub read_buffer_size: usize,
+
+    /// If set, connections on which too many requests fail are closed and replaced.
+    ///
+    /// The default is `None`, which disables the circuit breaker.
+    pub connection_circuit_breaker: Option<ErrorRateCircuitBreaker>,
+
+    /// If set, the session retains this many of the most recent warnings returned
+    /// by the database, which can be read with [`Session::recent_warnings`].
+    ///
+    /// The default is `None`, which disables retaining warnings.
+    pub recent_warnings_capacity: Option<NonZeroUsize>,
+
+    /// If set, metrics events of the session, such as completed requests,
+    /// retries and connection changes, are reported to this sink.
+    ///
+    /// The default is `None`.
+    pub metrics_sink: Option<Arc<dyn MetricsSink>>,
+
+    /// If true, Result responses of a kind unknown to the driver, e.g. introduced by a newer
+    /// protocol version, don't fail the request. Instead, their raw bytes are exposed with
+    /// [`QueryResult::unknown_result`](crate::response::query_result::QueryResult::unknown_result).
+    ///
+    /// The default is false.
+    pub pass_through_unknown_result_kinds: bool,
+
     /// Number of attempts to fetch [`TracingInfo`]
     /// in [`Session::get_tracing_info`]. Tracing info
     /// might not be available immediately on queried node - that's why
@@ -400,6 +604,7 @@ impl SessionConfig {
             local_ip_address: None,
             shard_aware_local_port_range: ShardAwarePortRange::EPHEMERAL_PORT_RANGE,
             compression: None,
+            compression_threshold: 0,
             tcp_nodelay: true,
             tcp_keepalive_interval: None,
             tcp_recv_buffer_size: None,
@@ -415,7 +620,14 @@ impl SessionConfig {
             authenticator: None,
             connect_timeout: Duration::from_secs(5),
             hostname_resolution_timeout: Some(Duration::from_secs(5)),
+            prefer_ipv6: false,
             connection_pool_size: Default::default(),
+            connection_pool_autoscaling: None,
+            connection_selection: Default::default(),
+            max_total_connections: None,
+            max_concurrent_connection_opens: None,
+            reprepare_on_new_connections: false,
+            preload_prepared_from: None,
             disallow_shard_aware_port: false,
             #[cfg(all(scylla_unstable, feature = "unstable-reconnect-policy"))]
             reconnect_policy: Arc::new(ExponentialReconnectPolicy::new()),
@@ -423,8 +635,13 @@ impl SessionConfig {
             keyspaces_to_fetch: Vec::new(),
             fetch_schema_metadata: true,
             metadata_request_serverside_timeout: Some(Duration::from_secs(2)),
+            standby_control_connection: false,
             keepalive_interval: Some(Duration::from_secs(30)),
             keepalive_timeout: Some(Duration::from_secs(30)),
+            rtt_probe_interval: None,
+            max_attempts_per_request: None,
+            max_concurrent_requests: None,
+            adaptive_concurrency: None,
             schema_agreement_timeout: Duration::from_secs(60),
             schema_agreement_automatic_waiting: true,
             address_translator: None,
@@ -434,6 +651,13 @@ impl SessionConfig {
             refresh_metadata_on_auto_schema_agreement: true,
             enable_write_coalescing: true,
             write_coalescing_delay: WriteCoalescingDelay::SmallNondeterministic,
+            frame_buffer_pool_size: DEFAULT_FRAME_BUFFER_POOL_SIZE,
+            frame_buffer_max_retained_size: DEFAULT_FRAME_BUFFER_MAX_RETAINED_SIZE,
+            read_buffer_size: DEFAULT_READ_BUFFER_SIZE,
+            connection_circuit_breaker: None,
+            recent_warnings_capacity: None,
+            metrics_sink: None,
+            pass_through_unknown_result_kinds: false,
             tracing_info_fetch_attempts: NonZeroU32::new(10).unwrap(),
             tracing_info_fetch_interval: Duration::from_millis(3),
             tracing_info_fetch_consistency: Consistency::One,
@@ -529,6 +753,12 @@ impl SessionConfig {
             ));
         }
 
+        if self.rtt_probe_interval == Some(Duration::ZERO) {
+            return Err(NewSessionError::IllegalConfig(
+                "RTT probe interval must be non-zero".into(),
+            ));
+        }
+
         // Ensure no illegal configuration with Client Routes
         #[cfg(feature = "unstable-client-routes")]
         if self.client_routes_config.is_some() {
@@ -557,6 +787,26 @@ pub(crate) enum RunRequestResult<ResT> {
     Completed(ResT),
 }
 
+/// A successful response to a single request attempt made by [`Session::run_request`].
+trait AttemptResponse {
+    /// Returns the parsed response, which is inspected for `USE <keyspace>` results
+    /// and schema changes. Responses that the driver doesn't parse are not inspected.
+    fn as_parsed(&self) -> Option<&NonErrorQueryResponse>;
+}
+
+impl AttemptResponse for NonErrorQueryResponse {
+    fn as_parsed(&self) -> Option<&NonErrorQueryResponse> {
+        Some(self)
+    }
+}
+
+#[cfg(all(scylla_unstable, feature = "unstable-csharp-rs"))]
+impl AttemptResponse for RawResponse {
+    fn as_parsed(&self) -> Option<&NonErrorQueryResponse> {
+        None
+    }
+}
+
 /// Represents a CQL session, which can be used to communicate
 /// with the database
 impl Session {
@@ -621,7 +871,8 @@ impl Session {
         statement: impl Into<Statement>,
         values: impl SerializeRow,
     ) -> Result<QueryResult, ExecutionError> {
-        self.do_query_unpaged(&statement.into(), values).await
+        self.do_query_unpaged(&statement.into().rewritten(), values)
+            .await
     }
 
     /// Queries a single page from the database, optionally continuing from a saved point.
@@ -681,7 +932,7 @@ impl Session {
         values: impl SerializeRow,
         paging_state: PagingState,
     ) -> Result<(QueryResult, PagingStateResponse), ExecutionError> {
-        self.do_query_single_page(&statement.into(), values, paging_state)
+        self.do_query_single_page(&statement.into().rewritten(), values, paging_state)
             .await
     }
 
@@ -726,7 +977,8 @@ impl Session {
         statement: impl Into<Statement>,
         values: impl SerializeRow,
     ) -> Result<QueryPager, PagerExecutionError> {
-        self.do_query_iter(statement.into(), values).await
+        self.do_query_iter(statement.into().rewritten(), values)
+            .await
     }
 
     /// Execute a prepared statement. Requires a [PreparedStatement]
@@ -778,13 +1030,34 @@ impl Session {
         values: impl SerializeRow,
     ) -> Result<QueryResult, ExecutionError> {
         let serialized_values = prepared.serialize_values(&values)?;
+        self.execute_unpaged_nongeneric(prepared, &serialized_values)
+            .await
+    }
+
+    async fn execute_unpaged_nongeneric(
+        &self,
+        prepared: &PreparedStatement,
+        serialized_values: &SerializedValues,
+    ) -> Result<QueryResult, ExecutionError> {
         let (result, paging_state) = self
-            .execute(prepared, &serialized_values, None, PagingState::start())
+            .execute(
+                prepared,
+                serialized_values,
+                prepared.config.unpaged_page_size(),
+                PagingState::start(),
+            )
             .await?;
+        check_unpaged_row_limit(&prepared.config, &result)
+            .map_err(ExecutionError::UnpagedRowLimitExceeded)?;
         if !paging_state.finished() {
-            error!(
-                "Unpaged prepared query returned a non-empty paging state! This is a driver-side or server-side bug."
-            );
+            if prepared.config.max_unpaged_rows.is_some() {
+                // The page size was limited, so the server may have ended the page early.
+                debug!("Unpaged prepared query with a row limit returned an incomplete page");
+            } else {
+                error!(
+                    "Unpaged prepared query returned a non-empty paging state! This is a driver-side or server-side bug."
+                );
+            }
             return Err(ExecutionError::LastAttemptError(
                 RequestAttemptError::NonfinishedPagingState,
             ));
@@ -792,6 +1065,166 @@ impl Session {
         Ok(result)
     }
 
+    /// Serializes values and routes a prepared statement, without sending it to the cluster.
+    ///
+    /// Performs the same type checking, serialization and token computation as
+    /// [Session::execute_unpaged], and asks the load balancing policy for the first
+    /// target of the plan, but does not perform any network I/O. This makes it useful
+    /// for validating statements and values, e.g. in CI.
+    ///
+    /// Note that the load balancing policy is queried as usual, so stateful
+    /// policies may observe the dry run as a regular request.
+    // The error type is shared with the executing methods, for which it is returned in a future.
+    // TODO(2.0): Make ExecutionError smaller.
+    #[expect(clippy::result_large_err)]
+    pub fn dry_run(
+        &self,
+        prepared: &PreparedStatement,
+        values: impl SerializeRow,
+    ) -> Result<DryRunInfo, ExecutionError> {
+        let serialized_values = prepared.serialize_values(&values)?;
+        let token = prepared
+            .calculate_token_untyped(&serialized_values)
+            .map_err(PartitionKeyError::into_execution_error)?;
+
+        let execution_profile = prepared
+            .get_execution_profile_handle()
+            .unwrap_or_else(|| self.get_default_execution_profile_handle())
+            .access();
+
+        let statement_info = RoutingInfo {
+            consistency: prepared
+                .config
+                .consistency
+                .unwrap_or(execution_profile.consistency),
+            serial_consistency: prepared
+                .config
+                .serial_consistency
+                .unwrap_or(execution_profile.serial_consistency),
+            token,
+            table: prepared.get_routing_table_spec(),
+            is_confirmed_lwt: prepared.is_confirmed_lwt(),
+            node_location_preference: &self.node_location_preference,
+        };
+
+        let load_balancer = prepared
+            .config
+            .load_balancing_policy
+            .as_deref()
+            .unwrap_or(execution_profile.load_balancing_policy.as_ref());
+        let cluster_state = self.get_cluster_state();
+        let preferred_replica = prepared
+            .config
+            .preferred_replica_index
+            .and_then(|index| cluster_state.replica_at(&statement_info, index));
+        let first_target = load_balancing::with_preferred_first(
+            load_balancing::Plan::new(load_balancer, &statement_info, &cluster_state),
+            preferred_replica,
+        )
+        .find(|(node, _)| !prepared.config.is_node_excluded(node));
+
+        Ok(DryRunInfo {
+            serialized_values_size: serialized_values.buffer_size(),
+            token,
+            shard: first_target.and_then(|(node, shard)| node.sharder().is_some().then_some(shard)),
+            coordinator: first_target.map(|(node, _)| Arc::clone(node)),
+        })
+    }
+
+    /// Executes a prepared write, recording what is needed to read it back.
+    ///
+    /// Unless the statement has a timestamp set, the write is assigned one on the client side,
+    /// using the session's [`TimestampGenerator`] if configured. The returned [`WriteToken`]
+    /// can then be passed to [Session::execute_read_your_writes].
+    ///
+    /// Writes which are lightweight transactions are recognized only if the database
+    /// supports the LWT optimisation extension (see [PreparedStatement::is_confirmed_lwt]).
+    pub async fn execute_write_tracked(
+        &self,
+        prepared: &PreparedStatement,
+        values: impl SerializeRow,
+    ) -> Result<(QueryResult, WriteToken), ExecutionError> {
+        let execution_profile = prepared
+            .get_execution_profile_handle()
+            .unwrap_or_else(|| self.get_default_execution_profile_handle())
+            .access();
+        let serial_consistency = prepared.is_confirmed_lwt().then(|| {
+            prepared
+                .config
+                .serial_consistency
+                .unwrap_or(execution_profile.serial_consistency)
+                .unwrap_or(SerialConsistency::Serial)
+        });
+
+        let mut prepared = Cow::Borrowed(prepared);
+        let timestamp = match prepared.get_timestamp() {
+            // Lightweight transactions can't have custom timestamps.
+            _ if serial_consistency.is_some() => None,
+            Some(timestamp) => Some(timestamp),
+            None => {
+                let timestamp = match self.timestamp_generator.as_deref() {
+                    Some(generator) => generator.next_timestamp(),
+                    None => SimpleTimestampGenerator::new().next_timestamp(),
+                };
+                prepared.to_mut().set_timestamp(Some(timestamp));
+                Some(timestamp)
+            }
+        };
+
+        let result = self.execute_unpaged(&prepared, values).await?;
+        // The retry policy may have downgraded the consistency of the write.
+        let consistency = result
+            .attempt_info()
+            .effective_consistency
+            .or(prepared.config.consistency)
+            .unwrap_or(execution_profile.consistency);
+        let token = WriteToken {
+            timestamp,
+            consistency,
+            serial_consistency,
+            keyspace: prepared.get_keyspace_name().map(ToOwned::to_owned),
+        };
+        Ok((result, token))
+    }
+
+    /// Executes a prepared read, so that it observes the write described by `write`.
+    ///
+    /// The read is performed at the weakest consistency level which guarantees that,
+    /// given the consistency level of the write and the replication of its keyspace.
+    /// If the write was performed at a level for which no such guarantee is possible
+    /// (e.g. `ANY`), a warning is logged and the read is performed at its own consistency level.
+    ///
+    /// Note that a write at `LOCAL_QUORUM` is guaranteed to be observed only by reads
+    /// served by the same datacenter.
+    pub async fn execute_read_your_writes(
+        &self,
+        write: &WriteToken,
+        prepared: &PreparedStatement,
+        values: impl SerializeRow,
+    ) -> Result<QueryResult, ExecutionError> {
+        let cluster_state = self.get_cluster_state();
+        let strategy = write
+            .keyspace
+            .as_ref()
+            .and_then(|keyspace| cluster_state.get_keyspace(keyspace))
+            .map(|keyspace| &keyspace.strategy);
+
+        match write.read_consistency(strategy) {
+            Some(consistency) => {
+                let mut prepared = prepared.clone();
+                prepared.set_consistency(consistency);
+                self.execute_unpaged(&prepared, values).await
+            }
+            None => {
+                warn!(
+                    "Visibility of a write at consistency {} can't be guaranteed; the read may not observe it",
+                    write.consistency
+                );
+                self.execute_unpaged(prepared, values).await
+            }
+        }
+    }
+
     /// Executes a prepared statement, restricting results to single page.
     /// Optionally continues fetching results from a saved point.
     ///
@@ -856,8 +1289,129 @@ impl Session {
     ) -> Result<(QueryResult, PagingStateResponse), ExecutionError> {
         let serialized_values = prepared.serialize_values(&values)?;
         let page_size = prepared.get_validated_page_size();
-        self.execute(prepared, &serialized_values, Some(page_size), paging_state)
-            .await
+        self.execute(
+            prepared,
+            &serialized_values,
+            prepared.config.requested_page_size(page_size),
+            paging_state.clone(),
+        )
+        .await
+        .map_err(|err| map_expired_paging_state(&paging_state, err))
+    }
+
+    /// Executes a prepared statement, restricting results to single page,
+    /// and returns the rows of the page together with the paging state of the next page.
+    ///
+    /// This is a convenience over [`Session::execute_single_page`] for cursor-based APIs.
+    /// The returned paging state is `None` if there are no more pages.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # use std::error::Error;
+    /// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+    /// use scylla::response::PagingState;
+    /// use scylla::statement::unprepared::Statement;
+    ///
+    /// let paged_prepared = session
+    ///     .prepare(Statement::new("SELECT a, b FROM ks.tbl").with_page_size(100))
+    ///     .await?;
+    ///
+    /// let mut paging_state = Some(PagingState::start());
+    /// while let Some(state) = paging_state {
+    ///     let (rows, next_paging_state) = session
+    ///         .execute_single_page_rows(&paged_prepared, &[], state)
+    ///         .await?;
+    ///     for row in rows {
+    ///         println!("{:?}", row.columns);
+    ///     }
+    ///     paging_state = next_paging_state;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_single_page_rows(
+        &self,
+        prepared: &PreparedStatement,
+        values: impl SerializeRow,
+        paging_state: PagingState,
+    ) -> Result<(Vec<Row>, Option<PagingState>), SinglePageRowsError> {
+        let (result, paging_state_response) = self
+            .execute_single_page(prepared, values, paging_state)
+            .await?;
+        let rows_result = result.into_rows_result()?;
+        // `Row` accepts any column types, so the type check can't fail.
+        let rows = rows_result
+            .rows::<Row>()
+            .expect("Row accepts all column types")
+            .collect::<Result<Vec<_>, _>>()?;
+        let next_paging_state = match paging_state_response.into_paging_control_flow() {
+            ControlFlow::Continue(paging_state) => Some(paging_state),
+            ControlFlow::Break(()) => None,
+        };
+        Ok((rows, next_paging_state))
+    }
+
+    /// Executes a prepared statement, choosing between unpaged and paged execution
+    /// by the size of the result.
+    ///
+    /// The first page is fetched, with the page size of the statement. If it holds
+    /// the whole result, it is returned as [`AutoExecutionResult::Unpaged`]. Otherwise,
+    /// [`AutoExecutionResult::Paged`] is returned with a pager over the result, as created
+    /// by [`Session::execute_iter`]. The pager yields the already fetched first page first,
+    /// and then fetches the remaining pages, continuing from where the first page ended.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # use futures::TryStreamExt as _;
+    /// # use std::error::Error;
+    /// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+    /// use scylla::client::session::AutoExecutionResult;
+    ///
+    /// let prepared = session.prepare("SELECT a, b FROM ks.t").await?;
+    ///
+    /// let rows: Vec<(i32, i32)> = match session.execute_auto(&prepared, &[]).await? {
+    ///     AutoExecutionResult::Unpaged(result) => {
+    ///         result.into_rows_result()?.rows()?.collect::<Result<_, _>>()?
+    ///     }
+    ///     AutoExecutionResult::Paged(pager) => pager.rows_stream()?.try_collect().await?,
+    /// };
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_auto(
+        &self,
+        prepared: &PreparedStatement,
+        values: impl SerializeRow,
+    ) -> Result<AutoExecutionResult, AutoExecutionError> {
+        let serialized_values = prepared
+            .serialize_values(&values)
+            .map_err(ExecutionError::from)?;
+        let page_size = prepared.get_validated_page_size();
+        let (result, paging_state_response) = self
+            .execute(
+                prepared,
+                &serialized_values,
+                prepared.config.requested_page_size(page_size),
+                PagingState::start(),
+            )
+            .await?;
+        let paging_state = match paging_state_response.into_paging_control_flow() {
+            ControlFlow::Continue(paging_state) => paging_state,
+            ControlFlow::Break(()) => return Ok(AutoExecutionResult::Unpaged(result)),
+        };
+
+        let pager = self
+            .execute_iter_nongeneric(
+                prepared.clone(),
+                serialized_values,
+                Some((result, paging_state)),
+            )
+            .await?;
+        Ok(AutoExecutionResult::Paged(pager))
     }
 
     /// Execute a prepared statement with paging.\
@@ -907,10 +1461,119 @@ impl Session {
         let prepared = prepared.into();
         let serialized_values = prepared.serialize_values(&values)?;
 
-        self.execute_iter_nongeneric(prepared, serialized_values)
+        self.execute_iter_nongeneric(prepared, serialized_values, None)
             .await
     }
 
+    /// Fetches rows for many keys with a prepared point-read statement.
+    ///
+    /// The statement is executed once for each key, with the key as its bound values.
+    /// Keys are grouped by the primary replica owning them, and reads are issued
+    /// concurrently - at most `concurrency` at a time - interleaving the groups,
+    /// so that the load is spread evenly across the replicas.
+    ///
+    /// Rows are yielded in the order the reads complete, which is not necessarily
+    /// the order of `keys`. A failed read yields a single error in place of its rows;
+    /// the remaining reads are not affected.
+    ///
+    /// # Arguments
+    /// * `prepared_select` - the prepared statement reading the rows of a single key
+    /// * `keys` - values bound to the statement, one per read
+    /// * `concurrency` - maximum number of reads in flight at once
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # use std::error::Error;
+    /// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+    /// use futures::TryStreamExt;
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let prepared = session
+    ///     .prepare("SELECT a, b FROM ks.tab WHERE a = ?")
+    ///     .await?;
+    ///
+    /// let rows: Vec<(i32, i32)> = session
+    ///     .multiget::<_, (i32, i32)>(
+    ///         &prepared,
+    ///         (0..100_i32).map(|a| (a,)),
+    ///         NonZeroUsize::new(64).unwrap(),
+    ///     )
+    ///     .try_collect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn multiget<'a, K, RowT>(
+        &'a self,
+        prepared_select: &'a PreparedStatement,
+        keys: impl IntoIterator<Item = K>,
+        concurrency: NonZeroUsize,
+    ) -> impl Stream<Item = Result<RowT, MultigetError>>
+    where
+        K: SerializeRow,
+        RowT: for<'frame, 'metadata> DeserializeRow<'frame, 'metadata> + 'a,
+    {
+        let cluster_state = self.get_cluster_state();
+        let table_spec = prepared_select.get_routing_table_spec();
+
+        // Keys whose replica can't be determined are put into a single group of their own.
+        let mut groups: HashMap<Option<Uuid>, Vec<Result<SerializedValues, SerializationError>>> =
+            HashMap::new();
+        for key in keys {
+            let values = prepared_select.serialize_values(&key);
+            let replica = match (&values, table_spec) {
+                (Ok(values), Some(table_spec)) => prepared_select
+                    .calculate_token_untyped(values)
+                    .ok()
+                    .flatten()
+                    .and_then(|token| {
+                        cluster_state
+                            .get_token_endpoints_iter(table_spec, token)
+                            .next()
+                            .map(|(node, _shard)| node.host_id)
+                    }),
+                _ => None,
+            };
+            groups.entry(replica).or_default().push(values);
+        }
+
+        // Take keys from each group in turn, so that the reads in flight
+        // target all replicas instead of exhausting them one by one.
+        let mut groups = groups.into_values().map(Vec::into_iter).collect::<Vec<_>>();
+        let mut reads = Vec::new();
+        while !groups.is_empty() {
+            groups.retain_mut(|group| match group.next() {
+                Some(values) => {
+                    reads.push(values);
+                    true
+                }
+                None => false,
+            });
+        }
+
+        futures::stream::iter(reads)
+            .map(move |values| async move {
+                let rows_result = self
+                    .execute_unpaged_nongeneric(prepared_select, &values?)
+                    .await?
+                    .into_rows_result()?;
+                let rows = rows_result.rows::<RowT>().map_err(|err| match err {
+                    RowsError::TypeCheckFailed(err) => MultigetError::TypeCheck(err),
+                })?;
+                rows.collect::<Result<Vec<_>, _>>()
+                    .map_err(MultigetError::RowDeserialization)
+            })
+            .buffer_unordered(concurrency.get())
+            .flat_map(|read_result| {
+                let rows = match read_result {
+                    Ok(rows) => rows.into_iter().map(Ok).collect::<Vec<_>>(),
+                    Err(err) => vec![Err(err)],
+                };
+                futures::stream::iter(rows)
+            })
+    }
+
     /// Execute a batch statement\
     /// Batch contains many `unprepared` or `prepared` statements which are executed at once\
     /// Batch doesn't return any rows.
@@ -993,7 +1656,7 @@ impl Session {
 
         let table_spec =
             if let Some(BatchStatement::PreparedStatement(ps)) = batch.statements.first() {
-                ps.get_table_spec()
+                ps.get_routing_table_spec()
             } else {
                 None
             };
@@ -1009,9 +1672,10 @@ impl Session {
 
         let span = RequestSpan::new_batch();
 
-        let (run_request_result, coordinator): (
+        let (run_request_result, coordinator, attempt_info): (
             RunRequestResult<NonErrorQueryResponse>,
             Coordinator,
+            AttemptInfo,
         ) = self
             .run_request(
                 statement_info,
@@ -1019,11 +1683,8 @@ impl Session {
                 execution_profile,
                 |connection: Arc<Connection>,
                  consistency: Consistency,
-                 execution_profile: &ExecutionProfileInner| {
-                    let serial_consistency = batch
-                        .config
-                        .serial_consistency
-                        .unwrap_or(execution_profile.serial_consistency);
+                 serial_consistency: Option<SerialConsistency>,
+                 _execution_profile: &ExecutionProfileInner| {
                     async move {
                         connection
                             .batch_with_consistency(
@@ -1041,16 +1702,53 @@ impl Session {
             .instrument(span.span().clone())
             .await?;
 
+        let mut attempt_info = attempt_info;
         let result = match run_request_result {
             RunRequestResult::IgnoredWriteError => QueryResult::mock_empty(coordinator),
             RunRequestResult::Completed(non_error_query_response) => {
+                attempt_info.bytes_sent = non_error_query_response.bytes_sent;
+                attempt_info.bytes_received = non_error_query_response.bytes_received;
                 let result = non_error_query_response.into_query_result(coordinator)?;
                 span.record_result_fields(&result);
                 result
             }
         };
 
-        Ok(result)
+        Ok(result.with_attempt_info(attempt_info))
+    }
+
+    /// Performs a batch request, which can be cancelled with the returned [`CancelHandle`].
+    ///
+    /// Works like [`Session::batch`], but the execution can be cancelled from outside
+    /// of the task awaiting it. A cancelled execution completes with [`ExecutionError::Cancelled`],
+    /// and the stream id of the abandoned request is reclaimed once its response arrives.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # use std::error::Error;
+    /// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+    /// use scylla::statement::batch::Batch;
+    ///
+    /// let mut batch: Batch = Default::default();
+    /// batch.append_statement("INSERT INTO ks.tab(a, b) VALUES(1, 2)");
+    ///
+    /// let (cancel_handle, execution) = session.batch_cancellable(&batch, ((),));
+    /// // The handle may be passed to a different task, e.g. one handling a shutdown.
+    /// cancel_handle.cancel();
+    /// assert!(execution.await.is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn batch_cancellable<'a>(
+        &'a self,
+        batch: &'a Batch,
+        values: impl BatchValues + 'a,
+    ) -> (
+        CancelHandle,
+        impl Future<Output = Result<QueryResult, ExecutionError>> + 'a,
+    ) {
+        cancellable(self.batch(batch, values))
     }
 
     /// Estabilishes a CQL session with the database
@@ -1102,10 +1800,15 @@ impl Session {
             None
         };
 
+        let warnings_log = config
+            .recent_warnings_capacity
+            .map(|capacity| Arc::new(WarningsLog::new(capacity)));
+
         let connection_config = ConnectionConfig {
             local_ip_address: config.local_ip_address,
             shard_aware_local_port_range: config.shard_aware_local_port_range,
             compression: config.compression,
+            compression_threshold: config.compression_threshold,
             tcp_socket_options: TcpSocketOptions {
                 nodelay: config.tcp_nodelay,
                 keepalive_interval: config.tcp_keepalive_interval,
@@ -1114,7 +1817,7 @@ impl Session {
                 reuse_address: config.tcp_reuse_address,
                 linger: config.tcp_linger,
             },
-            timestamp_generator: config.timestamp_generator,
+            timestamp_generator: config.timestamp_generator.clone(),
             tls_provider,
             authenticator: config.authenticator,
             connect_timeout: config.connect_timeout,
@@ -1124,20 +1827,46 @@ impl Session {
             write_coalescing_delay: config
                 .enable_write_coalescing
                 .then_some(config.write_coalescing_delay),
+            frame_buffer_pool: (config.frame_buffer_pool_size > 0).then(|| {
+                FrameBufferPool::new(
+                    config.frame_buffer_pool_size,
+                    config.frame_buffer_max_retained_size,
+                )
+            }),
+            read_buffer_size: config.read_buffer_size,
+            circuit_breaker: config.connection_circuit_breaker,
+            warnings_log: warnings_log.clone(),
+            pass_through_unknown_result_kinds: config.pass_through_unknown_result_kinds,
             keepalive_interval: config.keepalive_interval,
             keepalive_timeout: config.keepalive_timeout,
+            rtt_probe_interval: config.rtt_probe_interval,
             tablet_sender: Some(tablet_sender),
             identity: config.identity,
         };
 
+        let preloaded_prepared = match config.preload_prepared_from.as_deref() {
+            Some(path) => PreloadedPreparedStatements::load(path).await?,
+            None => PreloadedPreparedStatements::default(),
+        };
+
+        let repreparation_set = config
+            .reprepare_on_new_connections
+            .then(|| Arc::new(RepreparationSet::default()));
+
         let pool_config = PoolConfig {
             connection_config,
             pool_size: config.connection_pool_size,
+            autoscaling: config.connection_pool_autoscaling,
             can_use_shard_aware_port: !config.disallow_shard_aware_port,
             #[cfg(all(scylla_unstable, feature = "unstable-reconnect-policy"))]
             reconnect_policy: config.reconnect_policy,
             #[cfg(not(all(scylla_unstable, feature = "unstable-reconnect-policy")))]
             reconnect_policy: Arc::new(ExponentialReconnectPolicy::new()),
+            connection_budget: config.max_total_connections.map(ConnectionBudget::new),
+            repreparation_set: repreparation_set.clone(),
+            connection_selection: config.connection_selection,
+            max_concurrent_opens: config.max_concurrent_connection_opens,
+            metrics_sink: config.metrics_sink.clone(),
         };
 
         #[cfg(feature = "metrics")]
@@ -1171,7 +1900,9 @@ impl Session {
             config.keyspaces_to_fetch,
             config.fetch_schema_metadata,
             config.metadata_request_serverside_timeout,
+            config.standby_control_connection,
             config.hostname_resolution_timeout,
+            config.prefer_ipv6,
             config.host_filter,
             host_listener,
             config.cluster_metadata_refresh_interval,
@@ -1183,6 +1914,19 @@ impl Session {
         .await?;
 
         let default_execution_profile_handle = config.default_execution_profile_handle;
+        let admission_queue = match (config.adaptive_concurrency, config.max_concurrent_requests) {
+            (Some(adaptive), _) => Some(Arc::new(AdmissionQueue::new_adaptive(
+                adaptive,
+                #[cfg(feature = "metrics")]
+                Arc::clone(&metrics),
+            ))),
+            (None, Some(max)) => Some(Arc::new(AdmissionQueue::new(
+                max,
+                #[cfg(feature = "metrics")]
+                Arc::clone(&metrics),
+            ))),
+            (None, None) => None,
+        };
 
         let session = Self {
             cluster,
@@ -1199,7 +1943,14 @@ impl Session {
             tracing_info_fetch_interval: config.tracing_info_fetch_interval,
             tracing_info_fetch_consistency: config.tracing_info_fetch_consistency,
             node_location_preference: Arc::new(node_location_preference),
+            max_attempts_per_request: config.max_attempts_per_request,
+            admission_queue,
+            timestamp_generator: config.timestamp_generator,
             internal_statements: InternalStatements::default(),
+            repreparation_set,
+            preloaded_prepared,
+            warnings_log,
+            metrics_sink: config.metrics_sink,
         };
 
         if let Some(keyspace_name) = config.used_keyspace {
@@ -1251,12 +2002,24 @@ impl Session {
         values: impl SerializeRow,
     ) -> Result<QueryResult, ExecutionError> {
         let (result, paging_state_response) = self
-            .query(statement, values, None, PagingState::start())
+            .query(
+                statement,
+                values,
+                statement.config.unpaged_page_size(),
+                PagingState::start(),
+            )
             .await?;
+        check_unpaged_row_limit(&statement.config, &result)
+            .map_err(ExecutionError::UnpagedRowLimitExceeded)?;
         if !paging_state_response.finished() {
-            error!(
-                "Unpaged unprepared query returned a non-empty paging state! This is a driver-side or server-side bug."
-            );
+            if statement.config.max_unpaged_rows.is_some() {
+                // The page size was limited, so the server may have ended the page early.
+                debug!("Unpaged unprepared query with a row limit returned an incomplete page");
+            } else {
+                error!(
+                    "Unpaged unprepared query returned a non-empty paging state! This is a driver-side or server-side bug."
+                );
+            }
             return Err(ExecutionError::LastAttemptError(
                 RequestAttemptError::NonfinishedPagingState,
             ));
@@ -1273,10 +2036,13 @@ impl Session {
         self.query(
             statement,
             values,
-            Some(statement.get_validated_page_size()),
-            paging_state,
+            statement
+                .config
+                .requested_page_size(statement.get_validated_page_size()),
+            paging_state.clone(),
         )
         .await
+        .map_err(|err| map_expired_paging_state(&paging_state, err))
     }
 
     /// Sends a request to the database.
@@ -1319,9 +2085,10 @@ impl Session {
 
         let span = RequestSpan::new_query(&statement.contents);
         let span_ref = &span;
-        let (run_request_result, coordinator): (
+        let (run_request_result, coordinator, attempt_info): (
             RunRequestResult<NonErrorQueryResponse>,
             Coordinator,
+            AttemptInfo,
         ) = self
             .run_request(
                 statement_info,
@@ -1329,11 +2096,8 @@ impl Session {
                 execution_profile,
                 |connection: Arc<Connection>,
                  consistency: Consistency,
-                 execution_profile: &ExecutionProfileInner| {
-                    let serial_consistency = statement
-                        .config
-                        .serial_consistency
-                        .unwrap_or(execution_profile.serial_consistency);
+                 serial_consistency: Option<SerialConsistency>,
+                 _execution_profile: &ExecutionProfileInner| {
                     // Needed to avoid moving query and values into async move block
                     let values_ref = &values;
                     let paging_state_ref = &paging_state;
@@ -1380,15 +2144,27 @@ impl Session {
                 ),
                 tracing_id: None,
                 warnings: Vec::new(),
+                unknown_result: None,
+                bytes_sent: 0,
+                bytes_received: 0,
+                response_flags: 0,
             },
             RunRequestResult::Completed(response) => response,
         };
 
+        let attempt_info = AttemptInfo {
+            bytes_sent: response.bytes_sent,
+            bytes_received: response.bytes_received,
+            ..attempt_info
+        };
         let (result, paging_state_response) =
             response.into_query_result_and_paging_state(coordinator)?;
         span.record_result_fields(&result);
 
-        Ok((result, paging_state_response))
+        Ok((
+            result.with_attempt_info(attempt_info),
+            paging_state_response,
+        ))
     }
 
     pub(crate) async fn handle_set_keyspace_response(
@@ -1461,7 +2237,7 @@ impl Session {
             // we fully prepare a statement beforehand.
             let prepared = self.prepare_nongeneric(&statement).await?;
             let values = prepared.serialize_values(&values)?;
-            self.execute_iter_nongeneric(prepared, values).await
+            self.execute_iter_nongeneric(prepared, values, None).await
         }
     }
 
@@ -1483,6 +2259,9 @@ impl Session {
             #[cfg(feature = "metrics")]
             Arc::clone(&self.metrics),
             Arc::clone(&self.node_location_preference),
+            self.max_attempts_per_request,
+            self.metrics_sink.clone(),
+            self.admission_queue.clone(),
         )
         .await
     }
@@ -1553,22 +2332,137 @@ impl Session {
         self.prepare_nongeneric(&statement).await
     }
 
+    /// Prepares a statement in the given keyspace, associating it with the keyspace for routing.
+    ///
+    /// Unqualified table names used by the statement refer to `keyspace`, regardless of
+    /// the keyspace used by the session (see [Session::use_keyspace]), if any. Replicas of
+    /// the statement's partitions are computed using the replication of `keyspace`, as with
+    /// [`PreparedStatement::set_routing_keyspace`].
+    ///
+    /// The CQL protocol v4 has no way to send a keyspace along with a statement to be prepared.
+    /// Unless the session uses `keyspace`, the statement is therefore prepared on a new
+    /// connection to every node, which uses `keyspace` and is closed afterwards. This is
+    /// costlier than [Session::prepare]. The returned statement remembers `keyspace`, so if
+    /// a node forgets it (e.g. after a restart), it is reprepared in `keyspace` the same way.
+    ///
+    /// An invalid keyspace name is reported as [`DbError::Invalid`], like a keyspace
+    /// which does not exist.
+    pub async fn prepare_in_keyspace(
+        &self,
+        statement: impl Into<Statement>,
+        keyspace: impl Into<String>,
+    ) -> Result<PreparedStatement, PrepareError> {
+        let statement = statement.into();
+        let keyspace = keyspace.into();
+        let verified_keyspace =
+            VerifiedKeyspaceName::new(keyspace.clone(), false).map_err(|err| {
+                PrepareError::AllAttemptsFailed {
+                    first_attempt: RequestAttemptError::DbError(DbError::Invalid, err.to_string()),
+                }
+            })?;
+        let mut prepared = if self
+            .get_keyspace()
+            .is_some_and(|session_keyspace| *session_keyspace == keyspace)
+        {
+            self.prepare_nongeneric(&statement).await?
+        } else {
+            let cluster_state = self.get_cluster_state();
+            let mut connections_to_nodes = cluster_state.iter_working_connections_to_nodes()?;
+            let mut prepared = Self::prepare_on_all(
+                &statement,
+                Some(&verified_keyspace),
+                &cluster_state,
+                &mut connections_to_nodes,
+            )
+            .await?;
+            prepared.set_prepared_in_keyspace(Some(Arc::new(keyspace.clone())));
+            prepared
+        };
+        // The session may switch to another keyspace later on.
+        prepared.set_reprepare_keyspace(Some(verified_keyspace));
+        prepared.set_routing_keyspace(Some(keyspace));
+        Ok(prepared)
+    }
+
+    /// Prepares many statements, with at most `concurrency` of them being prepared at a time.
+    ///
+    /// Each statement is prepared as with [Session::prepare], so preparing a single statement
+    /// sends a PREPARE request to every node. Limiting the number of concurrent preparations
+    /// avoids overwhelming the nodes when preparing a large number of statements at once.
+    ///
+    /// Prepared statements are returned in the order of `statements`.
+    /// Fails with the first error encountered, in which case the preparations
+    /// still in progress are cancelled.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # use std::error::Error;
+    /// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let prepared = session
+    ///     .prepare_all(
+    ///         [
+    ///             "INSERT INTO ks.tab (a) VALUES(?)",
+    ///             "SELECT a FROM ks.tab WHERE a = ?",
+    ///         ],
+    ///         NonZeroUsize::new(8).unwrap(),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn prepare_all(
+        &self,
+        statements: impl IntoIterator<Item = impl Into<Statement>>,
+        concurrency: NonZeroUsize,
+    ) -> Result<Vec<PreparedStatement>, PrepareError> {
+        futures::stream::iter(statements)
+            .map(|statement| async move { self.prepare_nongeneric(&statement.into()).await })
+            .buffered(concurrency.get())
+            .try_collect()
+            .await
+    }
+
     // Introduced to avoid monomorphisation of this large function.
     async fn prepare_nongeneric(
         &self,
         statement: &Statement,
     ) -> Result<PreparedStatement, PrepareError> {
         let cluster_state = self.get_cluster_state();
+        // Unqualified table names in the statement refer to the keyspace in use.
+        let keyspace = self.get_keyspace();
+
+        // A preloaded statement does not need to be prepared. If the nodes don't know it,
+        // it will be reprepared upon `DbError::Unprepared` on execution.
+        if let Some((prepared_response, is_lwt)) = self
+            .preloaded_prepared
+            .take(keyspace.as_deref().map(String::as_str), &statement.contents)
+        {
+            let mut prepared =
+                RawPreparedStatement::new(statement, prepared_response, is_lwt, None)
+                    .into_prepared_statement();
+            prepared.set_partitioner_name(
+                Self::extract_partitioner_name(&prepared, &cluster_state)
+                    .and_then(PartitionerName::from_str)
+                    .unwrap_or_default(),
+            );
+            prepared.set_prepared_in_keyspace(keyspace);
+            return Ok(prepared);
+        }
 
         // Start by attempting preparation on a single (random) connection to every node.
         {
             let mut connections_to_nodes = cluster_state.iter_working_connections_to_nodes()?;
             let on_all_nodes_result =
-                Self::prepare_on_all(statement, &cluster_state, &mut connections_to_nodes).await;
-            if let Ok(prepared) = on_all_nodes_result {
+                Self::prepare_on_all(statement, None, &cluster_state, &mut connections_to_nodes)
+                    .await;
+            if let Ok(mut prepared) = on_all_nodes_result {
                 // We succeeded in preparing the statement on at least one node. We're done.
                 // Other nodes could have failed to prepare the statement, but this will be handled
                 // as `DbError::Unprepared` upon execution, followed by a repreparation attempt.
+                prepared.set_prepared_in_keyspace(keyspace);
                 return Ok(prepared);
             }
         }
@@ -1579,7 +2473,11 @@ impl Session {
         {
             let mut connections_to_shards = cluster_state.iter_working_connections_to_shards()?;
 
-            Self::prepare_on_all(statement, &cluster_state, &mut connections_to_shards).await
+            let mut prepared =
+                Self::prepare_on_all(statement, None, &cluster_state, &mut connections_to_shards)
+                    .await?;
+            prepared.set_prepared_in_keyspace(keyspace);
+            Ok(prepared)
         }
     }
 
@@ -1599,12 +2497,17 @@ impl Session {
     // Describing issue: #1332.
     async fn prepare_on_all(
         statement: &Statement,
+        keyspace: Option<&VerifiedKeyspaceName>,
         cluster_state: &ClusterState,
         working_connections: &mut (dyn Iterator<Item = Arc<Connection>> + Send),
     ) -> Result<PreparedStatement, PrepareError> {
         // Find the first result that is Ok, or Err if all failed.
-        let preparations =
-            working_connections.map(|c| async move { c.prepare_raw(statement).await });
+        let preparations = working_connections.map(|c| async move {
+            match keyspace {
+                Some(keyspace) => c.prepare_raw_in_keyspace(statement, keyspace).await,
+                None => c.prepare_raw(statement).await,
+            }
+        });
         let raw_prepared_statements_results = join_all(preparations).await;
 
         let mut raw_prepared_statements_results_iter = raw_prepared_statements_results.into_iter();
@@ -1656,10 +2559,7 @@ impl Session {
         cluster_state
             .keyspaces
             .get(table_spec.ks_name())?
-            .tables
-            .get(table_spec.table_name())?
-            .partitioner
-            .as_deref()
+            .table_partitioner(table_spec.table_name())
     }
 
     /// Sends a prepared request to the database, optionally continuing from a saved point.
@@ -1678,6 +2578,36 @@ impl Session {
         serialized_values: &SerializedValues,
         page_size: Option<PageSize>,
         paging_state: PagingState,
+    ) -> Result<(QueryResult, PagingStateResponse), ExecutionError> {
+        let Some(history) = prepared.get_execution_history() else {
+            return self
+                .execute_without_history(prepared, serialized_values, page_size, paging_state)
+                .await;
+        };
+
+        let started_at = SystemTime::now();
+        let start = std::time::Instant::now();
+        let result = self
+            .execute_without_history(prepared, serialized_values, page_size, paging_state)
+            .await;
+        history.record(StatementExecution {
+            started_at,
+            latency: start.elapsed(),
+            coordinator: result
+                .as_ref()
+                .ok()
+                .map(|(result, _)| result.request_coordinator().clone()),
+            result: result.as_ref().map(|_| ()).map_err(Clone::clone),
+        });
+        result
+    }
+
+    async fn execute_without_history(
+        &self,
+        prepared: &PreparedStatement,
+        serialized_values: &SerializedValues,
+        page_size: Option<PageSize>,
+        paging_state: PagingState,
     ) -> Result<(QueryResult, PagingStateResponse), ExecutionError> {
         let paging_state_ref = &paging_state;
 
@@ -1694,7 +2624,7 @@ impl Session {
             .unwrap_or_else(|| self.get_default_execution_profile_handle())
             .access();
 
-        let table_spec = prepared.get_table_spec();
+        let table_spec = prepared.get_routing_table_spec();
 
         let statement_info = RoutingInfo {
             consistency: prepared
@@ -1725,9 +2655,10 @@ impl Session {
             span.record_replicas(replicas)
         }
 
-        let (run_request_result, coordinator): (
+        let (run_request_result, coordinator, attempt_info): (
             RunRequestResult<NonErrorQueryResponse>,
             Coordinator,
+            AttemptInfo,
         ) = self
             .run_request(
                 statement_info,
@@ -1735,11 +2666,8 @@ impl Session {
                 execution_profile,
                 |connection: Arc<Connection>,
                  consistency: Consistency,
-                 execution_profile: &ExecutionProfileInner| {
-                    let serial_consistency = prepared
-                        .config
-                        .serial_consistency
-                        .unwrap_or(execution_profile.serial_consistency);
+                 serial_consistency: Option<SerialConsistency>,
+                 _execution_profile: &ExecutionProfileInner| {
                     async move {
                         connection
                             .execute_raw_with_consistency(
@@ -1766,15 +2694,27 @@ impl Session {
                 ),
                 tracing_id: None,
                 warnings: Vec::new(),
+                unknown_result: None,
+                bytes_sent: 0,
+                bytes_received: 0,
+                response_flags: 0,
             },
             RunRequestResult::Completed(response) => response,
         };
 
+        let attempt_info = AttemptInfo {
+            bytes_sent: response.bytes_sent,
+            bytes_received: response.bytes_received,
+            ..attempt_info
+        };
         let (result, paging_state_response) =
             response.into_query_result_and_paging_state(coordinator)?;
         span.record_result_fields(&result);
 
-        Ok((result, paging_state_response))
+        Ok((
+            result.with_attempt_info(attempt_info),
+            paging_state_response,
+        ))
     }
 
     /// Does the same as [`Session::execute_iter`], but without generics.
@@ -1794,6 +2734,7 @@ impl Session {
         &self,
         prepared: PreparedStatement,
         values: SerializedValues,
+        fetched_first_page: Option<(QueryResult, PagingState)>,
     ) -> Result<QueryPager, PagerExecutionError> {
         let execution_profile = prepared
             .get_execution_profile_handle()
@@ -1810,6 +2751,10 @@ impl Session {
                 #[cfg(feature = "metrics")]
                 metrics: Arc::clone(&self.metrics),
                 location_preference: Arc::clone(&self.node_location_preference),
+                max_attempts: self.max_attempts_per_request,
+                metrics_sink: self.metrics_sink.clone(),
+                admission_queue: self.admission_queue.clone(),
+                fetched_first_page,
             },
         )
         .await
@@ -1832,7 +2777,7 @@ impl Session {
         prepared: PreparedStatement,
         values: SerializedValues,
     ) -> Result<QueryPager, PagerExecutionError> {
-        self.execute_iter_nongeneric(prepared, values).await
+        self.execute_iter_nongeneric(prepared, values, None).await
     }
 
     /// Prepares all statements within the batch and returns a new batch where every
@@ -1886,6 +2831,9 @@ impl Session {
     /// Sends `USE <keyspace_name>` request on all connections\
     /// This allows to write `SELECT * FROM table` instead of `SELECT * FROM keyspace.table`\
     ///
+    /// Connections opened later, e.g. when reconnecting to a node, send `USE <keyspace_name>`
+    /// before they serve any request, so the keyspace stays in use on all of them.
+    ///
     /// Note that even failed `use_keyspace` can change currently used keyspace - the request is sent on all connections and
     /// can overwrite previously used keyspace.
     ///
@@ -1957,6 +2905,47 @@ impl Session {
         Arc::clone(&self.metrics)
     }
 
+    /// Returns the current limit of requests executed concurrently by the session,
+    /// or `None` if the number of concurrent requests is not limited.
+    ///
+    /// The limit changes over time only if
+    /// [`SessionBuilder::adaptive_concurrency`](crate::client::session_builder::SessionBuilder::adaptive_concurrency)
+    /// is used.
+    pub fn concurrency_limit(&self) -> Option<usize> {
+        self.admission_queue
+            .as_ref()
+            .map(|admission_queue| admission_queue.limit())
+    }
+
+    /// Returns the most recent warnings returned by the database to any request
+    /// of the session, from the oldest to the most recent one.
+    ///
+    /// Warnings are only retained if enabled with
+    /// [`SessionBuilder::recent_warnings_capacity`](crate::client::session_builder::SessionBuilder::recent_warnings_capacity);
+    /// otherwise the returned list is always empty.
+    pub fn recent_warnings(&self) -> Vec<ServerWarning> {
+        self.warnings_log
+            .as_ref()
+            .map(|warnings_log| warnings_log.warnings())
+            .unwrap_or_default()
+    }
+
+    /// Acquires a handle for sending statements in submission order
+    /// on a connection to the given node, pinned for the lifetime of the handle.
+    ///
+    /// See [`OrderedPipeline`] for details.
+    pub fn ordered_pipeline(
+        &self,
+        node: NodeRef<'_>,
+    ) -> Result<OrderedPipeline, ConnectionPoolError> {
+        let connection = node.get_random_connection()?;
+        Ok(OrderedPipeline::new(
+            connection,
+            Arc::clone(node),
+            self.default_execution_profile_handle.clone(),
+        ))
+    }
+
     /// Access cluster state visible by the driver.
     ///
     /// Driver collects various information about network topology or schema.
@@ -1965,6 +2954,16 @@ impl Session {
         self.cluster.get_state()
     }
 
+    /// Returns a stream of schema change events pushed by the cluster.
+    ///
+    /// Only events received after this call are yielded. The stream is bounded;
+    /// if it is not polled fast enough, events are dropped and counted in
+    /// [`SchemaChangeStream::dropped_events`].
+    pub fn schema_change_stream(&self) -> SchemaChangeStream {
+        self.cluster
+            .subscribe_to_schema_changes(SCHEMA_CHANGE_STREAM_CAPACITY)
+    }
+
     /// Get [`TracingInfo`] of a traced query performed earlier
     ///
     /// See [the book](https://rust-driver.docs.scylladb.com/stable/tracing/tracing.html)
@@ -1984,6 +2983,154 @@ impl Session {
         Err(TracingError::EmptyResults)
     }
 
+    /// Lists this session's connections as seen by the cluster in `system.clients`.
+    ///
+    /// `system.clients` is node-local, so it is queried on every node which has
+    /// a working connection pool. Only the rows whose client address matches
+    /// the local address of one of the session's pooled connections are returned;
+    /// the control connection is not included.
+    ///
+    /// This is meant for debugging, e.g. to cross-check the driver's connection
+    /// pools against what the server sees. Note that if there is some address
+    /// translation (e.g. NAT) between the driver and the cluster, the server sees
+    /// different addresses and no rows match.
+    pub async fn server_side_connections(
+        &self,
+    ) -> Result<Vec<ServerClientInfo>, ServerClientsError> {
+        let cluster_state = self.get_cluster_state();
+        let nodes_with_connections = cluster_state
+            .get_nodes_info()
+            .iter()
+            .filter_map(|node| {
+                let connections = node.get_working_connections().ok()?;
+                Some((node, connections))
+            })
+            .collect::<Vec<_>>();
+
+        let local_addresses: HashSet<SocketAddr> = nodes_with_connections
+            .iter()
+            .flat_map(|(_, connections)| connections.iter().map(|c| c.get_local_address()))
+            .collect();
+
+        let per_node_clients = try_join_all(nodes_with_connections.iter().map(|(node, _)| {
+            let mut stmt = Statement::new(crate::observability::clients::SYSTEM_CLIENTS_QUERY_STR);
+            stmt.set_is_idempotent(true);
+            stmt.set_load_balancing_policy(Some(SingleTargetLoadBalancingPolicy::new(
+                NodeIdentifier::Node(Arc::clone(node)),
+                None,
+            )));
+            async move {
+                let rows_result = self
+                    .query_unpaged(stmt, ())
+                    .await?
+                    .into_rows_result()
+                    .map_err(ServerClientsError::IntoRowsResultError)?;
+                rows_result
+                    .rows::<ServerClientInfo>()
+                    .map_err(|err| match err {
+                        RowsError::TypeCheckFailed(err) => {
+                            ServerClientsError::InvalidColumnType(err)
+                        }
+                    })?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(ServerClientsError::DeserializationFailed)
+            }
+        }))
+        .await?;
+
+        Ok(per_node_clients
+            .into_iter()
+            .flatten()
+            .filter(|client| {
+                client
+                    .client_address()
+                    .is_some_and(|addr| local_addresses.contains(&addr))
+            })
+            .collect())
+    }
+
+    /// Measures the skew between the local clock and the clock of a coordinator.
+    ///
+    /// The coordinator's current time, which it also uses to assign timestamps
+    /// to writes without a client-side timestamp, is compared to the local time
+    /// in the middle of the request. The coordinator is chosen by the default
+    /// execution profile's load balancing policy.
+    ///
+    /// This is meant for debugging issues with write timestamps.
+    pub async fn measure_clock_skew(&self) -> Result<ClockSkew, ClockSkewError> {
+        let mut stmt = Statement::new(COORDINATOR_TIME_QUERY_STR);
+        stmt.set_is_idempotent(true);
+
+        let sent = SystemTime::now();
+        let result = self.query_unpaged(stmt, ()).await?;
+        let received = SystemTime::now();
+
+        let (coordinator_millis,) = result.into_rows_result()?.single_row::<(i64,)>()?;
+        Ok(ClockSkew::from_measurement(
+            sent,
+            received,
+            coordinator_millis,
+        ))
+    }
+
+    /// Returns statistics of all working connections in the session's connection pools.
+    ///
+    /// The control connection is not included. RTT is only populated if
+    /// [`SessionBuilder::rtt_probe_interval`](crate::client::session_builder::SessionBuilder::rtt_probe_interval)
+    /// was set.
+    pub fn connection_stats(&self) -> Vec<ConnectionStats> {
+        self.get_cluster_state()
+            .get_nodes_info()
+            .iter()
+            .filter_map(|node| node.get_working_connections().ok())
+            .flatten()
+            .map(|connection| connection.get_stats())
+            .collect()
+    }
+
+    /// Returns the fraction of desired connections that are established in the session's
+    /// connection pools, from 0.0 to 1.0. Can be used to track the progress of filling
+    /// the pools, e.g. while the session warms up after creation.
+    ///
+    /// A pool of connections per shard is not counted as filled before its first connection
+    /// tells how many shards the node has; until then, the node is assumed to have as many
+    /// shards as the largest node whose shard count is known.
+    ///
+    /// Nodes disabled by the host filter are not taken into account. If the number of
+    /// connections is limited by
+    /// [`SessionBuilder::max_total_connections`](crate::client::session_builder::SessionBuilder::max_total_connections),
+    /// the progress may never reach 1.0.
+    pub fn pool_fill_progress(&self) -> f32 {
+        let fills = self
+            .get_cluster_state()
+            .get_nodes_info()
+            .iter()
+            .filter_map(|node| node.pool_fill())
+            .collect::<Vec<_>>();
+        // Pools whose shard count is not known yet are assumed to have as many shards
+        // as the largest known pool.
+        let assumed_shard_count = fills
+            .iter()
+            .filter_map(|fill| fill.shard_count)
+            .max()
+            .unwrap_or(1);
+        let (established, desired) = fills.iter().fold((0, 0), |(established, desired), fill| {
+            match fill.shard_count {
+                Some(shard_count) => (
+                    established + fill.established,
+                    desired + fill.target * shard_count,
+                ),
+                // The pool is not filled until it is known how many connections it needs.
+                None => (established, desired + fill.target * assumed_shard_count),
+            }
+        });
+        if desired == 0 {
+            // There are no pools to fill.
+            return 1.0;
+        }
+        established as f32 / desired as f32
+    }
+
     /// Gets the name of the keyspace that is currently set, or `None` if no
     /// keyspace was set.
     ///
@@ -1999,6 +3146,12 @@ impl Session {
         self.keyspace_name.load_full()
     }
 
+    /// Statements to prepare on newly opened connections,
+    /// if [`SessionConfig::reprepare_on_new_connections`] is enabled.
+    pub(crate) fn repreparation_set(&self) -> Option<&RepreparationSet> {
+        self.repreparation_set.as_deref()
+    }
+
     // Tries getting the tracing info
     // If the queries return 0 rows then returns None - the information didn't reach this node yet
     // If there is some other error returns this error
@@ -2069,17 +3222,33 @@ impl Session {
     /// On success, this request's result is returned.
     // I tried to make this closures take a reference instead of an Arc but failed
     // maybe once async closures get stabilized this can be fixed
-    async fn run_request<'a, QueryFut>(
+    async fn run_request<'a, ResT, QueryFut>(
         &'a self,
         statement_info: RoutingInfo<'a>,
         statement_config: &'a StatementConfig,
         execution_profile: Arc<ExecutionProfileInner>,
-        run_request_once: impl Fn(Arc<Connection>, Consistency, &ExecutionProfileInner) -> QueryFut,
+        run_request_once: impl Fn(
+            Arc<Connection>,
+            Consistency,
+            Option<SerialConsistency>,
+            &ExecutionProfileInner,
+        ) -> QueryFut,
         request_span: &'a RequestSpan,
-    ) -> Result<(RunRequestResult<NonErrorQueryResponse>, Coordinator), ExecutionError>
+    ) -> Result<(RunRequestResult<ResT>, Coordinator, AttemptInfo), ExecutionError>
     where
-        QueryFut: Future<Output = Result<NonErrorQueryResponse, RequestAttemptError>>,
+        ResT: AttemptResponse,
+        QueryFut: Future<Output = Result<ResT, RequestAttemptError>>,
     {
+        // Without a token, the load balancing policy can't route to the replicas.
+        let statement_info = if statement_config.disable_token_aware {
+            RoutingInfo {
+                token: None,
+                ..statement_info
+            }
+        } else {
+            statement_info
+        };
+
         let history_listener_and_id: Option<(&'a dyn HistoryListener, history::RequestId)> =
             statement_config
                 .history_listener
@@ -2091,10 +3260,29 @@ impl Session {
             .as_deref()
             .unwrap_or(execution_profile.load_balancing_policy.as_ref());
 
+        // Shared between all speculative fibers, so that the cap applies to the request as a whole.
+        let attempts_started = AtomicU32::new(0);
+
         let runner = async {
+            let _admission_permit = match &self.admission_queue {
+                Some(admission_queue) => {
+                    Some(admission_queue.acquire(statement_config.priority).await)
+                }
+                None => None,
+            };
+
             let cluster_state = self.cluster.get_state();
-            let request_plan =
-                load_balancing::Plan::new(load_balancer, &statement_info, &cluster_state);
+            let preferred_replica = statement_config
+                .preferred_replica_index
+                .and_then(|index| cluster_state.replica_at(&statement_info, index));
+            let request_plan = load_balancing::with_preferred_first(
+                load_balancing::Plan::new(load_balancer, &statement_info, &cluster_state),
+                preferred_replica,
+            )
+            .filter(|(node, _)| !statement_config.is_node_excluded(node));
+            let required_replicas = statement_config
+                .require_replica_coordinator
+                .then(|| cluster_state.replica_host_ids(&statement_info));
 
             // If a speculative execution policy is used to run request, request_plan has to be shared
             // between different async functions. This struct helps to wrap request_plan in mutex so it
@@ -2117,10 +3305,8 @@ impl Session {
                 }
             }
 
-            let retry_policy = statement_config
-                .retry_policy
-                .as_deref()
-                .unwrap_or(&*execution_profile.retry_policy);
+            let new_retry_session =
+                || execution_profile.new_retry_session(statement_config.retry_policy.as_ref());
 
             let speculative_policy = execution_profile.speculative_execution_policy.as_ref();
 
@@ -2153,20 +3339,41 @@ impl Session {
                             request_span.inc_speculative_executions();
                         }
 
-                        self.run_request_speculative_fiber(
+                        let fiber = self.run_request_speculative_fiber(
                             &shared_request_plan,
                             &run_request_once,
                             &execution_profile,
                             ExecuteRequestContext {
                                 is_idempotent: statement_config.is_idempotent,
                                 consistency_set_on_statement: statement_config.consistency,
-                                retry_session: retry_policy.new_session(),
+                                serial_consistency_set_on_statement: statement_config
+                                    .serial_consistency,
+                                serial_consistency_fallback: &statement_config
+                                    .serial_consistency_fallback,
+                                ignore_already_exists: statement_config.ignore_already_exists,
+                                retry_session: new_retry_session(),
                                 history_data,
                                 load_balancing_policy: load_balancer,
                                 query_info: &statement_info,
                                 request_span,
+                                max_attempts: self.max_attempts_per_request,
+                                attempts_started: &attempts_started,
+                                on_attempt: statement_config.on_attempt.as_ref(),
+                                required_replicas: required_replicas.as_deref(),
+                                force_fresh_connection: statement_config.force_fresh_connection,
                             },
-                        )
+                        );
+                        async move {
+                            fiber.await.map(|result| {
+                                result.map(|(result, coordinator, attempt_info)| {
+                                    let attempt_info = AttemptInfo {
+                                        won_by_speculative: is_speculative,
+                                        ..attempt_info
+                                    };
+                                    ((result, attempt_info), coordinator)
+                                })
+                            })
+                        }
                     };
 
                     let context = speculative_execution::Context {
@@ -2197,15 +3404,28 @@ impl Session {
                         ExecuteRequestContext {
                             is_idempotent: statement_config.is_idempotent,
                             consistency_set_on_statement: statement_config.consistency,
-                            retry_session: retry_policy.new_session(),
+                            serial_consistency_set_on_statement: statement_config
+                                .serial_consistency,
+                            serial_consistency_fallback: &statement_config
+                                .serial_consistency_fallback,
+                            ignore_already_exists: statement_config.ignore_already_exists,
+                            retry_session: new_retry_session(),
                             history_data,
                             load_balancing_policy: load_balancer,
                             query_info: &statement_info,
                             request_span,
+                            max_attempts: self.max_attempts_per_request,
+                            attempts_started: &attempts_started,
+                            on_attempt: statement_config.on_attempt.as_ref(),
+                            required_replicas: required_replicas.as_deref(),
+                            force_fresh_connection: statement_config.force_fresh_connection,
                         },
                     )
                     .await
                     .unwrap_or(Err(RequestError::EmptyPlan))
+                    .map(|(result, coordinator, attempt_info)| {
+                        ((result, attempt_info), coordinator)
+                    })
                 }
             }
         };
@@ -2239,40 +3459,87 @@ impl Session {
         }
 
         // Automatically handle meaningful responses.
-        if let Ok((RunRequestResult::Completed(ref response), ref coordinator)) = result {
+        if let Ok(((RunRequestResult::Completed(ref response), _), ref coordinator)) = result
+            && let Some(response) = response.as_parsed()
+        {
             self.handle_set_keyspace_response(response).await?;
             self.handle_auto_await_schema_agreement(response, coordinator.node().host_id)
                 .await?;
         }
 
-        result.map_err(RequestError::into_execution_error)
+        result
+            .map(|((result, attempt_info), coordinator)| {
+                let attempt_info = AttemptInfo {
+                    coordinator_was_local: is_local_datacenter(
+                        &self.node_location_preference,
+                        coordinator.node().datacenter.as_deref(),
+                    ),
+                    coordinator_host_id: Some(coordinator.node().host_id),
+                    ..attempt_info
+                };
+                (result, coordinator, attempt_info)
+            })
+            .map_err(RequestError::into_execution_error)
     }
 
     /// Executes the closure `run_request_once`, provided the load balancing plan and some information
     /// about the request, including retry session.
     /// If request fails, retry session is used to perform retries.
     ///
-    /// Returns None, if provided plan is empty.
-    async fn run_request_speculative_fiber<'a, QueryFut>(
+    /// Returns None, if provided plan is empty. Otherwise, the result is returned
+    /// together with the information about the last attempt, including its consistency,
+    /// which may have been changed by the retry policy.
+    async fn run_request_speculative_fiber<'a, ResT, QueryFut>(
         &'a self,
         request_plan: impl Iterator<Item = (NodeRef<'a>, Shard)>,
-        run_request_once: impl Fn(Arc<Connection>, Consistency, &ExecutionProfileInner) -> QueryFut,
+        run_request_once: impl Fn(
+            Arc<Connection>,
+            Consistency,
+            Option<SerialConsistency>,
+            &ExecutionProfileInner,
+        ) -> QueryFut,
         execution_profile: &ExecutionProfileInner,
         mut context: ExecuteRequestContext<'a>,
-    ) -> Option<Result<(RunRequestResult<NonErrorQueryResponse>, Coordinator), RequestError>>
+    ) -> Option<Result<(RunRequestResult<ResT>, Coordinator, AttemptInfo), RequestError>>
     where
-        QueryFut: Future<Output = Result<NonErrorQueryResponse, RequestAttemptError>>,
+        QueryFut: Future<Output = Result<ResT, RequestAttemptError>>,
     {
         let mut last_error: Option<RequestError> = None;
         let mut current_consistency: Consistency = context
             .consistency_set_on_statement
             .unwrap_or(execution_profile.consistency);
+        let mut current_serial_consistency: Option<SerialConsistency> = context
+            .serial_consistency_set_on_statement
+            .unwrap_or(execution_profile.serial_consistency);
 
         'nodes_in_plan: for (node, shard) in request_plan {
             let span = trace_span!("Executing request", node = %node.address, shard = %shard);
+            if let Some(replicas) = context.required_replicas
+                && !replicas.contains(&node.host_id)
+            {
+                trace!(parent: &span, "Coordinator is not a replica, skipping it");
+                // Reported only if no replica in the plan was tried.
+                last_error.get_or_insert(RequestError::CoordinatorNotReplica(node.address));
+                continue 'nodes_in_plan;
+            }
             'same_node_retries: loop {
                 trace!(parent: &span, "Execution started");
-                let connection = match node.connection_for_shard(shard).await {
+                let connection_wait_start = std::time::Instant::now();
+                // A fresh connection is closed when `fresh_connection` is dropped,
+                // after this attempt completes.
+                let connection_result = if context.force_fresh_connection {
+                    node.open_fresh_connection().await.map(|fresh_connection| {
+                        (
+                            Arc::clone(fresh_connection.connection()),
+                            Some(fresh_connection),
+                        )
+                    })
+                } else {
+                    node.connection_for_shard(shard)
+                        .await
+                        .map(|connection| (connection, None))
+                };
+                let (connection, fresh_connection) = match connection_result {
                     Ok(connection) => connection,
                     Err(e) => {
                         trace!(
@@ -2280,13 +3547,26 @@ impl Session {
                             error = %e,
                             "Choosing connection failed"
                         );
+                        context.log_connection_pool_error(node.address.into_inner(), &e);
                         last_error = Some(e.into());
                         // Broken connection doesn't count as a failed request, don't log in metrics
                         continue 'nodes_in_plan;
                     }
                 };
+                let connection_wait_time = connection_wait_start.elapsed();
                 context.request_span.record_shard_id(&connection);
 
+                let Some(attempt) = context.try_start_attempt() else {
+                    trace!(
+                        parent: &span,
+                        "Maximum number of attempts per request reached"
+                    );
+                    break 'nodes_in_plan;
+                };
+                if let Some(on_attempt) = context.on_attempt {
+                    on_attempt.call(attempt, node);
+                }
+
                 #[cfg(feature = "metrics")]
                 self.metrics.inc_total_nonpaged_queries();
                 let request_start = std::time::Instant::now();
@@ -2297,17 +3577,51 @@ impl Session {
                     connection = %connect_address,
                     "Sending"
                 );
-                let coordinator =
-                    Coordinator::new(node, node.sharder().is_some().then_some(shard), &connection);
+                // A fresh connection is not shard-aware, so it may be connected to another shard.
+                let coordinator_shard = match fresh_connection {
+                    Some(_) => connection
+                        .get_shard_info()
+                        .as_ref()
+                        .map(|shard_info| Shard::from(shard_info.shard)),
+                    None => node.sharder().is_some().then_some(shard),
+                };
+                let coordinator = Coordinator::new(node, coordinator_shard, &connection);
+                // The pool falls back to another shard if the target one has no working connection.
+                let shard_aware_routing_used = context.query_info.token.is_some()
+                    && connection
+                        .get_shard_info()
+                        .as_ref()
+                        .is_some_and(|shard_info| Shard::from(shard_info.shard) == shard);
+                let attempt_info = AttemptInfo {
+                    winning_attempt: attempt,
+                    effective_consistency: Some(current_consistency),
+                    connection_wait_time,
+                    shard_aware_routing_used,
+                    ..AttemptInfo::default()
+                };
 
                 let attempt_id: Option<history::AttemptId> =
                     context.log_attempt_start(connect_address);
-                let request_result: Result<NonErrorQueryResponse, RequestAttemptError> =
-                    run_request_once(connection, current_consistency, execution_profile)
-                        .instrument(span.clone())
-                        .await;
+                let request_result: Result<ResT, RequestAttemptError> = run_request_once(
+                    connection,
+                    current_consistency,
+                    current_serial_consistency,
+                    execution_profile,
+                )
+                .instrument(span.clone())
+                .await;
 
                 let elapsed = request_start.elapsed();
+                if let Some(metrics_sink) = &self.metrics_sink {
+                    metrics_sink.on_request_complete(&RequestCompleteEvent {
+                        node_address: node.address,
+                        latency: elapsed,
+                        succeeded: request_result.is_ok(),
+                    });
+                }
+                if let Some(admission_queue) = &self.admission_queue {
+                    admission_queue.record_attempt(&request_result);
+                }
                 let request_error: RequestAttemptError = match request_result {
                     Ok(response) => {
                         trace!(parent: &span, "Request succeeded");
@@ -2319,7 +3633,11 @@ impl Session {
                             elapsed,
                             node,
                         );
-                        return Some(Ok((RunRequestResult::Completed(response), coordinator)));
+                        return Some(Ok((
+                            RunRequestResult::Completed(response),
+                            coordinator,
+                            attempt_info,
+                        )));
                     }
                     Err(e) => {
                         trace!(
@@ -2339,6 +3657,47 @@ impl Session {
                     }
                 };
 
+                // Serial consistency fallback takes precedence over the retry policy.
+                if let Some(fallback) = context
+                    .serial_consistency_fallback_for(&request_error, current_serial_consistency)
+                {
+                    let retry_decision = RetryDecision::RetrySameTarget(None);
+                    trace!(
+                        parent: &span,
+                        serial_consistency = ?fallback,
+                        "Falling back to another serial consistency"
+                    );
+                    context.log_attempt_error(&attempt_id, &request_error, &retry_decision);
+
+                    last_error = Some(request_error.into());
+
+                    #[cfg(feature = "metrics")]
+                    self.metrics.inc_retries_num();
+                    self.report_retry(node.address, &retry_decision);
+                    current_serial_consistency = Some(fallback);
+                    continue 'same_node_retries;
+                }
+
+                // A schema change which has already been applied is fine, if so requested.
+                if context.ignore_already_exists
+                    && matches!(
+                        request_error,
+                        RequestAttemptError::DbError(DbError::AlreadyExists { .. }, _)
+                    )
+                {
+                    trace!(parent: &span, "Ignoring AlreadyExists error");
+                    context.log_attempt_error(
+                        &attempt_id,
+                        &request_error,
+                        &RetryDecision::IgnoreWriteError,
+                    );
+                    return Some(Ok((
+                        RunRequestResult::IgnoredWriteError,
+                        coordinator,
+                        attempt_info,
+                    )));
+                }
+
                 // Use retry policy to decide what to do next
                 let request_info = RequestInfo {
                     error: &request_error,
@@ -2362,19 +3721,25 @@ impl Session {
                     RetryDecision::RetrySameTarget(new_cl) => {
                         #[cfg(feature = "metrics")]
                         self.metrics.inc_retries_num();
+                        self.report_retry(node.address, &retry_decision);
                         current_consistency = new_cl.unwrap_or(current_consistency);
                         continue 'same_node_retries;
                     }
                     RetryDecision::RetryNextTarget(new_cl) => {
                         #[cfg(feature = "metrics")]
                         self.metrics.inc_retries_num();
+                        self.report_retry(node.address, &retry_decision);
                         current_consistency = new_cl.unwrap_or(current_consistency);
                         continue 'nodes_in_plan;
                     }
                     RetryDecision::DontRetry => break 'nodes_in_plan,
 
                     RetryDecision::IgnoreWriteError => {
-                        return Some(Ok((RunRequestResult::IgnoredWriteError, coordinator)));
+                        return Some(Ok((
+                            RunRequestResult::IgnoredWriteError,
+                            coordinator,
+                            attempt_info,
+                        )));
                     }
                 };
             }
@@ -2383,6 +3748,15 @@ impl Session {
         last_error.map(Result::Err)
     }
 
+    fn report_retry(&self, node_address: NodeAddr, decision: &RetryDecision) {
+        if let Some(metrics_sink) = &self.metrics_sink {
+            metrics_sink.on_retry(&RetryEvent {
+                node_address,
+                decision: decision.clone(),
+            });
+        }
+    }
+
     /// Awaits schema agreement among all reachable nodes.
     ///
     /// Issues an agreement check each `Session::schema_agreement_interval`.
@@ -2690,14 +4064,63 @@ impl Session {
     }
 }
 
+/// Fails with the limit if the result of an unpaged request has more rows than allowed
+/// by the statement.
+///
+/// A result which doesn't exceed the limit, but whose page was ended early by the server
+/// (e.g. because of its size in bytes), is not reported here. It is incomplete, so the caller
+/// fails with [`RequestAttemptError::NonfinishedPagingState`] instead.
+fn check_unpaged_row_limit(
+    config: &StatementConfig,
+    result: &QueryResult,
+) -> Result<(), NonZeroU32> {
+    let Some(max_rows) = config.max_unpaged_rows else {
+        return Ok(());
+    };
+    let rows_num = result
+        .deserialized_metadata_and_rows()
+        .map_or(0, |rows| rows.rows_count());
+    if rows_num > max_rows.get() as usize {
+        return Err(max_rows);
+    }
+    Ok(())
+}
+
+/// Maps the error returned by the server when it no longer accepts the paging state
+/// that a request was resumed from to [`ExecutionError::PagingStateExpired`].
+fn map_expired_paging_state(paging_state: &PagingState, error: ExecutionError) -> ExecutionError {
+    if paging_state.as_bytes_slice().is_none() {
+        // The request was not resumed.
+        return error;
+    }
+    match error {
+        ExecutionError::LastAttemptError(RequestAttemptError::DbError(
+            DbError::ProtocolError | DbError::Invalid,
+            reason,
+        )) if reason.to_lowercase().contains("paging state") => {
+            ExecutionError::PagingStateExpired(reason)
+        }
+        error => error,
+    }
+}
+
 struct ExecuteRequestContext<'a> {
     is_idempotent: bool,
     consistency_set_on_statement: Option<Consistency>,
+    serial_consistency_set_on_statement: Option<Option<SerialConsistency>>,
+    serial_consistency_fallback: &'a [SerialConsistency],
+    ignore_already_exists: bool,
     retry_session: Box<dyn RetrySession>,
     history_data: Option<HistoryData<'a>>,
     load_balancing_policy: &'a dyn load_balancing::LoadBalancingPolicy,
     query_info: &'a load_balancing::RoutingInfo<'a>,
     request_span: &'a RequestSpan,
+    max_attempts: Option<NonZeroU32>,
+    attempts_started: &'a AtomicU32,
+    on_attempt: Option<&'a AttemptCallback>,
+    // Host ids of the nodes allowed to coordinate the request, if restricted to replicas.
+    required_replicas: Option<&'a [Uuid]>,
+    force_fresh_connection: bool,
 }
 
 struct HistoryData<'a> {
@@ -2707,6 +4130,43 @@ struct HistoryData<'a> {
 }
 
 impl ExecuteRequestContext<'_> {
+    /// Accounts for a new attempt and returns its 1-based number. Returns `None` if the attempt
+    /// must not be started, because the maximum number of attempts per request was already reached.
+    fn try_start_attempt(&self) -> Option<u32> {
+        let started = self.attempts_started.fetch_add(1, Ordering::Relaxed);
+        self.max_attempts
+            .is_none_or(|max| started < max.get())
+            .then_some(started + 1)
+    }
+
+    /// Returns the serial consistency to retry with if the serial phase of the request
+    /// was unavailable at `serial_consistency`, and the statement has a fallback for it.
+    /// Each fallback entry is used at most once, so the fallback eventually runs out.
+    fn serial_consistency_fallback_for(
+        &mut self,
+        error: &RequestAttemptError,
+        serial_consistency: Option<SerialConsistency>,
+    ) -> Option<SerialConsistency> {
+        if !matches!(
+            error,
+            RequestAttemptError::DbError(
+                DbError::Unavailable {
+                    consistency: Consistency::Serial | Consistency::LocalSerial,
+                    ..
+                },
+                _
+            )
+        ) {
+            return None;
+        }
+        let fallback = self.serial_consistency_fallback;
+        let position = fallback
+            .iter()
+            .position(|sc| Some(*sc) != serial_consistency)?;
+        self.serial_consistency_fallback = &fallback[position + 1..];
+        Some(fallback[position])
+    }
+
     fn log_attempt_start(&self, node_addr: SocketAddr) -> Option<history::AttemptId> {
         self.history_data.as_ref().map(|hd| {
             hd.listener
@@ -2748,6 +4208,17 @@ impl ExecuteRequestContext<'_> {
             .listener
             .log_attempt_error(*attempt_id, error, retry_decision);
     }
+
+    fn log_connection_pool_error(&self, node_addr: SocketAddr, error: &ConnectionPoolError) {
+        if let Some(hd) = &self.history_data {
+            hd.listener.log_connection_pool_error(
+                hd.request_id,
+                hd.speculative_id,
+                node_addr,
+                error,
+            );
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -2768,4 +4239,104 @@ impl Session {
         self.await_schema_agreement_with_required_node(required_node)
             .await
     }
+
+    /// Executes a prepared statement with values that were already serialized, without paging.
+    ///
+    /// This is the counterpart of [`Session::execute_unpaged`] for interop code that
+    /// holds the bound values as [`SerializedValues`]. The values are only borrowed
+    /// for the duration of the call, so they can be reused.
+    ///
+    /// The values are not type checked against the statement: if they don't match
+    /// the prepared statement, the driver will misbehave (potentially leading to data
+    /// corruption). Therefore, this function must not be exposed to end users of this Rust driver.
+    pub async fn execute_preserialized(
+        &self,
+        prepared: &PreparedStatement,
+        values: &SerializedValues,
+    ) -> Result<QueryResult, ExecutionError> {
+        self.execute_unpaged_nongeneric(prepared, values).await
+    }
+
+    /// Executes a prepared statement with values that were already serialized, without paging,
+    /// and returns the body of the response as received from the database.
+    ///
+    /// The body is not decompressed, so it can be forwarded without re-compressing it;
+    /// [`RawResponse::compression`] tells which algorithm it is compressed with.
+    /// The request is subject to load balancing, retries and speculative execution as usual,
+    /// but the driver doesn't inspect the response, so `USE <keyspace>` statements don't
+    /// change the session's keyspace, schema agreement is not awaited automatically after
+    /// schema changes, and tablet information carried by the response is not taken into account.
+    ///
+    /// The values are not type checked against the statement, so the same caveats as
+    /// for [`Session::execute_preserialized`] apply.
+    pub async fn execute_unpaged_raw_response(
+        &self,
+        prepared: &PreparedStatement,
+        values: &SerializedValues,
+    ) -> Result<RawResponse, ExecutionError> {
+        let (partition_key, token) = prepared
+            .extract_partition_key_and_calculate_token(prepared.get_partitioner_name(), values)
+            .map_err(PartitionKeyError::into_execution_error)?
+            .unzip();
+
+        let execution_profile = prepared
+            .get_execution_profile_handle()
+            .unwrap_or_else(|| self.get_default_execution_profile_handle())
+            .access();
+
+        let statement_info = RoutingInfo {
+            consistency: prepared
+                .config
+                .consistency
+                .unwrap_or(execution_profile.consistency),
+            serial_consistency: prepared
+                .config
+                .serial_consistency
+                .unwrap_or(execution_profile.serial_consistency),
+            token,
+            table: prepared.get_routing_table_spec(),
+            is_confirmed_lwt: prepared.is_confirmed_lwt(),
+            node_location_preference: &self.node_location_preference,
+        };
+
+        let span = RequestSpan::new_prepared(
+            partition_key.as_ref().map(|pk| pk.iter()),
+            token,
+            values.buffer_size(),
+        );
+
+        let (run_request_result, _coordinator, _attempt_info): (
+            RunRequestResult<RawResponse>,
+            Coordinator,
+            AttemptInfo,
+        ) = self
+            .run_request(
+                statement_info,
+                &prepared.config,
+                execution_profile,
+                |connection: Arc<Connection>,
+                 consistency: Consistency,
+                 serial_consistency: Option<SerialConsistency>,
+                 _execution_profile: &ExecutionProfileInner| {
+                    async move {
+                        connection
+                            .execute_with_raw_response(
+                                prepared,
+                                values,
+                                consistency,
+                                serial_consistency,
+                            )
+                            .await
+                    }
+                },
+                &span,
+            )
+            .instrument(span.span().clone())
+            .await?;
+
+        Ok(match run_request_result {
+            RunRequestResult::IgnoredWriteError => RawResponse::void(),
+            RunRequestResult::Completed(response) => response,
+        })
+    }
 }
@@ -2,10 +2,14 @@
 
 use super::execution_profile::ExecutionProfileHandle;
 use super::session::{Session, SessionConfig};
-use super::{Compression, PoolSize, SelfIdentity, WriteCoalescingDelay};
+use super::{
+    AdaptiveConcurrency, Compression, ConnectionSelection, ErrorRateCircuitBreaker,
+    PoolAutoscaling, PoolSize, SelfIdentity, WriteCoalescingDelay,
+};
 use crate::authentication::{AuthenticatorProvider, PlainTextAuthenticator};
 use crate::client::session::TlsContext;
 use crate::errors::NewSessionError;
+use crate::observability::metrics_sink::MetricsSink;
 use crate::policies::address_translator::AddressTranslator;
 use crate::policies::host_filter::HostFilter;
 use crate::policies::timestamp_generator::TimestampGenerator;
@@ -14,7 +18,8 @@ use crate::statement::Consistency;
 use std::borrow::Borrow;
 use std::marker::PhantomData;
 use std::net::{IpAddr, SocketAddr};
-use std::num::NonZeroU32;
+use std::num::{NonZeroU32, NonZeroUsize};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::warn;
@@ -541,6 +546,33 @@ impl<K: SessionBuilderKind> GenericSessionBuilder<K> {
         self
     }
 
+    /// Send request frames whose body is shorter than `bytes` uncompressed,
+    /// even if compression is enabled, because compressing tiny frames wastes CPU.
+    /// The default is 0, which means that all request frames are compressed.
+    ///
+    /// This only applies to outgoing frames: whether response frames are compressed
+    /// is decided by the server.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # use scylla::client::Compression;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .compression(Some(Compression::Lz4))
+    ///     .compression_threshold(1024)
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn compression_threshold(mut self, bytes: usize) -> Self {
+        self.config.compression_threshold = bytes;
+        self
+    }
+
     /// Sets the preferred datacenter for this session.
     ///
     /// Nodes in the given datacenter will be treated as "local" and preferred
@@ -892,6 +924,194 @@ impl<K: SessionBuilderKind> GenericSessionBuilder<K> {
         self
     }
 
+    /// Makes per-node connection pools scale dynamically with load.
+    /// The default is `None`, which keeps pool sizes fixed.
+    ///
+    /// Each pool starts with the minimum number of connections set in [`PoolAutoscaling`].
+    /// It periodically computes the average number of in-flight requests per connection,
+    /// opens one more connection when it reaches the scale-up threshold, and closes the least
+    /// loaded surplus connection when it drops to the scale-down threshold. The kind of
+    /// [`pool_size`](Self::pool_size) decides whether the bounds apply per host or per shard;
+    /// its number of connections is ignored.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::num::NonZeroUsize;
+    /// use scylla::client::{PoolAutoscaling, PoolSize};
+    ///
+    /// // This session will keep between 1 and 4 connections to each shard,
+    /// // growing when connections have 500 in-flight requests on average.
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .pool_size(PoolSize::PerShard(NonZeroUsize::new(1).unwrap()))
+    ///     .pool_autoscaling(
+    ///         PoolAutoscaling::new(NonZeroUsize::new(1).unwrap(), NonZeroUsize::new(4).unwrap())?
+    ///             .with_load_thresholds(50, 500)?,
+    ///     )
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pool_autoscaling(mut self, autoscaling: PoolAutoscaling) -> Self {
+        self.config.connection_pool_autoscaling = Some(autoscaling);
+        self
+    }
+
+    /// Sets how a request picks one of the pooled connections to its target node
+    /// (or shard, for ScyllaDB), when the pool keeps more than one.
+    /// The default is [`ConnectionSelection::Random`].
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::num::NonZeroUsize;
+    /// use scylla::client::{ConnectionSelection, PoolSize};
+    ///
+    /// // Requests will be sent on the connection with the fewest in-flight requests.
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .pool_size(PoolSize::PerShard(NonZeroUsize::new(4).unwrap()))
+    ///     .connection_selection(ConnectionSelection::LeastInFlight)
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connection_selection(mut self, selection: ConnectionSelection) -> Self {
+        self.config.connection_selection = selection;
+        self
+    }
+
+    /// Sets the maximum number of pooled connections the session may keep open
+    /// to all nodes in total.
+    ///
+    /// The limit is split evenly among the nodes: each node's pool may hold at most
+    /// the limit divided by the number of nodes (rounded up), and never more than
+    /// [`pool_size`](Self::pool_size) allows. If the limit is lower than the number
+    /// of nodes, some nodes will have no connections at all. The control connection
+    /// is not counted.
+    ///
+    /// By default, there is no limit.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::num::NonZeroUsize;
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// // This session will keep at most 16 connections, across all nodes.
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .max_total_connections(NonZeroUsize::new(16).unwrap())
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn max_total_connections(mut self, max: NonZeroUsize) -> Self {
+        self.config.max_total_connections = Some(max);
+        self
+    }
+
+    /// Sets the maximum number of connections which may be opened to a single node
+    /// at the same time.
+    ///
+    /// Filling a large pool, e.g. with many connections per shard, opens all missing
+    /// connections at once by default. With the limit, the remaining ones wait until
+    /// some of the connections being opened are established (or fail).
+    ///
+    /// By default, there is no limit.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::num::NonZeroUsize;
+    /// use scylla::client::PoolSize;
+    ///
+    /// // At most 4 connections are opened to each node at the same time.
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .pool_size(PoolSize::PerShard(NonZeroUsize::new(8).unwrap()))
+    ///     .max_concurrent_connection_opens(NonZeroUsize::new(4).unwrap())
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn max_concurrent_connection_opens(mut self, max: NonZeroUsize) -> Self {
+        self.config.max_concurrent_connection_opens = Some(max);
+        self
+    }
+
+    /// If true, statements cached by [`CachingSession`](crate::client::caching_session::CachingSession)s
+    /// wrapping the session are prepared in the background on each newly opened connection.
+    ///
+    /// Nodes forget prepared statements when they restart, so without this option,
+    /// the first execution of each cached statement on a reconnected node fails with
+    /// UNPREPARED and has to be retried after repreparing the statement.
+    ///
+    /// The default is false.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .reprepare_on_new_connections(true)
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn reprepare_on_new_connections(mut self, enabled: bool) -> Self {
+        self.config.reprepare_on_new_connections = enabled;
+        self
+    }
+
+    /// Preloads prepared statements from a file saved with
+    /// [`save_prepared_cache`](crate::statement::prepared_cache::save_prepared_cache),
+    /// e.g. by a previous run of the application.
+    ///
+    /// The first [`Session::prepare`](crate::client::session::Session::prepare) of each
+    /// preloaded statement does not send a PREPARE request to the cluster, but uses the saved
+    /// id and metadata instead. If the cluster does not know the statement anymore, it is
+    /// reprepared upon its first execution. A statement is only preloaded if the session
+    /// prepares it in the same keyspace as the session which saved it.
+    ///
+    /// Each preloaded statement is used only once. Preparing the same statement again
+    /// sends a PREPARE request as usual, so that it fetches fresh metadata, e.g. after
+    /// a schema change.
+    ///
+    /// Building the session fails if the file cannot be read or is malformed.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .preload_prepared_from("prepared.bin")
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn preload_prepared_from(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.preload_prepared_from = Some(path.into());
+        self
+    }
+
     /// If true, prevents the driver from connecting to the shard-aware port, even if the node supports it.
     ///
     /// _This is a Scylla-specific option_. It has no effect on Cassandra clusters.
@@ -999,6 +1219,30 @@ impl<K: SessionBuilderKind> GenericSessionBuilder<K> {
         self
     }
 
+    /// Keep a warm standby control connection open to a second node.
+    /// If the control connection breaks, the standby is promoted in its place,
+    /// so metadata refresh can continue without opening a new connection first.
+    /// The standby is opened in the background, so it does not delay metadata refreshes.
+    /// The default is false.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .standby_control_connection(true)
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn standby_control_connection(mut self, enabled: bool) -> Self {
+        self.config.standby_control_connection = enabled;
+        self
+    }
+
     /// Set the server-side timeout for metadata queries.
     /// The default is `Some(Duration::from_secs(2))`. It means that
     /// the all metadata queries will be set the 2 seconds timeout
@@ -1087,6 +1331,124 @@ impl<K: SessionBuilderKind> GenericSessionBuilder<K> {
         self
     }
 
+    /// Set the interval of RTT probes.
+    /// The default is `None`, which means that RTT is not measured.
+    /// If set, a lightweight OPTIONS request is sent on every connection with
+    /// the given interval, and its round-trip time is exposed via
+    /// [`Session::connection_stats`](crate::client::session::Session::connection_stats).
+    /// Zero intervals are rejected during session creation.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .rtt_probe_interval(std::time::Duration::from_secs(5))
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rtt_probe_interval(mut self, interval: Duration) -> Self {
+        self.config.rtt_probe_interval = Some(interval);
+        self
+    }
+
+    /// Set the maximum number of attempts made to execute a single request.
+    /// The default is `None`, which means that the number of attempts is limited
+    /// only by the retry policy and the speculative execution policy.
+    ///
+    /// The cap counts every attempt sent to a node, including retries and attempts
+    /// started by speculative execution. Once it is reached, no further attempts are made
+    /// and the error of the last failed attempt is returned. For paged requests,
+    /// the cap applies to fetching each page separately.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # use std::num::NonZeroU32;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .max_attempts_per_request(NonZeroU32::new(10).unwrap())
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn max_attempts_per_request(mut self, max_attempts: NonZeroU32) -> Self {
+        self.config.max_attempts_per_request = Some(max_attempts);
+        self
+    }
+
+    /// Set the maximum number of requests executed concurrently by the session.
+    /// The default is `None`, which means that the number of concurrent requests is not limited.
+    ///
+    /// Requests above the limit wait in an admission queue. Whenever a request finishes,
+    /// the waiting request with the highest priority is admitted, see
+    /// [`Statement::set_priority`](crate::statement::Statement::set_priority).
+    /// Requests of equal priority are admitted in the order they were issued.
+    /// Time spent waiting counts towards the request timeout.
+    ///
+    /// Paged requests, e.g. those of a [`QueryPager`](crate::client::pager::QueryPager),
+    /// are admitted separately for each fetched page, so a pager holds no slot
+    /// while its pages are being consumed.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # use std::num::NonZeroUsize;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .max_concurrent_requests(NonZeroUsize::new(1024).unwrap())
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn max_concurrent_requests(mut self, max_concurrent_requests: NonZeroUsize) -> Self {
+        self.config.max_concurrent_requests = Some(max_concurrent_requests);
+        self
+    }
+
+    /// Makes the limit of requests executed concurrently by the session adapt to the load
+    /// of the cluster. The default is `None`, which means that the limit is fixed.
+    ///
+    /// The limit is lowered when attempts, including page fetches, fail with an
+    /// [`Overloaded`](crate::errors::DbError::Overloaded) error, and slowly raised
+    /// as attempts succeed, see [`AdaptiveConcurrency`]. Requests above the limit wait
+    /// in the admission queue, as with [`max_concurrent_requests`](Self::max_concurrent_requests),
+    /// which is ignored if this is set.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # use scylla::client::AdaptiveConcurrency;
+    /// # use std::num::NonZeroUsize;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .adaptive_concurrency(AdaptiveConcurrency::new(
+    ///         NonZeroUsize::new(16).unwrap(),
+    ///         NonZeroUsize::new(1024).unwrap(),
+    ///         0.5,
+    ///     )?)
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn adaptive_concurrency(mut self, adaptive_concurrency: AdaptiveConcurrency) -> Self {
+        self.config.adaptive_concurrency = Some(adaptive_concurrency);
+        self
+    }
+
     /// Sets the timeout for waiting for schema agreement.
     /// By default, the timeout is 60 seconds.
     ///
@@ -1152,6 +1514,31 @@ impl<K: SessionBuilderKind> GenericSessionBuilder<K> {
         self
     }
 
+    /// Prefer IPv6 addresses over IPv4 ones when a hostname resolves to both.
+    /// The default is false, which prefers IPv4 addresses.
+    ///
+    /// This applies to the resolution of contact points and of hostnames of client routes.
+    /// Enable it on IPv6-only networks, so that the driver doesn't attempt to connect
+    /// to unreachable IPv4 addresses.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("scylla.example.com:9042")
+    ///     .prefer_ipv6(true)
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prefer_ipv6(mut self, enabled: bool) -> Self {
+        self.config.prefer_ipv6 = enabled;
+        self
+    }
+
     /// Sets the host filter. The host filter decides whether any connections
     /// should be opened to the node or not. The driver will also avoid
     /// those nodes when re-establishing the control connection.
@@ -1347,6 +1734,219 @@ impl<K: SessionBuilderKind> GenericSessionBuilder<K> {
         self
     }
 
+    /// Set the maximum number of idle frame buffers the session keeps for reuse.
+    ///
+    /// Requests are serialized into buffers taken from a pool shared by all connections
+    /// of the session, and the buffers are given back once the requests are sent.
+    /// Compressed responses are read into pooled buffers too, which are given back
+    /// once the responses are decompressed.
+    /// This avoids allocating a new buffer for every frame under sustained load.
+    /// Setting the size to zero disables pooling.
+    ///
+    /// The default is [`DEFAULT_FRAME_BUFFER_POOL_SIZE`](crate::client::session::DEFAULT_FRAME_BUFFER_POOL_SIZE),
+    /// which disables pooling.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .frame_buffer_pool_size(1024)
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn frame_buffer_pool_size(mut self, size: usize) -> Self {
+        self.config.frame_buffer_pool_size = size;
+        self
+    }
+
+    /// Set the maximum capacity, in bytes, of a frame buffer kept for reuse.
+    ///
+    /// Buffers grown larger than this, e.g. by serializing a huge request, are freed
+    /// instead of being given back to the pool (see [`SessionBuilder::frame_buffer_pool_size()`]),
+    /// so that a single big request does not pin its memory for the lifetime of the session.
+    ///
+    /// The default is [`DEFAULT_FRAME_BUFFER_MAX_RETAINED_SIZE`](crate::client::session::DEFAULT_FRAME_BUFFER_MAX_RETAINED_SIZE).
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .frame_buffer_max_retained_size(16 * 1024)
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn frame_buffer_max_retained_size(mut self, size: usize) -> Self {
+        self.config.frame_buffer_max_retained_size = size;
+        self
+    }
+
+    /// Set the size of the buffer, per connection, into which responses are read.
+    ///
+    /// Responses which are already available on the socket are read with a single syscall
+    /// as long as they fit in the buffer. Unlike write coalescing
+    /// (see [`SessionBuilder::write_coalescing()`]), reads are never delayed to wait for
+    /// more responses, so this only sizes the buffer. A larger buffer uses more memory
+    /// on every connection. Setting the size to zero disables buffering, so the header
+    /// and the body of each frame are read separately.
+    ///
+    /// The default is [`DEFAULT_READ_BUFFER_SIZE`](crate::client::session::DEFAULT_READ_BUFFER_SIZE).
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .read_buffer_size(64 * 1024)
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_buffer_size(mut self, size: usize) -> Self {
+        self.config.read_buffer_size = size;
+        self
+    }
+
+    /// Enable the per-connection error-rate circuit breaker.
+    /// The default is `None`, which disables it.
+    ///
+    /// If enabled, every connection tracks the outcome of its requests. Once more than
+    /// the configured fraction of requests within a window fails, the connection is closed
+    /// and the pool opens a new one in its place. Only connection-level failures and timeouts
+    /// are counted: broken connections, exhausted stream ids, unparsable responses and
+    /// `ReadTimeout` and `WriteTimeout` database errors. Errors which describe the state
+    /// of the whole node, such as `Overloaded` or `IsBootstrapping`, are not counted.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # use scylla::client::ErrorRateCircuitBreaker;
+    /// # use std::num::NonZeroU32;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .connection_circuit_breaker(ErrorRateCircuitBreaker::new(
+    ///         0.5,
+    ///         NonZeroU32::new(100).unwrap(),
+    ///     )?)
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connection_circuit_breaker(mut self, circuit_breaker: ErrorRateCircuitBreaker) -> Self {
+        self.config.connection_circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Retain the given number of the most recent warnings returned by the database,
+    /// which can be read with [`Session::recent_warnings`](crate::client::session::Session::recent_warnings).
+    /// By default, warnings are not retained.
+    ///
+    /// Warnings of all requests of the session are retained, including internal ones.
+    /// Once the capacity is reached, the oldest warnings are evicted.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::num::NonZeroUsize;
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .recent_warnings_capacity(NonZeroUsize::new(100).unwrap())
+    ///     .build()
+    ///     .await?;
+    ///
+    /// for warning in session.recent_warnings() {
+    ///     println!("{}: {}", warning.node_address, warning.message);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn recent_warnings_capacity(mut self, capacity: NonZeroUsize) -> Self {
+        self.config.recent_warnings_capacity = Some(capacity);
+        self
+    }
+
+    /// Register a sink to which metrics events of the session are pushed:
+    /// completed requests, retries and connection changes.
+    /// By default, no sink is registered.
+    ///
+    /// This works independently of the `metrics` feature.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// use scylla::observability::metrics_sink::{MetricsSink, RequestCompleteEvent};
+    ///
+    /// struct LatencyPrinter;
+    ///
+    /// impl MetricsSink for LatencyPrinter {
+    ///     fn on_request_complete(&self, event: &RequestCompleteEvent) {
+    ///         println!("{}: {:?}", event.node_address, event.latency);
+    ///     }
+    /// }
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .metrics_sink(Arc::new(LatencyPrinter))
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn metrics_sink(mut self, sink: Arc<dyn MetricsSink>) -> Self {
+        self.config.metrics_sink = Some(sink);
+        self
+    }
+
+    /// Pass through Result responses of a kind unknown to the driver as raw bytes,
+    /// instead of failing the request. The default is false.
+    ///
+    /// This lets forward-compatible tooling work with result kinds introduced
+    /// by newer protocol versions. The raw result is exposed with
+    /// [`QueryResult::unknown_result`](crate::response::query_result::QueryResult::unknown_result).
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::session_builder::SessionBuilder;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let session: Session = SessionBuilder::new()
+    ///     .known_node("127.0.0.1:9042")
+    ///     .pass_through_unknown_result_kinds(true)
+    ///     .build()
+    ///     .await?;
+    ///
+    /// let result = session.query_unpaged("SELECT * FROM ks.t", ()).await?;
+    /// if let Some(unknown) = result.unknown_result() {
+    ///     println!("Result of kind {}: {:?}", unknown.kind, unknown.body);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pass_through_unknown_result_kinds(mut self, enabled: bool) -> Self {
+        self.config.pass_through_unknown_result_kinds = enabled;
+        self
+    }
+
     /// Set the interval at which the driver refreshes the cluster metadata which contains information
     /// about the cluster topology as well as the cluster schema.
     ///
@@ -1574,6 +2174,24 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn zero_rtt_probe_interval_is_rejected() {
+        setup_tracing();
+        let error = SessionBuilder::new()
+            .known_node("127.0.0.1:9042")
+            .rtt_probe_interval(Duration::ZERO)
+            .build()
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, NewSessionError::IllegalConfig(_)));
+        assert!(
+            error
+                .to_string()
+                .contains("RTT probe interval must be non-zero")
+        );
+    }
+
     #[test]
     fn fetch_schema_metadata() {
         setup_tracing();
@@ -172,14 +172,26 @@ impl ControlConnection {
             self.query_client_routes(connection_ids, &[]).await
         };
 
-        let peers_and_cluster_name;
+        let peers_and_local_settings;
         let client_routes: ClientRoutes;
-        let keyspaces: HashMap<String, Result<Keyspace, SingleKeyspaceMetadataError>>;
+        let mut keyspaces: HashMap<String, Result<Keyspace, SingleKeyspaceMetadataError>>;
 
-        (peers_and_cluster_name, client_routes, keyspaces) =
+        (peers_and_local_settings, client_routes, keyspaces) =
             tokio::try_join!(peers_query, client_routes_query, keyspaces_query)?;
 
-        let (peers, cluster_name) = peers_and_cluster_name;
+        let (
+            peers,
+            LocalSettings {
+                cluster_name,
+                partitioner,
+            },
+        ) = peers_and_local_settings;
+
+        // The partitioner is configured for the whole cluster, so all keyspaces use it,
+        // except for tables which declare their own one.
+        for keyspace in keyspaces.values_mut().flatten() {
+            keyspace.partitioner = partitioner.clone();
+        }
 
         let client_routes_updated_hosts =
             if let Some(client_routes_subscriber) = self.client_routes_subscriber() {
@@ -230,6 +242,23 @@ struct LocalNodeInfoRow {
     rack: Option<String>,
     tokens: Option<Vec<String>>,
     cluster_name: Option<String>,
+    partitioner: Option<String>,
+}
+
+/// Settings of the cluster, as reported by `system.local`.
+#[derive(Default)]
+struct LocalSettings {
+    cluster_name: Option<String>,
+    partitioner: Option<String>,
+}
+
+impl LocalSettings {
+    fn or(self, other: Self) -> Self {
+        Self {
+            cluster_name: self.cluster_name.or(other.cluster_name),
+            partitioner: self.partitioner.or(other.partitioner),
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -251,7 +280,7 @@ impl ControlConnection {
     async fn query_peers(
         &self,
         connect_port: u16,
-    ) -> Result<(Vec<Peer>, Option<String>), MetadataError> {
+    ) -> Result<(Vec<Peer>, LocalSettings), MetadataError> {
         let peers_query_stream = self
             .query_iter(
                 "SELECT host_id, rpc_address, data_center, rack, tokens FROM system.peers",
@@ -270,10 +299,10 @@ impl ControlConnection {
                 error,
                 table: "system.peers",
             })
-            .and_then(|row| future::ok((NodeInfoSource::Peer, row, None::<String>)));
+            .and_then(|row| future::ok((NodeInfoSource::Peer, row, LocalSettings::default())));
 
         let local_query_stream = self
-            .query_iter("SELECT host_id, rpc_address, data_center, rack, tokens, cluster_name FROM system.local WHERE key='local'", &())
+            .query_iter("SELECT host_id, rpc_address, data_center, rack, tokens, cluster_name, partitioner FROM system.local WHERE key='local'", &())
             .map(|pager_res| {
                 let pager = pager_res?;
                 let rows_stream = pager.rows_stream::<LocalNodeInfoRow>()?;
@@ -288,7 +317,10 @@ impl ControlConnection {
                 table: "system.local",
             })
             .and_then(|row| {
-                let cluster_name = row.cluster_name;
+                let local_settings = LocalSettings {
+                    cluster_name: row.cluster_name,
+                    partitioner: row.partitioner,
+                };
                 let node_row = NodeInfoRow {
                     host_id: row.host_id,
                     untranslated_ip_addr: row.untranslated_ip_addr,
@@ -296,7 +328,7 @@ impl ControlConnection {
                     rack: row.rack,
                     tokens: row.tokens,
                 };
-                future::ok((NodeInfoSource::Local, node_row, cluster_name))
+                future::ok((NodeInfoSource::Local, node_row, local_settings))
             });
 
         let untranslated_rows = stream::select(peers_query_stream, local_query_stream);
@@ -306,36 +338,36 @@ impl ControlConnection {
 
         let translated_peers_futures = untranslated_rows.map(|row_result| async {
             match row_result {
-                Ok((source, row, cluster_name)) => {
+                Ok((source, row, local_settings)) => {
                     let peer = Self::create_peer_from_row(source, row, local_address).await;
-                    (peer, cluster_name)
+                    (peer, local_settings)
                 }
                 Err(err) => {
                     warn!(
                         "system.peers or system.local has an invalid row, skipping it: {}",
                         err
                     );
-                    (None, None)
+                    (None, LocalSettings::default())
                 }
             }
         });
 
-        let (peers, cluster_name) = translated_peers_futures
+        let (peers, local_settings) = translated_peers_futures
             .buffer_unordered(256)
             .collect::<Vec<_>>()
             .await
             .into_iter()
             .fold(
-                (Vec::new(), None),
-                |(mut peers, cluster_name), (peer, name)| {
+                (Vec::new(), LocalSettings::default()),
+                |(mut peers, local_settings), (peer, settings)| {
                     if let Some(peer) = peer {
                         peers.push(peer);
                     }
-                    (peers, cluster_name.or(name))
+                    (peers, local_settings.or(settings))
                 },
             );
 
-        Ok((peers, cluster_name))
+        Ok((peers, local_settings))
     }
 
     async fn create_peer_from_row(
@@ -667,6 +699,8 @@ impl ControlConnection {
                 tables,
                 views,
                 user_defined_types,
+                // Filled in from `system.local` once the peers are fetched.
+                partitioner: None,
             };
 
             Ok((keyspace_name, Ok(keyspace)))
@@ -1656,6 +1690,8 @@ fn strategy_from_string_map(
 
 #[cfg(test)]
 mod tests {
+    use assert_matches::assert_matches;
+
     use crate::test_utils::setup_tracing;
 
     use super::*;
@@ -1840,4 +1876,83 @@ mod tests {
             assert_eq!(parsed, expected);
         }
     }
+
+    #[test]
+    fn test_strategy_parsing() {
+        setup_tracing();
+
+        let map = |entries: &[(&str, &str)]| {
+            entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+
+        for class in [
+            "org.apache.cassandra.locator.SimpleStrategy",
+            "SimpleStrategy",
+        ] {
+            assert_eq!(
+                strategy_from_string_map(map(&[("class", class), ("replication_factor", "3")]))
+                    .unwrap(),
+                Strategy::SimpleStrategy {
+                    replication_factor: 3
+                }
+            );
+        }
+
+        for class in [
+            "org.apache.cassandra.locator.NetworkTopologyStrategy",
+            "NetworkTopologyStrategy",
+        ] {
+            assert_eq!(
+                strategy_from_string_map(map(&[("class", class), ("dc1", "3"), ("dc2", "1")]))
+                    .unwrap(),
+                Strategy::NetworkTopologyStrategy {
+                    datacenter_repfactors: HashMap::from([
+                        ("dc1".to_owned(), 3),
+                        ("dc2".to_owned(), 1)
+                    ]),
+                }
+            );
+        }
+
+        assert_eq!(
+            strategy_from_string_map(map(&[("class", "LocalStrategy")])).unwrap(),
+            Strategy::LocalStrategy
+        );
+        assert_eq!(
+            strategy_from_string_map(map(&[("class", "EverywhereStrategy"), ("foo", "bar")]))
+                .unwrap(),
+            Strategy::Other {
+                name: "EverywhereStrategy".to_owned(),
+                data: map(&[("foo", "bar")]),
+            }
+        );
+
+        assert_matches!(
+            strategy_from_string_map(map(&[("replication_factor", "3")])),
+            Err(KeyspaceStrategyError::MissingClassForStrategyDefinition)
+        );
+        assert_matches!(
+            strategy_from_string_map(map(&[("class", "SimpleStrategy")])),
+            Err(KeyspaceStrategyError::MissingReplicationFactorForSimpleStrategy)
+        );
+        assert_matches!(
+            strategy_from_string_map(map(&[
+                ("class", "SimpleStrategy"),
+                ("replication_factor", "three")
+            ])),
+            Err(KeyspaceStrategyError::ReplicationFactorParseError(_))
+        );
+        assert_matches!(
+            strategy_from_string_map(map(&[
+                ("class", "NetworkTopologyStrategy"),
+                ("dc1", "3"),
+                ("tablets", "{}")
+            ])),
+            Err(KeyspaceStrategyError::UnexpectedNetworkTopologyStrategyOption { key, .. })
+                if key == "tablets"
+        );
+    }
 }
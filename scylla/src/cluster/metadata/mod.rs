@@ -158,6 +158,24 @@ pub struct Keyspace {
     ///
     /// Empty HashMap may as well mean that the client disabled schema fetching in SessionConfig.
     pub user_defined_types: HashMap<String, Arc<UserDefinedType<'static>>>,
+    /// Name of the partitioner used by the tables of the keyspace which do not declare
+    /// their own one (see [`Table::partitioner`]).
+    ///
+    /// It is the partitioner the cluster is configured with, as reported by `system.local`.
+    /// `None` means that the node did not report it.
+    pub partitioner: Option<String>,
+}
+
+impl Keyspace {
+    /// Returns the name of the partitioner used by the given table of this keyspace,
+    /// falling back to the partitioner of the keyspace if the table does not declare one
+    /// or is unknown.
+    pub(crate) fn table_partitioner(&self, table_name: &str) -> Option<&str> {
+        self.tables
+            .get(table_name)
+            .and_then(|table| table.partitioner.as_deref())
+            .or(self.partitioner.as_deref())
+    }
 }
 
 /// Describes a table in the cluster.
@@ -173,6 +191,12 @@ pub struct Table {
     /// All of the names are guaranteed to be present in `columns` field.
     pub clustering_key: Vec<String>,
     /// Name of the partitioner used by the table.
+    ///
+    /// `None` means that the table uses the partitioner of its keyspace
+    /// (see [`Keyspace::partitioner`]). Tables which use a different one, such as
+    /// CDC log tables, declare it themselves. Prepared statements are routed with
+    /// the partitioner of the table they refer to, see
+    /// [`PreparedStatement::get_partitioner_name`](crate::statement::prepared::PreparedStatement::get_partitioner_name).
     pub partitioner: Option<String>,
     /// Column specs for the partition key columns.
     pub(crate) pk_column_specs: Vec<ColumnSpec<'static>>,
@@ -11,6 +11,8 @@
 //! - Automatic reconnection to other known peers on connection failure
 //! - Fallback to initial contact points when all known peers are unreachable
 //! - Host filtering to ensure the control connection is established to an accepted node
+//! - Optionally, a warm standby control connection to a second node, opened in the background
+//!   and promoted in place of the control connection when it breaks
 //!
 
 use std::collections::HashSet;
@@ -74,6 +76,7 @@ pub(crate) struct MetadataReader {
     control_connection_config: ConnectionConfig,
     request_serverside_timeout: Option<Duration>,
     hostname_resolution_timeout: Option<Duration>,
+    prefer_ipv6: bool,
     keyspaces_to_fetch: Vec<String>,
     fetch_schema: bool,
     host_filter: Option<Arc<dyn HostFilter>>,
@@ -81,6 +84,8 @@ pub(crate) struct MetadataReader {
     // and establishing control connection to them is attempted.
     initial_known_nodes: Vec<KnownNode>,
     client_routes_subscriber: Option<Arc<dyn ClientRoutesSubscriber>>,
+    // Whether a warm standby control connection should be kept open to a second node.
+    standby_control_connection_enabled: bool,
 
     // ====================================================================
     // Mutable state of MetadataReader. It will change during its lifetime.
@@ -89,6 +94,17 @@ pub(crate) struct MetadataReader {
     // when control connection fails, MetadataReader tries to connect to one of known_peers
     known_peers: Vec<UntranslatedEndpoint>,
     cc_cache: Arc<ControlConnectionCache>,
+    // Warm standby, promoted when the control connection breaks.
+    // It is registered for events too, so that it needs no setup when promoted;
+    // its events are discarded until then.
+    standby_control_connection: Option<WorkingControlConnection>,
+    // Standby being opened in the background, so that opening it does not delay metadata refreshes.
+    opening_standby_control_connection: Option<OpeningControlConnection>,
+}
+
+struct OpeningControlConnection {
+    endpoint: UntranslatedEndpoint,
+    opened: oneshot::Receiver<ControlConnectionState>,
 }
 
 impl MetadataReader {
@@ -97,15 +113,21 @@ impl MetadataReader {
     pub(crate) async fn new(
         initial_known_nodes: Vec<KnownNode>,
         hostname_resolution_timeout: Option<Duration>,
+        prefer_ipv6: bool,
         connection_config: ConnectionConfig,
         request_serverside_timeout: Option<Duration>,
+        standby_control_connection_enabled: bool,
         keyspaces_to_fetch: Vec<String>,
         fetch_schema: bool,
         host_filter: &Option<Arc<dyn HostFilter>>,
         client_routes_subscriber: Option<Arc<dyn ClientRoutesSubscriber>>,
     ) -> Result<Self, NewSessionError> {
-        let (initial_peers, resolved_hostnames) =
-            resolve_contact_points(&initial_known_nodes, hostname_resolution_timeout).await;
+        let (initial_peers, resolved_hostnames) = resolve_contact_points(
+            &initial_known_nodes,
+            hostname_resolution_timeout,
+            prefer_ipv6,
+        )
+        .await;
         // Ensure there is at least one resolved node
         if initial_peers.is_empty() {
             return Err(NewSessionError::FailedToResolveAnyHostname(
@@ -136,6 +158,7 @@ impl MetadataReader {
             control_connection_state,
             request_serverside_timeout,
             hostname_resolution_timeout,
+            prefer_ipv6,
             known_peers: initial_peers
                 .into_iter()
                 .map(UntranslatedEndpoint::ContactPoint)
@@ -146,53 +169,229 @@ impl MetadataReader {
             initial_known_nodes,
             cc_cache,
             client_routes_subscriber,
+            standby_control_connection_enabled,
+            standby_control_connection: None,
+            opening_standby_control_connection: None,
         })
     }
 
     pub(crate) async fn wait_for_control_connection_event(&mut self) -> ControlConnectionEvent {
-        match &mut self.control_connection_state {
-            ControlConnectionState::Broken { .. } => std::future::pending().await,
-            ControlConnectionState::Working(working_connection) => {
-                tokio::select! {
-                    // Why only `Some`? `None` means that event channel was dropped.
-                    // In current implementation (as of writing this comment)
-                    // this should not be possible: events sender is stored in HostConnectionConfig,
-                    // which is a field of Connection that we own. If we got `None`, then most likely
-                    // two things happened:
-                    //  - The implementation changed, for example by moving event sender to router.
-                    //  - Connection was closed, router shutdown.
-                    //  - `tokio::select!` chose this branch instead of error channel.
-                    // The best thing we can imo do is ignore this `None`. `error_channel` should receive
-                    // info about connection shutdown very soon.
-                    Some(cql_event) = working_connection.events_channel.recv() => {
-                        ControlConnectionEvent::ServerEvent(cql_event)
-                    },
-                    maybe_control_connection_failed = &mut working_connection.error_channel => {
-                        let err = match maybe_control_connection_failed {
-                            Ok(err) => err,
-                            Err(_recv_error) => {
-                                // If we got here then error channel, in a Connection that we own,
-                                // was dropped without sending anything. This is definitely a bug in the driver!
-                                // We could theoretically recover by dropping a connection and creating new one,
-                                // but we would need to add an error variant to `BrokenConnectionErrorKind` that
-                                // could basically never happen. Let's panic instead.
-                                warn!(concat!("Error sender of control connection unexpectedly dropped. The only case when this ",
-                                "may happen is during runtime shutdown. If you see this and the runtime isn't shutting down, ",
-                                "this is a bug in the driver. Then please open an issue!"));
-                                return ControlConnectionEvent::Shutdown;
-                            },
-                        };
-                        self.control_connection_state = ControlConnectionState::Broken {
-                            last_error: MetadataError::ConnectionPoolError(ConnectionPoolError::Broken { last_connection_error: err }),
-                            last_endpoint: working_connection.endpoint.clone()
-                        };
-                        ControlConnectionEvent::Broken
-                    }
+        loop {
+            let working_connection = match &mut self.control_connection_state {
+                ControlConnectionState::Broken { .. } => return std::future::pending().await,
+                ControlConnectionState::Working(working_connection) => working_connection,
+            };
+            tokio::select! {
+                // Why only `Some`? `None` means that event channel was dropped.
+                // In current implementation (as of writing this comment)
+                // this should not be possible: events sender is stored in HostConnectionConfig,
+                // which is a field of Connection that we own. If we got `None`, then most likely
+                // two things happened:
+                //  - The implementation changed, for example by moving event sender to router.
+                //  - Connection was closed, router shutdown.
+                //  - `tokio::select!` chose this branch instead of error channel.
+                // The best thing we can imo do is ignore this `None`. `error_channel` should receive
+                // info about connection shutdown very soon.
+                Some(cql_event) = working_connection.events_channel.recv() => {
+                    return ControlConnectionEvent::ServerEvent(cql_event);
+                },
+                maybe_control_connection_failed = &mut working_connection.error_channel => {
+                    let err = match maybe_control_connection_failed {
+                        Ok(err) => err,
+                        Err(_recv_error) => {
+                            // If we got here then error channel, in a Connection that we own,
+                            // was dropped without sending anything. This is definitely a bug in the driver!
+                            // We could theoretically recover by dropping a connection and creating new one,
+                            // but we would need to add an error variant to `BrokenConnectionErrorKind` that
+                            // could basically never happen. Let's panic instead.
+                            warn!(concat!("Error sender of control connection unexpectedly dropped. The only case when this ",
+                            "may happen is during runtime shutdown. If you see this and the runtime isn't shutting down, ",
+                            "this is a bug in the driver. Then please open an issue!"));
+                            return ControlConnectionEvent::Shutdown;
+                        },
+                    };
+                    self.control_connection_state = ControlConnectionState::Broken {
+                        last_error: MetadataError::ConnectionPoolError(ConnectionPoolError::Broken { last_connection_error: err }),
+                        last_endpoint: working_connection.endpoint.clone()
+                    };
+                    // Promoting the standby makes the refresh that follows this event
+                    // use the standby right away.
+                    self.promote_standby_control_connection();
+                    return ControlConnectionEvent::Broken;
                 }
+                standby_error = Self::wait_for_standby_failure(&mut self.standby_control_connection) => {
+                    debug!(
+                        error = %standby_error,
+                        "Standby control connection broke, it will be reopened on the next metadata refresh"
+                    );
+                    self.standby_control_connection = None;
+                }
+                opened = Self::wait_for_opened_standby(&mut self.opening_standby_control_connection) => {
+                    self.opening_standby_control_connection = None;
+                    self.set_opened_standby_control_connection(opened);
+                }
+            }
+        }
+    }
+
+    /// Resolves once the standby control connection being opened in the background
+    /// is either opened or failed to open. Never resolves if no standby is being opened.
+    async fn wait_for_opened_standby(
+        opening: &mut Option<OpeningControlConnection>,
+    ) -> Option<ControlConnectionState> {
+        let Some(opening) = opening else {
+            return std::future::pending().await;
+        };
+        // The opening task is only dropped without sending if the runtime is shutting down.
+        (&mut opening.opened).await.ok()
+    }
+
+    /// Takes over the standby control connection if it was opened in the background in the meantime.
+    fn collect_opened_standby_control_connection(&mut self) {
+        let Some(opening) = &mut self.opening_standby_control_connection else {
+            return;
+        };
+        let opened = match opening.opened.try_recv() {
+            Ok(opened) => Some(opened),
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => None,
+        };
+        self.opening_standby_control_connection = None;
+        self.set_opened_standby_control_connection(opened);
+    }
+
+    fn set_opened_standby_control_connection(&mut self, opened: Option<ControlConnectionState>) {
+        match opened {
+            Some(ControlConnectionState::Working(standby)) => {
+                debug!(
+                    "Opened standby control connection to {}",
+                    standby.endpoint.address()
+                );
+                self.standby_control_connection = Some(standby);
             }
+            Some(ControlConnectionState::Broken {
+                last_error,
+                last_endpoint,
+            }) => {
+                warn!(
+                    error = %last_error,
+                    standby_address = %last_endpoint.address(),
+                    "Failed to open standby control connection"
+                );
+            }
+            None => {}
         }
     }
 
+    /// Discards events received on the standby control connection, and resolves
+    /// once it breaks. Never resolves if there is no standby.
+    async fn wait_for_standby_failure(
+        standby: &mut Option<WorkingControlConnection>,
+    ) -> ConnectionError {
+        let Some(standby) = standby else {
+            return std::future::pending().await;
+        };
+        loop {
+            tokio::select! {
+                // Events are received by the control connection. A standby only has
+                // to keep its channel drained, so that its router is not blocked.
+                Some(_cql_event) = standby.events_channel.recv() => {},
+                maybe_standby_failed = &mut standby.error_channel => {
+                    return match maybe_standby_failed {
+                        Ok(err) => err,
+                        // See the analogous case in `wait_for_control_connection_event`.
+                        Err(_recv_error) => std::future::pending().await,
+                    };
+                }
+            }
+        }
+    }
+
+    /// Replaces the control connection with the standby one, if there is a standby that still works.
+    /// Returns whether the standby was promoted.
+    fn promote_standby_control_connection(&mut self) -> bool {
+        self.collect_opened_standby_control_connection();
+        let Some(mut standby) = self.standby_control_connection.take() else {
+            return false;
+        };
+        if !matches!(
+            standby.error_channel.try_recv(),
+            Err(oneshot::error::TryRecvError::Empty)
+        ) {
+            debug!(
+                "Standby control connection to {} is broken, not promoting it",
+                standby.endpoint.address()
+            );
+            return false;
+        }
+        debug!(
+            "Promoting standby control connection to {}",
+            standby.endpoint.address()
+        );
+        self.control_connection_state = ControlConnectionState::Working(standby);
+        true
+    }
+
+    /// Starts opening a standby control connection to a known peer other than the control
+    /// connection's one in the background, unless a suitable standby is already open
+    /// or being opened, or standby is disabled.
+    fn maintain_standby_control_connection(&mut self) {
+        if !self.standby_control_connection_enabled {
+            return;
+        }
+        self.collect_opened_standby_control_connection();
+        let control_connection_address = self.control_connection_state.endpoint().address();
+        let is_suitable = |endpoint: &UntranslatedEndpoint| {
+            endpoint.address() != control_connection_address
+                && self
+                    .known_peers
+                    .iter()
+                    .any(|peer| peer.address() == endpoint.address())
+        };
+        if self
+            .standby_control_connection
+            .as_ref()
+            .is_some_and(|standby| is_suitable(&standby.endpoint))
+            || self
+                .opening_standby_control_connection
+                .as_ref()
+                .is_some_and(|opening| is_suitable(&opening.endpoint))
+        {
+            return;
+        }
+        self.standby_control_connection = None;
+        self.opening_standby_control_connection = None;
+
+        let candidates = self
+            .known_peers
+            .iter()
+            .filter(|peer| is_suitable(peer))
+            .collect::<Vec<_>>();
+        let Some(&endpoint) = candidates.choose(&mut rng()) else {
+            return;
+        };
+        debug!(
+            "Opening standby control connection to {}",
+            endpoint.address()
+        );
+        let make_standby = Self::make_control_connection(
+            endpoint.clone(),
+            self.control_connection_config.clone(),
+            self.request_serverside_timeout,
+            Arc::clone(&self.cc_cache),
+            self.client_routes_subscriber.as_ref().map(Arc::clone),
+        );
+        let (sender, opened) = oneshot::channel();
+        tokio::spawn(async move {
+            // If the receiver was dropped, the standby is not needed anymore and is closed.
+            let _ = sender.send(make_standby.await);
+        });
+        self.opening_standby_control_connection = Some(OpeningControlConnection {
+            endpoint: endpoint.clone(),
+            opened,
+        });
+    }
+
     pub(crate) fn control_connection_works(&self) -> bool {
         matches!(
             self.control_connection_state,
@@ -203,6 +402,10 @@ impl MetadataReader {
     /// Fetches current metadata from the cluster
     pub(crate) async fn read_metadata(&mut self, initial: bool) -> Result<Metadata, MetadataError> {
         let mut result = self.fetch_metadata(initial).await;
+        if result.is_err() && self.promote_standby_control_connection() {
+            // The standby is already connected, so it is tried before any other peer.
+            result = self.fetch_metadata(initial).await;
+        }
         let prev_err = match result {
             Ok(metadata) => {
                 debug!("Fetched new metadata");
@@ -211,6 +414,7 @@ impl MetadataReader {
                     self.handle_unaccepted_host_in_control_connection(&metadata)
                         .await;
                 }
+                self.maintain_standby_control_connection();
                 return Ok(metadata);
             }
             Err(err) => err,
@@ -250,6 +454,7 @@ impl MetadataReader {
                 let (initial_peers, _hostnames) = resolve_contact_points(
                     &self.initial_known_nodes,
                     self.hostname_resolution_timeout,
+                    self.prefer_ipv6,
                 )
                 .await;
                 result = self
@@ -272,6 +477,7 @@ impl MetadataReader {
                 self.update_known_peers(metadata);
                 self.handle_unaccepted_host_in_control_connection(metadata)
                     .await;
+                self.maintain_standby_control_connection();
                 debug!("Fetched new metadata");
             }
             Err(error) => {
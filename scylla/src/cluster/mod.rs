@@ -22,8 +22,14 @@ mod state;
 pub use state::ClusterState;
 
 pub(crate) mod node;
+pub use crate::network::ServerOptions;
 pub use node::{KnownNode, Node, NodeAddr, NodeRef};
 
 mod control_connection;
 
+mod schema_changes;
+pub use schema_changes::SchemaChangeStream;
+
+pub(crate) use schema_changes::SCHEMA_CHANGE_STREAM_CAPACITY;
+
 pub mod metadata;
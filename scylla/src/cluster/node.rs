@@ -5,8 +5,8 @@ use uuid::Uuid;
 
 use crate::errors::{ConnectionPoolError, DnsLookupError, UseKeyspaceError};
 use crate::network::VerifiedKeyspaceName;
-use crate::network::{Connection, ConnectivityChangeEvent};
-use crate::network::{NodeConnectionPool, PoolConfig};
+use crate::network::{Connection, ConnectivityChangeEvent, FreshConnection, ServerOptions};
+use crate::network::{NodeConnectionPool, PoolConfig, PoolFillState};
 #[cfg(feature = "metrics")]
 use crate::observability::metrics::Metrics;
 /// Node represents a cluster node along with it's data and connections
@@ -211,6 +211,12 @@ impl Node {
         self.get_pool()?.connection_for_shard(shard)
     }
 
+    pub(crate) async fn open_fresh_connection(
+        &self,
+    ) -> Result<FreshConnection, ConnectionPoolError> {
+        self.get_pool()?.open_fresh_connection().await
+    }
+
     /// Returns true if the driver has any open connections in the pool for this
     /// node.
     pub fn is_connected(&self) -> bool {
@@ -224,6 +230,28 @@ impl Node {
         pool.is_connected()
     }
 
+    /// Returns the number of requests currently in flight on all working
+    /// connections to this node. Returns 0 if the node has no open connections.
+    pub fn in_flight_requests(&self) -> usize {
+        self.get_working_connections()
+            .map(|connections| {
+                connections
+                    .iter()
+                    .map(|connection| connection.in_flight_requests())
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns the options advertised by this node in its SUPPORTED response,
+    /// or `None` if there is no working connection to the node.
+    pub fn server_options(&self) -> Option<ServerOptions> {
+        self.get_working_connections()
+            .ok()?
+            .first()
+            .map(|connection| connection.get_server_options().clone())
+    }
+
     /// Returns a boolean which indicates whether this node was is enabled.
     /// Only enabled nodes will have connections open. For disabled nodes,
     /// no connections will be opened.
@@ -251,6 +279,12 @@ impl Node {
         Ok(())
     }
 
+    /// Returns the number of established connections and the number of connections
+    /// the pool aims to keep, or `None` if the node is disabled.
+    pub(crate) fn pool_fill(&self) -> Option<PoolFillState> {
+        self.pool.as_ref().map(NodeConnectionPool::fill)
+    }
+
     pub(crate) fn get_working_connections(
         &self,
     ) -> Result<Vec<Arc<Connection>>, ConnectionPoolError> {
@@ -327,9 +361,11 @@ async fn lookup_host_with_timeout(
 // Resolve the given hostname using a DNS lookup if necessary.
 // The resolution may return multiple IPs and the function returns one of them.
 // It prefers to return IPv4s first, and only if there are none, IPv6s.
+// If `prefer_ipv6` is set, the preference is reversed.
 pub(crate) async fn resolve_hostname(
     hostname: &str,
     hostname_resolution_timeout: Option<Duration>,
+    prefer_ipv6: bool,
 ) -> Result<SocketAddr, DnsLookupError> {
     // When passing String to `lookup_host`, it expects it to be in the form "hostname:port".
     // If it is not, error will be returned immediately. In this case, we want to perform
@@ -350,11 +386,19 @@ pub(crate) async fn resolve_hostname(
         }
     };
 
-    addrs
-        .find_or_last(|addr| matches!(addr, SocketAddr::V4(_)))
+    pick_resolved_address(addrs, prefer_ipv6)
         .ok_or_else(|| DnsLookupError::EmptyAddressListForHost(hostname.into()))
 }
 
+// Picks the first address of the preferred IP version,
+// falling back to the last address if there is none.
+fn pick_resolved_address(
+    addrs: impl Iterator<Item = SocketAddr>,
+    prefer_ipv6: bool,
+) -> Option<SocketAddr> {
+    addrs.find_or_last(|addr| addr.is_ipv6() == prefer_ipv6)
+}
+
 /// Transforms the given [`InternalKnownNode`]s into [`ContactPoint`]s.
 ///
 /// In case of a hostname, resolves it using a DNS lookup.
@@ -362,6 +406,7 @@ pub(crate) async fn resolve_hostname(
 pub(crate) async fn resolve_contact_points(
     known_nodes: &[KnownNode],
     hostname_resolution_timeout: Option<Duration>,
+    prefer_ipv6: bool,
 ) -> (Vec<ResolvedContactPoint>, Vec<String>) {
     // Find IP addresses of all known nodes passed in the config
     let mut initial_peers: Vec<ResolvedContactPoint> = Vec::with_capacity(known_nodes.len());
@@ -381,7 +426,7 @@ pub(crate) async fn resolve_contact_points(
         };
     }
     let resolve_futures = to_resolve.into_iter().map(|hostname| async move {
-        match resolve_hostname(hostname, hostname_resolution_timeout).await {
+        match resolve_hostname(hostname, hostname_resolution_timeout, prefer_ipv6).await {
             Ok(address) => Some(ResolvedContactPoint { address }),
             Err(e) => {
                 warn!("Hostname resolution failed for {}: {}", hostname, &e);
@@ -398,6 +443,53 @@ pub(crate) async fn resolve_contact_points(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{ipv6_loopback_available, setup_tracing};
+
+    #[test]
+    fn resolved_address_of_preferred_ip_version_is_picked() {
+        let v4: SocketAddr = "127.0.0.1:9042".parse().unwrap();
+        let v4_other: SocketAddr = "127.0.0.2:9042".parse().unwrap();
+        let v6: SocketAddr = "[::1]:9042".parse().unwrap();
+
+        let addrs = [v4, v6, v4_other];
+        assert_eq!(pick_resolved_address(addrs.into_iter(), false), Some(v4));
+        assert_eq!(pick_resolved_address(addrs.into_iter(), true), Some(v6));
+
+        // Falls back to another IP version if the preferred one is missing.
+        assert_eq!(pick_resolved_address([v6].into_iter(), false), Some(v6));
+        assert_eq!(
+            pick_resolved_address([v4, v4_other].into_iter(), true),
+            Some(v4_other)
+        );
+
+        assert_eq!(pick_resolved_address(std::iter::empty(), true), None);
+    }
+
+    #[tokio::test]
+    async fn ipv6_contact_points_are_resolved() {
+        setup_tracing();
+        if !ipv6_loopback_available().await {
+            return;
+        }
+        let known_nodes = [
+            KnownNode::Hostname("[::1]:9042".to_owned()),
+            KnownNode::Address("[::1]:19042".parse().unwrap()),
+        ];
+        let (resolved, hostnames) = resolve_contact_points(&known_nodes, None, true).await;
+        assert_eq!(hostnames, ["[::1]:9042"]);
+        let mut addresses = resolved
+            .into_iter()
+            .map(|contact_point| contact_point.address)
+            .collect::<Vec<_>>();
+        addresses.sort();
+        assert_eq!(
+            addresses,
+            [
+                "[::1]:9042".parse::<SocketAddr>().unwrap(),
+                "[::1]:19042".parse().unwrap()
+            ]
+        );
+    }
 
     impl Node {
         pub(crate) fn new_for_test(
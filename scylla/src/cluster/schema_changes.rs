@@ -0,0 +1,143 @@
+//! Streaming of schema change events received on the control connection.
+//!
+//! A stream of events can be obtained with
+//! [`Session::schema_change_stream`](crate::client::session::Session::schema_change_stream).
+
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+
+use crate::frame::response::event::SchemaChangeEvent;
+
+/// Number of events buffered for each [`SchemaChangeStream`].
+/// If the stream is not polled and the buffer fills up, further events are dropped.
+pub(crate) const SCHEMA_CHANGE_STREAM_CAPACITY: usize = 256;
+
+struct Subscriber {
+    sender: mpsc::Sender<SchemaChangeEvent>,
+    dropped_events: Arc<AtomicU64>,
+}
+
+/// Distributes schema change events among all live [`SchemaChangeStream`]s.
+#[derive(Default)]
+pub(crate) struct SchemaChangeSubscribers {
+    subscribers: StdMutex<Vec<Subscriber>>,
+}
+
+impl SchemaChangeSubscribers {
+    pub(crate) fn subscribe(&self, capacity: usize) -> SchemaChangeStream {
+        let (sender, receiver) = mpsc::channel(capacity);
+        let dropped_events = Arc::new(AtomicU64::new(0));
+        self.subscribers.lock().unwrap().push(Subscriber {
+            sender,
+            dropped_events: Arc::clone(&dropped_events),
+        });
+
+        SchemaChangeStream {
+            receiver,
+            dropped_events,
+        }
+    }
+
+    /// Sends the event to all subscribers without waiting.
+    /// Events are dropped for subscribers which can't keep up,
+    /// and subscribers whose streams were dropped are forgotten.
+    pub(crate) fn notify(&self, event: &SchemaChangeEvent) {
+        self.subscribers.lock().unwrap().retain(|subscriber| {
+            match subscriber.sender.try_send(event.clone()) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    subscriber.dropped_events.fetch_add(1, Ordering::Relaxed);
+                    true
+                }
+                Err(TrySendError::Closed(_)) => false,
+            }
+        });
+    }
+}
+
+/// A stream of schema change events pushed by the cluster.
+///
+/// Events are received on the control connection, so events which happen
+/// while it is being reestablished are not observed.
+///
+/// The stream buffers a bounded number of events. If it is not polled fast enough,
+/// newer events are dropped; their number is reported by [`SchemaChangeStream::dropped_events`].
+#[derive(Debug)]
+pub struct SchemaChangeStream {
+    receiver: mpsc::Receiver<SchemaChangeEvent>,
+    dropped_events: Arc<AtomicU64>,
+}
+
+impl SchemaChangeStream {
+    /// Returns the number of events that were dropped because the stream's buffer was full.
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped_events.load(Ordering::Relaxed)
+    }
+}
+
+impl Stream for SchemaChangeStream {
+    type Item = SchemaChangeEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use futures::StreamExt;
+
+    use super::SchemaChangeSubscribers;
+    use crate::frame::response::event::{SchemaChangeEvent, SchemaChangeType};
+
+    fn keyspace_created(name: &str) -> SchemaChangeEvent {
+        SchemaChangeEvent::KeyspaceChange {
+            change_type: SchemaChangeType::Created,
+            keyspace_name: name.to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn events_are_dropped_and_counted_when_stream_is_full() {
+        let subscribers = SchemaChangeSubscribers::default();
+        let mut stream = subscribers.subscribe(2);
+
+        for name in ["ks1", "ks2", "ks3", "ks4"] {
+            subscribers.notify(&keyspace_created(name));
+        }
+        assert_eq!(stream.dropped_events(), 2);
+
+        for expected in ["ks1", "ks2"] {
+            let Some(SchemaChangeEvent::KeyspaceChange { keyspace_name, .. }) = stream.next().await
+            else {
+                panic!("expected a keyspace change event");
+            };
+            assert_eq!(keyspace_name, expected);
+        }
+
+        // After draining the buffer, new events are delivered again.
+        subscribers.notify(&keyspace_created("ks5"));
+        assert_matches!(
+            stream.next().await,
+            Some(SchemaChangeEvent::KeyspaceChange { keyspace_name, .. }) if keyspace_name == "ks5"
+        );
+        assert_eq!(stream.dropped_events(), 2);
+    }
+
+    #[test]
+    fn dropped_streams_are_unsubscribed() {
+        let subscribers = SchemaChangeSubscribers::default();
+        let stream = subscribers.subscribe(2);
+        drop(stream);
+
+        subscribers.notify(&keyspace_created("ks"));
+        assert!(subscribers.subscribers.lock().unwrap().is_empty());
+    }
+}
@@ -3,6 +3,7 @@ use crate::network::{Connection, ConnectivityChangeEvent, PoolConfig, VerifiedKe
 #[cfg(feature = "metrics")]
 use crate::observability::metrics::Metrics;
 use crate::policies::host_filter::HostFilter;
+use crate::policies::load_balancing::RoutingInfo;
 use crate::routing::locator::ReplicaLocator;
 use crate::routing::locator::tablets::{RawTablet, Tablet, TabletsInfo};
 use crate::routing::partitioner::{PartitionerName, calculate_token_for_partition_key};
@@ -12,7 +13,7 @@ use crate::utils::safe_format::IteratorSafeFormatExt;
 use crate::frame::response::result::TableSpec;
 use crate::serialize::row::{RowSerializationContext, SerializeRow, SerializedValues};
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::sync::mpsc;
@@ -339,17 +340,19 @@ impl ClusterState {
         )?;
 
         // Delegate actual token calculation to centralized helper.
-        self.do_compute_token(table_meta, &values)
+        self.do_compute_token(keyspace, table, &values)
     }
 
     fn do_compute_token(
         &self,
-        table: &Table,
+        keyspace: &str,
+        table: &str,
         serialized_partition_key: &SerializedValues,
     ) -> Result<Token, ClusterStateTokenError> {
-        let partitioner = table
-            .partitioner
-            .as_deref()
+        let partitioner = self
+            .keyspaces
+            .get(keyspace)
+            .and_then(|keyspace| keyspace.table_partitioner(table))
             .and_then(PartitionerName::from_str)
             .unwrap_or_default();
         calculate_token_for_partition_key(serialized_partition_key, &partitioner)
@@ -400,6 +403,32 @@ impl ClusterState {
         replica_set.into_iter()
     }
 
+    /// Host ids of the replicas of the partition targeted by a request,
+    /// or none if the partition is unknown.
+    pub(crate) fn replica_host_ids(&self, routing_info: &RoutingInfo) -> Vec<Uuid> {
+        match (routing_info.token, routing_info.table) {
+            (Some(token), Some(table_spec)) => self
+                .get_token_endpoints_iter(table_spec, token)
+                .map(|(node, _)| node.host_id)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The replica at the given position among the replicas of the partition
+    /// targeted by a request, in token order, or none if the partition is unknown
+    /// or has fewer replicas.
+    pub(crate) fn replica_at(
+        &self,
+        routing_info: &RoutingInfo,
+        index: usize,
+    ) -> Option<NodeRef<'_>> {
+        let (token, table_spec) = routing_info.token.zip(routing_info.table)?;
+        self.get_token_endpoints_iter(table_spec, token)
+            .nth(index)
+            .map(|(node, _)| node)
+    }
+
     /// Access to replicas owning a given partition key (similar to `nodetool getendpoints`)
     ///
     /// `partition_key` argument contains the values of all partition key
@@ -417,6 +446,18 @@ impl ClusterState {
         Ok(self.get_token_endpoints(keyspace, table, token))
     }
 
+    /// Returns the primary owner of each token in the token ring, ordered by token.
+    ///
+    /// The primary owner of a token is the node which the token was assigned to.
+    /// Ownership of tablet-based tables is not reflected in this map.
+    pub fn token_map(&self) -> BTreeMap<Token, NodeRef<'_>> {
+        self.locator
+            .ring()
+            .iter()
+            .map(|(token, node)| (*token, node))
+            .collect()
+    }
+
     /// Access replica location info
     pub fn replica_locator(&self) -> &ReplicaLocator {
         &self.locator
@@ -613,7 +654,7 @@ impl ClusterState {
             });
         }
 
-        self.do_compute_token(table_meta, serialized_partition_key)
+        self.do_compute_token(keyspace, table, serialized_partition_key)
     }
 
     /// Compute token using a specific partitioner, bypassing table metadata lookup.
@@ -838,6 +879,29 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn token_map_covers_all_tokens_of_known_nodes() {
+        setup_tracing();
+
+        let peers = (1..=3)
+            .map(|id| Peer {
+                tokens: (0..4).map(|i| Token::new(i * 1000 + id as i64)).collect(),
+                ..make_peer(Uuid::new_v4(), make_addr(id), Some("dc1"), Some("r1"))
+            })
+            .collect::<Vec<_>>();
+        let expected_owners = peers
+            .iter()
+            .flat_map(|peer| peer.tokens.iter().map(|token| (*token, peer.host_id)))
+            .collect::<Vec<_>>();
+        let state = build_cluster_state(make_metadata(peers), &HashMap::new(), None).await;
+
+        let token_map = state.token_map();
+        assert_eq!(token_map.len(), expected_owners.len());
+        for (token, host_id) in expected_owners {
+            assert_eq!(token_map[&token].host_id, host_id);
+        }
+    }
+
     /// Unit tests for the interop API.
     #[cfg(all(scylla_unstable, feature = "unstable-csharp-rs"))]
     mod interop_tests {
@@ -874,6 +938,7 @@ mod tests {
                 views: HashMap::new(),
                 user_defined_types: HashMap::new(),
                 durable_writes: true,
+                partitioner: None,
             };
             let mut keyspaces = HashMap::new();
             keyspaces.insert(keyspace_name.to_string(), keyspace);
@@ -3,6 +3,7 @@ use crate::client::client_routes::{
 };
 use crate::client::session::TABLET_CHANNEL_SIZE;
 use crate::cluster::metadata::reader::ControlConnectionEvent;
+use crate::cluster::schema_changes::{SchemaChangeStream, SchemaChangeSubscribers};
 use crate::cluster::{KnownNode, Node};
 use crate::errors::{MetadataError, NewSessionError, RequestAttemptError, UseKeyspaceError};
 use crate::frame::response::event::EventV2 as Event;
@@ -39,6 +40,9 @@ pub(crate) struct Cluster {
     refresh_channel: tokio::sync::mpsc::Sender<RefreshRequest>,
     use_keyspace_channel: tokio::sync::mpsc::Sender<UseKeyspaceRequest>,
 
+    // Shared with `ClusterWorker`, which feeds schema change events to subscribers.
+    schema_change_subscribers: Arc<SchemaChangeSubscribers>,
+
     _worker_handle: RemoteHandle<()>,
 }
 
@@ -104,6 +108,9 @@ struct ClusterWorker {
     // The host listener allows to listen for topology and node status changes.
     host_listener: Option<Arc<dyn HostListener>>,
 
+    // Streams of schema change events, fed from the control connection.
+    schema_change_subscribers: Arc<SchemaChangeSubscribers>,
+
     // This value determines how frequently the cluster
     // worker will refresh the cluster metadata
     cluster_metadata_refresh_interval: Duration,
@@ -131,7 +138,9 @@ impl Cluster {
         keyspaces_to_fetch: Vec<String>,
         fetch_schema_metadata: bool,
         metadata_request_serverside_timeout: Option<Duration>,
+        standby_control_connection: bool,
         hostname_resolution_timeout: Option<Duration>,
+        prefer_ipv6: bool,
         host_filter: Option<Arc<dyn HostFilter>>,
         host_listener: Option<Arc<dyn HostListener>>,
         cluster_metadata_refresh_interval: Duration,
@@ -150,11 +159,14 @@ impl Cluster {
             tokio::sync::mpsc::unbounded_channel();
 
         let client_routes_address_translator = client_routes_config.as_ref().map(|config| {
-            let translator = Arc::new(ClientRoutesAddressTranslator::new(
-                config.clone(),
-                hostname_resolution_timeout,
-                pool_config.connection_config.tls_provider.is_some(),
-            ));
+            let translator = Arc::new(
+                ClientRoutesAddressTranslator::new(
+                    config.clone(),
+                    hostname_resolution_timeout,
+                    pool_config.connection_config.tls_provider.is_some(),
+                )
+                .with_prefer_ipv6(prefer_ipv6),
+            );
             pool_config.connection_config.address_translator =
                 Some(Arc::clone(&translator) as Arc<dyn AddressTranslator>);
 
@@ -164,8 +176,10 @@ impl Cluster {
         let mut metadata_reader = MetadataReader::new(
             known_nodes,
             hostname_resolution_timeout,
+            prefer_ipv6,
             pool_config.connection_config.clone(),
             metadata_request_serverside_timeout,
+            standby_control_connection,
             keyspaces_to_fetch,
             fetch_schema_metadata,
             &host_filter,
@@ -203,6 +217,7 @@ impl Cluster {
 
         let cluster_state: Arc<ArcSwap<ClusterState>> =
             Arc::new(ArcSwap::from(Arc::new(cluster_state)));
+        let schema_change_subscribers = Arc::new(SchemaChangeSubscribers::default());
 
         let worker = ClusterWorker {
             cluster_state: cluster_state.clone(),
@@ -221,6 +236,7 @@ impl Cluster {
 
             host_filter,
             host_listener,
+            schema_change_subscribers: Arc::clone(&schema_change_subscribers),
             cluster_metadata_refresh_interval,
 
             #[cfg(feature = "metrics")]
@@ -234,6 +250,7 @@ impl Cluster {
             state: cluster_state,
             refresh_channel: refresh_sender,
             use_keyspace_channel: use_keyspace_sender,
+            schema_change_subscribers,
             _worker_handle: worker_handle,
         };
 
@@ -244,6 +261,10 @@ impl Cluster {
         self.state.load_full()
     }
 
+    pub(crate) fn subscribe_to_schema_changes(&self, capacity: usize) -> SchemaChangeStream {
+        self.schema_change_subscribers.subscribe(capacity)
+    }
+
     pub(crate) async fn refresh_metadata(&self) -> Result<(), MetadataError> {
         let (response_sender, response_receiver) = tokio::sync::oneshot::channel();
 
@@ -360,6 +381,8 @@ impl ClusterWorker {
                             // The first reconnect attempt will be immediate (by attempting metadata refresh below),
                             // and if it does not succeed, then `ControlConnectionState` will be set to `Broken`, so
                             // subsequent attempts will be issued every second.
+                            // If a standby control connection was kept, it has already been promoted,
+                            // so the refresh below uses it without reconnecting.
                         },
                         ControlConnectionEvent::ServerEvent(event) => {
                             debug!("Received server event: {:?}", event);
@@ -415,6 +438,10 @@ impl ClusterWorker {
                                     }
                                     continue; // Don't go to refreshing.
                                 },
+                                Event::SchemaChange(schema_change) => {
+                                    self.schema_change_subscribers.notify(&schema_change);
+                                    continue; // Don't go to refreshing.
+                                },
                                 _ => continue, // Don't go to refreshing.
                             }
                         }
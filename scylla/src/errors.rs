@@ -8,6 +8,10 @@ use std::sync::Arc;
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::cluster::node::NodeAddr;
+use crate::frame::frame_errors::{
+    LowLevelDeserializationError, PreparedParseError, PreparedSerializationError,
+};
 use crate::frame::response;
 
 // Re-export error types from pager module.
@@ -16,8 +20,8 @@ pub use crate::client::pager::{NextPageError, NextRowError};
 use crate::statement::prepared::TokenCalculationError;
 // Re-export error types from query_result module.
 pub use crate::response::query_result::{
-    FirstRowError, IntoRowsResultError, MaybeFirstRowError, ResultNotRowsError, RowsError,
-    SingleRowError,
+    FirstRowError, IntoRowsResultError, LwtTypedError, MaybeFirstRowError, ResultNotRowsError,
+    RowsError, SingleRowError, SortedByError,
 };
 
 // Re-export error type from authentication module.
@@ -36,7 +40,7 @@ pub use crate::frame::frame_errors::{
 };
 pub use crate::frame::request::CqlRequestKind;
 pub use crate::frame::response::CqlResponseKind;
-pub use crate::frame::response::error::{DbError, OperationType, WriteType};
+pub use crate::frame::response::error::{DbError, OperationType, ServerErrorDetails, WriteType};
 pub use crate::serialize::SerializationError;
 
 /// Error that occurred during request execution
@@ -69,6 +73,11 @@ pub enum ExecutionError {
     #[error(transparent)]
     LastAttemptError(#[from] RequestAttemptError),
 
+    /// The statement requires the coordinator to be a replica of the partition it targets,
+    /// but no node in the load balancing plan is one.
+    #[error("Coordinator {0} is not a replica of the partition targeted by the request")]
+    CoordinatorNotReplica(NodeAddr),
+
     /// Failed to run a request within a provided client timeout.
     #[error(
         "Request execution exceeded a client timeout of {}ms",
@@ -76,6 +85,20 @@ pub enum ExecutionError {
     )]
     RequestTimeout(std::time::Duration),
 
+    /// An unpaged request returned more rows than the limit set on the statement.
+    #[error("Unpaged request returned more than {0} rows")]
+    UnpagedRowLimitExceeded(std::num::NonZeroU32),
+
+    /// The server no longer accepts the paging state that the request was resumed from,
+    /// e.g. because it is stale. The scan has to be restarted from the beginning.
+    /// Contains the reason returned by the server.
+    #[error("The server no longer accepts the paging state: {0}")]
+    PagingStateExpired(String),
+
+    /// Request execution was cancelled with a [`CancelHandle`](crate::client::CancelHandle).
+    #[error("Request execution was cancelled")]
+    Cancelled,
+
     /// 'USE KEYSPACE <>' request failed.
     #[error("'USE KEYSPACE <>' request failed: {0}")]
     UseKeyspaceError(#[from] UseKeyspaceError),
@@ -151,6 +174,63 @@ pub enum PagerExecutionError {
     MetadataError(#[from] MetadataError),
 }
 
+/// An error yielded by the stream returned from
+/// [`Session::multiget`](crate::client::session::Session::multiget).
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum MultigetError {
+    /// Failed to serialize a key.
+    #[error("Failed to serialize a key: {0}")]
+    KeySerialization(#[from] SerializationError),
+
+    /// Point read of a key failed.
+    #[error("Point read failed: {0}")]
+    Execution(#[from] ExecutionError),
+
+    /// Point read of a key did not return rows.
+    #[error("Point read did not return rows: {0}")]
+    IntoRowsResult(#[from] IntoRowsResultError),
+
+    /// Type of the returned columns does not match the requested row type.
+    #[error("Returned columns don't match the requested row type: {0}")]
+    TypeCheck(#[from] TypeCheckError),
+
+    /// Failed to deserialize a returned row.
+    #[error("Failed to deserialize a row: {0}")]
+    RowDeserialization(#[from] DeserializationError),
+}
+
+/// An error returned by
+/// [`Session::execute_single_page_rows`](crate::client::session::Session::execute_single_page_rows).
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum SinglePageRowsError {
+    /// Execution of the statement failed.
+    #[error("Execution failed: {0}")]
+    Execution(#[from] ExecutionError),
+
+    /// The statement did not return rows.
+    #[error("Statement did not return rows: {0}")]
+    IntoRowsResult(#[from] IntoRowsResultError),
+
+    /// Failed to deserialize a returned row.
+    #[error("Failed to deserialize a row: {0}")]
+    RowDeserialization(#[from] DeserializationError),
+}
+
+/// An error returned by [`Session::execute_auto`](crate::client::session::Session::execute_auto).
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum AutoExecutionError {
+    /// Execution of the first page failed.
+    #[error("Execution failed: {0}")]
+    Execution(#[from] ExecutionError),
+
+    /// Failed to create a pager over a result spanning many pages.
+    #[error("Failed to create a pager: {0}")]
+    Pager(#[from] PagerExecutionError),
+}
+
 /// Error that occurred during session creation
 #[derive(Error, Debug, Clone)]
 #[non_exhaustive]
@@ -175,6 +255,44 @@ pub enum NewSessionError {
     /// Provided combination of Session configuration options is unsupported.
     #[error("Provided combination of Session configuration options is unsupported: {0}")]
     IllegalConfig(Box<str>),
+
+    /// Failed to load the prepared statements to preload.
+    #[error("Failed to load the prepared statements to preload: {0}")]
+    PreloadPreparedError(#[from] PreparedCacheError),
+}
+
+/// An error that occurred while saving or loading a file with prepared statements,
+/// see [`prepared_cache`](crate::statement::prepared_cache).
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum PreparedCacheError {
+    /// Failed to read or write the file.
+    #[error("Failed to read or write the prepared statement cache file: {0}")]
+    IoError(Arc<std::io::Error>),
+
+    /// The file was not saved with [`save_prepared_cache`](crate::statement::prepared_cache::save_prepared_cache).
+    #[error("The file is not a prepared statement cache file")]
+    NotAPreparedCacheFile,
+
+    /// A blob was saved in a format version which is not supported by this driver.
+    #[error("Unsupported format version of a prepared statement blob: {0}")]
+    UnsupportedBlobVersion(u8),
+
+    /// A blob is truncated or otherwise malformed.
+    #[error("Malformed prepared statement blob: {0}")]
+    MalformedBlob(#[from] LowLevelDeserializationError),
+
+    /// Failed to parse the metadata of a statement in a blob.
+    #[error("Malformed metadata in a prepared statement blob: {0}")]
+    MalformedMetadata(#[from] PreparedParseError),
+
+    /// A statement or its metadata is too large to be serialized.
+    #[error("Prepared statement is too large to be serialized")]
+    StatementTooLarge,
+
+    /// Failed to serialize the metadata of a statement.
+    #[error("Failed to serialize the metadata of a prepared statement: {0}")]
+    MetadataSerializationFailed(#[from] PreparedSerializationError),
 }
 
 /// An error that occurred during `USE KEYSPACE <>` request.
@@ -297,6 +415,44 @@ pub enum TracingError {
     EmptyResults,
 }
 
+/// An error that occurred while measuring the clock skew of a coordinator.
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum ClockSkewError {
+    /// Failed to query the coordinator's time.
+    #[error("Failed to query the coordinator's time: {0}")]
+    ExecutionError(#[from] ExecutionError),
+
+    /// The response to the coordinator's time query was not a single `bigint` row.
+    #[error("Invalid response to the coordinator's time query: {0}")]
+    InvalidResponse(#[from] SingleRowError),
+
+    /// Failed to convert the response to the coordinator's time query to rows result.
+    #[error("Failed to convert the response to the coordinator's time query to rows result: {0}")]
+    IntoRowsResultError(#[from] IntoRowsResultError),
+}
+
+/// An error that occurred while fetching client connections from `system.clients`.
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum ServerClientsError {
+    /// Failed to execute query to "system.clients".
+    #[error("Failed to execute query to \"system.clients\" system table: {0}")]
+    ExecutionError(#[from] ExecutionError),
+
+    /// Failed to convert result of system.clients query to rows result.
+    #[error("Failed to convert result of system.clients query to rows result: {0}")]
+    IntoRowsResultError(IntoRowsResultError),
+
+    /// system.clients has invalid column type.
+    #[error("system.clients has invalid column type: {0}")]
+    InvalidColumnType(TypeCheckError),
+
+    /// Response to system.clients failed to deserialize.
+    #[error("Response to system.clients failed to deserialize: {0}")]
+    DeserializationFailed(DeserializationError),
+}
+
 /// An error that occurred during metadata fetch and verification.
 ///
 /// The driver performs metadata fetch and verification of the cluster's schema
@@ -807,6 +963,10 @@ impl BrokenConnectionError {
     pub fn downcast_ref<T: Error + 'static>(&self) -> Option<&T> {
         self.0.downcast_ref()
     }
+    /// Wraps an error with which a connection opened for a single request failed.
+    pub(crate) fn from_connection_error(err: ConnectionError) -> Self {
+        BrokenConnectionError(Arc::new(err))
+    }
 }
 
 /// A reason why connection was broken.
@@ -844,6 +1004,11 @@ pub enum BrokenConnectionErrorKind {
     #[error("Too many orphaned stream ids: {0}")]
     TooManyOrphanedStreamIds(u16),
 
+    /// The fraction of failed requests on the connection exceeded the threshold
+    /// of the connection circuit breaker.
+    #[error("Error rate of requests exceeded the circuit breaker threshold of {0}")]
+    ErrorRateExceeded(f64),
+
     /// Failed to send data via tokio channel. This implies
     /// that connection was probably already broken for some other reason.
     #[error(
@@ -924,6 +1089,11 @@ pub enum RequestError {
     /// Failed to execute request.
     #[error(transparent)]
     LastAttemptError(#[from] RequestAttemptError),
+
+    /// The statement requires the coordinator to be a replica of the partition it targets,
+    /// but no node in the load balancing plan is one.
+    #[error("Coordinator {0} is not a replica of the partition targeted by the request")]
+    CoordinatorNotReplica(NodeAddr),
 }
 
 impl RequestError {
@@ -934,6 +1104,9 @@ impl RequestError {
             RequestError::ConnectionPoolError(e) => e.into(),
             RequestError::RequestTimeout(dur) => ExecutionError::RequestTimeout(dur),
             RequestError::LastAttemptError(e) => ExecutionError::LastAttemptError(e),
+            RequestError::CoordinatorNotReplica(node) => {
+                ExecutionError::CoordinatorNotReplica(node)
+            }
         }
     }
 }
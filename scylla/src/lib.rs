@@ -113,18 +113,24 @@ pub mod value {
     // Most types come from scylla-cql-core.
     pub(crate) use scylla_cql::value::deser_cql_value;
     pub use scylla_cql_core::value::{
-        Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
-        CqlTimeuuid, CqlValue, CqlVarint, CqlVarintBorrowed, Emptiable, MaybeEmpty, MaybeUnset,
-        Row, Unset, ValueOverflow,
+        Cidr, Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
+        CqlTimeuuid, CqlValue, CqlVarint, CqlVarintBorrowed, Emptiable, InvalidCidr, ListIter,
+        Maybe, MaybeEmpty, MaybeUnset, Row, Unset, ValueOverflow, WriteTime,
     };
+
+    #[cfg(feature = "chrono-04")]
+    pub use scylla_cql_core::value::{ChronoTimestamp, SubMillisecondPrecision};
+
+    #[cfg(feature = "serde-json-1")]
+    pub use scylla_cql_core::value::Json;
 }
 
 pub mod frame {
     //! Abstractions of the CQL wire protocol.
 
     pub(crate) use scylla_cql::frame::{
-        FrameParams, SerializedRequest, parse_response_body_extensions, read_response_frame,
-        request, server_event_type,
+        FrameParams, SerializedRequest, flag, parse_response_body_extensions,
+        read_response_frame_in, request, server_event_type,
     };
     pub use scylla_cql_core::frame::frame_errors;
     pub use scylla_cql_core::frame::protocol_features;
@@ -134,7 +140,14 @@ pub mod frame {
         //! CQL binary protocol in-wire types.
 
         #[cfg(test)]
-        pub(crate) use scylla_cql::frame::types::{read_string_map, write_bytes_opt};
+        pub(crate) use scylla_cql::frame::types::{
+            read_string_map, write_bytes_opt, write_string_list,
+        };
+
+        pub(crate) use scylla_cql::frame::types::{
+            read_bytes, read_int_length, read_long_string, read_string, write_bytes, write_int,
+            write_long_string, write_string,
+        };
         pub(crate) use scylla_cql_core::frame::types::RawValue;
         pub use scylla_cql_core::frame::types::{Consistency, SerialConsistency};
     }
@@ -145,6 +158,13 @@ pub mod frame {
         pub use scylla_cql::frame::response::CqlResponseKind;
         pub(crate) use scylla_cql::frame::response::*;
 
+        pub mod event {
+            //! CQL protocol-level representation of an `EVENT` response.
+
+            pub(crate) use scylla_cql::frame::response::event::*;
+            pub use scylla_cql::frame::response::event::{SchemaChangeEvent, SchemaChangeType};
+        }
+
         pub mod result {
             //! CQL protocol-level representation of a `RESULT` response.
             //!
@@ -181,7 +201,9 @@ pub mod serialize {
     pub mod row {
         // Main types
         pub(crate) use scylla_cql_core::serialize::row::SerializedValues;
-        pub use scylla_cql_core::serialize::row::{RowSerializationContext, SerializeRow};
+        pub use scylla_cql_core::serialize::row::{
+            RowSerializationContext, SerializeRow, SerializedValuesDump,
+        };
 
         // Errors
         pub use scylla_cql_core::serialize::row::{
@@ -239,8 +261,8 @@ pub mod deserialize {
     /// Deserializing a single CQL value from a column of the query result row.
     pub mod value {
         pub use scylla_cql_core::deserialize::value::{
-            BuiltinDeserializationError, BuiltinDeserializationErrorKind, BuiltinTypeCheckError,
-            BuiltinTypeCheckErrorKind, DeserializeValue, ListlikeIterator,
+            BlobChunks, BuiltinDeserializationError, BuiltinDeserializationErrorKind,
+            BuiltinTypeCheckError, BuiltinTypeCheckErrorKind, DeserializeValue, ListlikeIterator,
             MapDeserializationErrorKind, MapIterator, MapTypeCheckErrorKind,
             SetOrListDeserializationErrorKind, SetOrListTypeCheckErrorKind,
             TupleDeserializationErrorKind, TupleTypeCheckErrorKind, UdtIterator,
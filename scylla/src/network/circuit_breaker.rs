@@ -0,0 +1,153 @@
+//! A per-connection circuit breaker, closing connections on which too many requests fail.
+//!
+//! Each connection counts its requests in consecutive windows of a configured size.
+//! When a window ends with an error rate above the configured threshold, the connection
+//! is closed, and the connection pool replaces it with a new one.
+
+use std::num::NonZeroU32;
+use std::sync::Mutex as StdMutex;
+
+use thiserror::Error;
+use tokio::sync::Notify;
+
+/// Configuration of the per-connection error-rate circuit breaker.
+///
+/// See [`SessionBuilder::connection_circuit_breaker`](crate::client::session_builder::SessionBuilder::connection_circuit_breaker).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorRateCircuitBreaker {
+    max_error_rate: f64,
+    window: NonZeroU32,
+}
+
+impl ErrorRateCircuitBreaker {
+    /// Creates a circuit breaker which closes a connection if more than `max_error_rate`
+    /// of its requests fail within a window of `window` consecutive requests.
+    ///
+    /// The error is returned if `max_error_rate` is not within the `[0, 1)` range.
+    pub fn new(
+        max_error_rate: f64,
+        window: NonZeroU32,
+    ) -> Result<Self, InvalidErrorRateCircuitBreaker> {
+        if !(0.0..1.0).contains(&max_error_rate) {
+            return Err(InvalidErrorRateCircuitBreaker);
+        }
+        Ok(Self {
+            max_error_rate,
+            window,
+        })
+    }
+
+    /// The maximum tolerated fraction of failed requests within a window.
+    pub fn max_error_rate(&self) -> f64 {
+        self.max_error_rate
+    }
+
+    /// The number of consecutive requests over which the error rate is computed.
+    pub fn window(&self) -> NonZeroU32 {
+        self.window
+    }
+}
+
+/// An error returned by [`ErrorRateCircuitBreaker::new()`].
+#[derive(Debug, Error)]
+#[error("Invalid error-rate circuit breaker: the maximum error rate must be within [0, 1)")]
+pub struct InvalidErrorRateCircuitBreaker;
+
+#[derive(Debug, Default)]
+struct Window {
+    requests: u32,
+    errors: u32,
+}
+
+/// Tracks the error rate of a single connection.
+#[derive(Debug)]
+pub(crate) struct ErrorRateTracker {
+    config: ErrorRateCircuitBreaker,
+    window: StdMutex<Window>,
+    tripped: Notify,
+}
+
+impl ErrorRateTracker {
+    pub(crate) fn new(config: ErrorRateCircuitBreaker) -> Self {
+        Self {
+            config,
+            window: StdMutex::new(Window::default()),
+            tripped: Notify::new(),
+        }
+    }
+
+    pub(crate) fn config(&self) -> &ErrorRateCircuitBreaker {
+        &self.config
+    }
+
+    /// Records the outcome of a request, tripping the breaker if a window
+    /// ends with an error rate above the threshold.
+    pub(crate) fn record(&self, failed: bool) {
+        let error_rate = {
+            let mut window = self.window.lock().unwrap();
+            window.requests += 1;
+            if failed {
+                window.errors += 1;
+            }
+            if window.requests < self.config.window.get() {
+                return;
+            }
+            let window = std::mem::take(&mut *window);
+            window.errors as f64 / window.requests as f64
+        };
+
+        if error_rate > self.config.max_error_rate {
+            // The permit is stored if nobody waits yet, so the trip is not lost.
+            self.tripped.notify_one();
+        }
+    }
+
+    /// Resolves once the breaker trips.
+    pub(crate) async fn tripped(&self) {
+        self.tripped.notified().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use futures::FutureExt;
+
+    use super::{ErrorRateCircuitBreaker, ErrorRateTracker};
+
+    fn tracker(max_error_rate: f64, window: u32) -> ErrorRateTracker {
+        ErrorRateTracker::new(
+            ErrorRateCircuitBreaker::new(max_error_rate, NonZeroU32::new(window).unwrap()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn invalid_error_rates_are_rejected() {
+        let window = NonZeroU32::new(10).unwrap();
+        for rate in [-0.1, 1.0, 1.5, f64::NAN] {
+            assert!(ErrorRateCircuitBreaker::new(rate, window).is_err());
+        }
+        assert!(ErrorRateCircuitBreaker::new(0.0, window).is_ok());
+    }
+
+    #[test]
+    fn breaker_trips_when_window_exceeds_error_rate() {
+        let tracker = tracker(0.5, 4);
+
+        // 2 out of 4 is not above the threshold.
+        for failed in [true, false, true, false] {
+            tracker.record(failed);
+        }
+        assert!(tracker.tripped().now_or_never().is_none());
+
+        // The window was reset, so earlier errors don't count anymore.
+        for failed in [true, true, true] {
+            tracker.record(failed);
+        }
+        assert!(tracker.tripped().now_or_never().is_none());
+
+        tracker.record(false);
+        assert!(tracker.tripped().now_or_never().is_some());
+    }
+}
@@ -1,3 +1,6 @@
+use super::circuit_breaker::{ErrorRateCircuitBreaker, ErrorRateTracker};
+use super::connection_budget::ConnectionPermit;
+use super::frame_buffer_pool::FrameBufferPool;
 use super::tls::{TlsConfig, TlsProvider};
 use crate::authentication::AuthenticatorProvider;
 use crate::client::Compression;
@@ -5,18 +8,22 @@ use crate::client::SelfIdentity;
 use crate::client::pager::{NextRowError, QueryPager};
 use crate::cluster::NodeAddr;
 use crate::cluster::metadata::{PeerEndpoint, UntranslatedEndpoint};
+use crate::cluster::node::ResolvedContactPoint;
 use crate::errors::{
     BadKeyspaceName, BrokenConnectionError, BrokenConnectionErrorKind, ConnectionError,
     ConnectionSetupRequestError, ConnectionSetupRequestErrorKind, CqlEventHandlingError, DbError,
     InternalRequestError, RequestAttemptError, ResponseParseError, TranslationError,
     UseKeyspaceError,
 };
-use crate::frame::frame_errors::CqlResponseParseError;
+#[cfg(all(scylla_unstable, feature = "unstable-csharp-rs"))]
+use crate::frame::frame_errors::FrameBodyExtensionsParseError;
+use crate::frame::frame_errors::{CqlResponseParseError, CqlResultParseError};
 use crate::frame::protocol_features::ProtocolFeatures;
 use crate::frame::request::CqlRequestKind;
 use crate::frame::request::options::{self, Options};
 use crate::frame::request::query::QueryParameters;
 use crate::frame::request::register::RegisterV2 as Register;
+use crate::frame::response::CqlResponseKind;
 use crate::frame::response::authenticate::Authenticate;
 use crate::frame::response::result::{ResultMetadata, ResultWithDeserializedMetadata, TableSpec};
 use crate::frame::response::{self, error};
@@ -25,14 +32,18 @@ use crate::frame::response::{
 };
 use crate::frame::types::SerialConsistency;
 use crate::frame::{
-    self, FrameParams, SerializedRequest,
+    self, FrameParams, SerializedRequest, flag,
     request::{self, SerializableRequest, batch, execute, query},
     response::{ResponseOpcode, ResponseV2 as Response, event::EventV2 as Event, result},
     server_event_type::EventTypeV2 as EventType,
 };
+use crate::observability::connection_stats::{ConnectionStats, ConnectionStatsRecorder};
+use crate::observability::warnings::WarningsLog;
 use crate::policies::address_translator::{AddressTranslator, UntranslatedPeer};
 use crate::policies::timestamp_generator::TimestampGenerator;
-use crate::response::query_result::QueryResult;
+#[cfg(all(scylla_unstable, feature = "unstable-csharp-rs"))]
+use crate::response::RawResponse;
+use crate::response::query_result::{QueryResult, UnknownResult};
 use crate::response::{NonErrorAuthResponse, NonErrorStartupResponse, PagingState, QueryResponse};
 use crate::routing::locator::tablets::{RawTablet, TabletParsingError};
 use crate::routing::{Shard, ShardAwarePortRange, ShardInfo, Sharder, ShardingError};
@@ -53,7 +64,7 @@ use std::net::{IpAddr, SocketAddr};
 use std::num::NonZeroU64;
 use std::sync::Arc;
 use std::sync::Mutex as StdMutex;
-use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicI32, AtomicU64};
 use std::time::Duration;
 use std::{
     cmp::Ordering,
@@ -74,6 +85,9 @@ use tracing::{debug, error, trace, warn};
 const OLD_ORPHAN_COUNT_THRESHOLD: usize = 1024;
 const OLD_AGE_ORPHAN_THRESHOLD: std::time::Duration = std::time::Duration::from_secs(1);
 
+// Size of the header of a CQL v4 frame.
+const FRAME_HEADER_SIZE: usize = 9;
+
 /// Represents a write coalescing delay configuration option.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -97,16 +111,42 @@ pub(crate) struct Connection {
     _worker_handle: RemoteHandle<()>,
 
     connect_address: SocketAddr,
+    local_address: SocketAddr,
     config: HostConnectionConfig,
     features: ConnectionFeatures,
     router_handle: Arc<RouterHandle>,
+    stats: Arc<ConnectionStatsRecorder>,
+    // Held for the whole lifetime of a pooled connection if the session
+    // limits the total number of connections.
+    _budget_permit: Option<ConnectionPermit>,
     #[cfg(test)]
     socket: socket2::Socket,
 }
 
+/// Everything the router task needs apart from the stream it drives.
+struct RouterParams {
+    config: HostConnectionConfig,
+    receiver: mpsc::Receiver<Task>,
+    error_sender: tokio::sync::oneshot::Sender<ConnectionError>,
+    orphan_notification_receiver: mpsc::UnboundedReceiver<RequestId>,
+    router_handle: Arc<RouterHandle>,
+    stats: Arc<ConnectionStatsRecorder>,
+    node_address: SocketAddr,
+}
+
 struct RouterHandle {
     submit_channel: mpsc::Sender<Task>,
 
+    // Buffers for serializing requests are taken from this pool, if enabled.
+    frame_buffer_pool: Option<Arc<FrameBufferPool>>,
+
+    // Request bodies shorter than this are sent uncompressed.
+    compression_threshold: usize,
+
+    // Outcomes of requests are recorded here, if the circuit breaker is enabled.
+    // The router closes the connection once it trips.
+    error_rate_tracker: Option<ErrorRateTracker>,
+
     // Each request send by `Connection::send_request` needs a unique request id.
     // This field is a monotonic generator of such ids.
     request_id_generator: AtomicU64,
@@ -124,13 +164,51 @@ impl RouterHandle {
             .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
     }
 
+    /// Sends the request and waits for its response.
+    /// Returns the response along with the size of the sent request frame.
     async fn send_request(
         &self,
         request: &impl SerializableRequest,
         compression: Option<Compression>,
         tracing: bool,
-    ) -> Result<TaskResponse, InternalRequestError> {
-        let serialized_request = SerializedRequest::make(request, compression, tracing)?;
+    ) -> Result<(TaskResponse, usize), InternalRequestError> {
+        self.send_request_with_custom_payload(request, compression, tracing, None, None)
+            .await
+    }
+
+    /// Sends the request along with the custom payload, if any, and waits for its response.
+    /// If the request belongs to a stream id sequence, it gets a greater stream id than
+    /// the requests of the sequence submitted before.
+    /// Returns the response along with the size of the sent request frame.
+    async fn send_request_with_custom_payload(
+        &self,
+        request: &impl SerializableRequest,
+        compression: Option<Compression>,
+        tracing: bool,
+        custom_payload: Option<&HashMap<String, Bytes>>,
+        stream_id_sequence: Option<&Arc<StreamIdSequence>>,
+    ) -> Result<(TaskResponse, usize), InternalRequestError> {
+        let pool = self.frame_buffer_pool.as_deref();
+        let buffer = pool.map(FrameBufferPool::take).unwrap_or_default();
+        // The uncompressed body is serialized into a scratch buffer, if compression is enabled.
+        let mut compression_buffer = match compression {
+            Some(_) => pool.map(FrameBufferPool::take).unwrap_or_default(),
+            None => Vec::new(),
+        };
+        let serialized_request = SerializedRequest::make_in_with_custom_payload(
+            request,
+            compression,
+            self.compression_threshold,
+            tracing,
+            custom_payload,
+            buffer,
+            &mut compression_buffer,
+        );
+        if let Some(pool) = pool {
+            pool.give_back(compression_buffer);
+        }
+        let serialized_request = serialized_request?;
+        let request_frame_size = serialized_request.get_data().len();
         let request_id = self.allocate_request_id();
 
         let (response_sender, receiver) = oneshot::channel();
@@ -144,15 +222,22 @@ impl RouterHandle {
         // with this request as orphaned and free associated resources.
         let notifier = OrphanhoodNotifier::new(request_id, &self.orphan_notification_sender);
 
+        // Requests of a sequence must reach the writer in the order they are submitted in.
+        let submit_guard = match stream_id_sequence {
+            Some(sequence) => Some(sequence.submit_lock.lock().await),
+            None => None,
+        };
         self.submit_channel
             .send(Task {
                 serialized_request,
                 response_handler,
+                stream_id_sequence: stream_id_sequence.cloned(),
             })
             .await
             .map_err(|_| -> BrokenConnectionError {
                 BrokenConnectionErrorKind::ChannelError.into()
             })?;
+        drop(submit_guard);
 
         let task_response = receiver.await.map_err(|_| -> BrokenConnectionError {
             BrokenConnectionErrorKind::ChannelError.into()
@@ -162,7 +247,7 @@ impl RouterHandle {
         // notification about orphaning.
         notifier.disable();
 
-        task_response
+        task_response.map(|response| (response, request_frame_size))
     }
 }
 
@@ -171,6 +256,31 @@ pub(crate) struct ConnectionFeatures {
     shard_info: Option<ShardInfo>,
     shard_aware_port: Option<u16>,
     protocol_features: ProtocolFeatures,
+    server_options: ServerOptions,
+}
+
+/// Options advertised by a node in its SUPPORTED response,
+/// received while the connection was being established.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServerOptions {
+    cql_versions: Vec<String>,
+}
+
+impl ServerOptions {
+    fn parse_from_supported(supported: &HashMap<String, Vec<String>>) -> Self {
+        Self {
+            cql_versions: supported
+                .get(options::CQL_VERSION)
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// CQL versions supported by the node, as listed under
+    /// the `CQL_VERSION` key of the SUPPORTED response.
+    pub fn cql_versions(&self) -> &[String] {
+        &self.cql_versions
+    }
 }
 
 type RequestId = u64;
@@ -216,12 +326,37 @@ impl Drop for OrphanhoodNotifier<'_> {
 struct Task {
     serialized_request: SerializedRequest,
     response_handler: ResponseHandler,
+    stream_id_sequence: Option<Arc<StreamIdSequence>>,
+}
+
+/// Makes the requests sent on a connection as a sequence get increasing stream ids,
+/// in the order they were submitted in.
+///
+/// Once there is no free stream id greater than the last one of the sequence,
+/// the sequence starts over from the lowest free stream id.
+#[derive(Debug)]
+pub(crate) struct StreamIdSequence {
+    /// Held while a request of the sequence is submitted to the writer.
+    submit_lock: tokio::sync::Mutex<()>,
+    /// Stream id of the last request of the sequence, or -1. Only updated by the writer.
+    last_stream_id: AtomicI32,
+}
+
+impl StreamIdSequence {
+    pub(crate) fn new() -> Self {
+        Self {
+            submit_lock: tokio::sync::Mutex::new(()),
+            last_stream_id: AtomicI32::new(-1),
+        }
+    }
 }
 
 struct TaskResponse {
     params: FrameParams,
     opcode: ResponseOpcode,
     body: Bytes,
+    // Size of the whole response frame, as received (i.e. possibly compressed).
+    frame_size: usize,
 }
 
 impl<'id: 'map, 'map> SelfIdentity<'id> {
@@ -314,6 +449,7 @@ pub(crate) struct ConnectionConfig {
     pub(crate) local_ip_address: Option<IpAddr>,
     pub(crate) shard_aware_local_port_range: ShardAwarePortRange,
     pub(crate) compression: Option<Compression>,
+    pub(crate) compression_threshold: usize,
     pub(crate) tcp_socket_options: TcpSocketOptions,
     pub(crate) timestamp_generator: Option<Arc<dyn TimestampGenerator>>,
     pub(crate) tls_provider: Option<TlsProvider>,
@@ -324,9 +460,15 @@ pub(crate) struct ConnectionConfig {
     pub(crate) authenticator: Option<Arc<dyn AuthenticatorProvider>>,
     pub(crate) address_translator: Option<Arc<dyn AddressTranslator>>,
     pub(crate) write_coalescing_delay: Option<WriteCoalescingDelay>,
+    pub(crate) frame_buffer_pool: Option<Arc<FrameBufferPool>>,
+    pub(crate) read_buffer_size: usize,
+    pub(crate) circuit_breaker: Option<ErrorRateCircuitBreaker>,
+    pub(crate) warnings_log: Option<Arc<WarningsLog>>,
+    pub(crate) pass_through_unknown_result_kinds: bool,
 
     pub(crate) keepalive_interval: Option<Duration>,
     pub(crate) keepalive_timeout: Option<Duration>,
+    pub(crate) rtt_probe_interval: Option<Duration>,
     pub(crate) tablet_sender: Option<mpsc::Sender<(TableSpec<'static>, RawTablet)>>,
 
     pub(crate) identity: SelfIdentity<'static>,
@@ -347,6 +489,7 @@ impl ConnectionConfig {
             local_ip_address: self.local_ip_address,
             shard_aware_local_port_range: self.shard_aware_local_port_range.clone(),
             compression: self.compression,
+            compression_threshold: self.compression_threshold,
             tcp_socket_options: self.tcp_socket_options.clone(),
             timestamp_generator: self.timestamp_generator.clone(),
             tls_config,
@@ -356,8 +499,14 @@ impl ConnectionConfig {
             authenticator: self.authenticator.clone(),
             address_translator: self.address_translator.clone(),
             write_coalescing_delay: self.write_coalescing_delay.clone(),
+            frame_buffer_pool: self.frame_buffer_pool.clone(),
+            read_buffer_size: self.read_buffer_size,
+            circuit_breaker: self.circuit_breaker,
+            warnings_log: self.warnings_log.clone(),
+            pass_through_unknown_result_kinds: self.pass_through_unknown_result_kinds,
             keepalive_interval: self.keepalive_interval,
             keepalive_timeout: self.keepalive_timeout,
+            rtt_probe_interval: self.rtt_probe_interval,
             tablet_sender: self.tablet_sender.clone(),
             identity: self.identity.clone(),
         }
@@ -372,6 +521,7 @@ pub(crate) struct HostConnectionConfig {
     pub(crate) local_ip_address: Option<IpAddr>,
     pub(crate) shard_aware_local_port_range: ShardAwarePortRange,
     pub(crate) compression: Option<Compression>,
+    pub(crate) compression_threshold: usize,
     pub(crate) tcp_socket_options: TcpSocketOptions,
     pub(crate) timestamp_generator: Option<Arc<dyn TimestampGenerator>>,
     pub(crate) tls_config: Option<TlsConfig>,
@@ -382,9 +532,15 @@ pub(crate) struct HostConnectionConfig {
     pub(crate) authenticator: Option<Arc<dyn AuthenticatorProvider>>,
     pub(crate) address_translator: Option<Arc<dyn AddressTranslator>>,
     pub(crate) write_coalescing_delay: Option<WriteCoalescingDelay>,
+    pub(crate) frame_buffer_pool: Option<Arc<FrameBufferPool>>,
+    pub(crate) read_buffer_size: usize,
+    pub(crate) circuit_breaker: Option<ErrorRateCircuitBreaker>,
+    pub(crate) warnings_log: Option<Arc<WarningsLog>>,
+    pub(crate) pass_through_unknown_result_kinds: bool,
 
     pub(crate) keepalive_interval: Option<Duration>,
     pub(crate) keepalive_timeout: Option<Duration>,
+    pub(crate) rtt_probe_interval: Option<Duration>,
     pub(crate) tablet_sender: Option<mpsc::Sender<(TableSpec<'static>, RawTablet)>>,
 
     pub(crate) identity: SelfIdentity<'static>,
@@ -397,6 +553,7 @@ impl Default for HostConnectionConfig {
             local_ip_address: None,
             shard_aware_local_port_range: ShardAwarePortRange::EPHEMERAL_PORT_RANGE,
             compression: None,
+            compression_threshold: 0,
             tcp_socket_options: TcpSocketOptions::default(),
             timestamp_generator: None,
             event_sender: None,
@@ -406,10 +563,16 @@ impl Default for HostConnectionConfig {
             authenticator: None,
             address_translator: None,
             write_coalescing_delay: Some(WriteCoalescingDelay::SmallNondeterministic),
+            frame_buffer_pool: None,
+            read_buffer_size: crate::client::session::DEFAULT_READ_BUFFER_SIZE,
+            circuit_breaker: None,
+            warnings_log: None,
+            pass_through_unknown_result_kinds: false,
 
             // Note: this is different than SessionConfig default values.
             keepalive_interval: None,
             keepalive_timeout: None,
+            rtt_probe_interval: None,
 
             tablet_sender: None,
 
@@ -425,6 +588,7 @@ impl Default for ConnectionConfig {
             local_ip_address: None,
             shard_aware_local_port_range: ShardAwarePortRange::EPHEMERAL_PORT_RANGE,
             compression: None,
+            compression_threshold: 0,
             tcp_socket_options: TcpSocketOptions::default(),
             timestamp_generator: None,
             event_sender: None,
@@ -434,10 +598,16 @@ impl Default for ConnectionConfig {
             authenticator: None,
             address_translator: None,
             write_coalescing_delay: Some(WriteCoalescingDelay::SmallNondeterministic),
+            frame_buffer_pool: None,
+            read_buffer_size: crate::client::session::DEFAULT_READ_BUFFER_SIZE,
+            circuit_breaker: None,
+            warnings_log: None,
+            pass_through_unknown_result_kinds: false,
 
             // Note: this is different than SessionConfig default values.
             keepalive_interval: None,
             keepalive_timeout: None,
+            rtt_probe_interval: None,
 
             tablet_sender: None,
 
@@ -486,6 +656,7 @@ impl Connection {
                 return Err(ConnectionError::ConnectTimeout);
             }
         };
+        let local_address = stream.local_addr()?;
 
         // TODO: What should be the size of the channel?
         let (sender, receiver) = mpsc::channel(1024);
@@ -495,10 +666,15 @@ impl Connection {
 
         let router_handle = Arc::new(RouterHandle {
             submit_channel: sender,
+            frame_buffer_pool: config.frame_buffer_pool.clone(),
+            compression_threshold: config.compression_threshold,
+            error_rate_tracker: config.circuit_breaker.map(ErrorRateTracker::new),
             request_id_generator: AtomicU64::new(0),
             orphan_notification_sender,
         });
 
+        let stats = Arc::new(ConnectionStatsRecorder::default());
+
         #[cfg(test)]
         let socket = {
             use std::os::unix::io::AsFd;
@@ -506,13 +682,16 @@ impl Connection {
         };
 
         let _worker_handle = Self::run_router(
-            config.clone(),
+            RouterParams {
+                config: config.clone(),
+                receiver,
+                error_sender,
+                orphan_notification_receiver,
+                router_handle: router_handle.clone(),
+                stats: stats.clone(),
+                node_address: connect_address,
+            },
             stream,
-            receiver,
-            error_sender,
-            orphan_notification_receiver,
-            router_handle.clone(),
-            connect_address,
         )
         .await?;
 
@@ -521,7 +700,10 @@ impl Connection {
             config,
             features: Default::default(),
             connect_address,
+            local_address,
             router_handle,
+            stats,
+            _budget_permit: None,
             #[cfg(test)]
             socket,
         };
@@ -682,13 +864,55 @@ impl Connection {
         Ok(prepared_statement)
     }
 
+    /// Prepares the statement in the given keyspace, on a new connection to the same node
+    /// which uses that keyspace. The new connection is closed once the statement is prepared.
+    ///
+    /// Protocol v4 can't send a keyspace along with a PREPARE request, and switching this
+    /// connection to another keyspace would affect requests sent concurrently on it.
+    pub(crate) async fn prepare_raw_in_keyspace<'statement>(
+        &self,
+        statement: &'statement Statement,
+        keyspace: &VerifiedKeyspaceName,
+    ) -> Result<RawPreparedStatement<'statement>, RequestAttemptError> {
+        // The connect address is already translated, so it is used as a contact point.
+        let endpoint = UntranslatedEndpoint::ContactPoint(ResolvedContactPoint {
+            address: self.connect_address,
+        });
+        // Opening a connection is a large future, which is boxed so that it doesn't bloat
+        // the futures of all requests that may need to reprepare a statement.
+        let (connection, _error_receiver) =
+            Box::pin(open_connection(&endpoint, None, &self.config))
+                .await
+                .map_err(|err| {
+                    RequestAttemptError::BrokenConnectionError(
+                        BrokenConnectionError::from_connection_error(err),
+                    )
+                })?;
+        connection
+            .use_keyspace(keyspace)
+            .await
+            .map_err(|err| match err {
+                UseKeyspaceError::RequestError(err) => err,
+                // The keyspace name is already verified and no request timeout is set,
+                // so the server must have switched to another keyspace.
+                _ => RequestAttemptError::UnexpectedResponse(CqlResponseKind::Result),
+            })?;
+        connection.prepare_raw(statement).await
+    }
+
     async fn reprepare(
         &self,
         query: impl Into<Statement>,
         previous_prepared: &PreparedStatement,
     ) -> Result<(), RequestAttemptError> {
         let reprepare_query: Statement = query.into();
-        let raw_prepared = self.prepare_raw(&reprepare_query).await?;
+        let raw_prepared = match previous_prepared.get_reprepare_keyspace() {
+            Some(keyspace) => {
+                self.prepare_raw_in_keyspace(&reprepare_query, keyspace)
+                    .await?
+            }
+            None => self.prepare_raw(&reprepare_query).await?,
+        };
 
         // Reprepared statement should keep its id - it's the md5 sum
         // of statement contents
@@ -899,7 +1123,14 @@ impl Connection {
         };
 
         let response = self
-            .send_request(&query_frame, true, statement.config.tracing, None)
+            .send_request_with_custom_payload(
+                &query_frame,
+                true,
+                statement.config.tracing,
+                statement.config.custom_payload(),
+                None,
+                None,
+            )
             .await?;
 
         Ok(response)
@@ -1041,6 +1272,54 @@ impl Connection {
         serial_consistency: Option<SerialConsistency>,
         page_size: Option<PageSize>,
         paging_state: PagingState,
+    ) -> Result<QueryResponse, RequestAttemptError> {
+        self.execute_raw_in_optional_sequence(
+            prepared_statement,
+            values,
+            consistency,
+            serial_consistency,
+            page_size,
+            paging_state,
+            None,
+        )
+        .await
+    }
+
+    /// Executes a prepared statement without paging, as the next request of the sequence,
+    /// so that it gets a greater stream id than the requests of the sequence submitted before.
+    ///
+    /// If the statement has to be reprepared, it is executed again as the next request
+    /// of the sequence after the repreparation.
+    pub(crate) async fn execute_raw_in_sequence(
+        &self,
+        prepared_statement: &PreparedStatement,
+        values: &SerializedValues,
+        consistency: Consistency,
+        serial_consistency: Option<SerialConsistency>,
+        stream_id_sequence: &Arc<StreamIdSequence>,
+    ) -> Result<QueryResponse, RequestAttemptError> {
+        self.execute_raw_in_optional_sequence(
+            prepared_statement,
+            values,
+            consistency,
+            serial_consistency,
+            None,
+            PagingState::start(),
+            Some(stream_id_sequence),
+        )
+        .await
+    }
+
+    #[expect(clippy::too_many_arguments)]
+    async fn execute_raw_in_optional_sequence(
+        &self,
+        prepared_statement: &PreparedStatement,
+        values: &SerializedValues,
+        consistency: Consistency,
+        serial_consistency: Option<SerialConsistency>,
+        page_size: Option<PageSize>,
+        paging_state: PagingState,
+        stream_id_sequence: Option<&Arc<StreamIdSequence>>,
     ) -> Result<QueryResponse, RequestAttemptError> {
         let get_timestamp_from_gen = || {
             self.config
@@ -1070,12 +1349,15 @@ impl Connection {
             },
         };
 
+        let custom_payload = prepared_statement.config.custom_payload();
         let query_response = self
-            .send_request(
+            .send_request_with_custom_payload(
                 &execute_frame,
                 true,
                 prepared_statement.config.tracing,
+                custom_payload,
                 cached_metadata_params.cached_metadata,
+                stream_id_sequence,
             )
             .await?;
 
@@ -1106,7 +1388,7 @@ impl Connection {
                 let cached_metadata_params = self
                     .calculate_cached_metadata_params(prepared_statement, &current_result_metadata);
                 let new_response = self
-                    .send_request(
+                    .send_request_with_custom_payload(
                         &execute::ExecuteV2 {
                             result_metadata_id: cached_metadata_params
                                 .result_metadata_id
@@ -1119,7 +1401,9 @@ impl Connection {
                         },
                         true,
                         prepared_statement.config.tracing,
+                        custom_payload,
                         cached_metadata_params.cached_metadata,
+                        stream_id_sequence,
                     )
                     .await?;
 
@@ -1137,6 +1421,109 @@ impl Connection {
         }
     }
 
+    /// Executes a prepared statement without paging, leaving the body of a successful
+    /// response exactly as received - in particular, not decompressed.
+    ///
+    /// Error responses are parsed as usual, so that they can be handled by the retry policy,
+    /// and `Unprepared` errors trigger repreparation, like in [`Self::execute_raw_with_consistency`].
+    /// As the body of a successful response is not parsed, tablet information and new result
+    /// metadata ids that it carries are not taken into account.
+    #[cfg(all(scylla_unstable, feature = "unstable-csharp-rs"))]
+    pub(crate) async fn execute_with_raw_response(
+        &self,
+        prepared_statement: &PreparedStatement,
+        values: &SerializedValues,
+        consistency: Consistency,
+        serial_consistency: Option<SerialConsistency>,
+    ) -> Result<RawResponse, RequestAttemptError> {
+        let timestamp = prepared_statement.get_timestamp().or_else(|| {
+            self.config
+                .timestamp_generator
+                .as_ref()
+                .map(|generator| generator.next_timestamp())
+        });
+
+        let mut reprepared = false;
+        loop {
+            let current_result_metadata = prepared_statement.get_current_result_metadata();
+            let cached_metadata_params =
+                self.calculate_cached_metadata_params(prepared_statement, &current_result_metadata);
+
+            let execute_frame = execute::ExecuteV2 {
+                id: prepared_statement.get_id().as_ref().into(),
+                result_metadata_id: cached_metadata_params.result_metadata_id.map(Into::into),
+                parameters: query::QueryParameters {
+                    consistency,
+                    serial_consistency,
+                    values: Cow::Borrowed(values),
+                    page_size: None,
+                    timestamp,
+                    // The response is not parsed by the driver, so it must be self-contained.
+                    skip_metadata: false,
+                    paging_state: PagingState::start(),
+                },
+            };
+
+            let (task_response, request_frame_size) = self
+                .router_handle
+                .send_request_with_custom_payload(
+                    &execute_frame,
+                    self.config.compression,
+                    prepared_statement.config.tracing,
+                    prepared_statement.config.custom_payload(),
+                    None,
+                )
+                .await?;
+
+            if task_response.opcode != ResponseOpcode::Error {
+                let flags = task_response.params.flags;
+                let compression = if flags & frame::flag::COMPRESSION != 0 {
+                    Some(self.config.compression.ok_or(
+                        RequestAttemptError::BodyExtensionsParseError(
+                            FrameBodyExtensionsParseError::NoCompressionNegotiated,
+                        ),
+                    )?)
+                } else {
+                    None
+                };
+                return Ok(RawResponse::new(flags, compression, task_response.body));
+            }
+
+            let query_response = Self::parse_response(
+                task_response,
+                request_frame_size,
+                self.config.compression,
+                &self.features.protocol_features,
+                self.config.pass_through_unknown_result_kinds,
+                None,
+                self.config.frame_buffer_pool.as_deref(),
+            )
+            .map_err(InternalRequestError::from)?;
+            self.record_warnings(&query_response);
+
+            match query_response.response {
+                ResponseWithDeserializedMetadata::Error(Error {
+                    error: DbError::Unprepared { statement_id },
+                    ..
+                }) if !reprepared => {
+                    debug!(
+                        "Connection::execute_with_raw_response: Got DbError::Unprepared - repreparing statement with id {:?}",
+                        statement_id
+                    );
+                    self.reprepare(prepared_statement.get_statement(), prepared_statement)
+                        .await?;
+                    reprepared = true;
+                }
+                ResponseWithDeserializedMetadata::Error(err) => return Err(err.into()),
+                response => {
+                    return Err(RequestAttemptError::UnexpectedResponse(
+                        response.to_response_kind(),
+                    ));
+                }
+            }
+        }
+    }
+
     /// Executes a prepared statements and fetches its results over multiple pages, using
     /// the asynchronous iterator interface.
     ///
@@ -1199,9 +1586,17 @@ impl Connection {
             timestamp,
         };
 
+        let custom_payload = batch.config.custom_payload();
         loop {
             let query_response = self
-                .send_request(&batch_frame, true, batch.config.tracing, None)
+                .send_request_with_custom_payload(
+                    &batch_frame,
+                    true,
+                    batch.config.tracing,
+                    custom_payload,
+                    None,
+                    None,
+                )
                 .await
                 .map_err(RequestAttemptError::from)?;
 
@@ -1378,38 +1773,125 @@ impl Connection {
         tracing: bool,
         cached_metadata: Option<&Arc<ResultMetadata<'static>>>,
     ) -> Result<QueryResponse, InternalRequestError> {
+        self.send_request_with_custom_payload(
+            request,
+            compress,
+            tracing,
+            None,
+            cached_metadata,
+            None,
+        )
+        .await
+    }
+
+    async fn send_request_with_custom_payload(
+        &self,
+        request: &impl SerializableRequest,
+        compress: bool,
+        tracing: bool,
+        custom_payload: Option<&HashMap<String, Bytes>>,
+        cached_metadata: Option<&Arc<ResultMetadata<'static>>>,
+        stream_id_sequence: Option<&Arc<StreamIdSequence>>,
+    ) -> Result<QueryResponse, InternalRequestError> {
+        let _in_flight = self.stats.start_request();
         let compression = if compress {
             self.config.compression
         } else {
             None
         };
 
-        let task_response = self
+        let response = self
             .router_handle
-            .send_request(request, compression, tracing)
-            .await?;
+            .send_request_with_custom_payload(
+                request,
+                compression,
+                tracing,
+                custom_payload,
+                stream_id_sequence,
+            )
+            .await
+            .and_then(|(task_response, request_frame_size)| {
+                Self::parse_response(
+                    task_response,
+                    request_frame_size,
+                    self.config.compression,
+                    &self.features.protocol_features,
+                    self.config.pass_through_unknown_result_kinds,
+                    cached_metadata,
+                    self.config.frame_buffer_pool.as_deref(),
+                )
+                .map_err(InternalRequestError::from)
+            });
 
-        let response = Self::parse_response(
-            task_response,
-            self.config.compression,
-            &self.features.protocol_features,
-            cached_metadata,
-        )?;
+        if let Ok(response) = &response {
+            self.record_warnings(response);
+        }
 
-        Ok(response)
+        if let Some(tracker) = &self.router_handle.error_rate_tracker {
+            tracker.record(Self::indicates_faulty_connection(&response));
+        }
+
+        response
+    }
+
+    /// Tells whether the outcome of a request suggests that something is wrong with
+    /// the connection, rather than with the request or the load of the node.
+    ///
+    /// Only connection-level failures and timeouts are counted. Errors such as `Overloaded`
+    /// or `IsBootstrapping` describe the whole node, so replacing the connection would not help.
+    fn indicates_faulty_connection(response: &Result<QueryResponse, InternalRequestError>) -> bool {
+        match response {
+            Ok(response) => matches!(
+                &response.response,
+                ResponseWithDeserializedMetadata::Error(Error {
+                    error: DbError::ReadTimeout { .. } | DbError::WriteTimeout { .. },
+                    ..
+                })
+            ),
+            // The request itself could not be serialized, which says nothing about the connection.
+            Err(InternalRequestError::CqlRequestSerialization(_)) => false,
+            Err(
+                InternalRequestError::BodyExtensionsParseError(_)
+                | InternalRequestError::CqlResponseParseError(_)
+                | InternalRequestError::BrokenConnection(_)
+                | InternalRequestError::UnableToAllocStreamId,
+            ) => true,
+        }
+    }
+
+    /// Records the warnings of the response in the session-wide log, if enabled.
+    fn record_warnings(&self, response: &QueryResponse) {
+        if let Some(warnings_log) = &self.config.warnings_log {
+            warnings_log.record(self.connect_address, &response.warnings);
+        }
     }
 
     fn parse_response(
         task_response: TaskResponse,
+        request_frame_size: usize,
         compression: Option<Compression>,
         features: &ProtocolFeatures,
+        pass_through_unknown_result_kinds: bool,
         cached_metadata: Option<&Arc<ResultMetadata<'static>>>,
+        frame_buffer_pool: Option<&FrameBufferPool>,
     ) -> Result<QueryResponse, ResponseParseError> {
+        let response_frame_size = task_response.frame_size;
+        let response_flags = task_response.params.flags;
+        // A compressed body is no longer referenced once it's decompressed,
+        // so its buffer can be given back to the pool.
+        let compressed_body = (response_flags & flag::COMPRESSION != 0)
+            .then(|| frame_buffer_pool.map(|pool| (pool, task_response.body.clone())))
+            .flatten();
         let body_with_ext = frame::parse_response_body_extensions(
             task_response.params.flags,
             compression,
             task_response.body,
         )?;
+        if let Some((pool, compressed_body)) = compressed_body
+            && let Ok(compressed_body) = compressed_body.try_into_mut()
+        {
+            pool.give_back(compressed_body.into());
+        }
 
         for warn_description in &body_with_ext.warnings {
             warn!(
@@ -1418,14 +1900,26 @@ impl Connection {
             );
         }
 
-        let response = Response::deserialize(
+        let body = body_with_ext.body;
+        let (response, unknown_result) = match Response::deserialize(
             features,
             task_response.opcode,
-            body_with_ext.body,
+            body.clone(),
             cached_metadata,
-        )?
-        .deserialize_metadata()
-        .map_err(|e| {
+        ) {
+            Err(CqlResponseParseError::CqlResultParseError(
+                CqlResultParseError::UnknownResultId(kind),
+            )) if pass_through_unknown_result_kinds => {
+                // The body starts with the kind, which is an [int].
+                let unknown_result = UnknownResult {
+                    kind,
+                    body: body.slice(4..),
+                };
+                (Response::Result(result::Result::Void), Some(unknown_result))
+            }
+            response => (response?, None),
+        };
+        let response = response.deserialize_metadata().map_err(|e| {
             ResponseParseError::CqlResponseParseError(CqlResponseParseError::CqlResultParseError(
                 e.into(),
             ))
@@ -1436,49 +1930,34 @@ impl Connection {
             warnings: body_with_ext.warnings,
             tracing_id: body_with_ext.trace_id,
             custom_payload: body_with_ext.custom_payload,
+            bytes_sent: request_frame_size,
+            bytes_received: response_frame_size,
+            response_flags,
+            unknown_result,
         })
     }
 
     async fn run_router(
-        config: HostConnectionConfig,
+        params: RouterParams,
         stream: TcpStream,
-        receiver: mpsc::Receiver<Task>,
-        error_sender: tokio::sync::oneshot::Sender<ConnectionError>,
-        orphan_notification_receiver: mpsc::UnboundedReceiver<RequestId>,
-        router_handle: Arc<RouterHandle>,
-        node_address: SocketAddr,
     ) -> Result<RemoteHandle<()>, std::io::Error> {
         async fn spawn_router_and_get_handle(
-            config: HostConnectionConfig,
+            params: RouterParams,
             stream: impl AsyncRead + AsyncWrite + Send + 'static,
-            receiver: mpsc::Receiver<Task>,
-            error_sender: tokio::sync::oneshot::Sender<ConnectionError>,
-            orphan_notification_receiver: mpsc::UnboundedReceiver<RequestId>,
-            router_handle: Arc<RouterHandle>,
-            node_address: SocketAddr,
         ) -> RemoteHandle<()> {
-            let (task, handle) = Connection::router(
-                config,
-                stream,
-                receiver,
-                error_sender,
-                orphan_notification_receiver,
-                router_handle,
-                node_address,
-            )
-            .remote_handle();
+            let (task, handle) = Connection::router(params, stream).remote_handle();
             tokio::task::spawn(task);
             handle
         }
 
-        if let Some(tls_config) = &config.tls_config {
+        if let Some(tls_config) = &params.config.tls_config {
             // To silence warnings when TlsContext is an empty enum (tls features are disabled).
             #[allow(unreachable_code)]
             match tls_config.new_tls()? {
                 #[cfg(feature = "openssl-010")]
                 crate::network::tls::Tls::OpenSsl010(mut ssl) => {
                     ssl.param_mut()
-                        .set_ip(node_address.ip())
+                        .set_ip(params.node_address.ip())
                         .map_err(crate::network::tls::TlsError::OpenSsl010)?;
                     let mut stream = tokio_openssl::SslStream::new(ssl, stream)
                         .map_err(crate::network::tls::TlsError::OpenSsl010)?;
@@ -1486,57 +1965,31 @@ impl Connection {
                         .connect()
                         .await
                         .map_err(std::io::Error::other)?;
-                    return Ok(spawn_router_and_get_handle(
-                        config,
-                        stream,
-                        receiver,
-                        error_sender,
-                        orphan_notification_receiver,
-                        router_handle,
-                        node_address,
-                    )
-                    .await);
+                    return Ok(spawn_router_and_get_handle(params, stream).await);
                 }
                 #[cfg(feature = "rustls-023")]
                 crate::network::tls::Tls::Rustls023 { connector } => {
                     use rustls::pki_types::ServerName;
-                    let server_name = ServerName::IpAddress(node_address.ip().into());
+                    let server_name = ServerName::IpAddress(params.node_address.ip().into());
                     let stream = connector.connect(server_name, stream).await?;
-                    return Ok(spawn_router_and_get_handle(
-                        config,
-                        stream,
-                        receiver,
-                        error_sender,
-                        orphan_notification_receiver,
-                        router_handle,
-                        node_address,
-                    )
-                    .await);
+                    return Ok(spawn_router_and_get_handle(params, stream).await);
                 }
             }
         }
 
-        Ok(spawn_router_and_get_handle(
+        Ok(spawn_router_and_get_handle(params, stream).await)
+    }
+
+    async fn router(params: RouterParams, stream: impl AsyncRead + AsyncWrite) {
+        let RouterParams {
             config,
-            stream,
             receiver,
             error_sender,
             orphan_notification_receiver,
             router_handle,
+            stats,
             node_address,
-        )
-        .await)
-    }
-
-    async fn router(
-        config: HostConnectionConfig,
-        stream: impl AsyncRead + AsyncWrite,
-        receiver: mpsc::Receiver<Task>,
-        error_sender: tokio::sync::oneshot::Sender<ConnectionError>,
-        orphan_notification_receiver: mpsc::UnboundedReceiver<RequestId>,
-        router_handle: Arc<RouterHandle>,
-        node_address: SocketAddr,
-    ) {
+        } = params;
         let (read_half, write_half) = split(stream);
         // Why are we using a mutex here?
         //
@@ -1553,29 +2006,40 @@ impl Connection {
         let handler_map = StdMutex::new(ResponseHandlerMap::new());
 
         let write_coalescing_delay = config.write_coalescing_delay;
+        let frame_buffer_pool = config.frame_buffer_pool.clone();
 
         let k = Self::keepaliver(
-            router_handle,
+            router_handle.clone(),
             config.keepalive_interval,
             config.keepalive_timeout,
             node_address,
         );
+        let b = Self::circuit_breaker(router_handle.clone(), node_address);
+        let p = Self::rtt_prober(
+            router_handle,
+            stats,
+            config.rtt_probe_interval,
+            config.connect_timeout,
+            node_address,
+        );
 
         let r = Self::reader(
-            BufReader::with_capacity(8192, read_half),
+            BufReader::with_capacity(config.read_buffer_size, read_half),
             &handler_map,
             config.event_sender.map(|(sender, _)| sender),
             config.compression,
+            frame_buffer_pool.as_deref(),
         );
         let w = Self::writer(
             BufWriter::with_capacity(8192, write_half),
             &handler_map,
             receiver,
             write_coalescing_delay,
+            frame_buffer_pool.as_deref(),
         );
         let o = Self::orphaner(&handler_map, orphan_notification_receiver);
 
-        let result = futures::try_join!(r, w, o, k);
+        let result = futures::try_join!(r, w, o, k, p, b);
 
         let error: BrokenConnectionError = match result {
             Ok(_) => return, // Connection was dropped, we can return
@@ -1600,14 +2064,24 @@ impl Connection {
         handler_map: &StdMutex<ResponseHandlerMap>,
         event_sender: Option<mpsc::Sender<Event>>,
         compression: Option<Compression>,
+        frame_buffer_pool: Option<&FrameBufferPool>,
     ) -> Result<(), BrokenConnectionError> {
+        // Compressed responses are read into pooled buffers, which are given back
+        // once the responses are decompressed (see `Connection::parse_response`).
+        let body_buffer = |params: &FrameParams| match frame_buffer_pool {
+            Some(pool) if params.stream >= 0 && params.flags & flag::COMPRESSION != 0 => {
+                pool.take()
+            }
+            _ => Vec::new(),
+        };
         loop {
-            let (params, opcode, body) = frame::read_response_frame(&mut read_half)
+            let (params, opcode, body) = frame::read_response_frame_in(&mut read_half, body_buffer)
                 .await
                 .map_err(BrokenConnectionErrorKind::FrameHeaderParseError)?;
             let response = TaskResponse {
                 params,
                 opcode,
+                frame_size: FRAME_HEADER_SIZE + body.len(),
                 body,
             };
 
@@ -1664,11 +2138,28 @@ impl Connection {
     fn alloc_stream_id(
         handler_map: &StdMutex<ResponseHandlerMap>,
         response_handler: ResponseHandler,
+        stream_id_sequence: Option<&StreamIdSequence>,
     ) -> Option<i16> {
         // We are guaranteed here that handler_map will not be locked
         // by anybody else, so we can do try_lock().unwrap()
         let mut handler_map_guard = handler_map.try_lock().unwrap();
-        match handler_map_guard.allocate(response_handler) {
+        let allocated = match stream_id_sequence {
+            Some(sequence) => {
+                let last_stream_id = sequence
+                    .last_stream_id
+                    .load(std::sync::atomic::Ordering::Relaxed);
+                handler_map_guard
+                    .allocate_after(response_handler, last_stream_id)
+                    .or_else(|response_handler| handler_map_guard.allocate(response_handler))
+                    .inspect(|&stream_id| {
+                        sequence
+                            .last_stream_id
+                            .store(stream_id.into(), std::sync::atomic::Ordering::Relaxed)
+                    })
+            }
+            None => handler_map_guard.allocate(response_handler),
+        };
+        match allocated {
             Ok(stream_id) => Some(stream_id),
             Err(response_handler) => {
                 error!("Could not allocate stream id");
@@ -1685,6 +2176,7 @@ impl Connection {
         handler_map: &StdMutex<ResponseHandlerMap>,
         mut task_receiver: mpsc::Receiver<Task>,
         write_coalescing_delay: Option<WriteCoalescingDelay>,
+        frame_buffer_pool: Option<&FrameBufferPool>,
     ) -> Result<(), BrokenConnectionError> {
         // When the Connection object is dropped, the sender half
         // of the channel will be dropped, this task will return an error
@@ -1692,7 +2184,11 @@ impl Connection {
         while let Some(mut task) = task_receiver.recv().await {
             let mut num_requests = 0;
             let mut total_sent = 0;
-            while let Some(stream_id) = Self::alloc_stream_id(handler_map, task.response_handler) {
+            while let Some(stream_id) = Self::alloc_stream_id(
+                handler_map,
+                task.response_handler,
+                task.stream_id_sequence.as_deref(),
+            ) {
                 let mut req = task.serialized_request;
                 req.set_stream(stream_id);
                 let req_data: &[u8] = req.get_data();
@@ -1702,6 +2198,9 @@ impl Connection {
                     .write_all(req_data)
                     .await
                     .map_err(BrokenConnectionErrorKind::WriteError)?;
+                if let Some(pool) = frame_buffer_pool {
+                    pool.give_back(req.into_buffer());
+                }
                 task = match task_receiver.try_recv() {
                     Ok(t) => t,
                     Err(_) => match write_coalescing_delay {
@@ -1840,6 +2339,68 @@ impl Connection {
         }
     }
 
+    async fn rtt_prober(
+        router_handle: Arc<RouterHandle>,
+        stats: Arc<ConnectionStatsRecorder>,
+        probe_interval: Option<Duration>,
+        probe_timeout: Duration,
+        node_address: SocketAddr, // This address is only used to enrich the log messages
+    ) -> Result<(), BrokenConnectionError> {
+        if let Some(probe_interval) = probe_interval {
+            let mut interval = tokio::time::interval(probe_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                // The first tick is instant, so that RTT is known soon after the connection is opened.
+                interval.tick().await;
+
+                let start = Instant::now();
+                let probe = router_handle.send_request(&Options, None, false);
+                match tokio::time::timeout(probe_timeout, probe).await {
+                    Ok(Ok(_)) => {
+                        let rtt = start.elapsed();
+                        stats.record_rtt(rtt);
+                        trace!(
+                            "RTT probe on connection to node {} took {:?}",
+                            node_address, rtt
+                        );
+                    }
+                    // Broken connections are detected and reported by the other router futures,
+                    // so a failed probe only leaves the last measurement in place.
+                    Ok(Err(err)) => debug!(
+                        "Failed to execute RTT probe on connection to node {} - {}",
+                        node_address, err
+                    ),
+                    Err(_) => debug!(
+                        "RTT probe on connection to node {} timed out after {:?}",
+                        node_address, probe_timeout
+                    ),
+                }
+            }
+        } else {
+            // RTT is not measured.
+            Ok(())
+        }
+    }
+
+    async fn circuit_breaker(
+        router_handle: Arc<RouterHandle>,
+        node_address: SocketAddr, // This address is only used to enrich the log messages
+    ) -> Result<(), BrokenConnectionError> {
+        if let Some(tracker) = &router_handle.error_rate_tracker {
+            tracker.tripped().await;
+            let max_error_rate = tracker.config().max_error_rate();
+            warn!(
+                "Error rate on connection to node {} exceeded {}, closing the connection",
+                node_address, max_error_rate
+            );
+            Err(BrokenConnectionErrorKind::ErrorRateExceeded(max_error_rate).into())
+        } else {
+            // The circuit breaker is disabled.
+            Ok(())
+        }
+    }
+
     async fn handle_event(
         task_response: TaskResponse,
         compression: Option<Compression>,
@@ -1856,7 +2417,10 @@ impl Connection {
         // future implementers.
         let features = ProtocolFeatures::default(); // TODO: Use the right features
 
-        let event = match Self::parse_response(task_response, compression, &features, None) {
+        // Events are not responses to any request, so nothing was sent for them.
+        let parsed =
+            Self::parse_response(task_response, 0, compression, &features, false, None, None);
+        let event = match parsed {
             Ok(r) => match r.response {
                 ResponseWithDeserializedMetadata::Event(event) => event,
                 _ => {
@@ -1894,6 +2458,14 @@ impl Connection {
         self.features.shard_aware_port
     }
 
+    pub(crate) fn get_server_options(&self) -> &ServerOptions {
+        &self.features.server_options
+    }
+
+    pub(crate) fn set_budget_permit(&mut self, permit: ConnectionPermit) {
+        self._budget_permit = Some(permit);
+    }
+
     fn set_features(&mut self, features: ConnectionFeatures) {
         self.features = features;
     }
@@ -1902,6 +2474,28 @@ impl Connection {
         self.connect_address
     }
 
+    pub(crate) fn get_local_address(&self) -> SocketAddr {
+        self.local_address
+    }
+
+    pub(crate) fn get_stats(&self) -> ConnectionStats {
+        ConnectionStats {
+            node_address: self.connect_address,
+            local_address: self.local_address,
+            shard: self
+                .get_shard_info()
+                .as_ref()
+                .map(|info| Shard::from(info.shard)),
+            rtt: self.stats.last_rtt(),
+            in_flight_requests: self.stats.in_flight_requests(),
+            compression: self.config.compression,
+        }
+    }
+
+    pub(crate) fn in_flight_requests(&self) -> usize {
+        self.stats.in_flight_requests()
+    }
+
     async fn update_tablets_from_response(
         &self,
         table: &TableSpec<'_>,
@@ -2047,6 +2641,7 @@ pub(crate) async fn open_connection(
 
     // Parse nonstandard protocol extensions.
     let protocol_features = ProtocolFeatures::parse_from_supported(&supported.options);
+    let server_options = ServerOptions::parse_from_supported(&supported.options);
 
     // At the beginning, Connection assumes no sharding and no protocol extensions;
     // now that we know them, let's turn them on in the driver.
@@ -2054,6 +2649,7 @@ pub(crate) async fn open_connection(
         shard_info,
         shard_aware_port,
         protocol_features,
+        server_options,
     };
     connection.set_features(features);
 
@@ -2303,7 +2899,16 @@ impl ResponseHandlerMap {
     }
 
     fn allocate(&mut self, response_handler: ResponseHandler) -> Result<i16, ResponseHandler> {
-        if let Some(stream_id) = self.stream_set.allocate() {
+        self.allocate_after(response_handler, -1)
+    }
+
+    /// Allocates the lowest free stream id greater than `after`.
+    fn allocate_after(
+        &mut self,
+        response_handler: ResponseHandler,
+        after: i32,
+    ) -> Result<i16, ResponseHandler> {
+        if let Some(stream_id) = self.stream_set.allocate_after(after) {
             self.request_to_stream
                 .insert(response_handler.request_id, stream_id);
             let prev_handler = self.handlers.insert(stream_id, response_handler);
@@ -2375,10 +2980,17 @@ impl StreamIdSet {
         }
     }
 
-    fn allocate(&mut self) -> Option<i16> {
-        for (block_id, block) in self.used_bitmap.iter_mut().enumerate() {
-            if *block != !0 {
-                let off = block.trailing_ones();
+    /// Allocates the lowest free stream id greater than `after`, which may be -1.
+    fn allocate_after(&mut self, after: i32) -> Option<i16> {
+        let first = (after + 1) as usize;
+        for (block_id, block) in self.used_bitmap.iter_mut().enumerate().skip(first / 64) {
+            // Stream ids not greater than `after` are treated as used.
+            let mut used = *block;
+            if block_id == first / 64 {
+                used |= (1u64 << (first % 64)) - 1;
+            }
+            if used != !0 {
+                let off = used.trailing_ones();
                 *block |= 1u64 << off;
                 let stream_id = off as i16 + block_id as i16 * 64;
                 return Some(stream_id);
@@ -2460,6 +3072,7 @@ mod tests {
     use crate::frame::protocol_features::{
         LWT_OPTIMIZATION_META_BIT_MASK_KEY, SCYLLA_LWT_ADD_METADATA_MARK_EXTENSION,
     };
+    use crate::frame::response::ResponseOpcode;
     use crate::frame::types;
     use crate::serialize::row::SerializedValues;
     use assert_matches::assert_matches;
@@ -2468,19 +3081,26 @@ mod tests {
         RequestRule, ResponseFrame, ShardAwareness,
     };
 
+    use tokio::io::{AsyncRead, BufReader, ReadBuf};
     use tokio::select;
-    use tokio::sync::mpsc;
+    use tokio::sync::{mpsc, oneshot};
 
-    use super::{HostConnectionConfig, open_connection};
+    use super::{
+        Connection, HostConnectionConfig, ResponseHandler, ResponseHandlerMap, StreamIdSet,
+        open_connection,
+    };
     use crate::cluster::metadata::UntranslatedEndpoint;
     use crate::cluster::node::ResolvedContactPoint;
     use crate::statement::unprepared::Statement;
-    use crate::test_utils::setup_tracing;
+    use crate::test_utils::{ipv6_loopback_available, setup_tracing};
     use crate::utils::test_utils::{PerformDDL, resolve_hostname, unique_keyspace_name};
     use futures::{StreamExt, TryStreamExt};
     use std::collections::HashMap;
     use std::net::SocketAddr;
-    use std::sync::Arc;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex as StdMutex};
+    use std::task::Poll;
     use std::time::Duration;
 
     /// Tests for Connection::execute_iter
@@ -2877,6 +3497,44 @@ mod tests {
         let _ = proxy.finish().await;
     }
 
+    #[tokio::test]
+    async fn rtt_is_measured_when_probing_is_enabled() {
+        setup_tracing();
+
+        let uri = std::env::var("SCYLLA_URI").unwrap_or_else(|_| "172.42.0.2:9042".to_string());
+        let addr: SocketAddr = resolve_hostname(&uri).await;
+        let endpoint = UntranslatedEndpoint::ContactPoint(ResolvedContactPoint { address: addr });
+
+        // Without probing, RTT is never measured.
+        let (conn, _) = open_connection(&endpoint, None, &HostConnectionConfig::default())
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(conn.get_stats().rtt, None);
+
+        let config = HostConnectionConfig {
+            rtt_probe_interval: Some(Duration::from_millis(100)),
+            ..Default::default()
+        };
+        let (conn, _) = open_connection(&endpoint, None, &config).await.unwrap();
+
+        let rtt = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(rtt) = conn.get_stats().rtt {
+                    break rtt;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("RTT was not measured in time");
+        assert!(rtt > Duration::ZERO);
+
+        let stats = conn.get_stats();
+        assert_eq!(stats.node_address, addr);
+        assert_eq!(stats.local_address, conn.get_local_address());
+    }
+
     /// Verifies that setting tcp_recv_buffer_size, tcp_send_buffer_size, tcp_linger, and
     /// tcp_reuse_address on the builder are propagated correctly all the way to the
     /// underlying socket and do not prevent the driver from establishing a connection.
@@ -2980,4 +3638,191 @@ mod tests {
 
         let _ = proxy.finish().await;
     }
+
+    /// Counts reads issued to the underlying stream.
+    struct CountingReader<R> {
+        inner: R,
+        reads: Arc<AtomicUsize>,
+    }
+
+    impl<R: AsyncRead + Unpin> AsyncRead for CountingReader<R> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            Pin::new(&mut self.inner).poll_read(cx, buf)
+        }
+    }
+
+    /// Returns the number of reads issued to the socket by the connection reader
+    /// in order to receive responses to `request_count` requests, which arrived together.
+    async fn reads_for_responses(request_count: usize, read_buffer_size: usize) -> usize {
+        let handler_map = StdMutex::new(ResponseHandlerMap::new());
+        let mut responses = Vec::new();
+        let mut receivers = Vec::new();
+        for request_id in 0..request_count as u64 {
+            let (response_sender, receiver) = oneshot::channel();
+            let Ok(stream_id) = handler_map.lock().unwrap().allocate(ResponseHandler {
+                response_sender,
+                request_id,
+            }) else {
+                panic!("Could not allocate stream id");
+            };
+            receivers.push(receiver);
+
+            // A RESULT frame of kind Void.
+            responses.extend_from_slice(&[0x84, 0x00]);
+            responses.extend_from_slice(&stream_id.to_be_bytes());
+            responses.push(ResponseOpcode::Result as u8);
+            responses.extend_from_slice(&4_u32.to_be_bytes());
+            responses.extend_from_slice(&1_i32.to_be_bytes());
+        }
+
+        let reads = Arc::new(AtomicUsize::new(0));
+        let socket = CountingReader {
+            inner: std::io::Cursor::new(responses),
+            reads: Arc::clone(&reads),
+        };
+        // The reader fails once the socket is exhausted, after all the responses are handled.
+        Connection::reader(
+            BufReader::with_capacity(read_buffer_size, socket),
+            &handler_map,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+        for receiver in receivers {
+            assert!(receiver.await.unwrap().is_ok());
+        }
+
+        reads.load(Ordering::Relaxed)
+    }
+
+    #[tokio::test]
+    async fn available_responses_are_read_together_into_read_buffer() {
+        setup_tracing();
+        const REQUEST_COUNT: usize = 64;
+
+        // Without a buffer, the header and the body of each response are read separately.
+        let uncoalesced_reads = reads_for_responses(REQUEST_COUNT, 0).await;
+        assert!(uncoalesced_reads >= 2 * REQUEST_COUNT);
+
+        // All the responses fit in the default buffer, so they are read at once.
+        let coalesced_reads = reads_for_responses(
+            REQUEST_COUNT,
+            crate::client::session::DEFAULT_READ_BUFFER_SIZE,
+        )
+        .await;
+        assert!(coalesced_reads <= 2, "{coalesced_reads} reads");
+    }
+
+    #[tokio::test]
+    async fn frame_buffers_are_reused_by_connection() {
+        use crate::frame::Compression;
+        use crate::network::FrameBufferPool;
+        use scylla_cql::frame::compress_append;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        setup_tracing();
+        const REQUEST_COUNT: usize = 100;
+        let compression = Compression::Lz4;
+
+        // A server responding to each request with a compressed RESULT frame of kind Void.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut body = Vec::new();
+            compress_append(&1_i32.to_be_bytes(), compression, &mut body).unwrap();
+            for _ in 0..REQUEST_COUNT {
+                let mut header = [0u8; 9];
+                socket.read_exact(&mut header).await.unwrap();
+                let length = u32::from_be_bytes(header[5..9].try_into().unwrap());
+                let mut request_body = vec![0u8; length as usize];
+                socket.read_exact(&mut request_body).await.unwrap();
+
+                let mut response = vec![0x84, 0x01, header[2], header[3]];
+                response.push(ResponseOpcode::Result as u8);
+                response.extend_from_slice(&(body.len() as u32).to_be_bytes());
+                response.extend_from_slice(&body);
+                socket.write_all(&response).await.unwrap();
+            }
+        });
+
+        let pool = FrameBufferPool::new(16, 64 * 1024);
+        let config = HostConnectionConfig {
+            compression: Some(compression),
+            compression_threshold: 0,
+            frame_buffer_pool: Some(Arc::clone(&pool)),
+            ..Default::default()
+        };
+        let (connection, _) = Connection::new(addr, None, config).await.unwrap();
+        for _ in 0..REQUEST_COUNT {
+            connection
+                .query_unpaged(&"INSERT INTO ks.t (a) VALUES (1)".into())
+                .await
+                .unwrap();
+        }
+        server.await.unwrap();
+
+        // The request frame, the uncompressed request body and the compressed
+        // response frame are each allocated once and reused afterwards.
+        assert!(
+            pool.allocated() <= 3,
+            "{} buffers were allocated for {} requests",
+            pool.allocated(),
+            REQUEST_COUNT
+        );
+    }
+
+    #[tokio::test]
+    async fn connects_over_ipv6_loopback() {
+        setup_tracing();
+        if !ipv6_loopback_available().await {
+            return;
+        }
+        let listener = tokio::net::TcpListener::bind("[::1]:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let addr = crate::cluster::node::resolve_hostname(&format!("[::1]:{port}"), None, true)
+            .await
+            .unwrap();
+        assert!(addr.is_ipv6());
+
+        let socket_options = super::TcpSocketOptions::default();
+        let (stream, accepted) = tokio::join!(
+            super::connect_with_source_ip_and_port(addr, None, None, &socket_options),
+            listener.accept(),
+        );
+        let stream = stream.unwrap();
+        let (_, peer_addr) = accepted.unwrap();
+        assert!(stream.local_addr().unwrap().is_ipv6());
+        assert_eq!(peer_addr, stream.local_addr().unwrap());
+    }
+
+    #[test]
+    fn stream_ids_are_allocated_after_the_given_one() {
+        let mut stream_set = StreamIdSet::new();
+        assert_eq!(stream_set.allocate_after(-1), Some(0));
+        assert_eq!(stream_set.allocate_after(-1), Some(1));
+        assert_eq!(stream_set.allocate_after(62), Some(63));
+        // The next free stream id belongs to the next block.
+        assert_eq!(stream_set.allocate_after(62), Some(64));
+
+        // Freed stream ids are reused only if they are greater than the given one.
+        stream_set.free(1);
+        assert_eq!(stream_set.allocate_after(64), Some(65));
+        assert_eq!(stream_set.allocate_after(-1), Some(1));
+
+        assert_eq!(
+            stream_set.allocate_after(i16::MAX as i32 - 1),
+            Some(i16::MAX)
+        );
+        assert_eq!(stream_set.allocate_after(i16::MAX as i32 - 1), None);
+        assert_eq!(stream_set.allocate_after(i16::MAX as i32), None);
+    }
 }
@@ -0,0 +1,225 @@
+//! A cluster-wide limit on the number of pooled connections.
+//!
+//! All node pools of a session share a single [`ConnectionBudget`]. Before a pool
+//! starts opening a connection, it acquires a [`ConnectionPermit`] from the budget.
+//! The permit is then attached to the connection and released when the connection
+//! is dropped, so the number of open pooled connections never exceeds the limit.
+//!
+//! In order to distribute connections across nodes, each pool is allowed to hold
+//! at most its fair share of the budget, i.e. the limit divided by the number
+//! of pools (rounded up).
+
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+use tokio::sync::Notify;
+
+#[derive(Debug)]
+pub(crate) struct ConnectionBudget {
+    max_total: NonZeroUsize,
+    opened: AtomicUsize,
+    pools: AtomicUsize,
+    // Notifiers of the registered pools, signaled when a permit is released,
+    // so that pools which could not open a connection because of the limit
+    // can try again. Each pool has its own notifier, so that a release is
+    // remembered even if the pool is not waiting for it at the moment.
+    release_notifiers: Mutex<Vec<Weak<Notify>>>,
+}
+
+impl ConnectionBudget {
+    pub(crate) fn new(max_total: NonZeroUsize) -> Arc<Self> {
+        Arc::new(Self {
+            max_total,
+            opened: AtomicUsize::new(0),
+            pools: AtomicUsize::new(0),
+            release_notifiers: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Registers a new pool, which will share the budget with the other pools.
+    /// The pool is deregistered when all clones of the returned handle are dropped.
+    pub(crate) fn register_pool(self: &Arc<Self>) -> PoolConnectionBudget {
+        self.pools.fetch_add(1, Ordering::Relaxed);
+        let released = Arc::new(Notify::new());
+        self.release_notifiers
+            .lock()
+            .unwrap()
+            .push(Arc::downgrade(&released));
+        PoolConnectionBudget {
+            inner: Arc::new(PoolConnectionBudgetInner {
+                budget: Arc::clone(self),
+                opened: Arc::new(AtomicUsize::new(0)),
+                released,
+            }),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn opened(&self) -> usize {
+        self.opened.load(Ordering::Relaxed)
+    }
+
+    fn fair_share(&self) -> usize {
+        let pools = self.pools.load(Ordering::Relaxed).max(1);
+        self.max_total.get().div_ceil(pools)
+    }
+
+    fn try_acquire(&self) -> bool {
+        self.opened
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |opened| {
+                (opened < self.max_total.get()).then_some(opened + 1)
+            })
+            .is_ok()
+    }
+
+    fn release(&self) {
+        self.opened.fetch_sub(1, Ordering::AcqRel);
+        self.release_notifiers
+            .lock()
+            .unwrap()
+            .retain(|notifier| match notifier.upgrade() {
+                Some(notifier) => {
+                    // Stores a permit if the pool is not waiting right now,
+                    // so that the next wait completes immediately.
+                    notifier.notify_one();
+                    true
+                }
+                None => false,
+            });
+    }
+}
+
+/// A pool's handle to the session-wide [`ConnectionBudget`].
+#[derive(Clone, Debug)]
+pub(crate) struct PoolConnectionBudget {
+    inner: Arc<PoolConnectionBudgetInner>,
+}
+
+#[derive(Debug)]
+struct PoolConnectionBudgetInner {
+    budget: Arc<ConnectionBudget>,
+    // Number of permits currently held by this pool.
+    opened: Arc<AtomicUsize>,
+    released: Arc<Notify>,
+}
+
+impl Drop for PoolConnectionBudgetInner {
+    fn drop(&mut self) {
+        self.budget.pools.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl PoolConnectionBudget {
+    /// Returns true if the pool is allowed to open another connection,
+    /// i.e. it has not used up its share and the budget is not exhausted.
+    pub(crate) fn can_open(&self) -> bool {
+        let budget = &self.inner.budget;
+        self.inner.opened.load(Ordering::Relaxed) < budget.fair_share()
+            && budget.opened.load(Ordering::Acquire) < budget.max_total.get()
+    }
+
+    /// Acquires a permit for opening a new connection, if the pool is allowed to.
+    pub(crate) fn try_acquire(&self) -> Option<ConnectionPermit> {
+        if self.inner.opened.load(Ordering::Relaxed) >= self.inner.budget.fair_share()
+            || !self.inner.budget.try_acquire()
+        {
+            return None;
+        }
+        self.inner.opened.fetch_add(1, Ordering::Relaxed);
+        Some(ConnectionPermit {
+            budget: Arc::clone(&self.inner.budget),
+            pool_opened: Arc::clone(&self.inner.opened),
+        })
+    }
+
+    /// Waits until any permit of the session-wide budget is released.
+    ///
+    /// A release which happens while the pool is not waiting is not lost:
+    /// the next call returns immediately.
+    pub(crate) async fn released(&self) {
+        self.inner.released.notified().await
+    }
+}
+
+/// A permit for a single pooled connection. Released when dropped.
+#[derive(Debug)]
+pub(crate) struct ConnectionPermit {
+    budget: Arc<ConnectionBudget>,
+    pool_opened: Arc<AtomicUsize>,
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        self.pool_opened.fetch_sub(1, Ordering::Relaxed);
+        self.budget.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::ConnectionBudget;
+
+    #[test]
+    fn budget_is_never_exceeded() {
+        let budget = ConnectionBudget::new(NonZeroUsize::new(3).unwrap());
+        let pool = budget.register_pool();
+
+        let permits: Vec<_> = std::iter::from_fn(|| pool.try_acquire()).take(10).collect();
+        assert_eq!(permits.len(), 3);
+        assert_eq!(budget.opened(), 3);
+        assert!(!pool.can_open());
+
+        drop(permits);
+        assert_eq!(budget.opened(), 0);
+        assert!(pool.can_open());
+    }
+
+    #[test]
+    fn budget_is_distributed_across_pools() {
+        let budget = ConnectionBudget::new(NonZeroUsize::new(5).unwrap());
+        let mut pools: Vec<_> = (0..3).map(|_| budget.register_pool()).collect();
+
+        // Each pool may take at most ceil(5 / 3) = 2 permits.
+        let first: Vec<_> = std::iter::from_fn(|| pools[0].try_acquire())
+            .take(10)
+            .collect();
+        assert_eq!(first.len(), 2);
+
+        let second: Vec<_> = std::iter::from_fn(|| pools[1].try_acquire())
+            .take(10)
+            .collect();
+        assert_eq!(second.len(), 2);
+
+        // Only one permit is left in the budget.
+        let third: Vec<_> = std::iter::from_fn(|| pools[2].try_acquire())
+            .take(10)
+            .collect();
+        assert_eq!(third.len(), 1);
+        assert_eq!(budget.opened(), 5);
+
+        // A deregistered pool increases the share of the remaining ones.
+        drop(third);
+        drop(pools.pop());
+        assert_eq!(budget.fair_share(), 3);
+        assert!(pools[0].can_open());
+    }
+
+    #[tokio::test]
+    async fn release_is_not_lost_when_pool_is_not_waiting() {
+        let budget = ConnectionBudget::new(NonZeroUsize::new(1).unwrap());
+        let pools: Vec<_> = (0..2).map(|_| budget.register_pool()).collect();
+
+        let permit = pools[0].try_acquire().unwrap();
+        // Neither pool is waiting when the permit is released.
+        drop(permit);
+
+        for pool in &pools {
+            tokio::time::timeout(std::time::Duration::from_secs(1), pool.released())
+                .await
+                .expect("release notification was lost");
+        }
+    }
+}
@@ -2,6 +2,10 @@ use super::connection::{
     Connection, ConnectionConfig, ErrorReceiver, HostConnectionConfig, VerifiedKeyspaceName,
     open_connection, open_connection_to_shard_aware_port,
 };
+use super::connection_budget::{ConnectionBudget, PoolConnectionBudget};
+use super::connection_selection::{ConnectionSelection, ConnectionSelector};
+use super::pool_autoscaling::PoolAutoscaling;
+use super::repreparation::RepreparationSet;
 
 use crate::errors::{
     BrokenConnectionErrorKind, ConnectionError, ConnectionPoolError, UseKeyspaceError,
@@ -15,6 +19,7 @@ use crate::cluster::metadata::{PeerEndpoint, UntranslatedEndpoint};
 
 #[cfg(feature = "metrics")]
 use crate::observability::metrics::Metrics;
+use crate::observability::metrics_sink::{ConnectionEvent, ConnectionEventKind, MetricsSink};
 
 use crate::cluster::NodeAddr;
 use crate::utils::safe_format::IteratorSafeFormatExt;
@@ -26,10 +31,11 @@ use std::convert::TryInto;
 use std::num::NonZeroUsize;
 use std::panic::{RefUnwindSafe, UnwindSafe};
 use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock, Weak};
 use uuid::Uuid;
 
-use tokio::sync::{Notify, mpsc};
+use tokio::sync::{Notify, Semaphore, SemaphorePermit, mpsc};
 use tracing::{debug, error, trace, warn};
 
 /// The target size of a per-node connection pool.
@@ -56,12 +62,31 @@ impl Default for PoolSize {
     }
 }
 
+impl PoolSize {
+    fn connection_count(&self) -> NonZeroUsize {
+        match self {
+            PoolSize::PerHost(count) | PoolSize::PerShard(count) => *count,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct PoolConfig {
     pub(crate) connection_config: ConnectionConfig,
     pub(crate) pool_size: PoolSize,
+    /// If set, the number of connections in `pool_size` scales with load.
+    pub(crate) autoscaling: Option<PoolAutoscaling>,
     pub(crate) can_use_shard_aware_port: bool,
     pub(crate) reconnect_policy: Arc<dyn ReconnectPolicy>,
+    /// If set, limits the total number of connections opened by all pools.
+    pub(crate) connection_budget: Option<Arc<ConnectionBudget>>,
+    /// If set, these statements are prepared on each connection added to a pool.
+    pub(crate) repreparation_set: Option<Arc<RepreparationSet>>,
+    pub(crate) connection_selection: ConnectionSelection,
+    /// If set, limits the number of connections being opened to a node at the same time.
+    pub(crate) max_concurrent_opens: Option<NonZeroUsize>,
+    /// If set, connection events are reported to it.
+    pub(crate) metrics_sink: Option<Arc<dyn MetricsSink>>,
 }
 
 #[cfg(test)]
@@ -70,8 +95,14 @@ impl Default for PoolConfig {
         Self {
             connection_config: Default::default(),
             pool_size: Default::default(),
+            autoscaling: None,
             can_use_shard_aware_port: true,
             reconnect_policy: Arc::new(ExponentialReconnectPolicy::new()),
+            connection_budget: None,
+            repreparation_set: None,
+            connection_selection: Default::default(),
+            max_concurrent_opens: None,
+            metrics_sink: None,
         }
     }
 }
@@ -85,17 +116,57 @@ impl PoolConfig {
         let host_pool_config = HostPoolConfig {
             connection_config: self.connection_config.to_host_connection_config(endpoint),
             pool_size: self.pool_size,
+            autoscaling: self.autoscaling,
             can_use_shard_aware_port: self.can_use_shard_aware_port,
+            connection_budget: self
+                .connection_budget
+                .as_ref()
+                .map(ConnectionBudget::register_pool),
+            repreparation_set: self.repreparation_set.clone(),
+            open_limiter: self
+                .max_concurrent_opens
+                .map(|max| Arc::new(Semaphore::new(max.get()))),
+            metrics_sink: self.metrics_sink.clone(),
         };
         (host_pool_config, host_reconnect_policy)
     }
 }
 
+/// A connection opened by [NodeConnectionPool::open_fresh_connection].
+/// Reports the connection as closed when dropped.
+pub(crate) struct FreshConnection {
+    connection: Arc<Connection>,
+    metrics_sink: Option<Arc<dyn MetricsSink>>,
+    node_address: NodeAddr,
+}
+
+impl FreshConnection {
+    pub(crate) fn connection(&self) -> &Arc<Connection> {
+        &self.connection
+    }
+}
+
+impl Drop for FreshConnection {
+    fn drop(&mut self) {
+        ConnectionEvent::report(
+            self.metrics_sink.as_deref(),
+            self.node_address,
+            ConnectionEventKind::Closed,
+        );
+    }
+}
+
 #[derive(Clone)]
 struct HostPoolConfig {
     pub(crate) connection_config: HostConnectionConfig,
     pub(crate) pool_size: PoolSize,
+    pub(crate) autoscaling: Option<PoolAutoscaling>,
     pub(crate) can_use_shard_aware_port: bool,
+    pub(crate) connection_budget: Option<PoolConnectionBudget>,
+    pub(crate) repreparation_set: Option<Arc<RepreparationSet>>,
+    /// Permits to open connections to the node, if their concurrency is limited.
+    pub(crate) open_limiter: Option<Arc<Semaphore>>,
+    pub(crate) metrics_sink: Option<Arc<dyn MetricsSink>>,
 }
 
 #[cfg(test)]
@@ -104,7 +175,12 @@ impl Default for HostPoolConfig {
         Self {
             connection_config: Default::default(),
             pool_size: Default::default(),
+            autoscaling: None,
             can_use_shard_aware_port: true,
+            connection_budget: None,
+            repreparation_set: None,
+            open_limiter: None,
+            metrics_sink: None,
         }
     }
 }
@@ -184,9 +260,33 @@ impl std::fmt::Debug for PoolConnections {
     }
 }
 
+/// How far the pool is filled, published by the pool refiller.
+#[derive(Debug, Default)]
+struct PoolFill {
+    /// Connections to the current address of the node, up to the desired count per shard.
+    established: AtomicUsize,
+    /// Connections the pool aims to keep per shard, or per host with [`PoolSize::PerHost`].
+    target: AtomicUsize,
+    /// Number of shards the connections are spread over, or 0 if it is not known yet.
+    shard_count: AtomicUsize,
+}
+
+/// How far a pool is filled, as returned by [`NodeConnectionPool::fill`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PoolFillState {
+    /// Connections to the current address of the node, up to the desired count per shard.
+    pub(crate) established: usize,
+    /// Connections the pool aims to keep per shard, or per host with [`PoolSize::PerHost`].
+    pub(crate) target: usize,
+    /// Number of shards the connections are spread over, if known. It is not known
+    /// for a pool of connections per shard until its first connection is opened.
+    pub(crate) shard_count: Option<usize>,
+}
+
 #[derive(Clone)]
 pub(crate) struct NodeConnectionPool {
     conns: Arc<ArcSwap<MaybePoolConnections>>,
+    fill: Arc<PoolFill>,
     use_keyspace_request_sender: mpsc::Sender<UseKeyspaceRequest>,
     _refiller_handle: Arc<RemoteHandle<()>>,
     pool_updated_notify: Arc<Notify>,
@@ -194,7 +294,15 @@ pub(crate) struct NodeConnectionPool {
     /// its backoff. Used when client routes change makes previously
     /// untranslatable addresses translatable.
     refill_now_notify: Arc<Notify>,
+    /// Signaled when the node changes its address, so that the pool refiller
+    /// migrates the connections to the new one.
+    endpoint_changed_notify: Arc<Notify>,
     endpoint: Arc<RwLock<UntranslatedEndpoint>>,
+    connection_selector: Arc<ConnectionSelector>,
+    /// Used to open fresh connections, which are not a part of the pool.
+    connection_config: HostConnectionConfig,
+    metrics_sink: Option<Arc<dyn MetricsSink>>,
+    current_keyspace: Arc<RwLock<Option<VerifiedKeyspaceName>>>,
 }
 
 impl std::fmt::Debug for NodeConnectionPool {
@@ -228,18 +336,22 @@ impl NodeConnectionPool {
         let (use_keyspace_request_sender, use_keyspace_request_receiver) = mpsc::channel(1);
         let pool_updated_notify = Arc::new(Notify::new());
         let refill_now_notify = Arc::new(Notify::new());
+        let endpoint_changed_notify = Arc::new(Notify::new());
 
         let (host_pool_config, host_reconnect_policy) = pool_config.to_host_pool_config(&endpoint);
 
         let arced_endpoint = Arc::new(RwLock::new(endpoint));
+        let connection_config = host_pool_config.connection_config.clone();
+        let metrics_sink = host_pool_config.metrics_sink.clone();
 
         let refiller = PoolRefiller::new(
             arced_endpoint.clone(),
             host_pool_config,
             connectivity_events_sender,
-            current_keyspace,
+            current_keyspace.clone(),
             pool_updated_notify.clone(),
             refill_now_notify.clone(),
+            endpoint_changed_notify.clone(),
             pool_empty_notifier,
             #[cfg(feature = "metrics")]
             metrics,
@@ -247,16 +359,25 @@ impl NodeConnectionPool {
         );
 
         let conns = refiller.get_shared_connections();
+        let fill = Arc::clone(&refiller.fill);
         let (fut, refiller_handle) = refiller.run(use_keyspace_request_receiver).remote_handle();
         tokio::spawn(fut);
 
         Self {
             conns,
+            fill,
             use_keyspace_request_sender,
             _refiller_handle: Arc::new(refiller_handle),
             pool_updated_notify,
             refill_now_notify,
+            endpoint_changed_notify,
             endpoint: arced_endpoint,
+            connection_selector: Arc::new(ConnectionSelector::new(
+                pool_config.connection_selection,
+            )),
+            connection_config,
+            metrics_sink,
+            current_keyspace: Arc::new(RwLock::new(current_keyspace)),
         }
     }
 
@@ -270,8 +391,11 @@ impl NodeConnectionPool {
         }
     }
 
+    /// Changes the address of the node. Connections to the old address keep
+    /// serving requests until connections to the new one replace them.
     pub(crate) fn update_endpoint(&self, new_endpoint: PeerEndpoint) {
         *self.endpoint.write().unwrap() = UntranslatedEndpoint::Peer(new_endpoint);
+        self.endpoint_changed_notify.notify_one();
     }
 
     /// Signals the pool refiller to retry immediately, resetting its backoff.
@@ -298,7 +422,7 @@ impl NodeConnectionPool {
         trace!(shard = shard, "Selecting connection for shard");
         self.with_connections(|pool_conns| match pool_conns {
             PoolConnections::NotSharded(conns) => {
-                Self::choose_random_connection_from_slice(conns).unwrap()
+                self.choose_connection_from_slice(conns).unwrap()
             }
             PoolConnections::Sharded {
                 connections,
@@ -312,7 +436,7 @@ impl NodeConnectionPool {
                         error!("The provided shard number: {} does not fit u16! Using 0 as the shard number. Check your LoadBalancingPolicy implementation.", shard);
                         0
                     });
-                Self::connection_for_shard_helper(shard, sharder.nr_shards, connections.as_slice())
+                self.connection_for_shard_helper(shard, sharder.nr_shards, connections.as_slice())
             }
         })
     }
@@ -320,21 +444,20 @@ impl NodeConnectionPool {
     pub(crate) fn random_connection(&self) -> Result<Arc<Connection>, ConnectionPoolError> {
         trace!("Selecting random connection");
         self.with_connections(|pool_conns| match pool_conns {
-            PoolConnections::NotSharded(conns) => {
-                Self::choose_random_connection_from_slice(conns).unwrap()
-            }
+            PoolConnections::NotSharded(conns) => self.choose_connection_from_slice(conns).unwrap(),
             PoolConnections::Sharded {
                 sharder,
                 connections,
             } => {
                 let shard: u16 = rand::rng().random_range(0..sharder.nr_shards.get());
-                Self::connection_for_shard_helper(shard, sharder.nr_shards, connections.as_slice())
+                self.connection_for_shard_helper(shard, sharder.nr_shards, connections.as_slice())
             }
         })
     }
 
     // Tries to get a connection to given shard, if it's broken returns any working connection
     fn connection_for_shard_helper(
+        &self,
         shard: u16,
         nr_shards: ShardCount,
         shard_conns: &[Vec<Arc<Connection>>],
@@ -351,7 +474,7 @@ impl NodeConnectionPool {
                 );
                 None
             })
-            .and_then(|shard_conns| Self::choose_random_connection_from_slice(shard_conns))
+            .and_then(|shard_conns| self.choose_connection_from_slice(shard_conns))
         {
             trace!(shard = shard, "Found connection for the target shard");
             return conn;
@@ -368,9 +491,7 @@ impl NodeConnectionPool {
             let idx = rand::rng().random_range(0..shards_to_try.len());
             let shard = shards_to_try.swap_remove(idx);
 
-            if let Some(conn) =
-                Self::choose_random_connection_from_slice(&shard_conns[shard as usize])
-            {
+            if let Some(conn) = self.choose_connection_from_slice(&shard_conns[shard as usize]) {
                 trace!(
                     orig_shard = orig_shard,
                     shard = shard,
@@ -391,14 +512,58 @@ impl NodeConnectionPool {
 
         self.use_keyspace_request_sender
             .send(UseKeyspaceRequest {
-                keyspace_name,
+                keyspace_name: keyspace_name.clone(),
                 response_sender,
             })
             .await
             .expect("Bug in NodeConnectionPool::use_keyspace sending");
         // Other end of this channel is in the PoolRefiller, can't be dropped while we have &self to _refiller_handle
 
-        response_receiver.await.unwrap() // PoolRefiller always responds
+        // PoolRefiller always responds. Like the refiller, remember the keyspace even if setting it
+        // failed on some connections, so that it is set on connections opened later.
+        let result = response_receiver.await.unwrap();
+        *self.current_keyspace.write().unwrap() = Some(keyspace_name);
+        result
+    }
+
+    /// Opens a new connection to the node, which is not added to the pool.
+    ///
+    /// The connection is not shard-aware, i.e. it is connected to an arbitrary shard,
+    /// and it uses the keyspace currently used by the pool. It is closed when
+    /// the returned [FreshConnection] is dropped.
+    pub(crate) async fn open_fresh_connection(
+        &self,
+    ) -> Result<FreshConnection, ConnectionPoolError> {
+        let endpoint = self.endpoint.read().unwrap().clone();
+        let node_address = endpoint.address();
+        let result = open_connection(&endpoint, None, &self.connection_config).await;
+        let kind = if result.is_ok() {
+            ConnectionEventKind::Opened
+        } else {
+            ConnectionEventKind::OpenFailed
+        };
+        ConnectionEvent::report(self.metrics_sink.as_deref(), node_address, kind);
+        let (connection, _error_receiver) =
+            result.map_err(|last_connection_error| ConnectionPoolError::Broken {
+                last_connection_error,
+            })?;
+
+        let keyspace = self.current_keyspace.read().unwrap().clone();
+        if let Some(keyspace) = keyspace
+            && let Err(err) = connection.use_keyspace(&keyspace).await
+        {
+            warn!(
+                "[{}] Failed to set keyspace for fresh connection: {}",
+                connection.get_connect_address().ip(),
+                err,
+            );
+        }
+
+        Ok(FreshConnection {
+            connection: Arc::new(connection),
+            metrics_sink: self.metrics_sink.clone(),
+            node_address,
+        })
     }
 
     // Waits until the pool becomes initialized.
@@ -415,6 +580,19 @@ impl NodeConnectionPool {
         }
     }
 
+    /// Returns the number of established connections and the number of connections
+    /// the pool aims to keep. Connections above the desired count are not counted.
+    pub(crate) fn fill(&self) -> PoolFillState {
+        PoolFillState {
+            established: self.fill.established.load(Ordering::Relaxed),
+            target: self.fill.target.load(Ordering::Relaxed),
+            shard_count: match self.fill.shard_count.load(Ordering::Relaxed) {
+                0 => None,
+                shard_count => Some(shard_count),
+            },
+        }
+    }
+
     pub(crate) fn get_working_connections(
         &self,
     ) -> Result<Vec<Arc<Connection>>, ConnectionPoolError> {
@@ -426,7 +604,7 @@ impl NodeConnectionPool {
         })
     }
 
-    fn choose_random_connection_from_slice(v: &[Arc<Connection>]) -> Option<Arc<Connection>> {
+    fn choose_connection_from_slice(&self, v: &[Arc<Connection>]) -> Option<Arc<Connection>> {
         trace!(
             connections = tracing::field::display(
                 v.iter()
@@ -435,14 +613,9 @@ impl NodeConnectionPool {
             ),
             "Available"
         );
-        if v.is_empty() {
-            None
-        } else if v.len() == 1 {
-            Some(v[0].clone())
-        } else {
-            let idx = rand::rng().random_range(0..v.len());
-            Some(v[idx].clone())
-        }
+        self.connection_selector
+            .select(v, |conn| conn.in_flight_requests())
+            .cloned()
     }
 
     fn with_connections<T>(
@@ -466,19 +639,37 @@ struct PoolRefiller {
     // Following information identify the pool and do not change
     pool_config: HostPoolConfig,
 
+    // The number of connections to keep per host or per shard, depending on `pool_config.pool_size`.
+    // Fixed, unless the pool is autoscaled.
+    target_size: NonZeroUsize,
+
     /// If set, used to send connectivity change events about node with given host_id.
     connectivity_events_sender: Option<(Uuid, mpsc::UnboundedSender<ConnectivityChangeEvent>)>,
 
     // Following information is subject to updates on topology refresh
     endpoint: Arc<RwLock<UntranslatedEndpoint>>,
 
+    // Incremented each time the node changes its address. Connections opened
+    // to an outdated address are discarded when they become ready.
+    endpoint_generation: u64,
+
+    // Connections to the previous address of the node. They stay in `conns`
+    // and serve requests until a connection to the new address replaces each
+    // of them, so that the node is never left without connections while
+    // the pool migrates. They are not counted when deciding whether the pool is full.
+    stale_connections: Vec<Arc<Connection>>,
+
     // Following fields are updated with information from OPTIONS
     shard_aware_port: Option<u16>,
     sharder: Option<Sharder>,
+    // Set once a connection was opened, which tells whether and how the node is sharded.
+    sharding_known: bool,
 
     // `shared_conns` is updated only after `conns` change
     shared_conns: Arc<ArcSwap<MaybePoolConnections>>,
     conns: Vec<Vec<Arc<Connection>>>,
+    // Updated together with `shared_conns`
+    fill: Arc<PoolFill>,
 
     // Set to true if there was an error since the last refill,
     // set to false when refilling starts.
@@ -518,6 +709,9 @@ struct PoolRefiller {
     // Signaled to make the refiller retry immediately with reset backoff
     refill_now_notify: Arc<Notify>,
 
+    // Signaled when the address of the node changes
+    endpoint_changed_notify: Arc<Notify>,
+
     // Signaled when the connection pool becomes empty
     pool_empty_notifier: mpsc::Sender<()>,
 
@@ -531,6 +725,16 @@ struct UseKeyspaceRequest {
     response_sender: tokio::sync::oneshot::Sender<Result<(), UseKeyspaceError>>,
 }
 
+#[cfg(feature = "metrics")]
+impl Drop for PoolRefiller {
+    fn drop(&mut self) {
+        if let Some((host_id, _)) = self.connectivity_events_sender {
+            self.metrics
+                .remove_shard_connections(host_id, self.metrics_pool_id());
+        }
+    }
+}
+
 impl PoolRefiller {
     #[allow(clippy::too_many_arguments)] // Not always triggered, because of the metrics, so
     // I can't use `expect`.
@@ -541,6 +745,7 @@ impl PoolRefiller {
         current_keyspace: Option<VerifiedKeyspaceName>,
         pool_updated_notify: Arc<Notify>,
         refill_now_notify: Arc<Notify>,
+        endpoint_changed_notify: Arc<Notify>,
         pool_empty_notifier: mpsc::Sender<()>,
         #[cfg(feature = "metrics")] metrics: Arc<Metrics>,
         reconnect_policy: Box<dyn ReconnectPolicySession>,
@@ -550,16 +755,32 @@ impl PoolRefiller {
         let conns = vec![Vec::new()];
         let shared_conns = Arc::new(ArcSwap::new(Arc::new(MaybePoolConnections::Initializing)));
 
+        let target_size = match &pool_config.autoscaling {
+            Some(autoscaling) => autoscaling.min_connections(),
+            None => pool_config.pool_size.connection_count(),
+        };
+
+        let fill = Arc::new(PoolFill::default());
+        fill.target.store(target_size.get(), Ordering::Relaxed);
+        if let PoolSize::PerHost(_) = pool_config.pool_size {
+            fill.shard_count.store(1, Ordering::Relaxed);
+        }
+
         Self {
             endpoint,
+            endpoint_generation: 0,
+            stale_connections: Vec::new(),
             pool_config,
+            target_size,
             connectivity_events_sender,
 
             shard_aware_port: None,
             sharder: None,
+            sharding_known: false,
 
             shared_conns,
             conns,
+            fill,
 
             had_error_since_last_refill: false,
             refill_delay_strategy: reconnect_policy,
@@ -573,6 +794,7 @@ impl PoolRefiller {
 
             pool_updated_notify,
             refill_now_notify,
+            endpoint_changed_notify,
             pool_empty_notifier,
 
             #[cfg(feature = "metrics")]
@@ -584,6 +806,14 @@ impl PoolRefiller {
         self.endpoint.read().unwrap().address()
     }
 
+    fn report_connection_event(&self, kind: ConnectionEventKind) {
+        ConnectionEvent::report(
+            self.pool_config.metrics_sink.as_deref(),
+            self.endpoint_description(),
+            kind,
+        );
+    }
+
     pub(crate) fn get_shared_connections(&self) -> Arc<ArcSwap<MaybePoolConnections>> {
         self.shared_conns.clone()
     }
@@ -606,6 +836,20 @@ impl PoolRefiller {
             when: tokio::time::Instant::now(),
         });
 
+        let connection_budget = self.pool_config.connection_budget.clone();
+        let budget_released = || async {
+            match &connection_budget {
+                Some(budget) => budget.released().await,
+                None => std::future::pending().await,
+            }
+        };
+
+        let mut autoscaling_interval = self.pool_config.autoscaling.map(|autoscaling| {
+            let mut interval = tokio::time::interval(autoscaling.evaluation_interval());
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            interval
+        });
+
         loop {
             tokio::select! {
                 // Note that some default value must be passed to avoid `unwrap()` here; the guard ensures that `scheduled_refill` is `Some`
@@ -655,6 +899,16 @@ impl PoolRefiller {
                     }
                 }
 
+                _ = tick_if_autoscaled(autoscaling_interval.as_mut()) => {
+                    self.autoscale();
+                }
+
+                _ = budget_released() => {
+                    // Some connection of the session was closed, so if this pool
+                    // could not be filled because of the connection limit,
+                    // a refill is scheduled below.
+                }
+
                 _ = self.refill_now_notify.notified() => {
                     debug!(
                         "[{}] Immediate refill requested, resetting backoff",
@@ -671,6 +925,12 @@ impl PoolRefiller {
                     // we unschedule that refill here and then the block below will schedule a new refill with the correct delay.
                     scheduled_refill = None;
                 }
+
+                _ = self.endpoint_changed_notify.notified() => {
+                    self.start_migration();
+                    // Connect to the new address right away.
+                    scheduled_refill = None;
+                }
             }
             trace!(
                 pool_state = ?ShardedConnectionVectorWrapper(&self.conns)
@@ -703,10 +963,11 @@ impl PoolRefiller {
 
     fn is_full(&self) -> bool {
         match self.pool_config.pool_size {
-            PoolSize::PerHost(target) => self.active_connection_count() >= target.get(),
-            PoolSize::PerShard(target) => {
-                self.conns.iter().all(|conns| conns.len() >= target.get())
-            }
+            PoolSize::PerHost(_) => self.fresh_connection_count() >= self.target_size.get(),
+            PoolSize::PerShard(_) => self
+                .conns
+                .iter()
+                .all(|conns| self.fresh_shard_connection_count(conns) >= self.target_size.get()),
         }
     }
 
@@ -715,7 +976,14 @@ impl PoolRefiller {
     }
 
     fn need_filling(&self) -> bool {
-        !self.is_filling() && !self.is_full()
+        !self.is_filling() && !self.is_full() && self.connection_budget_allows_opening()
+    }
+
+    fn connection_budget_allows_opening(&self) -> bool {
+        self.pool_config
+            .connection_budget
+            .as_ref()
+            .is_none_or(PoolConnectionBudget::can_open)
     }
 
     fn can_use_shard_aware_port(&self) -> bool {
@@ -741,10 +1009,12 @@ impl PoolRefiller {
 
         if self.can_use_shard_aware_port() {
             // Only use the shard-aware port if we have a PerShard strategy
-            if let PoolSize::PerShard(target) = self.pool_config.pool_size {
-                // Try to fill up each shard up to `target` connections
+            if let PoolSize::PerShard(_) = self.pool_config.pool_size {
+                // Try to fill up each shard up to `target_size` connections
+                let target = self.target_size.get();
                 for (shard_id, shard_conns) in self.conns.iter().enumerate() {
-                    let to_open_count = target.get().saturating_sub(shard_conns.len());
+                    let to_open_count =
+                        target.saturating_sub(self.fresh_shard_connection_count(shard_conns));
                     if to_open_count == 0 {
                         continue;
                     }
@@ -761,14 +1031,13 @@ impl PoolRefiller {
         }
         // Calculate how many more connections we need to open in order
         // to achieve the target connection count.
+        let target = self.target_size.get();
         let to_open_count = match self.pool_config.pool_size {
-            PoolSize::PerHost(target) => {
-                target.get().saturating_sub(self.active_connection_count())
-            }
-            PoolSize::PerShard(target) => self
+            PoolSize::PerHost(_) => target.saturating_sub(self.fresh_connection_count()),
+            PoolSize::PerShard(_) => self
                 .conns
                 .iter()
-                .map(|conns| target.get().saturating_sub(conns.len()))
+                .map(|conns| target.saturating_sub(self.fresh_shard_connection_count(conns)))
                 .sum::<usize>(),
         };
         // When connecting to ScyllaDB through non-shard-aware port,
@@ -825,11 +1094,26 @@ impl PoolRefiller {
                 }
             }
             Ok((connection, error_receiver)) => {
+                if evt.endpoint_generation != self.endpoint_generation {
+                    // The node has changed its address since the connection
+                    // started opening, so it is connected to the old one.
+                    debug!(
+                        "[{}] Discarding connection to the previous address {}",
+                        endpoint,
+                        connection.get_connect_address(),
+                    );
+                    #[cfg(feature = "metrics")]
+                    self.metrics.dec_total_connections();
+                    self.report_connection_event(ConnectionEventKind::Closed);
+                    return;
+                }
+
                 // Update sharding and optionally reshard
                 let shard_info = connection.get_shard_info().as_ref();
                 let sharder = shard_info.map(|s| s.get_sharder());
                 let shard_id = shard_info.map_or(0, |s| s.shard as usize);
                 self.maybe_reshard(sharder);
+                self.sharding_known = true;
 
                 // Update the shard-aware port
                 if self.shard_aware_port != connection.get_shard_aware_port() {
@@ -855,20 +1139,25 @@ impl PoolRefiller {
                         connection,
                         error_receiver,
                         evt.requested_shard,
+                        evt.endpoint_generation,
                     );
                     return;
                 }
 
                 let active_connection_count = self.active_connection_count();
+                let target = self.target_size.get();
                 // `ShardInfo::new` rejects out-of-range shard IDs; without shard info
                 // we use shard 0. `maybe_reshard` has sized `self.conns` accordingly.
+                let fresh_shard_connection_count =
+                    self.fresh_shard_connection_count(&self.conns[shard_id]);
+                let fresh_connection_count = self.fresh_connection_count();
                 let shard_conns = &mut self.conns[shard_id];
 
                 // Decide if the connection can be accepted, according to
                 // the pool filling strategy
                 let can_be_accepted = match self.pool_config.pool_size {
-                    PoolSize::PerHost(target) => active_connection_count < target.get(),
-                    PoolSize::PerShard(target) => shard_conns.len() < target.get(),
+                    PoolSize::PerHost(_) => fresh_connection_count < target,
+                    PoolSize::PerShard(_) => fresh_shard_connection_count < target,
                 };
 
                 if can_be_accepted {
@@ -886,9 +1175,16 @@ impl PoolRefiller {
                         active_connection_count + 1,
                     );
 
+                    if let Some(repreparation_set) = &self.pool_config.repreparation_set {
+                        let repreparation_set = Arc::clone(repreparation_set);
+                        let conn = Arc::clone(&conn);
+                        tokio::spawn(async move { repreparation_set.reprepare_on(conn).await });
+                    }
+
                     self.connection_errors
                         .push(wait_for_error(Arc::downgrade(&conn), error_receiver).boxed());
                     shard_conns.push(conn);
+                    self.retire_stale_connection(shard_id);
 
                     self.update_shared_conns(None);
                 } else if evt.requested_shard.is_some() {
@@ -940,8 +1236,45 @@ impl PoolRefiller {
     // the shard aware port is available, it will attempt to connect directly
     // to the shard using the port.
     fn start_opening_connection(&self, shard: Option<Shard>) {
+        let budget_permit = match &self.pool_config.connection_budget {
+            Some(budget) => match budget.try_acquire() {
+                Some(permit) => Some(permit),
+                None => {
+                    trace!(
+                        "[{}] Not opening a connection, the session-wide connection limit is reached",
+                        self.endpoint_description()
+                    );
+                    return;
+                }
+            },
+            None => None,
+        };
+        let attach_permit = move |result: Result<(Connection, ErrorReceiver), ConnectionError>| {
+            result.map(|(mut connection, error_receiver)| {
+                if let Some(permit) = budget_permit {
+                    connection.set_budget_permit(permit);
+                }
+                (connection, error_receiver)
+            })
+        };
+
         let cfg = self.pool_config.connection_config.clone();
+        let open_limiter = self.pool_config.open_limiter.clone();
         let mut endpoint = self.endpoint.read().unwrap().clone();
+        let endpoint_generation = self.endpoint_generation;
+
+        let report_in_sink = {
+            let metrics_sink = self.pool_config.metrics_sink.clone();
+            let node_address = self.endpoint_description();
+            move |connect_result: &Result<_, ConnectionError>| {
+                let kind = if connect_result.is_ok() {
+                    ConnectionEventKind::Opened
+                } else {
+                    ConnectionEventKind::OpenFailed
+                };
+                ConnectionEvent::report(metrics_sink.as_deref(), node_address, kind);
+            }
+        };
 
         #[cfg(feature = "metrics")]
         let count_in_metrics = {
@@ -961,6 +1294,7 @@ impl PoolRefiller {
                     endpoint.set_port(port);
                     endpoint
                 };
+                let _open_permit = acquire_open_permit(open_limiter.as_deref()).await;
                 let result = open_connection_to_shard_aware_port(
                     &shard_aware_endpoint,
                     shard,
@@ -968,28 +1302,35 @@ impl PoolRefiller {
                     &cfg,
                 )
                 .await;
+                let result = attach_permit(result);
 
                 #[cfg(feature = "metrics")]
                 count_in_metrics(&result);
+                report_in_sink(&result);
 
                 OpenedConnectionEvent {
                     result,
                     requested_shard: Some(shard),
                     keyspace_name: None,
+                    endpoint_generation,
                 }
             }
             .boxed(),
             _ => async move {
                 let non_shard_aware_endpoint = endpoint;
+                let _open_permit = acquire_open_permit(open_limiter.as_deref()).await;
                 let result = open_connection(&non_shard_aware_endpoint, None, &cfg).await;
+                let result = attach_permit(result);
 
                 #[cfg(feature = "metrics")]
                 count_in_metrics(&result);
+                report_in_sink(&result);
 
                 OpenedConnectionEvent {
                     result,
                     requested_shard: None,
                     keyspace_name: None,
+                    endpoint_generation,
                 }
             }
             .boxed(),
@@ -1019,6 +1360,7 @@ impl PoolRefiller {
         self.conns.resize_with(shard_count, Vec::new);
 
         self.excess_connections.clear();
+        self.stale_connections.clear();
     }
 
     // Updates `shared_conns` based on `conns`.
@@ -1043,6 +1385,9 @@ impl PoolRefiller {
 
         // Make the connection list available.
         let old_conns = self.shared_conns.swap(new_conns);
+        self.publish_fill();
+        #[cfg(feature = "metrics")]
+        self.publish_shard_connections();
 
         // Notify potential waiters.
         self.pool_updated_notify.notify_waiters();
@@ -1051,6 +1396,48 @@ impl PoolRefiller {
         self.emit_events(old_conns.as_ref());
     }
 
+    // Updates `fill` based on `conns` and `target_size`.
+    fn publish_fill(&self) {
+        let target = self.target_size.get();
+        let (established, shard_count) = match self.pool_config.pool_size {
+            PoolSize::PerHost(_) => (self.fresh_connection_count().min(target), 1),
+            PoolSize::PerShard(_) => (
+                self.conns
+                    .iter()
+                    .map(|conns| self.fresh_shard_connection_count(conns).min(target))
+                    .sum(),
+                // Until the first connection is opened, `conns` assumes a single shard.
+                if self.sharding_known {
+                    self.conns.len()
+                } else {
+                    0
+                },
+            ),
+        };
+        self.fill.established.store(established, Ordering::Relaxed);
+        self.fill.target.store(target, Ordering::Relaxed);
+        self.fill.shard_count.store(shard_count, Ordering::Relaxed);
+    }
+
+    // Publishes the number of connections to each shard in metrics.
+    // Pools which don't belong to a known node (e.g. in tests) are not reported.
+    #[cfg(feature = "metrics")]
+    fn publish_shard_connections(&self) {
+        let Some((host_id, _)) = self.connectivity_events_sender else {
+            return;
+        };
+        let per_shard = self.conns.iter().map(|conns| conns.len() as u64).collect();
+        self.metrics
+            .set_shard_connections(host_id, self.metrics_pool_id(), per_shard);
+    }
+
+    // Distinguishes this pool from other pools of the same node in metrics.
+    // `fill` is owned by the pool, so its address is unique while the pool lives.
+    #[cfg(feature = "metrics")]
+    fn metrics_pool_id(&self) -> usize {
+        Arc::as_ptr(&self.fill) as usize
+    }
+
     /// Emits connectivity change events if the pool transitioned
     /// between empty and non-empty states,
     /// provided that connectivity notifier is configured.
@@ -1119,6 +1506,9 @@ impl PoolRefiller {
 
         let endpoint = self.endpoint_description();
 
+        self.stale_connections
+            .retain(|stale_conn| !Arc::ptr_eq(stale_conn, &connection));
+
         let maybe_remove_in_vec = |v: &mut Vec<Arc<Connection>>| -> bool {
             let maybe_idx = v
                 .iter()
@@ -1130,6 +1520,11 @@ impl PoolRefiller {
                     v.swap_remove(idx);
                     #[cfg(feature = "metrics")]
                     self.metrics.dec_total_connections();
+                    ConnectionEvent::report(
+                        self.pool_config.metrics_sink.as_deref(),
+                        endpoint,
+                        ConnectionEventKind::Closed,
+                    );
                     true
                 }
                 None => false,
@@ -1232,6 +1627,7 @@ impl PoolRefiller {
         connection: Connection,
         error_receiver: ErrorReceiver,
         requested_shard: Option<Shard>,
+        endpoint_generation: u64,
     ) {
         // TODO: There should be a timeout for this
 
@@ -1250,16 +1646,198 @@ impl PoolRefiller {
                     result: Ok((connection, error_receiver)),
                     requested_shard,
                     keyspace_name: Some(keyspace_name),
+                    endpoint_generation,
                 }
             }
             .boxed(),
         );
     }
 
+    // Adjusts the target size of an autoscaled pool to its current load.
+    // Growing is left to the refill scheduled in the main loop,
+    // while shrinking closes the surplus connections right away.
+    fn autoscale(&mut self) {
+        let Some(autoscaling) = self.pool_config.autoscaling else {
+            return;
+        };
+        let target = self.target_size.get();
+        let in_flight_requests = self
+            .conns
+            .iter()
+            .flatten()
+            .map(|conn| conn.in_flight_requests())
+            .sum::<usize>();
+        let new_target =
+            autoscaling.next_target(target, self.active_connection_count(), in_flight_requests);
+
+        // Don't grow a pool which has not reached its current target yet.
+        if new_target == target || (new_target > target && !self.is_full()) {
+            return;
+        }
+
+        debug!(
+            "[{}] Scaling pool from {} to {} connections per {}, {} requests in flight",
+            self.endpoint_description(),
+            target,
+            new_target,
+            match self.pool_config.pool_size {
+                PoolSize::PerHost(_) => "host",
+                PoolSize::PerShard(_) => "shard",
+            },
+            in_flight_requests,
+        );
+        // `next_target` never goes below the minimum, which is positive.
+        self.target_size = NonZeroUsize::new(new_target).unwrap();
+        self.publish_fill();
+
+        if new_target < target {
+            self.close_surplus_connections();
+        }
+    }
+
+    // Closes the least loaded connections above the target size.
+    // Requests in flight on them are allowed to complete.
+    fn close_surplus_connections(&mut self) {
+        let target = self.target_size.get();
+
+        let least_loaded = |conns: &[Arc<Connection>]| {
+            conns
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, conn)| conn.in_flight_requests())
+                .map(|(idx, conn)| (idx, conn.in_flight_requests()))
+        };
+
+        let mut closed = 0;
+        match self.pool_config.pool_size {
+            PoolSize::PerShard(_) => {
+                for shard_conns in self.conns.iter_mut() {
+                    while shard_conns.len() > target {
+                        let (idx, _) = least_loaded(shard_conns).unwrap();
+                        shard_conns.swap_remove(idx);
+                        closed += 1;
+                    }
+                }
+            }
+            PoolSize::PerHost(_) => {
+                while self.active_connection_count() > target {
+                    let (shard_id, idx) = self
+                        .conns
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(shard_id, shard_conns)| {
+                            least_loaded(shard_conns)
+                                .map(|(idx, in_flight)| (shard_id, idx, in_flight))
+                        })
+                        .min_by_key(|(_, _, in_flight)| *in_flight)
+                        .map(|(shard_id, idx, _)| (shard_id, idx))
+                        .unwrap();
+                    self.conns[shard_id].swap_remove(idx);
+                    closed += 1;
+                }
+            }
+        }
+
+        if closed == 0 {
+            return;
+        }
+        self.forget_closed_stale_connections();
+        trace!(
+            "[{}] Closed {} surplus connections, total {}",
+            self.endpoint_description(),
+            closed,
+            self.active_connection_count(),
+        );
+        for _ in 0..closed {
+            #[cfg(feature = "metrics")]
+            self.metrics.dec_total_connections();
+            self.report_connection_event(ConnectionEventKind::Closed);
+        }
+
+        // The target is positive, so the pool can't have become empty.
+        self.update_shared_conns(None);
+    }
+
     fn active_connection_count(&self) -> usize {
         self.conns.iter().map(Vec::len).sum::<usize>()
     }
 
+    fn is_stale(&self, connection: &Arc<Connection>) -> bool {
+        self.stale_connections
+            .iter()
+            .any(|stale_conn| Arc::ptr_eq(stale_conn, connection))
+    }
+
+    // Counts the connections of a shard which are connected to the current address.
+    fn fresh_shard_connection_count(&self, shard_conns: &[Arc<Connection>]) -> usize {
+        shard_conns
+            .iter()
+            .filter(|conn| !self.is_stale(conn))
+            .count()
+    }
+
+    // Counts the connections which are connected to the current address.
+    fn fresh_connection_count(&self) -> usize {
+        self.active_connection_count() - self.stale_connections.len()
+    }
+
+    // Begins migrating the pool to the new address of the node.
+    // All current connections become stale and are replaced one by one
+    // as connections to the new address are opened by refills.
+    fn start_migration(&mut self) {
+        self.endpoint_generation += 1;
+        self.stale_connections = self.conns.iter().flatten().cloned().collect();
+        self.excess_connections.clear();
+
+        debug!(
+            "[{}] Node changed its address, migrating {} connections",
+            self.endpoint_description(),
+            self.stale_connections.len(),
+        );
+    }
+
+    // Closes a stale connection after a fresh one was added to the given shard.
+    // With `PerShard` pool size, the stale connection is taken from the same shard.
+    // Requests in flight on it are allowed to complete.
+    fn retire_stale_connection(&mut self, shard_id: usize) {
+        let shards = match self.pool_config.pool_size {
+            PoolSize::PerShard(_) => shard_id..shard_id + 1,
+            PoolSize::PerHost(_) => 0..self.conns.len(),
+        };
+        let Some((shard_id, idx)) = shards.into_iter().find_map(|shard_id| {
+            self.conns[shard_id]
+                .iter()
+                .position(|conn| self.is_stale(conn))
+                .map(|idx| (shard_id, idx))
+        }) else {
+            return;
+        };
+
+        let conn = self.conns[shard_id].swap_remove(idx);
+        self.stale_connections
+            .retain(|stale_conn| !Arc::ptr_eq(stale_conn, &conn));
+        trace!(
+            "[{}] Replaced connection {:p} to the previous address, {} left to migrate",
+            self.endpoint_description(),
+            Arc::as_ptr(&conn),
+            self.stale_connections.len(),
+        );
+        #[cfg(feature = "metrics")]
+        self.metrics.dec_total_connections();
+        self.report_connection_event(ConnectionEventKind::Closed);
+    }
+
+    // Drops the stale connections which are not in the pool anymore.
+    fn forget_closed_stale_connections(&mut self) {
+        let conns = &self.conns;
+        self.stale_connections.retain(|stale_conn| {
+            conns
+                .iter()
+                .flatten()
+                .any(|conn| Arc::ptr_eq(conn, stale_conn))
+        });
+    }
+
     fn excess_connection_limit(&self) -> usize {
         match self.pool_config.pool_size {
             PoolSize::PerShard(_) => {
@@ -1293,10 +1871,34 @@ async fn wait_for_error(
     }
 }
 
+// Waits until one more connection may be opened to the node, if the concurrency is limited.
+async fn acquire_open_permit(open_limiter: Option<&Semaphore>) -> Option<SemaphorePermit<'_>> {
+    match open_limiter {
+        Some(open_limiter) => Some(
+            open_limiter
+                .acquire()
+                .await
+                .expect("BUG: The semaphore limiting connection opens is never closed"),
+        ),
+        None => None,
+    }
+}
+
+// Never resolves if the pool is not autoscaled.
+async fn tick_if_autoscaled(interval: Option<&mut tokio::time::Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
 struct OpenedConnectionEvent {
     result: Result<(Connection, ErrorReceiver), ConnectionError>,
     requested_shard: Option<Shard>,
     keyspace_name: Option<VerifiedKeyspaceName>,
+    endpoint_generation: u64,
 }
 
 /// Signals that connectivity to a node has changed.
@@ -1321,12 +1923,19 @@ impl ConnectivityChangeEvent {
 #[cfg(test)]
 mod tests {
     use super::super::connection::{HostConnectionConfig, open_connection_to_shard_aware_port};
-    use crate::cluster::metadata::UntranslatedEndpoint;
-    use crate::cluster::node::ResolvedContactPoint;
+    use super::{NodeConnectionPool, PoolConfig, PoolSize};
+    use crate::cluster::metadata::{PeerEndpoint, UntranslatedEndpoint};
+    use crate::cluster::node::{NodeAddr, ResolvedContactPoint};
     use crate::network::TcpSocketOptions;
     use crate::routing::{ShardCount, Sharder};
     use crate::test_utils::setup_tracing;
+    use crate::utils::test_utils::resolve_hostname;
+    use scylla_proxy::{Node, Proxy, ShardAwareness};
     use std::net::{SocketAddr, ToSocketAddrs};
+    use std::num::NonZeroUsize;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use uuid::Uuid;
 
     async fn test_many_connections_with_config(connection_config: HostConnectionConfig) {
         let connections_number = 400;
@@ -1385,4 +1994,94 @@ mod tests {
         })
         .await;
     }
+
+    // The node becomes reachable under a new address: the pool should
+    // replace its connections with ones to the new address, never becoming empty.
+    #[tokio::test]
+    async fn connections_migrate_when_node_changes_address() {
+        setup_tracing();
+
+        let uri = std::env::var("SCYLLA_URI").unwrap_or_else(|_| "172.42.0.2:9042".to_string());
+        let node_addr: SocketAddr = resolve_hostname(&uri).await;
+        let proxy_addr = SocketAddr::new(scylla_proxy::get_exclusive_local_address(), 9042);
+
+        // The proxy exposes the same node under a different address.
+        let proxy = Proxy::builder()
+            .with_node(
+                Node::builder()
+                    .proxy_address(proxy_addr)
+                    .real_address(node_addr)
+                    .shard_awareness(ShardAwareness::QueryNode)
+                    .build(),
+            )
+            .build()
+            .run()
+            .await
+            .unwrap();
+
+        let connection_count = 3;
+        let pool_config = PoolConfig {
+            pool_size: PoolSize::PerHost(NonZeroUsize::new(connection_count).unwrap()),
+            can_use_shard_aware_port: false,
+            ..Default::default()
+        };
+        let peer_endpoint = |address| PeerEndpoint {
+            host_id: Uuid::new_v4(),
+            address: NodeAddr::Translatable(address),
+            datacenter: None,
+            rack: None,
+        };
+        let (pool_empty_notifier, _pool_empty_receiver) = mpsc::channel(1);
+        let pool = NodeConnectionPool::new(
+            UntranslatedEndpoint::Peer(peer_endpoint(node_addr)),
+            &pool_config,
+            None,
+            None,
+            pool_empty_notifier,
+            #[cfg(feature = "metrics")]
+            Default::default(),
+        );
+
+        let connect_addresses = |pool: &NodeConnectionPool| {
+            pool.get_working_connections()
+                .unwrap()
+                .iter()
+                .map(|conn| conn.get_connect_address())
+                .collect::<Vec<_>>()
+        };
+
+        pool.wait_until_initialized().await;
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while connect_addresses(&pool).len() < connection_count {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert!(
+            connect_addresses(&pool)
+                .iter()
+                .all(|address| *address == node_addr)
+        );
+
+        pool.update_endpoint(peer_endpoint(proxy_addr));
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                // The pool keeps serving requests while it migrates.
+                let addresses = connect_addresses(&pool);
+                assert!(!addresses.is_empty());
+                if addresses.len() == connection_count
+                    && addresses.iter().all(|address| *address == proxy_addr)
+                {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        let _ = proxy.finish().await;
+    }
 }
@@ -0,0 +1,110 @@
+//! Selection of a connection among the connections of a per-node pool
+//! which can serve a request (i.e. the connections to the target shard).
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rand::Rng;
+
+/// Strategy of picking a connection for a request among the pooled connections
+/// to the target node (or shard), when there is more than one.
+///
+/// See [`SessionBuilder::connection_selection`](crate::client::session_builder::SessionBuilder::connection_selection).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConnectionSelection {
+    /// Picks a connection at random.
+    #[default]
+    Random,
+
+    /// Picks connections in turns.
+    RoundRobin,
+
+    /// Picks the connection with the fewest in-flight requests.
+    /// Ties are resolved in favour of the earliest connection in the pool.
+    LeastInFlight,
+}
+
+/// Picks a connection according to the strategy, remembering the position of the round-robin.
+#[derive(Debug)]
+pub(crate) struct ConnectionSelector {
+    strategy: ConnectionSelection,
+    round_robin_position: AtomicUsize,
+}
+
+impl ConnectionSelector {
+    pub(crate) fn new(strategy: ConnectionSelection) -> Self {
+        Self {
+            strategy,
+            round_robin_position: AtomicUsize::new(0),
+        }
+    }
+
+    /// Picks one of `items`, or returns `None` if there are none.
+    pub(crate) fn select<'a, T>(
+        &self,
+        items: &'a [T],
+        in_flight_requests: impl Fn(&T) -> usize,
+    ) -> Option<&'a T> {
+        match items {
+            [] => None,
+            [item] => Some(item),
+            _ => match self.strategy {
+                ConnectionSelection::Random => items.get(rand::rng().random_range(0..items.len())),
+                ConnectionSelection::RoundRobin => {
+                    let position = self.round_robin_position.fetch_add(1, Ordering::Relaxed);
+                    items.get(position % items.len())
+                }
+                ConnectionSelection::LeastInFlight => {
+                    items.iter().min_by_key(|item| in_flight_requests(item))
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConnectionSelection, ConnectionSelector};
+
+    // Connections are represented by their number of in-flight requests.
+    fn in_flight(conn: &usize) -> usize {
+        *conn
+    }
+
+    #[test]
+    fn least_in_flight_picks_least_loaded_connection() {
+        let selector = ConnectionSelector::new(ConnectionSelection::LeastInFlight);
+        let conns = [7, 3, 12, 1, 5];
+        for _ in 0..10 {
+            assert_eq!(selector.select(&conns, in_flight), Some(&1));
+        }
+
+        // The earliest of the least loaded connections is picked.
+        let conns = [4, 2, 8, 2];
+        let picked = selector.select(&conns, in_flight).unwrap();
+        assert!(std::ptr::eq(picked, &conns[1]));
+    }
+
+    #[test]
+    fn round_robin_picks_connections_in_turns() {
+        let selector = ConnectionSelector::new(ConnectionSelection::RoundRobin);
+        let conns = [10, 20, 30];
+        let picked = (0..6)
+            .map(|_| *selector.select(&conns, in_flight).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(picked, [10, 20, 30, 10, 20, 30]);
+    }
+
+    #[test]
+    fn every_strategy_handles_small_pools() {
+        for strategy in [
+            ConnectionSelection::Random,
+            ConnectionSelection::RoundRobin,
+            ConnectionSelection::LeastInFlight,
+        ] {
+            let selector = ConnectionSelector::new(strategy);
+            assert_eq!(selector.select(&[] as &[usize], in_flight), None);
+            assert_eq!(selector.select(&[42], in_flight), Some(&42));
+        }
+    }
+}
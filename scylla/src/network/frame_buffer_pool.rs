@@ -0,0 +1,142 @@
+//! A pool of buffers for serializing and receiving frames.
+//!
+//! All connections of a session share a single [`FrameBufferPool`]. A request frame
+//! is serialized into a buffer taken from the pool, and the buffer is given back once
+//! the frame is written to the socket, so that sustained traffic does not allocate
+//! a new buffer for every request. The same goes for the scratch buffer holding
+//! the uncompressed body of a compressed request.
+//!
+//! The body of a compressed response frame is read into a pooled buffer as well,
+//! which is given back once the body is decompressed. Uncompressed response bodies
+//! are not pooled, as they are handed over to the results without copying.
+
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+#[cfg(test)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug)]
+pub(crate) struct FrameBufferPool {
+    buffers: StdMutex<Vec<Vec<u8>>>,
+    max_pooled: usize,
+    // Buffers which grew larger than this are freed instead of being pooled,
+    // so that a few huge requests don't keep their memory pinned forever.
+    max_retained_size: usize,
+    // Number of buffers the pool could not provide, so new ones had to be allocated.
+    #[cfg(test)]
+    allocated: AtomicUsize,
+}
+
+impl FrameBufferPool {
+    /// Creates a pool which keeps at most `max_pooled` idle buffers,
+    /// each of capacity at most `max_retained_size` bytes.
+    pub(crate) fn new(max_pooled: usize, max_retained_size: usize) -> Arc<Self> {
+        Arc::new(Self {
+            buffers: StdMutex::new(Vec::with_capacity(max_pooled)),
+            max_pooled,
+            max_retained_size,
+            #[cfg(test)]
+            allocated: AtomicUsize::new(0),
+        })
+    }
+
+    /// Takes an idle buffer from the pool, or returns a new, empty one if there are none.
+    pub(crate) fn take(&self) -> Vec<u8> {
+        let buffer = self.buffers.lock().unwrap().pop();
+        #[cfg(test)]
+        if buffer.is_none() {
+            self.allocated.fetch_add(1, Ordering::Relaxed);
+        }
+        buffer.unwrap_or_default()
+    }
+
+    /// Gives a buffer back to the pool, unless the pool is full or the buffer is too large.
+    pub(crate) fn give_back(&self, mut buffer: Vec<u8>) {
+        if buffer.capacity() == 0 || buffer.capacity() > self.max_retained_size {
+            return;
+        }
+        buffer.clear();
+
+        let mut buffers = self.buffers.lock().unwrap();
+        if buffers.len() < self.max_pooled {
+            buffers.push(buffer);
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn allocated(&self) -> usize {
+        self.allocated.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::FrameBufferPool;
+
+    const MAX_RETAINED_SIZE: usize = 64 * 1024;
+
+    // Simulates `requests` requests, at most `concurrency` of which are in flight at once.
+    async fn sustained_load(pool: Arc<FrameBufferPool>, requests: usize, concurrency: usize) {
+        let mut tasks = Vec::with_capacity(concurrency);
+        for worker in 0..concurrency {
+            let pool = Arc::clone(&pool);
+            tasks.push(tokio::spawn(async move {
+                for request in (worker..requests).step_by(concurrency) {
+                    let mut buffer = pool.take();
+                    buffer.resize(128 + request % 512, 0);
+                    tokio::task::yield_now().await;
+                    pool.give_back(buffer);
+                }
+            }));
+        }
+        for task in tasks {
+            task.await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn pooling_reduces_allocations_under_sustained_load() {
+        const REQUESTS: usize = 10_000;
+        const CONCURRENCY: usize = 16;
+
+        let unpooled = FrameBufferPool::new(0, MAX_RETAINED_SIZE);
+        sustained_load(Arc::clone(&unpooled), REQUESTS, CONCURRENCY).await;
+        assert_eq!(unpooled.allocated(), REQUESTS);
+
+        let pooled = FrameBufferPool::new(CONCURRENCY, MAX_RETAINED_SIZE);
+        sustained_load(Arc::clone(&pooled), REQUESTS, CONCURRENCY).await;
+        assert!(
+            pooled.allocated() <= CONCURRENCY,
+            "{} buffers were allocated for {} requests",
+            pooled.allocated(),
+            REQUESTS
+        );
+    }
+
+    #[test]
+    fn oversized_buffers_are_not_pooled() {
+        let pool = FrameBufferPool::new(4, MAX_RETAINED_SIZE);
+        pool.give_back(Vec::with_capacity(MAX_RETAINED_SIZE + 1));
+        let buffer = pool.take();
+        assert_eq!(buffer.capacity(), 0);
+        assert_eq!(pool.allocated(), 1);
+    }
+
+    #[test]
+    fn buffers_above_configured_max_retained_size_are_not_pooled() {
+        const SMALL_LIMIT: usize = 1024;
+        let pool = FrameBufferPool::new(4, SMALL_LIMIT);
+
+        // A buffer within the limit is retained and reused.
+        pool.give_back(Vec::with_capacity(SMALL_LIMIT));
+        assert_eq!(pool.take().capacity(), SMALL_LIMIT);
+        assert_eq!(pool.allocated(), 0);
+
+        // A buffer grown by one huge request is dropped rather than pooled.
+        pool.give_back(Vec::with_capacity(16 * 1024 * 1024));
+        assert_eq!(pool.take().capacity(), 0);
+        assert_eq!(pool.allocated(), 1);
+    }
+}
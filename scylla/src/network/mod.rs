@@ -4,15 +4,31 @@
 //! - Connection - a single, possibly encrypted, connection to a ScyllaDB node over CQL protocol,
 //! - NodeConnectionPool - a manager that keeps a desired number of connections opened to each shard.
 
+mod circuit_breaker;
 mod connection;
+mod connection_budget;
+mod connection_selection;
+mod frame_buffer_pool;
+mod pool_autoscaling;
+mod repreparation;
 
 pub(crate) use connection::open_connection;
 
-pub(crate) use connection::{Connection, ConnectionConfig, TcpSocketOptions, VerifiedKeyspaceName};
+pub(crate) use connection::{
+    Connection, ConnectionConfig, StreamIdSequence, TcpSocketOptions, VerifiedKeyspaceName,
+};
+pub(crate) use connection_budget::ConnectionBudget;
+pub(crate) use frame_buffer_pool::FrameBufferPool;
+pub(crate) use repreparation::RepreparationSet;
 
 mod connection_pool;
 
-pub use connection::WriteCoalescingDelay;
+pub use circuit_breaker::{ErrorRateCircuitBreaker, InvalidErrorRateCircuitBreaker};
+pub use connection::{ServerOptions, WriteCoalescingDelay};
 pub use connection_pool::PoolSize;
-pub(crate) use connection_pool::{ConnectivityChangeEvent, NodeConnectionPool, PoolConfig};
+pub(crate) use connection_pool::{
+    ConnectivityChangeEvent, FreshConnection, NodeConnectionPool, PoolConfig, PoolFillState,
+};
+pub use connection_selection::ConnectionSelection;
+pub use pool_autoscaling::{InvalidPoolAutoscaling, PoolAutoscaling};
 pub(crate) mod tls;
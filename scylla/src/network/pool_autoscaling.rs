@@ -0,0 +1,225 @@
+//! Autoscaling of per-node connection pools, based on the number of in-flight requests.
+//!
+//! Periodically, each pool computes the average number of in-flight requests per
+//! connection. If it reaches the scale-up threshold, the pool opens one more connection
+//! per host or per shard (depending on [`PoolSize`](super::PoolSize)). If it drops
+//! to the scale-down threshold, the least loaded surplus connections are closed.
+
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use thiserror::Error;
+
+const DEFAULT_SCALE_UP_THRESHOLD: usize = 800;
+const DEFAULT_SCALE_DOWN_THRESHOLD: usize = 100;
+const DEFAULT_EVALUATION_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Configuration of connection pools which scale dynamically with load.
+///
+/// See [`SessionBuilder::pool_autoscaling`](crate::client::session_builder::SessionBuilder::pool_autoscaling).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolAutoscaling {
+    min_connections: NonZeroUsize,
+    max_connections: NonZeroUsize,
+    scale_down_threshold: usize,
+    scale_up_threshold: usize,
+    evaluation_interval: Duration,
+}
+
+impl PoolAutoscaling {
+    /// Creates a configuration which keeps between `min_connections` and `max_connections`
+    /// connections per host or per shard, as chosen by [`PoolSize`](super::PoolSize).
+    ///
+    /// The pool scales up when connections have 800 in-flight requests on average,
+    /// and scales down when they have 100 or less. The load is evaluated every second.
+    ///
+    /// The error is returned if `min_connections` is greater than `max_connections`.
+    pub fn new(
+        min_connections: NonZeroUsize,
+        max_connections: NonZeroUsize,
+    ) -> Result<Self, InvalidPoolAutoscaling> {
+        if min_connections > max_connections {
+            return Err(InvalidPoolAutoscaling::MinGreaterThanMax);
+        }
+        Ok(Self {
+            min_connections,
+            max_connections,
+            scale_down_threshold: DEFAULT_SCALE_DOWN_THRESHOLD,
+            scale_up_threshold: DEFAULT_SCALE_UP_THRESHOLD,
+            evaluation_interval: DEFAULT_EVALUATION_INTERVAL,
+        })
+    }
+
+    /// Sets the average number of in-flight requests per connection at which the pool
+    /// scales down (`scale_down`, inclusive) and up (`scale_up`, inclusive).
+    ///
+    /// The error is returned if `scale_down` is not lower than `scale_up`.
+    pub fn with_load_thresholds(
+        mut self,
+        scale_down: usize,
+        scale_up: usize,
+    ) -> Result<Self, InvalidPoolAutoscaling> {
+        if scale_down >= scale_up {
+            return Err(InvalidPoolAutoscaling::ThresholdsNotIncreasing);
+        }
+        self.scale_down_threshold = scale_down;
+        self.scale_up_threshold = scale_up;
+        Ok(self)
+    }
+
+    /// Sets how often the pool evaluates its load. The pool grows or shrinks
+    /// by at most one connection per host or per shard on each evaluation.
+    ///
+    /// The error is returned if `interval` is zero.
+    pub fn with_evaluation_interval(
+        mut self,
+        interval: Duration,
+    ) -> Result<Self, InvalidPoolAutoscaling> {
+        if interval.is_zero() {
+            return Err(InvalidPoolAutoscaling::ZeroEvaluationInterval);
+        }
+        self.evaluation_interval = interval;
+        Ok(self)
+    }
+
+    /// The minimum number of connections per host or per shard.
+    pub fn min_connections(&self) -> NonZeroUsize {
+        self.min_connections
+    }
+
+    /// The maximum number of connections per host or per shard.
+    pub fn max_connections(&self) -> NonZeroUsize {
+        self.max_connections
+    }
+
+    /// The average number of in-flight requests per connection
+    /// at or below which the pool scales down.
+    pub fn scale_down_threshold(&self) -> usize {
+        self.scale_down_threshold
+    }
+
+    /// The average number of in-flight requests per connection
+    /// at or above which the pool scales up.
+    pub fn scale_up_threshold(&self) -> usize {
+        self.scale_up_threshold
+    }
+
+    /// How often the pool evaluates its load.
+    pub fn evaluation_interval(&self) -> Duration {
+        self.evaluation_interval
+    }
+
+    /// Chooses the next target number of connections per host or per shard,
+    /// given that `connections` connections have `in_flight_requests` requests in flight.
+    pub(crate) fn next_target(
+        &self,
+        target: usize,
+        connections: usize,
+        in_flight_requests: usize,
+    ) -> usize {
+        let target = target.clamp(self.min_connections.get(), self.max_connections.get());
+        if connections == 0 {
+            return target;
+        }
+
+        let load = in_flight_requests / connections;
+        if load >= self.scale_up_threshold && target < self.max_connections.get() {
+            target + 1
+        } else if load <= self.scale_down_threshold && target > self.min_connections.get() {
+            target - 1
+        } else {
+            target
+        }
+    }
+}
+
+/// An error returned when building an invalid [`PoolAutoscaling`].
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InvalidPoolAutoscaling {
+    /// The minimum number of connections is greater than the maximum.
+    #[error("Invalid pool autoscaling: the minimum number of connections exceeds the maximum")]
+    MinGreaterThanMax,
+
+    /// The scale-down threshold is not lower than the scale-up threshold.
+    #[error(
+        "Invalid pool autoscaling: the scale-down threshold must be lower than the scale-up threshold"
+    )]
+    ThresholdsNotIncreasing,
+
+    /// The evaluation interval is zero.
+    #[error("Invalid pool autoscaling: the evaluation interval must be positive")]
+    ZeroEvaluationInterval,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::time::Duration;
+
+    use super::{InvalidPoolAutoscaling, PoolAutoscaling};
+
+    fn autoscaling(min: usize, max: usize) -> PoolAutoscaling {
+        PoolAutoscaling::new(
+            NonZeroUsize::new(min).unwrap(),
+            NonZeroUsize::new(max).unwrap(),
+        )
+        .unwrap()
+        .with_load_thresholds(10, 100)
+        .unwrap()
+    }
+
+    #[test]
+    fn invalid_configurations_are_rejected() {
+        let one = NonZeroUsize::new(1).unwrap();
+        let two = NonZeroUsize::new(2).unwrap();
+        assert_eq!(
+            PoolAutoscaling::new(two, one),
+            Err(InvalidPoolAutoscaling::MinGreaterThanMax)
+        );
+        let valid = PoolAutoscaling::new(one, two).unwrap();
+        assert_eq!(
+            valid.with_load_thresholds(100, 100),
+            Err(InvalidPoolAutoscaling::ThresholdsNotIncreasing)
+        );
+        assert_eq!(
+            valid.with_evaluation_interval(Duration::ZERO),
+            Err(InvalidPoolAutoscaling::ZeroEvaluationInterval)
+        );
+        assert!(
+            valid
+                .with_evaluation_interval(Duration::from_millis(1))
+                .is_ok()
+        );
+        assert!(PoolAutoscaling::new(one, one).is_ok());
+    }
+
+    #[test]
+    fn target_follows_load_within_bounds() {
+        let autoscaling = autoscaling(1, 3);
+
+        // Load goes up: the pool grows one connection at a time, up to the maximum.
+        let mut target = 1;
+        for expected in [2, 3, 3] {
+            target = autoscaling.next_target(target, target, 150 * target);
+            assert_eq!(target, expected);
+        }
+
+        // Moderate load keeps the pool as it is.
+        assert_eq!(autoscaling.next_target(target, target, 50 * target), 3);
+
+        // Load goes down: the pool shrinks one connection at a time, down to the minimum.
+        for expected in [2, 1, 1] {
+            target = autoscaling.next_target(target, target, target);
+            assert_eq!(target, expected);
+        }
+    }
+
+    #[test]
+    fn target_of_empty_pool_is_kept() {
+        let autoscaling = autoscaling(1, 3);
+        assert_eq!(autoscaling.next_target(2, 0, 0), 2);
+        // Targets outside of bounds are brought back into them.
+        assert_eq!(autoscaling.next_target(5, 0, 0), 3);
+    }
+}
@@ -0,0 +1,114 @@
+//! Proactive re-preparation of cached statements on newly opened connections.
+//!
+//! Nodes forget prepared statements when they restart, and a statement unknown
+//! to a node is reprepared only after an execution fails with UNPREPARED.
+//! To avoid this penalty, statements registered in the session's [`RepreparationSet`]
+//! (i.e. those cached by [`CachingSession`](crate::client::caching_session::CachingSession)s
+//! wrapping the session) are prepared in the background on each connection
+//! which is added to a connection pool.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use futures::StreamExt;
+use tracing::{debug, trace};
+
+use super::Connection;
+use crate::statement::unprepared::Statement;
+
+/// Maximum number of PREPARE requests sent at once on a single connection.
+const REPREPARATION_CONCURRENCY: usize = 16;
+
+/// Statements which are reprepared on newly opened connections.
+#[derive(Debug, Default)]
+pub(crate) struct RepreparationSet {
+    /// Contents of the registered statements, with the number of their registrations.
+    /// A statement may be cached by more than one `CachingSession`.
+    statements: Mutex<HashMap<String, usize>>,
+}
+
+impl RepreparationSet {
+    pub(crate) fn register(&self, contents: &str) {
+        *self
+            .statements
+            .lock()
+            .unwrap()
+            .entry(contents.to_owned())
+            .or_default() += 1;
+    }
+
+    pub(crate) fn deregister(&self, contents: &str) {
+        let mut statements = self.statements.lock().unwrap();
+        if let Some(registrations) = statements.get_mut(contents) {
+            *registrations -= 1;
+            if *registrations == 0 {
+                statements.remove(contents);
+            }
+        }
+    }
+
+    fn statements(&self) -> Vec<String> {
+        self.statements.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Prepares all registered statements on the connection.
+    ///
+    /// Failures are only logged, as the statements will be reprepared
+    /// on UNPREPARED anyway.
+    pub(crate) async fn reprepare_on(&self, connection: Arc<Connection>) {
+        let statements = self.statements();
+        if statements.is_empty() {
+            return;
+        }
+        trace!(
+            "[{}] Repreparing {} statements on a new connection",
+            connection.get_connect_address(),
+            statements.len()
+        );
+
+        futures::stream::iter(statements)
+            .for_each_concurrent(REPREPARATION_CONCURRENCY, |contents| {
+                let connection = &connection;
+                async move {
+                    let statement = Statement::new(contents);
+                    if let Err(err) = connection.prepare_raw(&statement).await {
+                        debug!(
+                            "[{}] Failed to reprepare statement \"{}\" on a new connection: {}",
+                            connection.get_connect_address(),
+                            statement.contents,
+                            err
+                        );
+                    }
+                }
+            })
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RepreparationSet;
+
+    #[test]
+    fn statements_are_kept_until_all_registrations_are_dropped() {
+        let set = RepreparationSet::default();
+        set.register("SELECT a FROM ks.t");
+        set.register("SELECT a FROM ks.t");
+        set.register("SELECT b FROM ks.t");
+
+        let mut statements = set.statements();
+        statements.sort();
+        assert_eq!(statements, ["SELECT a FROM ks.t", "SELECT b FROM ks.t"]);
+
+        set.deregister("SELECT a FROM ks.t");
+        set.deregister("SELECT b FROM ks.t");
+        assert_eq!(set.statements(), ["SELECT a FROM ks.t"]);
+
+        set.deregister("SELECT a FROM ks.t");
+        assert!(set.statements().is_empty());
+
+        // Deregistering an unknown statement is a no-op.
+        set.deregister("SELECT c FROM ks.t");
+        assert!(set.statements().is_empty());
+    }
+}
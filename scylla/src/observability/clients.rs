@@ -0,0 +1,61 @@
+//! Implements cross-checking of driver's connections against the cluster's view of them.
+//!
+//! ScyllaDB exposes the client connections it currently serves in the node-local
+//! `system.clients` table. [`Session::server_side_connections`](crate::client::session::Session::server_side_connections)
+//! reads it from every node and returns the rows describing the session's own connections.
+
+use crate::DeserializeRow;
+use std::net::{IpAddr, SocketAddr};
+
+/// A single client connection, as seen by the server in `system.clients`.
+#[derive(Debug, DeserializeRow, Clone, PartialEq, Eq)]
+#[scylla(crate = "crate")]
+pub struct ServerClientInfo {
+    /// Address of the client, as seen by the server.
+    pub address: IpAddr,
+
+    /// Port of the client, as seen by the server.
+    pub port: i32,
+
+    /// Type of the client, e.g. `cql`.
+    pub client_type: Option<String>,
+
+    /// Stage of the connection, e.g. `READY`.
+    pub connection_stage: Option<String>,
+
+    /// Name of the driver, as announced by the client in STARTUP.
+    pub driver_name: Option<String>,
+
+    /// Version of the driver, as announced by the client in STARTUP.
+    pub driver_version: Option<String>,
+
+    /// Hostname of the client.
+    pub hostname: Option<String>,
+
+    /// Version of the CQL protocol used on the connection.
+    pub protocol_version: Option<i32>,
+
+    /// Shard that handles the connection.
+    pub shard_id: Option<i32>,
+
+    /// Whether the connection uses TLS.
+    pub ssl_enabled: Option<bool>,
+
+    /// Name of the authenticated user, if any.
+    pub username: Option<String>,
+}
+
+impl ServerClientInfo {
+    /// Socket address of the client, as seen by the server.
+    ///
+    /// Returns `None` if the reported port does not fit in `u16`.
+    pub fn client_address(&self) -> Option<SocketAddr> {
+        let port = u16::try_from(self.port).ok()?;
+        Some(SocketAddr::new(self.address, port))
+    }
+}
+
+// A query used to query ServerClientInfo from system.clients
+pub(crate) const SYSTEM_CLIENTS_QUERY_STR: &str = "SELECT address, port, client_type, connection_stage, \
+    driver_name, driver_version, hostname, protocol_version, shard_id, ssl_enabled, username \
+    FROM system.clients";
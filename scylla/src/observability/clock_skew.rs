@@ -0,0 +1,94 @@
+//! Measuring the skew between the local clock and the clock of a coordinator.
+//!
+//! When a write does not carry a client-side timestamp, the coordinator assigns it
+//! one from its own clock. If the client and coordinator clocks diverge, writes with
+//! client-side timestamps may be ordered differently than expected against writes
+//! with server-side ones. [`Session::measure_clock_skew`](crate::client::session::Session::measure_clock_skew)
+//! helps to debug such issues.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Query returning the current time of the coordinator, in milliseconds since the unix epoch.
+pub(crate) const COORDINATOR_TIME_QUERY_STR: &str =
+    "SELECT toUnixTimestamp(now()) FROM system.local WHERE key='local'";
+
+/// The skew between the local clock and the clock of a coordinator,
+/// as measured by [`Session::measure_clock_skew`](crate::client::session::Session::measure_clock_skew).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockSkew {
+    offset: Duration,
+    coordinator_ahead: bool,
+    round_trip_time: Duration,
+}
+
+impl ClockSkew {
+    /// Compares the time of the coordinator to the local time in the middle of the request,
+    /// which was sent at `sent` and whose response was received at `received`.
+    pub(crate) fn from_measurement(
+        sent: SystemTime,
+        received: SystemTime,
+        coordinator_millis: i64,
+    ) -> Self {
+        let round_trip_time = received.duration_since(sent).unwrap_or_default();
+        let local_micros = micros_since_epoch(sent) + round_trip_time.as_micros() as i128 / 2;
+        let skew_micros = coordinator_millis as i128 * 1000 - local_micros;
+
+        Self {
+            offset: Duration::from_micros(
+                skew_micros.unsigned_abs().try_into().unwrap_or(u64::MAX),
+            ),
+            coordinator_ahead: skew_micros > 0,
+            round_trip_time,
+        }
+    }
+
+    /// The absolute difference between the coordinator's clock and the local clock.
+    ///
+    /// The measurement is accurate up to half of [`round_trip_time()`](Self::round_trip_time),
+    /// plus a millisecond, which is the resolution of the coordinator's time.
+    pub fn offset(&self) -> Duration {
+        self.offset
+    }
+
+    /// Whether the coordinator's clock is ahead of the local clock.
+    pub fn is_coordinator_ahead(&self) -> bool {
+        self.coordinator_ahead
+    }
+
+    /// The round-trip time of the request used for the measurement.
+    pub fn round_trip_time(&self) -> Duration {
+        self.round_trip_time
+    }
+}
+
+fn micros_since_epoch(time: SystemTime) -> i128 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since_epoch) => since_epoch.as_micros() as i128,
+        Err(err) => -(err.duration().as_micros() as i128),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::ClockSkew;
+
+    #[test]
+    fn skew_is_measured_against_the_middle_of_the_request() {
+        let sent = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let received = sent + Duration::from_millis(10);
+
+        let behind = ClockSkew::from_measurement(sent, received, 1_000_000_000 - 250);
+        assert_eq!(behind.offset(), Duration::from_millis(255));
+        assert!(!behind.is_coordinator_ahead());
+        assert_eq!(behind.round_trip_time(), Duration::from_millis(10));
+
+        let ahead = ClockSkew::from_measurement(sent, received, 1_000_000_000 + 250);
+        assert_eq!(ahead.offset(), Duration::from_millis(245));
+        assert!(ahead.is_coordinator_ahead());
+
+        let in_sync = ClockSkew::from_measurement(sent, received, 1_000_000_000 + 5);
+        assert_eq!(in_sync.offset(), Duration::ZERO);
+    }
+}
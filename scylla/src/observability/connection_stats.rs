@@ -0,0 +1,120 @@
+//! Implements per-connection statistics.
+//!
+//! Statistics of all connections currently opened by a session can be obtained
+//! with [`Session::connection_stats`](crate::client::session::Session::connection_stats).
+
+use crate::client::Compression;
+use crate::routing::Shard;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// A snapshot of statistics of a single connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConnectionStats {
+    /// Address of the node the connection is opened to.
+    pub node_address: SocketAddr,
+
+    /// Local address of the connection.
+    pub local_address: SocketAddr,
+
+    /// Shard of the node the connection is bound to, if the node is sharded.
+    pub shard: Option<Shard>,
+
+    /// Round-trip time measured by the most recent RTT probe.
+    ///
+    /// RTT probes are lightweight OPTIONS requests, periodically sent on each
+    /// connection when [`SessionBuilder::rtt_probe_interval`](crate::client::session_builder::SessionBuilder::rtt_probe_interval)
+    /// is set. `None` if probing is disabled or no probe has completed yet.
+    pub rtt: Option<Duration>,
+
+    /// Number of requests sent on the connection which are still awaiting a response.
+    pub in_flight_requests: usize,
+
+    /// Compression negotiated with the node when the connection was opened.
+    ///
+    /// `None` if compression was not requested, or if the node does not support
+    /// the requested algorithm, in which case the connection falls back to no compression.
+    pub compression: Option<Compression>,
+}
+
+/// Statistics collected during the lifetime of a connection,
+/// shared between the connection and its router task.
+#[derive(Debug, Default)]
+pub(crate) struct ConnectionStatsRecorder {
+    // In nanoseconds. Zero means that no RTT has been measured yet.
+    last_rtt: AtomicU64,
+    in_flight_requests: AtomicUsize,
+}
+
+impl ConnectionStatsRecorder {
+    pub(crate) fn record_rtt(&self, rtt: Duration) {
+        // Clamp to 1ns, as zero is reserved for "not measured".
+        let nanos = u64::try_from(rtt.as_nanos()).unwrap_or(u64::MAX).max(1);
+        self.last_rtt.store(nanos, Ordering::Relaxed);
+    }
+
+    pub(crate) fn last_rtt(&self) -> Option<Duration> {
+        match self.last_rtt.load(Ordering::Relaxed) {
+            0 => None,
+            nanos => Some(Duration::from_nanos(nanos)),
+        }
+    }
+
+    /// Counts a request as in flight until the returned guard is dropped.
+    pub(crate) fn start_request(&self) -> InFlightRequest<'_> {
+        self.in_flight_requests.fetch_add(1, Ordering::Relaxed);
+        InFlightRequest { recorder: self }
+    }
+
+    pub(crate) fn in_flight_requests(&self) -> usize {
+        self.in_flight_requests.load(Ordering::Relaxed)
+    }
+}
+
+/// A request counted by [`ConnectionStatsRecorder::start_request`].
+pub(crate) struct InFlightRequest<'a> {
+    recorder: &'a ConnectionStatsRecorder,
+}
+
+impl Drop for InFlightRequest<'_> {
+    fn drop(&mut self) {
+        self.recorder
+            .in_flight_requests
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::ConnectionStatsRecorder;
+
+    #[test]
+    fn rtt_is_none_until_recorded() {
+        let recorder = ConnectionStatsRecorder::default();
+        assert_eq!(recorder.last_rtt(), None);
+
+        recorder.record_rtt(Duration::from_micros(250));
+        assert_eq!(recorder.last_rtt(), Some(Duration::from_micros(250)));
+
+        // A zero RTT must still be reported as measured.
+        recorder.record_rtt(Duration::ZERO);
+        assert_eq!(recorder.last_rtt(), Some(Duration::from_nanos(1)));
+    }
+
+    #[test]
+    fn in_flight_requests_are_counted_until_finished() {
+        let recorder = ConnectionStatsRecorder::default();
+        let first = recorder.start_request();
+        let second = recorder.start_request();
+        assert_eq!(recorder.in_flight_requests(), 2);
+
+        drop(first);
+        assert_eq!(recorder.in_flight_requests(), 1);
+        drop(second);
+        assert_eq!(recorder.in_flight_requests(), 0);
+    }
+}
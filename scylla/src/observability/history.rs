@@ -7,7 +7,7 @@ use std::{
     time::SystemTime,
 };
 
-use crate::errors::{RequestAttemptError, RequestError};
+use crate::errors::{ConnectionPoolError, RequestAttemptError, RequestError};
 use crate::policies::retry::RetryDecision;
 use chrono::{DateTime, Utc};
 
@@ -66,6 +66,17 @@ pub trait HistoryListener: Debug + Send + Sync {
         error: &RequestAttemptError,
         retry_decision: &RetryDecision,
     );
+
+    /// Log that a node was skipped, because no connection to it could be chosen.
+    /// No attempt is started on such a node.
+    fn log_connection_pool_error(
+        &self,
+        _request_id: RequestId,
+        _speculative_id: Option<SpeculativeId>,
+        _node_addr: SocketAddr,
+        _error: &ConnectionPoolError,
+    ) {
+    }
 }
 
 /// A point in time when an event happened.
@@ -107,6 +118,14 @@ pub enum HistoryEvent {
     /// Attempt with [AttemptId] has finished with an error,
     /// including the error and retry decision.
     AttemptError(AttemptId, RequestAttemptError, RetryDecision),
+    /// A node was skipped by a request with [RequestId], because no connection
+    /// to it could be chosen.
+    ConnectionPoolError(
+        RequestId,
+        Option<SpeculativeId>,
+        SocketAddr,
+        ConnectionPoolError,
+    ),
 }
 
 impl HistoryCollectorData {
@@ -257,6 +276,23 @@ impl HistoryListener for HistoryCollector {
             ))
         })
     }
+
+    fn log_connection_pool_error(
+        &self,
+        request_id: RequestId,
+        speculative_id: Option<SpeculativeId>,
+        node_addr: SocketAddr,
+        error: &ConnectionPoolError,
+    ) {
+        self.do_with_data(|data| {
+            data.add_event(HistoryEvent::ConnectionPoolError(
+                request_id,
+                speculative_id,
+                node_addr,
+                error.clone(),
+            ))
+        })
+    }
 }
 
 /// Structured representation of requests history.\
@@ -283,6 +319,69 @@ pub struct RequestHistory {
     pub result: Option<RequestHistoryResult>,
 }
 
+impl RequestHistory {
+    /// Returns the failed attempts of the request, including those of speculative fibers,
+    /// in the order they failed. Each comes with the node it was sent to, its error
+    /// and the retry decision made in response to it.
+    pub fn failed_attempts(&self) -> Vec<(SocketAddr, &RequestAttemptError, &RetryDecision)> {
+        let mut failed_attempts = std::iter::once(&self.non_speculative_fiber)
+            .chain(&self.speculative_fibers)
+            .flat_map(|fiber| &fiber.attempts)
+            .filter_map(|attempt| match &attempt.result {
+                Some(AttemptResult::Error(time, error, retry_decision)) => {
+                    Some((*time, attempt.node_addr, error, retry_decision))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        failed_attempts.sort_by_key(|(time, ..)| *time);
+        failed_attempts
+            .into_iter()
+            .map(|(_, node_addr, error, retry_decision)| (node_addr, error, retry_decision))
+            .collect()
+    }
+
+    /// Returns the nodes on which the request failed, in the order they were first tried,
+    /// each with the last error that occurred on it. Besides failed attempts, this includes
+    /// the nodes which were skipped, because no connection to them could be chosen.
+    pub fn tried_nodes(&self) -> Vec<(SocketAddr, RequestError)> {
+        let fibers =
+            || std::iter::once(&self.non_speculative_fiber).chain(&self.speculative_fibers);
+        let mut failures = fibers()
+            .flat_map(|fiber| &fiber.attempts)
+            .filter_map(|attempt| match &attempt.result {
+                Some(AttemptResult::Error(time, error, _)) => Some((
+                    *time,
+                    attempt.node_addr,
+                    RequestError::LastAttemptError(error.clone()),
+                )),
+                _ => None,
+            })
+            .chain(
+                fibers()
+                    .flat_map(|fiber| &fiber.connection_pool_errors)
+                    .map(|(time, node_addr, error)| {
+                        (
+                            *time,
+                            *node_addr,
+                            RequestError::ConnectionPoolError(error.clone()),
+                        )
+                    }),
+            )
+            .collect::<Vec<_>>();
+        failures.sort_by_key(|(time, ..)| *time);
+
+        let mut tried_nodes: Vec<(SocketAddr, RequestError)> = Vec::new();
+        for (_, node_addr, error) in failures {
+            match tried_nodes.iter_mut().find(|(addr, _)| *addr == node_addr) {
+                Some((_, last_error)) => *last_error = error,
+                None => tried_nodes.push((node_addr, error)),
+            }
+        }
+        tried_nodes
+    }
+}
+
 /// Result of a request execution, either successful or with an error,
 /// including the time when it finished execution.
 #[derive(Debug, Clone)]
@@ -300,6 +399,9 @@ pub struct FiberHistory {
     pub start_time: TimePoint,
     /// List of attempts made within this speculative fiber.
     pub attempts: Vec<AttemptHistory>,
+    /// Nodes skipped within this speculative fiber, because no connection to them
+    /// could be chosen, each with the time it was skipped and the error.
+    pub connection_pool_errors: Vec<(TimePoint, SocketAddr, ConnectionPoolError)>,
 }
 
 /// History of a single attempt, including the time it was sent, the node it was sent to,
@@ -380,6 +482,7 @@ impl From<&HistoryCollectorData> for StructuredHistory {
                             non_speculative_fiber: FiberHistory {
                                 start_time: *event_time,
                                 attempts: Vec::new(),
+                                connection_pool_errors: Vec::new(),
                             },
                             speculative_fibers: Vec::new(),
                             result: None,
@@ -403,29 +506,44 @@ impl From<&HistoryCollectorData> for StructuredHistory {
                         FiberHistory {
                             start_time: *event_time,
                             attempts: Vec::new(),
+                            connection_pool_errors: Vec::new(),
                         },
                     );
                 }
+                HistoryEvent::ConnectionPoolError(..) => {}
             }
         }
 
-        // Move attempts to their speculative fibers
-        for (event, _) in &data.events {
-            if let HistoryEvent::NewAttempt(attempt_id, request_id, speculative_id, _) = event
-                && let Some(attempt) = attempts.remove(attempt_id)
-            {
-                match speculative_id {
-                    Some(spec_id) => {
-                        if let Some(spec_fiber) = fibers.get_mut(spec_id) {
-                            spec_fiber.attempts.push(attempt);
-                        }
-                    }
-                    None => {
-                        if let Some(request) = requests.get_mut(request_id) {
-                            request.non_speculative_fiber.attempts.push(attempt);
-                        }
+        // Move attempts and connection pool errors to their speculative fibers
+        for (event, event_time) in &data.events {
+            let (request_id, speculative_id) = match event {
+                HistoryEvent::NewAttempt(_, request_id, speculative_id, _)
+                | HistoryEvent::ConnectionPoolError(request_id, speculative_id, _, _) => {
+                    (request_id, speculative_id)
+                }
+                _ => continue,
+            };
+            let fiber = match speculative_id {
+                Some(spec_id) => fibers.get_mut(spec_id),
+                None => requests
+                    .get_mut(request_id)
+                    .map(|request| &mut request.non_speculative_fiber),
+            };
+            let Some(fiber) = fiber else {
+                continue;
+            };
+            match event {
+                HistoryEvent::NewAttempt(attempt_id, ..) => {
+                    if let Some(attempt) = attempts.remove(attempt_id) {
+                        fiber.attempts.push(attempt);
                     }
                 }
+                HistoryEvent::ConnectionPoolError(_, _, node_addr, error) => {
+                    fiber
+                        .connection_pool_errors
+                        .push((*event_time, *node_addr, error.clone()));
+                }
+                _ => {}
             }
         }
 
@@ -495,6 +613,11 @@ fn write_fiber_attempts(fiber: &FiberHistory, f: &mut std::fmt::Formatter<'_>) -
             None => writeln!(f, "|   No result yet")?,
         };
     }
+    for (time, node_addr, err) in &fiber.connection_pool_errors {
+        writeln!(f, "|")?;
+        writeln!(f, "| - Skipped {node_addr} at {time}")?;
+        writeln!(f, "|   Connection pool error: {err}")?;
+    }
 
     Ok(())
 }
@@ -504,7 +627,7 @@ mod tests {
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
     use crate::{
-        errors::{DbError, RequestAttemptError, RequestError},
+        errors::{ConnectionPoolError, DbError, RequestAttemptError, RequestError},
         policies::retry::RetryDecision,
         test_utils::setup_tracing,
     };
@@ -719,6 +842,84 @@ mod tests {
         assert_eq!(displayed, format!("{}", set_one_time(history)));
     }
 
+    #[test]
+    fn failed_attempts_and_tried_nodes() {
+        setup_tracing();
+        let history_collector = HistoryCollector::new();
+
+        let request_id: RequestId = history_collector.log_request_start();
+        let attempt1: AttemptId =
+            history_collector.log_attempt_start(request_id, None, node1_addr());
+        let speculative_id: SpeculativeId = history_collector.log_new_speculative_fiber(request_id);
+        let attempt2: AttemptId =
+            history_collector.log_attempt_start(request_id, Some(speculative_id), node2_addr());
+        history_collector.log_attempt_error(
+            attempt2,
+            &no_stream_id_error(),
+            &RetryDecision::RetryNextTarget(None),
+        );
+        history_collector.log_attempt_error(
+            attempt1,
+            &unavailable_error(),
+            &RetryDecision::RetrySameTarget(None),
+        );
+        let attempt3: AttemptId =
+            history_collector.log_attempt_start(request_id, None, node1_addr());
+        history_collector.log_attempt_error(
+            attempt3,
+            &unexpected_response(CqlResponseKind::Ready),
+            &RetryDecision::DontRetry,
+        );
+        // An attempt which hasn't finished is neither failed, nor counted as tried.
+        history_collector.log_attempt_start(request_id, Some(speculative_id), node3_addr());
+        // A node skipped because of its connection pool is counted as tried, but has no failed attempt.
+        history_collector.log_connection_pool_error(
+            request_id,
+            None,
+            node3_addr(),
+            &ConnectionPoolError::Initializing,
+        );
+
+        let history: StructuredHistory = history_collector.clone_structured_history();
+        let request = &history.requests[0];
+
+        let failed_attempts = request.failed_attempts();
+        assert_eq!(failed_attempts.len(), 3);
+        assert_matches!(
+            failed_attempts[0],
+            (addr, RequestAttemptError::UnableToAllocStreamId, RetryDecision::RetryNextTarget(None))
+                if addr == node2_addr()
+        );
+        assert_matches!(
+            failed_attempts[1],
+            (addr, RequestAttemptError::DbError(DbError::Unavailable { .. }, _), RetryDecision::RetrySameTarget(None))
+                if addr == node1_addr()
+        );
+        assert_matches!(
+            failed_attempts[2],
+            (addr, RequestAttemptError::UnexpectedResponse(_), RetryDecision::DontRetry)
+                if addr == node1_addr()
+        );
+
+        let tried_nodes = request.tried_nodes();
+        assert_eq!(tried_nodes.len(), 3);
+        assert_matches!(
+            &tried_nodes[0],
+            (addr, RequestError::LastAttemptError(RequestAttemptError::UnableToAllocStreamId))
+                if *addr == node2_addr()
+        );
+        assert_matches!(
+            &tried_nodes[1],
+            (addr, RequestError::LastAttemptError(RequestAttemptError::UnexpectedResponse(_)))
+                if *addr == node1_addr()
+        );
+        assert_matches!(
+            &tried_nodes[2],
+            (addr, RequestError::ConnectionPoolError(ConnectionPoolError::Initializing))
+                if *addr == node3_addr()
+        );
+    }
+
     #[test]
     fn empty_fibers() {
         setup_tracing();
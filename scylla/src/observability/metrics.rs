@@ -1,9 +1,12 @@
 //! Collecting metrics of driver operations.
 
+use crate::routing::Shard;
 use histogram::{AtomicHistogram, Histogram};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
+use uuid::Uuid;
 
 const ORDER_TYPE: Ordering = Ordering::Relaxed;
 
@@ -47,6 +50,21 @@ pub struct Snapshot {
     pub percentile_99_9: u64,
 }
 
+/// Latency percentiles commonly used to describe the tail of the latency distribution,
+/// in milliseconds.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Percentiles {
+    /// 50th percentile (median) latency.
+    pub p50: u64,
+    /// 95th percentile latency.
+    pub p95: u64,
+    /// 99th percentile latency.
+    pub p99: u64,
+    /// 99.9th percentile latency.
+    pub p999: u64,
+}
+
 /// The interval in seconds for which the rate is calculated.
 const INTERVAL: u64 = 5;
 
@@ -235,6 +253,24 @@ pub struct Metrics {
     total_connections: AtomicU64,
     connection_timeouts: AtomicU64,
     request_timeouts: AtomicU64,
+    /// Number of prepared statements held in the caches of `CachingSession`s wrapping the session.
+    cached_prepared_statements: AtomicU64,
+    /// Number of connections to each shard, as last published by the connection pool of each node.
+    shard_connections: Mutex<HashMap<Uuid, NodeShardConnections>>,
+    /// Number of requests waiting in the session's admission queue.
+    admission_queue_depth: AtomicU64,
+    /// Highest number of requests which have waited in the admission queue at the same time.
+    admission_queue_high_water: AtomicU64,
+}
+
+/// Connection counts published by the connection pool of a node.
+#[derive(Debug)]
+struct NodeShardConnections {
+    /// Identifies the publishing pool, so that a pool which is being torn down
+    /// does not remove the counts published by its replacement.
+    pool_id: usize,
+    /// Number of connections, indexed by shard.
+    per_shard: Vec<u64>,
 }
 
 impl Metrics {
@@ -262,6 +298,10 @@ impl Metrics {
             total_connections: AtomicU64::new(0),
             connection_timeouts: AtomicU64::new(0),
             request_timeouts: AtomicU64::new(0),
+            cached_prepared_statements: AtomicU64::new(0),
+            shard_connections: Mutex::new(HashMap::new()),
+            admission_queue_depth: AtomicU64::new(0),
+            admission_queue_high_water: AtomicU64::new(0),
         }
     }
 
@@ -315,6 +355,51 @@ impl Metrics {
         self.request_timeouts.fetch_add(1, ORDER_TYPE);
     }
 
+    /// Increments counter for prepared statements held in `CachingSession` caches.
+    pub(crate) fn inc_cached_prepared_statements(&self) {
+        self.cached_prepared_statements.fetch_add(1, ORDER_TYPE);
+    }
+
+    /// Decrements counter for prepared statements held in `CachingSession` caches
+    /// by the given number of statements removed from a cache.
+    pub(crate) fn sub_cached_prepared_statements(&self, removed: u64) {
+        self.cached_prepared_statements
+            .fetch_sub(removed, ORDER_TYPE);
+    }
+
+    /// Increments the number of requests waiting in the admission queue,
+    /// raising its high-water mark if needed.
+    pub(crate) fn inc_admission_queue_depth(&self) {
+        let depth = self.admission_queue_depth.fetch_add(1, ORDER_TYPE) + 1;
+        self.admission_queue_high_water.fetch_max(depth, ORDER_TYPE);
+    }
+
+    /// Decrements the number of requests waiting in the admission queue.
+    pub(crate) fn dec_admission_queue_depth(&self) {
+        self.admission_queue_depth.fetch_sub(1, ORDER_TYPE);
+    }
+
+    /// Replaces the per-shard connection counts of a node, as published by its connection pool.
+    /// Non-sharded nodes have a single shard, `0`.
+    pub(crate) fn set_shard_connections(&self, host_id: Uuid, pool_id: usize, per_shard: Vec<u64>) {
+        self.shard_connections
+            .lock()
+            .unwrap()
+            .insert(host_id, NodeShardConnections { pool_id, per_shard });
+    }
+
+    /// Removes the per-shard connection counts of a node,
+    /// unless they were published by a different pool.
+    pub(crate) fn remove_shard_connections(&self, host_id: Uuid, pool_id: usize) {
+        let mut shard_connections = self.shard_connections.lock().unwrap();
+        if shard_connections
+            .get(&host_id)
+            .is_some_and(|node| node.pool_id == pool_id)
+        {
+            shard_connections.remove(&host_id);
+        }
+    }
+
     /// Saves to histogram latency of completing single query.
     /// For paged queries it should log latency for every page.
     ///
@@ -385,6 +470,22 @@ impl Metrics {
         })
     }
 
+    /// Returns the p50, p95, p99 and p99.9 latencies in milliseconds,
+    /// computed from the latency histogram at the moment of calling this function.
+    pub fn latency_percentiles(&self) -> Result<Percentiles, MetricsError> {
+        let h = self.histogram.load();
+        let mut percentiles = Self::percentiles(&h, &[50.0, 95.0, 99.0, 99.9])?;
+
+        // SAFETY: `unwrap()`s are OK here, because `Self::percentiles()` returned iterator's length
+        // is equal to number of requested percentiles.
+        Ok(Percentiles {
+            p50: percentiles.next().unwrap(),
+            p95: percentiles.next().unwrap(),
+            p99: percentiles.next().unwrap(),
+            p999: percentiles.next().unwrap(),
+        })
+    }
+
     /// Returns counter for errors occurred in nonpaged queries
     pub fn get_errors_num(&self) -> u64 {
         self.errors_num.load(ORDER_TYPE)
@@ -445,6 +546,40 @@ impl Metrics {
         self.request_timeouts.load(ORDER_TYPE)
     }
 
+    /// Returns the number of prepared statements held in the caches of
+    /// [`CachingSession`](crate::client::caching_session::CachingSession)s wrapping the session.
+    pub fn get_cached_prepared_statements(&self) -> u64 {
+        self.cached_prepared_statements.load(ORDER_TYPE)
+    }
+
+    /// Returns the number of requests currently waiting in the admission queue
+    /// (see [`SessionBuilder::max_concurrent_requests`](crate::client::session_builder::SessionBuilder::max_concurrent_requests)).
+    pub fn admission_queue_depth(&self) -> u64 {
+        self.admission_queue_depth.load(ORDER_TYPE)
+    }
+
+    /// Returns the highest number of requests which have waited in the admission queue
+    /// at the same time since the session was created.
+    pub fn admission_queue_high_water(&self) -> u64 {
+        self.admission_queue_high_water.load(ORDER_TYPE)
+    }
+
+    /// Returns the number of active connections to each shard, keyed by the host ID
+    /// of the node and the shard number. Non-sharded nodes are reported as having a single shard, `0`.
+    pub fn per_shard_connections(&self) -> HashMap<(Uuid, Shard), u64> {
+        self.shard_connections
+            .lock()
+            .unwrap()
+            .iter()
+            .flat_map(|(&host_id, node)| {
+                node.per_shard
+                    .iter()
+                    .enumerate()
+                    .map(move |(shard, &count)| ((host_id, shard as Shard), count))
+            })
+            .collect()
+    }
+
     // Metric implementations
 
     // histogram crate used to implement Histogram::mean() method. Why did they remove it?
@@ -553,6 +688,16 @@ impl std::fmt::Debug for Metrics {
             .field("total_connections", &self.total_connections)
             .field("connection_timeouts", &self.connection_timeouts)
             .field("request_timeouts", &self.request_timeouts)
+            .field(
+                "cached_prepared_statements",
+                &self.cached_prepared_statements,
+            )
+            .field("shard_connections", &self.shard_connections)
+            .field("admission_queue_depth", &self.admission_queue_depth)
+            .field(
+                "admission_queue_high_water",
+                &self.admission_queue_high_water,
+            )
             .finish()
     }
 }
@@ -561,7 +706,9 @@ impl std::fmt::Debug for Metrics {
 mod tests {
     use rand::{Rng, SeedableRng};
 
-    use crate::observability::metrics::Snapshot;
+    use uuid::Uuid;
+
+    use crate::observability::metrics::{MetricsError, Percentiles, Snapshot};
 
     use super::Metrics;
 
@@ -639,4 +786,64 @@ mod tests {
         test_with_seed(42);
         test_with_seed(0xDEADCAFE);
     }
+
+    #[test]
+    fn test_latency_percentiles() {
+        let metrics = Metrics::new();
+        assert!(matches!(
+            metrics.latency_percentiles(),
+            Err(MetricsError::Empty)
+        ));
+
+        // Latencies of 1, 2, ..., 10000 ms, so the n-th percentile is n * 100 ms.
+        for latency in 1..=10_000 {
+            metrics.log_query_latency(latency).unwrap();
+        }
+
+        let Percentiles {
+            p50,
+            p95,
+            p99,
+            p999,
+        } = metrics.latency_percentiles().unwrap();
+
+        let assert_close = |actual: u64, expected: u64| {
+            // The histogram's buckets are narrower than 0.1% of their values.
+            assert!(
+                actual.abs_diff(expected) <= expected / 1000 + 1,
+                "expected {expected}, got {actual}"
+            );
+        };
+        assert_close(p50, 5_000);
+        assert_close(p95, 9_500);
+        assert_close(p99, 9_900);
+        assert_close(p999, 9_990);
+    }
+
+    #[test]
+    fn test_per_shard_connections() {
+        let metrics = Metrics::new();
+        assert!(metrics.per_shard_connections().is_empty());
+
+        let sharded_node = Uuid::from_u128(1);
+        let non_sharded_node = Uuid::from_u128(2);
+        metrics.set_shard_connections(sharded_node, 1, vec![2, 2, 1]);
+        metrics.set_shard_connections(non_sharded_node, 2, vec![3]);
+        let counts = metrics.per_shard_connections();
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts[&(sharded_node, 0)], 2);
+        assert_eq!(counts[&(sharded_node, 1)], 2);
+        assert_eq!(counts[&(sharded_node, 2)], 1);
+        assert_eq!(counts[&(non_sharded_node, 0)], 3);
+
+        // A pool replaced by another one does not remove the counts of its replacement.
+        metrics.set_shard_connections(sharded_node, 3, vec![1, 1, 1]);
+        metrics.remove_shard_connections(sharded_node, 1);
+        assert_eq!(metrics.per_shard_connections()[&(sharded_node, 2)], 1);
+
+        metrics.remove_shard_connections(sharded_node, 3);
+        let counts = metrics.per_shard_connections();
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[&(non_sharded_node, 0)], 3);
+    }
 }
@@ -0,0 +1,100 @@
+//! Pluggable sink of driver metrics events.
+//!
+//! Unlike the built-in metrics of the `metrics` feature, which aggregate
+//! the events into counters and histograms, a [`MetricsSink`] receives each event as it happens,
+//! so that it can be forwarded to an external metrics system. A sink is registered with
+//! [`SessionBuilder::metrics_sink`](crate::client::session_builder::SessionBuilder::metrics_sink).
+
+use std::time::Duration;
+
+use crate::cluster::NodeAddr;
+use crate::policies::retry::RetryDecision;
+
+/// Receives metrics events of a session.
+///
+/// The callbacks are invoked synchronously on the driver's hot paths,
+/// so they should return quickly and must not block.
+/// All of them do nothing by default, so that a sink only implements those it is interested in.
+pub trait MetricsSink: Send + Sync {
+    /// Called when a request sent to a node completes, successfully or not.
+    ///
+    /// Every attempt of a request, including retries and speculative executions,
+    /// is reported separately. So is each page fetched by a [`QueryPager`](crate::client::pager::QueryPager).
+    fn on_request_complete(&self, event: &RequestCompleteEvent) {
+        let _ = event;
+    }
+
+    /// Called when the retry policy decides to retry a failed request.
+    fn on_retry(&self, event: &RetryEvent) {
+        let _ = event;
+    }
+
+    /// Called when a connection of a pool is opened, fails to open or is closed.
+    fn on_connection_event(&self, event: &ConnectionEvent) {
+        let _ = event;
+    }
+}
+
+/// A request sent to a node has completed.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RequestCompleteEvent {
+    /// Address of the node to which the request was sent.
+    pub node_address: NodeAddr,
+
+    /// Time from sending the request to receiving the response.
+    pub latency: Duration,
+
+    /// Whether the request succeeded.
+    pub succeeded: bool,
+}
+
+/// A failed request is going to be retried.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RetryEvent {
+    /// Address of the node on which the request failed.
+    pub node_address: NodeAddr,
+
+    /// The decision of the retry policy.
+    pub decision: RetryDecision,
+}
+
+/// A change of the connections to a node.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ConnectionEvent {
+    /// Address of the node.
+    pub node_address: NodeAddr,
+
+    /// What happened to the connection.
+    pub kind: ConnectionEventKind,
+}
+
+/// Kind of a [`ConnectionEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConnectionEventKind {
+    /// A connection was opened and added to the pool, or opened for a single request
+    /// which forces a fresh connection (see e.g. [`Statement::set_force_fresh_connection`](crate::statement::unprepared::Statement::set_force_fresh_connection)).
+    Opened,
+
+    /// A connection failed to open.
+    OpenFailed,
+
+    /// A connection was removed from the pool, or a connection opened
+    /// for a single request was closed.
+    Closed,
+}
+
+impl ConnectionEvent {
+    pub(crate) fn report(
+        sink: Option<&dyn MetricsSink>,
+        node_address: NodeAddr,
+        kind: ConnectionEventKind,
+    ) {
+        if let Some(sink) = sink {
+            sink.on_connection_event(&ConnectionEvent { node_address, kind });
+        }
+    }
+}
@@ -2,11 +2,23 @@
 //! This includes:
 //! - driver-side tracing,
 //! - cluster-side tracing,
+//! - cluster-side view of client connections,
+//! - skew between the client's and cluster's clocks,
+//! - per-connection statistics,
 //! - request execution history,
-//! - driver metrics.
+//! - recent executions of prepared statements,
+//! - recent warnings returned by the database,
+//! - driver metrics,
+//! - pluggable sinks of metrics events.
 
+pub mod clients;
+pub mod clock_skew;
+pub mod connection_stats;
 pub(crate) mod driver_tracing;
 pub mod history;
 #[cfg(feature = "metrics")]
 pub mod metrics;
+pub mod metrics_sink;
+pub mod statement_history;
 pub mod tracing;
+pub mod warnings;
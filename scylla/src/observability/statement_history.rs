@@ -0,0 +1,97 @@
+//! Rolling logs of recent executions of prepared statements, for debugging.
+//!
+//! The log is opt-in: it is enabled on a prepared statement with
+//! [`PreparedStatement::set_execution_history_capacity`](crate::statement::prepared::PreparedStatement::set_execution_history_capacity)
+//! and read with [`PreparedStatement::recent_executions`](crate::statement::prepared::PreparedStatement::recent_executions).
+
+use std::collections::VecDeque;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::errors::ExecutionError;
+use crate::response::Coordinator;
+
+/// A single execution of a prepared statement, as recorded in its history.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct StatementExecution {
+    /// When the execution started.
+    pub started_at: SystemTime,
+
+    /// Time it took to execute the statement, including retries and speculative executions.
+    pub latency: Duration,
+
+    /// The coordinator which returned the response. `None` if the execution failed.
+    pub coordinator: Option<Coordinator>,
+
+    /// The outcome of the execution.
+    pub result: Result<(), ExecutionError>,
+}
+
+/// A bounded log of recent executions, shared by clones of a prepared statement.
+#[derive(Debug)]
+pub(crate) struct ExecutionHistory {
+    capacity: NonZeroUsize,
+    executions: Mutex<VecDeque<StatementExecution>>,
+}
+
+impl ExecutionHistory {
+    pub(crate) fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            capacity,
+            executions: Mutex::new(VecDeque::with_capacity(capacity.get())),
+        }
+    }
+
+    pub(crate) fn capacity(&self) -> NonZeroUsize {
+        self.capacity
+    }
+
+    /// Records an execution, evicting the oldest one if the log is full.
+    pub(crate) fn record(&self, execution: StatementExecution) {
+        let mut executions = self.executions.lock().unwrap();
+        if executions.len() == self.capacity.get() {
+            executions.pop_front();
+        }
+        executions.push_back(execution);
+    }
+
+    /// Returns the recorded executions, from the oldest to the most recent one.
+    pub(crate) fn executions(&self) -> Vec<StatementExecution> {
+        self.executions.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::time::{Duration, SystemTime};
+
+    use super::{ExecutionHistory, StatementExecution};
+
+    fn execution(latency_ms: u64) -> StatementExecution {
+        StatementExecution {
+            started_at: SystemTime::now(),
+            latency: Duration::from_millis(latency_ms),
+            coordinator: None,
+            result: Ok(()),
+        }
+    }
+
+    #[test]
+    fn history_keeps_most_recent_executions() {
+        let history = ExecutionHistory::new(NonZeroUsize::new(3).unwrap());
+        assert!(history.executions().is_empty());
+
+        for latency_ms in 1..=5 {
+            history.record(execution(latency_ms));
+        }
+        let latencies = history
+            .executions()
+            .iter()
+            .map(|execution| execution.latency.as_millis())
+            .collect::<Vec<_>>();
+        assert_eq!(latencies, [3, 4, 5]);
+    }
+}
@@ -0,0 +1,91 @@
+//! Session-wide log of recent warnings returned by the database, for post-mortem debugging.
+//!
+//! The log is opt-in: it is enabled with
+//! [`SessionBuilder::recent_warnings_capacity`](crate::client::session_builder::SessionBuilder::recent_warnings_capacity)
+//! and read with [`Session::recent_warnings`](crate::client::session::Session::recent_warnings).
+
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// A single warning returned by the database, as recorded in the session's log.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ServerWarning {
+    /// When the response carrying the warning was received.
+    pub received_at: SystemTime,
+
+    /// Address of the node which returned the warning.
+    pub node_address: SocketAddr,
+
+    /// The warning itself.
+    pub message: String,
+}
+
+/// A bounded log of recent warnings, shared by all connections of a session.
+#[derive(Debug)]
+pub(crate) struct WarningsLog {
+    capacity: NonZeroUsize,
+    warnings: Mutex<VecDeque<ServerWarning>>,
+}
+
+impl WarningsLog {
+    pub(crate) fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            capacity,
+            warnings: Mutex::new(VecDeque::with_capacity(capacity.get())),
+        }
+    }
+
+    /// Records the warnings of a single response, evicting the oldest ones if the log is full.
+    pub(crate) fn record(&self, node_address: SocketAddr, messages: &[String]) {
+        if messages.is_empty() {
+            return;
+        }
+
+        let received_at = SystemTime::now();
+        let mut warnings = self.warnings.lock().unwrap();
+        for message in messages {
+            if warnings.len() == self.capacity.get() {
+                warnings.pop_front();
+            }
+            warnings.push_back(ServerWarning {
+                received_at,
+                node_address,
+                message: message.clone(),
+            });
+        }
+    }
+
+    /// Returns the recorded warnings, from the oldest to the most recent one.
+    pub(crate) fn warnings(&self) -> Vec<ServerWarning> {
+        self.warnings.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::num::NonZeroUsize;
+
+    use super::WarningsLog;
+
+    #[test]
+    fn log_keeps_most_recent_warnings_in_order() {
+        let log = WarningsLog::new(NonZeroUsize::new(3).unwrap());
+        let addr: SocketAddr = "127.0.0.1:9042".parse().unwrap();
+        assert!(log.warnings().is_empty());
+
+        log.record(addr, &["a".to_owned(), "b".to_owned()]);
+        log.record(addr, &[]);
+        log.record(addr, &["c".to_owned(), "d".to_owned()]);
+        let messages = log
+            .warnings()
+            .into_iter()
+            .map(|warning| warning.message)
+            .collect::<Vec<_>>();
+        assert_eq!(messages, ["b", "c", "d"]);
+    }
+}
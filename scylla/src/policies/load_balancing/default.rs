@@ -16,7 +16,7 @@ use itertools::{Either, Itertools};
 use rand::{Rng, prelude::SliceRandom, rng};
 use rand_pcg::Pcg32;
 use std::hash::{Hash, Hasher};
-use std::{fmt, sync::Arc, time::Duration};
+use std::{fmt, num::NonZeroUsize, sync::Arc, time::Duration};
 use tracing::{debug, warn};
 use uuid::Uuid;
 
@@ -89,8 +89,8 @@ enum PickedReplica<'a> {
 /// nodes for recently measures latencies is believed to not be very stable
 /// and beneficial. The number of in-flight requests, for instance, seems
 /// to be a better metric showing how (over)loaded a target node/shard is.
-/// For now, however, we don't have an implementation of the
-/// in-flight-requests-aware policy.
+/// Nodes having too many requests in flight can be deprioritised with
+/// [`DefaultPolicyBuilder::max_in_flight_per_node`].
 #[expect(clippy::type_complexity)]
 pub struct DefaultPolicy {
     /// Preferences regarding node location. One of: rack and DC, DC, no DC preference,
@@ -110,7 +110,9 @@ pub struct DefaultPolicy {
     /// This was introduced to make latency awareness cleaner.
     /// - if latency awareness is disabled, then `pick_predicate` is just `Self::is_alive()`;
     /// - if latency awareness is enabled, then it is `Self::is_alive() && latency_predicate()`,
-    ///   which checks that the target is not penalised due to high latencies.
+    ///   which checks that the target is not penalised due to high latencies;
+    /// - if `max_in_flight_per_node` is set, then it additionally checks that
+    ///   the target is not saturated.
     pick_predicate: Box<dyn Fn(NodeRef<'_>, Option<Shard>) -> bool + Send + Sync>,
 
     /// Additional layer that penalises targets that are too slow compared to others
@@ -123,6 +125,11 @@ pub struct DefaultPolicy {
     /// Penalisation is done based on collected and updated latencies.
     latency_awareness: Option<LatencyAwareness>,
 
+    /// The number of in-flight requests at which a node is considered saturated.
+    /// Saturated nodes are never `pick`ed, and `fallback` moves them to the end
+    /// of the plan (but before nodes penalised by latency awareness), in a stable way.
+    max_in_flight_per_node: Option<NonZeroUsize>,
+
     /// The policy chooses (in `pick`) and shuffles (in `fallback`) replicas and nodes
     /// based on random number generator. For sake of deterministic testing,
     /// a fixed seed can be used.
@@ -136,6 +143,7 @@ impl fmt::Debug for DefaultPolicy {
             .field("is_token_aware", &self.is_token_aware)
             .field("permit_dc_failover", &self.permit_dc_failover)
             .field("latency_awareness", &self.latency_awareness)
+            .field("max_in_flight_per_node", &self.max_in_flight_per_node)
             .field("fixed_seed", &self.fixed_seed)
             .finish_non_exhaustive()
     }
@@ -531,6 +539,13 @@ or refrain from preferring datacenters (which may ban all other datacenters, if
                 shard: *shard,
             });
 
+        // If the in-flight cap is set, move saturated nodes behind the other ones, in a stable fashion.
+        let plan = if let Some(max_in_flight) = self.max_in_flight_per_node {
+            Either::Left(Self::deprioritise_saturated_nodes(plan, max_in_flight))
+        } else {
+            Either::Right(plan)
+        };
+
         // If latency awareness is enabled, wrap the plan by applying latency penalisation:
         // all penalised nodes are moved behind non-penalised nodes, in a stable fashion.
         if let Some(latency_awareness) = self.latency_awareness.as_ref() {
@@ -891,6 +906,22 @@ impl DefaultPolicy {
         vec.into_iter()
     }
 
+    /// Returns true iff the node has at least `max_in_flight` requests in flight.
+    fn is_saturated(node: NodeRef, max_in_flight: NonZeroUsize) -> bool {
+        node.in_flight_requests() >= max_in_flight.get()
+    }
+
+    /// Moves saturated nodes to the end of the plan, keeping the relative order
+    /// of both saturated and non-saturated nodes.
+    fn deprioritise_saturated_nodes<'a>(
+        plan: impl Iterator<Item = (NodeRef<'a>, Option<Shard>)>,
+        max_in_flight: NonZeroUsize,
+    ) -> impl Iterator<Item = (NodeRef<'a>, Option<Shard>)> {
+        let (unsaturated_targets, saturated_targets): (Vec<_>, Vec<_>) =
+            plan.partition(|(node, _shard)| !Self::is_saturated(node, max_in_flight));
+        unsaturated_targets.into_iter().chain(saturated_targets)
+    }
+
     /// Returns true iff the node should be considered to be alive.
     fn is_alive(node: NodeRef, _shard: Option<Shard>) -> bool {
         // For now we ignore the shard.
@@ -914,6 +945,7 @@ impl Default for DefaultPolicy {
             permit_dc_failover: false,
             pick_predicate: Box::new(Self::is_alive),
             latency_awareness: None,
+            max_in_flight_per_node: None,
             fixed_seed: None,
         }
     }
@@ -939,6 +971,7 @@ pub struct DefaultPolicyBuilder {
     is_token_aware: bool,
     permit_dc_failover: bool,
     latency_awareness: Option<LatencyAwarenessBuilder>,
+    max_in_flight_per_node: Option<NonZeroUsize>,
     enable_replica_shuffle: bool,
 }
 
@@ -950,6 +983,7 @@ impl DefaultPolicyBuilder {
             is_token_aware: true,
             permit_dc_failover: false,
             latency_awareness: None,
+            max_in_flight_per_node: None,
             enable_replica_shuffle: true,
         }
     }
@@ -957,14 +991,23 @@ impl DefaultPolicyBuilder {
     /// Builds a new DefaultPolicy with the previously set configuration.
     pub fn build(self) -> Arc<dyn LoadBalancingPolicy> {
         let latency_awareness = self.latency_awareness.map(|builder| builder.build());
-        let pick_predicate = if let Some(ref latency_awareness) = latency_awareness {
-            let latency_predicate = latency_awareness.generate_predicate();
-            Box::new(move |node: NodeRef<'_>, shard| {
-                DefaultPolicy::is_alive(node, shard) && latency_predicate(node)
-            })
-                as Box<dyn Fn(NodeRef<'_>, Option<Shard>) -> bool + Send + Sync + 'static>
-        } else {
-            Box::new(DefaultPolicy::is_alive)
+        let max_in_flight_per_node = self.max_in_flight_per_node;
+        let pick_predicate = match (&latency_awareness, max_in_flight_per_node) {
+            (None, None) => Box::new(DefaultPolicy::is_alive)
+                as Box<dyn Fn(NodeRef<'_>, Option<Shard>) -> bool + Send + Sync + 'static>,
+            (latency_awareness, max_in_flight_per_node) => {
+                let latency_predicate =
+                    latency_awareness.as_ref().map(|la| la.generate_predicate());
+                Box::new(move |node: NodeRef<'_>, shard| {
+                    DefaultPolicy::is_alive(node, shard)
+                        && latency_predicate
+                            .as_ref()
+                            .is_none_or(|latency_predicate| latency_predicate(node))
+                        && max_in_flight_per_node.is_none_or(|max_in_flight| {
+                            !DefaultPolicy::is_saturated(node, max_in_flight)
+                        })
+                })
+            }
         };
 
         Arc::new(DefaultPolicy {
@@ -973,6 +1016,7 @@ impl DefaultPolicyBuilder {
             permit_dc_failover: self.permit_dc_failover,
             pick_predicate,
             latency_awareness,
+            max_in_flight_per_node,
             fixed_seed: (!self.enable_replica_shuffle).then(|| {
                 let seed = rand::random();
                 debug!("DefaultPolicy: setting fixed seed to {}", seed);
@@ -1118,6 +1162,19 @@ impl DefaultPolicyBuilder {
         self
     }
 
+    /// Sets the number of in-flight requests at which a node is considered saturated.
+    ///
+    /// Saturated nodes are not chosen as the first target of a request, and are moved
+    /// to the end of the query plan (though still before nodes penalised by latency awareness),
+    /// so that requests are routed to them only if no other node is available.
+    /// The number of in-flight requests is summed over all connections to the node.
+    ///
+    /// This option is disabled (`None`) by default.
+    pub fn max_in_flight_per_node(mut self, max_in_flight: Option<NonZeroUsize>) -> Self {
+        self.max_in_flight_per_node = max_in_flight;
+        self
+    }
+
     /// Sets whether this policy should shuffle replicas when token-awareness
     /// is enabled. Shuffling can help distribute the load over replicas, but
     /// can reduce the effectiveness of caching on the database side (e.g.
@@ -3468,6 +3525,7 @@ mod latency_awareness {
                 is_token_aware: true,
                 pick_predicate,
                 latency_awareness: Some(latency_awareness),
+                max_in_flight_per_node: None,
                 fixed_seed: None,
             }
         }
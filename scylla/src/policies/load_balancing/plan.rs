@@ -168,6 +168,26 @@ impl<'a> Iterator for Plan<'a> {
     }
 }
 
+/// Moves the target on the preferred node to the front of the plan, if the plan includes it.
+/// The targets which preceded it keep their order.
+pub(crate) fn with_preferred_first<'a>(
+    mut plan: impl Iterator<Item = (NodeRef<'a>, Shard)>,
+    preferred: Option<NodeRef<'a>>,
+) -> impl Iterator<Item = (NodeRef<'a>, Shard)> {
+    let mut preceding = Vec::new();
+    let mut preferred_target = None;
+    if let Some(preferred) = preferred {
+        for target in plan.by_ref() {
+            if target.0.host_id == preferred.host_id {
+                preferred_target = Some(target);
+                break;
+            }
+            preceding.push(target);
+        }
+    }
+    preferred_target.into_iter().chain(preceding).chain(plan)
+}
+
 #[cfg(test)]
 mod tests {
     use std::{net::SocketAddr, str::FromStr, sync::Arc};
@@ -245,4 +265,32 @@ mod tests {
             policy.expected_nodes
         );
     }
+
+    #[test]
+    fn preferred_target_is_taken_from_the_plan() {
+        setup_tracing();
+        let nodes: Vec<Arc<Node>> = (0..3)
+            .map(|_| Arc::new(Node::new_for_test(None, None, None, None)))
+            .collect();
+        let plan = || nodes.iter().zip([1, 2, 3]);
+        let host_ids = |targets: Vec<(NodeRef<'_>, Shard)>| {
+            targets
+                .into_iter()
+                .map(|(node, shard)| (node.host_id, shard))
+                .collect::<Vec<_>>()
+        };
+
+        // The preferred node is moved to the front, keeping the shard chosen by the plan.
+        assert_eq!(
+            host_ids(with_preferred_first(plan(), Some(&nodes[2])).collect()),
+            host_ids(vec![(&nodes[2], 3), (&nodes[0], 1), (&nodes[1], 2)])
+        );
+
+        // A node which the plan doesn't include is not added to it.
+        let unplanned = Arc::new(Node::new_for_test(None, None, None, None));
+        assert_eq!(
+            host_ids(with_preferred_first(plan(), Some(&unplanned)).collect()),
+            host_ids(plan().collect())
+        );
+    }
 }
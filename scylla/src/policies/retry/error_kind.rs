@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::errors::{DbError, RequestAttemptError};
+
+use super::{RequestInfo, RetryDecision, RetrySession};
+
+/// Kind of a request attempt error, used to configure retry decisions
+/// declaratively in an execution profile.
+///
+/// See [`ExecutionProfileBuilder::retry_decisions`](crate::client::execution_profile::ExecutionProfileBuilder::retry_decisions).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RetryErrorKind {
+    /// [`DbError::Unavailable`]
+    Unavailable,
+    /// [`DbError::Overloaded`]
+    Overloaded,
+    /// [`DbError::IsBootstrapping`]
+    IsBootstrapping,
+    /// [`DbError::TruncateError`]
+    TruncateError,
+    /// [`DbError::ReadTimeout`]
+    ReadTimeout,
+    /// [`DbError::WriteTimeout`]
+    WriteTimeout,
+    /// [`DbError::ReadFailure`]
+    ReadFailure,
+    /// [`DbError::WriteFailure`]
+    WriteFailure,
+    /// [`DbError::ServerError`]
+    ServerError,
+    /// [`DbError::RateLimitReached`]
+    RateLimitReached,
+    /// [`DbError::FunctionFailure`]
+    ///
+    /// Built-in retry policies never retry failures of user defined functions.
+    FunctionFailure,
+    /// [`RequestAttemptError::BrokenConnectionError`]
+    BrokenConnection,
+    /// [`RequestAttemptError::UnableToAllocStreamId`]
+    UnableToAllocStreamId,
+}
+
+impl RetryErrorKind {
+    /// Classifies the given error. Returns `None` for errors which have no corresponding kind.
+    pub fn of(error: &RequestAttemptError) -> Option<Self> {
+        let kind = match error {
+            RequestAttemptError::DbError(db_error, _) => match db_error {
+                DbError::Unavailable { .. } => Self::Unavailable,
+                DbError::Overloaded => Self::Overloaded,
+                DbError::IsBootstrapping => Self::IsBootstrapping,
+                DbError::TruncateError => Self::TruncateError,
+                DbError::ReadTimeout { .. } => Self::ReadTimeout,
+                DbError::WriteTimeout { .. } => Self::WriteTimeout,
+                DbError::ReadFailure { .. } => Self::ReadFailure,
+                DbError::WriteFailure { .. } => Self::WriteFailure,
+                DbError::ServerError => Self::ServerError,
+                DbError::RateLimitReached { .. } => Self::RateLimitReached,
+                DbError::FunctionFailure { .. } => Self::FunctionFailure,
+                _ => return None,
+            },
+            RequestAttemptError::BrokenConnectionError(_) => Self::BrokenConnection,
+            RequestAttemptError::UnableToAllocStreamId => Self::UnableToAllocStreamId,
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+/// Maximum number of retries a single [`MappedRetrySession`] performs on behalf
+/// of the map. Once exhausted, further mapped retry decisions become
+/// [`RetryDecision::DontRetry`].
+pub(crate) const MAX_MAPPED_RETRIES: usize = 3;
+
+/// A [`RetrySession`] which consults a map of retry decisions first,
+/// and falls back to the wrapped session for errors not present in the map.
+///
+/// The map is consulted only for idempotent requests; non-idempotent ones
+/// are always decided by the wrapped session.
+pub(crate) struct MappedRetrySession {
+    decisions: Arc<HashMap<RetryErrorKind, RetryDecision>>,
+    inner: Box<dyn RetrySession>,
+    retry_count: usize,
+}
+
+impl MappedRetrySession {
+    pub(crate) fn new(
+        decisions: Arc<HashMap<RetryErrorKind, RetryDecision>>,
+        inner: Box<dyn RetrySession>,
+    ) -> Self {
+        Self {
+            decisions,
+            inner,
+            retry_count: 0,
+        }
+    }
+}
+
+impl RetrySession for MappedRetrySession {
+    fn decide_should_retry(&mut self, request_info: RequestInfo) -> RetryDecision {
+        let mapped = request_info
+            .is_idempotent
+            .then(|| RetryErrorKind::of(request_info.error))
+            .flatten()
+            .and_then(|kind| self.decisions.get(&kind));
+        match mapped {
+            Some(RetryDecision::RetrySameTarget(_) | RetryDecision::RetryNextTarget(_))
+                if self.retry_count >= MAX_MAPPED_RETRIES =>
+            {
+                RetryDecision::DontRetry
+            }
+            Some(
+                decision @ (RetryDecision::RetrySameTarget(_) | RetryDecision::RetryNextTarget(_)),
+            ) => {
+                self.retry_count += 1;
+                decision.clone()
+            }
+            Some(decision) => decision.clone(),
+            None => self.inner.decide_should_retry(request_info),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.retry_count = 0;
+        self.inner.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use super::{MAX_MAPPED_RETRIES, MappedRetrySession, RetryErrorKind};
+    use crate::errors::{BrokenConnectionErrorKind, DbError, RequestAttemptError};
+    use crate::frame::protocol_features::ProtocolFeatures;
+    use crate::frame::response::error::Error;
+    use crate::frame::types;
+    use crate::policies::retry::{
+        DefaultRetryPolicy, FallthroughRetryPolicy, RequestInfo, RetryDecision, RetryPolicy,
+        RetrySession,
+    };
+    use crate::statement::Consistency;
+    use crate::test_utils::setup_tracing;
+
+    fn make_request_info(error: &RequestAttemptError, is_idempotent: bool) -> RequestInfo<'_> {
+        RequestInfo {
+            error,
+            is_idempotent,
+            consistency: Consistency::One,
+        }
+    }
+
+    #[test]
+    fn mapped_retry_session_consults_the_map() {
+        setup_tracing();
+        let decisions = HashMap::from([
+            (
+                RetryErrorKind::Overloaded,
+                RetryDecision::RetryNextTarget(None),
+            ),
+            (
+                RetryErrorKind::BrokenConnection,
+                RetryDecision::RetrySameTarget(Some(Consistency::One)),
+            ),
+        ]);
+        let mut session = MappedRetrySession::new(
+            Arc::new(decisions),
+            FallthroughRetryPolicy::new().new_session(),
+        );
+
+        let overloaded = RequestAttemptError::DbError(DbError::Overloaded, String::new());
+        assert_eq!(
+            session.decide_should_retry(make_request_info(&overloaded, true)),
+            RetryDecision::RetryNextTarget(None)
+        );
+
+        let broken = RequestAttemptError::BrokenConnectionError(
+            BrokenConnectionErrorKind::TooManyOrphanedStreamIds(5).into(),
+        );
+        assert_eq!(
+            session.decide_should_retry(make_request_info(&broken, true)),
+            RetryDecision::RetrySameTarget(Some(Consistency::One))
+        );
+
+        // Errors not present in the map are decided by the wrapped session.
+        let server_error = RequestAttemptError::DbError(DbError::ServerError, String::new());
+        assert_eq!(
+            session.decide_should_retry(make_request_info(&server_error, true)),
+            RetryDecision::DontRetry
+        );
+        let syntax_error = RequestAttemptError::DbError(DbError::SyntaxError, String::new());
+        assert_eq!(RetryErrorKind::of(&syntax_error), None);
+        assert_eq!(
+            session.decide_should_retry(make_request_info(&syntax_error, true)),
+            RetryDecision::DontRetry
+        );
+    }
+
+    #[test]
+    fn function_failure_is_decoded_and_mapped() {
+        setup_tracing();
+        // An ERROR response with the FUNCTION_FAILURE code, as sent by the server.
+        let mut body = Vec::new();
+        types::write_int(0x1400, &mut body);
+        types::write_string("execution of ks.f failed", &mut body).unwrap();
+        types::write_string("ks", &mut body).unwrap();
+        types::write_string("f", &mut body).unwrap();
+        types::write_string_list(&["int".to_owned()], &mut body).unwrap();
+        let error = Error::deserialize(&ProtocolFeatures::default(), &mut body.as_slice()).unwrap();
+        let function_failure = RequestAttemptError::DbError(error.error, error.reason);
+        assert_eq!(
+            RetryErrorKind::of(&function_failure),
+            Some(RetryErrorKind::FunctionFailure)
+        );
+
+        // Built-in policies don't retry it, unless the map says otherwise.
+        let mut unmapped = MappedRetrySession::new(
+            Arc::new(HashMap::new()),
+            DefaultRetryPolicy::new().new_session(),
+        );
+        assert_eq!(
+            unmapped.decide_should_retry(make_request_info(&function_failure, true)),
+            RetryDecision::DontRetry
+        );
+        let mut mapped = MappedRetrySession::new(
+            Arc::new(HashMap::from([(
+                RetryErrorKind::FunctionFailure,
+                RetryDecision::RetryNextTarget(None),
+            )])),
+            DefaultRetryPolicy::new().new_session(),
+        );
+        assert_eq!(
+            mapped.decide_should_retry(make_request_info(&function_failure, true)),
+            RetryDecision::RetryNextTarget(None)
+        );
+    }
+
+    #[test]
+    fn mapped_retry_session_skips_non_idempotent_requests() {
+        setup_tracing();
+        let decisions = HashMap::from([(
+            RetryErrorKind::Overloaded,
+            RetryDecision::RetryNextTarget(None),
+        )]);
+        let mut session = MappedRetrySession::new(
+            Arc::new(decisions),
+            FallthroughRetryPolicy::new().new_session(),
+        );
+
+        let overloaded = RequestAttemptError::DbError(DbError::Overloaded, String::new());
+        assert_eq!(
+            session.decide_should_retry(make_request_info(&overloaded, false)),
+            RetryDecision::DontRetry
+        );
+    }
+
+    #[test]
+    fn mapped_retry_session_caps_retries() {
+        setup_tracing();
+        let decisions = HashMap::from([(
+            RetryErrorKind::Overloaded,
+            RetryDecision::RetrySameTarget(None),
+        )]);
+        let mut session = MappedRetrySession::new(
+            Arc::new(decisions),
+            FallthroughRetryPolicy::new().new_session(),
+        );
+
+        let overloaded = RequestAttemptError::DbError(DbError::Overloaded, String::new());
+        for _ in 0..MAX_MAPPED_RETRIES {
+            assert_eq!(
+                session.decide_should_retry(make_request_info(&overloaded, true)),
+                RetryDecision::RetrySameTarget(None)
+            );
+        }
+        assert_eq!(
+            session.decide_should_retry(make_request_info(&overloaded, true)),
+            RetryDecision::DontRetry
+        );
+
+        // Resetting the session restores the budget.
+        session.reset();
+        assert_eq!(
+            session.decide_should_retry(make_request_info(&overloaded, true)),
+            RetryDecision::RetrySameTarget(None)
+        );
+    }
+}
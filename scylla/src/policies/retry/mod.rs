@@ -2,12 +2,17 @@
 
 mod default;
 mod downgrading_consistency;
+mod error_kind;
 mod fallthrough;
+mod read_failure;
 mod retry_policy;
 
 pub use default::{DefaultRetryPolicy, DefaultRetrySession};
 pub use downgrading_consistency::{
     DowngradingConsistencyRetryPolicy, DowngradingConsistencyRetrySession,
 };
+pub(crate) use error_kind::MappedRetrySession;
+pub use error_kind::RetryErrorKind;
 pub use fallthrough::{FallthroughRetryPolicy, FallthroughRetrySession};
+pub use read_failure::{ReadFailureRetryPolicy, ReadFailureRetrySession};
 pub use retry_policy::{RequestInfo, RetryDecision, RetryPolicy, RetrySession};
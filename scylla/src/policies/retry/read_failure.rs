@@ -0,0 +1,159 @@
+use super::{DefaultRetrySession, RequestInfo, RetryDecision, RetryPolicy, RetrySession};
+use crate::errors::{DbError, RequestAttemptError};
+
+/// Read failure retry policy - behaves as [DefaultRetryPolicy](crate::policies::retry::DefaultRetryPolicy),
+/// but also retries idempotent requests which failed with [DbError::ReadFailure]
+/// on the next target, at most a configured number of times per request.
+///
+/// Read failures are caused by replicas failing to serve the read (e.g. because of
+/// reading too many tombstones), so [DefaultRetryPolicy](crate::policies::retry::DefaultRetryPolicy)
+/// does not retry them.
+#[derive(Debug)]
+pub struct ReadFailureRetryPolicy {
+    max_read_failure_retries: u32,
+}
+
+impl ReadFailureRetryPolicy {
+    /// Creates a new instance of [ReadFailureRetryPolicy], which retries a request
+    /// after [DbError::ReadFailure] at most `max_read_failure_retries` times.
+    pub fn new(max_read_failure_retries: u32) -> ReadFailureRetryPolicy {
+        ReadFailureRetryPolicy {
+            max_read_failure_retries,
+        }
+    }
+
+    /// The maximum number of retries of an idempotent request after [DbError::ReadFailure].
+    pub fn max_read_failure_retries(&self) -> u32 {
+        self.max_read_failure_retries
+    }
+}
+
+impl RetryPolicy for ReadFailureRetryPolicy {
+    fn new_session(&self) -> Box<dyn RetrySession> {
+        Box::new(ReadFailureRetrySession {
+            default_session: DefaultRetrySession::new(),
+            read_failure_retries: 0,
+            max_read_failure_retries: self.max_read_failure_retries,
+        })
+    }
+}
+
+/// Implementation of [RetrySession] for [ReadFailureRetryPolicy].
+pub struct ReadFailureRetrySession {
+    default_session: DefaultRetrySession,
+    read_failure_retries: u32,
+    max_read_failure_retries: u32,
+}
+
+impl RetrySession for ReadFailureRetrySession {
+    fn decide_should_retry(&mut self, request_info: RequestInfo) -> RetryDecision {
+        if request_info.consistency.is_serial() {
+            return RetryDecision::DontRetry;
+        };
+        match request_info.error {
+            RequestAttemptError::DbError(DbError::ReadFailure { .. }, _) => {
+                if request_info.is_idempotent
+                    && self.read_failure_retries < self.max_read_failure_retries
+                {
+                    self.read_failure_retries += 1;
+                    RetryDecision::RetryNextTarget(None)
+                } else {
+                    RetryDecision::DontRetry
+                }
+            }
+            _ => self.default_session.decide_should_retry(request_info),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.default_session.reset();
+        self.read_failure_retries = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReadFailureRetryPolicy, RequestInfo, RetryDecision, RetryPolicy};
+    use crate::errors::{DbError, RequestAttemptError};
+    use crate::statement::Consistency;
+    use crate::test_utils::setup_tracing;
+
+    fn make_request_info(error: &RequestAttemptError, is_idempotent: bool) -> RequestInfo<'_> {
+        RequestInfo {
+            error,
+            is_idempotent,
+            consistency: Consistency::One,
+        }
+    }
+
+    // ReadFailure is retried on next, at most the configured number of times, for idempotent requests only
+    #[test]
+    fn read_failure_bounded_retries() {
+        setup_tracing();
+        let error = RequestAttemptError::DbError(
+            DbError::ReadFailure {
+                consistency: Consistency::Two,
+                received: 1,
+                required: 2,
+                numfailures: 1,
+                data_present: false,
+            },
+            String::new(),
+        );
+        let policy = ReadFailureRetryPolicy::new(2);
+
+        // Not idempotent
+        let mut session = policy.new_session();
+        assert_eq!(
+            session.decide_should_retry(make_request_info(&error, false)),
+            RetryDecision::DontRetry
+        );
+
+        // Idempotent
+        let mut session = policy.new_session();
+        for _ in 0..2 {
+            assert_eq!(
+                session.decide_should_retry(make_request_info(&error, true)),
+                RetryDecision::RetryNextTarget(None)
+            );
+        }
+        assert_eq!(
+            session.decide_should_retry(make_request_info(&error, true)),
+            RetryDecision::DontRetry
+        );
+
+        // The budget is restored on reset
+        session.reset();
+        for _ in 0..2 {
+            assert_eq!(
+                session.decide_should_retry(make_request_info(&error, true)),
+                RetryDecision::RetryNextTarget(None)
+            );
+        }
+        assert_eq!(
+            session.decide_should_retry(make_request_info(&error, true)),
+            RetryDecision::DontRetry
+        );
+    }
+
+    // Other errors are handled like in DefaultRetryPolicy
+    #[test]
+    fn read_failure_policy_defers_to_default() {
+        setup_tracing();
+        let policy = ReadFailureRetryPolicy::new(2);
+
+        let error = RequestAttemptError::DbError(DbError::IsBootstrapping, String::new());
+        let mut session = policy.new_session();
+        assert_eq!(
+            session.decide_should_retry(make_request_info(&error, false)),
+            RetryDecision::RetryNextTarget(None)
+        );
+
+        let error = RequestAttemptError::DbError(DbError::SyntaxError, String::new());
+        let mut session = policy.new_session();
+        assert_eq!(
+            session.decide_should_retry(make_request_info(&error, true)),
+            RetryDecision::DontRetry
+        );
+    }
+}
@@ -123,6 +123,10 @@ fn can_be_ignored<ResT>(result: &Result<ResT, RequestError>) -> bool {
             // in the future, it should not be ignored.
             RequestError::RequestTimeout(_) => false,
 
+            // This fiber drew only non-replicas from the shared plan.
+            // Other fibers may still reach a replica.
+            RequestError::CoordinatorNotReplica(_) => true,
+
             // Can try on another node.
             RequestError::ConnectionPoolError { .. } => true,
 
@@ -193,7 +197,11 @@ where
                 if let Some(r) = res {
                     if !can_be_ignored(&r) {
                         return r;
-                    } else {
+                    } else if !matches!(r, Err(RequestError::CoordinatorNotReplica(_)))
+                        || last_error.is_none()
+                    {
+                        // An error of an attempt on a replica is more informative
+                        // than the lack of replicas in one of the fibers.
                         last_error = Some(r)
                     }
                 } else {
@@ -220,18 +228,20 @@ mod tests {
     // Starting paused is done with `#[tokio::test(flavor = "current_thread", start_paused = true)]`.
     // Pausing can only be done with current_thread executor.
 
-    #[cfg(feature = "metrics")]
     use std::sync::Arc;
     use std::sync::LazyLock;
     use std::time::Duration;
 
     use assert_matches::assert_matches;
 
-    use crate::errors::{RequestAttemptError, RequestError};
+    use crate::cluster::Node;
+    use crate::cluster::NodeAddr;
+    use crate::errors::{DbError, RequestAttemptError, RequestError};
     #[cfg(feature = "metrics")]
     use crate::observability::metrics::Metrics;
     use crate::policies::speculative_execution::{Context, SimpleSpeculativeExecutionPolicy};
     use crate::response::Coordinator;
+    use crate::statement::Consistency;
 
     static EMPTY_CONTEXT: LazyLock<Context> = LazyLock::new(|| Context {
         #[cfg(feature = "metrics")]
@@ -377,4 +387,74 @@ mod tests {
             now.checked_add(Duration::from_secs(10)).unwrap()
         )
     }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_speculative_unavailable_does_not_abort_original_execution() {
+        let policy = SimpleSpeculativeExecutionPolicy {
+            max_retry_count: 1,
+            retry_interval: Duration::from_secs(1),
+        };
+
+        let generator = move |is_speculative: bool| async move {
+            if is_speculative {
+                // The speculative execution hits a coordinator which sees a replica down.
+                Some(Err(RequestError::LastAttemptError(
+                    RequestAttemptError::DbError(
+                        DbError::Unavailable {
+                            consistency: Consistency::All,
+                            required: 3,
+                            alive: 2,
+                        },
+                        "Cannot achieve consistency level".to_owned(),
+                    ),
+                )))
+            } else {
+                // The original execution is slow, but succeeds.
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                let node = Arc::new(Node::new_for_test(None, None, None, None));
+                Some(Ok(((), Coordinator::new_for_test(node, None))))
+            }
+        };
+
+        let now = tokio::time::Instant::now();
+        let res = super::execute(&policy, &EMPTY_CONTEXT, generator).await;
+        assert_matches!(res, Ok(((), _)));
+        // t - now
+        // First execution is started at t, the speculative one at t+1 fails instantly
+        // with Unavailable. The error is ignored, and the first execution finishes at t+5.
+        assert_eq!(
+            tokio::time::Instant::now(),
+            now.checked_add(Duration::from_secs(5)).unwrap()
+        )
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_speculative_without_replica_does_not_abort_original_execution() {
+        let policy = SimpleSpeculativeExecutionPolicy {
+            max_retry_count: 1,
+            retry_interval: Duration::from_secs(1),
+        };
+
+        let generator = move |is_speculative: bool| async move {
+            if is_speculative {
+                // The speculative execution draws only non-replicas from the shared plan.
+                Some(Err(RequestError::CoordinatorNotReplica(
+                    NodeAddr::Translatable("127.0.0.1:9042".parse().unwrap()),
+                )))
+            } else {
+                // The original execution, on a replica, is slow, but fails with an ignorable error.
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                IGNORABLE_ERROR.clone()
+            }
+        };
+
+        let res = super::execute(&policy, &EMPTY_CONTEXT, generator).await;
+        // The error of the attempt on the replica is returned.
+        assert_matches!(
+            res,
+            Err(RequestError::LastAttemptError(
+                RequestAttemptError::UnableToAllocStreamId
+            ))
+        );
+    }
 }
@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::routing::NodeLocationPreference;
+use crate::statement::Consistency;
+
+/// Information about the attempt whose response became the result of a request.
+///
+/// A request may be attempted more than once, because of retries
+/// and speculative execution. Only the response of one of the attempts
+/// is returned to the user.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AttemptInfo {
+    /// Whether the response came from an execution started by the speculative execution
+    /// policy, rather than from the original execution of the request.
+    pub won_by_speculative: bool,
+
+    /// The number of the attempt whose response was used, starting from 1.
+    ///
+    /// Attempts are numbered in the order they were started, counting retries and
+    /// speculative executions alike - the same way as in the callback set with
+    /// [`Statement::set_on_attempt`](crate::statement::unprepared::Statement::set_on_attempt).
+    /// Zero if the result was not obtained by executing a request, e.g. a mocked one.
+    pub winning_attempt: u32,
+
+    /// Whether the coordinator is in the datacenter preferred by the session,
+    /// as set with [`SessionBuilder::prefer_datacenter`](crate::client::session_builder::SessionBuilder::prefer_datacenter).
+    ///
+    /// If the session has no preferred datacenter, every coordinator is considered local.
+    /// If it has one, a coordinator with an unknown datacenter is considered remote.
+    pub coordinator_was_local: bool,
+
+    /// The host id of the coordinator, as known from the cluster metadata.
+    ///
+    /// It identifies the node in the server logs, unlike its address, which may change.
+    /// `None` if the result was not obtained by executing a request, e.g. a mocked one.
+    pub coordinator_host_id: Option<Uuid>,
+
+    /// The size, in bytes, of the request frame sent in this attempt, including the frame header.
+    ///
+    /// If compression is used, this is the size after compression, i.e. the number of bytes
+    /// actually written to the connection. Zero if the result was not received from the server,
+    /// e.g. because the retry policy decided to ignore a write error.
+    pub bytes_sent: usize,
+
+    /// The size, in bytes, of the response frame received in this attempt, including the frame header.
+    ///
+    /// As with [`bytes_sent`](Self::bytes_sent), this is the size of the frame as received,
+    /// before decompression.
+    pub bytes_received: usize,
+
+    /// The consistency level of this attempt.
+    ///
+    /// It differs from the consistency of the request if the retry policy downgraded it
+    /// (see [`RetryDecision`](crate::policies::retry::RetryDecision)) before this attempt.
+    /// `None` if the result was not obtained by executing a request, e.g. a mocked one.
+    pub effective_consistency: Option<Consistency>,
+
+    /// The time this attempt spent waiting for a connection to the coordinator
+    /// from the node's connection pool, before the request could be sent. If the request
+    /// forces a fresh connection, this is the time it took to open the connection.
+    ///
+    /// It is included in the latency of the request, but not in the time
+    /// the coordinator took to respond. Zero if the result was not obtained
+    /// by executing a request, e.g. a mocked one.
+    pub connection_wait_time: Duration,
+
+    /// Whether this attempt was sent to the shard owning the request's token.
+    ///
+    /// This is the case if the request has a token (e.g. it is a prepared statement
+    /// with all partition key values bound), the coordinator is a sharded (ScyllaDB) node,
+    /// and a connection to the shard chosen by the load balancing policy was available.
+    /// False if the connection pool had to fall back to a connection to another shard.
+    pub shard_aware_routing_used: bool,
+}
+
+/// Tells whether a node in `node_datacenter` is local, given the location preference of the session.
+pub(crate) fn is_local_datacenter(
+    preference: &NodeLocationPreference,
+    node_datacenter: Option<&str>,
+) -> bool {
+    match preference.datacenter() {
+        Some(local_datacenter) => node_datacenter == Some(local_datacenter),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_local_datacenter;
+    use crate::routing::NodeLocationPreference;
+
+    #[test]
+    fn coordinator_is_local_in_preferred_datacenter() {
+        let preference = NodeLocationPreference::Datacenter("dc1".to_owned());
+        assert!(is_local_datacenter(&preference, Some("dc1")));
+        assert!(!is_local_datacenter(&preference, Some("dc2")));
+        assert!(!is_local_datacenter(&preference, None));
+
+        let preference =
+            NodeLocationPreference::DatacenterAndRack("dc1".to_owned(), "rack1".to_owned());
+        assert!(is_local_datacenter(&preference, Some("dc1")));
+        assert!(!is_local_datacenter(&preference, Some("dc2")));
+
+        // Without a preferred datacenter, no coordinator is remote.
+        assert!(is_local_datacenter(
+            &NodeLocationPreference::Any,
+            Some("dc2")
+        ));
+        assert!(is_local_datacenter(&NodeLocationPreference::Any, None));
+    }
+}
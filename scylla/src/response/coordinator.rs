@@ -55,3 +55,14 @@ impl Coordinator {
         self.shard
     }
 }
+
+#[cfg(test)]
+impl Coordinator {
+    pub(crate) fn new_for_test(node: Arc<Node>, shard: Option<Shard>) -> Self {
+        Self {
+            connection_address: SocketAddr::from(([127, 0, 0, 1], 9042)),
+            node,
+            shard,
+        }
+    }
+}
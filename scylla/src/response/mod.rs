@@ -7,12 +7,19 @@
 //! - [QueryRowsResult](query_result::QueryRowsResult) - a result of CQL QUERY/EXECUTE/BATCH
 //!   request that contains some rows, which can be deserialized by the user.
 
+mod attempt_info;
 mod coordinator;
 pub mod query_result;
+#[cfg(all(scylla_unstable, feature = "unstable-csharp-rs"))]
+mod raw_response;
 mod request_response;
 
 pub use crate::frame::request::query::{PagingState, PagingStateResponse};
+pub use attempt_info::AttemptInfo;
+pub(crate) use attempt_info::is_local_datacenter;
 pub use coordinator::Coordinator;
+#[cfg(all(scylla_unstable, feature = "unstable-csharp-rs"))]
+pub use raw_response::RawResponse;
 pub(crate) use request_response::{
     NonErrorAuthResponse, NonErrorQueryResponse, NonErrorStartupResponse, QueryResponse,
 };
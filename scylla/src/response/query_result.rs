@@ -3,16 +3,19 @@
 
 use std::fmt::Debug;
 
+use bytes::Bytes;
 use thiserror::Error;
 use uuid::Uuid;
 
 use crate::deserialize::result::TypedRowIterator;
-use crate::deserialize::row::DeserializeRow;
+use crate::deserialize::row::{ColumnIterator, DeserializeRow};
+use crate::deserialize::value::DeserializeValue;
 use crate::deserialize::{DeserializationError, TypeCheckError};
 use crate::frame::frame_errors::ResultMetadataAndRowsCountParseError;
 use crate::frame::response::result::{ColumnSpec, DeserializedMetadataAndRawRows};
 
-use crate::response::Coordinator;
+use crate::response::{AttemptInfo, Coordinator};
+use crate::value::{CqlValue, Row};
 
 /// A view over specification of columns returned by the database.
 #[derive(Debug, Clone, Copy)]
@@ -61,6 +64,21 @@ impl<'slice, 'spec> ColumnSpecs<'slice, 'spec> {
     }
 }
 
+/// A Result frame of a kind unknown to the driver, e.g. introduced by a newer protocol version.
+///
+/// Such results are only passed through if enabled with
+/// [`SessionBuilder::pass_through_unknown_result_kinds`](crate::client::session_builder::SessionBuilder::pass_through_unknown_result_kinds);
+/// otherwise, they fail the request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UnknownResult {
+    /// The kind of the result, as sent by the database.
+    pub kind: i32,
+
+    /// The body of the result, following its kind, as raw bytes.
+    pub body: Bytes,
+}
+
 /// Result of a single request to the database. It represents any kind of Result frame.
 ///
 /// The received rows and metadata, which are present if the frame is of Result:Rows kind,
@@ -78,6 +96,18 @@ pub struct QueryResult {
     deserialized_metadata_and_rows: Option<DeserializedMetadataAndRawRows>,
     tracing_id: Option<Uuid>,
     warnings: Vec<String>,
+    details: Box<QueryResultDetails>,
+}
+
+/// Diagnostic details of a [QueryResult].
+///
+/// They are boxed to keep the size of [QueryResult], and of errors containing it, small.
+#[derive(Debug, Clone, Default)]
+struct QueryResultDetails {
+    attempt_info: AttemptInfo,
+    truncated: bool,
+    unknown_result: Option<UnknownResult>,
+    response_flags: u8,
 }
 
 impl QueryResult {
@@ -92,6 +122,7 @@ impl QueryResult {
             deserialized_metadata_and_rows: raw_rows,
             tracing_id,
             warnings,
+            details: Box::default(),
         }
     }
 
@@ -113,6 +144,7 @@ impl QueryResult {
             deserialized_metadata_and_rows: raw_rows,
             tracing_id,
             warnings,
+            details: Box::default(),
         }
     }
 
@@ -124,13 +156,50 @@ impl QueryResult {
             deserialized_metadata_and_rows: None,
             tracing_id: None,
             warnings: Vec::new(),
+            details: Box::default(),
         }
     }
 
+    pub(crate) fn with_attempt_info(mut self, attempt_info: AttemptInfo) -> Self {
+        self.details.attempt_info = attempt_info;
+        self
+    }
+
+    pub(crate) fn with_response_flags(mut self, response_flags: u8) -> Self {
+        self.details.response_flags = response_flags;
+        self
+    }
+
+    pub(crate) fn with_truncated(mut self, truncated: bool) -> Self {
+        self.details.truncated = truncated;
+        self
+    }
+
+    pub(crate) fn with_unknown_result(mut self, unknown_result: Option<UnknownResult>) -> Self {
+        self.details.unknown_result = unknown_result;
+        self
+    }
+
     pub(crate) fn deserialized_metadata_and_rows(&self) -> Option<&DeserializedMetadataAndRawRows> {
         self.deserialized_metadata_and_rows.as_ref()
     }
 
+    /// Splits the result into its rows, tracing id and coordinator,
+    /// e.g. to hand it over to a pager as its first page.
+    pub(crate) fn into_page_parts(
+        self,
+    ) -> (
+        Option<DeserializedMetadataAndRawRows>,
+        Option<Uuid>,
+        Option<Coordinator>,
+    ) {
+        (
+            self.deserialized_metadata_and_rows,
+            self.tracing_id,
+            self.request_coordinator,
+        )
+    }
+
     /// The node+shard that served the request.
     #[inline]
     pub fn request_coordinator(&self) -> &Coordinator {
@@ -139,6 +208,12 @@ impl QueryResult {
             .expect("BUG: Driver leaked a QueryResult with an unknown Coordinator, even though such results are driver-internal.")
     }
 
+    /// Information about the attempt that produced this result.
+    #[inline]
+    pub fn attempt_info(&self) -> AttemptInfo {
+        self.details.attempt_info
+    }
+
     /// Warnings emitted by the database.
     #[inline]
     pub fn warnings(&self) -> impl Iterator<Item = &str> {
@@ -151,6 +226,40 @@ impl QueryResult {
         self.tracing_id
     }
 
+    /// Whether the server stopped returning rows before the end of the result,
+    /// as indicated by the "has more pages" flag of the result metadata.
+    ///
+    /// This is an alias of `!paging_state_response.finished()`, for the
+    /// [`PagingStateResponse`](crate::response::PagingStateResponse) received along with
+    /// this result, e.g. from [`Session::query_single_page`](crate::client::session::Session::query_single_page).
+    /// It is provided for results of methods which don't return the paging state response.
+    ///
+    /// This happens when a page is cut short by a server-side limit, i.e. the page size
+    /// (in rows or bytes). A `LIMIT` clause of the statement does not truncate the result,
+    /// as it is a part of the result's definition.
+    #[inline]
+    pub fn was_truncated(&self) -> bool {
+        self.details.truncated
+    }
+
+    /// The raw Result frame, if it was of a kind unknown to the driver.
+    ///
+    /// Such a result is not of Rows kind. See [`UnknownResult`].
+    #[inline]
+    pub fn unknown_result(&self) -> Option<&UnknownResult> {
+        self.details.unknown_result.as_ref()
+    }
+
+    /// Flags from the header of the response frame, for protocol-level debugging.
+    ///
+    /// The bits are defined by the CQL protocol: `0x01` - compression, `0x02` - tracing,
+    /// `0x04` - custom payload, `0x08` - warning.
+    #[cfg(all(scylla_unstable, feature = "unstable-frame-debug"))]
+    #[inline]
+    pub fn response_frame_flags(&self) -> u8 {
+        self.details.response_flags
+    }
+
     /// Returns a bool indicating the current response is of Rows type.
     #[inline]
     pub fn is_rows(&self) -> bool {
@@ -217,14 +326,202 @@ impl QueryResult {
         let tracing_id = self.tracing_id;
         let warnings = self.warnings;
         let request_coordinator = self.request_coordinator;
+        let attempt_info = self.details.attempt_info;
 
         Ok(QueryRowsResult {
             request_coordinator,
+            attempt_info,
             raw_rows_with_metadata,
             warnings,
             tracing_id,
         })
     }
+
+    /// Interprets the result of a lightweight transaction, e.g. `INSERT ... IF NOT EXISTS`
+    /// or `UPDATE ... IF <condition>`.
+    ///
+    /// The result of such a statement is a single row, whose first column is `[applied]`.
+    /// If the statement was not applied, the remaining columns hold the row which
+    /// caused the rejection, and they are deserialized to `T`.
+    ///
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # async fn example(session: &Session) -> Result<(), Box<dyn std::error::Error>> {
+    /// use scylla::response::query_result::LwtOutcome;
+    ///
+    /// let result = session
+    ///     .query_unpaged("INSERT INTO ks.tab (a, b) VALUES (1, 2) IF NOT EXISTS", ())
+    ///     .await?;
+    ///
+    /// match result.lwt_typed::<(i32, i32)>()? {
+    ///     LwtOutcome::Applied => println!("Inserted"),
+    ///     LwtOutcome::Rejected((a, b)) => println!("Row ({a}, {b}) already exists"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lwt_typed<'frame, T: DeserializeRow<'frame, 'frame>>(
+        &'frame self,
+    ) -> Result<LwtOutcome<T>, LwtTypedError> {
+        let Some(raw_rows_with_metadata) = &self.deserialized_metadata_and_rows else {
+            return Err(LwtTypedError::ResultNotRows);
+        };
+        let mut rows = raw_rows_with_metadata.rows_iter::<LwtRow<T>>()?;
+        match rows.next() {
+            Some(Ok(LwtRow(outcome))) => {
+                if rows.rows_remaining() != 0 {
+                    return Err(LwtTypedError::UnexpectedRowCount(rows.rows_remaining() + 1));
+                }
+                Ok(outcome)
+            }
+            Some(Err(err)) => Err(LwtTypedError::DeserializationFailed(err)),
+            None => Err(LwtTypedError::UnexpectedRowCount(0)),
+        }
+    }
+
+    /// Interprets the result of a lightweight transaction without knowing its columns upfront,
+    /// returning whether the statement was applied, followed by the values of the remaining
+    /// columns, if any. Null values are represented as `None`.
+    ///
+    /// See [`QueryResult::lwt_typed`] for a typed alternative.
+    pub fn lwt_dynamic(&self) -> Result<(bool, Vec<Option<CqlValue>>), LwtTypedError> {
+        let Some(raw_rows_with_metadata) = &self.deserialized_metadata_and_rows else {
+            return Err(LwtTypedError::ResultNotRows);
+        };
+        let mut rows = raw_rows_with_metadata.rows_iter::<LwtDynamicRow>()?;
+        match rows.next() {
+            Some(Ok(LwtDynamicRow { applied, columns })) => {
+                if rows.rows_remaining() != 0 {
+                    return Err(LwtTypedError::UnexpectedRowCount(rows.rows_remaining() + 1));
+                }
+                Ok((applied, columns))
+            }
+            Some(Err(err)) => Err(LwtTypedError::DeserializationFailed(err)),
+            None => Err(LwtTypedError::UnexpectedRowCount(0)),
+        }
+    }
+
+    /// Deserializes all the received rows and returns them sorted by the given key.
+    ///
+    /// The sort is stable and happens on the client, so it's meant for small result sets
+    /// whose order can't be expressed with `ORDER BY`.
+    ///
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # async fn example(session: &Session) -> Result<(), Box<dyn std::error::Error>> {
+    /// let users = session
+    ///     .query_unpaged("SELECT id, name FROM ks.users", ())
+    ///     .await?
+    ///     .sorted_by(|(_id, name): &(i32, String)| name.clone())?;
+    ///
+    /// for (id, name) in users {
+    ///     println!("{name}: {id}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sorted_by<T, K, F>(self, key: F) -> Result<Vec<T>, SortedByError>
+    where
+        T: for<'frame, 'metadata> DeserializeRow<'frame, 'metadata>,
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        let Some(raw_rows_with_metadata) = &self.deserialized_metadata_and_rows else {
+            return Err(SortedByError::ResultNotRows);
+        };
+        let mut rows = raw_rows_with_metadata
+            .rows_iter::<T>()?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.sort_by_key(key);
+        Ok(rows)
+    }
+}
+
+/// Outcome of a lightweight transaction, returned by [`QueryResult::lwt_typed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LwtOutcome<T> {
+    /// The statement was applied.
+    Applied,
+
+    /// The statement was not applied, because of the contained row.
+    Rejected(T),
+}
+
+const LWT_APPLIED_COLUMN: &str = "[applied]";
+
+/// A row of a lightweight transaction result: `[applied]`, followed by the columns of `T`.
+struct LwtRow<T>(LwtOutcome<T>);
+
+#[derive(Debug, Error)]
+enum LwtRowError {
+    #[error("The first column of a lightweight transaction result must be `{LWT_APPLIED_COLUMN}`")]
+    AppliedColumnMissing,
+
+    #[error("The lightweight transaction was not applied, but the result holds no rejecting row")]
+    RejectingRowMissing,
+}
+
+impl<'frame, 'metadata, T: DeserializeRow<'frame, 'metadata>> DeserializeRow<'frame, 'metadata>
+    for LwtRow<T>
+{
+    fn type_check(specs: &[ColumnSpec]) -> Result<(), TypeCheckError> {
+        let Some((applied, rest)) = specs.split_first() else {
+            return Err(TypeCheckError::new(LwtRowError::AppliedColumnMissing));
+        };
+        if applied.name() != LWT_APPLIED_COLUMN {
+            return Err(TypeCheckError::new(LwtRowError::AppliedColumnMissing));
+        }
+        <bool as DeserializeValue<'frame, 'metadata>>::type_check(applied.typ())?;
+        // Cassandra returns only the `[applied]` column if the statement was applied.
+        if rest.is_empty() {
+            return Ok(());
+        }
+        T::type_check(rest)
+    }
+
+    fn deserialize(
+        mut row: ColumnIterator<'frame, 'metadata>,
+    ) -> Result<Self, DeserializationError> {
+        let applied = row
+            .next()
+            .ok_or_else(|| DeserializationError::new(LwtRowError::AppliedColumnMissing))??;
+        if <bool as DeserializeValue<'frame, 'metadata>>::deserialize(
+            applied.spec.typ(),
+            applied.slice,
+        )? {
+            return Ok(Self(LwtOutcome::Applied));
+        }
+        if row.columns_remaining() == 0 {
+            return Err(DeserializationError::new(LwtRowError::RejectingRowMissing));
+        }
+        T::deserialize(row).map(|rejecting_row| Self(LwtOutcome::Rejected(rejecting_row)))
+    }
+}
+
+/// A row of a lightweight transaction result: `[applied]`, followed by any columns.
+struct LwtDynamicRow {
+    applied: bool,
+    columns: Vec<Option<CqlValue>>,
+}
+
+impl<'frame, 'metadata> DeserializeRow<'frame, 'metadata> for LwtDynamicRow {
+    fn type_check(specs: &[ColumnSpec]) -> Result<(), TypeCheckError> {
+        <LwtRow<Row> as DeserializeRow<'frame, 'metadata>>::type_check(specs)
+    }
+
+    fn deserialize(
+        mut row: ColumnIterator<'frame, 'metadata>,
+    ) -> Result<Self, DeserializationError> {
+        let applied = row
+            .next()
+            .ok_or_else(|| DeserializationError::new(LwtRowError::AppliedColumnMissing))??;
+        let applied = <bool as DeserializeValue<'frame, 'metadata>>::deserialize(
+            applied.spec.typ(),
+            applied.slice,
+        )?;
+        let Row { columns } = Row::deserialize(row)?;
+        Ok(Self { applied, columns })
+    }
 }
 
 /// Enables deserialization of rows received from the database in a [`QueryResult`].
@@ -261,6 +558,7 @@ pub struct QueryRowsResult {
     /// If user gets a `QueryResult` with `request_coordinator` set to `None`,
     /// this is a bug.
     request_coordinator: Option<Coordinator>,
+    attempt_info: AttemptInfo,
     raw_rows_with_metadata: DeserializedMetadataAndRawRows,
     tracing_id: Option<Uuid>,
     warnings: Vec<String>,
@@ -287,6 +585,12 @@ impl QueryRowsResult {
             .expect("BUG: Driver leaked a QueryResult with an unknown Coordinator, even though such results are driver-internal.")
     }
 
+    /// Information about the attempt that produced this result.
+    #[inline]
+    pub fn attempt_info(&self) -> AttemptInfo {
+        self.attempt_info
+    }
+
     /// Returns the number of received rows.
     #[inline]
     pub fn rows_num(&self) -> usize {
@@ -396,6 +700,7 @@ impl QueryRowsResult {
             tracing_id,
             warnings,
             request_coordinator,
+            attempt_info: _,
         } = self;
 
         (
@@ -482,6 +787,42 @@ pub enum SingleRowError {
     DeserializationFailed(#[from] DeserializationError),
 }
 
+/// An error returned by [`QueryResult::lwt_typed`] and [`QueryResult::lwt_dynamic`].
+#[derive(Debug, Error, Clone)]
+pub enum LwtTypedError {
+    /// Result is not of Rows kind
+    #[error("Result is not of Rows kind")]
+    ResultNotRows,
+
+    /// Expected one row, but got a different count
+    #[error("Expected a single row, but got {0} rows")]
+    UnexpectedRowCount(usize),
+
+    /// Type check failed
+    #[error("Type check failed: {0}")]
+    TypeCheckFailed(#[from] TypeCheckError),
+
+    /// Deserialization failed
+    #[error("Deserialization failed: {0}")]
+    DeserializationFailed(#[from] DeserializationError),
+}
+
+/// An error returned by [`QueryResult::sorted_by`].
+#[derive(Debug, Error, Clone)]
+pub enum SortedByError {
+    /// Result is not of Rows kind
+    #[error("Result is not of Rows kind")]
+    ResultNotRows,
+
+    /// Type check failed
+    #[error("Type check failed: {0}")]
+    TypeCheckFailed(#[from] TypeCheckError),
+
+    /// Deserialization failed
+    #[error("Deserialization failed: {0}")]
+    DeserializationFailed(#[from] DeserializationError),
+}
+
 /// An error returned by [`QueryResult::result_not_rows`].
 ///
 /// It indicates that response to the request was, unexpectedly, of Rows kind.
@@ -821,4 +1162,201 @@ mod tests {
             }
         }
     }
+
+    fn lwt_result(applied: bool, rejecting_row: Option<(i32, &str)>) -> QueryResult {
+        let mut specs = vec![ColumnSpec::owned(
+            "[applied]".to_owned(),
+            ColumnType::Native(NativeType::Boolean),
+            TABLE_SPEC,
+        )];
+        let mut bytes = BytesMut::new();
+        types::write_bytes_opt(Some(&[applied as u8]), &mut bytes).unwrap();
+        if let Some((id, name)) = rejecting_row {
+            specs.push(ColumnSpec::owned(
+                "id".to_owned(),
+                ColumnType::Native(NativeType::Int),
+                TABLE_SPEC,
+            ));
+            specs.push(ColumnSpec::owned(
+                "name".to_owned(),
+                ColumnType::Native(NativeType::Text),
+                TABLE_SPEC,
+            ));
+            types::write_bytes_opt(Some(id.to_be_bytes()), &mut bytes).unwrap();
+            types::write_bytes_opt(Some(name.as_bytes()), &mut bytes).unwrap();
+        }
+        let metadata = ResultMetadata::new_for_test(specs.len(), specs);
+        let rr = DeserializedMetadataAndRawRows::new_for_test(metadata, 1, bytes.freeze());
+        QueryResult::new_with_unknown_coordinator(Some(rr), None, Vec::new())
+    }
+
+    #[test]
+    fn test_lwt_typed() {
+        // Applied
+        {
+            let qr = lwt_result(true, None);
+            assert_eq!(qr.lwt_typed::<(i32, &str)>().unwrap(), LwtOutcome::Applied);
+        }
+
+        // Rejected, with the existing row returned
+        {
+            let qr = lwt_result(false, Some((42, "Ferris")));
+            assert_eq!(
+                qr.lwt_typed::<(i32, &str)>().unwrap(),
+                LwtOutcome::Rejected((42, "Ferris"))
+            );
+        }
+
+        // Rejected, but the existing row has a different shape
+        {
+            let qr = lwt_result(false, Some((42, "Ferris")));
+            assert_matches!(
+                qr.lwt_typed::<(i32,)>(),
+                Err(LwtTypedError::TypeCheckFailed(_))
+            );
+        }
+
+        // Not RESULT::Rows response
+        {
+            let qr = QueryResult::new_with_unknown_coordinator(None, None, Vec::new());
+            assert_matches!(
+                qr.lwt_typed::<(i32, &str)>(),
+                Err(LwtTypedError::ResultNotRows)
+            );
+        }
+    }
+
+    #[test]
+    fn test_lwt_dynamic() {
+        // Applied
+        {
+            let qr = lwt_result(true, None);
+            assert_eq!(qr.lwt_dynamic().unwrap(), (true, vec![]));
+        }
+
+        // Rejected, with the existing row returned
+        {
+            let qr = lwt_result(false, Some((42, "Ferris")));
+            assert_eq!(
+                qr.lwt_dynamic().unwrap(),
+                (
+                    false,
+                    vec![
+                        Some(CqlValue::Int(42)),
+                        Some(CqlValue::Text("Ferris".to_owned()))
+                    ]
+                )
+            );
+        }
+
+        // Rejected, with a null value in the existing row
+        {
+            let specs = vec![
+                ColumnSpec::owned(
+                    "[applied]".to_owned(),
+                    ColumnType::Native(NativeType::Boolean),
+                    TABLE_SPEC,
+                ),
+                ColumnSpec::owned(
+                    "name".to_owned(),
+                    ColumnType::Native(NativeType::Text),
+                    TABLE_SPEC,
+                ),
+            ];
+            let mut bytes = BytesMut::new();
+            types::write_bytes_opt(Some(&[0_u8]), &mut bytes).unwrap();
+            types::write_bytes_opt(None::<&[u8]>, &mut bytes).unwrap();
+            let metadata = ResultMetadata::new_for_test(specs.len(), specs);
+            let rr = DeserializedMetadataAndRawRows::new_for_test(metadata, 1, bytes.freeze());
+            let qr = QueryResult::new_with_unknown_coordinator(Some(rr), None, Vec::new());
+            assert_eq!(qr.lwt_dynamic().unwrap(), (false, vec![None]));
+        }
+
+        // Not a result of a lightweight transaction, even though the first column is a boolean
+        {
+            let specs = vec![ColumnSpec::owned(
+                "applied".to_owned(),
+                ColumnType::Native(NativeType::Boolean),
+                TABLE_SPEC,
+            )];
+            let mut bytes = BytesMut::new();
+            types::write_bytes_opt(Some(&[1_u8]), &mut bytes).unwrap();
+            let metadata = ResultMetadata::new_for_test(specs.len(), specs);
+            let rr = DeserializedMetadataAndRawRows::new_for_test(metadata, 1, bytes.freeze());
+            let qr = QueryResult::new_with_unknown_coordinator(Some(rr), None, Vec::new());
+            assert_matches!(qr.lwt_dynamic(), Err(LwtTypedError::TypeCheckFailed(_)));
+        }
+
+        // Not RESULT::Rows response
+        {
+            let qr = QueryResult::new_with_unknown_coordinator(None, None, Vec::new());
+            assert_matches!(qr.lwt_dynamic(), Err(LwtTypedError::ResultNotRows));
+        }
+    }
+
+    #[test]
+    fn test_sorted_by() {
+        let specs = vec![
+            ColumnSpec::owned(
+                "id".to_owned(),
+                ColumnType::Native(NativeType::Int),
+                TABLE_SPEC,
+            ),
+            ColumnSpec::owned(
+                "name".to_owned(),
+                ColumnType::Native(NativeType::Text),
+                TABLE_SPEC,
+            ),
+        ];
+        let rows = [(3_i32, "Ferris"), (1, "Crab"), (2, "Ferris"), (4, "Alice")];
+        let mut bytes = BytesMut::new();
+        for (id, name) in rows {
+            types::write_bytes_opt(Some(id.to_be_bytes()), &mut bytes).unwrap();
+            types::write_bytes_opt(Some(name.as_bytes()), &mut bytes).unwrap();
+        }
+        let metadata = ResultMetadata::new_for_test(specs.len(), specs);
+        let rr = DeserializedMetadataAndRawRows::new_for_test(metadata, rows.len(), bytes.freeze());
+        let qr = QueryResult::new_with_unknown_coordinator(Some(rr), None, Vec::new());
+
+        // Sorted by id
+        assert_eq!(
+            qr.clone()
+                .sorted_by(|(id, _name): &(i32, String)| *id)
+                .unwrap(),
+            vec![
+                (1, "Crab".to_owned()),
+                (2, "Ferris".to_owned()),
+                (3, "Ferris".to_owned()),
+                (4, "Alice".to_owned()),
+            ]
+        );
+
+        // Sorted by name, keeping the received order of equal names
+        assert_eq!(
+            qr.clone()
+                .sorted_by(|(_id, name): &(i32, String)| name.clone())
+                .unwrap(),
+            vec![
+                (4, "Alice".to_owned()),
+                (1, "Crab".to_owned()),
+                (3, "Ferris".to_owned()),
+                (2, "Ferris".to_owned()),
+            ]
+        );
+
+        // Rows of a different shape
+        assert_matches!(
+            qr.sorted_by(|(id,): &(i32,)| *id),
+            Err(SortedByError::TypeCheckFailed(_))
+        );
+
+        // Not RESULT::Rows response
+        {
+            let qr = QueryResult::new_with_unknown_coordinator(None, None, Vec::new());
+            assert_matches!(
+                qr.sorted_by(|(id,): &(i32,)| *id),
+                Err(SortedByError::ResultNotRows)
+            );
+        }
+    }
 }
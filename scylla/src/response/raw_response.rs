@@ -0,0 +1,60 @@
+use bytes::Bytes;
+
+use crate::frame::Compression;
+
+/// A successful response to a request, with the frame body left exactly as received
+/// from the database - in particular, still compressed if compression is in use.
+///
+/// This allows forwarding the response without decompressing and re-compressing it.
+/// The body includes the frame body extensions (tracing id, warnings, custom payload),
+/// whose presence is indicated by [`RawResponse::flags`].
+#[derive(Debug, Clone)]
+pub struct RawResponse {
+    flags: u8,
+    compression: Option<Compression>,
+    body: Bytes,
+}
+
+impl RawResponse {
+    pub(crate) fn new(flags: u8, compression: Option<Compression>, body: Bytes) -> Self {
+        Self {
+            flags,
+            compression,
+            body,
+        }
+    }
+
+    /// An uncompressed `RESULT` of kind `Void`, used when a failed write was ignored
+    /// by the retry policy and there is no response from the database.
+    pub(crate) fn void() -> Self {
+        Self {
+            flags: 0,
+            compression: None,
+            body: Bytes::from_static(&[0x00, 0x00, 0x00, 0x01]),
+        }
+    }
+
+    /// Flags of the response frame header.
+    #[inline]
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// The algorithm the body is compressed with, or `None` if the body is not compressed.
+    #[inline]
+    pub fn compression(&self) -> Option<Compression> {
+        self.compression
+    }
+
+    /// The frame body, as received from the database.
+    #[inline]
+    pub fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// Consumes the response, returning the frame body as received from the database.
+    #[inline]
+    pub fn into_body(self) -> Bytes {
+        self.body
+    }
+}
@@ -12,7 +12,7 @@ use uuid::Uuid;
 use crate::errors::RequestAttemptError;
 use crate::frame::response::{self, result};
 use crate::response::Coordinator;
-use crate::response::query_result::QueryResult;
+use crate::response::query_result::{QueryResult, UnknownResult};
 
 pub(crate) struct QueryResponse {
     pub(crate) response: ResponseWithDeserializedMetadata,
@@ -20,6 +20,14 @@ pub(crate) struct QueryResponse {
     pub(crate) warnings: Vec<String>,
     // This is not exposed to user (yet?)
     pub(crate) custom_payload: Option<HashMap<String, Bytes>>,
+    // Sizes of the request frame and of the response frame.
+    pub(crate) bytes_sent: usize,
+    pub(crate) bytes_received: usize,
+    // Flags from the header of the response frame.
+    pub(crate) response_flags: u8,
+    // Set if the response is a RESULT of an unknown kind, which was passed through
+    // instead of failing. `response` is then a VOID result.
+    pub(crate) unknown_result: Option<UnknownResult>,
 }
 
 // A QueryResponse in which response can not be Response::Error
@@ -27,6 +35,10 @@ pub(crate) struct NonErrorQueryResponse {
     pub(crate) response: NonErrorResponseWithDeserializedMetadata,
     pub(crate) tracing_id: Option<Uuid>,
     pub(crate) warnings: Vec<String>,
+    pub(crate) unknown_result: Option<UnknownResult>,
+    pub(crate) bytes_sent: usize,
+    pub(crate) bytes_received: usize,
+    pub(crate) response_flags: u8,
 }
 
 impl QueryResponse {
@@ -37,6 +49,10 @@ impl QueryResponse {
             response: self.response.into_non_error_response()?,
             tracing_id: self.tracing_id,
             warnings: self.warnings,
+            unknown_result: self.unknown_result,
+            bytes_sent: self.bytes_sent,
+            bytes_received: self.bytes_received,
+            response_flags: self.response_flags,
         })
     }
 }
@@ -68,6 +84,9 @@ impl NonErrorQueryResponse {
             response,
             tracing_id,
             warnings,
+            response_flags,
+            unknown_result,
+            ..
         } = self;
         let (raw_rows, paging_state_response) = match response {
             NonErrorResponseWithDeserializedMetadata::Result(
@@ -83,11 +102,16 @@ impl NonErrorQueryResponse {
             }
         };
 
+        let result = match request_coordinator {
+            Some(coordinator) => QueryResult::new(coordinator, raw_rows, tracing_id, warnings),
+            None => QueryResult::new_with_unknown_coordinator(raw_rows, tracing_id, warnings),
+        };
+
         Ok((
-            match request_coordinator {
-                Some(coordinator) => QueryResult::new(coordinator, raw_rows, tracing_id, warnings),
-                None => QueryResult::new_with_unknown_coordinator(raw_rows, tracing_id, warnings),
-            },
+            result
+                .with_response_flags(response_flags)
+                .with_unknown_result(unknown_result)
+                .with_truncated(!paging_state_response.finished()),
             paging_state_response,
         ))
     }
@@ -239,6 +239,7 @@ mod tests {
                 tables: HashMap::new(),
                 views: HashMap::new(),
                 user_defined_types: HashMap::new(),
+                partitioner: None,
             }),
         )]
         .iter()
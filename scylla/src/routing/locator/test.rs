@@ -126,6 +126,7 @@ pub(crate) fn mock_metadata_for_token_aware_tests() -> Metadata {
                 tables: HashMap::new(),
                 views: HashMap::new(),
                 user_defined_types: HashMap::new(),
+                partitioner: None,
             }),
         ),
         (
@@ -140,6 +141,7 @@ pub(crate) fn mock_metadata_for_token_aware_tests() -> Metadata {
                 tables: HashMap::new(),
                 views: HashMap::new(),
                 user_defined_types: HashMap::new(),
+                partitioner: None,
             }),
         ),
         (
@@ -154,6 +156,7 @@ pub(crate) fn mock_metadata_for_token_aware_tests() -> Metadata {
                 tables: HashMap::new(),
                 views: HashMap::new(),
                 user_defined_types: HashMap::new(),
+                partitioner: None,
             }),
         ),
     ]
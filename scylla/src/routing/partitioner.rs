@@ -8,7 +8,6 @@
 //! - CDCPartitioner
 //!     - the partitioner employed when using CDC (_Change Data Capture_).
 
-use crate::frame::types::RawValue;
 use crate::serialize::row::SerializedValues;
 use bytes::Buf;
 use std::num::Wrapping;
@@ -396,19 +395,33 @@ impl PartitionerHasher for CDCPartitionerHasher {
 pub(crate) fn calculate_token_for_partition_key(
     serialized_partition_key_values: &SerializedValues,
     partitioner: &PartitionerName,
+) -> Result<Token, TokenCalculationError> {
+    calculate_token_for_raw_partition_key(
+        serialized_partition_key_values.element_count() as usize,
+        serialized_partition_key_values
+            .iter()
+            .map(|value| value.as_value()),
+        partitioner,
+    )
+}
+
+/// Calculates the token for given partitioner and raw values of the partition key columns,
+/// e.g. read from a received row. Null values are `None`.
+///
+/// `values_count` must be the number of `values`.
+pub(crate) fn calculate_token_for_raw_partition_key<'a>(
+    values_count: usize,
+    values: impl Iterator<Item = Option<&'a [u8]>>,
+    partitioner: &PartitionerName,
 ) -> Result<Token, TokenCalculationError> {
     let mut partitioner_hasher = partitioner.build_hasher();
 
-    if serialized_partition_key_values.element_count() == 1 {
-        let val = serialized_partition_key_values.iter().next().unwrap();
-        if let RawValue::Value(val) = val {
+    if values_count == 1 {
+        for val in values.flatten() {
             partitioner_hasher.write(val);
         }
     } else {
-        for val in serialized_partition_key_values
-            .iter()
-            .filter_map(|rv| rv.as_value())
-        {
+        for val in values.flatten() {
             let val_len_u16: u16 = val
                 .len()
                 .try_into()
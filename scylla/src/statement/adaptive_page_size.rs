@@ -0,0 +1,213 @@
+//! Adaptive paging, which adjusts the number of rows per page to reach a target page size in bytes.
+
+use std::num::NonZeroUsize;
+
+use thiserror::Error;
+
+use super::PageSize;
+
+/// Weight of the most recent page in the running estimate of bytes per row.
+const BYTES_PER_ROW_SMOOTHING: f64 = 0.5;
+
+/// Configuration of adaptive paging.
+///
+/// When set on a statement, the first page is requested with the statement's page size.
+/// Then, the size of each following page is chosen so that, given the average size of rows
+/// observed so far, the page approaches `target_page_bytes`. This keeps pages of tables
+/// with variable-width rows at a predictable size in memory.
+///
+/// Only paged executions (`Session::{query,execute}_iter`) are affected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptivePageSize {
+    target_page_bytes: NonZeroUsize,
+    min_page_size: PageSize,
+    max_page_size: PageSize,
+}
+
+impl AdaptivePageSize {
+    /// Creates a configuration aiming for pages of `target_page_bytes` bytes of rows,
+    /// with no bounds on the number of rows per page.
+    pub fn new(target_page_bytes: NonZeroUsize) -> Self {
+        Self {
+            target_page_bytes,
+            min_page_size: PageSize(1),
+            max_page_size: PageSize(i32::MAX),
+        }
+    }
+
+    /// Bounds the number of rows per page chosen by adaptive paging.
+    ///
+    /// The error is returned if `min` is not positive or is greater than `max`.
+    pub fn with_page_size_bounds(
+        mut self,
+        min: i32,
+        max: i32,
+    ) -> Result<Self, InvalidPageSizeBounds> {
+        if min <= 0 || min > max {
+            return Err(InvalidPageSizeBounds { min, max });
+        }
+        self.min_page_size = PageSize(min);
+        self.max_page_size = PageSize(max);
+        Ok(self)
+    }
+
+    /// The targeted size of rows in a single page, in bytes.
+    pub fn target_page_bytes(&self) -> NonZeroUsize {
+        self.target_page_bytes
+    }
+
+    /// The minimum and maximum number of rows per page.
+    pub fn page_size_bounds(&self) -> (i32, i32) {
+        (self.min_page_size.inner(), self.max_page_size.inner())
+    }
+}
+
+/// An error returned by [`AdaptivePageSize::with_page_size_bounds()`].
+#[derive(Debug, Error)]
+#[error("Invalid adaptive page size bounds [{min}, {max}]")]
+pub struct InvalidPageSizeBounds {
+    min: i32,
+    max: i32,
+}
+
+/// Chooses the page size of consecutive pages of a paged execution.
+#[derive(Debug)]
+pub(crate) struct PageSizer {
+    page_size: PageSize,
+    adaptive: Option<AdaptivePageSize>,
+    bytes_per_row: Option<f64>,
+}
+
+impl PageSizer {
+    pub(crate) fn new(initial_page_size: PageSize, adaptive: Option<AdaptivePageSize>) -> Self {
+        Self {
+            page_size: initial_page_size,
+            adaptive,
+            bytes_per_row: None,
+        }
+    }
+
+    /// The page size to request the next page with.
+    pub(crate) fn page_size(&self) -> PageSize {
+        self.page_size
+    }
+
+    /// Adjusts the page size after receiving a page of `rows_count` rows,
+    /// serialized in `rows_bytes` bytes.
+    pub(crate) fn observe_page(&mut self, rows_count: usize, rows_bytes: usize) {
+        let Some(adaptive) = &self.adaptive else {
+            return;
+        };
+        if rows_count == 0 {
+            // Nothing can be learned about the size of rows.
+            return;
+        }
+
+        let observed = rows_bytes as f64 / rows_count as f64;
+        let bytes_per_row = match self.bytes_per_row {
+            Some(estimate) => estimate + BYTES_PER_ROW_SMOOTHING * (observed - estimate),
+            None => observed,
+        };
+        self.bytes_per_row = Some(bytes_per_row);
+
+        let rows = adaptive.target_page_bytes.get() as f64 / bytes_per_row.max(1.0);
+        self.page_size = PageSize(rows.round().clamp(
+            adaptive.min_page_size.inner() as f64,
+            adaptive.max_page_size.inner() as f64,
+        ) as i32);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::{AdaptivePageSize, PageSizer};
+    use crate::statement::PageSize;
+
+    const TARGET_PAGE_BYTES: usize = 64 * 1024;
+
+    fn adaptive() -> AdaptivePageSize {
+        AdaptivePageSize::new(NonZeroUsize::new(TARGET_PAGE_BYTES).unwrap())
+    }
+
+    // Returns the sizes in bytes of consecutive pages fetched with the page sizer.
+    fn fetch_pages(sizer: &mut PageSizer, row_width: impl Fn(usize) -> usize) -> Vec<usize> {
+        let mut next_row = 0;
+        (0..10)
+            .map(|_| {
+                let rows = sizer.page_size().inner() as usize;
+                let bytes = (next_row..next_row + rows).map(&row_width).sum();
+                next_row += rows;
+                sizer.observe_page(rows, bytes);
+                bytes
+            })
+            .collect()
+    }
+
+    #[test]
+    fn page_size_trends_toward_target_bytes() {
+        // Rows alternate between narrow and wide ones, 1 KiB on average.
+        let row_width = |row: usize| if row.is_multiple_of(3) { 2048 } else { 512 };
+
+        for initial_page_size in [1, 10, 5000] {
+            let mut sizer =
+                PageSizer::new(PageSize::new(initial_page_size).unwrap(), Some(adaptive()));
+            let page_bytes = fetch_pages(&mut sizer, row_width);
+
+            // Pages approach the target, and eventually stay within 5% of it.
+            let first_distance = page_bytes[0].abs_diff(TARGET_PAGE_BYTES);
+            for bytes in &page_bytes[1..] {
+                assert!(
+                    bytes.abs_diff(TARGET_PAGE_BYTES) <= first_distance,
+                    "{page_bytes:?}"
+                );
+            }
+            for bytes in &page_bytes[page_bytes.len() - 3..] {
+                assert!(
+                    bytes.abs_diff(TARGET_PAGE_BYTES) < TARGET_PAGE_BYTES / 20,
+                    "{page_bytes:?}"
+                );
+            }
+            assert_eq!(sizer.page_size().inner(), 64);
+        }
+    }
+
+    #[test]
+    fn page_size_follows_changing_row_width() {
+        let mut sizer = PageSizer::new(PageSize::new(100).unwrap(), Some(adaptive()));
+        fetch_pages(&mut sizer, |_| 256);
+        assert_eq!(sizer.page_size().inner(), 256);
+
+        fetch_pages(&mut sizer, |_| 4096);
+        assert_eq!(sizer.page_size().inner(), 16);
+    }
+
+    #[test]
+    fn page_size_respects_bounds() {
+        let mut sizer = PageSizer::new(
+            PageSize::default(),
+            Some(adaptive().with_page_size_bounds(100, 1000).unwrap()),
+        );
+        fetch_pages(&mut sizer, |_| 16 * 1024);
+        assert_eq!(sizer.page_size().inner(), 100);
+
+        fetch_pages(&mut sizer, |_| 1);
+        assert_eq!(sizer.page_size().inner(), 1000);
+    }
+
+    #[test]
+    fn invalid_page_size_bounds_are_rejected() {
+        adaptive().with_page_size_bounds(0, 1000).unwrap_err();
+        adaptive().with_page_size_bounds(-5, 1000).unwrap_err();
+        adaptive().with_page_size_bounds(1000, 100).unwrap_err();
+        adaptive().with_page_size_bounds(100, 100).unwrap();
+    }
+
+    #[test]
+    fn page_size_is_fixed_without_adaptive_paging() {
+        let mut sizer = PageSizer::new(PageSize::new(100).unwrap(), None);
+        fetch_pages(&mut sizer, |_| 16 * 1024);
+        assert_eq!(sizer.page_size().inner(), 100);
+    }
+}
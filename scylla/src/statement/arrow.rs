@@ -0,0 +1,442 @@
+//! Binding values from Apache Arrow [`RecordBatch`]es, for bulk loading columnar data.
+//!
+//! A [`RecordBatchValues`] maps the columns of a record batch onto the bind markers
+//! of a prepared statement, checking once for the whole batch that their types are
+//! compatible, and yields the rows of the batch as values bound to the statement.
+//!
+//! ```rust
+//! # use scylla::client::session::Session;
+//! # use std::error::Error;
+//! # async fn check_only_compiles(session: &Session, batch: &arrow_array::RecordBatch) -> Result<(), Box<dyn Error>> {
+//! use scylla::statement::arrow::RecordBatchValues;
+//!
+//! let prepared = session
+//!     .prepare("INSERT INTO ks.tab (a, b) VALUES (?, ?)")
+//!     .await?;
+//!
+//! let values = RecordBatchValues::new(&prepared, batch)?;
+//! for row in values.rows() {
+//!     session.execute_unpaged(&prepared, row).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type, Float32Type, Float64Type, Int8Type, Int16Type, Int32Type, Int64Type,
+    Time64NanosecondType, TimestampMillisecondType,
+};
+use arrow_array::{Array, RecordBatch};
+use arrow_schema::{DataType, TimeUnit};
+use thiserror::Error;
+
+use crate::cluster::metadata::{ColumnType, NativeType};
+use crate::frame::response::result::ColumnSpec;
+use crate::serialize::SerializationError;
+use crate::serialize::row::{RowSerializationContext, SerializeRow, SerializedValues};
+use crate::serialize::writers::{CellOverflowError, CellWriter, RowWriter, WrittenCellProof};
+use crate::statement::prepared::PreparedStatement;
+
+/// The rows of a [`RecordBatch`], bound to the bind markers of a prepared statement.
+///
+/// Columns are bound positionally: the i-th column of the batch provides values
+/// for the i-th bind marker. Null entries are bound as nulls.
+///
+/// Supported column types, and the CQL types they can be bound to, are:
+///
+/// | Arrow type                                   | CQL type              |
+/// |----------------------------------------------|-----------------------|
+/// | `Boolean`                                    | `boolean`             |
+/// | `Int8`                                       | `tinyint`             |
+/// | `Int16`                                      | `smallint`            |
+/// | `Int32`                                      | `int`                 |
+/// | `Int64`                                      | `bigint`              |
+/// | `Float32`                                    | `float`               |
+/// | `Float64`                                    | `double`              |
+/// | `Utf8`, `LargeUtf8`                          | `text`, `ascii`       |
+/// | `Binary`, `LargeBinary`                      | `blob`                |
+/// | `Date32`                                     | `date`                |
+/// | `Timestamp(Millisecond, _)`                  | `timestamp`           |
+/// | `Time64(Nanosecond)`                         | `time`                |
+/// | `FixedSizeBinary(16)`                        | `uuid`, `timeuuid`    |
+///
+/// Strings bound to `ascii` bind markers must consist of ASCII characters only,
+/// and times bound to `time` bind markers must lie within a single day.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordBatchValues<'a> {
+    prepared: &'a PreparedStatement,
+    batch: &'a RecordBatch,
+}
+
+impl<'a> RecordBatchValues<'a> {
+    /// Binds the columns of `batch` to the bind markers of `prepared`.
+    ///
+    /// Fails if the number of columns doesn't match the number of bind markers,
+    /// or if any column can't be bound to its bind marker.
+    ///
+    /// This is the only place where the batch is checked: its rows are serialized
+    /// for `prepared` without checking them again.
+    pub fn new(
+        prepared: &'a PreparedStatement,
+        batch: &'a RecordBatch,
+    ) -> Result<Self, RecordBatchBindError> {
+        check_batch(batch, prepared.get_variable_col_specs().as_slice())?;
+        Ok(Self { prepared, batch })
+    }
+
+    /// Returns the number of rows in the batch.
+    #[inline]
+    pub fn num_rows(&self) -> usize {
+        self.batch.num_rows()
+    }
+
+    /// Returns the row with the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn row(&self, index: usize) -> RecordBatchRow<'a> {
+        assert!(
+            index < self.num_rows(),
+            "row index {index} out of bounds for a record batch of {} rows",
+            self.num_rows()
+        );
+        RecordBatchRow {
+            batch: self.batch,
+            checked_specs: self.prepared.get_variable_col_specs().as_slice(),
+            index,
+        }
+    }
+
+    /// Returns an iterator over the rows of the batch.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = RecordBatchRow<'a>> + use<'a> {
+        let batch = self.batch;
+        let checked_specs = self.prepared.get_variable_col_specs().as_slice();
+        (0..batch.num_rows()).map(move |index| RecordBatchRow {
+            batch,
+            checked_specs,
+            index,
+        })
+    }
+
+    /// Returns an iterator over the rows of the batch, serialized for the prepared statement.
+    pub fn serialized_rows(
+        &self,
+    ) -> impl ExactSizeIterator<Item = Result<SerializedValues, SerializationError>> + use<'a> {
+        let prepared = self.prepared;
+        self.rows().map(move |row| {
+            let ctx =
+                RowSerializationContext::from_specs(prepared.get_variable_col_specs().as_slice());
+            SerializedValues::from_serializable(&ctx, &row)
+        })
+    }
+}
+
+/// A single row of a [`RecordBatch`], obtained from [`RecordBatchValues`].
+///
+/// Implements [`SerializeRow`], so it can be passed as values of the prepared statement
+/// the batch was bound to. If it is passed as values of another statement, the batch
+/// is checked against its bind markers first.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordBatchRow<'a> {
+    batch: &'a RecordBatch,
+    // Bind markers the batch was checked against when it was bound.
+    checked_specs: &'a [ColumnSpec<'static>],
+    index: usize,
+}
+
+impl SerializeRow for RecordBatchRow<'_> {
+    fn serialize(
+        &self,
+        ctx: &RowSerializationContext<'_>,
+        writer: &mut RowWriter,
+    ) -> Result<(), SerializationError> {
+        // The checks of the batch depend only on the types of the bind markers,
+        // so the batch only needs to be checked again if they differ.
+        let specs = ctx.columns();
+        let is_checked = specs.len() == self.checked_specs.len()
+            && specs
+                .iter()
+                .zip(self.checked_specs)
+                .all(|(spec, checked)| spec.typ() == checked.typ());
+        if !is_checked {
+            check_batch(self.batch, specs).map_err(SerializationError::new)?;
+        }
+
+        for (index, (column, spec)) in self.batch.columns().iter().zip(specs).enumerate() {
+            serialize_cell(
+                column.as_ref(),
+                self.index,
+                index,
+                spec,
+                writer.make_cell_writer(),
+            )
+            .map_err(SerializationError::new)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.batch.num_columns() == 0
+    }
+}
+
+/// An error returned when a [`RecordBatch`] can't be bound to a prepared statement.
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum RecordBatchBindError {
+    /// The number of columns differs from the number of bind markers.
+    #[error(
+        "Record batch has {batch_columns} columns, but the statement has {bind_markers} bind markers"
+    )]
+    ColumnCountMismatch {
+        /// Number of columns of the record batch.
+        batch_columns: usize,
+        /// Number of bind markers of the statement.
+        bind_markers: usize,
+    },
+
+    /// A column can't be bound to its bind marker.
+    #[error(
+        "Column {index} ({name}) of type {arrow_type} can't be bound to a bind marker of type {cql_type:?}"
+    )]
+    IncompatibleType {
+        /// Index of the column.
+        index: usize,
+        /// Name of the column.
+        name: String,
+        /// Arrow type of the column.
+        arrow_type: DataType,
+        /// CQL type of the bind marker.
+        cql_type: ColumnType<'static>,
+    },
+
+    /// A column bound to an `ascii` bind marker contains a string with non-ASCII characters.
+    #[error(
+        "Column {index} ({name}) contains a non-ASCII string, but is bound to an ascii bind marker"
+    )]
+    NonAsciiValue {
+        /// Index of the column.
+        index: usize,
+        /// Name of the column.
+        name: String,
+    },
+
+    /// A column bound to a `time` bind marker contains a time outside of a single day.
+    #[error(
+        "Column {index} ({name}) contains time {nanoseconds}ns, which is outside of the range of a CQL time"
+    )]
+    TimeOutOfRange {
+        /// Index of the column.
+        index: usize,
+        /// Name of the column.
+        name: String,
+        /// The offending time, in nanoseconds since midnight.
+        nanoseconds: i64,
+    },
+
+    /// A value is too large to be sent as a CQL value.
+    #[error("Value bound to {name} exceeds the maximum size of a CQL value")]
+    ValueTooLarge {
+        /// Name of the bind marker.
+        name: String,
+    },
+}
+
+/// Checks that the columns of `batch` can be bound to the bind markers described by `specs`.
+fn check_batch(batch: &RecordBatch, specs: &[ColumnSpec]) -> Result<(), RecordBatchBindError> {
+    if batch.num_columns() != specs.len() {
+        return Err(RecordBatchBindError::ColumnCountMismatch {
+            batch_columns: batch.num_columns(),
+            bind_markers: specs.len(),
+        });
+    }
+
+    for (index, ((field, column), spec)) in batch
+        .schema_ref()
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .zip(specs)
+        .enumerate()
+    {
+        if !is_compatible(field.data_type(), spec.typ()) {
+            return Err(RecordBatchBindError::IncompatibleType {
+                index,
+                name: field.name().clone(),
+                arrow_type: field.data_type().clone(),
+                cql_type: spec.typ().clone().into_owned(),
+            });
+        }
+        if matches!(spec.typ(), ColumnType::Native(NativeType::Ascii))
+            && !is_ascii_column(column.as_ref())
+        {
+            return Err(RecordBatchBindError::NonAsciiValue {
+                index,
+                name: field.name().clone(),
+            });
+        }
+        if let Some(nanoseconds) = first_time_out_of_range(column.as_ref()) {
+            return Err(RecordBatchBindError::TimeOutOfRange {
+                index,
+                name: field.name().clone(),
+                nanoseconds,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// The number of nanoseconds in a day; CQL times lie in `0..NANOSECONDS_PER_DAY`.
+const NANOSECONDS_PER_DAY: i64 = 86_400_000_000_000;
+
+/// Returns the first time of a time column which isn't a valid CQL time, if any.
+fn first_time_out_of_range(column: &dyn Array) -> Option<i64> {
+    match column.data_type() {
+        DataType::Time64(TimeUnit::Nanosecond) => column
+            .as_primitive::<Time64NanosecondType>()
+            .iter()
+            .flatten()
+            .find(|nanoseconds| !(0..NANOSECONDS_PER_DAY).contains(nanoseconds)),
+        _ => None,
+    }
+}
+
+/// Tells whether all strings of a string column consist of ASCII characters only.
+fn is_ascii_column(column: &dyn Array) -> bool {
+    match column.data_type() {
+        DataType::Utf8 => column
+            .as_string::<i32>()
+            .iter()
+            .flatten()
+            .all(str::is_ascii),
+        DataType::LargeUtf8 => column
+            .as_string::<i64>()
+            .iter()
+            .flatten()
+            .all(str::is_ascii),
+        _ => true,
+    }
+}
+
+fn is_compatible(arrow_type: &DataType, cql_type: &ColumnType) -> bool {
+    let ColumnType::Native(native_type) = cql_type else {
+        return false;
+    };
+
+    matches!(
+        (arrow_type, native_type),
+        (DataType::Boolean, NativeType::Boolean)
+            | (DataType::Int8, NativeType::TinyInt)
+            | (DataType::Int16, NativeType::SmallInt)
+            | (DataType::Int32, NativeType::Int)
+            | (DataType::Int64, NativeType::BigInt)
+            | (DataType::Float32, NativeType::Float)
+            | (DataType::Float64, NativeType::Double)
+            | (
+                DataType::Utf8 | DataType::LargeUtf8,
+                NativeType::Text | NativeType::Ascii
+            )
+            | (DataType::Binary | DataType::LargeBinary, NativeType::Blob)
+            | (DataType::Date32, NativeType::Date)
+            | (
+                DataType::Timestamp(TimeUnit::Millisecond, _),
+                NativeType::Timestamp
+            )
+            | (DataType::Time64(TimeUnit::Nanosecond), NativeType::Time)
+            | (
+                DataType::FixedSizeBinary(16),
+                NativeType::Uuid | NativeType::Timeuuid
+            )
+    )
+}
+
+/// Writes the value at `index` of `column`, which was checked by [`check_batch`]
+/// against `spec`, the bind marker at `column_index`.
+fn serialize_cell<'buf>(
+    column: &dyn Array,
+    index: usize,
+    column_index: usize,
+    spec: &ColumnSpec,
+    writer: CellWriter<'buf>,
+) -> Result<WrittenCellProof<'buf>, RecordBatchBindError> {
+    if column.is_null(index) {
+        return Ok(writer.set_null());
+    }
+
+    let result = match column.data_type() {
+        DataType::Boolean => writer.set_value(&[column.as_boolean().value(index) as u8]),
+        DataType::Int8 => {
+            writer.set_value(&column.as_primitive::<Int8Type>().value(index).to_be_bytes())
+        }
+        DataType::Int16 => writer.set_value(
+            &column
+                .as_primitive::<Int16Type>()
+                .value(index)
+                .to_be_bytes(),
+        ),
+        DataType::Int32 => writer.set_value(
+            &column
+                .as_primitive::<Int32Type>()
+                .value(index)
+                .to_be_bytes(),
+        ),
+        DataType::Int64 => writer.set_value(
+            &column
+                .as_primitive::<Int64Type>()
+                .value(index)
+                .to_be_bytes(),
+        ),
+        DataType::Float32 => writer.set_value(
+            &column
+                .as_primitive::<Float32Type>()
+                .value(index)
+                .to_be_bytes(),
+        ),
+        DataType::Float64 => writer.set_value(
+            &column
+                .as_primitive::<Float64Type>()
+                .value(index)
+                .to_be_bytes(),
+        ),
+        DataType::Utf8 => writer.set_value(column.as_string::<i32>().value(index).as_bytes()),
+        DataType::LargeUtf8 => writer.set_value(column.as_string::<i64>().value(index).as_bytes()),
+        DataType::Binary => writer.set_value(column.as_binary::<i32>().value(index)),
+        DataType::LargeBinary => writer.set_value(column.as_binary::<i64>().value(index)),
+        DataType::Date32 => {
+            // Arrow counts days from the unix epoch, CQL from 2^31 days before it.
+            let days = column.as_primitive::<Date32Type>().value(index);
+            let cql_days = (days as u32).wrapping_add(1 << 31);
+            writer.set_value(&cql_days.to_be_bytes())
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => writer.set_value(
+            &column
+                .as_primitive::<TimestampMillisecondType>()
+                .value(index)
+                .to_be_bytes(),
+        ),
+        DataType::Time64(TimeUnit::Nanosecond) => writer.set_value(
+            &column
+                .as_primitive::<Time64NanosecondType>()
+                .value(index)
+                .to_be_bytes(),
+        ),
+        DataType::FixedSizeBinary(16) => {
+            writer.set_value(column.as_fixed_size_binary().value(index))
+        }
+        arrow_type => {
+            return Err(RecordBatchBindError::IncompatibleType {
+                index: column_index,
+                name: spec.name().to_owned(),
+                arrow_type: arrow_type.clone(),
+                cql_type: spec.typ().clone().into_owned(),
+            });
+        }
+    };
+    result.map_err(|_: CellOverflowError| RecordBatchBindError::ValueTooLarge {
+        name: spec.name().to_owned(),
+    })
+}
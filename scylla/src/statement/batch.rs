@@ -1,18 +1,22 @@
 //! Defines the [`Batch`] type, which represents a batch of CQL statements
 //! that can be executed together.
 
+use bytes::Bytes;
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use uuid::Uuid;
 
 use crate::client::execution_profile::ExecutionProfileHandle;
+use crate::cluster::NodeRef;
 use crate::observability::history::HistoryListener;
 use crate::policies::load_balancing::LoadBalancingPolicy;
-use crate::policies::retry::RetryPolicy;
+use crate::policies::retry::{FallthroughRetryPolicy, RetryPolicy};
 use crate::statement::prepared::PreparedStatement;
 use crate::statement::unprepared::Statement;
 
-use super::StatementConfig;
+use super::{AttemptCallback, StatementConfig};
 use super::{Consistency, SerialConsistency};
 pub use crate::frame::request::batch::BatchType;
 
@@ -103,6 +107,15 @@ impl Batch {
         self.config.serial_consistency = Some(sc);
     }
 
+    /// Sets both the consistency and the serial consistency to be used when executing
+    /// this batch, e.g. `Quorum` with `LocalSerial` for an LWT.
+    ///
+    /// Equivalent to calling [`Self::set_consistency`] and [`Self::set_serial_consistency`].
+    pub fn set_consistency_pair(&mut self, c: Consistency, sc: Option<SerialConsistency>) {
+        self.set_consistency(c);
+        self.set_serial_consistency(sc);
+    }
+
     /// Unsets the serial consistency overridden on this batch.
     /// This means that serial consistency will be derived from the execution profile
     /// (per-batch or, if absent, the default one).
@@ -116,6 +129,24 @@ impl Batch {
         self.config.serial_consistency.flatten()
     }
 
+    /// Sets the serial consistencies to fall back to when the serial phase of this batch
+    /// fails with an `Unavailable` error, e.g. `[LocalSerial, Serial]` to try the local
+    /// datacenter first.
+    ///
+    /// On such an error, the batch is retried on the same node with the next entry
+    /// of the list which differs from the serial consistency of the failed attempt.
+    /// Each entry is tried at most once, and the retry policy is consulted only
+    /// once the list is exhausted. (Ignored unless the batch is an LWT)
+    pub fn set_serial_consistency_fallback(&mut self, fallback: Vec<SerialConsistency>) {
+        self.config.serial_consistency_fallback = fallback;
+    }
+
+    /// Gets the serial consistencies to fall back to when the serial phase of this batch
+    /// fails with an `Unavailable` error.
+    pub fn get_serial_consistency_fallback(&self) -> &[SerialConsistency] {
+        &self.config.serial_consistency_fallback
+    }
+
     /// Sets the idempotence of this batch
     /// A query is idempotent if it can be applied multiple times without changing the result of the initial application
     /// If set to `true` we can be sure that it is idempotent
@@ -142,6 +173,21 @@ impl Batch {
         self.config.tracing
     }
 
+    /// Sets a custom payload to be sent to the coordinator along with this batch.
+    ///
+    /// The custom payload is a map of arbitrary keys and byte values, carried
+    /// in the request frame. Stock ScyllaDB and Cassandra servers ignore it;
+    /// it is only useful with servers, or proxies in front of them,
+    /// which read the given keys.
+    pub fn set_custom_payload(&mut self, custom_payload: Option<HashMap<String, Bytes>>) {
+        self.config.custom_payload = custom_payload;
+    }
+
+    /// Gets the custom payload sent along with this batch, if any.
+    pub fn get_custom_payload(&self) -> Option<&HashMap<String, Bytes>> {
+        self.config.custom_payload.as_ref()
+    }
+
     /// Sets the default timestamp for this batch in microseconds.
     /// If not None, it will replace the server side assigned timestamp as default timestamp for
     /// all the statements contained in the batch.
@@ -167,12 +213,36 @@ impl Batch {
         self.config.request_timeout
     }
 
+    /// Sets the priority of this batch in the session's admission queue.
+    /// When the session executes as many requests as allowed by
+    /// [`SessionBuilder::max_concurrent_requests`](crate::client::session_builder::SessionBuilder::max_concurrent_requests),
+    /// waiting requests with higher priority are admitted first.
+    /// Has no effect if the number of concurrent requests is not limited.
+    ///
+    /// The default priority is 0, the lowest one.
+    pub fn set_priority(&mut self, priority: u8) {
+        self.config.priority = priority;
+    }
+
+    /// Gets the priority of this batch in the session's admission queue.
+    pub fn get_priority(&self) -> u8 {
+        self.config.priority
+    }
+
     /// Set the retry policy for this batch, overriding the one from execution profile if not None.
     #[inline]
     pub fn set_retry_policy(&mut self, retry_policy: Option<Arc<dyn RetryPolicy>>) {
         self.config.retry_policy = retry_policy;
     }
 
+    /// Disables retries for this batch: every error is returned right away,
+    /// whatever the retry policy of the execution profile is.
+    /// This is a shorthand for setting [`FallthroughRetryPolicy`] as the retry policy.
+    #[inline]
+    pub fn set_no_retry(&mut self) {
+        self.set_retry_policy(Some(Arc::new(FallthroughRetryPolicy)));
+    }
+
     /// Get the retry policy set for the batch.
     ///
     /// This method returns the retry policy that is **overridden** on this statement.
@@ -202,6 +272,96 @@ impl Batch {
         self.config.load_balancing_policy.as_ref()
     }
 
+    /// Excludes the given nodes from the load balancing plan of this batch,
+    /// so that none of them is sent any attempt to execute it, including
+    /// retries and speculative executions.
+    ///
+    /// Nodes are identified by their host ids, and subsequent calls add up.
+    /// If every node in the plan is excluded, the execution fails with
+    /// [`ExecutionError::EmptyPlan`](crate::errors::ExecutionError::EmptyPlan).
+    pub fn exclude_nodes(&mut self, nodes: &[NodeRef<'_>]) {
+        self.config
+            .excluded_nodes
+            .extend(nodes.iter().map(|node| node.host_id));
+    }
+
+    /// Gets the host ids of the nodes excluded with `exclude_nodes`.
+    pub fn get_excluded_nodes(&self) -> &[Uuid] {
+        &self.config.excluded_nodes
+    }
+
+    /// Removes all exclusions made with `exclude_nodes`.
+    pub fn clear_excluded_nodes(&mut self) {
+        self.config.excluded_nodes.clear();
+    }
+
+    /// Sets whether the coordinator of each attempt to execute this batch is required
+    /// to be a replica of the partition the batch targets.
+    ///
+    /// If set to `true`, nodes picked by the load balancing policy which do not own the
+    /// partition are skipped, so the request is only sent to replicas. If the plan contains
+    /// no replica, the execution fails with
+    /// [`ExecutionError::CoordinatorNotReplica`](crate::errors::ExecutionError::CoordinatorNotReplica);
+    /// if all replicas fail, the error of the last one is returned. The same happens if the partition cannot be
+    /// determined, e.g. because its first statement is not prepared. Defaults to `false`.
+    pub fn set_require_replica_coordinator(&mut self, require: bool) {
+        self.config.require_replica_coordinator = require;
+    }
+
+    /// Gets whether the coordinator is required to be a replica of the targeted partition.
+    pub fn get_require_replica_coordinator(&self) -> bool {
+        self.config.require_replica_coordinator
+    }
+
+    /// Sets whether token-aware routing is disabled for this batch.
+    ///
+    /// If set to `true`, the load balancing policy is not told the token of the partition
+    /// targeted by the batch, so it routes the batch as if the partition was unknown.
+    /// See [`PreparedStatement::set_disable_token_aware`](crate::statement::prepared::PreparedStatement::set_disable_token_aware).
+    /// Defaults to `false`.
+    pub fn set_disable_token_aware(&mut self, disable: bool) {
+        self.config.disable_token_aware = disable;
+    }
+
+    /// Gets whether token-aware routing is disabled for this batch.
+    pub fn get_disable_token_aware(&self) -> bool {
+        self.config.disable_token_aware
+    }
+
+    /// Sets whether each attempt to execute this batch opens a new connection
+    /// instead of using one from the connection pool.
+    ///
+    /// The connection is opened to the node chosen by the load balancing policy
+    /// and closed as soon as the attempt completes. It is not shard-aware, so it is
+    /// connected to an arbitrary shard. This is costly and meant for testing,
+    /// e.g. to isolate a request from the state of pooled connections.
+    /// It is not applied to paged queries executed with a [`QueryPager`](crate::client::pager::QueryPager).
+    /// Defaults to `false`.
+    pub fn set_force_fresh_connection(&mut self, force: bool) {
+        self.config.force_fresh_connection = force;
+    }
+
+    /// Gets whether each attempt to execute this batch opens a new connection.
+    pub fn get_force_fresh_connection(&self) -> bool {
+        self.config.force_fresh_connection
+    }
+
+    /// Sets the index of the replica of the targeted partition, in token order,
+    /// which should be tried first when executing this batch.
+    ///
+    /// The remaining nodes are tried in the order of the load balancing plan. The preference
+    /// is ignored if the partition cannot be determined (e.g. because its first statement is not prepared),
+    /// if it has no more replicas than `index`, or if the load balancing plan doesn't include
+    /// that replica (e.g. because it is down or in a remote datacenter). Defaults to `None`.
+    pub fn set_preferred_replica_index(&mut self, index: Option<usize>) {
+        self.config.preferred_replica_index = index;
+    }
+
+    /// Gets the index of the replica which should be tried first, if set.
+    pub fn get_preferred_replica_index(&self) -> Option<usize> {
+        self.config.preferred_replica_index
+    }
+
     /// Sets the listener capable of listening what happens during query execution.
     pub fn set_history_listener(&mut self, history_listener: Arc<dyn HistoryListener>) {
         self.config.history_listener = Some(history_listener);
@@ -212,6 +372,21 @@ impl Batch {
         self.config.history_listener.take()
     }
 
+    /// Sets a callback invoked before each attempt to execute the batch, including
+    /// retries and speculative executions. It receives the number of the attempt,
+    /// starting from 1, and the node the attempt is sent to.
+    ///
+    /// The callback is shared between clones of the batch. It is called synchronously
+    /// on the execution path, so it should not block.
+    pub fn set_on_attempt(&mut self, on_attempt: impl FnMut(u32, NodeRef<'_>) + Send + 'static) {
+        self.config.on_attempt = Some(AttemptCallback::new(on_attempt));
+    }
+
+    /// Removes the callback set by `set_on_attempt`.
+    pub fn remove_on_attempt(&mut self) {
+        self.config.on_attempt = None;
+    }
+
     /// Associates the batch with execution profile referred by the provided handle.
     /// Handle may be later remapped to another profile, and batch will reflect those changes.
     pub fn set_execution_profile_handle(&mut self, profile_handle: Option<ExecutionProfileHandle>) {
@@ -5,20 +5,34 @@
 //! - PreparedStatement,
 //! - Batch.
 
-use std::{sync::Arc, time::Duration};
-
+use std::{
+    collections::HashMap,
+    num::NonZeroU32,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bytes::Bytes;
 use thiserror::Error;
+use uuid::Uuid;
 
 use crate::client::execution_profile::ExecutionProfileHandle;
+use crate::cluster::NodeRef;
 use crate::observability::history::HistoryListener;
 use crate::policies::load_balancing::LoadBalancingPolicy;
 use crate::policies::retry::RetryPolicy;
 
+mod adaptive_page_size;
+#[cfg(feature = "arrow-55")]
+pub mod arrow;
 pub mod batch;
 pub mod prepared;
+pub mod prepared_cache;
 pub mod unprepared;
 
 pub use crate::frame::types::{Consistency, SerialConsistency};
+pub(crate) use adaptive_page_size::PageSizer;
+pub use adaptive_page_size::{AdaptivePageSize, InvalidPageSizeBounds};
 pub use unprepared::Statement;
 
 // This is the default common to drivers.
@@ -28,19 +42,33 @@ const DEFAULT_PAGE_SIZE: i32 = 5000;
 pub(crate) struct StatementConfig {
     pub(crate) consistency: Option<Consistency>,
     pub(crate) serial_consistency: Option<Option<SerialConsistency>>,
+    pub(crate) serial_consistency_fallback: Vec<SerialConsistency>,
 
     pub(crate) is_idempotent: bool,
+    pub(crate) ignore_already_exists: bool,
 
     pub(crate) skip_result_metadata: bool,
     pub(crate) tracing: bool,
+    pub(crate) custom_payload: Option<HashMap<String, Bytes>>,
     pub(crate) timestamp: Option<i64>,
     pub(crate) request_timeout: Option<Duration>,
+    pub(crate) max_unpaged_rows: Option<NonZeroU32>,
+    pub(crate) adaptive_page_size: Option<AdaptivePageSize>,
+    pub(crate) paging_deadline: Option<Duration>,
+    pub(crate) priority: u8,
 
     pub(crate) history_listener: Option<Arc<dyn HistoryListener>>,
+    pub(crate) on_attempt: Option<AttemptCallback>,
 
     pub(crate) execution_profile_handle: Option<ExecutionProfileHandle>,
     pub(crate) load_balancing_policy: Option<Arc<dyn LoadBalancingPolicy>>,
     pub(crate) retry_policy: Option<Arc<dyn RetryPolicy>>,
+    pub(crate) excluded_nodes: Vec<Uuid>,
+    pub(crate) require_replica_coordinator: bool,
+    pub(crate) preferred_replica_index: Option<usize>,
+    pub(crate) disable_token_aware: bool,
+    pub(crate) force_fresh_connection: bool,
+    pub(crate) no_paging: bool,
 }
 
 impl StatementConfig {
@@ -49,6 +77,59 @@ impl StatementConfig {
     pub(crate) fn determine_consistency(&self, default_consistency: Consistency) -> Consistency {
         self.consistency.unwrap_or(default_consistency)
     }
+
+    /// Custom payload to send along with the request, if any.
+    pub(crate) fn custom_payload(&self) -> Option<&HashMap<String, Bytes>> {
+        self.custom_payload.as_ref()
+    }
+
+    /// Whether the node was excluded from the load balancing plan of the statement.
+    pub(crate) fn is_node_excluded(&self, node: NodeRef<'_>) -> bool {
+        self.excluded_nodes.contains(&node.host_id)
+    }
+
+    /// Page size to request paged results with: `None`, i.e. unlimited,
+    /// if paging was disabled for the statement.
+    pub(crate) fn requested_page_size(&self, page_size: PageSize) -> Option<PageSize> {
+        (!self.no_paging).then_some(page_size)
+    }
+
+    /// Page size to request unpaged results with, if their number of rows is limited.
+    /// One row more than the limit is requested, so that exceeding it can be detected.
+    pub(crate) fn unpaged_page_size(&self) -> Option<PageSize> {
+        self.max_unpaged_rows.map(|max_rows| {
+            PageSize(
+                i32::try_from(max_rows.get())
+                    .unwrap_or(i32::MAX)
+                    .saturating_add(1),
+            )
+        })
+    }
+}
+
+/// A callback invoked before each attempt of a request,
+/// with the 1-based number of the attempt and the target node.
+///
+/// It is shared between clones of the statement it was set on.
+#[derive(Clone)]
+pub(crate) struct AttemptCallback(Arc<Mutex<AttemptCallbackFn>>);
+
+type AttemptCallbackFn = dyn FnMut(u32, NodeRef<'_>) + Send;
+
+impl AttemptCallback {
+    pub(crate) fn new(callback: impl FnMut(u32, NodeRef<'_>) + Send + 'static) -> Self {
+        Self(Arc::new(Mutex::new(callback)))
+    }
+
+    pub(crate) fn call(&self, attempt: u32, node: NodeRef<'_>) {
+        (self.0.lock().unwrap())(attempt, node)
+    }
+}
+
+impl std::fmt::Debug for AttemptCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttemptCallback").finish_non_exhaustive()
+    }
 }
 
 #[derive(Debug, Clone, Copy, Error)]
@@ -4,28 +4,36 @@
 use crate::frame::response::result::{ColumnSpec, PartitionKeyIndex, ResultMetadata, TableSpec};
 use crate::frame::types::RawValue;
 use crate::serialize::SerializationError;
-use crate::serialize::row::{RowSerializationContext, SerializeRow, SerializedValues};
+use crate::serialize::row::{
+    RowSerializationContext, SerializeRow, SerializedValues, SerializedValuesDump,
+};
 use arc_swap::{ArcSwap, Guard};
 use bytes::{Bytes, BytesMut};
 use smallvec::{SmallVec, smallvec};
+use std::collections::HashMap;
 use std::convert::TryInto;
+use std::num::{NonZeroU32, NonZeroUsize};
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
 use uuid::Uuid;
 
-use super::{PageSize, StatementConfig};
+use super::{AdaptivePageSize, AttemptCallback, PageSize, StatementConfig};
 use crate::client::execution_profile::ExecutionProfileHandle;
-use crate::errors::{BadQuery, ExecutionError};
+use crate::cluster::NodeRef;
+use crate::errors::{BadQuery, ExecutionError, PreparedCacheError};
 use crate::frame::response::result::{self, PreparedMetadata};
 use crate::frame::types::{Consistency, SerialConsistency};
+use crate::network::VerifiedKeyspaceName;
 use crate::observability::history::HistoryListener;
+use crate::observability::statement_history::{ExecutionHistory, StatementExecution};
 use crate::policies::load_balancing::LoadBalancingPolicy;
-use crate::policies::retry::RetryPolicy;
+use crate::policies::retry::{FallthroughRetryPolicy, RetryPolicy};
 use crate::response::query_result::ColumnSpecs;
 use crate::routing::Token;
 use crate::routing::partitioner::{Partitioner, PartitionerHasher, PartitionerName};
 use crate::statement::Statement;
+use crate::statement::prepared_cache;
 
 /// Parts which are needed to construct [PreparedStatement].
 ///
@@ -206,6 +214,10 @@ pub struct PreparedStatement {
     shared: Arc<PreparedStatementSharedData>,
     page_size: PageSize,
     partitioner_name: PartitionerName,
+    routing_table_spec: Option<TableSpec<'static>>,
+    prepared_in_keyspace: Option<Arc<String>>,
+    reprepare_keyspace: Option<VerifiedKeyspaceName>,
+    execution_history: Option<Arc<ExecutionHistory>>,
 }
 
 #[derive(Debug)]
@@ -226,6 +238,10 @@ impl Clone for PreparedStatement {
             shared: self.shared.clone(),
             page_size: self.page_size,
             partitioner_name: self.partitioner_name.clone(),
+            routing_table_spec: self.routing_table_spec.clone(),
+            prepared_in_keyspace: self.prepared_in_keyspace.clone(),
+            reprepare_keyspace: self.reprepare_keyspace.clone(),
+            execution_history: self.execution_history.clone(),
         }
     }
 }
@@ -265,6 +281,10 @@ impl PreparedStatement {
             prepare_tracing_ids: Vec::new(),
             page_size,
             partitioner_name: Default::default(),
+            routing_table_spec: None,
+            prepared_in_keyspace: None,
+            reprepare_keyspace: None,
+            execution_history: None,
             config,
         }
     }
@@ -303,6 +323,40 @@ impl PreparedStatement {
         &self.prepare_tracing_ids
     }
 
+    /// Serializes this statement into a portable blob, which contains its id and metadata.
+    ///
+    /// Blobs are independent of the session and of the negotiated protocol extensions.
+    /// See [`prepared_cache`] for how they are used.
+    pub fn to_portable_blob(&self) -> Result<Vec<u8>, PreparedCacheError> {
+        let prepared = result::Prepared {
+            id: self.shared.id.clone(),
+            prepared_metadata: self.shared.metadata.clone(),
+            result_metadata: (*self.get_current_result_metadata()).clone(),
+        };
+        prepared_cache::encode_blob(
+            self.get_statement(),
+            self.prepared_in_keyspace.as_deref().map(String::as_str),
+            &prepared,
+            self.is_confirmed_lwt(),
+        )
+    }
+
+    /// Sets the keyspace the session used when this statement was prepared.
+    pub(crate) fn set_prepared_in_keyspace(&mut self, keyspace: Option<Arc<String>>) {
+        self.prepared_in_keyspace = keyspace;
+    }
+
+    /// Sets the keyspace the statement has to be reprepared in, regardless of
+    /// the keyspace used by the connection which finds it unprepared.
+    pub(crate) fn set_reprepare_keyspace(&mut self, keyspace: Option<VerifiedKeyspaceName>) {
+        self.reprepare_keyspace = keyspace;
+    }
+
+    /// Returns the keyspace the statement has to be reprepared in, if any.
+    pub(crate) fn get_reprepare_keyspace(&self) -> Option<&VerifiedKeyspaceName> {
+        self.reprepare_keyspace.as_ref()
+    }
+
     /// Returns true if the prepared statement has necessary information
     /// to be routed in a token-aware manner. If false, the query
     /// will always be sent to a random node/shard.
@@ -359,6 +413,27 @@ impl PreparedStatement {
         Ok(buf.freeze())
     }
 
+    /// Computes the routing key for given values, i.e. the encoded partition key
+    /// which is hashed by the partitioner to obtain a token.
+    ///
+    /// If the partition key consists of a single column, the routing key is
+    /// the serialized value of that column. Otherwise, it is a concatenation of
+    /// the partition key values, in partition key order, each prefixed with its
+    /// length as a big-endian `u16` and followed by a zero byte.
+    ///
+    /// Returns `None` if the statement is not token-aware (see [Self::is_token_aware()]).
+    /// This is useful for client-side partitioning schemes that are independent of
+    /// the cluster's token ring.
+    pub fn routing_key(
+        &self,
+        bound_values: &impl SerializeRow,
+    ) -> Result<Option<Bytes>, PartitionKeyError> {
+        if !self.is_token_aware() {
+            return Ok(None);
+        }
+        self.compute_partition_key(bound_values).map(Some)
+    }
+
     /// Determines which values constitute the partition key and puts them in order.
     ///
     /// This is a preparation step necessary for calculating token based on a prepared statement.
@@ -432,6 +507,40 @@ impl PreparedStatement {
             .map(|col_spec| col_spec.table_spec().table_name())
     }
 
+    /// Sets the keyspace used to compute replicas when routing this statement.
+    ///
+    /// By default, the driver routes the statement using the keyspace reported
+    /// by the database in the prepared metadata. If the statement text is unqualified
+    /// (e.g. it was prepared with a keyspace chosen by `USE`), the keyspace given here
+    /// is used for token-aware routing instead. It does not change the keyspace
+    /// the statement is executed against.
+    ///
+    /// The keyspace is not sent to the database: the per-request keyspace field
+    /// was introduced in CQL protocol v5, while the driver only speaks protocol v4.
+    ///
+    /// Pass `None` to go back to using the keyspace from the prepared metadata.
+    pub fn set_routing_keyspace(&mut self, keyspace: Option<String>) {
+        self.routing_table_spec = keyspace.map(|ks| {
+            let table = self.get_table_name().unwrap_or_default().to_owned();
+            TableSpec::owned(ks, table)
+        });
+    }
+
+    /// Gets the keyspace set with [`PreparedStatement::set_routing_keyspace`].
+    pub fn get_routing_keyspace(&self) -> Option<&str> {
+        self.routing_table_spec.as_ref().map(|spec| spec.ks_name())
+    }
+
+    /// Returns the keyspace and table used for routing this statement.
+    ///
+    /// This is the table spec from prepared metadata with the keyspace replaced
+    /// by the routing keyspace, if one was set.
+    pub(crate) fn get_routing_table_spec(&self) -> Option<&TableSpec<'_>> {
+        self.routing_table_spec
+            .as_ref()
+            .or_else(|| self.get_table_spec())
+    }
+
     /// Sets the consistency to be used when executing this statement.
     pub fn set_consistency(&mut self, c: Consistency) {
         self.config.consistency = Some(c);
@@ -456,6 +565,15 @@ impl PreparedStatement {
         self.config.serial_consistency = Some(sc);
     }
 
+    /// Sets both the consistency and the serial consistency to be used when executing
+    /// this statement, e.g. `Quorum` with `LocalSerial` for an LWT.
+    ///
+    /// Equivalent to calling [`Self::set_consistency`] and [`Self::set_serial_consistency`].
+    pub fn set_consistency_pair(&mut self, c: Consistency, sc: Option<SerialConsistency>) {
+        self.set_consistency(c);
+        self.set_serial_consistency(sc);
+    }
+
     /// Unsets the serial consistency overridden on this statement.
     /// This means that serial consistency will be derived from the execution profile
     /// (per-statement or, if absent, the default one).
@@ -469,6 +587,38 @@ impl PreparedStatement {
         self.config.serial_consistency.flatten()
     }
 
+    /// Sets the serial consistencies to fall back to when the serial phase of this statement
+    /// fails with an `Unavailable` error, e.g. `[LocalSerial, Serial]` to try the local
+    /// datacenter first.
+    ///
+    /// On such an error, the statement is retried on the same node with the next entry
+    /// of the list which differs from the serial consistency of the failed attempt.
+    /// Each entry is tried at most once, and the retry policy is consulted only
+    /// once the list is exhausted. (Ignored unless the statement is an LWT)
+    pub fn set_serial_consistency_fallback(&mut self, fallback: Vec<SerialConsistency>) {
+        self.config.serial_consistency_fallback = fallback;
+    }
+
+    /// Gets the serial consistencies to fall back to when the serial phase of this statement
+    /// fails with an `Unavailable` error.
+    pub fn get_serial_consistency_fallback(&self) -> &[SerialConsistency] {
+        &self.config.serial_consistency_fallback
+    }
+
+    /// Sets whether an `AlreadyExists` error returned for this statement should be treated
+    /// as success, e.g. to make schema changes idempotent without `IF NOT EXISTS`.
+    ///
+    /// If set to `true`, such an error is neither retried nor returned; the execution
+    /// results in an empty result instead. Defaults to `false`.
+    pub fn set_ignore_already_exists(&mut self, ignore_already_exists: bool) {
+        self.config.ignore_already_exists = ignore_already_exists;
+    }
+
+    /// Gets whether an `AlreadyExists` error returned for this statement is treated as success.
+    pub fn get_ignore_already_exists(&self) -> bool {
+        self.config.ignore_already_exists
+    }
+
     /// Sets the idempotence of this statement
     /// A query is idempotent if it can be applied multiple times without changing the result of the initial application
     /// If set to `true` we can be sure that it is idempotent
@@ -495,6 +645,21 @@ impl PreparedStatement {
         self.config.tracing
     }
 
+    /// Sets a custom payload to be sent to the coordinator along with this statement.
+    ///
+    /// The custom payload is a map of arbitrary keys and byte values, carried
+    /// in the request frame. Stock ScyllaDB and Cassandra servers ignore it;
+    /// it is only useful with servers, or proxies in front of them,
+    /// which read the given keys.
+    pub fn set_custom_payload(&mut self, custom_payload: Option<HashMap<String, Bytes>>) {
+        self.config.custom_payload = custom_payload;
+    }
+
+    /// Gets the custom payload sent along with this statement, if any.
+    pub fn get_custom_payload(&self) -> Option<&HashMap<String, Bytes>> {
+        self.config.custom_payload.as_ref()
+    }
+
     /// Make use of cached metadata to decode results
     /// of the statement's execution.
     ///
@@ -521,6 +686,9 @@ impl PreparedStatement {
     /// If not None, it will replace the server side assigned timestamp as default timestamp
     /// If a statement contains a `USING TIMESTAMP` clause, calling this method won't change
     /// anything
+    ///
+    /// The timestamp is the write timestamp of the mutations. It doesn't affect reads:
+    /// the CQL protocol has no way to read data as of a given timestamp.
     pub fn set_timestamp(&mut self, timestamp: Option<i64>) {
         self.config.timestamp = timestamp
     }
@@ -543,6 +711,70 @@ impl PreparedStatement {
         self.config.request_timeout
     }
 
+    /// Limits the number of rows an unpaged execution of this statement may return.
+    /// If not None, the statement is sent with a page size just above the limit, and
+    /// the execution fails with [`ExecutionError::UnpagedRowLimitExceeded`] if the result
+    /// has more rows than the limit. This guards against accidental full scans.
+    ///
+    /// If the server ends the page early, e.g. because of its size in bytes, without
+    /// exceeding the limit, the result is incomplete and the execution fails with
+    /// [`RequestAttemptError::NonfinishedPagingState`](crate::errors::RequestAttemptError::NonfinishedPagingState) instead.
+    ///
+    /// Paged executions are not affected.
+    pub fn set_max_unpaged_rows(&mut self, max_rows: Option<NonZeroU32>) {
+        self.config.max_unpaged_rows = max_rows;
+    }
+
+    /// Gets the limit of rows an unpaged execution of this statement may return.
+    pub fn get_max_unpaged_rows(&self) -> Option<NonZeroU32> {
+        self.config.max_unpaged_rows
+    }
+
+    /// Enables adaptive paging for paged executions of this statement.
+    /// If not None, the page size set on the statement is used only for the first page,
+    /// and the sizes of following pages are adjusted to approach a target size in bytes,
+    /// based on the observed size of rows. See [`AdaptivePageSize`].
+    pub fn set_adaptive_page_size(&mut self, adaptive_page_size: Option<AdaptivePageSize>) {
+        self.config.adaptive_page_size = adaptive_page_size;
+    }
+
+    /// Gets the adaptive paging configuration of this statement.
+    pub fn get_adaptive_page_size(&self) -> Option<AdaptivePageSize> {
+        self.config.adaptive_page_size
+    }
+
+    /// Sets a total deadline for paged executions of this statement, measured from
+    /// the start of the execution. Once it elapses, fetching the next page fails with
+    /// [`RequestError::RequestTimeout`](crate::errors::RequestError::RequestTimeout),
+    /// regardless of how many pages have been fetched so far.
+    ///
+    /// Unlike the request timeout, which bounds the fetch of each page separately,
+    /// the deadline spans all pages. Unpaged executions are not affected.
+    pub fn set_paging_deadline(&mut self, deadline: Option<Duration>) {
+        self.config.paging_deadline = deadline;
+    }
+
+    /// Gets the total deadline for paged executions of this statement.
+    pub fn get_paging_deadline(&self) -> Option<Duration> {
+        self.config.paging_deadline
+    }
+
+    /// Sets the priority of this statement in the session's admission queue.
+    /// When the session executes as many requests as allowed by
+    /// [`SessionBuilder::max_concurrent_requests`](crate::client::session_builder::SessionBuilder::max_concurrent_requests),
+    /// waiting requests with higher priority are admitted first.
+    /// Has no effect if the number of concurrent requests is not limited.
+    ///
+    /// The default priority is 0, the lowest one.
+    pub fn set_priority(&mut self, priority: u8) {
+        self.config.priority = priority;
+    }
+
+    /// Gets the priority of this statement in the session's admission queue.
+    pub fn get_priority(&self) -> u8 {
+        self.config.priority
+    }
+
     /// Sets the name of the partitioner used for this statement.
     pub(crate) fn set_partitioner_name(&mut self, partitioner_name: PartitionerName) {
         self.partitioner_name = partitioner_name;
@@ -553,7 +785,13 @@ impl PreparedStatement {
         &self.shared.metadata
     }
 
-    /// Access column specifications of the bind variables of this statement
+    /// Access column specifications of the bind variables of this statement,
+    /// in the order of the bind markers.
+    ///
+    /// Each specification holds the name of the bind variable and its exact CQL type
+    /// (see [`ColumnSpec::typ`](crate::frame::response::result::ColumnSpec::typ)), which can
+    /// be used e.g. to bind values generically. Use [`ColumnSpecs::as_slice`] to get
+    /// the specifications as `&[ColumnSpec]`.
     pub fn get_variable_col_specs(&self) -> ColumnSpecs<'_, 'static> {
         ColumnSpecs::new(&self.shared.metadata.col_specs)
     }
@@ -594,12 +832,51 @@ impl PreparedStatement {
     }
 
     /// Access column specifications of the result set returned after the execution of this statement
+    ///
+    /// The specifications are updated if the database sends new result metadata,
+    /// which is why they are returned behind a guard rather than as a plain slice.
     pub fn get_current_result_set_col_specs(&self) -> ColumnSpecsGuard {
         ColumnSpecsGuard {
             result: self.shared.current_result_metadata.load(),
         }
     }
 
+    /// Access column specifications of the result set, as received when the statement was prepared.
+    ///
+    /// They are known before the statement is executed, so they can be used to build
+    /// a typed deserializer ahead of time, e.g. by type checking a row type with
+    /// [`DeserializeRow::type_check`](crate::deserialize::row::DeserializeRow::type_check).
+    /// If the database later sends new result metadata (e.g. after the table is altered),
+    /// it is not reflected here; use [`get_current_result_set_col_specs`](Self::get_current_result_set_col_specs)
+    /// to observe it.
+    pub fn result_col_specs(&self) -> &[ColumnSpec<'static>] {
+        self.shared.initial_result_metadata.col_specs()
+    }
+
+    /// Checks whether this statement has the same metadata as `other`: the same bind
+    /// variables, partition key indexes and result set columns.
+    ///
+    /// This is useful to detect schema changes after re-preparing a statement,
+    /// e.g. a column added to a table that is queried with `SELECT *`.
+    /// Statement ids and statement configuration are not compared.
+    pub fn metadata_matches(&self, other: &PreparedStatement) -> bool {
+        let (metadata, other_metadata) = (&self.shared.metadata, &other.shared.metadata);
+        let pk_indexes_match = metadata.pk_indexes.len() == other_metadata.pk_indexes.len()
+            && metadata
+                .pk_indexes
+                .iter()
+                .zip(&other_metadata.pk_indexes)
+                .all(|(pk_index, other_pk_index)| {
+                    pk_index.index == other_pk_index.index
+                        && pk_index.sequence == other_pk_index.sequence
+                });
+
+        metadata.col_specs == other_metadata.col_specs
+            && pk_indexes_match
+            && self.get_current_result_metadata().col_specs()
+                == other.get_current_result_metadata().col_specs()
+    }
+
     /// Get the name of the partitioner used for this statement.
     pub fn get_partitioner_name(&self) -> &PartitionerName {
         &self.partitioner_name
@@ -611,6 +888,14 @@ impl PreparedStatement {
         self.config.retry_policy = retry_policy;
     }
 
+    /// Disables retries for this statement: every error is returned right away,
+    /// whatever the retry policy of the execution profile is.
+    /// This is a shorthand for setting [`FallthroughRetryPolicy`] as the retry policy.
+    #[inline]
+    pub fn set_no_retry(&mut self) {
+        self.set_retry_policy(Some(Arc::new(FallthroughRetryPolicy)));
+    }
+
     /// Get the retry policy set for the statement.
     ///
     /// This method returns the retry policy that is **overridden** on this statement.
@@ -640,6 +925,112 @@ impl PreparedStatement {
         self.config.load_balancing_policy.as_ref()
     }
 
+    /// Excludes the given nodes from the load balancing plan of this statement,
+    /// so that none of them is sent any attempt to execute it, including
+    /// retries and speculative executions.
+    ///
+    /// Nodes are identified by their host ids, and subsequent calls add up.
+    /// If every node in the plan is excluded, the execution fails with
+    /// [`ExecutionError::EmptyPlan`].
+    pub fn exclude_nodes(&mut self, nodes: &[NodeRef<'_>]) {
+        self.config
+            .excluded_nodes
+            .extend(nodes.iter().map(|node| node.host_id));
+    }
+
+    /// Gets the host ids of the nodes excluded with `exclude_nodes`.
+    pub fn get_excluded_nodes(&self) -> &[Uuid] {
+        &self.config.excluded_nodes
+    }
+
+    /// Removes all exclusions made with `exclude_nodes`.
+    pub fn clear_excluded_nodes(&mut self) {
+        self.config.excluded_nodes.clear();
+    }
+
+    /// Sets whether the coordinator of each attempt to execute this statement is required
+    /// to be a replica of the partition the statement targets.
+    ///
+    /// If set to `true`, nodes picked by the load balancing policy which do not own the
+    /// partition are skipped, so the request is only sent to replicas. If the plan contains
+    /// no replica, the execution fails with
+    /// [`ExecutionError::CoordinatorNotReplica`];
+    /// if all replicas fail, the error of the last one is returned. The same happens if the partition cannot be
+    /// determined, e.g. because the partition key is not passed as bound values. Defaults to `false`.
+    pub fn set_require_replica_coordinator(&mut self, require: bool) {
+        self.config.require_replica_coordinator = require;
+    }
+
+    /// Gets whether the coordinator is required to be a replica of the targeted partition.
+    pub fn get_require_replica_coordinator(&self) -> bool {
+        self.config.require_replica_coordinator
+    }
+
+    /// Sets whether token-aware routing is disabled for this statement.
+    ///
+    /// If set to `true`, the load balancing policy is not told the token of the targeted
+    /// partition, so it routes the statement as if the partition was unknown - the default
+    /// policy spreads such requests round-robin over the nodes, regardless of replica
+    /// ownership. Useful e.g. for load testing. Defaults to `false`.
+    pub fn set_disable_token_aware(&mut self, disable: bool) {
+        self.config.disable_token_aware = disable;
+    }
+
+    /// Gets whether token-aware routing is disabled for this statement.
+    pub fn get_disable_token_aware(&self) -> bool {
+        self.config.disable_token_aware
+    }
+
+    /// Sets whether each attempt to execute this statement opens a new connection
+    /// instead of using one from the connection pool.
+    ///
+    /// The connection is opened to the node chosen by the load balancing policy
+    /// and closed as soon as the attempt completes. It is not shard-aware, so it is
+    /// connected to an arbitrary shard. This is costly and meant for testing,
+    /// e.g. to isolate a request from the state of pooled connections.
+    /// It is not applied to paged queries executed with a [`QueryPager`](crate::client::pager::QueryPager).
+    /// Defaults to `false`.
+    pub fn set_force_fresh_connection(&mut self, force: bool) {
+        self.config.force_fresh_connection = force;
+    }
+
+    /// Gets whether each attempt to execute this statement opens a new connection.
+    pub fn get_force_fresh_connection(&self) -> bool {
+        self.config.force_fresh_connection
+    }
+
+    /// Sets whether paging is disabled for this statement.
+    ///
+    /// If set to `true`, the results are requested with an unlimited page size, even by
+    /// the paged execution methods (e.g. the ones returning a [`QueryPager`](crate::client::pager::QueryPager)),
+    /// so the server returns all rows in a single page and the page size set on the statement
+    /// is ignored. Only use this for queries known to return a bounded number of rows.
+    /// Defaults to `false`.
+    pub fn set_no_paging(&mut self, no_paging: bool) {
+        self.config.no_paging = no_paging;
+    }
+
+    /// Gets whether paging is disabled for this statement.
+    pub fn get_no_paging(&self) -> bool {
+        self.config.no_paging
+    }
+
+    /// Sets the index of the replica of the targeted partition, in token order,
+    /// which should be tried first when executing this statement.
+    ///
+    /// The remaining nodes are tried in the order of the load balancing plan. The preference
+    /// is ignored if the partition cannot be determined (e.g. because the partition key is not passed as bound values),
+    /// if it has no more replicas than `index`, or if the load balancing plan doesn't include
+    /// that replica (e.g. because it is down or in a remote datacenter). Defaults to `None`.
+    pub fn set_preferred_replica_index(&mut self, index: Option<usize>) {
+        self.config.preferred_replica_index = index;
+    }
+
+    /// Gets the index of the replica which should be tried first, if set.
+    pub fn get_preferred_replica_index(&self) -> Option<usize> {
+        self.config.preferred_replica_index
+    }
+
     /// Sets the listener capable of listening what happens during query execution.
     pub fn set_history_listener(&mut self, history_listener: Arc<dyn HistoryListener>) {
         self.config.history_listener = Some(history_listener);
@@ -650,6 +1041,21 @@ impl PreparedStatement {
         self.config.history_listener.take()
     }
 
+    /// Sets a callback invoked before each attempt to execute the statement, including
+    /// retries and speculative executions. It receives the number of the attempt,
+    /// starting from 1, and the node the attempt is sent to.
+    ///
+    /// The callback is shared between clones of the statement. It is called synchronously
+    /// on the execution path, so it should not block.
+    pub fn set_on_attempt(&mut self, on_attempt: impl FnMut(u32, NodeRef<'_>) + Send + 'static) {
+        self.config.on_attempt = Some(AttemptCallback::new(on_attempt));
+    }
+
+    /// Removes the callback set by `set_on_attempt`.
+    pub fn remove_on_attempt(&mut self) {
+        self.config.on_attempt = None;
+    }
+
     /// Associates the query with execution profile referred by the provided handle.
     /// Handle may be later remapped to another profile, and query will reflect those changes.
     pub fn set_execution_profile_handle(&mut self, profile_handle: Option<ExecutionProfileHandle>) {
@@ -661,6 +1067,48 @@ impl PreparedStatement {
         self.config.execution_profile_handle.as_ref()
     }
 
+    /// Enables a rolling log of the `capacity` most recent executions of this statement,
+    /// available through [`recent_executions`](Self::recent_executions). `None` disables it.
+    ///
+    /// The log starts empty and is shared with clones of this statement made afterwards.
+    /// Executions of paged queries (`execute_iter`) and batches are not recorded.
+    pub fn set_execution_history_capacity(&mut self, capacity: Option<NonZeroUsize>) {
+        self.execution_history = capacity.map(|capacity| Arc::new(ExecutionHistory::new(capacity)));
+    }
+
+    /// Returns the capacity of the execution log, or `None` if it is disabled.
+    pub fn get_execution_history_capacity(&self) -> Option<NonZeroUsize> {
+        self.execution_history
+            .as_ref()
+            .map(|history| history.capacity())
+    }
+
+    /// Returns the most recent executions of this statement, from the oldest to the newest.
+    ///
+    /// Empty unless enabled with [`set_execution_history_capacity`](Self::set_execution_history_capacity).
+    pub fn recent_executions(&self) -> Vec<StatementExecution> {
+        self.execution_history
+            .as_ref()
+            .map_or_else(Vec::new, |history| history.executions())
+    }
+
+    pub(crate) fn get_execution_history(&self) -> Option<&ExecutionHistory> {
+        self.execution_history.as_deref()
+    }
+
+    /// Serializes the values like an execution of this statement would,
+    /// and returns them exactly as they would be sent, for debugging purposes.
+    ///
+    /// See [`SerializedValuesDump`]
+    /// for the contents of the dump.
+    pub fn debug_dump_values(
+        &self,
+        values: &impl SerializeRow,
+    ) -> Result<SerializedValuesDump, SerializationError> {
+        self.serialize_values(values)
+            .map(|serialized_values| serialized_values.debug_dump())
+    }
+
     pub(crate) fn serialize_values(
         &self,
         values: &impl SerializeRow,
@@ -673,6 +1121,8 @@ impl PreparedStatement {
         UnconfiguredPreparedStatement {
             shared: Arc::clone(&self.shared),
             partitioner_name: self.get_partitioner_name().clone(),
+            prepared_in_keyspace: self.prepared_in_keyspace.clone(),
+            reprepare_keyspace: self.reprepare_keyspace.clone(),
         }
     }
 }
@@ -687,6 +1137,8 @@ impl PreparedStatement {
 pub(crate) struct UnconfiguredPreparedStatement {
     shared: Arc<PreparedStatementSharedData>,
     partitioner_name: PartitionerName,
+    prepared_in_keyspace: Option<Arc<String>>,
+    reprepare_keyspace: Option<VerifiedKeyspaceName>,
 }
 
 impl UnconfiguredPreparedStatement {
@@ -700,6 +1152,10 @@ impl UnconfiguredPreparedStatement {
             prepare_tracing_ids: Vec::new(),
             page_size,
             partitioner_name: self.partitioner_name.clone(),
+            routing_table_spec: None,
+            prepared_in_keyspace: self.prepared_in_keyspace.clone(),
+            reprepare_keyspace: self.reprepare_keyspace.clone(),
+            execution_history: None,
             config,
         }
     }
@@ -928,6 +1384,61 @@ mod tests {
         );
     }
 
+    fn make_prepared(meta: PreparedMetadata) -> crate::statement::prepared::PreparedStatement {
+        use crate::frame::response::result::ResultMetadata;
+        use crate::statement::prepared::PreparedStatement;
+        use bytes::Bytes;
+
+        PreparedStatement::new(
+            Bytes::from_static(b"test_id"),
+            false,
+            meta,
+            std::sync::Arc::new(ResultMetadata::new_for_test(0, Vec::new())),
+            "SELECT * FROM t".to_string(),
+            crate::statement::PageSize::new(100).unwrap(),
+            Default::default(),
+        )
+    }
+
+    #[test]
+    fn test_routing_key_matches_manual_construction() {
+        setup_tracing();
+
+        // Single-column partition key: the routing key is the value itself.
+        let prepared = make_prepared(make_meta(
+            [
+                ColumnType::Native(NativeType::Int),
+                ColumnType::Native(NativeType::Text),
+            ],
+            [0],
+        ));
+        let routing_key = prepared.routing_key(&(7i32, "x")).unwrap().unwrap();
+        assert_eq!(&routing_key[..], 7i32.to_be_bytes());
+
+        // Composite partition key: length-prefixed components in partition key order.
+        let prepared = make_prepared(make_meta(
+            [
+                ColumnType::Native(NativeType::Int),
+                ColumnType::Native(NativeType::Text),
+                ColumnType::Native(NativeType::BigInt),
+            ],
+            [2, 0],
+        ));
+        let routing_key = prepared.routing_key(&(7i32, "x", 9i64)).unwrap().unwrap();
+
+        let mut expected = Vec::new();
+        for component in [&9i64.to_be_bytes()[..], &7i32.to_be_bytes()[..]] {
+            expected.extend_from_slice(&(component.len() as u16).to_be_bytes());
+            expected.extend_from_slice(component);
+            expected.push(0);
+        }
+        assert_eq!(&routing_key[..], expected);
+
+        // Statements without partition key information have no routing key.
+        let prepared = make_prepared(make_meta([ColumnType::Native(NativeType::Int)], []));
+        assert_eq!(prepared.routing_key(&(7i32,)).unwrap(), None);
+    }
+
     #[test]
     fn test_column_specs_guard_debug() {
         use crate::frame::response::result::ResultMetadata;
@@ -965,4 +1476,48 @@ mod tests {
         assert!(debug_output.contains("ColumnSpecsGuard"));
         assert!(debug_output.contains("test_column_name"));
     }
+
+    #[test]
+    fn test_routing_keyspace_overrides_metadata_keyspace() {
+        use crate::frame::response::result::ResultMetadata;
+        use crate::statement::prepared::PreparedStatement;
+        use bytes::Bytes;
+
+        setup_tracing();
+
+        let meta = make_meta([ColumnType::Native(NativeType::Int)], [0]);
+        let mut prepared = PreparedStatement::new(
+            Bytes::from_static(b"test_id"),
+            false,
+            meta,
+            std::sync::Arc::new(ResultMetadata::new_for_test(0, Vec::new())),
+            "INSERT INTO t (a) VALUES (?)".to_string(),
+            crate::statement::PageSize::new(100).unwrap(),
+            Default::default(),
+        );
+
+        assert_eq!(prepared.get_routing_keyspace(), None);
+        assert_eq!(
+            prepared.get_routing_table_spec(),
+            Some(&TableSpec::borrowed("ks", "t"))
+        );
+
+        prepared.set_routing_keyspace(Some("routing_ks".to_owned()));
+        assert_eq!(prepared.get_routing_keyspace(), Some("routing_ks"));
+        assert_eq!(
+            prepared.get_routing_table_spec(),
+            Some(&TableSpec::borrowed("routing_ks", "t"))
+        );
+        // The keyspace reported by the database is left intact.
+        assert_eq!(prepared.get_keyspace_name(), Some("ks"));
+
+        // The override survives cloning.
+        assert_eq!(prepared.clone().get_routing_keyspace(), Some("routing_ks"));
+
+        prepared.set_routing_keyspace(None);
+        assert_eq!(
+            prepared.get_routing_table_spec(),
+            Some(&TableSpec::borrowed("ks", "t"))
+        );
+    }
 }
@@ -0,0 +1,185 @@
+//! Files with portable blobs of prepared statements, used to warm up new sessions.
+//!
+//! A [PreparedStatement] can be serialized into a portable blob with
+//! [PreparedStatement::to_portable_blob], which contains its id and metadata, i.e. everything
+//! the driver learns by preparing it. Statements saved with [save_prepared_cache] can be preloaded
+//! by a new session with
+//! [SessionBuilder::preload_prepared_from](crate::client::session_builder::SessionBuilder::preload_prepared_from),
+//! so that [Session::prepare](crate::client::session::Session::prepare) does not need a PREPARE
+//! round trip for them.
+//!
+//! A blob records the keyspace that was in use (see
+//! [Session::use_keyspace](crate::client::session::Session::use_keyspace)) when the statement
+//! was prepared, because unqualified table names in the statement refer to it. A preloaded
+//! statement is only used by a session which prepares it in the same keyspace.
+//!
+//! Preloaded statements are used as if they had just been prepared, so they become stale
+//! in the same cases (see [PreparedStatement]). If the cluster does not know a preloaded
+//! statement (e.g. because it was restarted since the file had been saved), the statement
+//! is transparently reprepared on its first execution.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::errors::PreparedCacheError;
+use crate::frame::frame_errors::LowLevelDeserializationError;
+use crate::frame::protocol_features::ProtocolFeatures;
+use crate::frame::response::result::Prepared;
+use crate::frame::types;
+use crate::statement::prepared::PreparedStatement;
+
+/// Marks the beginning of a file with prepared statements.
+const FILE_MAGIC: &[u8; 8] = b"SCYLLAPS";
+
+/// Version of the format of portable blobs.
+const BLOB_FORMAT_VERSION: u8 = 1;
+
+const BLOB_FLAG_LWT: u8 = 0x01;
+const BLOB_FLAG_RESULT_METADATA_ID: u8 = 0x02;
+const BLOB_FLAG_KEYSPACE: u8 = 0x04;
+
+/// Key of a preloaded statement: the keyspace it was prepared in and the statement string.
+type PreloadedKey = (Option<String>, String);
+
+/// Serializes a prepared statement into a portable blob.
+pub(crate) fn encode_blob(
+    statement: &str,
+    keyspace: Option<&str>,
+    prepared: &Prepared,
+    is_lwt: bool,
+) -> Result<Vec<u8>, PreparedCacheError> {
+    let mut flags = 0;
+    if keyspace.is_some() {
+        flags |= BLOB_FLAG_KEYSPACE;
+    }
+    if is_lwt {
+        flags |= BLOB_FLAG_LWT;
+    }
+    if prepared.result_metadata.id().is_some() {
+        flags |= BLOB_FLAG_RESULT_METADATA_ID;
+    }
+
+    let mut blob = vec![BLOB_FORMAT_VERSION, flags];
+    types::write_long_string(statement, &mut blob)
+        .map_err(|_| PreparedCacheError::StatementTooLarge)?;
+    if let Some(keyspace) = keyspace {
+        types::write_string(keyspace, &mut blob)
+            .map_err(|_| PreparedCacheError::StatementTooLarge)?;
+    }
+    prepared.serialize(&mut blob, &blob_features(flags))?;
+
+    Ok(blob)
+}
+
+/// Deserializes a portable blob into the keyspace and the statement string,
+/// its preparation result and whether it is an LWT.
+fn decode_blob(blob: &[u8]) -> Result<(PreloadedKey, Prepared, bool), PreparedCacheError> {
+    let (&[version, flags], mut buf) =
+        blob.split_first_chunk::<2>()
+            .ok_or(LowLevelDeserializationError::TooFewBytesReceived {
+                expected: 2,
+                received: blob.len(),
+            })?;
+    if version != BLOB_FORMAT_VERSION {
+        return Err(PreparedCacheError::UnsupportedBlobVersion(version));
+    }
+    let statement = types::read_long_string(&mut buf)?.to_owned();
+    let keyspace = if flags & BLOB_FLAG_KEYSPACE != 0 {
+        Some(types::read_string(&mut buf)?.to_owned())
+    } else {
+        None
+    };
+    let prepared = Prepared::deserialize(&mut buf, &blob_features(flags))?;
+
+    Ok(((keyspace, statement), prepared, flags & BLOB_FLAG_LWT != 0))
+}
+
+/// The result metadata id is only present in the blob if the flag says so.
+fn blob_features(flags: u8) -> ProtocolFeatures {
+    let mut features = ProtocolFeatures::default();
+    features.scylla_metadata_id_supported = flags & BLOB_FLAG_RESULT_METADATA_ID != 0;
+    features
+}
+
+/// Saves portable blobs of the prepared statements to a file, which can be preloaded
+/// by a new session with
+/// [SessionBuilder::preload_prepared_from](crate::client::session_builder::SessionBuilder::preload_prepared_from).
+///
+/// The file is overwritten if it exists.
+///
+/// # Example
+/// ```rust
+/// # use scylla::client::session::Session;
+/// # async fn example(session: &Session) -> Result<(), Box<dyn std::error::Error>> {
+/// use scylla::statement::prepared_cache::save_prepared_cache;
+///
+/// let select = session.prepare("SELECT a, b FROM ks.tab WHERE a = ?").await?;
+/// let insert = session.prepare("INSERT INTO ks.tab (a, b) VALUES (?, ?)").await?;
+/// save_prepared_cache("prepared.bin", &[select, insert])?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn save_prepared_cache(
+    path: impl AsRef<Path>,
+    statements: &[PreparedStatement],
+) -> Result<(), PreparedCacheError> {
+    let mut file = FILE_MAGIC.to_vec();
+    types::write_int(
+        i32::try_from(statements.len()).map_err(|_| PreparedCacheError::StatementTooLarge)?,
+        &mut file,
+    );
+    for statement in statements {
+        types::write_bytes(&statement.to_portable_blob()?, &mut file)
+            .map_err(|_| PreparedCacheError::StatementTooLarge)?;
+    }
+
+    std::fs::write(path, file).map_err(|err| PreparedCacheError::IoError(Arc::new(err)))
+}
+
+/// Statements loaded from a file, which are used by the session instead of preparing them.
+#[derive(Debug, Default)]
+pub(crate) struct PreloadedPreparedStatements {
+    /// Preparation results of the statements, with whether they are LWTs,
+    /// by keyspace and statement string.
+    statements: Mutex<HashMap<PreloadedKey, (Prepared, bool)>>,
+}
+
+impl PreloadedPreparedStatements {
+    /// Loads the statements saved with [save_prepared_cache].
+    pub(crate) async fn load(path: &Path) -> Result<Self, PreparedCacheError> {
+        let file = tokio::fs::read(path)
+            .await
+            .map_err(|err| PreparedCacheError::IoError(Arc::new(err)))?;
+        Self::parse(&file)
+    }
+
+    fn parse(file: &[u8]) -> Result<Self, PreparedCacheError> {
+        let buf = &mut file
+            .strip_prefix(FILE_MAGIC)
+            .ok_or(PreparedCacheError::NotAPreparedCacheFile)?;
+
+        let count = types::read_int_length(buf)?;
+        let mut statements = HashMap::new();
+        for _ in 0..count {
+            let (key, prepared, is_lwt) = decode_blob(types::read_bytes(buf)?)?;
+            statements.insert(key, (prepared, is_lwt));
+        }
+
+        Ok(Self {
+            statements: Mutex::new(statements),
+        })
+    }
+
+    /// Takes out the preparation result of the statement, if it was preloaded
+    /// in the given keyspace.
+    ///
+    /// Each preloaded statement is used only once, so that preparing it again
+    /// later (e.g. after a schema change) fetches fresh metadata from the cluster.
+    pub(crate) fn take(&self, keyspace: Option<&str>, statement: &str) -> Option<(Prepared, bool)> {
+        self.statements
+            .lock()
+            .unwrap()
+            .remove(&(keyspace.map(str::to_owned), statement.to_owned()))
+    }
+}
@@ -1,13 +1,18 @@
 //! Defines the [`Statement`] type, which represents an unprepared CQL statement.
 
-use super::{PageSize, StatementConfig};
+use super::{AdaptivePageSize, AttemptCallback, PageSize, StatementConfig};
 use crate::client::execution_profile::ExecutionProfileHandle;
+use crate::cluster::NodeRef;
 use crate::frame::types::{Consistency, SerialConsistency};
 use crate::observability::history::HistoryListener;
 use crate::policies::load_balancing::LoadBalancingPolicy;
-use crate::policies::retry::RetryPolicy;
+use crate::policies::retry::{FallthroughRetryPolicy, RetryPolicy};
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::num::NonZeroU32;
 use std::sync::Arc;
 use std::time::Duration;
+use uuid::Uuid;
 
 /// **Unprepared** CQL statement.
 ///
@@ -19,8 +24,11 @@ pub struct Statement {
     /// The CQL statement text.
     pub contents: String,
     page_size: PageSize,
+    rewrite: Option<StatementRewrite>,
 }
 
+type StatementRewrite = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
 impl Statement {
     /// Creates a new [`Statement`] from a CQL statement string.
     pub fn new(query_text: impl Into<String>) -> Self {
@@ -28,6 +36,7 @@ impl Statement {
             contents: query_text.into(),
             page_size: PageSize::default(),
             config: Default::default(),
+            rewrite: None,
         }
     }
 
@@ -82,6 +91,15 @@ impl Statement {
         self.config.serial_consistency = Some(sc);
     }
 
+    /// Sets both the consistency and the serial consistency to be used when executing
+    /// this statement, e.g. `Quorum` with `LocalSerial` for an LWT.
+    ///
+    /// Equivalent to calling [`Self::set_consistency`] and [`Self::set_serial_consistency`].
+    pub fn set_consistency_pair(&mut self, c: Consistency, sc: Option<SerialConsistency>) {
+        self.set_consistency(c);
+        self.set_serial_consistency(sc);
+    }
+
     /// Unsets the serial consistency overridden on this statement.
     /// This means that serial consistency will be derived from the execution profile
     /// (per-statement or, if absent, the default one).
@@ -95,6 +113,38 @@ impl Statement {
         self.config.serial_consistency.flatten()
     }
 
+    /// Sets the serial consistencies to fall back to when the serial phase of this statement
+    /// fails with an `Unavailable` error, e.g. `[LocalSerial, Serial]` to try the local
+    /// datacenter first.
+    ///
+    /// On such an error, the statement is retried on the same node with the next entry
+    /// of the list which differs from the serial consistency of the failed attempt.
+    /// Each entry is tried at most once, and the retry policy is consulted only
+    /// once the list is exhausted. (Ignored unless the statement is an LWT)
+    pub fn set_serial_consistency_fallback(&mut self, fallback: Vec<SerialConsistency>) {
+        self.config.serial_consistency_fallback = fallback;
+    }
+
+    /// Gets the serial consistencies to fall back to when the serial phase of this statement
+    /// fails with an `Unavailable` error.
+    pub fn get_serial_consistency_fallback(&self) -> &[SerialConsistency] {
+        &self.config.serial_consistency_fallback
+    }
+
+    /// Sets whether an `AlreadyExists` error returned for this statement should be treated
+    /// as success, e.g. to make schema changes idempotent without `IF NOT EXISTS`.
+    ///
+    /// If set to `true`, such an error is neither retried nor returned; the execution
+    /// results in an empty result instead. Defaults to `false`.
+    pub fn set_ignore_already_exists(&mut self, ignore_already_exists: bool) {
+        self.config.ignore_already_exists = ignore_already_exists;
+    }
+
+    /// Gets whether an `AlreadyExists` error returned for this statement is treated as success.
+    pub fn get_ignore_already_exists(&self) -> bool {
+        self.config.ignore_already_exists
+    }
+
     /// Sets the idempotence of this statement
     /// A statement is idempotent if it can be applied multiple times without changing the result of the initial application
     /// If set to `true` we can be sure that it is idempotent
@@ -121,10 +171,28 @@ impl Statement {
         self.config.tracing
     }
 
+    /// Sets a custom payload to be sent to the coordinator along with this statement.
+    ///
+    /// The custom payload is a map of arbitrary keys and byte values, carried
+    /// in the request frame. Stock ScyllaDB and Cassandra servers ignore it;
+    /// it is only useful with servers, or proxies in front of them,
+    /// which read the given keys.
+    pub fn set_custom_payload(&mut self, custom_payload: Option<HashMap<String, Bytes>>) {
+        self.config.custom_payload = custom_payload;
+    }
+
+    /// Gets the custom payload sent along with this statement, if any.
+    pub fn get_custom_payload(&self) -> Option<&HashMap<String, Bytes>> {
+        self.config.custom_payload.as_ref()
+    }
+
     /// Sets the default timestamp for this statement in microseconds.
     /// If not None, it will replace the server side assigned timestamp as default timestamp
     /// If a statement contains a `USING TIMESTAMP` clause, calling this method won't change
     /// anything
+    ///
+    /// The timestamp is the write timestamp of the mutations. It doesn't affect reads:
+    /// the CQL protocol has no way to read data as of a given timestamp.
     pub fn set_timestamp(&mut self, timestamp: Option<i64>) {
         self.config.timestamp = timestamp
     }
@@ -147,12 +215,84 @@ impl Statement {
         self.config.request_timeout
     }
 
+    /// Limits the number of rows an unpaged execution of this statement may return.
+    /// If not None, the statement is sent with a page size just above the limit, and
+    /// the execution fails with [`ExecutionError::UnpagedRowLimitExceeded`](crate::errors::ExecutionError::UnpagedRowLimitExceeded) if the result
+    /// has more rows than the limit. This guards against accidental full scans.
+    ///
+    /// If the server ends the page early, e.g. because of its size in bytes, without
+    /// exceeding the limit, the result is incomplete and the execution fails with
+    /// [`RequestAttemptError::NonfinishedPagingState`](crate::errors::RequestAttemptError::NonfinishedPagingState) instead.
+    ///
+    /// Paged executions are not affected.
+    pub fn set_max_unpaged_rows(&mut self, max_rows: Option<NonZeroU32>) {
+        self.config.max_unpaged_rows = max_rows;
+    }
+
+    /// Gets the limit of rows an unpaged execution of this statement may return.
+    pub fn get_max_unpaged_rows(&self) -> Option<NonZeroU32> {
+        self.config.max_unpaged_rows
+    }
+
+    /// Enables adaptive paging for paged executions of this statement.
+    /// If not None, the page size set on the statement is used only for the first page,
+    /// and the sizes of following pages are adjusted to approach a target size in bytes,
+    /// based on the observed size of rows. See [`AdaptivePageSize`].
+    pub fn set_adaptive_page_size(&mut self, adaptive_page_size: Option<AdaptivePageSize>) {
+        self.config.adaptive_page_size = adaptive_page_size;
+    }
+
+    /// Gets the adaptive paging configuration of this statement.
+    pub fn get_adaptive_page_size(&self) -> Option<AdaptivePageSize> {
+        self.config.adaptive_page_size
+    }
+
+    /// Sets a total deadline for paged executions of this statement, measured from
+    /// the start of the execution. Once it elapses, fetching the next page fails with
+    /// [`RequestError::RequestTimeout`](crate::errors::RequestError::RequestTimeout),
+    /// regardless of how many pages have been fetched so far.
+    ///
+    /// Unlike the request timeout, which bounds the fetch of each page separately,
+    /// the deadline spans all pages. Unpaged executions are not affected.
+    pub fn set_paging_deadline(&mut self, deadline: Option<Duration>) {
+        self.config.paging_deadline = deadline;
+    }
+
+    /// Gets the total deadline for paged executions of this statement.
+    pub fn get_paging_deadline(&self) -> Option<Duration> {
+        self.config.paging_deadline
+    }
+
+    /// Sets the priority of this statement in the session's admission queue.
+    /// When the session executes as many requests as allowed by
+    /// [`SessionBuilder::max_concurrent_requests`](crate::client::session_builder::SessionBuilder::max_concurrent_requests),
+    /// waiting requests with higher priority are admitted first.
+    /// Has no effect if the number of concurrent requests is not limited.
+    ///
+    /// The default priority is 0, the lowest one.
+    pub fn set_priority(&mut self, priority: u8) {
+        self.config.priority = priority;
+    }
+
+    /// Gets the priority of this statement in the session's admission queue.
+    pub fn get_priority(&self) -> u8 {
+        self.config.priority
+    }
+
     /// Set the retry policy for this statement, overriding the one from execution profile if not None.
     #[inline]
     pub fn set_retry_policy(&mut self, retry_policy: Option<Arc<dyn RetryPolicy>>) {
         self.config.retry_policy = retry_policy;
     }
 
+    /// Disables retries for this statement: every error is returned right away,
+    /// whatever the retry policy of the execution profile is.
+    /// This is a shorthand for setting [`FallthroughRetryPolicy`] as the retry policy.
+    #[inline]
+    pub fn set_no_retry(&mut self) {
+        self.set_retry_policy(Some(Arc::new(FallthroughRetryPolicy)));
+    }
+
     /// Get the retry policy set for the statement.
     ///
     /// This method returns the retry policy that is **overridden** on this statement.
@@ -182,6 +322,97 @@ impl Statement {
         self.config.load_balancing_policy.as_ref()
     }
 
+    /// Excludes the given nodes from the load balancing plan of this statement,
+    /// so that none of them is sent any attempt to execute it, including
+    /// retries and speculative executions.
+    ///
+    /// Nodes are identified by their host ids, and subsequent calls add up.
+    /// If every node in the plan is excluded, the execution fails with
+    /// [`ExecutionError::EmptyPlan`](crate::errors::ExecutionError::EmptyPlan).
+    pub fn exclude_nodes(&mut self, nodes: &[NodeRef<'_>]) {
+        self.config
+            .excluded_nodes
+            .extend(nodes.iter().map(|node| node.host_id));
+    }
+
+    /// Gets the host ids of the nodes excluded with `exclude_nodes`.
+    pub fn get_excluded_nodes(&self) -> &[Uuid] {
+        &self.config.excluded_nodes
+    }
+
+    /// Removes all exclusions made with `exclude_nodes`.
+    pub fn clear_excluded_nodes(&mut self) {
+        self.config.excluded_nodes.clear();
+    }
+
+    /// Sets whether the coordinator of each attempt to execute this statement is required
+    /// to be a replica of the partition the statement targets.
+    ///
+    /// If set to `true`, nodes picked by the load balancing policy which do not own the
+    /// partition are skipped, so the request is only sent to replicas. If the plan contains
+    /// no replica, the execution fails with
+    /// [`ExecutionError::CoordinatorNotReplica`](crate::errors::ExecutionError::CoordinatorNotReplica);
+    /// if all replicas fail, the error of the last one is returned. The same happens if the partition cannot be
+    /// determined, e.g. because the statement is not prepared. Defaults to `false`.
+    pub fn set_require_replica_coordinator(&mut self, require: bool) {
+        self.config.require_replica_coordinator = require;
+    }
+
+    /// Gets whether the coordinator is required to be a replica of the targeted partition.
+    pub fn get_require_replica_coordinator(&self) -> bool {
+        self.config.require_replica_coordinator
+    }
+
+    /// Sets whether each attempt to execute this statement opens a new connection
+    /// instead of using one from the connection pool.
+    ///
+    /// The connection is opened to the node chosen by the load balancing policy
+    /// and closed as soon as the attempt completes. It is not shard-aware, so it is
+    /// connected to an arbitrary shard. This is costly and meant for testing,
+    /// e.g. to isolate a request from the state of pooled connections.
+    /// It is not applied to paged queries executed with a [`QueryPager`](crate::client::pager::QueryPager).
+    /// Defaults to `false`.
+    pub fn set_force_fresh_connection(&mut self, force: bool) {
+        self.config.force_fresh_connection = force;
+    }
+
+    /// Gets whether each attempt to execute this statement opens a new connection.
+    pub fn get_force_fresh_connection(&self) -> bool {
+        self.config.force_fresh_connection
+    }
+
+    /// Sets whether paging is disabled for this statement.
+    ///
+    /// If set to `true`, the results are requested with an unlimited page size, even by
+    /// the paged execution methods (e.g. the ones returning a [`QueryPager`](crate::client::pager::QueryPager)),
+    /// so the server returns all rows in a single page and the page size set on the statement
+    /// is ignored. Only use this for queries known to return a bounded number of rows.
+    /// Defaults to `false`.
+    pub fn set_no_paging(&mut self, no_paging: bool) {
+        self.config.no_paging = no_paging;
+    }
+
+    /// Gets whether paging is disabled for this statement.
+    pub fn get_no_paging(&self) -> bool {
+        self.config.no_paging
+    }
+
+    /// Sets the index of the replica of the targeted partition, in token order,
+    /// which should be tried first when executing this statement.
+    ///
+    /// The remaining nodes are tried in the order of the load balancing plan. The preference
+    /// is ignored if the partition cannot be determined (e.g. because the statement is not prepared),
+    /// if it has no more replicas than `index`, or if the load balancing plan doesn't include
+    /// that replica (e.g. because it is down or in a remote datacenter). Defaults to `None`.
+    pub fn set_preferred_replica_index(&mut self, index: Option<usize>) {
+        self.config.preferred_replica_index = index;
+    }
+
+    /// Gets the index of the replica which should be tried first, if set.
+    pub fn get_preferred_replica_index(&self) -> Option<usize> {
+        self.config.preferred_replica_index
+    }
+
     /// Sets the listener capable of listening what happens during statement execution.
     pub fn set_history_listener(&mut self, history_listener: Arc<dyn HistoryListener>) {
         self.config.history_listener = Some(history_listener);
@@ -192,6 +423,48 @@ impl Statement {
         self.config.history_listener.take()
     }
 
+    /// Sets a callback invoked before each attempt to execute the statement, including
+    /// retries and speculative executions. It receives the number of the attempt,
+    /// starting from 1, and the node the attempt is sent to.
+    ///
+    /// The callback is shared between clones of the statement. It is called synchronously
+    /// on the execution path, so it should not block.
+    pub fn set_on_attempt(&mut self, on_attempt: impl FnMut(u32, NodeRef<'_>) + Send + 'static) {
+        self.config.on_attempt = Some(AttemptCallback::new(on_attempt));
+    }
+
+    /// Removes the callback set by `set_on_attempt`.
+    pub fn remove_on_attempt(&mut self) {
+        self.config.on_attempt = None;
+    }
+
+    /// Sets a function which rewrites the CQL text of the statement each time it is
+    /// executed, e.g. to run A/B experiments with alternative queries.
+    ///
+    /// The rewritten text is what gets sent to the database, and what gets prepared
+    /// if the statement is executed with non-empty values. The function is applied
+    /// by `Session::query_*` methods only - not by [`Session::prepare`](crate::client::session::Session::prepare)
+    /// nor when the statement is a part of a batch.
+    pub fn set_rewrite_statement(
+        &mut self,
+        rewrite: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) {
+        self.rewrite = Some(Arc::new(rewrite));
+    }
+
+    /// Removes the function set by `set_rewrite_statement`.
+    pub fn remove_rewrite_statement(&mut self) {
+        self.rewrite = None;
+    }
+
+    /// Applies the rewrite function of the statement, if any, to its contents.
+    pub(crate) fn rewritten(mut self) -> Self {
+        if let Some(rewrite) = self.rewrite.take() {
+            self.contents = rewrite(&self.contents);
+        }
+        self
+    }
+
     /// Associates the query with execution profile referred by the provided handle.
     /// Handle may be later remapped to another profile, and query will reflect those changes.
     pub fn set_execution_profile_handle(&mut self, profile_handle: Option<ExecutionProfileHandle>) {
@@ -101,6 +101,18 @@ pub(crate) async fn scylla_supports_tablets(session: &Session) -> bool {
     supports_feature(session, "TABLETS").await
 }
 
+/// Returns false if the IPv6 loopback address can't be bound, e.g. in containers
+/// without IPv6. Tests which need it should be skipped then.
+pub(crate) async fn ipv6_loopback_available() -> bool {
+    match tokio::net::TcpListener::bind("[::1]:0").await {
+        Ok(_) => true,
+        Err(err) => {
+            warn!("Skipping test because IPv6 loopback is unavailable: {err}");
+            false
+        }
+    }
+}
+
 pub(crate) fn setup_tracing() {
     let testing_layer = tracing_subscriber::fmt::layer()
         .with_test_writer()
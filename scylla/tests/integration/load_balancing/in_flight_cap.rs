@@ -0,0 +1,111 @@
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::Duration;
+
+use scylla::client::execution_profile::ExecutionProfile;
+use scylla::client::session::Session;
+use scylla::client::session_builder::SessionBuilder;
+use scylla::policies::load_balancing::{
+    DefaultPolicy, NodeIdentifier, SingleTargetLoadBalancingPolicy,
+};
+use scylla::statement::Statement;
+use scylla_proxy::{
+    Condition, ProxyError, Reaction, RequestOpcode, RequestReaction, RequestRule, ShardAwareness,
+    WorkerError,
+};
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+const MAX_IN_FLIGHT: usize = 4;
+const SLOW_QUERY: &str = "SELECT host_id FROM system.local WHERE key = 'in_flight_cap_test'";
+
+#[tokio::test]
+async fn saturated_node_is_deprioritised() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let policy = DefaultPolicy::builder()
+                .token_aware(false)
+                .max_in_flight_per_node(NonZeroUsize::new(MAX_IN_FLIGHT))
+                .build();
+            let session: Arc<Session> = Arc::new(
+                SessionBuilder::new()
+                    .known_node(proxy_uris[0].as_str())
+                    .address_translator(Arc::new(translation_map))
+                    .default_execution_profile_handle(
+                        ExecutionProfile::builder()
+                            .load_balancing_policy(policy)
+                            .build()
+                            .into_handle(),
+                    )
+                    .build()
+                    .await
+                    .unwrap(),
+            );
+
+            // Slow responses keep the saturating requests in flight.
+            for running_node in running_proxy.running_nodes.iter_mut() {
+                running_node.change_request_rules(Some(vec![RequestRule(
+                    Condition::not(Condition::ConnectionRegisteredAnyEvent)
+                        .and(Condition::RequestOpcode(RequestOpcode::Query))
+                        .and(Condition::BodyContainsCaseSensitive(Box::new(
+                            *b"in_flight_cap_test",
+                        ))),
+                    RequestReaction::delay(Duration::from_secs(5)),
+                )]));
+            }
+
+            // Saturate the first node.
+            let saturated_node = session.get_cluster_state().get_nodes_info()[0].clone();
+            let mut slow_statement = Statement::new(SLOW_QUERY);
+            slow_statement.set_load_balancing_policy(Some(SingleTargetLoadBalancingPolicy::new(
+                NodeIdentifier::Node(Arc::clone(&saturated_node)),
+                None,
+            )));
+            let saturating_requests = (0..MAX_IN_FLIGHT)
+                .map(|_| {
+                    let session = Arc::clone(&session);
+                    let slow_statement = slow_statement.clone();
+                    tokio::spawn(async move {
+                        let _ = session.query_unpaged(slow_statement, ()).await;
+                    })
+                })
+                .collect::<Vec<_>>();
+            tokio::time::timeout(Duration::from_secs(5), async {
+                while saturated_node.in_flight_requests() < MAX_IN_FLIGHT {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            })
+            .await
+            .expect("The node did not get saturated");
+
+            // While the node is saturated, other nodes are chosen as coordinators.
+            for _ in 0..30 {
+                let result = session
+                    .query_unpaged("SELECT host_id FROM system.local WHERE key = 'local'", ())
+                    .await
+                    .unwrap();
+                assert_ne!(
+                    result.request_coordinator().node().host_id,
+                    saturated_node.host_id
+                );
+            }
+
+            running_proxy.turn_off_rules();
+            for request in saturating_requests {
+                request.await.unwrap();
+            }
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
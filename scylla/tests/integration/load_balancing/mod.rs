@@ -1,3 +1,4 @@
+mod in_flight_cap;
 mod latency_awareness;
 mod lwt_optimisation;
 mod shards;
@@ -65,3 +65,131 @@ async fn test_token_awareness() {
 
     session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
 }
+
+#[tokio::test]
+async fn test_token_awareness_with_routing_keyspace() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+    let session_ks = unique_keyspace_name();
+
+    // The keyspaces are replicated differently, so that routing tells them apart:
+    // every node is a replica in the session keyspace, but only one is in `ks`.
+    for (keyspace, replication_factor) in [(&ks, 1), (&session_ks, 3)] {
+        let mut create_ks = format!(
+            "CREATE KEYSPACE IF NOT EXISTS {keyspace} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : {replication_factor}}}"
+        );
+        if scylla_supports_tablets(&session).await {
+            create_ks += " AND TABLETS = {'enabled': false}"
+        }
+        session.ddl(create_ks).await.unwrap();
+        session
+            .ddl(format!(
+                "CREATE TABLE IF NOT EXISTS {keyspace}.t (a text primary key)"
+            ))
+            .await
+            .unwrap();
+    }
+    session.use_keyspace(&session_ks, false).await.unwrap();
+
+    // The keyspace used for routing is given explicitly, instead of being taken
+    // from the prepared metadata, which refers to the session keyspace.
+    let mut prepared_statement = session
+        .prepare("INSERT INTO t (a) VALUES (?)")
+        .await
+        .unwrap();
+    assert_eq!(
+        prepared_statement.get_table_spec().unwrap().ks_name(),
+        session_ks
+    );
+    prepared_statement.set_routing_keyspace(Some(ks.clone()));
+    assert_eq!(prepared_statement.get_routing_keyspace(), Some(ks.as_str()));
+
+    let cluster_state = session.get_cluster_state();
+    for size in 1..50usize {
+        let key = vec!['a'; size].into_iter().collect::<String>();
+
+        // The statement is routed to the only replica of the key in `ks`.
+        let dry_run = session.dry_run(&prepared_statement, (&key,)).unwrap();
+        let replicas = cluster_state
+            .get_token_endpoints(&ks, "t", dry_run.token.unwrap())
+            .into_iter()
+            .map(|(node, _shard)| node.host_id)
+            .collect::<Vec<_>>();
+        assert_eq!(replicas, vec![dry_run.coordinator.unwrap().host_id]);
+    }
+
+    for keyspace in [&ks, &session_ks] {
+        session
+            .ddl(format!("DROP KEYSPACE {keyspace}"))
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_token_awareness_with_prepare_in_keyspace() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    let mut create_ks = format!(
+        "CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}"
+    );
+    if scylla_supports_tablets(&session).await {
+        create_ks += " AND TABLETS = {'enabled': false}"
+    }
+
+    session.ddl(create_ks).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a text primary key)"
+        ))
+        .await
+        .unwrap();
+
+    // The session uses no keyspace, so the unqualified table name can only be resolved
+    // with the keyspace given to `prepare_in_keyspace`.
+    assert_eq!(session.get_keyspace(), None);
+    let prepared_statement = session
+        .prepare_in_keyspace("INSERT INTO t (a) VALUES (?)", ks.clone())
+        .await
+        .unwrap();
+    assert_eq!(prepared_statement.get_routing_keyspace(), Some(ks.as_str()));
+
+    let cluster_state = session.get_cluster_state();
+    let keys = (1..10usize)
+        .map(|size| vec!['a'; size].into_iter().collect::<String>())
+        .collect::<Vec<_>>();
+    for key in &keys {
+        // The statement is routed to the replica of the key in the given keyspace.
+        let dry_run = session.dry_run(&prepared_statement, (key,)).unwrap();
+        let replicas = cluster_state
+            .get_token_endpoints(&ks, "t", dry_run.token.unwrap())
+            .into_iter()
+            .map(|(node, _shard)| node.host_id)
+            .collect::<Vec<_>>();
+        assert_eq!(replicas, vec![dry_run.coordinator.unwrap().host_id]);
+
+        session
+            .execute_unpaged(&prepared_statement, (key,))
+            .await
+            .unwrap();
+    }
+
+    // The rows were written to the table in the given keyspace.
+    let mut written = session
+        .query_unpaged(format!("SELECT a FROM {ks}.t"), ())
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .rows::<(String,)>()
+        .unwrap()
+        .map(|row| row.unwrap().0)
+        .collect::<Vec<_>>();
+    written.sort();
+    assert_eq!(written, keys);
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
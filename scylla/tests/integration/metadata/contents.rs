@@ -433,6 +433,34 @@ async fn test_table_partitioner_in_metadata() {
     session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
 }
 
+#[tokio::test]
+async fn test_keyspace_partitioner_in_metadata() {
+    setup_tracing();
+
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session
+        .ddl(format!(
+            "CREATE KEYSPACE {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}"
+        ))
+        .await
+        .unwrap();
+
+    let cluster_state = session.get_cluster_state();
+    for keyspace_name in ["system", ks.as_str()] {
+        let partitioner = cluster_state
+            .get_keyspace(keyspace_name)
+            .unwrap()
+            .partitioner
+            .as_deref()
+            .unwrap();
+        assert_eq!(partitioner, "org.apache.cassandra.dht.Murmur3Partitioner");
+    }
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
 #[tokio::test]
 #[cfg_attr(cassandra_tests, ignore)]
 async fn test_views_in_schema_info() {
@@ -1,2 +1,3 @@
 mod configuration;
 mod contents;
+mod standby_control_connection;
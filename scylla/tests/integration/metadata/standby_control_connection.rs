@@ -0,0 +1,121 @@
+//! Tests that a warm standby control connection takes over metadata fetching
+//! when the control connection's node dies.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use scylla::client::session::Session;
+use scylla::client::session_builder::SessionBuilder;
+use scylla_proxy::{
+    Condition, ProxyError, Reaction as _, RequestFrame, RequestOpcode, RequestReaction,
+    RequestRule, ShardAwareness, WorkerError,
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+fn received_any(rx: &mut UnboundedReceiver<(RequestFrame, Option<u16>)>) -> bool {
+    let mut received = false;
+    while rx.try_recv().is_ok() {
+        received = true;
+    }
+    received
+}
+
+#[tokio::test]
+async fn standby_control_connection_is_promoted_when_control_node_dies() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            // Feeds back REGISTER requests, sent by the control connection and by the standby.
+            let mut register_rxs = Vec::new();
+            for running_node in running_proxy.running_nodes.iter_mut() {
+                let (tx, rx) = mpsc::unbounded_channel();
+                running_node.change_request_rules(Some(vec![RequestRule(
+                    Condition::RequestOpcode(RequestOpcode::Register),
+                    RequestReaction::noop().with_feedback_when_performed(tx),
+                )]));
+                register_rxs.push(rx);
+            }
+
+            let session: Session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .standby_control_connection(true)
+                .build()
+                .await
+                .unwrap();
+
+            // The standby is opened in the background, so it may register a bit later.
+            let mut registered_nodes = BTreeSet::new();
+            tokio::time::timeout(Duration::from_secs(5), async {
+                while registered_nodes.len() < 2 {
+                    registered_nodes.extend(
+                        register_rxs
+                            .iter_mut()
+                            .enumerate()
+                            .filter_map(|(i, rx)| received_any(rx).then_some(i)),
+                    );
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            })
+            .await
+            .expect("Expected the control connection and the standby to register for events");
+            assert_eq!(registered_nodes.len(), 2);
+
+            // Feeds back metadata queries, which are sent on the control connection only.
+            let mut metadata_query_rxs = Vec::new();
+            for running_node in running_proxy.running_nodes.iter_mut() {
+                let (tx, rx) = mpsc::unbounded_channel();
+                running_node.change_request_rules(Some(vec![RequestRule(
+                    Condition::ConnectionRegisteredAnyEvent
+                        .and(Condition::RequestOpcode(RequestOpcode::Query)),
+                    RequestReaction::noop().with_feedback_when_performed(tx),
+                )]));
+                metadata_query_rxs.push(rx);
+            }
+            session.refresh_metadata().await.unwrap();
+            let control_nodes = metadata_query_rxs
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(i, rx)| received_any(rx).then_some(i))
+                .collect::<Vec<_>>();
+            let [control_node] = control_nodes[..] else {
+                panic!("Expected metadata to be fetched from one node, got {control_nodes:?}");
+            };
+            let standby_node = registered_nodes
+                .into_iter()
+                .find(|&i| i != control_node)
+                .unwrap();
+
+            // Kill the control node: every request it receives drops the connection.
+            running_proxy.running_nodes[control_node].change_request_rules(Some(vec![
+                RequestRule(Condition::True, RequestReaction::drop_connection()),
+            ]));
+
+            // The standby takes over, so the refresh completes without delay.
+            tokio::time::timeout(Duration::from_secs(2), session.refresh_metadata())
+                .await
+                .expect("Metadata refresh stalled after the control node died")
+                .unwrap();
+            assert!(
+                received_any(&mut metadata_query_rxs[standby_node]),
+                "Expected metadata to be fetched on the promoted standby"
+            );
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -0,0 +1,103 @@
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+use assert_matches::assert_matches;
+use scylla::client::AdaptiveConcurrency;
+use scylla::client::session_builder::SessionBuilder;
+use scylla::errors::{DbError, ExecutionError, RequestAttemptError};
+use scylla::policies::retry::FallthroughRetryPolicy;
+use scylla::statement::Statement;
+use scylla_proxy::{
+    Condition, ProxyError, RequestOpcode, RequestReaction, RequestRule, ShardAwareness, WorkerError,
+};
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+const OVERLOADED_QUERY: &str = "SELECT host_id FROM system.local WHERE key = 'overloaded'";
+
+#[tokio::test]
+async fn concurrency_limit_adapts_to_overload() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .adaptive_concurrency(
+                    AdaptiveConcurrency::new(
+                        NonZeroUsize::new(2).unwrap(),
+                        NonZeroUsize::new(16).unwrap(),
+                        0.5,
+                    )
+                    .unwrap(),
+                )
+                .build()
+                .await
+                .unwrap();
+            let initial_limit = session.concurrency_limit().unwrap();
+            assert_eq!(initial_limit, 16);
+
+            for node in running_proxy.running_nodes.iter_mut() {
+                node.change_request_rules(Some(vec![RequestRule(
+                    Condition::RequestOpcode(RequestOpcode::Query).and(
+                        Condition::BodyContainsCaseSensitive(Box::new(*b"overloaded")),
+                    ),
+                    RequestReaction::forge_with_error(DbError::Overloaded),
+                )]));
+            }
+
+            let mut overloaded = Statement::new(OVERLOADED_QUERY);
+            overloaded.set_retry_policy(Some(Arc::new(FallthroughRetryPolicy)));
+
+            let query_overloaded = || async {
+                let err = session
+                    .query_unpaged(overloaded.clone(), ())
+                    .await
+                    .unwrap_err();
+                assert_matches!(
+                    err,
+                    ExecutionError::LastAttemptError(RequestAttemptError::DbError(
+                        DbError::Overloaded,
+                        _
+                    ))
+                );
+            };
+
+            // An overload error lowers the limit.
+            query_overloaded().await;
+            assert_eq!(session.concurrency_limit(), Some(8));
+
+            // Overloads of as many attempts as the previous limit don't lower it again,
+            // as they could have been sent before it was lowered.
+            for _ in 0..16 {
+                query_overloaded().await;
+            }
+            assert_eq!(session.concurrency_limit(), Some(8));
+
+            // The next one does.
+            query_overloaded().await;
+            assert_eq!(session.concurrency_limit(), Some(4));
+
+            // Successful requests raise the limit again.
+            running_proxy.turn_off_rules();
+            for _ in 0..4 {
+                session
+                    .query_unpaged("SELECT host_id FROM system.local", ())
+                    .await
+                    .unwrap();
+            }
+            assert_eq!(session.concurrency_limit(), Some(5));
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use crate::utils::{create_new_session_builder, setup_tracing};
+
+#[tokio::test]
+async fn test_measure_clock_skew() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+
+    let skew = session.measure_clock_skew().await.unwrap();
+
+    // The test cluster runs on the same machine, so their clocks should agree
+    // up to the accuracy of the measurement.
+    let accuracy = skew.round_trip_time() / 2 + Duration::from_millis(1);
+    assert!(
+        skew.offset() <= accuracy + Duration::from_secs(1),
+        "Unexpected clock skew: {skew:?}"
+    );
+}
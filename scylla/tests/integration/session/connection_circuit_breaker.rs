@@ -0,0 +1,147 @@
+use std::net::SocketAddr;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
+
+use scylla::client::execution_profile::ExecutionProfile;
+use scylla::client::session::Session;
+use scylla::client::session_builder::SessionBuilder;
+use scylla::client::{ErrorRateCircuitBreaker, PoolSize};
+use scylla::errors::{DbError, ExecutionError, RequestAttemptError};
+use scylla::policies::load_balancing::{NodeIdentifier, SingleTargetLoadBalancingPolicy};
+use scylla::statement::Consistency;
+use scylla::statement::Statement;
+use scylla_proxy::{
+    Condition, ProxyError, RequestOpcode, RequestReaction, RequestRule, ShardAwareness, WorkerError,
+};
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+const WINDOW: u32 = 4;
+const FAILING_QUERY: &str = "SELECT host_id FROM system.local WHERE key = 'circuit_breaker_test'";
+// Not enough replicas responded, so the default retry policy does not retry.
+const TIMEOUT_ERROR: DbError = DbError::ReadTimeout {
+    consistency: Consistency::One,
+    received: 0,
+    required: 1,
+    data_present: false,
+};
+
+fn local_address_of_connection_to(
+    session: &Session,
+    node_address: SocketAddr,
+) -> Option<SocketAddr> {
+    session
+        .connection_stats()
+        .into_iter()
+        .find(|stats| stats.node_address == node_address)
+        .map(|stats| stats.local_address)
+}
+
+#[tokio::test]
+async fn connection_exceeding_error_rate_is_replaced() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session: Session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                // A single connection per node makes it easy to tell whether it was replaced.
+                .pool_size(PoolSize::PerHost(1.try_into().unwrap()))
+                .connection_circuit_breaker(
+                    ErrorRateCircuitBreaker::new(0.5, NonZeroU32::new(WINDOW).unwrap()).unwrap(),
+                )
+                .build()
+                .await
+                .unwrap();
+
+            let cluster_state = session.get_cluster_state();
+            let nodes = cluster_state.get_nodes_info();
+            let faulty_node = nodes[0].clone();
+            let faulty_node_address =
+                SocketAddr::new(faulty_node.address.ip(), faulty_node.address.port());
+            let initial_local_addresses = nodes
+                .iter()
+                .map(|node| {
+                    let address = SocketAddr::new(node.address.ip(), node.address.port());
+                    (
+                        address,
+                        local_address_of_connection_to(&session, address).unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            for node in running_proxy.running_nodes.iter_mut() {
+                node.change_request_rules(Some(vec![RequestRule(
+                    Condition::not(Condition::ConnectionRegisteredAnyEvent)
+                        .and(Condition::RequestOpcode(RequestOpcode::Query))
+                        .and(Condition::BodyContainsCaseSensitive(Box::new(
+                            *b"circuit_breaker_test",
+                        ))),
+                    RequestReaction::forge_with_error(TIMEOUT_ERROR),
+                )]));
+            }
+
+            // Only the connection to the faulty node receives the failing requests.
+            let mut statement = Statement::new(FAILING_QUERY);
+            let profile = ExecutionProfile::builder()
+                .load_balancing_policy(SingleTargetLoadBalancingPolicy::new(
+                    NodeIdentifier::Node(faulty_node),
+                    None,
+                ))
+                .build();
+            statement.set_execution_profile_handle(Some(profile.into_handle()));
+
+            for _ in 0..WINDOW {
+                let err = session
+                    .query_unpaged(statement.clone(), ())
+                    .await
+                    .unwrap_err();
+                assert!(matches!(
+                    err,
+                    ExecutionError::LastAttemptError(RequestAttemptError::DbError(
+                        DbError::ReadTimeout { .. },
+                        _
+                    ))
+                ));
+            }
+
+            // The pool should open a new connection in place of the closed one.
+            tokio::time::timeout(Duration::from_secs(10), async {
+                loop {
+                    let local_address =
+                        local_address_of_connection_to(&session, faulty_node_address);
+                    if local_address.is_some()
+                        && local_address != Some(initial_local_addresses[0].1)
+                    {
+                        break;
+                    }
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                }
+            })
+            .await
+            .expect("Connection to the faulty node was not replaced");
+
+            // Connections to the other nodes are left intact.
+            for (node_address, local_address) in &initial_local_addresses[1..] {
+                assert_eq!(
+                    local_address_of_connection_to(&session, *node_address),
+                    Some(*local_address)
+                );
+            }
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
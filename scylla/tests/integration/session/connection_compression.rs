@@ -0,0 +1,26 @@
+use scylla::client::Compression;
+
+use crate::utils::{create_new_session_builder, setup_tracing};
+
+#[tokio::test]
+async fn connection_stats_report_negotiated_compression() {
+    setup_tracing();
+
+    for compression in [None, Some(Compression::Lz4), Some(Compression::Snappy)] {
+        let session = create_new_session_builder()
+            .compression(compression)
+            .build()
+            .await
+            .unwrap();
+
+        let stats = session.connection_stats();
+        assert!(!stats.is_empty());
+        for stats in stats {
+            assert_eq!(
+                stats.compression, compression,
+                "connection to {} reports unexpected compression",
+                stats.node_address
+            );
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Tests that the pool does not open more connections to a node at the same time
+//! than allowed by [SessionBuilder::max_concurrent_connection_opens].
+
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use scylla::client::PoolSize;
+use scylla::client::session_builder::SessionBuilder;
+use scylla_proxy::{
+    Condition, ProxyError, Reaction as _, RequestOpcode, RequestReaction, RequestRule,
+    ShardAwareness, WorkerError,
+};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+const CONNECTIONS_PER_NODE: usize = 6;
+const MAX_CONCURRENT_OPENS: usize = 2;
+
+/// Opening each connection takes at least that long, because its STARTUP is delayed.
+const STARTUP_DELAY: Duration = Duration::from_millis(300);
+
+#[tokio::test]
+async fn concurrent_connection_opens_stay_within_limit() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            // Records when each node receives a STARTUP, i.e. when a connection starts being opened.
+            let mut startups = Vec::new();
+            let mut recorders = Vec::new();
+            for running_node in running_proxy.running_nodes.iter_mut() {
+                let (tx, mut rx) = mpsc::unbounded_channel();
+                running_node.change_request_rules(Some(vec![RequestRule(
+                    Condition::RequestOpcode(RequestOpcode::Startup),
+                    RequestReaction::delay(STARTUP_DELAY).with_feedback_when_performed(tx),
+                )]));
+                let received_at = Arc::new(Mutex::new(Vec::new()));
+                startups.push(Arc::clone(&received_at));
+                recorders.push(tokio::spawn(async move {
+                    while rx.recv().await.is_some() {
+                        received_at.lock().unwrap().push(Instant::now());
+                    }
+                }));
+            }
+
+            let session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .pool_size(PoolSize::PerHost(
+                    NonZeroUsize::new(CONNECTIONS_PER_NODE).unwrap(),
+                ))
+                .max_concurrent_connection_opens(NonZeroUsize::new(MAX_CONCURRENT_OPENS).unwrap())
+                .build()
+                .await
+                .unwrap();
+
+            tokio::time::timeout(Duration::from_secs(30), async {
+                while session.pool_fill_progress() < 1.0 {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                }
+            })
+            .await
+            .expect("Pools did not fill");
+
+            running_proxy.turn_off_rules();
+            recorders.iter().for_each(|recorder| recorder.abort());
+
+            for received_at in startups {
+                let mut received_at = received_at.lock().unwrap().clone();
+                received_at.sort();
+                assert!(received_at.len() >= CONNECTIONS_PER_NODE);
+
+                // If more connections were being opened at the same time than allowed,
+                // some STARTUP would arrive before the delayed one of an earlier connection
+                // was answered. Some slack is left for the timer resolution.
+                for window in received_at.windows(MAX_CONCURRENT_OPENS + 1) {
+                    let elapsed = window[MAX_CONCURRENT_OPENS] - window[0];
+                    assert!(
+                        elapsed >= STARTUP_DELAY - Duration::from_millis(20),
+                        "{} connections were opened within {elapsed:?}",
+                        MAX_CONCURRENT_OPENS + 1
+                    );
+                }
+            }
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use scylla::client::session_builder::SessionBuilder;
+use scylla::statement::unprepared::Statement;
+use scylla_proxy::{
+    Condition, ProxyError, Reaction, RequestOpcode, RequestReaction, RequestRule, ShardAwareness,
+    WorkerError,
+};
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+const STARTUP_DELAY: Duration = Duration::from_millis(200);
+
+#[tokio::test]
+async fn attempt_info_reports_connection_wait_time() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .build()
+                .await
+                .unwrap();
+
+            // Connections from the pool are ready, so there is no wait for them.
+            let result = session
+                .query_unpaged("SELECT host_id FROM system.local", ())
+                .await
+                .unwrap();
+            assert!(result.attempt_info().connection_wait_time < STARTUP_DELAY);
+
+            // Delay the handshake of new connections, so that a request which forces
+            // a fresh connection has to wait for it to be opened.
+            for node in running_proxy.running_nodes.iter_mut() {
+                node.change_request_rules(Some(vec![RequestRule(
+                    Condition::RequestOpcode(RequestOpcode::Startup),
+                    RequestReaction::delay(STARTUP_DELAY),
+                )]));
+            }
+
+            let mut statement = Statement::new("SELECT host_id FROM system.local");
+            statement.set_force_fresh_connection(true);
+            let start = Instant::now();
+            let result = session.query_unpaged(statement, ()).await.unwrap();
+            let elapsed = start.elapsed();
+
+            // The wait for a connection is a part of the latency of the request.
+            let connection_wait_time = result.attempt_info().connection_wait_time;
+            assert!(connection_wait_time >= STARTUP_DELAY);
+            assert!(connection_wait_time <= elapsed);
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bytes::Bytes;
+
+use scylla::client::session_builder::SessionBuilder;
+use scylla::statement::Statement;
+use scylla::statement::batch::Batch;
+use scylla_cql::frame::{flag, types};
+use scylla_proxy::{
+    Condition, ProxyError, Reaction as _, RequestFrame, RequestOpcode, RequestReaction,
+    RequestRule, ShardAwareness, WorkerError,
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+use crate::utils::{PerformDDL, setup_tracing, test_with_3_node_cluster, unique_keyspace_name};
+
+fn payload() -> HashMap<String, Bytes> {
+    HashMap::from([(
+        "request-id".to_owned(),
+        Bytes::from_static(b"checkout-service/request-42"),
+    )])
+}
+
+/// Returns the custom payload of each received request.
+fn received_payloads(
+    rx: &mut UnboundedReceiver<(RequestFrame, Option<u16>)>,
+) -> Vec<Option<HashMap<String, Bytes>>> {
+    std::iter::from_fn(|| rx.try_recv().ok())
+        .map(|(frame, _)| {
+            if frame.params.flags & flag::CUSTOM_PAYLOAD == 0 {
+                return None;
+            }
+            let payload = types::read_bytes_map(&mut &*frame.body).unwrap();
+            Some(payload)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn custom_payload_is_sent_with_requests() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .build()
+                .await
+                .unwrap();
+
+            let ks = unique_keyspace_name();
+            session
+                .ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 3}}"))
+                .await
+                .unwrap();
+            session
+                .ddl(format!("CREATE TABLE IF NOT EXISTS {ks}.t (a int primary key)"))
+                .await
+                .unwrap();
+
+            let mut rxs = Vec::new();
+            for running_node in running_proxy.running_nodes.iter_mut() {
+                let (tx, rx) = mpsc::unbounded_channel();
+                running_node.change_request_rules(Some(vec![RequestRule(
+                    Condition::RequestOpcode(RequestOpcode::Query)
+                        .or(Condition::RequestOpcode(RequestOpcode::Execute))
+                        .or(Condition::RequestOpcode(RequestOpcode::Batch))
+                        .and(Condition::not(Condition::ConnectionRegisteredAnyEvent)),
+                    RequestReaction::noop().with_feedback_when_performed(tx),
+                )]));
+                rxs.push(rx);
+            }
+            let mut received = || {
+                rxs.iter_mut()
+                    .flat_map(received_payloads)
+                    .collect::<Vec<_>>()
+            };
+
+            let mut statement = Statement::new(format!("SELECT a FROM {ks}.t"));
+            session.query_unpaged(statement.clone(), ()).await.unwrap();
+            assert_eq!(received(), [None]);
+
+            statement.set_custom_payload(Some(payload()));
+            assert_eq!(statement.get_custom_payload(), Some(&payload()));
+            session.query_unpaged(statement.clone(), ()).await.unwrap();
+            assert_eq!(received(), [Some(payload())]);
+
+            let mut prepared = session.prepare(statement).await.unwrap();
+            session.execute_unpaged(&prepared, ()).await.unwrap();
+            assert_eq!(received(), [Some(payload())]);
+
+            prepared.set_custom_payload(None);
+            session.execute_unpaged(&prepared, ()).await.unwrap();
+            assert_eq!(received(), [None]);
+
+            let mut batch = Batch::default();
+            batch.append_statement(format!("INSERT INTO {ks}.t (a) VALUES (1)").as_str());
+            batch.set_custom_payload(Some(payload()));
+            session.batch(&batch, ((),)).await.unwrap();
+            assert_eq!(received(), [Some(payload())]);
+
+            running_proxy.turn_off_rules();
+            session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -4,6 +4,7 @@ use crate::utils::{
 
 use scylla::errors::OperationType;
 use scylla::errors::{DbError, ExecutionError, RequestAttemptError};
+use scylla::statement::Statement;
 
 // Test that some Database Errors are parsed correctly
 #[tokio::test]
@@ -64,6 +65,33 @@ async fn test_db_errors() {
     session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
 }
 
+#[tokio::test]
+async fn test_ignore_already_exists() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+
+    let mut create_table = Statement::new(format!("CREATE TABLE {ks}.tab (a text primary key)"));
+    create_table.set_ignore_already_exists(true);
+
+    session.ddl(create_table.clone()).await.unwrap();
+    // The table already exists, but the error is treated as success.
+    session.ddl(create_table.clone()).await.unwrap();
+
+    // Without the option, the error is returned as usual.
+    create_table.set_ignore_already_exists(false);
+    assert!(matches!(
+        session.ddl(create_table).await,
+        Err(ExecutionError::LastAttemptError(
+            RequestAttemptError::DbError(DbError::AlreadyExists { .. }, _)
+        ))
+    ));
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
 #[tokio::test]
 async fn test_rate_limit_exceeded_exception() {
     let session = create_new_session_builder().build().await.unwrap();
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use scylla::client::session_builder::SessionBuilder;
+use scylla_proxy::{
+    Condition, ProxyError, Reaction, RequestOpcode, RequestReaction, RequestRule, ShardAwareness,
+    WorkerError,
+};
+use tokio::sync::mpsc;
+
+use crate::utils::{PerformDDL, setup_tracing, test_with_3_node_cluster, unique_keyspace_name};
+
+#[tokio::test]
+async fn dry_run_does_not_send_requests() {
+    setup_tracing();
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .build()
+                .await
+                .unwrap();
+
+            let ks = unique_keyspace_name();
+            session
+                .ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 3}}"))
+                .await
+                .unwrap();
+            session
+                .ddl(format!("CREATE TABLE IF NOT EXISTS {ks}.t (a int primary key, b text)"))
+                .await
+                .unwrap();
+            let prepared = session
+                .prepare(format!("INSERT INTO {ks}.t (a, b) VALUES (?, ?)"))
+                .await
+                .unwrap();
+
+            // From now on, report every frame sent by the driver, except for
+            // the control connection and keepalives.
+            let (feedback_tx, mut feedback_rx) = mpsc::unbounded_channel();
+            let feedback_rule = RequestRule(
+                Condition::and(
+                    Condition::not(Condition::ConnectionRegisteredAnyEvent),
+                    Condition::not(Condition::RequestOpcode(RequestOpcode::Options)),
+                ),
+                RequestReaction::noop().with_feedback_when_performed(feedback_tx),
+            );
+            for node in running_proxy.running_nodes.iter_mut() {
+                node.change_request_rules(Some(vec![feedback_rule.clone()]));
+            }
+
+            let values = (42_i32, "dry");
+            let info = session.dry_run(&prepared, values).unwrap();
+            assert_eq!(info.token, prepared.calculate_token(&values).unwrap());
+            assert!(info.token.is_some());
+            assert!(info.serialized_values_size > 0);
+            let coordinator = info.coordinator.unwrap();
+            assert!(
+                session
+                    .get_cluster_state()
+                    .get_nodes_info()
+                    .iter()
+                    .any(|node| Arc::ptr_eq(node, &coordinator))
+            );
+
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            assert!(feedback_rx.try_recv().is_err(), "dry run sent a frame");
+
+            // Invalid values are rejected by the dry run as well.
+            session.dry_run(&prepared, (42_i32,)).unwrap_err();
+
+            running_proxy.turn_off_rules();
+            session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+
+            running_proxy
+        },
+    )
+    .await;
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
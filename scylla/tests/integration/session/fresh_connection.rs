@@ -0,0 +1,55 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use scylla::observability::metrics_sink::{ConnectionEvent, ConnectionEventKind, MetricsSink};
+use scylla::statement::Statement;
+
+use crate::utils::{create_new_session_builder, setup_tracing};
+
+#[derive(Default)]
+struct RecordingSink {
+    connection_events: Mutex<Vec<ConnectionEvent>>,
+}
+
+impl MetricsSink for RecordingSink {
+    fn on_connection_event(&self, event: &ConnectionEvent) {
+        self.connection_events.lock().unwrap().push(event.clone());
+    }
+}
+
+#[tokio::test]
+async fn force_fresh_connection_opens_and_closes_a_connection() {
+    setup_tracing();
+    let sink = Arc::new(RecordingSink::default());
+    let session = create_new_session_builder()
+        .metrics_sink(sink.clone())
+        .build()
+        .await
+        .unwrap();
+
+    // Wait for the pools to fill, so that they don't open connections during the test.
+    tokio::time::timeout(Duration::from_secs(30), async {
+        while session.pool_fill_progress() < 1.0 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("Pools did not fill");
+    sink.connection_events.lock().unwrap().clear();
+
+    let mut statement = Statement::new("SELECT host_id FROM system.local WHERE key = 'local'");
+    statement.set_force_fresh_connection(true);
+    let result = session.query_unpaged(statement, ()).await.unwrap();
+    let coordinator_address = result.request_coordinator().node().address;
+
+    let connection_events = sink.connection_events.lock().unwrap();
+    let kinds = connection_events
+        .iter()
+        .filter(|event| event.node_address == coordinator_address)
+        .map(|event| event.kind)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [ConnectionEventKind::Opened, ConnectionEventKind::Closed]
+    );
+}
@@ -0,0 +1,36 @@
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use scylla::client::PoolSize;
+
+use crate::utils::{create_new_session_builder, setup_tracing};
+
+#[tokio::test]
+async fn total_connections_never_exceed_max_total_connections() {
+    setup_tracing();
+    const MAX_TOTAL_CONNECTIONS: usize = 2;
+
+    let session = create_new_session_builder()
+        .pool_size(PoolSize::PerShard(NonZeroUsize::new(4).unwrap()))
+        .max_total_connections(NonZeroUsize::new(MAX_TOTAL_CONNECTIONS).unwrap())
+        .build()
+        .await
+        .unwrap();
+
+    for _ in 0..10 {
+        session
+            .query_unpaged("SELECT host_id FROM system.local WHERE key='local'", &[])
+            .await
+            .unwrap();
+
+        let connections = session.server_side_connections().await.unwrap();
+        assert!(
+            connections.len() <= MAX_TOTAL_CONNECTIONS,
+            "session keeps {} connections, but the limit is {}",
+            connections.len(),
+            MAX_TOTAL_CONNECTIONS
+        );
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
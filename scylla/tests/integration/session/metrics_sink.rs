@@ -0,0 +1,104 @@
+use std::sync::{Arc, Mutex};
+
+use futures::TryStreamExt as _;
+use scylla::observability::metrics_sink::{MetricsSink, RequestCompleteEvent};
+use scylla::statement::Statement;
+
+use crate::utils::{
+    PerformDDL as _, create_new_session_builder, setup_tracing, unique_keyspace_name,
+};
+
+#[derive(Default)]
+struct RecordingSink {
+    request_completions: Mutex<Vec<RequestCompleteEvent>>,
+}
+
+impl MetricsSink for RecordingSink {
+    fn on_request_complete(&self, event: &RequestCompleteEvent) {
+        self.request_completions.lock().unwrap().push(event.clone());
+    }
+}
+
+#[tokio::test]
+async fn metrics_sink_receives_request_complete_events() {
+    setup_tracing();
+    let sink = Arc::new(RecordingSink::default());
+    let session = create_new_session_builder()
+        .metrics_sink(sink.clone())
+        .build()
+        .await
+        .unwrap();
+
+    sink.request_completions.lock().unwrap().clear();
+
+    const REQUESTS: usize = 3;
+    for _ in 0..REQUESTS {
+        session
+            .query_unpaged("SELECT host_id FROM system.local", ())
+            .await
+            .unwrap();
+    }
+
+    let request_completions = sink.request_completions.lock().unwrap();
+    assert!(request_completions.len() >= REQUESTS);
+    let cluster_state = session.get_cluster_state();
+    for event in request_completions.iter() {
+        assert!(event.succeeded);
+        assert!(
+            cluster_state
+                .get_nodes_info()
+                .iter()
+                .any(|node| node.address == event.node_address)
+        );
+    }
+}
+
+#[tokio::test]
+async fn metrics_sink_receives_request_complete_events_of_pages() {
+    setup_tracing();
+    let sink = Arc::new(RecordingSink::default());
+    let session = create_new_session_builder()
+        .metrics_sink(sink.clone())
+        .build()
+        .await
+        .unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b int, primary key (a, b))"
+        ))
+        .await
+        .unwrap();
+    const ROWS: i32 = 3;
+    for b in 0..ROWS {
+        session
+            .query_unpaged(format!("INSERT INTO {ks}.t (a, b) VALUES (0, ?)"), (b,))
+            .await
+            .unwrap();
+    }
+
+    sink.request_completions.lock().unwrap().clear();
+
+    // Every page is reported as a separate request.
+    let statement = Statement::new(format!("SELECT b FROM {ks}.t WHERE a = 0")).with_page_size(1);
+    let rows = session
+        .query_iter(statement, ())
+        .await
+        .unwrap()
+        .rows_stream::<(i32,)>()
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), ROWS as usize);
+
+    {
+        let request_completions = sink.request_completions.lock().unwrap();
+        assert!(request_completions.len() >= ROWS as usize);
+        assert!(request_completions.iter().all(|event| event.succeeded));
+    }
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
@@ -1,12 +1,38 @@
+mod adaptive_concurrency;
 mod caching_session;
+mod clock_skew;
 mod cluster_reachability;
+mod connection_circuit_breaker;
+mod connection_compression;
+mod connection_open_concurrency;
+mod connection_wait_time;
+mod custom_payload;
 mod db_errors;
+mod dry_run;
+mod fresh_connection;
 mod history;
 mod internal_requests;
+mod max_total_connections;
+mod metrics_sink;
 mod new_session;
+mod ordered_pipeline;
 mod pager;
+#[cfg(feature = "metrics")]
+mod per_shard_connections;
+mod pool_autoscaling;
+mod pool_fill_progress;
+mod preloaded_prepared;
+mod prepare_all;
+mod recent_warnings;
+mod repreparation;
+mod request_sizes;
 mod retries;
 mod schema_agreement;
+mod schema_change_stream;
 mod self_identity;
+mod server_options;
+mod server_side_connections;
+mod shard_aware_port_range;
 mod tracing;
+mod unknown_result_kinds;
 mod use_keyspace;
@@ -0,0 +1,88 @@
+//! Tests that requests executed with an ordered pipeline are sent
+//! with increasing stream ids.
+
+use std::sync::Arc;
+
+use futures::future::try_join_all;
+use scylla::client::session_builder::SessionBuilder;
+use scylla_proxy::{
+    Condition, ProxyError, Reaction as _, RequestFrame, RequestOpcode, RequestReaction,
+    RequestRule, ShardAwareness, WorkerError,
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+fn received_stream_ids(rx: &mut UnboundedReceiver<(RequestFrame, Option<u16>)>) -> Vec<i16> {
+    std::iter::from_fn(|| rx.try_recv().ok())
+        .map(|(frame, _shard)| frame.params.stream)
+        .collect()
+}
+
+#[tokio::test]
+async fn ordered_pipeline_sends_requests_with_increasing_stream_ids() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .build()
+                .await
+                .unwrap();
+
+            let select = session
+                .prepare("SELECT host_id FROM system.local WHERE key = ?")
+                .await
+                .unwrap();
+
+            let mut execute_rxs = Vec::new();
+            for running_node in running_proxy.running_nodes.iter_mut() {
+                let (tx, rx) = mpsc::unbounded_channel();
+                running_node.change_request_rules(Some(vec![RequestRule(
+                    Condition::RequestOpcode(RequestOpcode::Execute),
+                    RequestReaction::noop().with_feedback_when_performed(tx),
+                )]));
+                execute_rxs.push(rx);
+            }
+
+            let cluster_state = session.get_cluster_state();
+            let pipeline = session
+                .ordered_pipeline(&cluster_state.get_nodes_info()[0])
+                .unwrap();
+
+            // Without the pipeline, each of these requests would reuse the stream id
+            // freed by the previous one.
+            for _ in 0..5 {
+                pipeline.execute_unpaged(&select, ("local",)).await.unwrap();
+            }
+            try_join_all((0..20).map(|_| pipeline.execute_unpaged(&select, ("local",))))
+                .await
+                .unwrap();
+
+            let sent_stream_ids = execute_rxs
+                .iter_mut()
+                .flat_map(received_stream_ids)
+                .collect::<Vec<_>>();
+            assert_eq!(sent_stream_ids.len(), 25);
+            // The proxy receives the frames in the order they were sent.
+            assert!(
+                sent_stream_ids.is_sorted_by(|earlier, later| earlier < later),
+                "Stream ids not increasing in send order: {sent_stream_ids:?}"
+            );
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
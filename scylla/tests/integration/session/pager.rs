@@ -1,4 +1,5 @@
 use std::{
+    num::NonZeroUsize,
     sync::{
         Arc,
         atomic::{AtomicBool, Ordering},
@@ -11,6 +12,7 @@ use futures::{StreamExt as _, TryStreamExt as _};
 use scylla::{
     client::execution_profile::ExecutionProfile,
     policies::retry::{RequestInfo, RetryDecision, RetryPolicy, RetrySession},
+    response::PagingState,
     statement::Statement,
     value::Row,
 };
@@ -428,3 +430,382 @@ async fn test_pager_timeouts() {
         Err(err) => panic!("{}", err),
     }
 }
+
+#[tokio::test]
+async fn test_pager_deadline() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        scylla_proxy::ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            /* Prepare phase */
+            let ks = unique_keyspace_name();
+
+            let session: Session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .build()
+                .await
+                .unwrap();
+
+            session
+                .ddl(format!(
+                "CREATE KEYSPACE IF NOT EXISTS {ks} WITH
+                    REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}"
+            ))
+                .await
+                .unwrap();
+
+            session
+                .ddl(format!(
+                    "CREATE TABLE IF NOT EXISTS {ks}.t (a int PRIMARY KEY)"
+                ))
+                .await
+                .unwrap();
+
+            let rows_count = 20;
+            for i in 0..rows_count {
+                session
+                    .query_unpaged(format!("INSERT INTO {ks}.t (a) VALUES (?)"), (i,))
+                    .await
+                    .unwrap();
+            }
+
+            let mut prepared = session
+                .prepare(format!("SELECT a FROM {ks}.t"))
+                .await
+                .unwrap();
+            // Important to have multiple pages.
+            prepared.set_page_size(1);
+
+            /* Test phase */
+
+            // Each page takes 50ms to fetch, well within the per-page timeout,
+            // but fetching all pages would take longer than the deadline.
+            let page_delay = Duration::from_millis(50);
+            let deadline = Duration::from_millis(300);
+            prepared.set_request_timeout(Some(Duration::from_secs(5)));
+            prepared.set_paging_deadline(Some(deadline));
+
+            running_proxy.running_nodes.iter_mut().for_each(|node| {
+                node.change_request_rules(Some(vec![RequestRule(
+                    Condition::RequestOpcode(RequestOpcode::Execute)
+                        .and(Condition::not(Condition::ConnectionRegisteredAnyEvent)),
+                    RequestReaction::delay(page_delay),
+                )]));
+            });
+
+            let mut row_stream = session
+                .execute_iter(prepared, ())
+                .await
+                .unwrap()
+                .rows_stream::<(i32,)>()
+                .unwrap();
+
+            let mut rows_fetched = 0;
+            let row_err = loop {
+                match row_stream.next().await {
+                    Some(Ok(_)) => rows_fetched += 1,
+                    Some(Err(err)) => break err,
+                    None => panic!("Paging should have stopped at the deadline"),
+                }
+            };
+            let NextRowError::NextPageError(NextPageError::RequestFailure(
+                RequestError::RequestTimeout(got_timeout),
+            )) = row_err
+            else {
+                panic!("Expected RequestTimeout error, got: {:?}", row_err);
+            };
+            assert_eq!(got_timeout, deadline);
+            assert!(rows_fetched > 0 && rows_fetched < rows_count);
+
+            /* Teardown */
+            running_proxy.turn_off_rules();
+            session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+#[tokio::test]
+async fn test_pager_last_row_token() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session
+        .ddl(format!(
+            "CREATE KEYSPACE IF NOT EXISTS {ks} WITH
+        REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}"
+        ))
+        .await
+        .unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b int, c text, primary key ((a, b), c))"
+        ))
+        .await
+        .unwrap();
+    session.refresh_metadata().await.unwrap();
+
+    let insert = session
+        .prepare(format!("INSERT INTO {ks}.t (a, b, c) VALUES (?, ?, ?)"))
+        .await
+        .unwrap();
+    for a in 0..10 {
+        session
+            .execute_unpaged(&insert, (a, a * 10, "abc"))
+            .await
+            .unwrap();
+    }
+
+    let mut select = Statement::from(format!("SELECT c, b, a FROM {ks}.t"));
+    select.set_page_size(3);
+    let mut rows_stream = session
+        .query_iter(select, ())
+        .await
+        .unwrap()
+        .rows_stream::<(String, i32, i32)>()
+        .unwrap();
+    assert_eq!(rows_stream.last_row_token(), None);
+
+    let cluster_state = session.get_cluster_state();
+    let mut rows_read = 0;
+    while let Some((_c, b, a)) = rows_stream.try_next().await.unwrap() {
+        let expected_token = cluster_state.compute_token(&ks, "t", &(a, b)).unwrap();
+        assert_eq!(rows_stream.last_row_token(), Some(expected_token));
+        rows_read += 1;
+    }
+    assert_eq!(rows_read, 10);
+
+    // Without all partition key columns, the token can't be computed.
+    let mut rows_stream = session
+        .query_iter(format!("SELECT a, c FROM {ks}.t"), ())
+        .await
+        .unwrap()
+        .rows_stream::<(i32, String)>()
+        .unwrap();
+    rows_stream.try_next().await.unwrap().unwrap();
+    assert_eq!(rows_stream.last_row_token(), None);
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_pager_scan_summary() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session
+        .ddl(format!(
+            "CREATE KEYSPACE IF NOT EXISTS {ks} WITH
+        REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}"
+        ))
+        .await
+        .unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b int, primary key (a, b))"
+        ))
+        .await
+        .unwrap();
+
+    let insert = session
+        .prepare(format!("INSERT INTO {ks}.t (a, b) VALUES (?, ?)"))
+        .await
+        .unwrap();
+    for b in 0..25 {
+        session.execute_unpaged(&insert, (0, b)).await.unwrap();
+    }
+
+    // Each row consists of two ints, each serialized with its 4-byte length.
+    const ROW_BYTES: usize = 2 * (4 + 4);
+
+    let mut select = Statement::from(format!("SELECT a, b FROM {ks}.t WHERE a = 0"));
+    select.set_page_size(10);
+    let summary = session
+        .query_iter(select.clone(), ())
+        .await
+        .unwrap()
+        .scan_summary()
+        .await
+        .unwrap();
+    assert_eq!(summary.rows, 25);
+    assert_eq!(summary.pages, 3);
+    assert_eq!(summary.bytes, 25 * ROW_BYTES);
+
+    // Pages whose rows were already read are included as well.
+    let mut rows_stream = session
+        .query_iter(select, ())
+        .await
+        .unwrap()
+        .rows_stream::<(i32, i32)>()
+        .unwrap();
+    for _ in 0..15 {
+        rows_stream.try_next().await.unwrap().unwrap();
+    }
+    let summary = rows_stream.scan_summary().await.unwrap();
+    assert_eq!(summary.rows, 25);
+    assert_eq!(summary.pages, 3);
+    assert_eq!(summary.bytes, 25 * ROW_BYTES);
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_no_paging() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session
+        .ddl(format!(
+            "CREATE KEYSPACE IF NOT EXISTS {ks} WITH
+        REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}"
+        ))
+        .await
+        .unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b int, primary key (a, b))"
+        ))
+        .await
+        .unwrap();
+
+    let insert = session
+        .prepare(format!("INSERT INTO {ks}.t (a, b) VALUES (?, ?)"))
+        .await
+        .unwrap();
+    for b in 0..25 {
+        session.execute_unpaged(&insert, (0, b)).await.unwrap();
+    }
+
+    // The page size set on the statement is ignored, so all rows come in a single page.
+    let mut select = Statement::from(format!("SELECT a, b FROM {ks}.t WHERE a = 0"));
+    select.set_page_size(10);
+    select.set_no_paging(true);
+    let (result, paging_state_response) = session
+        .query_single_page(select.clone(), (), PagingState::start())
+        .await
+        .unwrap();
+    assert!(paging_state_response.finished());
+    assert_eq!(result.into_rows_result().unwrap().rows_num(), 25);
+
+    let summary = session
+        .query_iter(select.clone(), ())
+        .await
+        .unwrap()
+        .scan_summary()
+        .await
+        .unwrap();
+    assert_eq!(summary.rows, 25);
+    assert_eq!(summary.pages, 1);
+
+    let mut prepared = session.prepare(select).await.unwrap();
+    assert!(prepared.get_no_paging());
+    let (result, paging_state_response) = session
+        .execute_single_page(&prepared, (), PagingState::start())
+        .await
+        .unwrap();
+    assert!(paging_state_response.finished());
+    assert_eq!(result.into_rows_result().unwrap().rows_num(), 25);
+
+    // With paging enabled again, the statement's page size is used.
+    prepared.set_no_paging(false);
+    let summary = session
+        .execute_iter(prepared, ())
+        .await
+        .unwrap()
+        .scan_summary()
+        .await
+        .unwrap();
+    assert_eq!(summary.pages, 3);
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
+/// Tests that page fetches of a pager wait in the session's admission queue,
+/// like other requests.
+#[tokio::test]
+async fn test_pager_page_fetches_are_admitted() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        scylla_proxy::ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session: Arc<Session> = Arc::new(
+                SessionBuilder::new()
+                    .known_node(proxy_uris[0].as_str())
+                    .address_translator(Arc::new(translation_map))
+                    .max_concurrent_requests(NonZeroUsize::new(1).unwrap())
+                    .build()
+                    .await
+                    .unwrap(),
+            );
+
+            let (feedback_tx, mut feedback_rx) = tokio::sync::mpsc::unbounded_channel();
+            running_proxy.running_nodes.iter_mut().for_each(|node| {
+                node.change_request_rules(Some(vec![RequestRule(
+                    Condition::RequestOpcode(RequestOpcode::Query)
+                        .and(Condition::not(Condition::ConnectionRegisteredAnyEvent))
+                        .and(Condition::BodyContainsCaseSensitive(Box::new(
+                            *b"admission_slow",
+                        ))),
+                    RequestReaction::delay(Duration::from_secs(1))
+                        .with_feedback_when_performed(feedback_tx.clone()),
+                )]));
+            });
+
+            // Occupy the only slot of the admission queue.
+            let slow_request = tokio::spawn({
+                let session = Arc::clone(&session);
+                async move {
+                    session
+                        .query_unpaged(
+                            "SELECT host_id FROM system.local WHERE key = 'admission_slow'",
+                            (),
+                        )
+                        .await
+                        .unwrap();
+                }
+            });
+            feedback_rx.recv().await.unwrap();
+
+            // The first page of the pager can't be admitted before the timeout.
+            let timeout = Duration::from_millis(200);
+            let mut statement = Statement::new("SELECT host_id FROM system.local");
+            statement.set_request_timeout(Some(timeout));
+            let pager_err = session.query_iter(statement.clone(), ()).await.unwrap_err();
+            assert_matches!(
+                pager_err,
+                PagerExecutionError::NextPageError(NextPageError::RequestFailure(
+                    RequestError::RequestTimeout(_)
+                ))
+            );
+
+            // Once the slot is released, the pager is admitted.
+            slow_request.await.unwrap();
+            session.query_iter(statement, ()).await.unwrap();
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -0,0 +1,48 @@
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use scylla::client::PoolSize;
+use scylla::routing::Shard;
+
+use crate::utils::{create_new_session_builder, setup_tracing};
+
+const CONNECTIONS_PER_SHARD: usize = 2;
+
+#[tokio::test]
+async fn per_shard_connections_match_shard_aware_pool() {
+    setup_tracing();
+
+    let session = create_new_session_builder()
+        .pool_size(PoolSize::PerShard(
+            NonZeroUsize::new(CONNECTIONS_PER_SHARD).unwrap(),
+        ))
+        .build()
+        .await
+        .unwrap();
+
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while session.pool_fill_progress() < 1.0 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("Pools did not fill");
+
+    let per_shard_connections = session.get_metrics().per_shard_connections();
+    let mut expected_entries = 0;
+    for node in session.get_cluster_state().get_nodes_info() {
+        let nr_shards = node
+            .sharder()
+            .map_or(1, |sharder| sharder.nr_shards.get() as Shard);
+        for shard in 0..nr_shards {
+            assert_eq!(
+                per_shard_connections.get(&(node.host_id, shard)).copied(),
+                Some(CONNECTIONS_PER_SHARD as u64),
+                "Unexpected number of connections to shard {shard} of node {}",
+                node.host_id
+            );
+        }
+        expected_entries += nr_shards as usize;
+    }
+    assert_eq!(per_shard_connections.len(), expected_entries);
+}
@@ -0,0 +1,133 @@
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::join_all;
+use scylla::client::execution_profile::ExecutionProfile;
+use scylla::client::session::Session;
+use scylla::client::session_builder::SessionBuilder;
+use scylla::client::{PoolAutoscaling, PoolSize};
+use scylla::policies::load_balancing::{NodeIdentifier, SingleTargetLoadBalancingPolicy};
+use scylla::statement::Statement;
+use scylla_proxy::{
+    Condition, ProxyError, Reaction, RequestOpcode, RequestReaction, RequestRule, ShardAwareness,
+    WorkerError,
+};
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+const MIN_CONNECTIONS: usize = 1;
+const MAX_CONNECTIONS: usize = 3;
+const CONCURRENCY: usize = 32;
+const SLOW_QUERY: &str = "SELECT host_id FROM system.local WHERE key = 'pool_autoscaling_test'";
+
+fn connection_count_to(session: &Session, node_address: SocketAddr) -> usize {
+    session
+        .connection_stats()
+        .into_iter()
+        .filter(|stats| stats.node_address == node_address)
+        .count()
+}
+
+async fn wait_for_connection_count(session: &Session, node_address: SocketAddr, count: usize) {
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while connection_count_to(session, node_address) != count {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| {
+        panic!(
+            "Pool did not resize to {} connections, it has {}",
+            count,
+            connection_count_to(session, node_address)
+        )
+    });
+}
+
+#[tokio::test]
+async fn pool_grows_under_load_and_shrinks_when_idle() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session: Arc<Session> = Arc::new(
+                SessionBuilder::new()
+                    .known_node(proxy_uris[0].as_str())
+                    .address_translator(Arc::new(translation_map))
+                    .pool_size(PoolSize::PerHost(NonZeroUsize::new(1).unwrap()))
+                    .pool_autoscaling(
+                        PoolAutoscaling::new(
+                            NonZeroUsize::new(MIN_CONNECTIONS).unwrap(),
+                            NonZeroUsize::new(MAX_CONNECTIONS).unwrap(),
+                        )
+                        .unwrap()
+                        .with_load_thresholds(0, 4)
+                        .unwrap()
+                        .with_evaluation_interval(Duration::from_millis(100))
+                        .unwrap(),
+                    )
+                    .build()
+                    .await
+                    .unwrap(),
+            );
+
+            let node = session.get_cluster_state().get_nodes_info()[0].clone();
+            let node_address = SocketAddr::new(node.address.ip(), node.address.port());
+            wait_for_connection_count(&session, node_address, MIN_CONNECTIONS).await;
+
+            // Slow responses keep the requests in flight.
+            for running_node in running_proxy.running_nodes.iter_mut() {
+                running_node.change_request_rules(Some(vec![RequestRule(
+                    Condition::not(Condition::ConnectionRegisteredAnyEvent)
+                        .and(Condition::RequestOpcode(RequestOpcode::Query))
+                        .and(Condition::BodyContainsCaseSensitive(Box::new(
+                            *b"pool_autoscaling_test",
+                        ))),
+                    RequestReaction::delay(Duration::from_millis(200)),
+                )]));
+            }
+
+            let mut statement = Statement::new(SLOW_QUERY);
+            let profile = ExecutionProfile::builder()
+                .load_balancing_policy(SingleTargetLoadBalancingPolicy::new(
+                    NodeIdentifier::Node(node),
+                    None,
+                ))
+                .build();
+            statement.set_execution_profile_handle(Some(profile.into_handle()));
+
+            // Drive the load up: the pool grows up to the maximum.
+            let load = tokio::spawn({
+                let session = Arc::clone(&session);
+                async move {
+                    loop {
+                        join_all(
+                            (0..CONCURRENCY).map(|_| session.query_unpaged(statement.clone(), ())),
+                        )
+                        .await;
+                    }
+                }
+            });
+            wait_for_connection_count(&session, node_address, MAX_CONNECTIONS).await;
+
+            // Drive the load down: the pool shrinks back to the minimum.
+            load.abort();
+            let _ = load.await;
+            wait_for_connection_count(&session, node_address, MIN_CONNECTIONS).await;
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
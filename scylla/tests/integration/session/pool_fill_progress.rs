@@ -0,0 +1,38 @@
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use scylla::client::PoolSize;
+
+use crate::utils::{create_new_session_builder, setup_tracing};
+
+#[tokio::test]
+async fn pool_fill_progress_reaches_one_after_warm_up() {
+    setup_tracing();
+
+    let session = create_new_session_builder()
+        .pool_size(PoolSize::PerShard(NonZeroUsize::new(2).unwrap()))
+        .build()
+        .await
+        .unwrap();
+
+    // Building the session waits for the first connection to each node only.
+    let progress = session.pool_fill_progress();
+    assert!(
+        progress > 0.0 && progress <= 1.0,
+        "Unexpected progress after session creation: {progress}"
+    );
+
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while session.pool_fill_progress() < 1.0 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| {
+        panic!(
+            "Pools did not fill, progress is {}",
+            session.pool_fill_progress()
+        )
+    });
+    assert_eq!(session.pool_fill_progress(), 1.0);
+}
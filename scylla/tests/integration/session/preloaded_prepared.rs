@@ -0,0 +1,116 @@
+//! Tests that statements preloaded from a file of portable blobs
+//! are not prepared again by a new session in the same keyspace.
+
+use std::sync::Arc;
+
+use scylla::client::session_builder::SessionBuilder;
+use scylla::statement::prepared_cache::save_prepared_cache;
+use scylla_proxy::{
+    Condition, ProxyError, Reaction as _, RequestFrame, RequestOpcode, RequestReaction,
+    RequestRule, ShardAwareness, WorkerError,
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+const PRELOADED_STATEMENT: &str = "SELECT host_id FROM system.local WHERE key = ?";
+
+fn count_received(rx: &mut UnboundedReceiver<(RequestFrame, Option<u16>)>) -> usize {
+    std::iter::from_fn(|| rx.try_recv().ok()).count()
+}
+
+#[tokio::test]
+async fn preloaded_statements_are_not_prepared() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("prepared.bin");
+
+            let session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map.clone()))
+                .build()
+                .await
+                .unwrap();
+            let prepared = session.prepare(PRELOADED_STATEMENT).await.unwrap();
+            save_prepared_cache(&path, std::slice::from_ref(&prepared)).unwrap();
+
+            let mut prepare_rxs = Vec::new();
+            for running_node in running_proxy.running_nodes.iter_mut() {
+                let (tx, rx) = mpsc::unbounded_channel();
+                running_node.change_request_rules(Some(vec![RequestRule(
+                    Condition::RequestOpcode(RequestOpcode::Prepare),
+                    RequestReaction::noop().with_feedback_when_performed(tx),
+                )]));
+                prepare_rxs.push(rx);
+            }
+
+            // The statement was saved without a keyspace in use, so a session
+            // which prepares it in a keyspace does not use the preloaded entry.
+            let session_in_keyspace = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map.clone()))
+                .use_keyspace("system", false)
+                .preload_prepared_from(&path)
+                .build()
+                .await
+                .unwrap();
+            for rx in prepare_rxs.iter_mut() {
+                count_received(rx);
+            }
+            session_in_keyspace
+                .prepare(PRELOADED_STATEMENT)
+                .await
+                .unwrap();
+            assert_ne!(prepare_rxs.iter_mut().map(count_received).sum::<usize>(), 0);
+
+            let session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .preload_prepared_from(&path)
+                .build()
+                .await
+                .unwrap();
+
+            let preloaded = session.prepare(PRELOADED_STATEMENT).await.unwrap();
+            assert_eq!(prepare_rxs.iter_mut().map(count_received).sum::<usize>(), 0);
+
+            assert_eq!(preloaded.get_id(), prepared.get_id());
+            assert_eq!(preloaded.get_statement(), prepared.get_statement());
+            assert_eq!(
+                preloaded.get_variable_col_specs().len(),
+                prepared.get_variable_col_specs().len()
+            );
+            assert_eq!(preloaded.is_confirmed_lwt(), prepared.is_confirmed_lwt());
+
+            // The nodes know the statement, so it is not reprepared on execution either.
+            let result = session
+                .execute_unpaged(&preloaded, ("local",))
+                .await
+                .unwrap();
+            assert_eq!(result.into_rows_result().unwrap().rows_num(), 1);
+            assert_eq!(prepare_rxs.iter_mut().map(count_received).sum::<usize>(), 0);
+
+            // Statements which were not preloaded are prepared as usual.
+            session
+                .prepare("SELECT host_id FROM system.local")
+                .await
+                .unwrap();
+            assert_ne!(prepare_rxs.iter_mut().map(count_received).sum::<usize>(), 0);
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -0,0 +1,101 @@
+//! Tests that [Session::prepare_all] does not prepare more statements at the same time
+//! than allowed by its `concurrency` argument.
+
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use scylla::client::session_builder::SessionBuilder;
+use scylla_proxy::{
+    Condition, ProxyError, Reaction as _, RequestOpcode, RequestReaction, RequestRule,
+    ShardAwareness, WorkerError,
+};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+const STATEMENTS: usize = 6;
+const CONCURRENCY: usize = 2;
+
+/// Preparing each statement takes at least that long, because its PREPARE is delayed.
+const PREPARE_DELAY: Duration = Duration::from_millis(300);
+
+#[tokio::test]
+async fn prepare_all_stays_within_concurrency_limit() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .build()
+                .await
+                .unwrap();
+
+            // Records when each node receives a PREPARE, i.e. when a statement starts being prepared.
+            let mut prepares = Vec::new();
+            let mut recorders = Vec::new();
+            for running_node in running_proxy.running_nodes.iter_mut() {
+                let (tx, mut rx) = mpsc::unbounded_channel();
+                running_node.change_request_rules(Some(vec![RequestRule(
+                    Condition::RequestOpcode(RequestOpcode::Prepare),
+                    RequestReaction::delay(PREPARE_DELAY).with_feedback_when_performed(tx),
+                )]));
+                let received_at = Arc::new(Mutex::new(Vec::new()));
+                prepares.push(Arc::clone(&received_at));
+                recorders.push(tokio::spawn(async move {
+                    while rx.recv().await.is_some() {
+                        received_at.lock().unwrap().push(Instant::now());
+                    }
+                }));
+            }
+
+            let statements = (0..STATEMENTS)
+                .map(|i| format!("SELECT host_id FROM system.local WHERE key = 'prepare_all_{i}'"))
+                .collect::<Vec<_>>();
+            let prepared = session
+                .prepare_all(statements.clone(), NonZeroUsize::new(CONCURRENCY).unwrap())
+                .await
+                .unwrap();
+
+            // Statements are returned in the order they were given.
+            assert_eq!(prepared.len(), STATEMENTS);
+            for (prepared, statement) in prepared.iter().zip(statements.iter()) {
+                assert_eq!(prepared.get_statement(), statement);
+            }
+
+            running_proxy.turn_off_rules();
+            recorders.iter().for_each(|recorder| recorder.abort());
+
+            for received_at in prepares {
+                let mut received_at = received_at.lock().unwrap().clone();
+                received_at.sort();
+                assert!(received_at.len() >= STATEMENTS);
+
+                // If more statements were being prepared at the same time than allowed,
+                // some PREPARE would arrive before the delayed one of an earlier statement
+                // was answered. Some slack is left for the timer resolution.
+                for window in received_at.windows(CONCURRENCY + 1) {
+                    let elapsed = window[CONCURRENCY] - window[0];
+                    assert!(
+                        elapsed >= PREPARE_DELAY - Duration::from_millis(20),
+                        "{} statements were prepared within {elapsed:?}",
+                        CONCURRENCY + 1
+                    );
+                }
+            }
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
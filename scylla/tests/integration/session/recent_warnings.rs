@@ -0,0 +1,76 @@
+use std::num::NonZeroUsize;
+
+use scylla::statement::batch::{Batch, BatchType};
+
+use crate::utils::{PerformDDL, create_new_session_builder, setup_tracing, unique_keyspace_name};
+
+/// Large enough for a batch of this many bytes to exceed the default batch size warning
+/// threshold (128 KiB), but not the failure threshold.
+const LARGE_BATCH_SIZES: [usize; 2] = [200 * 1024, 300 * 1024];
+
+#[tokio::test]
+async fn recent_warnings_are_retained_in_order() {
+    setup_tracing();
+    let session = create_new_session_builder()
+        .recent_warnings_capacity(NonZeroUsize::new(100).unwrap())
+        .build()
+        .await
+        .unwrap();
+    let ks = unique_keyspace_name();
+
+    session
+        .ddl(format!(
+            "CREATE KEYSPACE IF NOT EXISTS {ks} WITH replication = {{'class': 'NetworkTopologyStrategy', 'replication_factor': 1}}"
+        ))
+        .await
+        .unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b int, c text, PRIMARY KEY (a, b))"
+        ))
+        .await
+        .unwrap();
+
+    let insert = session
+        .prepare(format!("INSERT INTO {ks}.t (a, b, c) VALUES (?, ?, ?)"))
+        .await
+        .unwrap();
+
+    let mut expected_warnings = Vec::new();
+    for size in LARGE_BATCH_SIZES {
+        let rows = 4;
+        let value = "x".repeat(size / rows);
+        let mut batch = Batch::new(BatchType::Logged);
+        let mut values = Vec::new();
+        for b in 0..rows as i32 {
+            batch.append_statement(insert.clone());
+            values.push((0, b, value.as_str()));
+        }
+
+        let result = session.batch(&batch, values).await.unwrap();
+        let warnings = result.warnings().map(str::to_owned).collect::<Vec<_>>();
+        assert!(!warnings.is_empty());
+        expected_warnings.extend(warnings);
+    }
+
+    let recent_warnings = session
+        .recent_warnings()
+        .into_iter()
+        .map(|warning| warning.message)
+        .collect::<Vec<_>>();
+    assert!(recent_warnings.ends_with(&expected_warnings));
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
+#[tokio::test]
+async fn recent_warnings_are_not_retained_by_default() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+
+    session
+        .query_unpaged("SELECT host_id FROM system.local", ())
+        .await
+        .unwrap();
+    assert!(session.recent_warnings().is_empty());
+}
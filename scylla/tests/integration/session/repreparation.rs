@@ -0,0 +1,104 @@
+//! Tests that statements cached by a `CachingSession` are reprepared
+//! in the background on newly opened connections.
+
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::Duration;
+
+use scylla::client::PoolSize;
+use scylla::client::caching_session::CachingSession;
+use scylla::client::session_builder::SessionBuilder;
+use scylla_proxy::{
+    Condition, ProxyError, Reaction as _, RequestFrame, RequestOpcode, RequestReaction,
+    RequestRule, ShardAwareness, WorkerError,
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+const CACHED_STATEMENT: &str = "SELECT host_id FROM system.local WHERE key = 'repreparation_test'";
+
+fn count_received(rx: &mut UnboundedReceiver<(RequestFrame, Option<u16>)>) -> usize {
+    std::iter::from_fn(|| rx.try_recv().ok()).count()
+}
+
+#[tokio::test]
+async fn cached_statements_are_reprepared_on_reconnect() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .pool_size(PoolSize::PerHost(NonZeroUsize::new(1).unwrap()))
+                .reprepare_on_new_connections(true)
+                .build()
+                .await
+                .unwrap();
+            let session: CachingSession = CachingSession::from(session, 16);
+            session.execute_unpaged(CACHED_STATEMENT, ()).await.unwrap();
+
+            // Feeds back PREPARE requests of the cached statement, and drops
+            // a connection when it receives a request with a marker.
+            let mut prepare_rxs = Vec::new();
+            for running_node in running_proxy.running_nodes.iter_mut() {
+                let (tx, rx) = mpsc::unbounded_channel();
+                running_node.change_request_rules(Some(vec![
+                    RequestRule(
+                        Condition::RequestOpcode(RequestOpcode::Prepare).and(
+                            Condition::BodyContainsCaseSensitive(Box::new(*b"repreparation_test")),
+                        ),
+                        RequestReaction::noop().with_feedback_when_performed(tx),
+                    ),
+                    RequestRule(
+                        Condition::RequestOpcode(RequestOpcode::Query)
+                            .and(Condition::BodyContainsCaseSensitive(Box::new(
+                                *b"repreparation_drop",
+                            )))
+                            .and(Condition::TrueForLimitedTimes(1)),
+                        RequestReaction::drop_connection(),
+                    ),
+                ]));
+                prepare_rxs.push(rx);
+            }
+
+            // Break the connection to the coordinator of the request, which is then reopened.
+            let _ = session
+                .get_session()
+                .query_unpaged(
+                    "SELECT host_id FROM system.local WHERE key = 'repreparation_drop'",
+                    (),
+                )
+                .await;
+
+            // The cached statement is prepared on the new connection in the background.
+            tokio::time::timeout(Duration::from_secs(10), async {
+                while prepare_rxs.iter_mut().map(count_received).sum::<usize>() == 0 {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                }
+            })
+            .await
+            .expect("The cached statement was not reprepared on the new connection");
+
+            // Executions on any node succeed at the first attempt,
+            // without repreparing the statement after an UNPREPARED error.
+            for _ in 0..10 {
+                session.execute_unpaged(CACHED_STATEMENT, ()).await.unwrap();
+            }
+            assert_eq!(prepare_rxs.iter_mut().map(count_received).sum::<usize>(), 0);
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use scylla::client::session_builder::SessionBuilder;
+use scylla_proxy::{
+    Condition, ProxyError, Reaction, RequestOpcode, RequestReaction, RequestRule, ResponseOpcode,
+    ResponseReaction, ResponseRule, ShardAwareness, WorkerError,
+};
+use tokio::sync::mpsc;
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+/// Size of the header of a CQL v4 frame.
+const FRAME_HEADER_SIZE: usize = 9;
+
+#[tokio::test]
+async fn attempt_info_reports_request_and_response_sizes() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .build()
+                .await
+                .unwrap();
+
+            const REQUEST: &str =
+                "SELECT host_id, release_version FROM system.local WHERE key = 'local'";
+
+            let (request_tx, mut request_rx) = mpsc::unbounded_channel();
+            let (response_tx, mut response_rx) = mpsc::unbounded_channel();
+            for node in running_proxy.running_nodes.iter_mut() {
+                node.change_request_rules(Some(vec![RequestRule(
+                    Condition::RequestOpcode(RequestOpcode::Query).and(
+                        Condition::BodyContainsCaseSensitive(Box::new(
+                            *b"SELECT host_id, release_version",
+                        )),
+                    ),
+                    RequestReaction::noop().with_feedback_when_performed(request_tx.clone()),
+                )]));
+                node.change_response_rules(Some(vec![ResponseRule(
+                    Condition::ResponseOpcode(ResponseOpcode::Result)
+                        .and(Condition::not(Condition::ConnectionRegisteredAnyEvent)),
+                    ResponseReaction::noop().with_feedback_when_performed(response_tx.clone()),
+                )]));
+            }
+
+            let result = session.query_unpaged(REQUEST, ()).await.unwrap();
+
+            let (request_frame, _) = request_rx.recv().await.unwrap();
+            let _ = request_rx.try_recv().unwrap_err(); // There should be only one frame.
+            let (response_frame, _) = response_rx.recv().await.unwrap();
+            let _ = response_rx.try_recv().unwrap_err(); // There should be only one frame.
+
+            let attempt_info = result.attempt_info();
+            assert_eq!(
+                attempt_info.bytes_sent,
+                FRAME_HEADER_SIZE + request_frame.body.len()
+            );
+            assert_eq!(
+                attempt_info.bytes_received,
+                FRAME_HEADER_SIZE + response_frame.body.len()
+            );
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -1,22 +1,33 @@
 use crate::utils::{
-    PerformDDL, fetch_negotiated_features, setup_tracing, test_with_3_node_cluster,
-    unique_keyspace_name,
+    PerformDDL, calculate_proxy_host_ids, fetch_negotiated_features, setup_tracing,
+    test_with_3_node_cluster, unique_keyspace_name,
 };
 use assert_matches::assert_matches;
 use scylla::client::execution_profile::ExecutionProfile;
 use scylla::client::session::Session;
 use scylla::client::session_builder::SessionBuilder;
-use scylla::errors::{DbError, ExecutionError, RequestAttemptError};
-use scylla::policies::retry::{DowngradingConsistencyRetryPolicy, FallthroughRetryPolicy};
+use scylla::cluster::{ClusterState, NodeRef};
+use scylla::errors::{
+    DbError, ExecutionError, NextPageError, PagerExecutionError, RequestAttemptError, RequestError,
+};
+use scylla::observability::history::HistoryCollector;
+use scylla::policies::load_balancing::{FallbackPlan, LoadBalancingPolicy, RoutingInfo};
+use scylla::policies::retry::{
+    DefaultRetryPolicy, DowngradingConsistencyRetryPolicy, FallthroughRetryPolicy, RequestInfo,
+    RetryDecision, RetryErrorKind, RetryPolicy, RetrySession,
+};
 use scylla::policies::speculative_execution::SimpleSpeculativeExecutionPolicy;
+use scylla::routing::Shard;
 use scylla::statement::unprepared::Statement;
 use scylla_cql::Consistency;
 use std::collections::HashMap;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
+use std::num::NonZeroU32;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use tracing::info;
+use uuid::Uuid;
 
 use scylla_proxy::{
     Condition, ProxyError, Reaction, RequestOpcode, RequestReaction, RequestRule, ShardAwareness,
@@ -106,6 +117,469 @@ async fn speculative_execution_is_fired() {
     }
 }
 
+// Plans the primary node first, followed by all the other nodes.
+#[derive(Debug)]
+struct PrimaryFirstLBP {
+    primary: Uuid,
+}
+
+impl LoadBalancingPolicy for PrimaryFirstLBP {
+    fn pick<'a>(
+        &'a self,
+        _query: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> Option<(NodeRef<'a>, Option<Shard>)> {
+        cluster
+            .get_nodes_info()
+            .iter()
+            .find(|node| node.host_id == self.primary)
+            .map(|node| (node, None))
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        _query: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> FallbackPlan<'a> {
+        Box::new(
+            cluster
+                .get_nodes_info()
+                .iter()
+                .filter(|node| node.host_id != self.primary)
+                .map(|node| (node, None)),
+        )
+    }
+
+    fn name(&self) -> String {
+        "PrimaryFirstLBP".to_owned()
+    }
+}
+
+#[tokio::test]
+async fn attempt_info_reports_speculative_win() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(ShardAwareness::QueryNode, |proxy_uris, translation_map, mut running_proxy| async move {
+        let session: Session = SessionBuilder::new()
+            .known_node(proxy_uris[0].as_str())
+            .address_translator(Arc::new(translation_map.clone()))
+            .build()
+            .await
+            .unwrap();
+        let host_ids = calculate_proxy_host_ids(&proxy_uris, &translation_map, &session);
+
+        let ks = unique_keyspace_name();
+        session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 3}}")).await.unwrap();
+        session.use_keyspace(&ks, false).await.unwrap();
+        session
+            .ddl("CREATE TABLE t (a int primary key)")
+            .await
+            .unwrap();
+
+        let speculative_profile = ExecutionProfile::builder()
+            .speculative_execution_policy(Some(Arc::new(SimpleSpeculativeExecutionPolicy {
+                max_retry_count: 1,
+                retry_interval: Duration::from_millis(50),
+            })))
+            .retry_policy(Arc::new(FallthroughRetryPolicy))
+            .load_balancing_policy(Arc::new(PrimaryFirstLBP {
+                primary: host_ids[0],
+            }))
+            .build();
+        let mut s = Statement::from("INSERT INTO t (a) VALUES (1)");
+        s.set_is_idempotent(true); // this is to allow speculative execution to fire
+        s.set_execution_profile_handle(Some(speculative_profile.into_handle()));
+
+        let delay_rule = || {
+            RequestRule(
+                Condition::RequestOpcode(RequestOpcode::Query)
+                    .and(Condition::BodyContainsCaseSensitive(Box::new(*b"INTO t"))),
+                RequestReaction::delay(Duration::from_secs(2)),
+            )
+        };
+
+        info!("--------------------- speculative targets are slow ----------------");
+        // Delay the speculative targets, so that the primary wins even if
+        // the speculative attempt is fired.
+        for node in &mut running_proxy.running_nodes[1..] {
+            node.change_request_rules(Some(vec![delay_rule()]));
+        }
+        let result = session.query_unpaged(s.clone(), ()).await.unwrap();
+        assert!(!result.attempt_info().won_by_speculative);
+        assert_eq!(result.request_coordinator().node().host_id, host_ids[0]);
+        for node in &mut running_proxy.running_nodes[1..] {
+            node.change_request_rules(None);
+        }
+
+        info!("--------------------- primary is slow ----------------");
+        running_proxy.running_nodes[0].change_request_rules(Some(vec![delay_rule()]));
+
+        let result = session.query_unpaged(s, ()).await.unwrap();
+        assert!(result.attempt_info().won_by_speculative);
+        assert_eq!(result.attempt_info().winning_attempt, 2);
+        assert_ne!(result.request_coordinator().node().host_id, host_ids[0]);
+
+        running_proxy.running_nodes[0].change_request_rules(None);
+        session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+
+        running_proxy
+    }).await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+#[tokio::test]
+async fn attempt_info_reports_winning_attempt() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(ShardAwareness::QueryNode, |proxy_uris, translation_map, mut running_proxy| async move {
+        let session: Session = SessionBuilder::new()
+            .known_node(proxy_uris[0].as_str())
+            .address_translator(Arc::new(translation_map.clone()))
+            .build()
+            .await
+            .unwrap();
+        let host_ids = calculate_proxy_host_ids(&proxy_uris, &translation_map, &session);
+
+        let ks = unique_keyspace_name();
+        session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 3}}")).await.unwrap();
+        session.use_keyspace(&ks, false).await.unwrap();
+        session
+            .ddl("CREATE TABLE t (a int primary key)")
+            .await
+            .unwrap();
+
+        let profile = ExecutionProfile::builder()
+            .retry_policy(Arc::new(DefaultRetryPolicy::new()))
+            .load_balancing_policy(Arc::new(PrimaryFirstLBP {
+                primary: host_ids[0],
+            }))
+            .build();
+        let mut s = Statement::from("INSERT INTO t (a) VALUES (1)");
+        s.set_is_idempotent(true); // this is to allow retrying on another node
+        s.set_execution_profile_handle(Some(profile.into_handle()));
+
+        info!("--------------------- primary responds ----------------");
+        let result = session.query_unpaged(s.clone(), ()).await.unwrap();
+        assert_eq!(result.attempt_info().winning_attempt, 1);
+        assert_eq!(result.request_coordinator().node().host_id, host_ids[0]);
+
+        info!("--------------------- primary is overloaded ----------------");
+        let overloaded_rule = RequestRule(
+            Condition::RequestOpcode(RequestOpcode::Query)
+                .and(Condition::BodyContainsCaseSensitive(Box::new(*b"INTO t"))),
+            RequestReaction::forge_with_error(DbError::Overloaded),
+        );
+        running_proxy.running_nodes[0].change_request_rules(Some(vec![overloaded_rule]));
+
+        let result = session.query_unpaged(s, ()).await.unwrap();
+        assert_eq!(result.attempt_info().winning_attempt, 2);
+        assert!(!result.attempt_info().won_by_speculative);
+        assert_ne!(result.request_coordinator().node().host_id, host_ids[0]);
+
+        running_proxy.running_nodes[0].change_request_rules(None);
+        session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+
+        running_proxy
+    }).await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// Plans only the given nodes, in the given order.
+#[derive(Debug)]
+struct FixedPlanLBP {
+    plan: Vec<Uuid>,
+}
+
+impl LoadBalancingPolicy for FixedPlanLBP {
+    fn pick<'a>(
+        &'a self,
+        _query: &'a RoutingInfo,
+        _cluster: &'a ClusterState,
+    ) -> Option<(NodeRef<'a>, Option<Shard>)> {
+        None
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        _query: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> FallbackPlan<'a> {
+        Box::new(self.plan.iter().filter_map(|host_id| {
+            cluster
+                .get_nodes_info()
+                .iter()
+                .find(|node| node.host_id == *host_id)
+                .map(|node| (node, None))
+        }))
+    }
+
+    fn name(&self) -> String {
+        "FixedPlanLBP".to_owned()
+    }
+}
+
+#[tokio::test]
+async fn history_lists_broken_connection_errors_of_tried_nodes() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(ShardAwareness::QueryNode, |proxy_uris, translation_map, mut running_proxy| async move {
+        let session: Session = SessionBuilder::new()
+            .known_node(proxy_uris[0].as_str())
+            .address_translator(Arc::new(translation_map.clone()))
+            .build()
+            .await
+            .unwrap();
+        let host_ids = calculate_proxy_host_ids(&proxy_uris, &translation_map, &session);
+
+        let ks = unique_keyspace_name();
+        session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 3}}")).await.unwrap();
+        session.use_keyspace(&ks, false).await.unwrap();
+        session
+            .ddl("CREATE TABLE t (a int primary key)")
+            .await
+            .unwrap();
+
+        let profile = ExecutionProfile::builder()
+            .load_balancing_policy(Arc::new(FixedPlanLBP {
+                plan: vec![host_ids[0], host_ids[1]],
+            }))
+            .build();
+        let history_collector = Arc::new(HistoryCollector::new());
+        let mut s = Statement::from("INSERT INTO t (a) VALUES (1)");
+        s.set_is_idempotent(true); // this is to allow retrying on the next node
+        s.set_execution_profile_handle(Some(profile.into_handle()));
+        s.set_history_listener(history_collector.clone());
+
+        // Down both planned nodes.
+        let drop_connection_rule = RequestRule(
+            Condition::RequestOpcode(RequestOpcode::Query)
+                .and(Condition::not(Condition::ConnectionRegisteredAnyEvent))
+                .and(Condition::BodyContainsCaseSensitive(Box::new(*b"INTO t"))),
+            RequestReaction::drop_connection(),
+        );
+        for node in &mut running_proxy.running_nodes[..2] {
+            node.change_request_rules(Some(vec![drop_connection_rule.clone()]));
+        }
+
+        // The request fails with the error of the last attempt, as usual.
+        let err = session.query_unpaged(s, ()).await.unwrap_err();
+        assert_matches!(err, ExecutionError::LastAttemptError(RequestAttemptError::BrokenConnectionError(_)));
+
+        // The history lists the errors on all the tried nodes.
+        let history = history_collector.take_structured_history();
+        let tried_nodes = history.requests[0].tried_nodes();
+        let tried_ips = tried_nodes.iter().map(|(addr, _)| addr.ip()).collect::<Vec<_>>();
+        assert_eq!(tried_ips, proxy_ips(&proxy_uris[..2]));
+        for (_, error) in &tried_nodes {
+            assert_matches!(
+                error,
+                RequestError::LastAttemptError(RequestAttemptError::BrokenConnectionError(_))
+            );
+        }
+
+        running_proxy.turn_off_rules();
+        session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+
+        running_proxy
+    }).await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+fn proxy_ips(proxy_uris: &[String]) -> Vec<IpAddr> {
+    proxy_uris
+        .iter()
+        .map(|uri| uri.parse::<SocketAddr>().unwrap().ip())
+        .collect()
+}
+
+#[tokio::test]
+async fn failed_request_reports_retry_history() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session: Session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map.clone()))
+                .build()
+                .await
+                .unwrap();
+            let host_ids = calculate_proxy_host_ids(&proxy_uris, &translation_map, &session);
+
+            let profile = ExecutionProfile::builder()
+                .load_balancing_policy(Arc::new(FixedPlanLBP { plan: host_ids }))
+                .build()
+                .into_handle();
+            let new_statement = |history_collector: &Arc<HistoryCollector>| {
+                let mut s =
+                    Statement::from("SELECT host_id FROM system.local WHERE key = 'retry_history'");
+                s.set_execution_profile_handle(Some(profile.clone()));
+                s.set_history_listener(history_collector.clone());
+                s
+            };
+
+            // Every node is bootstrapping, so the request is retried on the next node twice.
+            let bootstrapping_rule = RequestRule(
+                Condition::RequestOpcode(RequestOpcode::Query)
+                    .and(Condition::not(Condition::ConnectionRegisteredAnyEvent))
+                    .and(Condition::BodyContainsCaseSensitive(Box::new(
+                        *b"retry_history",
+                    ))),
+                RequestReaction::forge_with_error(DbError::IsBootstrapping),
+            );
+            for node in running_proxy.running_nodes.iter_mut() {
+                node.change_request_rules(Some(vec![bootstrapping_rule.clone()]));
+            }
+
+            let assert_retry_history = |history_collector: &HistoryCollector| {
+                let history = history_collector.take_structured_history();
+                let retry_history = history.requests[0].failed_attempts();
+                assert_eq!(retry_history.len(), 3);
+
+                let history_ips = retry_history
+                    .iter()
+                    .map(|(addr, _, _)| addr.ip())
+                    .collect::<Vec<_>>();
+                assert_eq!(history_ips, proxy_ips(&proxy_uris));
+                for (_, error, decision) in &retry_history {
+                    assert_matches!(
+                        error,
+                        RequestAttemptError::DbError(DbError::IsBootstrapping, _)
+                    );
+                    assert_eq!(**decision, RetryDecision::RetryNextTarget(None));
+                }
+            };
+
+            let history_collector = Arc::new(HistoryCollector::new());
+            let err = session
+                .query_unpaged(new_statement(&history_collector), ())
+                .await
+                .unwrap_err();
+            assert_matches!(
+                err,
+                ExecutionError::LastAttemptError(RequestAttemptError::DbError(
+                    DbError::IsBootstrapping,
+                    _
+                ))
+            );
+            assert_retry_history(&history_collector);
+
+            // The pager reports the same history.
+            let history_collector = Arc::new(HistoryCollector::new());
+            let err = session
+                .query_iter(new_statement(&history_collector), ())
+                .await
+                .unwrap_err();
+            assert_matches!(
+                err,
+                PagerExecutionError::NextPageError(NextPageError::RequestFailure(
+                    RequestError::LastAttemptError(RequestAttemptError::DbError(
+                        DbError::IsBootstrapping,
+                        _
+                    ))
+                ))
+            );
+            assert_retry_history(&history_collector);
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+#[tokio::test]
+async fn on_attempt_is_called_per_attempt() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(ShardAwareness::QueryNode, |proxy_uris, translation_map, mut running_proxy| async move {
+        let session: Session = SessionBuilder::new()
+            .known_node(proxy_uris[0].as_str())
+            .address_translator(Arc::new(translation_map.clone()))
+            .build()
+            .await
+            .unwrap();
+        let host_ids = calculate_proxy_host_ids(&proxy_uris, &translation_map, &session);
+
+        let ks = unique_keyspace_name();
+        session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 3}}")).await.unwrap();
+        session.use_keyspace(&ks, false).await.unwrap();
+        session
+            .ddl("CREATE TABLE t (a int primary key)")
+            .await
+            .unwrap();
+
+        let profile = ExecutionProfile::builder()
+            .load_balancing_policy(Arc::new(FixedPlanLBP {
+                plan: vec![host_ids[0], host_ids[1]],
+            }))
+            .build();
+        let mut s = Statement::from("INSERT INTO t (a) VALUES (1)");
+        s.set_is_idempotent(true); // this is to allow retrying on the next node
+        s.set_execution_profile_handle(Some(profile.into_handle()));
+
+        let attempts = Arc::new(std::sync::Mutex::new(Vec::new()));
+        s.set_on_attempt({
+            let attempts = Arc::clone(&attempts);
+            move |attempt, node| attempts.lock().unwrap().push((attempt, node.host_id))
+        });
+
+        // The first node is overloaded, so the request is retried on the second one.
+        let forge_error_rule = RequestRule(
+            Condition::RequestOpcode(RequestOpcode::Query)
+                .and(Condition::not(Condition::ConnectionRegisteredAnyEvent))
+                .and(Condition::BodyContainsCaseSensitive(Box::new(*b"INTO t"))),
+            RequestReaction::forge().overloaded(),
+        );
+        running_proxy.running_nodes[0].change_request_rules(Some(vec![forge_error_rule]));
+
+        session.query_unpaged(s.clone(), ()).await.unwrap();
+        assert_eq!(
+            *attempts.lock().unwrap(),
+            [(1, host_ids[0]), (2, host_ids[1])]
+        );
+
+        // Attempts are numbered per request.
+        attempts.lock().unwrap().clear();
+        running_proxy.turn_off_rules();
+        session.query_unpaged(s, ()).await.unwrap();
+        assert_eq!(*attempts.lock().unwrap(), [(1, host_ids[0])]);
+
+        session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+
+        running_proxy
+    }).await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
+
 #[tokio::test]
 async fn retries_occur() {
     setup_tracing();
@@ -175,6 +649,131 @@ async fn retries_occur() {
     }
 }
 
+#[derive(Debug)]
+struct AlwaysRetrySameTargetPolicy;
+
+impl RetryPolicy for AlwaysRetrySameTargetPolicy {
+    fn new_session(&self) -> Box<dyn RetrySession> {
+        Box::new(AlwaysRetrySameTargetSession)
+    }
+}
+
+struct AlwaysRetrySameTargetSession;
+
+impl RetrySession for AlwaysRetrySameTargetSession {
+    fn decide_should_retry(&mut self, _request_info: RequestInfo) -> RetryDecision {
+        RetryDecision::RetrySameTarget(None)
+    }
+
+    fn reset(&mut self) {}
+}
+
+/// Tests that `SessionBuilder::max_attempts_per_request` stops a retry policy
+/// which would otherwise retry forever, both for unpaged and paged requests.
+#[tokio::test]
+async fn max_attempts_per_request_stops_endless_retries() {
+    setup_tracing();
+    const MAX_ATTEMPTS: NonZeroU32 = NonZeroU32::new(5).unwrap();
+
+    let res = test_with_3_node_cluster(ShardAwareness::QueryNode, |proxy_uris, translation_map, mut running_proxy| async move {
+        let session: Session = SessionBuilder::new()
+            .known_node(proxy_uris[0].as_str())
+            .address_translator(Arc::new(translation_map))
+            .max_attempts_per_request(MAX_ATTEMPTS)
+            .build()
+            .await
+            .unwrap();
+
+        let ks = unique_keyspace_name();
+        session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 3}}")).await.unwrap();
+        session.use_keyspace(&ks, false).await.unwrap();
+        session
+            .ddl("CREATE TABLE t (a int primary key)")
+            .await
+            .unwrap();
+
+        let mut s = Statement::from("INSERT INTO t (a) VALUES (1)");
+        s.set_retry_policy(Some(Arc::new(AlwaysRetrySameTargetPolicy)));
+
+        let (feedback_tx, mut feedback_rx) = mpsc::unbounded_channel();
+        let forge_error_rule = RequestRule(
+            Condition::RequestOpcode(RequestOpcode::Query)
+                .and(Condition::not(Condition::ConnectionRegisteredAnyEvent))
+                .and(Condition::BodyContainsCaseSensitive(Box::new(*b"INTO t"))),
+            RequestReaction::forge()
+                .overloaded()
+                .with_feedback_when_performed(feedback_tx),
+        );
+        running_proxy
+            .running_nodes
+            .iter_mut()
+            .for_each(|n| n.change_request_rules(Some(vec![forge_error_rule.clone()])));
+
+        let err = session.query_unpaged(s, ()).await.unwrap_err();
+        assert_matches!(
+            err,
+            ExecutionError::LastAttemptError(RequestAttemptError::DbError(DbError::Overloaded, _))
+        );
+
+        for _ in 0..MAX_ATTEMPTS.get() {
+            feedback_rx.recv().await.unwrap();
+        }
+        assert!(
+            feedback_rx.try_recv().is_err(),
+            "more than {MAX_ATTEMPTS} attempts were made"
+        );
+
+        // Paged requests are limited in the same way.
+        let mut s = Statement::from("SELECT a FROM t");
+        s.set_page_size(1);
+        s.set_retry_policy(Some(Arc::new(AlwaysRetrySameTargetPolicy)));
+
+        let (feedback_tx, mut feedback_rx) = mpsc::unbounded_channel();
+        let forge_error_rule = RequestRule(
+            Condition::RequestOpcode(RequestOpcode::Query)
+                .and(Condition::not(Condition::ConnectionRegisteredAnyEvent))
+                .and(Condition::BodyContainsCaseSensitive(Box::new(*b"FROM t"))),
+            RequestReaction::forge()
+                .overloaded()
+                .with_feedback_when_performed(feedback_tx),
+        );
+        running_proxy
+            .running_nodes
+            .iter_mut()
+            .for_each(|n| n.change_request_rules(Some(vec![forge_error_rule.clone()])));
+
+        let err = session.query_iter(s, ()).await.unwrap_err();
+        assert_matches!(
+            err,
+            PagerExecutionError::NextPageError(NextPageError::RequestFailure(
+                RequestError::LastAttemptError(RequestAttemptError::DbError(
+                    DbError::Overloaded,
+                    _
+                ))
+            ))
+        );
+
+        for _ in 0..MAX_ATTEMPTS.get() {
+            feedback_rx.recv().await.unwrap();
+        }
+        assert!(
+            feedback_rx.try_recv().is_err(),
+            "more than {MAX_ATTEMPTS} attempts were made to fetch a page"
+        );
+
+        running_proxy.running_nodes.iter_mut().for_each(|n| n.change_request_rules(None));
+        session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+
+        running_proxy
+    }).await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
+
 /// Tests that DowngradingConsistencyRetryPolicy retries with a lower consistency level
 /// when nodes return an Unavailable error.
 ///
@@ -255,7 +854,13 @@ async fn downgrading_consistency_policy_downgrades_on_unavailable() {
                     async move {
                         let mut stmt = Statement::from("INSERT INTO t (a) VALUES (?)");
                         stmt.set_is_idempotent(is_idempotent);
-                        session.query_unpaged(stmt, (value,)).await.unwrap();
+                        let result = session.query_unpaged(stmt, (value,)).await.unwrap();
+
+                        // The consistency of the successful attempt is reported.
+                        assert_eq!(
+                            result.attempt_info().effective_consistency,
+                            Some(Consistency::Two)
+                        );
 
                         // Verify: exactly 1 request rejected (CL=ALL), exactly 1 retry passed (CL=TWO).
                         let (rejected_frame, _) = error_rx.recv().await.unwrap();
@@ -428,3 +1033,404 @@ async fn speculative_execution_panic_regression_test() {
         Err(err) => panic!("{}", err),
     }
 }
+
+/// Wraps the default retry policy, recording the errors it is consulted about.
+#[derive(Debug)]
+struct RecordingRetryPolicy {
+    errors: Arc<std::sync::Mutex<Vec<RequestAttemptError>>>,
+}
+
+impl RetryPolicy for RecordingRetryPolicy {
+    fn new_session(&self) -> Box<dyn RetrySession> {
+        Box::new(RecordingRetrySession {
+            inner: DefaultRetryPolicy::new().new_session(),
+            errors: Arc::clone(&self.errors),
+        })
+    }
+}
+
+struct RecordingRetrySession {
+    inner: Box<dyn RetrySession>,
+    errors: Arc<std::sync::Mutex<Vec<RequestAttemptError>>>,
+}
+
+impl RetrySession for RecordingRetrySession {
+    fn decide_should_retry(&mut self, request_info: RequestInfo) -> RetryDecision {
+        self.errors.lock().unwrap().push(request_info.error.clone());
+        self.inner.decide_should_retry(request_info)
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+/// Tests that when a connection is closed while a request is in flight on it,
+/// the retry policy is consulted with a broken connection error, and an idempotent
+/// request is retried on another node, while a non-idempotent one fails.
+#[tokio::test]
+async fn idempotent_request_is_retried_after_connection_closed_mid_request() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session: Session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map.clone()))
+                .build()
+                .await
+                .unwrap();
+            let host_ids = calculate_proxy_host_ids(&proxy_uris, &translation_map, &session);
+
+            let errors = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let profile = ExecutionProfile::builder()
+                .load_balancing_policy(Arc::new(FixedPlanLBP {
+                    plan: vec![host_ids[0], host_ids[1]],
+                }))
+                .retry_policy(Arc::new(RecordingRetryPolicy {
+                    errors: Arc::clone(&errors),
+                }))
+                .build()
+                .into_handle();
+
+            // The first node closes the connection once it receives the request.
+            running_proxy.running_nodes[0].change_request_rules(Some(vec![RequestRule(
+                Condition::RequestOpcode(RequestOpcode::Query)
+                    .and(Condition::not(Condition::ConnectionRegisteredAnyEvent))
+                    .and(Condition::BodyContainsCaseSensitive(Box::new(
+                        *b"connection_closed_test",
+                    ))),
+                RequestReaction::drop_connection(),
+            )]));
+
+            let mut statement = Statement::new(
+                "SELECT host_id FROM system.local WHERE key = 'connection_closed_test'",
+            );
+            statement.set_execution_profile_handle(Some(profile));
+
+            // Idempotent: retried on the second node.
+            statement.set_is_idempotent(true);
+            let result = session.query_unpaged(statement.clone(), ()).await.unwrap();
+            assert_eq!(result.request_coordinator().node().host_id, host_ids[1]);
+            {
+                let errors = std::mem::take(&mut *errors.lock().unwrap());
+                assert_eq!(errors.len(), 1);
+                assert_matches!(errors[0], RequestAttemptError::BrokenConnectionError(_));
+            }
+
+            // Wait until the first node is connected again, so that the next request is sent to it.
+            let first_node = session
+                .get_cluster_state()
+                .get_nodes_info()
+                .iter()
+                .find(|node| node.host_id == host_ids[0])
+                .cloned()
+                .unwrap();
+            tokio::time::timeout(Duration::from_secs(10), async {
+                while !first_node.is_connected() {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            })
+            .await
+            .unwrap();
+
+            // Not idempotent: it is unknown whether the request was executed, so it is not retried.
+            statement.set_is_idempotent(false);
+            let err = session.query_unpaged(statement, ()).await.unwrap_err();
+            assert_matches!(
+                err,
+                ExecutionError::LastAttemptError(RequestAttemptError::BrokenConnectionError(_))
+            );
+            {
+                let errors = errors.lock().unwrap();
+                assert_eq!(errors.len(), 1);
+                assert_matches!(errors[0], RequestAttemptError::BrokenConnectionError(_));
+            }
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// Tests that `set_no_retry` disables retries of a statement, even if the retry policy
+/// of the execution profile would retry the error.
+#[tokio::test]
+async fn no_retry_prevents_retrying_read_timeout() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session: Session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .default_execution_profile_handle(
+                    ExecutionProfile::builder()
+                        .retry_policy(Arc::new(DefaultRetryPolicy::new()))
+                        .build()
+                        .into_handle(),
+                )
+                .build()
+                .await
+                .unwrap();
+
+            // DefaultRetryPolicy retries such a read timeout once on the same node.
+            let read_timeout = DbError::ReadTimeout {
+                consistency: Consistency::One,
+                received: 1,
+                required: 1,
+                data_present: false,
+            };
+            let (feedback_tx, mut feedback_rx) = mpsc::unbounded_channel();
+            let forge_error_rule = RequestRule(
+                Condition::RequestOpcode(RequestOpcode::Query)
+                    .and(Condition::not(Condition::ConnectionRegisteredAnyEvent))
+                    .and(Condition::BodyContainsCaseSensitive(Box::new(
+                        *b"no_retry_test",
+                    ))),
+                RequestReaction::forge_with_error(read_timeout)
+                    .with_feedback_when_performed(feedback_tx),
+            );
+            running_proxy
+                .running_nodes
+                .iter_mut()
+                .for_each(|n| n.change_request_rules(Some(vec![forge_error_rule.clone()])));
+
+            let mut statement =
+                Statement::new("SELECT host_id FROM system.local WHERE key = 'no_retry_test'");
+            let count_attempts = |feedback_rx: &mut mpsc::UnboundedReceiver<_>| {
+                std::iter::from_fn(|| feedback_rx.try_recv().ok()).count()
+            };
+
+            // Without `set_no_retry`, the read timeout is retried.
+            let err = session
+                .query_unpaged(statement.clone(), ())
+                .await
+                .unwrap_err();
+            assert_matches!(
+                err,
+                ExecutionError::LastAttemptError(RequestAttemptError::DbError(
+                    DbError::ReadTimeout { .. },
+                    _
+                ))
+            );
+            assert_eq!(count_attempts(&mut feedback_rx), 2);
+
+            // With `set_no_retry`, the read timeout is returned after the first attempt.
+            statement.set_no_retry();
+            let err = session.query_unpaged(statement, ()).await.unwrap_err();
+            assert_matches!(
+                err,
+                ExecutionError::LastAttemptError(RequestAttemptError::DbError(
+                    DbError::ReadTimeout { .. },
+                    _
+                ))
+            );
+            assert_eq!(count_attempts(&mut feedback_rx), 1);
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// Tests that a retry policy set on a clone of a prepared statement overrides
+/// the retry policy of the execution profile, for executions of that clone only.
+#[tokio::test]
+async fn per_request_retry_policy_overrides_profile_policy() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session: Session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .default_execution_profile_handle(
+                    ExecutionProfile::builder()
+                        .retry_policy(Arc::new(DefaultRetryPolicy::new()))
+                        .build()
+                        .into_handle(),
+                )
+                .build()
+                .await
+                .unwrap();
+
+            let prepared = session
+                .prepare("SELECT host_id FROM system.local WHERE key = 'per_request_retry_test'")
+                .await
+                .unwrap();
+
+            // DefaultRetryPolicy retries such a read timeout once on the same node.
+            let read_timeout = DbError::ReadTimeout {
+                consistency: Consistency::One,
+                received: 1,
+                required: 1,
+                data_present: false,
+            };
+            let (feedback_tx, mut feedback_rx) = mpsc::unbounded_channel();
+            let forge_error_rule = RequestRule(
+                Condition::RequestOpcode(RequestOpcode::Execute)
+                    .and(Condition::not(Condition::ConnectionRegisteredAnyEvent)),
+                RequestReaction::forge_with_error(read_timeout)
+                    .with_feedback_when_performed(feedback_tx),
+            );
+            running_proxy
+                .running_nodes
+                .iter_mut()
+                .for_each(|n| n.change_request_rules(Some(vec![forge_error_rule.clone()])));
+
+            let count_attempts = |feedback_rx: &mut mpsc::UnboundedReceiver<_>| {
+                std::iter::from_fn(|| feedback_rx.try_recv().ok()).count()
+            };
+            let assert_read_timeout = |err: ExecutionError| {
+                assert_matches!(
+                    err,
+                    ExecutionError::LastAttemptError(RequestAttemptError::DbError(
+                        DbError::ReadTimeout { .. },
+                        _
+                    ))
+                );
+            };
+
+            // The per-request policy is used instead of the profile's one.
+            let mut with_fallthrough_policy = prepared.clone();
+            with_fallthrough_policy.set_retry_policy(Some(Arc::new(FallthroughRetryPolicy::new())));
+            let err = session
+                .execute_unpaged(&with_fallthrough_policy, ())
+                .await
+                .unwrap_err();
+            assert_read_timeout(err);
+            assert_eq!(count_attempts(&mut feedback_rx), 1);
+
+            // Other executions of the statement still use the profile's policy.
+            let err = session.execute_unpaged(&prepared, ()).await.unwrap_err();
+            assert_read_timeout(err);
+            assert_eq!(count_attempts(&mut feedback_rx), 2);
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// Tests that retry decisions configured on an execution profile apply only to
+/// idempotent requests, are capped, and yield to a statement's own retry policy.
+#[tokio::test]
+async fn retry_decisions_are_bounded_and_yield_to_statement_policy() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session: Session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .default_execution_profile_handle(
+                    ExecutionProfile::builder()
+                        .retry_policy(Arc::new(FallthroughRetryPolicy::new()))
+                        .retry_decisions(HashMap::from([(
+                            RetryErrorKind::Overloaded,
+                            RetryDecision::RetrySameTarget(None),
+                        )]))
+                        .build()
+                        .into_handle(),
+                )
+                .build()
+                .await
+                .unwrap();
+
+            let (feedback_tx, mut feedback_rx) = mpsc::unbounded_channel();
+            let forge_error_rule = RequestRule(
+                Condition::RequestOpcode(RequestOpcode::Query)
+                    .and(Condition::not(Condition::ConnectionRegisteredAnyEvent))
+                    .and(Condition::BodyContainsCaseSensitive(Box::new(
+                        *b"retry_decisions_test",
+                    ))),
+                RequestReaction::forge_with_error(DbError::Overloaded)
+                    .with_feedback_when_performed(feedback_tx),
+            );
+            running_proxy
+                .running_nodes
+                .iter_mut()
+                .for_each(|n| n.change_request_rules(Some(vec![forge_error_rule.clone()])));
+
+            let statement = Statement::new(
+                "SELECT host_id FROM system.local WHERE key = 'retry_decisions_test'",
+            );
+            let count_attempts = |feedback_rx: &mut mpsc::UnboundedReceiver<_>| {
+                std::iter::from_fn(|| feedback_rx.try_recv().ok()).count()
+            };
+            let assert_overloaded = |err: ExecutionError| {
+                assert_matches!(
+                    err,
+                    ExecutionError::LastAttemptError(RequestAttemptError::DbError(
+                        DbError::Overloaded,
+                        _
+                    ))
+                );
+            };
+
+            // Non-idempotent requests are decided by the retry policy alone.
+            let err = session
+                .query_unpaged(statement.clone(), ())
+                .await
+                .unwrap_err();
+            assert_overloaded(err);
+            assert_eq!(count_attempts(&mut feedback_rx), 1);
+
+            // Idempotent requests follow the mapping, but only a limited number of times.
+            let mut idempotent = statement.clone();
+            idempotent.set_is_idempotent(true);
+            let err = session
+                .query_unpaged(idempotent.clone(), ())
+                .await
+                .unwrap_err();
+            assert_overloaded(err);
+            assert_eq!(count_attempts(&mut feedback_rx), 4);
+
+            // A retry policy set on the statement takes precedence over the mapping.
+            idempotent.set_retry_policy(Some(Arc::new(FallthroughRetryPolicy::new())));
+            let err = session.query_unpaged(idempotent, ()).await.unwrap_err();
+            assert_overloaded(err);
+            assert_eq!(count_attempts(&mut feedback_rx), 1);
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
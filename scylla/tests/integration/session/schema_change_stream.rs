@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use scylla::frame::response::event::{SchemaChangeEvent, SchemaChangeType};
+
+use crate::utils::{PerformDDL, create_new_session_builder, setup_tracing, unique_keyspace_name};
+
+#[tokio::test]
+async fn schema_change_stream_yields_table_creation() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    let mut stream = session.schema_change_stream();
+
+    session
+        .ddl(format!(
+            "CREATE KEYSPACE {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}"
+        ))
+        .await
+        .unwrap();
+    session
+        .ddl(format!("CREATE TABLE {ks}.t (a int PRIMARY KEY)"))
+        .await
+        .unwrap();
+
+    let table_created = SchemaChangeEvent::TableChange {
+        change_type: SchemaChangeType::Created,
+        keyspace_name: ks.clone(),
+        object_name: "t".to_owned(),
+    };
+    // Events for other keyspaces (e.g. from concurrently running tests) may arrive in between.
+    tokio::time::timeout(Duration::from_secs(30), async {
+        while let Some(event) = stream.next().await {
+            if event == table_created {
+                return;
+            }
+        }
+        panic!("schema change stream ended unexpectedly");
+    })
+    .await
+    .expect("table creation event was not received in time");
+    assert_eq!(stream.dropped_events(), 0);
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
@@ -0,0 +1,15 @@
+use crate::utils::{create_new_session_builder, setup_tracing};
+
+#[tokio::test]
+async fn server_options_list_supported_cql_versions() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+
+    let cluster_state = session.get_cluster_state();
+    for node in cluster_state.get_nodes_info() {
+        let server_options = node
+            .server_options()
+            .expect("connected node should have server options");
+        assert!(!server_options.cql_versions().is_empty());
+    }
+}
@@ -0,0 +1,34 @@
+use crate::utils::{create_new_session_builder, setup_tracing};
+
+#[tokio::test]
+async fn server_side_connections_match_pool_size() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+
+    // Default pool size is one connection per shard.
+    let expected_connections: usize = session
+        .get_cluster_state()
+        .get_nodes_info()
+        .iter()
+        .filter(|node| node.is_connected())
+        .map(|node| {
+            node.sharder()
+                .map_or(1, |sharder| sharder.nr_shards.get() as usize)
+        })
+        .sum();
+
+    let clients = session.server_side_connections().await.unwrap();
+
+    // Pools may be refilling in the background, so allow some slack.
+    let slack = expected_connections / 10 + 1;
+    assert!(
+        clients.len().abs_diff(expected_connections) <= slack,
+        "server sees {} connections, expected about {}",
+        clients.len(),
+        expected_connections
+    );
+
+    for client in &clients {
+        assert!(client.client_address().is_some());
+    }
+}
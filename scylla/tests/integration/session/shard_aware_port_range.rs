@@ -0,0 +1,55 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use scylla::routing::ShardAwarePortRange;
+
+use crate::utils::{create_new_session_builder, setup_tracing};
+
+#[tokio::test]
+async fn shard_aware_connections_use_ports_from_configured_range() {
+    setup_tracing();
+    const PORT_RANGE: std::ops::RangeInclusive<u16> = 51000..=52000;
+
+    let session = create_new_session_builder()
+        .shard_aware_local_port_range(ShardAwarePortRange::new(PORT_RANGE).unwrap())
+        .build()
+        .await
+        .unwrap();
+
+    // Connections to all shards but the first one are opened to the shard-aware port.
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while session.pool_fill_progress() < 1.0 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("Pools did not fill");
+
+    let cql_ports = session
+        .get_cluster_state()
+        .get_nodes_info()
+        .iter()
+        .map(|node| node.address.port())
+        .collect::<HashSet<_>>();
+
+    // Connections opened to the regular CQL port pick a random local port,
+    // so only those opened to the shard-aware port are checked.
+    let mut checked_connections = 0;
+    for stats in session.connection_stats() {
+        if cql_ports.contains(&stats.node_address.port()) {
+            continue;
+        }
+        checked_connections += 1;
+        assert!(
+            PORT_RANGE.contains(&stats.local_address.port()),
+            "connection to {} uses local port {}, outside of {:?}",
+            stats.node_address,
+            stats.local_address.port(),
+            PORT_RANGE
+        );
+    }
+    assert!(
+        checked_connections > 0,
+        "No connections to the shard-aware port were opened"
+    );
+}
@@ -267,3 +267,27 @@ async fn assert_in_tracing_table(session: &Session, tracing_uuid: Uuid) {
     // If all retries failed panic with an error
     panic!("No rows for tracing with this session id!");
 }
+
+#[cfg(all(scylla_unstable, feature = "unstable-frame-debug"))]
+#[tokio::test]
+async fn test_tracing_flag_in_response_frame_flags() {
+    use scylla_cql::frame::flag;
+
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+
+    let untraced_query = Statement::new("SELECT host_id FROM system.local");
+    let untraced_query_result = session.query_unpaged(untraced_query, &[]).await.unwrap();
+    assert_eq!(
+        untraced_query_result.response_frame_flags() & flag::TRACING,
+        0
+    );
+
+    let mut traced_query = Statement::new("SELECT host_id FROM system.local");
+    traced_query.set_tracing(true);
+    let traced_query_result = session.query_unpaged(traced_query, &[]).await.unwrap();
+    assert_ne!(
+        traced_query_result.response_frame_flags() & flag::TRACING,
+        0
+    );
+}
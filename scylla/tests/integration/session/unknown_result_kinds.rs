@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use assert_matches::assert_matches;
+use bytes::Bytes;
+use scylla::client::session::Session;
+use scylla::client::session_builder::SessionBuilder;
+use scylla::errors::{ExecutionError, RequestAttemptError};
+use scylla_proxy::{
+    Condition, ProxyError, Reaction, RequestFrame, RequestOpcode, RequestReaction, RequestRule,
+    ResponseFrame, ResponseOpcode, ShardAwareness, WorkerError,
+};
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+const UNKNOWN_RESULT_KIND: i32 = 0x2a;
+const UNKNOWN_RESULT_BODY: &[u8] = &[1, 2, 3, 4, 5];
+const STATEMENT: &str = "SELECT host_id FROM system.local WHERE key = 'unknown-result-kind'";
+
+/// Responds with a Result of a kind that no protocol version defines (yet).
+fn forge_unknown_result(frame: RequestFrame) -> ResponseFrame {
+    let mut body = UNKNOWN_RESULT_KIND.to_be_bytes().to_vec();
+    body.extend_from_slice(UNKNOWN_RESULT_BODY);
+    ResponseFrame {
+        params: frame.params.for_response(),
+        opcode: ResponseOpcode::Result,
+        body: Bytes::from(body),
+    }
+}
+
+#[tokio::test]
+async fn unknown_result_kinds_are_passed_through() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let translation_map = Arc::new(translation_map);
+            let passing_session: Session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(translation_map.clone())
+                .pass_through_unknown_result_kinds(true)
+                .build()
+                .await
+                .unwrap();
+            let failing_session: Session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(translation_map)
+                .build()
+                .await
+                .unwrap();
+
+            for node in running_proxy.running_nodes.iter_mut() {
+                node.change_request_rules(Some(vec![RequestRule(
+                    Condition::RequestOpcode(RequestOpcode::Query).and(
+                        Condition::BodyContainsCaseSensitive(Box::new(*b"unknown-result-kind")),
+                    ),
+                    RequestReaction::forge_response(Arc::new(forge_unknown_result)),
+                )]));
+            }
+
+            let result = passing_session.query_unpaged(STATEMENT, ()).await.unwrap();
+            let unknown = result.unknown_result().unwrap();
+            assert_eq!(unknown.kind, UNKNOWN_RESULT_KIND);
+            assert_eq!(unknown.body, UNKNOWN_RESULT_BODY);
+            assert!(!result.is_rows());
+
+            // Without the option, the request fails as before.
+            let err = failing_session
+                .query_unpaged(STATEMENT, ())
+                .await
+                .unwrap_err();
+            assert_matches!(
+                err,
+                ExecutionError::LastAttemptError(RequestAttemptError::CqlResultParseError(_))
+            );
+
+            running_proxy.turn_off_rules();
+
+            // Results of known kinds are not affected.
+            let result = passing_session
+                .query_unpaged("SELECT host_id FROM system.local", ())
+                .await
+                .unwrap();
+            assert!(result.unknown_result().is_none());
+            assert!(result.is_rows());
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
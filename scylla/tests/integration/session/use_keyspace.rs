@@ -1,10 +1,20 @@
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::Duration;
+
 use scylla::{
-    client::session::Session,
+    client::{PoolSize, session::Session, session_builder::SessionBuilder},
     errors::{BadKeyspaceName, UseKeyspaceError},
 };
+use scylla_proxy::{
+    Condition, ProxyError, Reaction as _, RequestFrame, RequestOpcode, RequestReaction,
+    RequestRule, ShardAwareness, WorkerError,
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver};
 
 use crate::utils::{
-    PerformDDL as _, create_new_session_builder, setup_tracing, unique_keyspace_name,
+    PerformDDL as _, create_new_session_builder, setup_tracing, test_with_3_node_cluster,
+    unique_keyspace_name,
 };
 
 #[tokio::test]
@@ -279,3 +289,93 @@ async fn test_get_keyspace_name() {
 
     session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
 }
+
+fn count_received(rx: &mut UnboundedReceiver<(RequestFrame, Option<u16>)>) -> usize {
+    std::iter::from_fn(|| rx.try_recv().ok()).count()
+}
+
+#[tokio::test]
+async fn test_keyspace_is_used_on_reconnect() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .pool_size(PoolSize::PerHost(NonZeroUsize::new(1).unwrap()))
+                .build()
+                .await
+                .unwrap();
+
+            let ks = unique_keyspace_name();
+            session
+                .ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 3}}"))
+                .await
+                .unwrap();
+            session
+                .ddl(format!("CREATE TABLE IF NOT EXISTS {ks}.tab (a int primary key)"))
+                .await
+                .unwrap();
+            session.use_keyspace(&ks, false).await.unwrap();
+
+            // Feeds back USE requests, and drops a connection when it receives
+            // a request with a marker.
+            let mut use_rxs = Vec::new();
+            for running_node in running_proxy.running_nodes.iter_mut() {
+                let (tx, rx) = mpsc::unbounded_channel();
+                running_node.change_request_rules(Some(vec![
+                    RequestRule(
+                        Condition::RequestOpcode(RequestOpcode::Query)
+                            .and(Condition::BodyContainsCaseSensitive(Box::new(*b"USE "))),
+                        RequestReaction::noop().with_feedback_when_performed(tx),
+                    ),
+                    RequestRule(
+                        Condition::RequestOpcode(RequestOpcode::Query)
+                            .and(Condition::BodyContainsCaseSensitive(Box::new(
+                                *b"use_keyspace_drop",
+                            )))
+                            .and(Condition::TrueForLimitedTimes(1)),
+                        RequestReaction::drop_connection(),
+                    ),
+                ]));
+                use_rxs.push(rx);
+            }
+
+            // Break the connection to the coordinator of the request, which is then reopened.
+            let _ = session
+                .query_unpaged(
+                    "SELECT host_id FROM system.local WHERE key = 'use_keyspace_drop'",
+                    (),
+                )
+                .await;
+
+            // The new connection uses the keyspace before it serves any request.
+            tokio::time::timeout(Duration::from_secs(10), async {
+                while use_rxs.iter_mut().map(count_received).sum::<usize>() == 0 {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                }
+            })
+            .await
+            .expect("The keyspace was not used on the new connection");
+
+            // Unqualified statements work on every node, including the reconnected one.
+            for _ in 0..10 {
+                session.query_unpaged("SELECT a FROM tab", ()).await.unwrap();
+            }
+
+            running_proxy.turn_off_rules();
+            session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
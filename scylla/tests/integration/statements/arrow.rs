@@ -0,0 +1,149 @@
+#![cfg(feature = "arrow-55")]
+
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, Date32Array, Int32Array, RecordBatch, StringArray, Time64NanosecondArray,
+};
+use assert_matches::assert_matches;
+use scylla::statement::arrow::{RecordBatchBindError, RecordBatchValues};
+use scylla::value::CqlDate;
+
+use crate::utils::{PerformDDL, create_new_session_builder, setup_tracing, unique_keyspace_name};
+
+#[tokio::test]
+async fn test_bind_record_batch() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b text, c date, primary key (a))"
+        ))
+        .await
+        .unwrap();
+
+    let batch = RecordBatch::try_from_iter([
+        ("a", Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef),
+        (
+            "b",
+            Arc::new(StringArray::from(vec![Some("one"), None, Some("three")])) as ArrayRef,
+        ),
+        ("c", Arc::new(Date32Array::from(vec![0, 1, -1])) as ArrayRef),
+    ])
+    .unwrap();
+
+    let insert = session
+        .prepare(format!("INSERT INTO {ks}.t (a, b, c) VALUES (?, ?, ?)"))
+        .await
+        .unwrap();
+    let values = RecordBatchValues::new(&insert, &batch).unwrap();
+    assert_eq!(values.num_rows(), 3);
+    for row in values.rows() {
+        session.execute_unpaged(&insert, row).await.unwrap();
+    }
+
+    let mut rows = session
+        .query_unpaged(format!("SELECT a, b, c FROM {ks}.t"), ())
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .rows::<(i32, Option<String>, CqlDate)>()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    rows.sort_by_key(|(a, _, _)| *a);
+    assert_eq!(
+        rows,
+        vec![
+            (1, Some("one".to_owned()), CqlDate(1 << 31)),
+            (2, None, CqlDate((1 << 31) + 1)),
+            (3, Some("three".to_owned()), CqlDate((1 << 31) - 1)),
+        ]
+    );
+
+    // Columns are bound positionally, so `b` can't be bound to the date column.
+    let swapped = session
+        .prepare(format!("INSERT INTO {ks}.t (a, c, b) VALUES (?, ?, ?)"))
+        .await
+        .unwrap();
+    assert_matches!(
+        RecordBatchValues::new(&swapped, &batch),
+        Err(RecordBatchBindError::IncompatibleType { index: 1, .. })
+    );
+
+    let too_few_markers = session
+        .prepare(format!("INSERT INTO {ks}.t (a, b) VALUES (?, ?)"))
+        .await
+        .unwrap();
+    assert_matches!(
+        RecordBatchValues::new(&too_few_markers, &batch),
+        Err(RecordBatchBindError::ColumnCountMismatch {
+            batch_columns: 3,
+            bind_markers: 2
+        })
+    );
+
+    // Strings bound to ascii columns must not contain non-ASCII characters.
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t_ascii (a int, b ascii, primary key (a))"
+        ))
+        .await
+        .unwrap();
+    let insert_ascii = session
+        .prepare(format!("INSERT INTO {ks}.t_ascii (a, b) VALUES (?, ?)"))
+        .await
+        .unwrap();
+    let ascii_batch = batch.project(&[0, 1]).unwrap();
+    let values = RecordBatchValues::new(&insert_ascii, &ascii_batch).unwrap();
+    for row in values.serialized_rows() {
+        row.unwrap();
+    }
+
+    let non_ascii_batch = RecordBatch::try_from_iter([
+        ("a", Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef),
+        (
+            "b",
+            Arc::new(StringArray::from(vec!["one", "zwei\u{df}"])) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    assert_matches!(
+        RecordBatchValues::new(&insert_ascii, &non_ascii_batch),
+        Err(RecordBatchBindError::NonAsciiValue { index: 1, .. })
+    );
+
+    // Times bound to time columns must lie within a single day.
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t_time (a int, b time, primary key (a))"
+        ))
+        .await
+        .unwrap();
+    let insert_time = session
+        .prepare(format!("INSERT INTO {ks}.t_time (a, b) VALUES (?, ?)"))
+        .await
+        .unwrap();
+    let time_batch = RecordBatch::try_from_iter([
+        ("a", Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef),
+        (
+            "b",
+            Arc::new(Time64NanosecondArray::from(vec![0, 86_400_000_000_000])) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    assert_matches!(
+        RecordBatchValues::new(&insert_time, &time_batch),
+        Err(RecordBatchBindError::TimeOutOfRange {
+            index: 1,
+            nanoseconds: 86_400_000_000_000,
+            ..
+        })
+    );
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use assert_matches::assert_matches;
+use scylla::client::session::Session;
+use scylla::client::session_builder::SessionBuilder;
+use scylla::errors::ExecutionError;
+use scylla::statement::batch::{Batch, BatchType};
+use scylla_proxy::{
+    Condition, ProxyError, Reaction as _, RequestOpcode, RequestReaction, RequestRule,
+    ShardAwareness, WorkerError,
+};
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+#[tokio::test]
+async fn test_cancelling_slow_batch() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session: Session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .build()
+                .await
+                .unwrap();
+
+            running_proxy.running_nodes.iter_mut().for_each(|node| {
+                node.change_request_rules(Some(vec![RequestRule(
+                    Condition::RequestOpcode(RequestOpcode::Batch)
+                        .and(Condition::not(Condition::ConnectionRegisteredAnyEvent)),
+                    RequestReaction::delay(Duration::from_secs(10)),
+                )]));
+            });
+
+            let batch = Batch::new(BatchType::Logged);
+            let (cancel_handle, execution) = session.batch_cancellable(&batch, &[][..] as &[()]);
+
+            let cancel_after_a_while = async {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                cancel_handle.cancel();
+            };
+            let (result, ()) = tokio::time::timeout(
+                Duration::from_secs(5),
+                futures::future::join(execution, cancel_after_a_while),
+            )
+            .await
+            .expect("cancelled batch did not complete before the delayed response");
+            assert_matches!(result, Err(ExecutionError::Cancelled));
+
+            // The connections remain usable after the batch was abandoned.
+            running_proxy.turn_off_rules();
+            session.batch(&batch, &[][..] as &[()]).await.unwrap();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -343,6 +343,72 @@ async fn consistency_is_correctly_set_in_cql_requests() {
     }
 }
 
+// Checks that both consistencies set with `set_consistency_pair` are sent in the EXECUTE request.
+#[tokio::test]
+async fn consistency_pair_is_set_in_execute_request() {
+    setup_tracing();
+    let features = fetch_negotiated_features(None).await;
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .build()
+                .await
+                .unwrap();
+
+            let mut prepared = session
+                .prepare("SELECT host_id FROM system.local WHERE key = ?")
+                .await
+                .unwrap();
+            prepared
+                .set_consistency_pair(Consistency::Quorum, Some(SerialConsistency::LocalSerial));
+            assert_eq!(prepared.get_consistency(), Some(Consistency::Quorum));
+            assert_eq!(
+                prepared.get_serial_consistency(),
+                Some(SerialConsistency::LocalSerial)
+            );
+
+            let (request_tx, mut request_rx) = mpsc::unbounded_channel();
+            for running_node in running_proxy.running_nodes.iter_mut() {
+                running_node.change_request_rules(Some(vec![RequestRule(
+                    Condition::not(Condition::ConnectionRegisteredAnyEvent)
+                        .and(Condition::RequestOpcode(RequestOpcode::Execute)),
+                    RequestReaction::noop().with_feedback_when_performed(request_tx.clone()),
+                )]));
+            }
+
+            session
+                .execute_unpaged(&prepared, ("local",))
+                .await
+                .unwrap();
+
+            let (request_frame, _shard) = request_rx.recv().await.unwrap();
+            let deserialized_request = request_frame.deserialize(&features).unwrap();
+            assert_eq!(
+                deserialized_request.get_consistency().unwrap(),
+                Consistency::Quorum
+            );
+            assert_eq!(
+                deserialized_request.get_serial_consistency().unwrap(),
+                Some(SerialConsistency::LocalSerial)
+            );
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct OwnedRoutingInfo {
     consistency: Consistency,
@@ -1,14 +1,20 @@
 use std::net::{IpAddr, SocketAddr};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use assert_matches::assert_matches;
 use futures::StreamExt;
+use scylla::client::execution_profile::ExecutionProfile;
 use scylla::client::pager::QueryPager;
 use scylla::client::session::Session;
-use scylla::errors::ExecutionError;
+use scylla::cluster::{ClusterState, NodeRef};
+use scylla::errors::{ExecutionError, NextPageError, PagerExecutionError, RequestError};
 
-use scylla::policies::load_balancing::{NodeIdentifier, SingleTargetLoadBalancingPolicy};
+use scylla::policies::load_balancing::{
+    DefaultPolicy, FallbackPlan, LoadBalancingPolicy, NodeIdentifier, RoutingInfo,
+    SingleTargetLoadBalancingPolicy,
+};
 use scylla::response::query_result::QueryResult;
 use scylla::response::{Coordinator, PagingState};
 use scylla::routing::Shard;
@@ -112,6 +118,367 @@ async fn test_enforce_non_existent_request_coordinator() {
     assert_matches!(result, Err(ExecutionError::EmptyPlan))
 }
 
+#[tokio::test]
+async fn test_excluded_nodes_are_not_request_coordinators() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+
+    let select = "SELECT host_id FROM system.local WHERE key='local'";
+    let prepared = session.prepare(select).await.unwrap();
+    let cluster_state = session.get_cluster_state();
+    let nodes = cluster_state.get_nodes_info();
+
+    for excluded in nodes {
+        let mut statement = Statement::new(select);
+        statement.exclude_nodes(&[excluded]);
+        let mut prepared = prepared.clone();
+        prepared.exclude_nodes(&[excluded]);
+
+        for _ in 0..10 {
+            let result = session.query_unpaged(statement.clone(), ()).await.unwrap();
+            assert_ne!(
+                result.request_coordinator().node().host_id,
+                excluded.host_id
+            );
+
+            let result = session.execute_unpaged(&prepared, ()).await.unwrap();
+            assert_ne!(
+                result.request_coordinator().node().host_id,
+                excluded.host_id
+            );
+
+            let mut rows_stream = session
+                .execute_iter(prepared.clone(), ())
+                .await
+                .unwrap()
+                .rows_stream::<(Uuid,)>()
+                .unwrap();
+            let (host_id,) = rows_stream.next().await.unwrap().unwrap();
+            assert_ne!(host_id, excluded.host_id);
+        }
+    }
+
+    // With every node excluded, the plan is empty.
+    let mut statement = Statement::new(select);
+    statement.exclude_nodes(&nodes.iter().collect::<Vec<_>>());
+    let result = session.query_unpaged(statement, ()).await;
+    assert_matches!(result, Err(ExecutionError::EmptyPlan))
+}
+
+// Counts the plans it is asked for and delegates to the wrapped policy,
+// or returns an empty plan if there is none.
+#[derive(Debug)]
+struct PlanCountingLBP {
+    wrapped: Option<Arc<dyn LoadBalancingPolicy>>,
+    plans: AtomicUsize,
+}
+
+impl PlanCountingLBP {
+    fn new(wrapped: Option<Arc<dyn LoadBalancingPolicy>>) -> Arc<Self> {
+        Arc::new(Self {
+            wrapped,
+            plans: AtomicUsize::new(0),
+        })
+    }
+}
+
+impl LoadBalancingPolicy for PlanCountingLBP {
+    fn pick<'a>(
+        &'a self,
+        query: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> Option<(NodeRef<'a>, Option<Shard>)> {
+        self.plans.fetch_add(1, Ordering::Relaxed);
+        self.wrapped.as_ref()?.pick(query, cluster)
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        query: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> FallbackPlan<'a> {
+        match &self.wrapped {
+            Some(wrapped) => wrapped.fallback(query, cluster),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    fn name(&self) -> String {
+        "PlanCountingLBP".to_owned()
+    }
+}
+
+#[tokio::test]
+async fn test_require_replica_coordinator() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session
+        .ddl(format!(
+            "CREATE KEYSPACE IF NOT EXISTS {ks} WITH replication = {{'class': 'NetworkTopologyStrategy', 'replication_factor': 1}}"
+        ))
+        .await
+        .unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int PRIMARY KEY)"
+        ))
+        .await
+        .unwrap();
+    session.refresh_metadata().await.unwrap();
+
+    let mut prepared = session
+        .prepare(format!("SELECT a FROM {ks}.t WHERE a = ?"))
+        .await
+        .unwrap();
+    prepared.set_require_replica_coordinator(true);
+
+    let cluster_state = session.get_cluster_state();
+    let replicas = cluster_state.get_endpoints(&ks, "t", &(1,)).unwrap();
+    let (replica, _) = &replicas[0];
+
+    // Routing is not token-aware, so the single target decides whether it's a replica.
+    for node in cluster_state.get_nodes_info() {
+        prepared.set_load_balancing_policy(Some(SingleTargetLoadBalancingPolicy::new(
+            NodeIdentifier::Node(Arc::clone(node)),
+            None,
+        )));
+        let result = session.execute_unpaged(&prepared, (1,)).await;
+        let pager_result = session.execute_iter(prepared.clone(), (1,)).await;
+        if node.host_id == replica.host_id {
+            assert_eq!(
+                result.unwrap().request_coordinator().node().host_id,
+                replica.host_id
+            );
+            pager_result.unwrap();
+        } else {
+            assert_matches!(
+                result,
+                Err(ExecutionError::CoordinatorNotReplica(addr)) if addr == node.address
+            );
+            assert_matches!(
+                pager_result,
+                Err(PagerExecutionError::NextPageError(NextPageError::RequestFailure(
+                    RequestError::CoordinatorNotReplica(addr)
+                ))) if addr == node.address
+            );
+        }
+    }
+
+    // Non-replicas in a plan which is not token-aware are skipped.
+    prepared.set_load_balancing_policy(None);
+    prepared.set_disable_token_aware(true);
+    for _ in 0..cluster_state.get_nodes_info().len() {
+        let result = session.execute_unpaged(&prepared, (1,)).await.unwrap();
+        assert_eq!(result.request_coordinator().node().host_id, replica.host_id);
+    }
+
+    // Without a known partition, no coordinator is a replica.
+    let mut statement = Statement::new(format!("SELECT a FROM {ks}.t WHERE a = 1"));
+    statement.set_require_replica_coordinator(true);
+    assert_matches!(
+        session.query_unpaged(statement, ()).await,
+        Err(ExecutionError::CoordinatorNotReplica(_))
+    );
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_disable_token_aware() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session
+        .ddl(format!(
+            "CREATE KEYSPACE IF NOT EXISTS {ks} WITH replication = {{'class': 'NetworkTopologyStrategy', 'replication_factor': 1}}"
+        ))
+        .await
+        .unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int PRIMARY KEY)"
+        ))
+        .await
+        .unwrap();
+    session.refresh_metadata().await.unwrap();
+
+    let mut prepared = session
+        .prepare(format!("SELECT a FROM {ks}.t WHERE a = ?"))
+        .await
+        .unwrap();
+
+    let cluster_state = session.get_cluster_state();
+    if cluster_state.get_nodes_info().len() < 2 {
+        // Every node is a replica in a single node cluster.
+        return;
+    }
+    let replicas = cluster_state.get_endpoints(&ks, "t", &(1,)).unwrap();
+    let (replica, _) = &replicas[0];
+
+    // The coordinators are spread over the cluster, instead of always being the replica.
+    // The chance that all requests are still coordinated by the replica is negligible.
+    prepared.set_disable_token_aware(true);
+    let mut coordinated_by_non_replica = false;
+    for _ in 0..30 {
+        let result = session.execute_unpaged(&prepared, (1,)).await.unwrap();
+        coordinated_by_non_replica |=
+            result.request_coordinator().node().host_id != replica.host_id;
+    }
+    assert!(coordinated_by_non_replica);
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_attempt_info_reports_shard_aware_routing() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    if session
+        .get_cluster_state()
+        .get_nodes_info()
+        .iter()
+        .any(|node| node.sharder().is_none())
+    {
+        // Shard-aware routing is only possible on a sharded (ScyllaDB) cluster.
+        return;
+    }
+    let ks = unique_keyspace_name();
+
+    session
+        .ddl(format!(
+            "CREATE KEYSPACE IF NOT EXISTS {ks} WITH replication = {{'class': 'NetworkTopologyStrategy', 'replication_factor': 1}}"
+        ))
+        .await
+        .unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int PRIMARY KEY)"
+        ))
+        .await
+        .unwrap();
+    session.refresh_metadata().await.unwrap();
+
+    // Make sure that a connection to every shard is open, so that the pools
+    // don't have to fall back to connections to other shards.
+    tokio::time::timeout(std::time::Duration::from_secs(30), async {
+        while session.pool_fill_progress() < 1.0 {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("Pools did not fill");
+
+    let prepared = session
+        .prepare(format!("SELECT a FROM {ks}.t WHERE a = ?"))
+        .await
+        .unwrap();
+    for a in 0..10 {
+        let result = session.execute_unpaged(&prepared, (a,)).await.unwrap();
+        assert!(result.attempt_info().shard_aware_routing_used);
+    }
+
+    // Without a token, the shard is chosen at random.
+    let result = session
+        .query_unpaged(format!("SELECT a FROM {ks}.t"), ())
+        .await
+        .unwrap();
+    assert!(!result.attempt_info().shard_aware_routing_used);
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_preferred_replica_index_is_coordinator() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session
+        .ddl(format!(
+            "CREATE KEYSPACE IF NOT EXISTS {ks} WITH replication = {{'class': 'NetworkTopologyStrategy', 'replication_factor': 3}}"
+        ))
+        .await
+        .unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int PRIMARY KEY)"
+        ))
+        .await
+        .unwrap();
+    session.refresh_metadata().await.unwrap();
+
+    let mut prepared = session
+        .prepare(format!("SELECT a FROM {ks}.t WHERE a = ?"))
+        .await
+        .unwrap();
+
+    let cluster_state = session.get_cluster_state();
+    let replicas = cluster_state.get_endpoints(&ks, "t", &(1,)).unwrap();
+    assert!(!replicas.is_empty());
+
+    for (index, (replica, _)) in replicas.iter().enumerate() {
+        prepared.set_preferred_replica_index(Some(index));
+        let dry_run = session.dry_run(&prepared, (1,)).unwrap();
+        assert_eq!(dry_run.coordinator.unwrap().host_id, replica.host_id);
+        for _ in 0..10 {
+            let result = session.execute_unpaged(&prepared, (1,)).await.unwrap();
+            assert_eq!(result.request_coordinator().node().host_id, replica.host_id);
+        }
+    }
+
+    // An index out of the range of replicas leaves the plan as it was.
+    prepared.set_preferred_replica_index(Some(replicas.len()));
+    session.execute_unpaged(&prepared, (1,)).await.unwrap();
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
+/// Checks that a load balancing policy set on a statement takes precedence
+/// over the one from the execution profile, and only for that statement.
+#[tokio::test]
+async fn test_statement_load_balancing_policy_overrides_profile() {
+    setup_tracing();
+
+    let profile_policy = PlanCountingLBP::new(None);
+    let handle = ExecutionProfile::builder()
+        .load_balancing_policy(profile_policy.clone())
+        .build()
+        .into_handle();
+    let session = create_new_session_builder()
+        .default_execution_profile_handle(handle)
+        .build()
+        .await
+        .unwrap();
+
+    let prepared = session
+        .prepare("SELECT host_id FROM system.local WHERE key='local'")
+        .await
+        .unwrap();
+
+    // The session itself may have issued requests with the profile's policy, e.g. during preparation.
+    let profile_plans = profile_policy.plans.load(Ordering::Relaxed);
+
+    let override_policy = PlanCountingLBP::new(Some(DefaultPolicy::builder().build()));
+    let mut overridden = prepared.clone();
+    overridden.set_load_balancing_policy(Some(override_policy.clone()));
+
+    session.execute_unpaged(&overridden, ()).await.unwrap();
+    assert_eq!(override_policy.plans.load(Ordering::Relaxed), 1);
+    assert_eq!(profile_policy.plans.load(Ordering::Relaxed), profile_plans);
+
+    // The original statement still uses the profile's policy, whose plan is empty.
+    let result = session.execute_unpaged(&prepared, ()).await;
+    assert_matches!(result, Err(ExecutionError::EmptyPlan));
+    assert_eq!(override_policy.plans.load(Ordering::Relaxed), 1);
+    assert_eq!(
+        profile_policy.plans.load(Ordering::Relaxed),
+        profile_plans + 1
+    );
+}
+
 /// Checks that if a node is enforced as the coordinator of a request, the [Coordinator] struct
 /// exposed on the request result (`QueryResult` and `QueryPager`) contains that `Node`.
 #[tokio::test]
@@ -245,3 +612,80 @@ async fn test_exposed_request_coordinator() {
 
     session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
 }
+
+#[tokio::test]
+async fn test_attempt_info_reports_coordinator_host_id() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+
+    for _ in 0..10 {
+        let result = session
+            .query_unpaged("SELECT host_id FROM system.local WHERE key='local'", ())
+            .await
+            .unwrap();
+        let coordinator_host_id = result.attempt_info().coordinator_host_id.unwrap();
+        assert_eq!(
+            coordinator_host_id,
+            result.request_coordinator().node().host_id
+        );
+
+        // The host id is the one the coordinator reports about itself.
+        let (local_host_id,) = result
+            .into_rows_result()
+            .unwrap()
+            .single_row::<(Uuid,)>()
+            .unwrap();
+        assert_eq!(coordinator_host_id, local_host_id);
+
+        // And it is known to the cluster metadata.
+        assert!(
+            session
+                .get_cluster_state()
+                .get_nodes_info()
+                .iter()
+                .any(|node| node.host_id == coordinator_host_id)
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_attempt_info_reports_local_coordinator() {
+    setup_tracing();
+    const QUERY: &str = "SELECT host_id FROM system.local WHERE key='local'";
+
+    async fn coordinator_was_local(session: &Session) -> bool {
+        session
+            .query_unpaged(QUERY, ())
+            .await
+            .unwrap()
+            .attempt_info()
+            .coordinator_was_local
+    }
+
+    // Without a preferred datacenter, every coordinator is local.
+    let session = create_new_session_builder().build().await.unwrap();
+    assert!(coordinator_was_local(&session).await);
+
+    let local_dc = session.get_cluster_state().get_nodes_info()[0]
+        .datacenter
+        .clone()
+        .unwrap();
+    let local_session = create_new_session_builder()
+        .prefer_datacenter(local_dc)
+        .build()
+        .await
+        .unwrap();
+    assert!(coordinator_was_local(&local_session).await);
+
+    // No node is in the preferred datacenter, so requests fail over to remote ones.
+    let failover_profile = ExecutionProfile::builder()
+        .load_balancing_policy(DefaultPolicy::builder().permit_dc_failover(true).build())
+        .build();
+    let remote_session = create_new_session_builder()
+        .prefer_datacenter("nonexistent_dc".to_owned())
+        .default_execution_profile_handle(failover_profile.into_handle())
+        .build()
+        .await
+        .unwrap();
+    assert!(!coordinator_was_local(&remote_session).await);
+}
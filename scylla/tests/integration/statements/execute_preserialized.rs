@@ -0,0 +1,57 @@
+#![cfg(all(scylla_unstable, feature = "unstable-csharp-rs"))]
+
+use crate::utils::{PerformDDL, create_new_session_builder, setup_tracing, unique_keyspace_name};
+use scylla::serialize::row::RowSerializationContext;
+use scylla_cql::serialize::row::SerializedValues;
+
+#[tokio::test]
+async fn test_execute_preserialized_with_borrowed_values() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session
+        .ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session.use_keyspace(ks.as_str(), true).await.unwrap();
+    session
+        .ddl("CREATE TABLE IF NOT EXISTS t (a int, b text, primary key (a))")
+        .await
+        .unwrap();
+
+    let insert = session
+        .prepare("INSERT INTO t (a, b) VALUES (?, ?)")
+        .await
+        .unwrap();
+    let col_specs = insert.get_variable_col_specs();
+    let ctx = RowSerializationContext::from_specs(col_specs.as_slice());
+    let values = SerializedValues::from_serializable(&ctx, &(1_i32, "one")).unwrap();
+
+    // The values are only borrowed, so they can be used for more than one request.
+    session
+        .execute_preserialized(&insert, &values)
+        .await
+        .unwrap();
+    session
+        .execute_preserialized(&insert, &values)
+        .await
+        .unwrap();
+
+    let select = session
+        .prepare("SELECT a, b FROM t WHERE a = ?")
+        .await
+        .unwrap();
+    let col_specs = select.get_variable_col_specs();
+    let ctx = RowSerializationContext::from_specs(col_specs.as_slice());
+    let key = SerializedValues::from_serializable(&ctx, &(1_i32,)).unwrap();
+    let row = session
+        .execute_preserialized(&select, &key)
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .single_row::<(i32, String)>()
+        .unwrap();
+    assert_eq!(row, (1, "one".to_owned()));
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
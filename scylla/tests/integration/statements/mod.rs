@@ -1,11 +1,20 @@
+mod arrow;
 mod batch;
+mod cancellation;
 mod consistency;
 mod coordinator;
+mod execute_preserialized;
 mod execution_profiles;
+mod multiget;
 mod named_bind_markers;
+mod paging_state_expiry;
 mod prepared;
+mod raw_response;
+mod read_your_writes;
 mod request_timeout;
 mod result_metadata_extension;
+mod serial_consistency_fallback;
 mod timestamps;
 mod transparent_reprepare;
+mod unpaged_row_limit;
 mod unprepared;
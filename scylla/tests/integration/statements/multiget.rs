@@ -0,0 +1,48 @@
+use crate::utils::{PerformDDL, create_new_session_builder, setup_tracing, unique_keyspace_name};
+use futures::TryStreamExt;
+use std::num::NonZeroUsize;
+
+#[tokio::test]
+async fn test_multiget_fetches_all_keys() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b int, primary key (a))"
+        ))
+        .await
+        .unwrap();
+
+    let insert = session
+        .prepare(format!("INSERT INTO {ks}.t (a, b) VALUES (?, ?)"))
+        .await
+        .unwrap();
+    for a in 0..200_i32 {
+        session.execute_unpaged(&insert, (a, a * 2)).await.unwrap();
+    }
+
+    let select = session
+        .prepare(format!("SELECT a, b FROM {ks}.t WHERE a = ?"))
+        .await
+        .unwrap();
+
+    // Keys without a row don't yield anything.
+    let mut rows: Vec<(i32, i32)> = session
+        .multiget::<_, (i32, i32)>(
+            &select,
+            (0..250_i32).map(|a| (a,)),
+            NonZeroUsize::new(16).unwrap(),
+        )
+        .try_collect()
+        .await
+        .unwrap();
+    rows.sort();
+
+    let expected = (0..200_i32).map(|a| (a, a * 2)).collect::<Vec<_>>();
+    assert_eq!(rows, expected);
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
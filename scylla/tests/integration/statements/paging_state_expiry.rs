@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use assert_matches::assert_matches;
+use scylla::client::session::Session;
+use scylla::client::session_builder::SessionBuilder;
+use scylla::errors::{DbError, ExecutionError, RequestAttemptError};
+use scylla::response::{PagingState, PagingStateResponse};
+use scylla::statement::Statement;
+use scylla_proxy::{
+    Condition, ProxyError, RequestOpcode, RequestReaction, RequestRule, ShardAwareness, WorkerError,
+};
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+const EXPIRED_PAGING_STATE_MESSAGE: &str = "Invalid value for the paging state";
+
+fn next_paging_state(paging_state_response: PagingStateResponse) -> PagingState {
+    match paging_state_response {
+        PagingStateResponse::HasMorePages { state } => state,
+        PagingStateResponse::NoMorePages => panic!("Expected more pages"),
+    }
+}
+
+#[tokio::test]
+async fn resuming_from_expired_paging_state_returns_distinct_error() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session: Session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .build()
+                .await
+                .unwrap();
+
+            let mut statement = Statement::new("SELECT keyspace_name FROM system_schema.keyspaces");
+            statement.set_page_size(1);
+            let prepared = session.prepare(statement.clone()).await.unwrap();
+
+            let (_, paging_state_response) = session
+                .query_single_page(statement.clone(), (), PagingState::start())
+                .await
+                .unwrap();
+            let query_paging_state = next_paging_state(paging_state_response);
+            let (_, paging_state_response) = session
+                .execute_single_page(&prepared, (), PagingState::start())
+                .await
+                .unwrap();
+            let execute_paging_state = next_paging_state(paging_state_response);
+
+            // The server rejects the paging state of the next pages.
+            let expired_paging_state_rule = RequestRule(
+                Condition::not(Condition::ConnectionRegisteredAnyEvent).and(
+                    Condition::RequestOpcode(RequestOpcode::Query)
+                        .or(Condition::RequestOpcode(RequestOpcode::Execute)),
+                ),
+                RequestReaction::forge_with_error_and_message(
+                    DbError::ProtocolError,
+                    Some(EXPIRED_PAGING_STATE_MESSAGE.to_owned()),
+                ),
+            );
+            running_proxy
+                .running_nodes
+                .iter_mut()
+                .for_each(|n| n.change_request_rules(Some(vec![expired_paging_state_rule.clone()])));
+
+            let err = session
+                .query_single_page(statement.clone(), (), query_paging_state)
+                .await
+                .unwrap_err();
+            assert_matches!(err, ExecutionError::PagingStateExpired(reason) if reason == EXPIRED_PAGING_STATE_MESSAGE);
+
+            let err = session
+                .execute_single_page(&prepared, (), execute_paging_state)
+                .await
+                .unwrap_err();
+            assert_matches!(err, ExecutionError::PagingStateExpired(reason) if reason == EXPIRED_PAGING_STATE_MESSAGE);
+
+            // A request which is not resumed from a paging state returns the error as is.
+            let err = session
+                .query_single_page(statement, (), PagingState::start())
+                .await
+                .unwrap_err();
+            assert_matches!(
+                err,
+                ExecutionError::LastAttemptError(RequestAttemptError::DbError(
+                    DbError::ProtocolError,
+                    _
+                ))
+            );
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -1,7 +1,8 @@
 use assert_matches::assert_matches;
+use futures::TryStreamExt as _;
 use itertools::Itertools;
-use scylla::client::session::Session;
-use scylla::cluster::metadata::{ColumnType, NativeType};
+use scylla::client::session::{AutoExecutionResult, Session};
+use scylla::cluster::metadata::{CollectionType, ColumnType, NativeType};
 use scylla::errors::{DbError, PrepareError, RequestAttemptError};
 use scylla::frame::response::result::{ColumnSpec, TableSpec};
 use scylla::policies::load_balancing::{NodeIdentifier, SingleTargetLoadBalancingPolicy};
@@ -11,6 +12,7 @@ use scylla::routing::partitioner::PartitionerName;
 use scylla::serialize::row::SerializeRow;
 use scylla::statement::Statement;
 use scylla::statement::prepared::PreparedStatement;
+use scylla::value::Row;
 use scylla_cql::frame::types;
 use scylla_proxy::{
     Condition, ProxyError, Reaction, RequestFrame, RequestOpcode, RequestReaction, RequestRule,
@@ -18,7 +20,9 @@ use scylla_proxy::{
     TargetShard, WorkerError,
 };
 use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
 use tokio::sync::mpsc;
 use tracing::{debug, info};
 use uuid::Uuid;
@@ -216,6 +220,164 @@ async fn test_prepared_config() {
     assert_eq!(prepared_statement.get_page_size(), 42);
 }
 
+#[tokio::test]
+async fn test_execute_single_page_rows() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b int, primary key (a, b))"
+        ))
+        .await
+        .unwrap();
+    for b in 0..3 {
+        session
+            .query_unpaged(format!("INSERT INTO {ks}.t (a, b) VALUES (0, ?)"), (b,))
+            .await
+            .unwrap();
+    }
+
+    let query = Statement::new(format!("SELECT b FROM {ks}.t WHERE a = 0")).with_page_size(2);
+    let prepared = session.prepare(query).await.unwrap();
+    let column_values = |rows: &[Row]| {
+        rows.iter()
+            .map(|row| row.columns[0].as_ref().unwrap().as_int().unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    let (first_page, paging_state) = session
+        .execute_single_page_rows(&prepared, &[], PagingState::start())
+        .await
+        .unwrap();
+    assert_eq!(column_values(&first_page), [0, 1]);
+
+    let (second_page, paging_state) = session
+        .execute_single_page_rows(&prepared, &[], paging_state.unwrap())
+        .await
+        .unwrap();
+    assert_eq!(column_values(&second_page), [2]);
+    assert!(paging_state.is_none());
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_execute_auto() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b int, primary key (a, b))"
+        ))
+        .await
+        .unwrap();
+    for b in 0..5 {
+        session
+            .query_unpaged(format!("INSERT INTO {ks}.t (a, b) VALUES (0, ?)"), (b,))
+            .await
+            .unwrap();
+    }
+
+    let query =
+        Statement::new(format!("SELECT b FROM {ks}.t WHERE a = 0 AND b < ?")).with_page_size(3);
+    let prepared = session.prepare(query).await.unwrap();
+
+    // A small result fits in the first page.
+    match session.execute_auto(&prepared, (2,)).await.unwrap() {
+        AutoExecutionResult::Unpaged(result) => {
+            let rows = result
+                .into_rows_result()
+                .unwrap()
+                .rows::<(i32,)>()
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(rows, [(0,), (1,)]);
+        }
+        AutoExecutionResult::Paged(_) => panic!("Expected an unpaged result"),
+    }
+
+    // A large result is fetched whole by the pager, which continues after the first page
+    // instead of fetching it again.
+    let mut prepared = prepared;
+    let attempts = Arc::new(AtomicU32::new(0));
+    prepared.set_on_attempt({
+        let attempts = Arc::clone(&attempts);
+        move |_, _| {
+            attempts.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+    match session.execute_auto(&prepared, (5,)).await.unwrap() {
+        AutoExecutionResult::Unpaged(_) => panic!("Expected a pager"),
+        AutoExecutionResult::Paged(pager) => {
+            let rows = pager
+                .rows_stream::<(i32,)>()
+                .unwrap()
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap();
+            assert_eq!(rows, [(0,), (1,), (2,), (3,), (4,)]);
+        }
+    }
+    assert_eq!(attempts.load(Ordering::Relaxed), 2);
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_recent_executions() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int primary key)"
+        ))
+        .await
+        .unwrap();
+
+    let mut prepared = session
+        .prepare(format!("INSERT INTO {ks}.t (a) VALUES (?)"))
+        .await
+        .unwrap();
+    assert_eq!(prepared.get_execution_history_capacity(), None);
+    session.execute_unpaged(&prepared, (0,)).await.unwrap();
+    assert!(prepared.recent_executions().is_empty());
+
+    prepared.set_execution_history_capacity(Some(NonZeroUsize::new(2).unwrap()));
+    for a in 1..=3 {
+        session.execute_unpaged(&prepared, (a,)).await.unwrap();
+    }
+    let executions = prepared.recent_executions();
+    assert_eq!(executions.len(), 2);
+    assert!(executions[0].started_at <= executions[1].started_at);
+    for execution in &executions {
+        assert!(execution.result.is_ok());
+        assert!(execution.coordinator.is_some());
+    }
+
+    // Failed executions are recorded too, and clones share the log.
+    session.ddl(format!("DROP TABLE {ks}.t")).await.unwrap();
+    let cloned = prepared.clone();
+    session.execute_unpaged(&cloned, (4,)).await.unwrap_err();
+    let executions = prepared.recent_executions();
+    assert_eq!(executions.len(), 2);
+    assert!(executions[0].result.is_ok());
+    let last = &executions[1];
+    assert!(last.result.is_err());
+    assert!(last.coordinator.is_none());
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
 #[tokio::test]
 #[cfg_attr(cassandra_tests, ignore)]
 async fn test_prepared_partitioner() {
@@ -485,6 +647,118 @@ async fn test_prepared_statement_col_specs() {
         spec("c", ColumnType::Native(NativeType::SmallInt)),
     ];
     assert_eq!(result_set_col_specs, expected_result_set_col_specs);
+    assert_eq!(prepared.result_col_specs(), expected_result_set_col_specs);
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_prepared_statement_col_specs_with_complex_types() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+
+    let ks = unique_keyspace_name();
+    session
+        .ddl(format!(
+            "CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION =
+            {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}"
+        ))
+        .await
+        .unwrap();
+    session.use_keyspace(&ks, false).await.unwrap();
+
+    session
+        .ddl(
+            "CREATE TABLE t (k int PRIMARY KEY, l list<int>,
+            m map<text, frozen<set<bigint>>>, tup tuple<int, text>)",
+        )
+        .await
+        .unwrap();
+
+    let prepared = session
+        .prepare("INSERT INTO t (k, l, m, tup) VALUES (?, ?, ?, ?)")
+        .await
+        .unwrap();
+
+    // Types of bind markers never have the `frozen` flag set.
+    let variable_types = prepared
+        .get_variable_col_specs()
+        .iter()
+        .map(|spec| (spec.name(), spec.typ().clone()))
+        .collect::<Vec<_>>();
+    let expected_variable_types = [
+        ("k", ColumnType::Native(NativeType::Int)),
+        (
+            "l",
+            ColumnType::Collection {
+                frozen: false,
+                typ: CollectionType::List(Box::new(ColumnType::Native(NativeType::Int))),
+            },
+        ),
+        (
+            "m",
+            ColumnType::Collection {
+                frozen: false,
+                typ: CollectionType::Map(
+                    Box::new(ColumnType::Native(NativeType::Text)),
+                    Box::new(ColumnType::Collection {
+                        frozen: false,
+                        typ: CollectionType::Set(Box::new(ColumnType::Native(NativeType::BigInt))),
+                    }),
+                ),
+            },
+        ),
+        (
+            "tup",
+            ColumnType::Tuple(vec![
+                ColumnType::Native(NativeType::Int),
+                ColumnType::Native(NativeType::Text),
+            ]),
+        ),
+    ];
+    assert_eq!(variable_types, expected_variable_types);
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_prepared_statement_metadata_matches_across_alter() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+
+    let ks = unique_keyspace_name();
+    session
+        .ddl(format!(
+            "CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION =
+            {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}"
+        ))
+        .await
+        .unwrap();
+    session.use_keyspace(&ks, false).await.unwrap();
+
+    session
+        .ddl("CREATE TABLE t (a int PRIMARY KEY, b text)")
+        .await
+        .unwrap();
+
+    let select = "SELECT * FROM t WHERE a = ?";
+    let before = session.prepare(select).await.unwrap();
+    let reprepared = session.prepare(select).await.unwrap();
+    assert!(before.metadata_matches(&reprepared));
+
+    // A statement with different bind variables doesn't match.
+    let other = session
+        .prepare("SELECT * FROM t WHERE a = ? AND b = ? ALLOW FILTERING")
+        .await
+        .unwrap();
+    assert!(!before.metadata_matches(&other));
+
+    session.ddl("ALTER TABLE t ADD c int").await.unwrap();
+
+    let after = session.prepare(select).await.unwrap();
+    assert!(!before.metadata_matches(&after));
+    assert!(!after.metadata_matches(&before));
+    assert!(after.metadata_matches(&after.clone()));
 
     session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
 }
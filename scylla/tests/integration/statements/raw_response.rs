@@ -0,0 +1,65 @@
+#![cfg(all(scylla_unstable, feature = "unstable-csharp-rs"))]
+
+use crate::utils::{PerformDDL, create_new_session_builder, setup_tracing, unique_keyspace_name};
+use scylla::frame::Compression;
+use scylla::serialize::row::RowSerializationContext;
+use scylla_cql::serialize::row::SerializedValues;
+
+#[tokio::test]
+async fn test_execute_unpaged_raw_response_is_not_decompressed() {
+    setup_tracing();
+    let compressed_session = create_new_session_builder()
+        .compression(Some(Compression::Lz4))
+        .build()
+        .await
+        .unwrap();
+    let uncompressed_session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    compressed_session
+        .ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    compressed_session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b text, primary key (a))"
+        ))
+        .await
+        .unwrap();
+
+    // A long, repetitive value, so that the response is worth compressing.
+    let value = "raw response ".repeat(100);
+    compressed_session
+        .query_unpaged(
+            format!("INSERT INTO {ks}.t (a, b) VALUES (?, ?)"),
+            (1_i32, &value),
+        )
+        .await
+        .unwrap();
+
+    let select = format!("SELECT a, b FROM {ks}.t WHERE a = ?");
+    let mut bodies = Vec::new();
+    for session in [&compressed_session, &uncompressed_session] {
+        let prepared = session.prepare(select.as_str()).await.unwrap();
+        let col_specs = prepared.get_variable_col_specs();
+        let ctx = RowSerializationContext::from_specs(col_specs.as_slice());
+        let key = SerializedValues::from_serializable(&ctx, &(1_i32,)).unwrap();
+        bodies.push(
+            session
+                .execute_unpaged_raw_response(&prepared, &key)
+                .await
+                .unwrap(),
+        );
+    }
+    let [compressed, uncompressed] = bodies.try_into().unwrap();
+
+    assert_eq!(compressed.compression(), Some(Compression::Lz4));
+    assert_eq!(uncompressed.compression(), None);
+    assert_ne!(compressed.body(), uncompressed.body());
+
+    let decompressed = scylla_cql::frame::decompress(compressed.body(), Compression::Lz4).unwrap();
+    assert_eq!(decompressed, uncompressed.body().as_ref());
+
+    compressed_session
+        .ddl(format!("DROP KEYSPACE {ks}"))
+        .await
+        .unwrap();
+}
@@ -0,0 +1,76 @@
+use scylla::statement::Consistency;
+
+use crate::utils::{
+    PerformDDL as _, create_new_session_builder, setup_tracing, unique_keyspace_name,
+};
+
+#[tokio::test]
+async fn test_read_your_writes() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int primary key, b text)"
+        ))
+        .await
+        .unwrap();
+
+    let mut insert = session
+        .prepare(format!("INSERT INTO {ks}.t (a, b) VALUES (?, ?)"))
+        .await
+        .unwrap();
+    insert.set_consistency(Consistency::One);
+    let select = session
+        .prepare(format!("SELECT b, WRITETIME(b) FROM {ks}.t WHERE a = ?"))
+        .await
+        .unwrap();
+
+    let (_, token) = session
+        .execute_write_tracked(&insert, (1, "written"))
+        .await
+        .unwrap();
+    assert_eq!(token.consistency(), Consistency::One);
+    assert_eq!(token.serial_consistency(), None);
+
+    let (value, writetime) = session
+        .execute_read_your_writes(&token, &select, (1,))
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .single_row::<(String, i64)>()
+        .unwrap();
+    assert_eq!(value, "written");
+    assert_eq!(Some(writetime), token.timestamp());
+
+    // Lightweight transactions are read back at serial consistency.
+    let conditional_insert = session
+        .prepare(format!(
+            "INSERT INTO {ks}.t (a, b) VALUES (?, ?) IF NOT EXISTS"
+        ))
+        .await
+        .unwrap();
+    let (_, token) = session
+        .execute_write_tracked(&conditional_insert, (2, "written conditionally"))
+        .await
+        .unwrap();
+    if conditional_insert.is_confirmed_lwt() {
+        assert_eq!(token.timestamp(), None);
+        assert!(token.serial_consistency().is_some());
+    }
+
+    let (value, _) = session
+        .execute_read_your_writes(&token, &select, (2,))
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .single_row::<(String, i64)>()
+        .unwrap();
+    assert_eq!(value, "written conditionally");
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use scylla::client::session_builder::SessionBuilder;
+use scylla::errors::DbError;
+use scylla::statement::{Consistency, SerialConsistency, Statement};
+use scylla_cql::frame::request::DeserializableRequest;
+use scylla_cql::frame::request::query::Query;
+use scylla_proxy::{
+    Condition, ProxyError, Reaction as _, RequestFrame, RequestOpcode, RequestReaction,
+    RequestRule, ShardAwareness, WorkerError,
+};
+use tokio::sync::mpsc;
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster};
+
+fn serial_consistency_of(frame: &RequestFrame) -> Option<SerialConsistency> {
+    Query::deserialize_with_features(&mut &*frame.body, &Default::default())
+        .unwrap()
+        .parameters
+        .serial_consistency
+}
+
+#[tokio::test]
+async fn serial_consistency_falls_back_on_local_serial_unavailability() {
+    setup_tracing();
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .build()
+                .await
+                .unwrap();
+
+            // The first attempt finds too few replicas alive in the local DC,
+            // later ones are passed through to the node.
+            let (unavailable_tx, mut unavailable_rx) = mpsc::unbounded_channel();
+            let (pass_tx, mut pass_rx) = mpsc::unbounded_channel();
+            let fallback_query = Condition::not(Condition::ConnectionRegisteredAnyEvent)
+                .and(Condition::RequestOpcode(RequestOpcode::Query))
+                .and(Condition::BodyContainsCaseSensitive(Box::new(
+                    *b"serial_fallback_test",
+                )));
+            for node in running_proxy.running_nodes.iter_mut() {
+                node.change_request_rules(Some(vec![
+                    RequestRule(
+                        fallback_query
+                            .clone()
+                            .and(Condition::TrueForLimitedTimes(1)),
+                        RequestReaction::forge_with_error(DbError::Unavailable {
+                            consistency: Consistency::LocalSerial,
+                            required: 2,
+                            alive: 1,
+                        })
+                        .with_feedback_when_performed(unavailable_tx.clone()),
+                    ),
+                    RequestRule(
+                        fallback_query.clone(),
+                        RequestReaction::noop().with_feedback_when_performed(pass_tx.clone()),
+                    ),
+                ]));
+            }
+
+            let mut statement = Statement::new(
+                "SELECT host_id FROM system.local WHERE key = 'serial_fallback_test'",
+            );
+            statement.set_serial_consistency(Some(SerialConsistency::LocalSerial));
+            statement.set_serial_consistency_fallback(vec![
+                SerialConsistency::LocalSerial,
+                SerialConsistency::Serial,
+            ]);
+            session.query_unpaged(statement, ()).await.unwrap();
+
+            let (unavailable_frame, _) = unavailable_rx.try_recv().unwrap();
+            assert_eq!(
+                serial_consistency_of(&unavailable_frame),
+                Some(SerialConsistency::LocalSerial)
+            );
+            let (retried_frame, _) = pass_rx.try_recv().unwrap();
+            assert_eq!(
+                serial_consistency_of(&retried_frame),
+                Some(SerialConsistency::Serial)
+            );
+            assert!(pass_rx.try_recv().is_err());
+
+            running_proxy.turn_off_rules();
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -10,6 +10,7 @@ use scylla::{
         Statement,
         batch::{Batch, BatchType},
     },
+    value::WriteTime,
 };
 
 use crate::utils::{
@@ -201,3 +202,44 @@ async fn test_timestamp_generator() {
 
     session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
 }
+
+#[tokio::test]
+async fn test_writetime() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t_writetime (a int primary key, b int)"
+        ))
+        .await
+        .unwrap();
+
+    // WriteTime can be bound as the timestamp of a write...
+    let write_time = WriteTime(1_700_000_000_123_456);
+    session
+        .query_unpaged(
+            format!("INSERT INTO {ks}.t_writetime (a, b) VALUES (1, 1) USING TIMESTAMP ?"),
+            (write_time,),
+        )
+        .await
+        .unwrap();
+
+    // ...and read back from WRITETIME(column), keeping microsecond precision.
+    let (read_write_time,) = session
+        .query_unpaged(
+            format!("SELECT WRITETIME(b) FROM {ks}.t_writetime WHERE a = 1"),
+            &[],
+        )
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .single_row::<(WriteTime,)>()
+        .unwrap();
+    assert_eq!(read_write_time, write_time);
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
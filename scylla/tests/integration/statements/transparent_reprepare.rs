@@ -94,6 +94,67 @@ async fn test_unprepared_reprepare_in_execute() {
     session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
 }
 
+// A test which checks that a statement prepared with Session::prepare_in_keyspace is reprepared
+// in its keyspace, even though the session uses another one.
+// Doing an ALTER TABLE statement clears prepared statement cache and all prepared statements need
+// to be prepared again.
+#[tokio::test]
+async fn test_unprepared_reprepare_in_keyspace() {
+    setup_tracing();
+
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+    let other_ks = unique_keyspace_name();
+
+    for keyspace in [&ks, &other_ks] {
+        session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {keyspace} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    }
+    session.use_keyspace(&other_ks, false).await.unwrap();
+
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.tab (a int, b int, primary key (a, b))"
+        ))
+        .await
+        .unwrap();
+
+    let insert_a_b = session
+        .prepare_in_keyspace("INSERT INTO tab (a, b) VALUES (?, ?)", ks.clone())
+        .await
+        .unwrap();
+
+    session.execute_unpaged(&insert_a_b, (1, 2)).await.unwrap();
+
+    // Altering the table makes the nodes forget the statement.
+    session
+        .ddl(format!("ALTER TABLE {ks}.tab ADD c int"))
+        .await
+        .unwrap();
+
+    // The statement is reprepared in its keyspace, so it keeps its id.
+    session.execute_unpaged(&insert_a_b, (3, 4)).await.unwrap();
+
+    let mut all_rows: Vec<(i32, i32)> = session
+        .query_unpaged(format!("SELECT a, b FROM {ks}.tab"), ())
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .rows::<(i32, i32)>()
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+    all_rows.sort_unstable();
+    assert_eq!(all_rows, vec![(1, 2), (3, 4)]);
+
+    for keyspace in [&ks, &other_ks] {
+        session
+            .ddl(format!("DROP KEYSPACE {keyspace}"))
+            .await
+            .unwrap();
+    }
+}
+
 // A tests which checks that Session::batch automatically reprepares PreparedStatemtns if they become unprepared.
 // Doing an ALTER TABLE statement clears prepared statement cache and all prepared statements need
 // to be prepared again.
@@ -0,0 +1,64 @@
+use std::num::NonZeroU32;
+
+use assert_matches::assert_matches;
+use scylla::errors::ExecutionError;
+use scylla::statement::Statement;
+
+use crate::utils::{PerformDDL, create_new_session_builder, setup_tracing, unique_keyspace_name};
+
+#[tokio::test]
+async fn test_unpaged_row_limit() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b int, primary key (a, b))"
+        ))
+        .await
+        .unwrap();
+
+    let insert = session
+        .prepare(format!("INSERT INTO {ks}.t (a, b) VALUES (?, ?)"))
+        .await
+        .unwrap();
+    for b in 0..1000_i32 {
+        session.execute_unpaged(&insert, (0_i32, b)).await.unwrap();
+    }
+
+    let max_rows = NonZeroU32::new(100).unwrap();
+    let mut scan = Statement::new(format!("SELECT a, b FROM {ks}.t"));
+    scan.set_max_unpaged_rows(Some(max_rows));
+
+    // A full scan exceeds the limit, both unprepared and prepared.
+    let err = session.query_unpaged(scan.clone(), ()).await.unwrap_err();
+    assert_matches!(err, ExecutionError::UnpagedRowLimitExceeded(limit) if limit == max_rows);
+
+    let prepared_scan = session.prepare(scan).await.unwrap();
+    assert_eq!(prepared_scan.get_max_unpaged_rows(), Some(max_rows));
+    let err = session
+        .execute_unpaged(&prepared_scan, ())
+        .await
+        .unwrap_err();
+    assert_matches!(err, ExecutionError::UnpagedRowLimitExceeded(limit) if limit == max_rows);
+
+    // Results within the limit are returned as usual, including a result of exactly `max_rows` rows.
+    let mut select = session
+        .prepare(format!("SELECT a, b FROM {ks}.t WHERE a = 0 AND b < ?"))
+        .await
+        .unwrap();
+    select.set_max_unpaged_rows(Some(max_rows));
+    for expected_rows in [50, 100] {
+        let rows = session
+            .execute_unpaged(&select, (expected_rows as i32,))
+            .await
+            .unwrap()
+            .into_rows_result()
+            .unwrap();
+        assert_eq!(rows.rows_num(), expected_rows);
+    }
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
@@ -2,6 +2,7 @@ use crate::utils::{
     PerformDDL as _, create_new_session_builder, setup_tracing, test_with_3_node_cluster,
     unique_keyspace_name,
 };
+use futures::TryStreamExt as _;
 use scylla::client::session::Session;
 use scylla::client::session_builder::SessionBuilder;
 use scylla::response::{PagingState, PagingStateResponse};
@@ -106,6 +107,7 @@ async fn test_unprepared_statement() {
         results_from_manual_paging.append(&mut page_results);
         match paging_state_response {
             PagingStateResponse::HasMorePages { state } => {
+                assert!(state.debug_describe().starts_with("PagingState"));
                 paging_state = state;
             }
             _ if watchdog > 30 => break,
@@ -220,3 +222,103 @@ async fn test_query_with_no_values() {
         Err(err) => panic!("{}", err),
     }
 }
+
+#[tokio::test]
+async fn test_result_truncated_by_page_size() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b int, PRIMARY KEY (a, b))"
+        ))
+        .await
+        .unwrap();
+    for b in 0..5 {
+        session
+            .query_unpaged(format!("INSERT INTO {ks}.t (a, b) VALUES (1, ?)"), (b,))
+            .await
+            .unwrap();
+    }
+
+    // The page size limits the number of rows returned by the server.
+    let limited = Statement::new(format!("SELECT b FROM {ks}.t WHERE a = 1")).with_page_size(2);
+    let (result, paging_state_response) = session
+        .query_single_page(limited.clone(), (), PagingState::start())
+        .await
+        .unwrap();
+    assert!(result.was_truncated());
+    assert_eq!(result.into_rows_result().unwrap().rows_num(), 2);
+
+    // The last page is not truncated.
+    let PagingStateResponse::HasMorePages { state } = paging_state_response else {
+        panic!("Expected more pages");
+    };
+    let (result, _) = session
+        .query_single_page(limited.with_page_size(10), (), state)
+        .await
+        .unwrap();
+    assert!(!result.was_truncated());
+    assert_eq!(result.into_rows_result().unwrap().rows_num(), 3);
+
+    // A LIMIT clause defines the result, so it does not truncate it.
+    let result = session
+        .query_unpaged(format!("SELECT b FROM {ks}.t WHERE a = 1 LIMIT 2"), ())
+        .await
+        .unwrap();
+    assert!(!result.was_truncated());
+    assert_eq!(result.into_rows_result().unwrap().rows_num(), 2);
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_rewrite_statement() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t_b (a int primary key, b int)"
+        ))
+        .await
+        .unwrap();
+    session
+        .query_unpaged(format!("INSERT INTO {ks}.t_b (a, b) VALUES (1, 2)"), &[])
+        .await
+        .unwrap();
+
+    // Table `t_a` doesn't exist, so only the rewritten statement can succeed.
+    let mut statement = Statement::new(format!("SELECT a, b FROM {ks}.t_a WHERE a = ?"));
+    statement.set_rewrite_statement(|contents| contents.replace(".t_a ", ".t_b "));
+
+    let row = session
+        .query_unpaged(statement.clone(), (1,))
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .single_row::<(i32, i32)>()
+        .unwrap();
+    assert_eq!(row, (1, 2));
+
+    let rows = session
+        .query_iter(statement.clone(), (1,))
+        .await
+        .unwrap()
+        .rows_stream::<(i32, i32)>()
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![(1, 2)]);
+
+    statement.remove_rewrite_statement();
+    session.query_unpaged(statement, (1,)).await.unwrap_err();
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
@@ -171,6 +171,41 @@ async fn test_cql_set() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn test_cql_set_btreeset_order_matches_server() {
+    setup_tracing();
+    let session: Session = connect().await;
+
+    let table_name: &str = "test_cql_set_order_tab";
+    create_table(&session, table_name, "set<text>").await;
+
+    session
+        .query_unpaged(
+            format!("INSERT INTO {table_name} (p, val) VALUES (0, {{'b', 'ab', 'B', 'ä', 'a'}})"),
+            (),
+        )
+        .await
+        .unwrap();
+
+    let (server_order, btreeset) = session
+        .query_unpaged(format!("SELECT val, val FROM {table_name} WHERE p = 0"), ())
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .single_row::<(Vec<String>, BTreeSet<String>)>()
+        .unwrap();
+
+    // Elements of a BTreeSet are iterated in the same order as the server sorts them.
+    assert_eq!(server_order, ["B", "a", "ab", "b", "ä"]);
+    assert!(btreeset.iter().eq(server_order.iter()));
+
+    session
+        .ddl(format!("DROP KEYSPACE {}", session.get_keyspace().unwrap()))
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn test_cql_map() {
     setup_tracing();
@@ -255,6 +290,31 @@ async fn test_cql_tuple() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn test_cql_list_of_tuples() {
+    setup_tracing();
+    let session: Session = connect().await;
+
+    let table_name: &str = "test_cql_list_of_tuples_tab";
+    create_table(&session, table_name, "list<frozen<tuple<int, text>>>").await;
+
+    let list: Vec<(i32, String)> = vec![
+        (1, "one".to_string()),
+        (2, String::new()),
+        (-3, "three".to_string()),
+    ];
+    insert_and_select(&session, table_name, &list, &list).await;
+
+    let list_with_nulls: Vec<(Option<i32>, Option<String>)> =
+        vec![(Some(1), None), (None, Some("two".to_string()))];
+    insert_and_select(&session, table_name, &list_with_nulls, &list_with_nulls).await;
+
+    session
+        .ddl(format!("DROP KEYSPACE {}", session.get_keyspace().unwrap()))
+        .await
+        .unwrap();
+}
+
 // Cassandra does not support altering column types starting with version 3.0.11 and 3.10.
 // See https://stackoverflow.com/a/76926622 for explanation.
 #[cfg_attr(cassandra_tests, ignore)]
@@ -2,7 +2,7 @@ use itertools::Itertools;
 use scylla::client::session::Session;
 use scylla::serialize::value::SerializeValue;
 use scylla::value::{Counter, CqlDate, CqlTime, CqlTimestamp, CqlTimeuuid, CqlValue, CqlVarint};
-use scylla::{DeserializeValue, SerializeValue};
+use scylla::{DeserializeRow, DeserializeValue, SerializeRow, SerializeValue};
 use std::cmp::PartialEq;
 use std::fmt::Debug;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
@@ -1896,6 +1896,91 @@ async fn test_udt_with_missing_field() {
     session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
 }
 
+#[tokio::test]
+async fn test_nested_udt_roundtrip() {
+    setup_tracing();
+
+    let session: Session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session
+        .ddl(format!(
+            "CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = \
+            {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}"
+        ))
+        .await
+        .unwrap();
+    session.use_keyspace(&ks, false).await.unwrap();
+
+    session
+        .ddl("CREATE TYPE IF NOT EXISTS address (street text, number int)")
+        .await
+        .unwrap();
+    session
+        .ddl("CREATE TYPE IF NOT EXISTS person (name text, address frozen<address>)")
+        .await
+        .unwrap();
+    session
+        .ddl(
+            "CREATE TABLE IF NOT EXISTS people \
+            (id int PRIMARY KEY, person frozen<person>, home frozen<address>)",
+        )
+        .await
+        .unwrap();
+
+    // A struct nested in a UDT struct is serialized as a nested UDT,
+    // as long as it derives the UDT traits itself.
+    #[derive(SerializeValue, DeserializeValue, Debug, PartialEq, Clone)]
+    struct Address {
+        street: String,
+        number: i32,
+    }
+
+    #[derive(SerializeValue, DeserializeValue, Debug, PartialEq)]
+    struct Person {
+        name: String,
+        address: Address,
+    }
+
+    #[derive(SerializeRow, DeserializeRow, Debug, PartialEq)]
+    struct PeopleRow {
+        id: i32,
+        person: Person,
+        home: Address,
+    }
+
+    let address = Address {
+        street: "Main Street".to_owned(),
+        number: 7,
+    };
+    let row = PeopleRow {
+        id: 0,
+        person: Person {
+            name: "John".to_owned(),
+            address: address.clone(),
+        },
+        home: address,
+    };
+
+    let insert = session
+        .prepare("INSERT INTO people (id, person, home) VALUES (:id, :person, :home)")
+        .await
+        .unwrap();
+    session.execute_unpaged(&insert, &row).await.unwrap();
+
+    let read_row = session
+        .query_unpaged("SELECT id, person, home FROM people WHERE id = 0", ())
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .single_row::<PeopleRow>()
+        .unwrap();
+    assert_eq!(read_row, row);
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
+
 #[tokio::test]
 async fn test_unusual_serializerow_impls() {
     setup_tracing();